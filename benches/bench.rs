@@ -12,7 +12,7 @@ use osu_file_parser::{
             Event,
         },
         hitobjects::HitObject,
-        OsuFile, Position, VersionedFromStr, VersionedToString,
+        OsuFile, ParseOptions, Parser, Position, VersionedFromStr, VersionedToString,
     },
 };
 
@@ -347,6 +347,29 @@ fn aspire_files_to_string(c: &mut Criterion) {
     });
 }
 
+fn parser_reuse(c: &mut Criterion) {
+    let files = [ONE_HOUR_OSU, CRAZY_OSU, ASPIRE1, ASPIRE2, ASPIRE3, ASPIRE4];
+    let options = ParseOptions::new().trim_fields(true);
+
+    let mut group = c.benchmark_group("parser_reuse");
+
+    group.bench_function("from_str_with_options", |b| {
+        b.iter(|| {
+            for file in files {
+                OsuFile::from_str_with_options(black_box(file), &options).unwrap();
+            }
+        })
+    });
+    group.bench_function("parser", |b| {
+        let mut parser = Parser::with_options(options.clone());
+        b.iter(|| {
+            for file in files {
+                parser.parse(black_box(file)).unwrap();
+            }
+        })
+    });
+}
+
 criterion_group!(
     benches,
     storyboard_cmds_parse,
@@ -358,5 +381,6 @@ criterion_group!(
     files_to_string,
     aspire_files_parse,
     aspire_files_to_string,
+    parser_reuse,
 );
 criterion_main!(benches);
@@ -0,0 +1,226 @@
+//! Derive macros for [osu-file-parser](https://crates.io/crates/osu-file-parser).
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Fields, GenericArgument, Lit, Meta,
+    NestedMeta, PathArguments, Type,
+};
+
+/// Derives `Section` for a struct of `key: value` fields, the same way `[General]` and `[Metadata]` are defined.
+/// - Every field has to be an `Option<T>` where `T` implements `VersionedFromStr` and `VersionedToString`, and `T::Err` implements `std::error::Error + Send + Sync`.
+/// - The key of a field is the name of `T`, which can be changed with `#[osu(key = "...")]`.
+/// - The section name is the name of the struct, which can be changed with `#[osu(name = "...")]`.
+/// - Fields are written as `key: value`, and the spacing after the colon can be changed with `#[osu(spacing = "")]`.
+#[proc_macro_derive(OsuSection, attributes(osu))]
+pub fn derive_osu_section(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match osu_section(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Returns the `key = "value"` string attributes in `#[osu(...)]`.
+fn osu_attrs(attrs: &[syn::Attribute]) -> syn::Result<Vec<(String, String, Span)>> {
+    let mut values = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("osu")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(syn::Error::new(meta.span(), "expected `#[osu(...)]`")),
+        };
+
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                    let name = match name_value.path.get_ident() {
+                        Some(ident) => ident.to_string(),
+                        None => {
+                            return Err(syn::Error::new(name_value.path.span(), "expected a name"))
+                        }
+                    };
+                    let value = match &name_value.lit {
+                        Lit::Str(s) => s.value(),
+                        lit => return Err(syn::Error::new(lit.span(), "expected a string")),
+                    };
+
+                    values.push((name, value, name_value.span()));
+                }
+                nested => {
+                    return Err(syn::Error::new(
+                        nested.span(),
+                        "expected `name = \"value\"`",
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(values)
+}
+
+/// Returns `T` from `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn osu_section(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+    let mut name = ident.to_string();
+    let mut spacing = " ".to_string();
+
+    for (attr, value, span) in osu_attrs(&input.attrs)? {
+        match attr.as_str() {
+            "name" => name = value,
+            "spacing" => spacing = value,
+            _ => return Err(syn::Error::new(span, "expected `name` or `spacing`")),
+        }
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "`OsuSection` requires named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                ident.span(),
+                "`OsuSection` can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut field_idents = Vec::new();
+    let mut field_types = Vec::new();
+    let mut keys = Vec::new();
+
+    for field in fields {
+        let field_type = option_inner(&field.ty).ok_or_else(|| {
+            syn::Error::new(field.ty.span(), "`OsuSection` fields must be `Option<T>`")
+        })?;
+
+        let mut key = match field_type {
+            Type::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+            _ => None,
+        };
+
+        for (attr, value, span) in osu_attrs(&field.attrs)? {
+            match attr.as_str() {
+                "key" => key = Some(value),
+                _ => return Err(syn::Error::new(span, "expected `key`")),
+            }
+        }
+
+        let key = key.ok_or_else(|| {
+            syn::Error::new(
+                field.span(),
+                "the key can't be found, set it with `#[osu(key = \"...\")]`",
+            )
+        })?;
+
+        field_idents.push(field.ident.clone().unwrap());
+        field_types.push(field_type.clone());
+        keys.push(key);
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::osu_file_parser::osu_file::Section for #ident #ty_generics #where_clause {
+            type ParseError = ::osu_file_parser::osu_file::ParseSectionError;
+
+            const NAME: &'static str = #name;
+
+            fn from_str(
+                s: &str,
+                version: ::osu_file_parser::osu_file::Version,
+            ) -> ::std::result::Result<
+                ::std::option::Option<Self>,
+                ::osu_file_parser::osu_file::Error<Self::ParseError>,
+            > {
+                let mut section = Self {
+                    #(#field_idents: ::std::option::Option::None,)*
+                };
+                let mut parsed_keys = ::std::vec::Vec::new();
+
+                for (key, value, line_index) in ::osu_file_parser::osu_file::key_value_fields(s)? {
+                    if parsed_keys.contains(&key) {
+                        return ::std::result::Result::Err(::osu_file_parser::osu_file::Error::new(
+                            ::osu_file_parser::osu_file::ParseSectionError::DuplicateField,
+                            line_index,
+                        ));
+                    }
+
+                    match key {
+                        #(
+                            #keys => {
+                                section.#field_idents =
+                                    <#field_types as ::osu_file_parser::osu_file::VersionedFromStr>::from_str(value, version)
+                                        .map_err(|err| ::osu_file_parser::osu_file::Error::new(
+                                            ::osu_file_parser::osu_file::ParseSectionError::InvalidValue {
+                                                key: key.to_string(),
+                                                source: ::std::boxed::Box::new(err),
+                                            },
+                                            line_index,
+                                        ))?;
+                            }
+                        )*
+                        _ => {
+                            return ::std::result::Result::Err(::osu_file_parser::osu_file::Error::new(
+                                ::osu_file_parser::osu_file::ParseSectionError::InvalidKey,
+                                line_index,
+                            ))
+                        }
+                    }
+
+                    parsed_keys.push(key);
+                }
+
+                ::std::result::Result::Ok(::std::option::Option::Some(section))
+            }
+
+            fn to_string(
+                &self,
+                version: ::osu_file_parser::osu_file::Version,
+            ) -> ::std::option::Option<::std::string::String> {
+                let mut lines = ::std::vec::Vec::new();
+
+                #(
+                    if let ::std::option::Option::Some(value) = &self.#field_idents {
+                        if let ::std::option::Option::Some(value) =
+                            ::osu_file_parser::osu_file::VersionedToString::to_string(value, version)
+                        {
+                            lines.push(::std::format!("{}:{}{}", #keys, #spacing, value));
+                        }
+                    }
+                )*
+
+                ::std::option::Option::Some(lines.join("\n"))
+            }
+        }
+    })
+}
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use osu_file_parser::osu_file::OsuFile;
+
+fuzz_target!(|s: &str| {
+    let _ = s.parse::<OsuFile>();
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use osu_file_parser::osu_file::{timingpoints::TimingPoint, VersionedFromStr};
+
+fuzz_target!(|input: (u8, &str)| {
+    let (version, s) = input;
+    let _ = TimingPoint::from_str(s, version);
+});
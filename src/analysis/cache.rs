@@ -0,0 +1,168 @@
+//! Module for keeping the results of the analysis between queries, for tools that query the same beatmap many times such as editors.
+
+use std::cell::OnceCell;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use super::{
+    objects::TimedObject,
+    stack_heights,
+    timeline::{sorted_timeline, timeline_order},
+    TimelineEntry,
+};
+use crate::osu_file::{timingpoints::DisplayBpm, Integer, OsuFile, SectionName};
+
+/// Sections the hitobject end times and combo are worked out from.
+const OBJECT_SECTIONS: &[SectionName] = &[
+    SectionName::Difficulty,
+    SectionName::TimingPoints,
+    SectionName::HitObjects,
+];
+
+/// Sections the stack heights are worked out from.
+const STACKING_SECTIONS: &[SectionName] = &[
+    SectionName::General,
+    SectionName::Difficulty,
+    SectionName::TimingPoints,
+    SectionName::HitObjects,
+];
+
+/// Sections the BPM is worked out from.
+const BPM_SECTIONS: &[SectionName] = &[SectionName::TimingPoints, SectionName::HitObjects];
+
+/// Sections the timeline is worked out from, along with the events of the .osb file.
+const TIMELINE_SECTIONS: &[SectionName] = &[
+    SectionName::Editor,
+    SectionName::Difficulty,
+    SectionName::Events,
+    SectionName::TimingPoints,
+    SectionName::HitObjects,
+];
+
+/// Hash of each section in the order of [`SectionName::ALL`], and of the .osb file.
+fn hashes(osu_file: &OsuFile) -> ([u64; SectionName::ALL.len()], u64) {
+    let mut hasher = DefaultHasher::new();
+    osu_file.osb.hash(&mut hasher);
+
+    (
+        SectionName::ALL.map(|section| osu_file.section_hash(section)),
+        hasher.finish(),
+    )
+}
+
+/// [`OsuFile`] that keeps the results of the analysis until a section they are worked out from changes.
+/// - Changes are made with [`edit`][Self::edit], which finds the changed sections by comparing their hashes, so changes made through the public fields are seen too.
+#[derive(Clone, Debug)]
+pub struct CachedAnalysis {
+    osu_file: OsuFile,
+    end_times: OnceCell<Vec<f64>>,
+    max_combo: OnceCell<u64>,
+    display_bpm: OnceCell<Option<DisplayBpm>>,
+    stack_heights: OnceCell<Vec<i32>>,
+    timeline_order: OnceCell<Vec<usize>>,
+}
+
+impl CachedAnalysis {
+    pub fn new(osu_file: OsuFile) -> Self {
+        Self {
+            osu_file,
+            end_times: OnceCell::new(),
+            max_combo: OnceCell::new(),
+            display_bpm: OnceCell::new(),
+            stack_heights: OnceCell::new(),
+            timeline_order: OnceCell::new(),
+        }
+    }
+
+    pub fn osu_file(&self) -> &OsuFile {
+        &self.osu_file
+    }
+
+    pub fn into_inner(self) -> OsuFile {
+        self.osu_file
+    }
+
+    /// Changes the file with `f`, dropping the results worked out from the sections that changed.
+    /// - Every section is hashed before and after `f`, which takes about as long as going through the file once.
+    pub fn edit<R>(&mut self, f: impl FnOnce(&mut OsuFile) -> R) -> R {
+        let (before, osb_before) = hashes(&self.osu_file);
+        let result = f(&mut self.osu_file);
+        let (after, osb_after) = hashes(&self.osu_file);
+
+        let changed = SectionName::ALL
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| before[*i] != after[*i])
+            .map(|(_, section)| section)
+            .collect::<Vec<_>>();
+        let changed_any = |sections: &[SectionName]| changed.iter().any(|s| sections.contains(s));
+
+        if changed_any(OBJECT_SECTIONS) {
+            self.end_times.take();
+            self.max_combo.take();
+        }
+        if changed_any(BPM_SECTIONS) {
+            self.display_bpm.take();
+        }
+        if changed_any(STACKING_SECTIONS) {
+            self.stack_heights.take();
+        }
+        if changed_any(TIMELINE_SECTIONS) || osb_before != osb_after {
+            self.timeline_order.take();
+        }
+
+        result
+    }
+
+    /// End time of each hitobject in milliseconds, which is the start time for hitcircles.
+    /// - Slider end times are worked out from the timing points and the slider velocity.
+    pub fn end_times(&self) -> &[f64] {
+        self.end_times.get_or_init(|| {
+            self.osu_file
+                .timed_objects()
+                .iter()
+                .map(TimedObject::end_time)
+                .collect()
+        })
+    }
+
+    /// Cached [`OsuFile::max_combo`].
+    pub fn max_combo(&self) -> u64 {
+        *self.max_combo.get_or_init(|| self.osu_file.max_combo())
+    }
+
+    /// Cached [`TimingPoints::display_bpm`][crate::osu_file::TimingPoints::display_bpm], up to the start of the last hitobject.
+    /// - Returns `None` if there's no `[TimingPoints]` section or no BPM in it.
+    pub fn display_bpm(&self) -> Option<&DisplayBpm> {
+        self.display_bpm
+            .get_or_init(|| {
+                let last_object_time = self
+                    .osu_file
+                    .hitobjects
+                    .as_ref()
+                    .and_then(|h| h.0.iter().map(|h| h.time).max())
+                    .map_or(0, |time| Integer::try_from(time).unwrap_or(Integer::MAX));
+
+                self.osu_file
+                    .timing_points
+                    .as_ref()
+                    .and_then(|t| t.display_bpm(last_object_time))
+            })
+            .as_ref()
+    }
+
+    /// Cached [`stack_heights`][super::stack_heights].
+    pub fn stack_heights(&self) -> &[i32] {
+        self.stack_heights
+            .get_or_init(|| stack_heights(&self.osu_file))
+    }
+
+    /// [`OsuFile::timeline`] using the cached [`end_times`][Self::end_times].
+    /// - The order of the items is cached, so they're only sorted again after the sections they're from change.
+    pub fn timeline(&self) -> impl Iterator<Item = TimelineEntry<'_>> {
+        let entries = self.osu_file.unsorted_timeline(self.end_times());
+        let order = self.timeline_order.get_or_init(|| timeline_order(&entries));
+
+        sorted_timeline(entries, order)
+    }
+}
@@ -0,0 +1,39 @@
+//! Module for the object density bar shown above the beatmap on the osu! website.
+
+use crate::osu_file::OsuFile;
+
+/// Splits the beatmap into `buckets` equal parts and counts the hitobjects starting in each of them.
+/// - The parts span from the start of the first hitobject to the end of the last one, with objects at the very end counted in the last part.
+/// - Counts are divided by the highest count, so the busiest part is `1.0` and the bar can be drawn with the values as heights.
+/// - All values are `0.0` if there are no hitobjects.
+pub fn density_graph(osu_file: &OsuFile, buckets: usize) -> Vec<f64> {
+    let objects = osu_file.timed_objects();
+    if buckets == 0 || objects.is_empty() {
+        return vec![0.0; buckets];
+    }
+
+    let start = objects
+        .iter()
+        .map(|obj| obj.obj.time as f64)
+        .fold(f64::INFINITY, f64::min);
+    let end = objects
+        .iter()
+        .map(|obj| obj.end_time())
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    let mut counts = vec![0u32; buckets];
+    let length = end - start;
+    for obj in &objects {
+        let position = if length > 0.0 {
+            (obj.obj.time as f64 - start) / length
+        } else {
+            0.0
+        };
+        let bucket = ((position * buckets as f64) as usize).min(buckets - 1);
+
+        counts[bucket] += 1;
+    }
+
+    let max = counts.iter().copied().max().unwrap_or_default().max(1) as f64;
+    counts.into_iter().map(|count| count as f64 / max).collect()
+}
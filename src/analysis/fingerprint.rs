@@ -0,0 +1,161 @@
+//! Module for fingerprinting beatmaps to detect duplicates.
+
+use std::collections::HashSet;
+
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::osu_file::{
+    hitobjects::{HitObject, HitObjectParams},
+    OsuFile,
+};
+
+/// Number of features in a single shingle, used for [`Fingerprint::similarity`].
+const SHINGLE_LEN: usize = 4;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+fn fnv1a(features: &[u16]) -> u64 {
+    features
+        .iter()
+        .flat_map(|f| f.to_le_bytes())
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+        })
+}
+
+/// A compact fingerprint of the rhythm and the movement of a beatmap.
+/// - Each hitobject becomes a single feature, made from the object type, the ratio between its interval and the previous interval, and the angle it turns from the previous movement.
+/// - Only relative values are used, so metadata edits, offset changes, rate changes and moving the whole map don't change the fingerprint.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Fingerprint(Vec<u16>);
+
+impl Fingerprint {
+    /// Creates a fingerprint from the hitobjects.
+    /// - The hitobjects are expected to be sorted by time.
+    pub fn new(hitobjects: &[HitObject]) -> Self {
+        let positions = hitobjects
+            .iter()
+            .map(|obj| {
+                (
+                    obj.position.x.to_f64().unwrap_or_default(),
+                    obj.position.y.to_f64().unwrap_or_default(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let features = hitobjects
+            .iter()
+            .enumerate()
+            .map(|(i, obj)| {
+                let kind = match obj.obj_params {
+                    HitObjectParams::HitCircle => 0,
+                    HitObjectParams::Slider(_) => 1,
+                    HitObjectParams::Spinner { .. } => 2,
+                    HitObjectParams::OsuManiaHold { .. } => 3,
+                };
+
+                let (rhythm, angle) = if i >= 2 {
+                    let prev_interval = hitobjects[i - 1]
+                        .time
+                        .saturating_sub(hitobjects[i - 2].time);
+                    let interval = obj.time.saturating_sub(hitobjects[i - 1].time);
+
+                    (
+                        rhythm_bucket(prev_interval, interval),
+                        angle_bucket(positions[i - 2], positions[i - 1], positions[i]),
+                    )
+                } else {
+                    // no previous movement to compare with
+                    (18, 7)
+                };
+
+                kind | (rhythm << 2) | (angle << 7)
+            })
+            .collect();
+
+        Self(features)
+    }
+
+    /// Returns the features, one per hitobject.
+    pub fn features(&self) -> &[u16] {
+        &self.0
+    }
+
+    /// Returns a hash of the whole fingerprint.
+    /// - The hash is stable between runs and crate versions, so it can be stored for lookups.
+    /// - Only exact duplicates share the same hash, use [`similarity`][Self::similarity] for partial matches.
+    pub fn hash_u64(&self) -> u64 {
+        fnv1a(&self.0)
+    }
+
+    fn shingles(&self) -> HashSet<u64> {
+        if self.0.len() < SHINGLE_LEN {
+            return self.0.chunks(SHINGLE_LEN).map(fnv1a).collect();
+        }
+
+        self.0.windows(SHINGLE_LEN).map(fnv1a).collect()
+    }
+
+    /// Returns how similar the two fingerprints are, from `0.0` to `1.0`.
+    /// - Uses the Jaccard index of the overlapping runs of features, so it still matches if parts of the map were removed, added or reordered.
+    /// - Two empty fingerprints are treated as the same.
+    pub fn similarity(&self, other: &Fingerprint) -> f32 {
+        let shingles = self.shingles();
+        let other_shingles = other.shingles();
+
+        let union = shingles.union(&other_shingles).count();
+
+        if union == 0 {
+            return 1.0;
+        }
+
+        shingles.intersection(&other_shingles).count() as f32 / union as f32
+    }
+}
+
+/// Quantizes the ratio between the intervals into quarters of an octave.
+/// - `0` ~ `16` for ratios from `1/4` to `4`, and `17` if either interval is `0`.
+fn rhythm_bucket(prev_interval: u32, interval: u32) -> u16 {
+    if prev_interval == 0 || interval == 0 {
+        return 17;
+    }
+
+    let ratio = (interval as f64 / prev_interval as f64).log2();
+
+    ((ratio * 4.0).round().clamp(-8.0, 8.0) + 8.0) as u16
+}
+
+/// Quantizes the turning angle between the two movements into 30 degree steps.
+/// - `0` ~ `5` for the angle, and `6` if the objects are stacked.
+fn angle_bucket(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> u16 {
+    let v1 = (b.0 - a.0, b.1 - a.1);
+    let v2 = (c.0 - b.0, c.1 - b.1);
+
+    let len1 = v1.0.hypot(v1.1);
+    let len2 = v2.0.hypot(v2.1);
+
+    if len1 < 1.0 || len2 < 1.0 {
+        return 6;
+    }
+
+    let cos = ((v1.0 * v2.0 + v1.1 * v2.1) / (len1 * len2)).clamp(-1.0, 1.0);
+
+    ((cos.acos().to_degrees() / 30.0) as u16).min(5)
+}
+
+impl OsuFile {
+    /// Creates a [`Fingerprint`] from the hitobjects.
+    pub fn fingerprint(&self) -> Fingerprint {
+        match &self.hitobjects {
+            Some(hitobjects) => Fingerprint::new(&hitobjects.0),
+            None => Fingerprint::default(),
+        }
+    }
+
+    /// Returns how similar the beatmaps are, from `0.0` to `1.0`.
+    /// - Shorthand for comparing the [`fingerprint`][Self::fingerprint] of both beatmaps.
+    pub fn similarity(&self, other: &OsuFile) -> f32 {
+        self.fingerprint().similarity(&other.fingerprint())
+    }
+}
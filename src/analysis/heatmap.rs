@@ -0,0 +1,105 @@
+//! Module for counting where on the playfield the hitobjects are, for thumbnails and dashboards.
+
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::osu_file::{hitobjects::HitObjectParams, OsuFile, Position};
+
+/// Width of the playfield in osu!pixels.
+const PLAYFIELD_WIDTH: f64 = 512.0;
+
+/// Height of the playfield in osu!pixels.
+const PLAYFIELD_HEIGHT: f64 = 384.0;
+
+/// Most cells across a [`Heatmap`], which keeps the grid of a huge resolution from using up the memory.
+pub const MAX_RESOLUTION: usize = 1024;
+
+/// Number of positions in each cell of the playfield, made by [`position_heatmap`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Heatmap {
+    /// Number of cells across.
+    pub width: usize,
+    /// Number of cells down.
+    pub height: usize,
+    /// Counts of the cells row by row, starting from the top left.
+    pub cells: Vec<u32>,
+}
+
+impl Heatmap {
+    fn new(width: usize) -> Self {
+        let width = width.clamp(1, MAX_RESOLUTION);
+        let height = ((width as f64 * PLAYFIELD_HEIGHT / PLAYFIELD_WIDTH).ceil() as usize).max(1);
+
+        Self {
+            width,
+            height,
+            cells: vec![0; width * height],
+        }
+    }
+
+    /// Size of a cell in osu!pixels, which is square.
+    pub fn cell_size(&self) -> f64 {
+        PLAYFIELD_WIDTH / self.width as f64
+    }
+
+    /// Count of the cell at the column `x` and row `y`.
+    pub fn get(&self, x: usize, y: usize) -> Option<u32> {
+        if x < self.width && y < self.height {
+            Some(self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Highest count of any cell.
+    pub fn max(&self) -> u32 {
+        self.cells.iter().copied().max().unwrap_or_default()
+    }
+
+    /// Adds a position, where positions outside of the playfield are counted in the closest cell.
+    fn add(&mut self, x: f64, y: f64) {
+        let cell_size = self.cell_size();
+        let column = ((x / cell_size).floor().max(0.0) as usize).min(self.width - 1);
+        let row = ((y / cell_size).floor().max(0.0) as usize).min(self.height - 1);
+
+        self.cells[row * self.width + column] += 1;
+    }
+}
+
+fn to_f64(position: &Position) -> (f64, f64) {
+    (
+        position.x.to_f64().unwrap_or_default(),
+        position.y.to_f64().unwrap_or_default(),
+    )
+}
+
+/// Counts the positions of the hitobjects in a grid `resolution` cells across the playfield, with square cells.
+/// - The `resolution` is clamped to `1 ~` [`MAX_RESOLUTION`].
+/// - Sliders count a point every cell along their path, from [`SlideParams::path_points`][crate::osu_file::hitobjects::SlideParams::path_points], or only their head if the path can't be worked out.
+/// - Spinners aren't counted, since they are always in the centre.
+pub fn position_heatmap(osu_file: &OsuFile, resolution: usize) -> Heatmap {
+    let mut heatmap = Heatmap::new(resolution);
+    let spacing = heatmap.cell_size();
+
+    for obj in osu_file.hitobjects.iter().flat_map(|h| &h.0) {
+        match &obj.obj_params {
+            HitObjectParams::Spinner { .. } => (),
+            HitObjectParams::Slider(params) => match params.path_points(obj.position, spacing) {
+                Ok(points) => {
+                    for point in points {
+                        heatmap.add(point.x, point.y);
+                    }
+                }
+                Err(_) => {
+                    let (x, y) = to_f64(&obj.position);
+                    heatmap.add(x, y);
+                }
+            },
+            _ => {
+                let (x, y) = to_f64(&obj.position);
+                heatmap.add(x, y);
+            }
+        }
+    }
+
+    heatmap
+}
@@ -0,0 +1,25 @@
+//! Module for analysing the contents of an .osu file.
+//! - Everything here is derived from the parsed data, without needing the audio or the skin.
+
+pub mod cache;
+pub mod density;
+pub mod fingerprint;
+pub mod heatmap;
+pub mod mods;
+pub mod score;
+pub mod stacking;
+pub mod timeline;
+pub mod visibility;
+
+pub(crate) mod objects;
+pub(crate) mod slider;
+
+pub use cache::*;
+pub use density::*;
+pub use fingerprint::*;
+pub use heatmap::*;
+pub use mods::*;
+pub use score::*;
+pub use stacking::*;
+pub use timeline::*;
+pub use visibility::*;
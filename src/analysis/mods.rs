@@ -0,0 +1,103 @@
+//! Module for gameplay mods.
+
+use std::ops::{BitOr, BitOrAssign};
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::osu_file::general::Mode;
+
+/// Gameplay mods, stored as the same bit flags osu! uses in replays and the API.
+/// - Combine mods with `|`, like `Mods::HIDDEN | Mods::HARD_ROCK`.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Mods(pub u32);
+
+impl Mods {
+    pub const NONE: Mods = Mods(0);
+    pub const NO_FAIL: Mods = Mods(1 << 0);
+    pub const EASY: Mods = Mods(1 << 1);
+    pub const TOUCH_DEVICE: Mods = Mods(1 << 2);
+    pub const HIDDEN: Mods = Mods(1 << 3);
+    pub const HARD_ROCK: Mods = Mods(1 << 4);
+    pub const SUDDEN_DEATH: Mods = Mods(1 << 5);
+    pub const DOUBLE_TIME: Mods = Mods(1 << 6);
+    pub const RELAX: Mods = Mods(1 << 7);
+    pub const HALF_TIME: Mods = Mods(1 << 8);
+    /// Always set together with [`DOUBLE_TIME`][Self::DOUBLE_TIME] by osu!.
+    pub const NIGHTCORE: Mods = Mods(1 << 9);
+    pub const FLASHLIGHT: Mods = Mods(1 << 10);
+    pub const AUTOPLAY: Mods = Mods(1 << 11);
+    pub const SPUN_OUT: Mods = Mods(1 << 12);
+    pub const AUTOPILOT: Mods = Mods(1 << 13);
+    /// Always set together with [`SUDDEN_DEATH`][Self::SUDDEN_DEATH] by osu!.
+    pub const PERFECT: Mods = Mods(1 << 14);
+    pub const FADE_IN: Mods = Mods(1 << 20);
+
+    /// Returns `true` if all of the mods in `other` are set.
+    pub fn contains(self, other: Mods) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if any of the mods in `other` are set.
+    pub fn intersects(self, other: Mods) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Speed the beatmap is played at, which is `1.5` with double time and `0.75` with half time.
+    /// - Nightcore is treated the same as double time even if the double time bit isn't set.
+    pub fn clock_rate(self) -> Decimal {
+        if self.intersects(Mods::DOUBLE_TIME | Mods::NIGHTCORE) {
+            dec!(1.5)
+        } else if self.contains(Mods::HALF_TIME) {
+            dec!(0.75)
+        } else {
+            Decimal::ONE
+        }
+    }
+
+    /// Score multiplier of the mods in osu!stable for the mode.
+    /// - Nightcore is treated the same as double time even if the double time bit isn't set.
+    pub fn score_multiplier(self, mode: Mode) -> Decimal {
+        let mut multiplier = Decimal::ONE;
+
+        let mut apply = |mods: Mods, value: Decimal| {
+            if self.intersects(mods) {
+                multiplier *= value;
+            }
+        };
+
+        match mode {
+            Mode::Mania => {
+                apply(Mods::NO_FAIL, dec!(0.5));
+                apply(Mods::EASY, dec!(0.5));
+                apply(Mods::HALF_TIME, dec!(0.5));
+            }
+            Mode::Osu | Mode::Taiko | Mode::Catch | Mode::Other(_) => {
+                apply(Mods::NO_FAIL, dec!(0.5));
+                apply(Mods::EASY, dec!(0.5));
+                apply(Mods::HALF_TIME, dec!(0.3));
+                apply(Mods::HIDDEN, dec!(1.06));
+                apply(Mods::HARD_ROCK, dec!(1.06));
+                apply(Mods::DOUBLE_TIME | Mods::NIGHTCORE, dec!(1.12));
+                apply(Mods::FLASHLIGHT, dec!(1.12));
+                apply(Mods::SPUN_OUT, dec!(0.9));
+            }
+        }
+
+        multiplier
+    }
+}
+
+impl BitOr for Mods {
+    type Output = Mods;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Mods(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Mods {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
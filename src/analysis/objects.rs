@@ -0,0 +1,105 @@
+//! Module for hitobjects with the timing of their nested objects worked out.
+
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use rust_decimal_macros::dec;
+
+use super::slider::SliderTiming;
+use crate::osu_file::{
+    general::Mode,
+    hitobjects::{HitObject, HitObjectParams},
+    OsuFile,
+};
+
+/// Hitobject with the timing of its nested objects worked out.
+pub(crate) struct TimedObject<'a> {
+    pub obj: &'a HitObject,
+    pub slider: Option<SliderTiming>,
+}
+
+impl TimedObject<'_> {
+    pub fn end_time(&self) -> f64 {
+        match (&self.obj.obj_params, &self.slider) {
+            (HitObjectParams::Slider(_), Some(slider)) => self.obj.time as f64 + slider.duration(),
+            (HitObjectParams::Spinner { end_time }, _)
+            | (HitObjectParams::OsuManiaHold { end_time }, _) => *end_time as f64,
+            _ => self.obj.time as f64,
+        }
+    }
+
+    /// Combo given by the object, including the nested objects.
+    pub fn combo(&self) -> u64 {
+        match &self.slider {
+            Some(slider) => (slider.tick_count() + slider.span_count + 1) as u64,
+            None => 1,
+        }
+    }
+}
+
+pub(crate) fn field<T: Clone + Into<Decimal>>(field: &Option<T>, default: Decimal) -> Decimal {
+    field.clone().map(Into::into).unwrap_or(default)
+}
+
+impl OsuFile {
+    /// Mode of the beatmap, which defaults to osu!standard.
+    pub(crate) fn mode_or_default(&self) -> Mode {
+        self.general
+            .as_ref()
+            .and_then(|g| g.mode)
+            .unwrap_or(Mode::Osu)
+    }
+
+    pub(crate) fn timed_objects(&self) -> Vec<TimedObject> {
+        let hitobjects = match &self.hitobjects {
+            Some(hitobjects) => &hitobjects.0[..],
+            None => &[],
+        };
+        let timing_points = match &self.timing_points {
+            Some(timing_points) => &timing_points.0[..],
+            None => &[],
+        };
+        let (slider_multiplier, tick_rate) = match &self.difficulty {
+            Some(difficulty) => (
+                field(&difficulty.slider_multiplier, dec!(1.4)),
+                field(&difficulty.slider_tickrate, Decimal::ONE),
+            ),
+            None => (dec!(1.4), Decimal::ONE),
+        };
+
+        hitobjects
+            .iter()
+            .map(|obj| TimedObject {
+                obj,
+                slider: match &obj.obj_params {
+                    HitObjectParams::Slider(params) => Some(SliderTiming::new(
+                        params,
+                        obj.time,
+                        timing_points,
+                        slider_multiplier,
+                        tick_rate,
+                        self.version,
+                    )),
+                    _ => None,
+                },
+            })
+            .collect()
+    }
+
+    /// Spins needed to clear a spinner lasting `duration_ms`, scaled by `OD`.
+    pub(crate) fn required_spins(&self, duration_ms: u64) -> u64 {
+        let od = match &self.difficulty {
+            Some(difficulty) => field(&difficulty.overall_difficulty, dec!(5)),
+            None => dec!(5),
+        };
+
+        let spins_per_second = if od > dec!(5) {
+            dec!(5) + dec!(2.5) * (od - dec!(5)) / dec!(5)
+        } else {
+            dec!(5) - dec!(2) * (dec!(5) - od) / dec!(5)
+        };
+
+        (Decimal::from(duration_ms) * spins_per_second / dec!(1000))
+            .floor()
+            .to_u64()
+            .unwrap_or_default()
+    }
+}
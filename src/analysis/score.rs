@@ -0,0 +1,154 @@
+//! Module for calculating the maximum score of a beatmap.
+
+use rust_decimal::{prelude::ToPrimitive, Decimal, RoundingStrategy};
+use rust_decimal_macros::dec;
+
+use super::{
+    objects::{field, TimedObject},
+    Mods,
+};
+use crate::osu_file::{events::Event, general::Mode, hitobjects::HitObjectParams, OsuFile};
+
+/// Spinning speed used for the spinner bonus, which is the fastest osu! allows.
+const MAX_SPINS_PER_MINUTE: u64 = 477;
+
+/// Maximum score in osu!mania before the mod multiplier.
+const MANIA_MAX_SCORE: u64 = 1_000_000;
+
+/// Mode that [`OsuFile::max_score`] calculates the score for.
+/// - Only has the modes whose ScoreV1 is worked out, osu!taiko and osu!catch aren't supported.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ScoreMode {
+    Osu,
+    Mania,
+}
+
+impl From<ScoreMode> for Mode {
+    fn from(mode: ScoreMode) -> Self {
+        match mode {
+            ScoreMode::Osu => Mode::Osu,
+            ScoreMode::Mania => Mode::Mania,
+        }
+    }
+}
+
+impl OsuFile {
+    /// Calculates the maximum combo in osu!standard.
+    /// - Each hitcircle and spinner gives `1` combo.
+    /// - Sliders give `1` combo for the head, each tick, each repeat and the tail.
+    pub fn max_combo(&self) -> u64 {
+        self.timed_objects().iter().map(TimedObject::combo).sum()
+    }
+
+    /// Calculates the maximum score using ScoreV1, which is the scoring used by osu!stable.
+    /// - `mode` is the mode the beatmap is played in, which can be osu!mania for converted osu!standard beatmaps.
+    /// - Spinners are assumed to be spun at the maximum speed of `477` spins per minute.
+    /// - Returns `None` for osu!standard if the beatmap is made for another mode, since it can't be converted to osu!standard.
+    pub fn max_score(&self, mods: Mods, mode: ScoreMode) -> Option<u64> {
+        let mod_multiplier = mods.score_multiplier(mode.into());
+
+        match mode {
+            ScoreMode::Osu if self.mode_or_default() == Mode::Osu => {
+                Some(self.max_score_osu(mod_multiplier))
+            }
+            ScoreMode::Osu => None,
+            ScoreMode::Mania => {
+                let has_hitobjects = matches!(&self.hitobjects, Some(h) if !h.0.is_empty());
+
+                if has_hitobjects {
+                    (Decimal::from(MANIA_MAX_SCORE) * mod_multiplier)
+                        .floor()
+                        .to_u64()
+                } else {
+                    Some(0)
+                }
+            }
+        }
+    }
+
+    /// Difficulty multiplier used for the combo bonus in ScoreV1.
+    /// - Made from `HP`, `CS`, `OD`, and how many objects there are per second of drain time.
+    fn score_difficulty_multiplier(&self, objects: &[TimedObject]) -> Decimal {
+        let (hp, cs, od) = match &self.difficulty {
+            Some(difficulty) => (
+                field(&difficulty.hp_drain_rate, dec!(5)),
+                field(&difficulty.circle_size, dec!(5)),
+                field(&difficulty.overall_difficulty, dec!(5)),
+            ),
+            None => (dec!(5), dec!(5), dec!(5)),
+        };
+
+        let drain_time = match (objects.first(), objects.last()) {
+            (Some(first), Some(last)) => {
+                let break_time = self.events.as_ref().map_or(0, |events| {
+                    events
+                        .0
+                        .iter()
+                        .filter_map(|e| match e {
+                            Event::Break(b) => {
+                                Some(b.end_time.saturating_sub(b.start_time).max(0) as i64)
+                            }
+                            _ => None,
+                        })
+                        .sum()
+                });
+
+                last.end_time() - first.obj.time as f64 - break_time as f64
+            }
+            _ => 0.0,
+        };
+
+        let max_density = dec!(16);
+        let density = match Decimal::from_f64_retain(drain_time) {
+            Some(drain_time) if drain_time > Decimal::ZERO => {
+                (Decimal::from(objects.len()) * dec!(8000) / drain_time).min(max_density)
+            }
+            _ => max_density,
+        };
+
+        ((hp + cs + od + density) / dec!(38) * dec!(5))
+            .round_dp_with_strategy(0, RoundingStrategy::MidpointNearestEven)
+    }
+
+    fn max_score_osu(&self, mod_multiplier: Decimal) -> u64 {
+        let objects = self.timed_objects();
+        let bonus_multiplier = self.score_difficulty_multiplier(&objects) * mod_multiplier;
+
+        let mut combo: u64 = 0;
+        let mut score: u64 = 0;
+
+        // a 300 judgement, which is the only one that gets the combo bonus
+        let hit_300 = |combo: &mut u64| {
+            let combo_multiplier = Decimal::from(combo.saturating_sub(1));
+            let bonus = (dec!(300) * combo_multiplier * bonus_multiplier / dec!(25)).floor();
+
+            *combo += 1;
+            300 + bonus.to_u64().unwrap_or_default()
+        };
+
+        for object in &objects {
+            match (&object.obj.obj_params, &object.slider) {
+                (HitObjectParams::Slider(_), Some(slider)) => {
+                    // head, ticks and repeats
+                    score +=
+                        30 + slider.tick_count() as u64 * 10 + (slider.span_count as u64 - 1) * 30;
+                    combo += 1 + slider.tick_count() as u64 + slider.span_count as u64 - 1;
+
+                    // tail
+                    score += 30 + hit_300(&mut combo);
+                }
+                (HitObjectParams::Spinner { end_time }, _) => {
+                    let duration_ms = end_time.saturating_sub(object.obj.time) as u64;
+                    let spins = duration_ms * MAX_SPINS_PER_MINUTE / 60_000;
+                    let required_spins = self.required_spins(duration_ms);
+
+                    score += spins * 100 + spins.saturating_sub(required_spins) * 1000;
+                    score += hit_300(&mut combo);
+                }
+                _ => score += hit_300(&mut combo),
+            }
+        }
+
+        score
+    }
+}
@@ -0,0 +1,152 @@
+//! Module for the timing of the objects nested inside of sliders.
+
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+
+use crate::osu_file::{hitobjects::SlideParams, timingpoints::TimingPoint, Version};
+
+/// Beat length used when there are no uninherited timing points, which is `60` BPM.
+const DEFAULT_BEAT_LENGTH: f64 = 1000.0;
+
+/// Ticks closer than this to the end of the span are skipped.
+const MIN_TICK_DISTANCE_FROM_END_MS: f64 = 10.0;
+
+/// Upper limit of ticks in a single span, so broken slider lengths can't loop forever.
+const MAX_TICKS_PER_SPAN: usize = 32768;
+
+/// Timing of a slider, worked out from the timing points and the difficulty settings.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SliderTiming {
+    /// Duration of a single span in milliseconds.
+    pub span_duration: f64,
+    /// Number of spans, which is the number of repeats + 1.
+    pub span_count: usize,
+    /// Time of each tick in milliseconds, relative to the start of the span.
+    /// - Reversed spans have the ticks mirrored, so it's the same count on every span.
+    pub tick_offsets: Vec<f64>,
+}
+
+impl SliderTiming {
+    /// Works out the timing of the slider starting at `time`.
+    /// - `timing_points` are expected to be sorted by time.
+    pub fn new(
+        slider: &SlideParams,
+        time: u32,
+        timing_points: &[TimingPoint],
+        slider_multiplier: Decimal,
+        tick_rate: Decimal,
+        version: Version,
+    ) -> Self {
+        let (beat_length, slider_velocity) = timing_at(timing_points, time);
+
+        let span_count = slider.slides.max(1) as usize;
+        let length = slider.length.to_f64().unwrap_or_default().max(0.0);
+        let slider_multiplier = slider_multiplier.to_f64().unwrap_or_default();
+        let tick_rate = tick_rate.to_f64().unwrap_or_default();
+
+        let scoring_distance = 100.0 * slider_multiplier * slider_velocity;
+        let velocity = scoring_distance / beat_length;
+
+        if !(velocity.is_finite() && velocity > 0.0) {
+            return Self {
+                span_duration: 0.0,
+                span_count,
+                tick_offsets: Vec::new(),
+            };
+        }
+
+        // old versions don't speed up the ticks with the slider velocity
+        let tick_distance = if version < 8 {
+            scoring_distance / tick_rate / slider_velocity
+        } else {
+            scoring_distance / tick_rate
+        };
+
+        let mut tick_offsets = Vec::new();
+
+        if tick_distance.is_finite() && tick_distance > 0.0 {
+            let max_distance = length - velocity * MIN_TICK_DISTANCE_FROM_END_MS;
+            let mut distance = tick_distance;
+
+            while distance < max_distance && tick_offsets.len() < MAX_TICKS_PER_SPAN {
+                tick_offsets.push(distance / velocity);
+                distance += tick_distance;
+            }
+        }
+
+        Self {
+            span_duration: length / velocity,
+            span_count,
+            tick_offsets,
+        }
+    }
+
+    /// Total duration of the slider in milliseconds.
+    pub fn duration(&self) -> f64 {
+        self.span_duration * self.span_count as f64
+    }
+
+    /// Number of ticks over all spans.
+    pub fn tick_count(&self) -> usize {
+        self.tick_offsets.len() * self.span_count
+    }
+
+    /// Times of the head, each repeat and the tail, for the slider starting at `start`.
+    pub fn edge_times(&self, start: f64) -> Vec<f64> {
+        (0..=self.span_count)
+            .map(|span| start + span as f64 * self.span_duration)
+            .collect()
+    }
+
+    /// Times of the ticks over all spans in order, for the slider starting at `start`.
+    pub fn tick_times(&self, start: f64) -> Vec<f64> {
+        (0..self.span_count)
+            .flat_map(|span| {
+                let span_start = start + span as f64 * self.span_duration;
+                let mut ticks = self
+                    .tick_offsets
+                    .iter()
+                    .map(|offset| match span % 2 {
+                        0 => span_start + offset,
+                        _ => span_start + self.span_duration - offset,
+                    })
+                    .collect::<Vec<_>>();
+                ticks.sort_by(f64::total_cmp);
+                ticks
+            })
+            .collect()
+    }
+}
+
+/// Returns the beat length and the slider velocity multiplier active at `time`.
+pub fn timing_at(timing_points: &[TimingPoint], time: u32) -> (f64, f64) {
+    let mut beat_length = timing_points
+        .iter()
+        .find(|t| t.uninherited)
+        .and_then(|t| positive_f64(t.beat_length))
+        .unwrap_or(DEFAULT_BEAT_LENGTH);
+    let mut slider_velocity = 1.0;
+
+    for timing_point in timing_points {
+        if i64::from(timing_point.time) > i64::from(time) {
+            break;
+        }
+
+        if timing_point.uninherited {
+            if let Some(b) = positive_f64(timing_point.beat_length) {
+                beat_length = b;
+            }
+            slider_velocity = 1.0;
+        } else {
+            slider_velocity = match timing_point.beat_length.to_f64() {
+                Some(b) if b < 0.0 => (-100.0 / b).clamp(0.1, 10.0),
+                _ => 1.0,
+            };
+        }
+    }
+
+    (beat_length, slider_velocity)
+}
+
+fn positive_f64(d: Decimal) -> Option<f64> {
+    d.to_f64().filter(|d| *d > 0.0)
+}
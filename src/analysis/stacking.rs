@@ -0,0 +1,114 @@
+//! Module for working out the stack heights of osu!standard hitobjects, which is how far they're moved so they don't overlap.
+//! - Follows the stacking of version 6 and later files, which is used for every version.
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal_macros::dec;
+
+use super::{
+    objects::field,
+    visibility::{approach_rate, preempt},
+    Mods,
+};
+use crate::osu_file::{general::Mode, hitobjects::HitObjectParams, OsuFile, Position};
+
+/// Hitobjects closer than this many osu!pixels are stacked.
+const STACK_DISTANCE: f64 = 3.0;
+
+fn distance(a: Option<Position<f64>>, b: Option<Position<f64>>) -> f64 {
+    match (a, b) {
+        (Some(a), Some(b)) => (a.x - b.x).hypot(a.y - b.y),
+        _ => f64::INFINITY,
+    }
+}
+
+/// Stack height of each hitobject, in the order of the `[HitObjects]` section.
+/// - Hitobjects with a positive height are moved up and left, and sliders that end on the next hitobject move it down and right with a negative height.
+/// - Uses the approach rate without mods and the `StackLeniency`, so hitobjects that are close in time and position are stacked.
+/// - Beatmaps that aren't osu!standard aren't stacked, and have a height of `0` for every hitobject.
+/// - Positions that can't be worked out, such as of sliders without a length, don't stack.
+pub fn stack_heights(osu_file: &OsuFile) -> Vec<i32> {
+    let objects = osu_file.timed_objects();
+    let mut heights = vec![0; objects.len()];
+
+    if osu_file.mode_or_default() != Mode::Osu {
+        return heights;
+    }
+
+    let stack_leniency = match &osu_file.general {
+        Some(general) => field(&general.stack_leniency, dec!(0.7)),
+        None => dec!(0.7),
+    };
+    let stack_threshold =
+        preempt(approach_rate(osu_file, Mods::NONE)) * stack_leniency.to_f64().unwrap_or(0.7);
+
+    let start_times = objects
+        .iter()
+        .map(|o| o.obj.time as f64)
+        .collect::<Vec<_>>();
+    let end_times = objects.iter().map(|o| o.end_time()).collect::<Vec<_>>();
+    let positions = objects
+        .iter()
+        .map(|o| Position::<f64>::try_from(o.obj.position).ok())
+        .collect::<Vec<_>>();
+    let end_positions = objects
+        .iter()
+        .map(|o| o.obj.end_position().ok())
+        .collect::<Vec<_>>();
+    let is_spinner =
+        |i: usize| matches!(objects[i].obj.obj_params, HitObjectParams::Spinner { .. });
+    let is_slider = |i: usize| matches!(objects[i].obj.obj_params, HitObjectParams::Slider(_));
+
+    for i in (1..objects.len()).rev() {
+        if heights[i] != 0 || is_spinner(i) {
+            continue;
+        }
+
+        // the object the earlier objects are stacked under, which moves back as the stack grows
+        let mut top = i;
+
+        if is_slider(i) {
+            for n in (0..i).rev() {
+                if is_spinner(n) {
+                    continue;
+                }
+                if start_times[top] - start_times[n] > stack_threshold {
+                    break;
+                }
+
+                if distance(end_positions[n], positions[top]) < STACK_DISTANCE {
+                    heights[n] = heights[top] + 1;
+                    top = n;
+                }
+            }
+            continue;
+        }
+
+        for n in (0..i).rev() {
+            if is_spinner(n) {
+                continue;
+            }
+            if start_times[top] - end_times[n] > stack_threshold {
+                break;
+            }
+
+            // sliders ending on the stack move the objects after them down and right instead
+            if is_slider(n) && distance(end_positions[n], positions[top]) < STACK_DISTANCE {
+                let offset = heights[top] - heights[n] + 1;
+
+                for j in n + 1..=i {
+                    if distance(end_positions[n], positions[j]) < STACK_DISTANCE {
+                        heights[j] -= offset;
+                    }
+                }
+                break;
+            }
+
+            if distance(positions[n], positions[top]) < STACK_DISTANCE {
+                heights[n] = heights[top] + 1;
+                top = n;
+            }
+        }
+    }
+
+    heights
+}
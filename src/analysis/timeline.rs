@@ -0,0 +1,190 @@
+//! Module for going through everything in a beatmap in the order it happens.
+
+use rust_decimal::prelude::ToPrimitive;
+
+use super::objects::TimedObject;
+use crate::osu_file::{
+    events::{AudioSample, Break, Event, Events, SampleLegacy},
+    hitobjects::{HitObject, HitObjectParams},
+    timingpoints::TimingPoint,
+    OsuFile,
+};
+
+/// Something that happens at a point in time in the beatmap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TimelineItem<'a> {
+    /// Start of the hitobject at `index` in `[HitObjects]`.
+    HitObjectStart {
+        index: usize,
+        hitobject: &'a HitObject,
+    },
+    /// End of the hitobject at `index` in `[HitObjects]`.
+    /// - Only for sliders, spinners and osu!mania holds.
+    HitObjectEnd {
+        index: usize,
+        hitobject: &'a HitObject,
+    },
+    BreakStart(&'a Break),
+    BreakEnd(&'a Break),
+    /// Any timing point, inherited or uninherited.
+    TimingChange(&'a TimingPoint),
+    /// Timing point where kiai time is turned on or off.
+    KiaiToggle {
+        enabled: bool,
+        timing_point: &'a TimingPoint,
+    },
+    /// Storyboard sample in the .osu file or the .osb file.
+    SampleLegacy(&'a SampleLegacy),
+    /// Storyboard sample in the .osu file or the .osb file.
+    AudioSample(&'a AudioSample),
+    /// Editor bookmark.
+    Bookmark,
+}
+
+/// A [`TimelineItem`] with the time it happens in milliseconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimelineEntry<'a> {
+    pub time: f64,
+    pub item: TimelineItem<'a>,
+}
+
+fn push_events<'a>(events: &'a Events, entries: &mut Vec<TimelineEntry<'a>>) {
+    for event in &events.0 {
+        let (time, item) = match event {
+            Event::Break(b) => {
+                entries.push(TimelineEntry {
+                    time: b.start_time as f64,
+                    item: TimelineItem::BreakStart(b),
+                });
+                (b.end_time as f64, TimelineItem::BreakEnd(b))
+            }
+            Event::SampleLegacy(sample) => (
+                sample.time.to_f64().unwrap_or_default(),
+                TimelineItem::SampleLegacy(sample),
+            ),
+            Event::AudioSample(sample) => (sample.time as f64, TimelineItem::AudioSample(sample)),
+            _ => continue,
+        };
+
+        entries.push(TimelineEntry { time, item });
+    }
+}
+
+impl OsuFile {
+    /// Returns everything that happens in the beatmap sorted by time.
+    /// - Includes timing points, kiai toggles, breaks, storyboard samples, bookmarks, and hitobject starts and ends.
+    /// - Items at the same time are kept in the order of the list above, except that breaks and storyboard samples are in the order they are in `[Events]`, followed by the ones in the .osb file.
+    /// - Items of the same kind at the same time are in the order they are in the file, and each kiai toggle comes right after its timing point.
+    pub fn timeline(&self) -> impl Iterator<Item = TimelineEntry<'_>> {
+        let end_times = self
+            .timed_objects()
+            .iter()
+            .map(TimedObject::end_time)
+            .collect::<Vec<_>>();
+
+        self.timeline_with_end_times(&end_times)
+    }
+
+    /// [`timeline`][Self::timeline] with the end times of the hitobjects already worked out.
+    pub(crate) fn timeline_with_end_times(
+        &self,
+        end_times: &[f64],
+    ) -> std::vec::IntoIter<TimelineEntry<'_>> {
+        let entries = self.unsorted_timeline(end_times);
+        let order = timeline_order(&entries);
+
+        sorted_timeline(entries, &order)
+    }
+
+    /// Entries of the [`timeline`][Self::timeline] before they're sorted by time.
+    pub(crate) fn unsorted_timeline(&self, end_times: &[f64]) -> Vec<TimelineEntry<'_>> {
+        let mut entries = Vec::new();
+
+        if let Some(timing_points) = &self.timing_points {
+            let mut kiai = false;
+
+            for timing_point in &timing_points.0 {
+                let time = timing_point.time as f64;
+                entries.push(TimelineEntry {
+                    time,
+                    item: TimelineItem::TimingChange(timing_point),
+                });
+
+                let enabled = timing_point
+                    .effects
+                    .as_ref()
+                    .is_some_and(|effects| effects.kiai_time_enabled());
+
+                if enabled != kiai {
+                    kiai = enabled;
+                    entries.push(TimelineEntry {
+                        time,
+                        item: TimelineItem::KiaiToggle {
+                            enabled,
+                            timing_point,
+                        },
+                    });
+                }
+            }
+        }
+
+        if let Some(events) = &self.events {
+            push_events(events, &mut entries);
+        }
+        if let Some(events) = self.osb.as_ref().and_then(|osb| osb.events.as_ref()) {
+            push_events(events, &mut entries);
+        }
+
+        let bookmarks = self
+            .editor
+            .as_ref()
+            .and_then(|editor| editor.bookmarks.clone())
+            .map(Vec::from)
+            .unwrap_or_default();
+        for bookmark in bookmarks {
+            entries.push(TimelineEntry {
+                time: bookmark as f64,
+                item: TimelineItem::Bookmark,
+            });
+        }
+
+        let hitobjects = self.hitobjects.iter().flat_map(|h| &h.0);
+        for (index, (hitobject, end_time)) in hitobjects.zip(end_times).enumerate() {
+            entries.push(TimelineEntry {
+                time: hitobject.time as f64,
+                item: TimelineItem::HitObjectStart { index, hitobject },
+            });
+
+            if !matches!(hitobject.obj_params, HitObjectParams::HitCircle) {
+                entries.push(TimelineEntry {
+                    time: *end_time,
+                    item: TimelineItem::HitObjectEnd { index, hitobject },
+                });
+            }
+        }
+
+        entries
+    }
+}
+
+/// Indices of the `entries` sorted by time.
+pub(crate) fn timeline_order(entries: &[TimelineEntry]) -> Vec<usize> {
+    let mut order = (0..entries.len()).collect::<Vec<_>>();
+
+    // stable sort, so items at the same time keep the order they were added in
+    order.sort_by(|a, b| entries[*a].time.total_cmp(&entries[*b].time));
+    order
+}
+
+/// The `entries` in the `order` from [`timeline_order`].
+pub(crate) fn sorted_timeline<'a>(
+    entries: Vec<TimelineEntry<'a>>,
+    order: &[usize],
+) -> std::vec::IntoIter<TimelineEntry<'a>> {
+    order
+        .iter()
+        .map(|i| entries[*i])
+        .collect::<Vec<_>>()
+        .into_iter()
+}
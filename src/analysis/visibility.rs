@@ -0,0 +1,106 @@
+//! Module for working out when hitobjects can be seen, from the approach rate and the mods.
+//! - Follows osu!standard, where the approach rate sets how early hitobjects appear.
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal_macros::dec;
+
+use super::{objects::field, Mods};
+use crate::osu_file::OsuFile;
+
+/// Time a hitobject appears before it's hit at approach rate `5`, in milliseconds.
+const PREEMPT_MID: f64 = 1200.0;
+
+/// Fade in duration of hitobjects without hidden, in milliseconds.
+const FADE_IN: f64 = 400.0;
+
+/// Hitobjects that appear less than this many milliseconds before they're hit fade in faster.
+const FADE_IN_FULL_PREEMPT: f64 = 450.0;
+
+/// Part of the time on screen hitobjects spend fading in with hidden.
+const HIDDEN_FADE_IN_RATIO: f64 = 0.4;
+
+/// When a hitobject can be seen, worked out by [`visibility`].
+/// - Times are in milliseconds from the beginning of the beatmap's audio, so they don't change with the speed of the mods.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObjectVisibility {
+    /// Time the hitobject starts fading in.
+    pub appear_time: f64,
+    /// How long the hitobject takes to fade in completely.
+    pub fade_in: f64,
+    /// Time the hitobject is hit, which is its start time.
+    pub hit_time: f64,
+}
+
+/// Approach rate after hard rock or easy, without the speed of the mods.
+/// - Beatmaps without an `ApproachRate` use the `OverallDifficulty`, like old versions did.
+pub(crate) fn approach_rate(osu_file: &OsuFile, mods: Mods) -> f64 {
+    let approach_rate = match &osu_file.difficulty {
+        Some(difficulty) if difficulty.approach_rate.is_some() => {
+            field(&difficulty.approach_rate, dec!(5))
+        }
+        Some(difficulty) => field(&difficulty.overall_difficulty, dec!(5)),
+        None => dec!(5),
+    };
+    let mut approach_rate = approach_rate.to_f64().unwrap_or(5.0);
+
+    if mods.contains(Mods::HARD_ROCK) {
+        approach_rate = (approach_rate * 1.4).min(10.0);
+    }
+    if mods.contains(Mods::EASY) {
+        approach_rate *= 0.5;
+    }
+
+    approach_rate
+}
+
+/// Milliseconds a hitobject appears before it's hit at the `approach_rate`.
+pub(crate) fn preempt(approach_rate: f64) -> f64 {
+    if approach_rate < 5.0 {
+        PREEMPT_MID + 600.0 * (5.0 - approach_rate) / 5.0
+    } else {
+        PREEMPT_MID - 750.0 * (approach_rate - 5.0) / 5.0
+    }
+}
+
+/// Approach rate that appears `preempt` milliseconds before the hit, which is the inverse of [`preempt`].
+fn approach_rate_from_preempt(preempt: f64) -> f64 {
+    if preempt > PREEMPT_MID {
+        5.0 - (preempt - PREEMPT_MID) / 120.0
+    } else {
+        5.0 + (PREEMPT_MID - preempt) / 150.0
+    }
+}
+
+/// Approach rate that looks the same to the player as the beatmap played with the `mods`.
+/// - Double time and half time change how long the hitobjects are on screen, so the approach rate can go above `10` and below `0`.
+pub fn effective_approach_rate(osu_file: &OsuFile, mods: Mods) -> f64 {
+    let clock_rate = mods.clock_rate().to_f64().unwrap_or(1.0);
+
+    approach_rate_from_preempt(preempt(approach_rate(osu_file, mods)) / clock_rate)
+}
+
+/// When each hitobject in the `[HitObjects]` section can be seen when played with the `mods`.
+/// - Hard rock and easy change the approach rate, and hidden makes the hitobjects fade in for longer.
+/// - Returns an empty `Vec` if there's no `[HitObjects]` section.
+pub fn visibility(osu_file: &OsuFile, mods: Mods) -> Vec<ObjectVisibility> {
+    let hitobjects = match &osu_file.hitobjects {
+        Some(hitobjects) => &hitobjects.0[..],
+        None => return Vec::new(),
+    };
+
+    let preempt = preempt(approach_rate(osu_file, mods));
+    let fade_in = if mods.contains(Mods::HIDDEN) {
+        preempt * HIDDEN_FADE_IN_RATIO
+    } else {
+        FADE_IN * (preempt / FADE_IN_FULL_PREEMPT).min(1.0)
+    };
+
+    hitobjects
+        .iter()
+        .map(|obj| ObjectVisibility {
+            appear_time: obj.time as f64 - preempt,
+            fade_in,
+            hit_time: obj.time as f64,
+        })
+        .collect()
+}
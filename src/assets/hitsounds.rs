@@ -0,0 +1,366 @@
+//! Module for matching custom hitsound files to the samples a beatmap plays.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    lint::FileReferenceKind,
+    osu_file::{
+        general::{self, Mode},
+        hitobjects::{self, HitObject, HitObjectParams, HitSound},
+        timingpoints::{self, TimingPoint},
+        Integer, OsuFile,
+    },
+};
+
+/// File extensions that osu! loads hitsounds from.
+const EXTENSIONS: &[&str] = &["wav", "mp3", "ogg"];
+
+/// Sample bank of a hitsound, the first part of its file name.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SampleBank {
+    Normal,
+    Soft,
+    Drum,
+}
+
+impl SampleBank {
+    /// Name used in the file name, such as `normal`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SampleBank::Normal => "normal",
+            SampleBank::Soft => "soft",
+            SampleBank::Drum => "drum",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [SampleBank::Normal, SampleBank::Soft, SampleBank::Drum]
+            .into_iter()
+            .find(|bank| bank.name() == name)
+    }
+
+    fn from_hitobject(set: hitobjects::SampleSet) -> Option<Self> {
+        match set {
+            hitobjects::SampleSet::NormalSet => Some(SampleBank::Normal),
+            hitobjects::SampleSet::SoftSet => Some(SampleBank::Soft),
+            hitobjects::SampleSet::DrumSet => Some(SampleBank::Drum),
+            hitobjects::SampleSet::NoCustomSampleSet | hitobjects::SampleSet::Other(_) => None,
+        }
+    }
+
+    fn from_timing_point(set: timingpoints::SampleSet) -> Option<Self> {
+        match set {
+            timingpoints::SampleSet::Normal => Some(SampleBank::Normal),
+            timingpoints::SampleSet::Soft => Some(SampleBank::Soft),
+            timingpoints::SampleSet::Drum => Some(SampleBank::Drum),
+            timingpoints::SampleSet::BeatmapDefault | timingpoints::SampleSet::Other(_) => None,
+        }
+    }
+
+    fn from_general(set: general::SampleSet) -> Self {
+        match set {
+            general::SampleSet::Soft => SampleBank::Soft,
+            general::SampleSet::Drum => SampleBank::Drum,
+            general::SampleSet::Normal | general::SampleSet::None => SampleBank::Normal,
+        }
+    }
+}
+
+/// Sound of a hitsound, the second part of its file name.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SampleSound {
+    HitNormal,
+    HitWhistle,
+    HitFinish,
+    HitClap,
+    SliderSlide,
+    SliderWhistle,
+    SliderTick,
+}
+
+impl SampleSound {
+    const ALL: [SampleSound; 7] = [
+        SampleSound::HitNormal,
+        SampleSound::HitWhistle,
+        SampleSound::HitFinish,
+        SampleSound::HitClap,
+        SampleSound::SliderSlide,
+        SampleSound::SliderWhistle,
+        SampleSound::SliderTick,
+    ];
+
+    /// Name used in the file name, such as `hitnormal`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SampleSound::HitNormal => "hitnormal",
+            SampleSound::HitWhistle => "hitwhistle",
+            SampleSound::HitFinish => "hitfinish",
+            SampleSound::HitClap => "hitclap",
+            SampleSound::SliderSlide => "sliderslide",
+            SampleSound::SliderWhistle => "sliderwhistle",
+            SampleSound::SliderTick => "slidertick",
+        }
+    }
+}
+
+/// Sample that a custom hitsound file replaces, such as `normal-hitnormal3.wav`.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HitsoundKey {
+    pub bank: SampleBank,
+    pub sound: SampleSound,
+    /// Custom sample index, which is at least `1`.
+    /// - Index `1` is written without a number, but `1` is also accepted.
+    pub index: u32,
+}
+
+impl HitsoundKey {
+    pub fn new(bank: SampleBank, sound: SampleSound, index: u32) -> Self {
+        Self { bank, sound, index }
+    }
+
+    /// Matches the file name to a sample, ignoring the case.
+    /// - Returns `None` if the file isn't a `.wav`, `.mp3` or `.ogg` file named after a sample, or if it's in a subfolder, since osu! only loads hitsounds from the beatmap folder itself.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        if path
+            .parent()
+            .is_some_and(|parent| !parent.as_os_str().is_empty())
+        {
+            return None;
+        }
+
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        if !EXTENSIONS.contains(&extension.as_str()) {
+            return None;
+        }
+
+        let stem = path.file_stem()?.to_str()?.to_lowercase();
+        let (bank, rest) = stem.split_once('-')?;
+        let bank = SampleBank::from_name(bank)?;
+
+        SampleSound::ALL.into_iter().find_map(|sound| {
+            let index = rest.strip_prefix(sound.name())?;
+
+            let index = if index.is_empty() {
+                1
+            } else if index.bytes().all(|b| b.is_ascii_digit()) {
+                index.parse().ok().filter(|index| *index > 0)?
+            } else {
+                return None;
+            };
+
+            Some(Self::new(bank, sound, index))
+        })
+    }
+
+    /// File name without the extension, such as `soft-hitclap2`.
+    pub fn file_stem(&self) -> String {
+        match self.index {
+            0 | 1 => format!("{}-{}", self.bank.name(), self.sound.name()),
+            index => format!("{}-{}{index}", self.bank.name(), self.sound.name()),
+        }
+    }
+}
+
+/// Result of [`match_hitsounds`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HitsoundMatch {
+    /// Files named after a sample, grouped by the sample they replace.
+    pub matched: BTreeMap<HitsoundKey, Vec<PathBuf>>,
+    /// Matched files that the beatmap never plays, in the order they were given.
+    /// - Files used by a hitsample's custom file name or a storyboard sample aren't included.
+    pub unused: Vec<PathBuf>,
+}
+
+impl HitsoundMatch {
+    /// Samples with more than one file, such as `normal-hitnormal.wav` and `normal-hitnormal1.ogg`, where only one of them gets loaded.
+    pub fn conflicts(&self) -> impl Iterator<Item = (&HitsoundKey, &[PathBuf])> {
+        self.matched
+            .iter()
+            .filter(|(_, files)| files.len() > 1)
+            .map(|(key, files)| (key, files.as_slice()))
+    }
+}
+
+/// Matches the files in a beatmap folder to the samples they replace, finding conflicting and unused files.
+/// - `files` are the paths relative to the beatmap folder, like from [`AssetProber::files`][crate::lint::AssetProber::files].
+/// - Files that aren't named after a sample are ignored.
+pub fn match_hitsounds(files: &[PathBuf], osu_file: &OsuFile) -> HitsoundMatch {
+    let used = osu_file.used_hitsounds();
+    let referenced = referenced_files(osu_file);
+
+    let mut result = HitsoundMatch::default();
+
+    for file in files {
+        let key = match HitsoundKey::from_path(file) {
+            Some(key) => key,
+            None => continue,
+        };
+
+        result.matched.entry(key).or_default().push(file.clone());
+
+        let name = file.to_string_lossy().to_lowercase();
+        if !used.contains(&key) && !referenced.contains(&name) {
+            result.unused.push(file.clone());
+        }
+    }
+
+    result
+}
+
+/// Lowercase paths of the files played directly, through hitsample file names and storyboard samples.
+fn referenced_files(osu_file: &OsuFile) -> BTreeSet<String> {
+    let hitsample_files = osu_file
+        .hitobjects
+        .iter()
+        .flat_map(|hitobjects| &hitobjects.0)
+        .filter_map(|obj| obj.hitsample.as_ref()?.filename.clone())
+        .filter(|filename| !filename.is_empty());
+
+    let storyboard_files = osu_file
+        .file_references()
+        .into_iter()
+        .filter(|reference| reference.kind == FileReferenceKind::Sample)
+        .map(|reference| reference.path.to_string_lossy().into_owned());
+
+    hitsample_files
+        .chain(storyboard_files)
+        .map(|path| path.replace('\\', "/").to_lowercase())
+        .collect()
+}
+
+/// Timing point in effect at `time`, where the first timing point is used before it starts.
+fn timing_point_at(timing_points: &[TimingPoint], time: Integer) -> Option<&TimingPoint> {
+    timing_points
+        .iter()
+        .rev()
+        .find(|t| t.time <= time)
+        .or_else(|| timing_points.first())
+}
+
+/// Collects the samples played by a single object.
+struct SampleResolver {
+    default_bank: SampleBank,
+    mode: Mode,
+}
+
+impl SampleResolver {
+    fn push(
+        &self,
+        used: &mut BTreeSet<HitsoundKey>,
+        hitsound: HitSound,
+        normal: SampleBank,
+        addition: SampleBank,
+        index: u32,
+    ) {
+        // only osu!mania leaves out the normal sound when there are additions
+        if hitsound.normal() || self.mode != Mode::Mania {
+            used.insert(HitsoundKey::new(normal, SampleSound::HitNormal, index));
+        }
+        if hitsound.whistle() {
+            used.insert(HitsoundKey::new(addition, SampleSound::HitWhistle, index));
+        }
+        if hitsound.finish() {
+            used.insert(HitsoundKey::new(addition, SampleSound::HitFinish, index));
+        }
+        if hitsound.clap() {
+            used.insert(HitsoundKey::new(addition, SampleSound::HitClap, index));
+        }
+    }
+
+    fn object(
+        &self,
+        used: &mut BTreeSet<HitsoundKey>,
+        obj: &HitObject,
+        timing_point: Option<&TimingPoint>,
+    ) {
+        let index = match obj.hitsample.as_ref().map(|s| s.index) {
+            Some(hitobjects::SampleIndex::Index(index)) => index.get() as u32,
+            _ => match timing_point.map(|t| t.sample_index) {
+                Some(timingpoints::SampleIndex::Index(index)) => index.get(),
+                _ => 0,
+            },
+        };
+
+        // index 0 uses the skin's hitsounds
+        if index == 0 {
+            return;
+        }
+
+        let timing_bank = timing_point
+            .and_then(|t| SampleBank::from_timing_point(t.sample_set))
+            .unwrap_or(self.default_bank);
+        let normal = obj
+            .hitsample
+            .as_ref()
+            .and_then(|s| SampleBank::from_hitobject(s.normal_set))
+            .unwrap_or(timing_bank);
+        let addition = obj
+            .hitsample
+            .as_ref()
+            .and_then(|s| SampleBank::from_hitobject(s.addition_set))
+            .unwrap_or(normal);
+
+        let params = match &obj.obj_params {
+            HitObjectParams::Slider(params) => params,
+            _ => return self.push(used, obj.hitsound, normal, addition, index),
+        };
+
+        for node in params.node_samples() {
+            let hitsound = if params.edge_sounds.is_empty() {
+                obj.hitsound
+            } else {
+                node.hitsound
+            };
+            let node_normal =
+                SampleBank::from_hitobject(node.edge_set.normal_set).unwrap_or(normal);
+            let node_addition =
+                SampleBank::from_hitobject(node.edge_set.addition_set).unwrap_or(addition);
+
+            self.push(used, hitsound, node_normal, node_addition, index);
+        }
+
+        used.insert(HitsoundKey::new(normal, SampleSound::SliderSlide, index));
+        used.insert(HitsoundKey::new(normal, SampleSound::SliderTick, index));
+        if obj.hitsound.whistle() {
+            used.insert(HitsoundKey::new(
+                addition,
+                SampleSound::SliderWhistle,
+                index,
+            ));
+        }
+    }
+}
+
+impl OsuFile {
+    /// Returns the custom samples that the hitobjects play.
+    /// - Sample sets and indexes are resolved from the hitsample, then the timing point at the object's time, then the `SampleSet` in the `[General]` section.
+    /// - All samples of an object use the timing point at its start time.
+    /// - Samples with index `0` are left out, since they use the skin's hitsounds.
+    pub fn used_hitsounds(&self) -> BTreeSet<HitsoundKey> {
+        let general = self.general.as_ref();
+        let resolver = SampleResolver {
+            default_bank: general
+                .and_then(|g| g.sample_set)
+                .map(SampleBank::from_general)
+                .unwrap_or(SampleBank::Normal),
+            mode: self.mode_or_default(),
+        };
+        let timing_points = self
+            .timing_points
+            .as_ref()
+            .map(|t| t.0.as_slice())
+            .unwrap_or_default();
+
+        let mut used = BTreeSet::new();
+
+        for obj in self.hitobjects.iter().flat_map(|hitobjects| &hitobjects.0) {
+            let time = Integer::try_from(obj.time).unwrap_or(Integer::MAX);
+            resolver.object(&mut used, obj, timing_point_at(timing_points, time));
+        }
+
+        used
+    }
+}
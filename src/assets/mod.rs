@@ -0,0 +1,5 @@
+//! Module for auditing the files in a beatmap folder against an .osu file.
+
+pub mod hitsounds;
+
+pub use hitsounds::*;
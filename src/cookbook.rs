@@ -0,0 +1,199 @@
+//! Small building blocks for common tasks, meant to be copied and changed to fit.
+//! - Only available with the `cookbook` feature.
+//! - Each function is tested, so the examples keep working as the API changes.
+
+use std::path::Path;
+
+use rust_decimal::Decimal;
+
+use crate::osu_file::{
+    events::{
+        storyboard::{
+            cmds::{Command, CommandProperties},
+            error::FilePathNotRelative,
+            sprites::{Layer, Object, ObjectType, Origin, OriginType, Sprite},
+            types::Easing,
+        },
+        Event, Events,
+    },
+    timingpoints::{Effects, SampleIndex, SampleSet, TimingPoint, Volume},
+    Error, Extensions, Integer, Millis, OsuFile, ParseError, Position, TimingPoints, Version,
+    LATEST_VERSION,
+};
+
+/// Parses the .osu file and writes it back.
+///
+/// # Example
+/// ```
+/// use osu_file_parser::cookbook::parse_and_write;
+///
+/// let osu_file = "osu file format v14\n\n[General]\nAudioFilename: audio.mp3";
+///
+/// assert!(parse_and_write(osu_file).unwrap().contains("AudioFilename: audio.mp3"));
+/// ```
+pub fn parse_and_write(s: &str) -> Result<String, Error<ParseError>> {
+    let osu_file = s.parse::<OsuFile>()?;
+
+    Ok(osu_file.to_string())
+}
+
+/// Sets the slider velocity multiplier from `time` with an inherited timing point.
+/// - The sample set, volume and effects are copied from the timing point active at `time`.
+/// - Replaces an inherited timing point at the same time, otherwise the new one is added after the timing points at `time`.
+///
+/// # Example
+/// ```
+/// use osu_file_parser::{cookbook::set_slider_velocity, OsuFile};
+/// use rust_decimal_macros::dec;
+///
+/// let mut osu_file = "osu file format v14\n\n[TimingPoints]\n0,500,4,2,0,60,1,0"
+///     .parse::<OsuFile>()
+///     .unwrap();
+///
+/// set_slider_velocity(&mut osu_file, 1000, dec!(2));
+///
+/// let timing_point = &osu_file.timing_points.unwrap().0[1];
+/// assert_eq!(timing_point.calc_slider_velocity_multiplier(), Some(dec!(2)));
+/// ```
+pub fn set_slider_velocity(osu_file: &mut OsuFile, time: Integer, multiplier: Decimal) {
+    let timing_points = &mut osu_file
+        .timing_points_mut()
+        .get_or_insert_with(TimingPoints::default)
+        .0;
+
+    let timing_point = match timing_points.iter().rev().find(|t| t.time <= time) {
+        Some(active) => TimingPoint::new_inherited(
+            time,
+            multiplier,
+            active.meter,
+            active.sample_set,
+            active.sample_index,
+            active.volume,
+            active.effects.unwrap_or(Effects::new(false, false)),
+        ),
+        None => TimingPoint::new_inherited(
+            time,
+            multiplier,
+            4,
+            SampleSet::BeatmapDefault,
+            SampleIndex::OsuDefaultHitsounds,
+            Volume::new(100, LATEST_VERSION).unwrap(),
+            Effects::new(false, false),
+        ),
+    };
+
+    match timing_points
+        .iter()
+        .position(|t| t.time == time && !t.uninherited)
+    {
+        Some(i) => timing_points[i] = timing_point,
+        None => {
+            let i = timing_points.partition_point(|t| t.time <= time);
+            timing_points.insert(i, timing_point);
+        }
+    }
+}
+
+/// Adds a sprite to the storyboard in the `Foreground` layer, fading in at `start_time` and out at `end_time`.
+/// - Fails if `path` isn't relative to the beatmap folder.
+///
+/// # Example
+/// ```
+/// use std::path::Path;
+///
+/// use osu_file_parser::{cookbook::add_sprite, OsuFile, Position};
+///
+/// let mut osu_file = "osu file format v14\n\n[Events]".parse::<OsuFile>().unwrap();
+///
+/// add_sprite(&mut osu_file, Path::new("sb/star.png"), Position::default(), 1000, 2000).unwrap();
+///
+/// let osu_file = osu_file.to_string();
+/// assert!(osu_file.contains("\"sb/star.png\",256,192"));
+/// assert!(osu_file.contains(" F,0,1000,2000,1,0"));
+/// ```
+pub fn add_sprite(
+    osu_file: &mut OsuFile,
+    path: &Path,
+    position: Position,
+    start_time: Integer,
+    end_time: Integer,
+) -> Result<(), FilePathNotRelative> {
+    let fade = Command {
+        start_time: Some(start_time),
+        properties: CommandProperties::Fade {
+            easing: Easing::Linear,
+            end_time: Some(end_time),
+            start_opacity: Decimal::ONE,
+            continuing_opacities: vec![Decimal::ZERO],
+        },
+        indent_char: Default::default(),
+    };
+
+    let sprite = Object {
+        layer: Layer::Foreground,
+        layer_shorthand: false,
+        origin: Origin::from(OriginType::Centre),
+        position,
+        object_type: ObjectType::Sprite(Sprite::new(path)?),
+        commands: vec![fade],
+        extensions: Extensions::new(),
+    };
+
+    osu_file
+        .events_mut()
+        .get_or_insert_with(|| Events(Vec::new()))
+        .0
+        .push(Event::StoryboardObject(sprite));
+
+    Ok(())
+}
+
+/// Writes the .osu file in another file format version.
+/// - When writing version 14, the legacy storyboard sprites and samples are upgraded first.
+/// - Fields that were moved to another section, such as `EditorBookmarks`, are moved with [`OsuFile::migrate_fields`].
+/// - Fields and sections that don't exist in `version` are left out.
+///
+/// # Example
+/// ```
+/// use osu_file_parser::{cookbook::convert_version, OsuFile};
+///
+/// let osu_file = "osu file format v3\n\n[General]\nAudioFilename: audio.mp3"
+///     .parse::<OsuFile>()
+///     .unwrap();
+///
+/// assert!(convert_version(&osu_file, 14).starts_with("osu file format v14"));
+/// ```
+pub fn convert_version(osu_file: &OsuFile, version: Version) -> String {
+    let mut osu_file = osu_file.clone();
+
+    if version >= LATEST_VERSION {
+        if let Some(events) = &mut osu_file.events {
+            events.upgrade_legacy();
+        }
+    }
+    osu_file.migrate_fields(version);
+    osu_file.version = version;
+
+    osu_file.to_string()
+}
+
+/// BPM at `time`, from the uninherited timing point active at that time.
+/// - Returns `None` if there are no uninherited timing points, or the BPM doesn't fit in a [`Decimal`].
+///
+/// # Example
+/// ```
+/// use osu_file_parser::{cookbook::bpm_at, OsuFile};
+/// use rust_decimal_macros::dec;
+///
+/// let osu_file = "osu file format v14\n\n[TimingPoints]\n0,500,4,2,0,60,1,0\n1000,250,4,2,0,60,1,0"
+///     .parse::<OsuFile>()
+///     .unwrap();
+///
+/// assert_eq!(bpm_at(&osu_file, 500), Some(dec!(120)));
+/// assert_eq!(bpm_at(&osu_file, 1500), Some(dec!(240)));
+/// ```
+pub fn bpm_at(osu_file: &OsuFile, time: Integer) -> Option<Decimal> {
+    let context = osu_file.timing_points.as_ref()?.timing_context()?;
+
+    TimingPoint::checked_beat_duration_ms_to_bpm(context.beat_length_at(Millis(time)))
+}
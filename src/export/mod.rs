@@ -0,0 +1,6 @@
+//! Module for exporting the contents of an .osu file into other formats.
+
+pub mod svg;
+pub mod tensor;
+
+pub use svg::svg_layout;
@@ -0,0 +1,79 @@
+//! Module for drawing the layout of the hitobjects as an SVG image, for previews and documentation.
+
+use std::fmt::Write;
+use std::ops::Range;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal_macros::dec;
+
+use crate::{
+    analysis::objects::field,
+    osu_file::{hitobjects::HitObjectParams, OsuFile},
+};
+
+/// Distance between the points of a slider path in osu!pixels.
+const PATH_SPACING: f64 = 4.0;
+
+/// Radius of a spinner drawn in the centre of the playfield.
+const SPINNER_RADIUS: f64 = 160.0;
+
+/// Draws the hitobjects starting inside of `range` on the 512x384 playfield as an SVG document.
+/// - Hitcircles and hold notes are circles, sliders are their path with a circle on the head, and spinners are a circle in the centre of the playfield.
+/// - The circle size comes from `CircleSize`, and stacking isn't applied to the positions.
+/// - Earlier objects are drawn on top of later ones, like in the game.
+/// - Sliders with a path that can't be worked out are drawn as only their head.
+pub fn svg_layout(osu_file: &OsuFile, range: Range<u32>) -> String {
+    let cs = match &osu_file.difficulty {
+        Some(difficulty) => field(&difficulty.circle_size, dec!(5)),
+        None => dec!(5),
+    };
+    let radius = 54.4 - 4.48 * cs.to_f64().unwrap_or(5.0);
+
+    let mut svg = String::from(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 512 384\" width=\"512\" height=\"384\">\n",
+    );
+
+    let objects = osu_file
+        .hitobjects
+        .iter()
+        .flat_map(|h| h.objects_between(range.start, range.end))
+        .collect::<Vec<_>>();
+
+    for obj in objects.into_iter().rev() {
+        let x = obj.position.x.to_f64().unwrap_or_default();
+        let y = obj.position.y.to_f64().unwrap_or_default();
+
+        match &obj.obj_params {
+            HitObjectParams::Spinner { .. } => {
+                let _ = writeln!(
+                    svg,
+                    "<circle class=\"spinner\" cx=\"256\" cy=\"192\" r=\"{SPINNER_RADIUS}\" fill=\"none\" stroke=\"black\"/>"
+                );
+                continue;
+            }
+            HitObjectParams::Slider(params) => {
+                if let Ok(points) = params.path_points(obj.position, PATH_SPACING) {
+                    let points = points
+                        .iter()
+                        .map(|p| format!("{:.2},{:.2}", p.x, p.y))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    let _ = writeln!(
+                        svg,
+                        "<polyline class=\"slider\" points=\"{points}\" fill=\"none\" stroke=\"grey\" stroke-opacity=\"0.5\" stroke-width=\"{:.2}\" stroke-linecap=\"round\" stroke-linejoin=\"round\"/>",
+                        radius * 2.0
+                    );
+                }
+            }
+            _ => (),
+        }
+
+        let _ = writeln!(
+            svg,
+            "<circle class=\"hitobject\" cx=\"{x}\" cy=\"{y}\" r=\"{radius:.2}\" fill=\"white\" stroke=\"black\"/>"
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
@@ -0,0 +1,211 @@
+//! Module for exporting hitobjects and timing points as flat numeric matrices.
+//! - Meant for machine learning pipelines, where each row is a single object and each column is a [`Feature`].
+
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+
+use crate::{
+    analysis::slider::timing_at,
+    osu_file::{hitobjects::HitObjectParams, timingpoints::TimingPoint, OsuFile},
+};
+
+/// Description of a single column in a [`Tensor`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Feature {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+const fn feature(name: &'static str, description: &'static str) -> Feature {
+    Feature { name, description }
+}
+
+/// Columns of [`objects`].
+pub const OBJECT_FEATURES: &[Feature] = &[
+    feature("time", "Start time in milliseconds."),
+    feature("x", "X position in osu!pixels."),
+    feature("y", "Y position in osu!pixels."),
+    feature("is_hitcircle", "1 if the object is a hitcircle."),
+    feature("is_slider", "1 if the object is a slider."),
+    feature("is_spinner", "1 if the object is a spinner."),
+    feature(
+        "is_osu_mania_hold",
+        "1 if the object is an osu!mania hold note.",
+    ),
+    feature("new_combo", "1 if the object starts a new combo."),
+    feature(
+        "duration",
+        "Time from the start to the end in milliseconds, 0 for hitcircles.",
+    ),
+    feature(
+        "slider_length",
+        "Length of the slider in osu!pixels, 0 for other objects.",
+    ),
+    feature(
+        "slider_slides",
+        "Times the slider is travelled over, 0 for other objects.",
+    ),
+    feature(
+        "slider_velocity",
+        "Slider velocity multiplier active at the start time.",
+    ),
+    feature("bpm", "BPM active at the start time."),
+    feature("kiai", "1 if kiai time is active at the start time."),
+];
+
+/// Columns of [`timing_points`].
+pub const TIMING_POINT_FEATURES: &[Feature] = &[
+    feature("time", "Start time in milliseconds."),
+    feature("uninherited", "1 if the timing point is uninherited."),
+    feature("beat_length", "Raw beat length field."),
+    feature(
+        "bpm",
+        "BPM for uninherited timing points, 0 for inherited ones.",
+    ),
+    feature(
+        "slider_velocity",
+        "Slider velocity multiplier for inherited timing points, 1 for uninherited ones.",
+    ),
+    feature("meter", "Beats in a measure."),
+    feature("volume", "Volume percentage."),
+    feature("kiai", "1 if kiai time is enabled."),
+];
+
+/// A row-major matrix of `f32` values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tensor {
+    /// Description of each column.
+    pub features: &'static [Feature],
+    /// Values of all rows one after another.
+    pub data: Vec<f32>,
+}
+
+impl Tensor {
+    /// Number of columns.
+    pub fn columns(&self) -> usize {
+        self.features.len()
+    }
+
+    /// Number of rows.
+    pub fn rows(&self) -> usize {
+        self.data.len() / self.columns()
+    }
+
+    /// Returns the row at `index`.
+    pub fn row(&self, index: usize) -> Option<&[f32]> {
+        self.data.chunks_exact(self.columns()).nth(index)
+    }
+
+    /// Returns the index of the column with the name.
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.features.iter().position(|f| f.name == name)
+    }
+}
+
+fn flag(value: bool) -> f32 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+fn kiai_at(timing_points: &[TimingPoint], time: u32) -> bool {
+    timing_points
+        .iter()
+        .take_while(|t| i64::from(t.time) <= i64::from(time))
+        .last()
+        .and_then(|t| t.effects)
+        .is_some_and(|e| e.kiai_time_enabled())
+}
+
+/// Exports the hitobjects, with one row per object in the order of [`OBJECT_FEATURES`].
+/// - Timing points are expected to be sorted by time.
+pub fn objects(osu_file: &OsuFile) -> Tensor {
+    let timing_points = match &osu_file.timing_points {
+        Some(timing_points) => &timing_points.0[..],
+        None => &[],
+    };
+
+    let data = osu_file
+        .timed_objects()
+        .iter()
+        .flat_map(|timed| {
+            let obj = timed.obj;
+            let (beat_length, slider_velocity) = timing_at(timing_points, obj.time);
+            let (slider_length, slider_slides) = match &obj.obj_params {
+                HitObjectParams::Slider(params) => (
+                    params.length.to_f32().unwrap_or_default(),
+                    params.slides as f32,
+                ),
+                _ => (0.0, 0.0),
+            };
+
+            [
+                obj.time as f32,
+                obj.position.x.to_f32().unwrap_or_default(),
+                obj.position.y.to_f32().unwrap_or_default(),
+                flag(matches!(obj.obj_params, HitObjectParams::HitCircle)),
+                flag(matches!(obj.obj_params, HitObjectParams::Slider(_))),
+                flag(matches!(obj.obj_params, HitObjectParams::Spinner { .. })),
+                flag(matches!(
+                    obj.obj_params,
+                    HitObjectParams::OsuManiaHold { .. }
+                )),
+                flag(obj.new_combo),
+                (timed.end_time() - obj.time as f64) as f32,
+                slider_length,
+                slider_slides,
+                slider_velocity as f32,
+                (60_000.0 / beat_length) as f32,
+                flag(kiai_at(timing_points, obj.time)),
+            ]
+        })
+        .collect();
+
+    Tensor {
+        features: OBJECT_FEATURES,
+        data,
+    }
+}
+
+/// Exports the timing points, with one row per timing point in the order of [`TIMING_POINT_FEATURES`].
+pub fn timing_points(osu_file: &OsuFile) -> Tensor {
+    let timing_points = match &osu_file.timing_points {
+        Some(timing_points) => &timing_points.0[..],
+        None => &[],
+    };
+
+    let data = timing_points
+        .iter()
+        .flat_map(|t| {
+            let bpm = if t.uninherited && t.beat_length > Decimal::ZERO {
+                t.calc_bpm().and_then(|b| b.to_f32()).unwrap_or_default()
+            } else {
+                0.0
+            };
+            let slider_velocity = if t.uninherited || t.beat_length >= Decimal::ZERO {
+                1.0
+            } else {
+                t.calc_slider_velocity_multiplier()
+                    .and_then(|sv| sv.to_f32())
+                    .unwrap_or(1.0)
+            };
+
+            [
+                t.time as f32,
+                flag(t.uninherited),
+                t.beat_length.to_f32().unwrap_or_default(),
+                bpm,
+                slider_velocity,
+                t.meter as f32,
+                t.volume.volume() as f32,
+                flag(t.effects.is_some_and(|e| e.kiai_time_enabled())),
+            ]
+        })
+        .collect();
+
+    Tensor {
+        features: TIMING_POINT_FEATURES,
+        data,
+    }
+}
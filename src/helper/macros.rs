@@ -1,6 +1,11 @@
 macro_rules! versioned_inner {
     ($name:ident, $field_type:ty, $error_from_string:ty, $s_from_string:ident, $version_from_string:ident, $inner_from_string:block) => {
         #[derive(PartialEq, Debug, Clone, Eq, Hash)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(transparent)
+        )]
         pub struct $name($field_type);
 
         impl From<$field_type> for $name {
@@ -15,6 +20,16 @@ macro_rules! versioned_inner {
             }
         }
 
+        impl crate::osu_file::FieldValueType for $name {
+            fn to_field_value(&self) -> crate::osu_file::FieldValue {
+                crate::osu_file::FieldValue::from(self.0.clone())
+            }
+
+            fn from_field_value(value: crate::osu_file::FieldValue) -> Option<Self> {
+                <$field_type>::try_from(value).ok().map($name)
+            }
+        }
+
         impl crate::osu_file::types::VersionedFromStr for $name {
             type Err = $error_from_string;
 
@@ -49,6 +64,31 @@ macro_rules! versioned_field_default {
     };
 }
 
+macro_rules! versioned_field_schema {
+    ($name:ident, $field_type:ty) => {
+        impl crate::schema::DescribeField for $name {
+            fn sample() -> Self {
+                $name(<$field_type>::default())
+            }
+
+            fn default_value(_: crate::osu_file::types::Version) -> Option<Self> {
+                None
+            }
+        }
+    };
+    ($name:ident, $field_type:ty, default) => {
+        impl crate::schema::DescribeField for $name {
+            fn sample() -> Self {
+                $name(<$field_type>::default())
+            }
+
+            fn default_value(version: crate::osu_file::types::Version) -> Option<Self> {
+                <Self as crate::osu_file::types::VersionedDefault>::default(version)
+            }
+        }
+    };
+}
+
 macro_rules! versioned_field {
     // full syntax
     // $name:ident, $field_type:ty, no_versions | (nothing), |$s:ident| $from_str_inner:block -> $parse_str_error:ty, |$v:ident| $to_string_inner:block | boolean, $default:block
@@ -58,12 +98,14 @@ macro_rules! versioned_field {
         });
         versioned_field_to_string!($name, $version_to_string, $v, $to_string_inner);
         versioned_field_default!($name, $version_default, { $default.map(|v| v.into()) });
+        versioned_field_schema!($name, $field_type, default);
     };
     ($name:ident, $field_type:ty, no_versions, |$s:ident| $from_str_inner:block -> $parse_str_error:ty, |$v:ident, $version_to_string:ident| $to_string_inner:block,) => {
         versioned_inner!($name, $field_type, $parse_str_error, $s, _version, {
             $from_str_inner.map(|value| Some(Self(value)))
         });
         versioned_field_to_string!($name, $version_to_string, $v, $to_string_inner);
+        versioned_field_schema!($name, $field_type);
     };
     ($name:ident, $field_type:ty, no_versions, |$s:ident| $from_str_inner:block -> $parse_str_error:ty, |$v:ident| $to_string_inner:block, $default:expr) => {
         versioned_inner!($name, $field_type, $parse_str_error, $s, _version, {
@@ -71,6 +113,7 @@ macro_rules! versioned_field {
         });
         versioned_field_to_string!($name, _version, $v, { Some($to_string_inner) });
         versioned_field_default!($name, _version, { Some($default.into()) });
+        versioned_field_schema!($name, $field_type, default);
     };
     ($name:ident, $field_type:ty, no_versions, |$s:ident| $from_str_inner:block -> $parse_str_error:ty,, $default:expr) => {
         versioned_inner!($name, $field_type, $parse_str_error, $s, _version, {
@@ -78,18 +121,21 @@ macro_rules! versioned_field {
         });
         versioned_field_to_string!($name, _version, v, { Some(v.to_string()) });
         versioned_field_default!($name, _version, { Some($default.into()) });
+        versioned_field_schema!($name, $field_type, default);
     };
     ($name:ident, $field_type:ty, no_versions, |$s:ident| $from_str_inner:block -> $parse_str_error:ty, |$v:ident| $to_string_inner:block,) => {
         versioned_inner!($name, $field_type, $parse_str_error, $s, _version, {
             $from_str_inner.map(|value| Some(Self(value)))
         });
         versioned_field_to_string!($name, _version, $v, { Some($to_string_inner) });
+        versioned_field_schema!($name, $field_type);
     };
     ($name:ident, $field_type:ty, no_versions, |$s:ident| $from_str_inner:block -> $parse_str_error:ty,,) => {
         versioned_inner!($name, $field_type, $parse_str_error, $s, _version, {
             $from_str_inner.map(|value| Some(Self(value)))
         });
         versioned_field_to_string!($name, _version, v, { Some(v.to_string()) });
+        versioned_field_schema!($name, $field_type);
     };
     ($name:ident, $field_type:ty, no_versions, |$s:ident| $from_str_inner:block -> $parse_str_error:ty, boolean, $default:expr) => {
         versioned_inner!($name, $field_type, $parse_str_error, $s, _version, {
@@ -97,12 +143,14 @@ macro_rules! versioned_field {
         });
         versioned_field_to_string!($name, _version, v, { Some((*v as u8).to_string()) });
         versioned_field_default!($name, _version, { Some($default.into()) });
+        versioned_field_schema!($name, $field_type, default);
     };
 }
 
 macro_rules! general_section_inner {
     ($(#[$outer:meta])*, $section_name:ident, $($(#[$inner:meta])*, $field:ident, $field_type:ty)*, $parse_error:ty, $spacing:expr, $default_version:ident, $default_field_name:ident) => {
         #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         $(#[$outer])*
         pub struct $section_name {
             $(
@@ -112,6 +160,14 @@ macro_rules! general_section_inner {
         }
 
         impl $section_name {
+            /// Names of the fields as written in the file, in the order they are written.
+            pub const FIELD_NAMES: &'static [&'static str] = &[$(stringify!($field_type)),*];
+
+            /// Describes the fields in the order they are written, used by [`schema::sections`][crate::schema::sections].
+            pub(crate) fn field_schemas() -> Vec<crate::schema::FieldSchema> {
+                vec![$(crate::schema::FieldSchema::of::<$field_type>(stringify!($field_type))),*]
+            }
+
             /// Creates a new instance, with all fields being `None`.
             pub fn new() -> Self {
                 $section_name {
@@ -119,6 +175,55 @@ macro_rules! general_section_inner {
                 }
             }
 
+            /// Gets a field by the name written in the file, such as `AudioFilename`.
+            /// - Returns `None` if the field isn't set or there's no field with the name.
+            pub fn get(&self, name: &str) -> Option<crate::osu_file::FieldValue> {
+                match name {
+                    $(
+                        stringify!($field_type) => self.$field.as_ref().map(crate::osu_file::FieldValueType::to_field_value),
+                    )*
+                    _ => None,
+                }
+            }
+
+            /// Sets a field by the name written in the file, such as `AudioFilename`.
+            pub fn set(&mut self, name: &str, value: crate::osu_file::FieldValue) -> Result<(), crate::osu_file::SetFieldError> {
+                match name {
+                    $(
+                        stringify!($field_type) => {
+                            let value = <$field_type as crate::osu_file::FieldValueType>::from_field_value(value)
+                                .ok_or_else(|| crate::osu_file::SetFieldError::InvalidValue(name.to_string()))?;
+                            self.$field = Some(value);
+                            Ok(())
+                        }
+                    )*
+                    _ => Err(crate::osu_file::SetFieldError::UnknownField(name.to_string())),
+                }
+            }
+
+            /// Unsets a field by the name written in the file, returning its value.
+            /// - Returns `None` if the field isn't set or there's no field with the name.
+            pub fn remove(&mut self, name: &str) -> Option<crate::osu_file::FieldValue> {
+                let value = self.get(name)?;
+
+                match name {
+                    $(
+                        stringify!($field_type) => self.$field = None,
+                    )*
+                    _ => (),
+                }
+
+                Some(value)
+            }
+
+            /// Names and values of the fields that are set, in the order they are written.
+            pub fn fields(&self) -> Vec<(&'static str, crate::osu_file::FieldValue)> {
+                Self::FIELD_NAMES
+                    .iter()
+                    .filter_map(|name| Some((*name, self.get(name)?)))
+                    .collect()
+            }
+
             pub fn from_str(s: &str, version: crate::osu_file::types::Version) -> Result<Option<$section_name>, crate::osu_file::types::Error<$parse_error>> {
                 let mut section = $section_name::new();
 
@@ -134,18 +239,23 @@ macro_rules! general_section_inner {
                 let mut line_count = 0;
                 let mut parsed_fields = Vec::new();
 
-                for (name, _, value, ws_2) in fields {
+                for (name, ws_1, value, ws_2) in fields {
+                    let name_column = 0..name.chars().count();
+                    let value_start = name_column.end + 1 + ws_1.chars().count();
+                    let value_column = value_start..value_start + value.trim_end().chars().count();
+
                     if parsed_fields.contains(&name) {
-                        return Err(crate::osu_file::types::Error::new(ParseError::DuplicateField, line_count));
+                        return Err(crate::osu_file::types::Error::new(ParseError::DuplicateField, line_count).with_column(name_column));
                     }
 
                     match name {
                         $(
                             stringify!($field_type) => {
-                                section.$field = crate::osu_file::types::Error::new_from_result_into(<$field_type as crate::osu_file::types::VersionedFromStr>::from_str(value, version), line_count)?;
+                                section.$field = crate::osu_file::types::Error::new_from_result_into(<$field_type as crate::osu_file::types::VersionedFromStr>::from_str(value, version), line_count)
+                                    .map_err(|err| err.with_column(value_column))?;
                             }
                         )*
-                        _ => return Err(crate::osu_file::types::Error::new(ParseError::InvalidKey, line_count)),
+                        _ => return Err(crate::osu_file::types::Error::new(ParseError::InvalidKey, line_count).with_column(name_column)),
                     }
 
                     line_count += ws_2.lines().count();
@@ -241,8 +351,11 @@ macro_rules! verbose_error_to_error {
                     nom::Err::Error(err) | nom::Err::Failure(err) => {
                         for (_, err) in err.errors {
                             if let nom::error::VerboseErrorKind::Context(context) = err {
-                                return <$error_type as std::str::FromStr>::from_str(context)
-                                    .unwrap();
+                                if let Ok(err) =
+                                    <$error_type as std::str::FromStr>::from_str(context)
+                                {
+                                    return err;
+                                }
                             }
                         }
 
@@ -254,6 +367,29 @@ macro_rules! verbose_error_to_error {
             }
         }
     };
+    // used when the parser can fail without a context, such as fuzzed input
+    ($error_type:ty, $fallback:expr) => {
+        impl From<nom::Err<nom::error::VerboseError<&str>>> for $error_type {
+            fn from(err: nom::Err<nom::error::VerboseError<&str>>) -> Self {
+                match err {
+                    nom::Err::Error(err) | nom::Err::Failure(err) => {
+                        for (_, err) in err.errors {
+                            if let nom::error::VerboseErrorKind::Context(context) = err {
+                                if let Ok(err) =
+                                    <$error_type as std::str::FromStr>::from_str(context)
+                                {
+                                    return err;
+                                }
+                            }
+                        }
+
+                        $fallback
+                    }
+                    nom::Err::Incomplete(_) => $fallback,
+                }
+            }
+        }
+    };
 }
 
 macro_rules! unreachable_err_impl {
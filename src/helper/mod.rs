@@ -10,7 +10,7 @@ pub const OLD_VERSION_TIME_OFFSET: u32 = 24;
 
 pub fn add_old_version_time_offset(t: u32, version: Version) -> u32 {
     if (3..=4).contains(&version) {
-        t + OLD_VERSION_TIME_OFFSET
+        t.saturating_add(OLD_VERSION_TIME_OFFSET)
     } else {
         t
     }
@@ -26,6 +26,101 @@ where
         .join("|")
 }
 
+/// Trims the whitespace around each field of every line.
+/// - Fields are separated by `,`, `|` and `:`.
+/// - Line count is kept the same so line indexes in errors stay correct.
+pub fn trim_fields(s: &str) -> String {
+    let mut trimmed = String::with_capacity(s.len());
+    trim_fields_into(s, &mut trimmed);
+    trimmed
+}
+
+/// Same as [`trim_fields`], but writes into `trimmed` after clearing it, so its capacity can be reused.
+pub fn trim_fields_into(s: &str, trimmed: &mut String) {
+    trimmed.clear();
+
+    for (line_index, line) in s.lines().enumerate() {
+        if line_index > 0 {
+            trimmed.push('\n');
+        }
+
+        push_trimmed_fields(line, trimmed);
+    }
+}
+
+/// Same as [`trim_fields_into`] for the `[HitObjects]` section, which keeps the hitsample file name of each line as it is.
+/// - File names can start or end with spaces, so only the fields before them are trimmed.
+/// - File names that are only whitespace are the spacing at the end of the line, and are removed.
+pub(crate) fn trim_hitobject_fields_into(s: &str, trimmed: &mut String) {
+    trimmed.clear();
+
+    for (line_index, line) in s.lines().enumerate() {
+        if line_index > 0 {
+            trimmed.push('\n');
+        }
+
+        match hitsample_filename_start(line) {
+            Some(start) => {
+                push_trimmed_fields(&line[..start], trimmed);
+                let filename = &line[start..];
+                if !filename.trim().is_empty() {
+                    trimmed.push_str(filename);
+                }
+            }
+            None => push_trimmed_fields(line, trimmed),
+        }
+    }
+}
+
+fn push_trimmed_fields(line: &str, trimmed: &mut String) {
+    let mut field_start = 0;
+    for (i, c) in line.char_indices() {
+        if matches!(c, ',' | '|' | ':') {
+            trimmed.push_str(line[field_start..i].trim());
+            trimmed.push(c);
+            field_start = i + 1;
+        }
+    }
+    trimmed.push_str(line[field_start..].trim());
+}
+
+/// Byte index where the hitsample file name of the hitobject `line` starts, if the line ends with a hitsample.
+fn hitsample_filename_start(line: &str) -> Option<usize> {
+    let obj_type = line.split(',').nth(3)?.trim().parse::<u32>().ok()?;
+    // osu!mania holds have their end time before the hitsample
+    let colons = if obj_type & 128 != 0 { 5 } else { 4 };
+
+    let last_field = line.rfind(',')? + 1;
+    // slider edge sets are separated by `|`, and are last when the hitsample is left out
+    if line[last_field..].contains('|') {
+        return None;
+    }
+
+    line[last_field..]
+        .match_indices(':')
+        .nth(colons - 1)
+        .map(|(i, _)| last_field + i + 1)
+}
+
+/// Writes `s` as a JSON string, with the quotes around it.
+pub(crate) fn write_json_string(json: &mut String, s: &str) {
+    json.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+}
+
 pub fn check_flag_at_bit_u8(value: u8, nth_bit: u8) -> bool {
     value >> nth_bit & 1 == 1
 }
@@ -0,0 +1,73 @@
+//! Module for generating hitobjects from a list of MIDI notes.
+
+use std::collections::HashSet;
+
+use super::{Builder, ImportOptions};
+use crate::osu_file::{general::Mode, hitobjects::HitObjects, timingpoints::TimingContext, Millis};
+
+/// Velocity from which a note is treated as an accent, giving it a finish hitsound.
+const ACCENT_VELOCITY: u8 = 112;
+
+/// Key of middle C, notes from it up are kats in osu!taiko.
+const MIDDLE_C: u8 = 60;
+
+/// A MIDI note with its times already converted from ticks.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct MidiNote {
+    pub start: Millis,
+    pub end: Millis,
+    /// Key of the note from `0` to `127`.
+    pub key: u8,
+    /// Velocity of the note from `0` to `127`.
+    pub velocity: u8,
+}
+
+/// Generates a hitobject for each note, snapped to the beat.
+/// - osu!mania uses the key for the column and makes holds for notes that last at least one beat snap.
+/// - osu!taiko makes notes from middle C up into kats, using a whistle hitsound.
+/// - Accented notes get a finish hitsound.
+/// - Notes that snap to the same time, or the same time and column in osu!mania, become a single object.
+/// - Notes that snap to a time that doesn't fit in [`Millis`] are skipped.
+pub fn hitobjects_from_midi(
+    notes: &[MidiNote],
+    timing: &TimingContext,
+    options: &ImportOptions,
+) -> HitObjects {
+    let mut builder = Builder::new(timing, options);
+    let key_count = options.key_count.max(1);
+
+    let mut notes = notes
+        .iter()
+        .filter_map(|note| {
+            Some(MidiNote {
+                start: builder.snap(note.start)?,
+                end: builder.snap(note.end)?,
+                ..*note
+            })
+        })
+        .collect::<Vec<_>>();
+    notes.sort_by_key(|note| note.start);
+
+    let mut placed = HashSet::new();
+
+    for note in notes {
+        let column = match options.mode {
+            Mode::Mania => note.key % key_count,
+            _ => 0,
+        };
+
+        if !placed.insert((note.start, column)) {
+            continue;
+        }
+
+        if let Some(obj) = builder.push(note.start, Some(note.end), column) {
+            obj.hitsound.set_finish(note.velocity >= ACCENT_VELOCITY);
+
+            if options.mode == Mode::Taiko {
+                obj.hitsound.set_whistle(note.key >= MIDDLE_C);
+            }
+        }
+    }
+
+    HitObjects(builder.finish())
+}
@@ -0,0 +1,140 @@
+//! Module for generating hitobjects from timestamps, as a starting point for auto-mapping.
+//! - Times are snapped to the beat using the [`TimingContext`][crate::osu_file::timingpoints::TimingContext] of the beatmap.
+//! - The `midi` feature adds [`hitobjects_from_midi`] for a list of notes.
+
+#[cfg(feature = "midi")]
+pub mod midi;
+pub mod onsets;
+
+#[cfg(feature = "midi")]
+pub use midi::*;
+pub use onsets::*;
+
+use crate::osu_file::{
+    general::Mode,
+    hitobjects::{HitObject, HitObjectParams},
+    timingpoints::TimingContext,
+    Beats, Integer, Millis, Position,
+};
+
+/// Options used when generating hitobjects.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ImportOptions {
+    /// Game mode the hitobjects are made for.
+    pub mode: Mode,
+    /// Beat snap divisor the times are snapped to, where `1` is every beat and `4` is every quarter beat.
+    pub beat_snap: u32,
+    /// Number of columns used for osu!mania.
+    pub key_count: u8,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            mode: Mode::Osu,
+            beat_snap: 4,
+            key_count: 4,
+        }
+    }
+}
+
+impl ImportOptions {
+    /// Creates the default options, which is osu! with a beat snap of `4`.
+    pub fn new(mode: Mode) -> Self {
+        Self {
+            mode,
+            ..Default::default()
+        }
+    }
+
+    /// Sets [`beat_snap`][Self::beat_snap].
+    pub fn beat_snap(mut self, beat_snap: u32) -> Self {
+        self.beat_snap = beat_snap;
+        self
+    }
+
+    /// Sets [`key_count`][Self::key_count].
+    pub fn key_count(mut self, key_count: u8) -> Self {
+        self.key_count = key_count;
+        self
+    }
+}
+
+/// Number of beats in a measure, used to start a new combo.
+const BEATS_PER_COMBO: i64 = 4;
+
+/// Builds the hitobjects from the snapped times, with the column used for osu!mania.
+/// - Objects in osu!, osu!taiko and osu!catch are placed in the middle of the playfield, with a new combo every measure.
+/// - osu!mania objects become holds if they have an end time after the start time.
+pub(crate) struct Builder<'a> {
+    timing: &'a TimingContext,
+    options: &'a ImportOptions,
+    last_measure: Option<i64>,
+    hitobjects: Vec<HitObject>,
+}
+
+impl<'a> Builder<'a> {
+    pub(crate) fn new(timing: &'a TimingContext, options: &'a ImportOptions) -> Self {
+        Self {
+            timing,
+            options,
+            last_measure: None,
+            hitobjects: Vec::new(),
+        }
+    }
+
+    /// Snaps the time to the beat snap, returning `None` if it doesn't fit in [`Millis`].
+    pub(crate) fn snap(&self, time: Millis) -> Option<Millis> {
+        self.timing.snap(time, self.options.beat_snap)
+    }
+
+    /// Pushes the object, returning the object if it was made.
+    /// - Times before `0` are skipped.
+    pub(crate) fn push(
+        &mut self,
+        time: Millis,
+        end_time: Option<Millis>,
+        column: u8,
+    ) -> Option<&mut HitObject> {
+        let start = u32::try_from(time.0).ok()?;
+        let mut obj = HitObject::hitcircle_default();
+        obj.time = start;
+
+        if self.options.mode == Mode::Mania {
+            let key_count = Integer::from(self.options.key_count.max(1));
+            let column = Integer::from(column) % key_count;
+
+            // middle of the column
+            obj.position = Position::from(Position::<Integer> {
+                x: 512 * (2 * column + 1) / (2 * key_count),
+                y: 192,
+            });
+
+            if let Some(end) = end_time
+                .filter(|end| *end > time)
+                .and_then(|end| u32::try_from(end.0).ok())
+            {
+                obj.obj_params = HitObjectParams::OsuManiaHold { end_time: end };
+            }
+        } else {
+            obj.position = Position::from(Position::<Integer> { x: 256, y: 192 });
+
+            let Beats(beats) = self.timing.to_beats(time);
+            let measure = (beats / rust_decimal::Decimal::from(BEATS_PER_COMBO))
+                .floor()
+                .try_into()
+                .unwrap_or_default();
+
+            obj.new_combo = self.last_measure != Some(measure);
+            self.last_measure = Some(measure);
+        }
+
+        self.hitobjects.push(obj);
+        self.hitobjects.last_mut()
+    }
+
+    pub(crate) fn finish(self) -> Vec<HitObject> {
+        self.hitobjects
+    }
+}
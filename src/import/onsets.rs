@@ -0,0 +1,34 @@
+//! Module for generating hitobjects from onset timestamps, such as the output of an onset detector.
+
+use super::{Builder, ImportOptions};
+use crate::osu_file::{general::Mode, hitobjects::HitObjects, timingpoints::TimingContext, Millis};
+
+/// Generates a hitobject for each onset, snapped to the beat.
+/// - Onsets that snap to the same time become a single object.
+/// - osu!mania objects go through the columns from left to right.
+/// - Onsets before `0`, or that snap to a time that doesn't fit in [`Millis`], are skipped.
+pub fn hitobjects_from_onsets(
+    onsets: &[Millis],
+    timing: &TimingContext,
+    options: &ImportOptions,
+) -> HitObjects {
+    let mut builder = Builder::new(timing, options);
+
+    let mut times = onsets
+        .iter()
+        .filter_map(|t| builder.snap(*t))
+        .collect::<Vec<_>>();
+    times.sort();
+    times.dedup();
+
+    for (i, time) in times.into_iter().enumerate() {
+        let column = match options.mode {
+            Mode::Mania => (i % usize::from(options.key_count.max(1))) as u8,
+            _ => 0,
+        };
+
+        builder.push(time, None, column);
+    }
+
+    HitObjects(builder.finish())
+}
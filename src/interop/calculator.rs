@@ -0,0 +1,250 @@
+//! Module for converting beatmaps into the types of the `rosu-pp` difficulty and performance calculator.
+//! - Only available with the `rosu-pp` feature.
+//! - Times are converted as they are, since they already include the offset of old versions from parsing.
+
+use rosu_pp::model::{
+    beatmap::BreakPeriod,
+    control_point::{DifficultyPoint, EffectPoint, TimingPoint as ControlTimingPoint},
+    hit_object::{
+        HitObject as CalculatorHitObject, HitObjectKind, HitSoundType, HoldNote, PathControlPoint,
+        PathType, Pos, Slider, Spinner,
+    },
+    mode::GameMode,
+};
+use rosu_pp::Beatmap;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use rust_decimal_macros::dec;
+use thiserror::Error;
+
+use crate::analysis::objects::field;
+use crate::osu_file::{
+    events::Event,
+    general::Mode,
+    hitobjects::{CurveType, HitObject, HitObjectParams, HitSound},
+    timingpoints::TimingPoint,
+    OsuFile, Position,
+};
+
+/// Error when converting a [`TimingPoint`] into a `rosu-pp` timing point.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ControlPointError {
+    /// Only uninherited timing points set the BPM, inherited ones are [`DifficultyPoint`]s.
+    #[error("Inherited timing points don't set the BPM")]
+    Inherited,
+}
+
+/// Error when converting a [`Mode`] that isn't one of the known gamemodes into a `rosu-pp` [`GameMode`].
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+#[error("Mode `{0}` isn't one of the gamemodes of `rosu-pp`")]
+pub struct UnknownModeError(pub u8);
+
+fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or_default()
+}
+
+fn to_f32(value: Decimal) -> f32 {
+    value.to_f32().unwrap_or_default()
+}
+
+fn to_pos(position: &Position) -> Pos {
+    Pos::new(to_f32(position.x), to_f32(position.y))
+}
+
+fn path_type(curve_type: CurveType) -> PathType {
+    match curve_type {
+        CurveType::Bezier => PathType::BEZIER,
+        CurveType::Centripetal => PathType::CATMULL,
+        CurveType::Linear => PathType::LINEAR,
+        CurveType::PerfectCircle => PathType::PERFECT_CURVE,
+    }
+}
+
+impl From<HitSound> for HitSoundType {
+    fn from(hitsound: HitSound) -> Self {
+        let bits = u8::from(hitsound.normal())
+            | u8::from(hitsound.whistle()) << 1
+            | u8::from(hitsound.finish()) << 2
+            | u8::from(hitsound.clap()) << 3;
+
+        HitSoundType::from(bits)
+    }
+}
+
+impl TryFrom<Mode> for GameMode {
+    type Error = UnknownModeError;
+
+    /// Fails for [`Mode::Other`], which `rosu-pp` can't calculate.
+    fn try_from(mode: Mode) -> Result<Self, Self::Error> {
+        match mode {
+            Mode::Osu => Ok(GameMode::Osu),
+            Mode::Taiko => Ok(GameMode::Taiko),
+            Mode::Catch => Ok(GameMode::Catch),
+            Mode::Mania => Ok(GameMode::Mania),
+            Mode::Other(mode) => Err(UnknownModeError(mode)),
+        }
+    }
+}
+
+impl TryFrom<&TimingPoint> for ControlTimingPoint {
+    type Error = ControlPointError;
+
+    /// Fails if the timing point is inherited.
+    fn try_from(timing_point: &TimingPoint) -> Result<Self, Self::Error> {
+        if !timing_point.uninherited {
+            return Err(ControlPointError::Inherited);
+        }
+
+        Ok(ControlTimingPoint::new(
+            f64::from(timing_point.time),
+            to_f64(timing_point.beat_length),
+        ))
+    }
+}
+
+impl From<&TimingPoint> for DifficultyPoint {
+    /// Uninherited timing points reset the slider velocity to `1`, like the game does.
+    fn from(timing_point: &TimingPoint) -> Self {
+        let beat_length = to_f64(timing_point.beat_length);
+        let speed_multiplier = if timing_point.uninherited || beat_length >= 0.0 {
+            1.0
+        } else {
+            100.0 / -beat_length
+        };
+
+        DifficultyPoint::new(f64::from(timing_point.time), beat_length, speed_multiplier)
+    }
+}
+
+impl From<&TimingPoint> for EffectPoint {
+    fn from(timing_point: &TimingPoint) -> Self {
+        let kiai = timing_point
+            .effects
+            .as_ref()
+            .is_some_and(|effects| effects.kiai_time_enabled());
+
+        EffectPoint::new(f64::from(timing_point.time), kiai)
+    }
+}
+
+impl From<&HitObject> for CalculatorHitObject {
+    /// Control points of sliders are relative to the position of the slider, starting with the head.
+    fn from(obj: &HitObject) -> Self {
+        let start_time = f64::from(obj.time);
+        let duration = |end_time: u32| f64::from(end_time.saturating_sub(obj.time));
+
+        let kind = match &obj.obj_params {
+            HitObjectParams::HitCircle => HitObjectKind::Circle,
+            HitObjectParams::Slider(params) => {
+                let head = PathControlPoint {
+                    pos: Pos::default(),
+                    path_type: Some(path_type(params.curve_type)),
+                };
+                let control_points = std::iter::once(head)
+                    .chain(params.curve_points.iter().map(|point| PathControlPoint {
+                        pos: to_pos(&Position::new(
+                            point.0.x - obj.position.x,
+                            point.0.y - obj.position.y,
+                        )),
+                        path_type: None,
+                    }))
+                    .collect();
+                let node_sounds = params
+                    .node_samples()
+                    .into_iter()
+                    .map(|sample| HitSoundType::from(sample.hitsound))
+                    .collect();
+
+                HitObjectKind::Slider(Slider {
+                    expected_dist: Some(to_f64(params.length)),
+                    repeats: params.slides.max(1) as usize - 1,
+                    control_points,
+                    node_sounds,
+                })
+            }
+            HitObjectParams::Spinner { end_time } => HitObjectKind::Spinner(Spinner {
+                duration: duration(*end_time),
+            }),
+            HitObjectParams::OsuManiaHold { end_time } => HitObjectKind::Hold(HoldNote {
+                duration: duration(*end_time),
+            }),
+        };
+
+        CalculatorHitObject {
+            pos: to_pos(&obj.position),
+            start_time,
+            kind,
+        }
+    }
+}
+
+impl From<&OsuFile> for Beatmap {
+    /// Missing fields use the defaults of the game, and a missing `ApproachRate` uses the `OverallDifficulty` like old versions did.
+    /// - Modes that aren't known, kept with [`ParseOptions::lenient`][crate::osu_file::ParseOptions::lenient], are converted as osu!standard like the game does.
+    fn from(osu_file: &OsuFile) -> Self {
+        let (hp, cs, od, slider_multiplier, slider_tick_rate) = match &osu_file.difficulty {
+            Some(difficulty) => (
+                field(&difficulty.hp_drain_rate, dec!(5)),
+                field(&difficulty.circle_size, dec!(5)),
+                field(&difficulty.overall_difficulty, dec!(5)),
+                field(&difficulty.slider_multiplier, dec!(1.4)),
+                field(&difficulty.slider_tickrate, dec!(1)),
+            ),
+            None => (dec!(5), dec!(5), dec!(5), dec!(1.4), dec!(1)),
+        };
+        let ar = match &osu_file.difficulty {
+            Some(difficulty) => field(&difficulty.approach_rate, od),
+            None => od,
+        };
+        let stack_leniency = match &osu_file.general {
+            Some(general) => field(&general.stack_leniency, dec!(0.7)),
+            None => dec!(0.7),
+        };
+
+        let timing_points = osu_file
+            .timing_points
+            .as_ref()
+            .map_or(&[][..], |timing_points| &timing_points.0[..]);
+        let hitobjects = osu_file
+            .hitobjects
+            .as_ref()
+            .map_or(&[][..], |hitobjects| &hitobjects.0[..]);
+        let breaks = osu_file
+            .events
+            .iter()
+            .flat_map(|events| &events.0)
+            .filter_map(|event| match event {
+                Event::Break(b) => Some(BreakPeriod {
+                    start_time: f64::from(b.start_time),
+                    end_time: f64::from(b.end_time),
+                }),
+                _ => None,
+            })
+            .collect();
+
+        Beatmap {
+            version: osu_file.version as i32,
+            is_convert: false,
+            stack_leniency: to_f32(stack_leniency),
+            mode: GameMode::try_from(osu_file.mode_or_default()).unwrap_or(GameMode::Osu),
+            ar: to_f32(ar),
+            cs: to_f32(cs),
+            hp: to_f32(hp),
+            od: to_f32(od),
+            slider_multiplier: to_f64(slider_multiplier),
+            slider_tick_rate: to_f64(slider_tick_rate),
+            breaks,
+            timing_points: timing_points
+                .iter()
+                .filter_map(|t| ControlTimingPoint::try_from(t).ok())
+                .collect(),
+            difficulty_points: timing_points.iter().map(DifficultyPoint::from).collect(),
+            effect_points: timing_points.iter().map(EffectPoint::from).collect(),
+            hit_objects: hitobjects.iter().map(CalculatorHitObject::from).collect(),
+            hit_sounds: hitobjects
+                .iter()
+                .map(|obj| HitSoundType::from(obj.hitsound))
+                .collect(),
+        }
+    }
+}
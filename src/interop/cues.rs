@@ -0,0 +1,88 @@
+//! Module for converting editor bookmarks to and from cue formats used by audio editors.
+//! - Audacity labels are lines of `start<TAB>end<TAB>label`, with the times in seconds.
+//! - CSV is a list of times in seconds, separated by commas or new lines.
+//! - Bookmarks are in milliseconds, so times are rounded to the nearest millisecond when they are read.
+
+use std::str::FromStr;
+
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use strum_macros::IntoStaticStr;
+use thiserror::Error;
+
+use crate::osu_file::{Error, Integer};
+
+/// Error used when a cue file can't be read.
+#[derive(Debug, Error, IntoStaticStr)]
+#[non_exhaustive]
+pub enum ParseCueError {
+    /// The time isn't a number of seconds.
+    #[error("Invalid time `{0}`, expected a number of seconds")]
+    InvalidTime(String),
+    /// The time is too large to be a bookmark.
+    #[error("The time `{0}` is out of range")]
+    TimeOutOfRange(String),
+}
+
+fn seconds_to_millis(seconds: &str) -> Result<Integer, ParseCueError> {
+    let seconds = seconds.trim();
+    let parsed = Decimal::from_str(seconds)
+        .or_else(|_| Decimal::from_scientific(seconds))
+        .map_err(|_| ParseCueError::InvalidTime(seconds.to_string()))?;
+
+    parsed
+        .checked_mul(Decimal::ONE_THOUSAND)
+        .and_then(|millis| millis.round().to_i32())
+        .ok_or_else(|| ParseCueError::TimeOutOfRange(seconds.to_string()))
+}
+
+fn millis_to_seconds(millis: Integer) -> String {
+    format!("{:.6}", Decimal::new(millis as i64, 3))
+}
+
+/// Writes the bookmarks as Audacity point labels, with the label being the bookmark number starting from `1`.
+pub fn bookmarks_to_audacity_labels(bookmarks: &[Integer]) -> String {
+    bookmarks
+        .iter()
+        .enumerate()
+        .map(|(i, bookmark)| {
+            let time = millis_to_seconds(*bookmark);
+            format!("{time}\t{time}\t{}", i + 1)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Reads the start times of Audacity labels as bookmarks, ignoring the end times and labels.
+/// - Lines starting with `\`, which Audacity uses for the frequency range of a label, are skipped.
+/// - The line index of the error is the line of the label.
+pub fn bookmarks_from_audacity_labels(s: &str) -> Result<Vec<Integer>, Error<ParseCueError>> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('\\'))
+        .map(|(line_index, line)| {
+            let start = line.split('\t').next().unwrap_or_default();
+            Error::new_from_result(seconds_to_millis(start), line_index)
+        })
+        .collect()
+}
+
+/// Writes the bookmarks as seconds, one on each line.
+pub fn bookmarks_to_csv(bookmarks: &[Integer]) -> String {
+    bookmarks
+        .iter()
+        .map(|bookmark| millis_to_seconds(*bookmark))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Reads bookmarks from seconds separated by commas or new lines.
+/// - Empty values are skipped, so trailing commas are allowed.
+/// - The line index of the error is the line of the value.
+pub fn bookmarks_from_csv(s: &str) -> Result<Vec<Integer>, Error<ParseCueError>> {
+    s.lines()
+        .enumerate()
+        .flat_map(|(line_index, line)| line.split(',').map(move |value| (line_index, value)))
+        .filter(|(_, value)| !value.trim().is_empty())
+        .map(|(line_index, value)| Error::new_from_result(seconds_to_millis(value), line_index))
+        .collect()
+}
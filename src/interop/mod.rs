@@ -0,0 +1,9 @@
+//! Module for exchanging data with tools outside of osu!, such as audio editors and calculators.
+
+#[cfg(feature = "rosu-pp")]
+pub mod calculator;
+pub mod cues;
+
+#[cfg(feature = "rosu-pp")]
+pub use calculator::*;
+pub use cues::*;
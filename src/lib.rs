@@ -30,11 +30,25 @@
 //! - The error type is wrapped in [`Error`] in those cases.
 //! - [`Error`] has methods that tells you where the error happened in the input string and what the error was.
 
+#[cfg(test)]
+extern crate self as osu_file_parser;
 #[cfg(test)]
 mod tests;
 
+pub mod analysis;
+pub mod assets;
+#[cfg(feature = "cookbook")]
+pub mod cookbook;
+pub mod export;
 mod helper;
+pub mod import;
+pub mod interop;
+pub mod lint;
+pub mod mapset;
 pub mod osu_file;
+pub mod schema;
+pub mod simulation;
+pub mod transform;
 pub use osu_file::*;
 mod parsers;
 
@@ -141,3 +155,32 @@ pub fn assert_eq_osu_str<L: AsRef<str>, R: AsRef<str>>(left: L, right: R) {
 
     pretty_assertions::assert_eq!(left, right)
 }
+
+/// Asserts that two values are equal with [`ApproxEq`], allowing decimals to differ by an epsilon.
+/// - The epsilon is [`DEFAULT_EPSILON`] if it isn't given.
+///
+/// ```
+/// use osu_file_parser::*;
+/// use rust_decimal_macros::dec;
+///
+/// assert_osu_eq!(dec!(0.3333333), dec!(0.3333334));
+/// assert_osu_eq!(dec!(1.2), dec!(1.25), dec!(0.1));
+/// ```
+#[macro_export]
+macro_rules! assert_osu_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_osu_eq!($left, $right, $crate::osu_file::DEFAULT_EPSILON)
+    };
+    ($left:expr, $right:expr, $epsilon:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !$crate::osu_file::ApproxEq::approx_eq(left, right, $epsilon) {
+                    panic!(
+                        "assertion failed: `left.approx_eq(right, {})`\n  left: `{:?}`\n right: `{:?}`",
+                        $epsilon, left, right
+                    );
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,50 @@
+//! Module for linting the fields of the `[Editor]` section.
+
+use rust_decimal::Decimal;
+
+use super::{general::issue, LintIssue, LintRule};
+use crate::osu_file::{editor::DistanceSpacing, Integer, OsuFile};
+
+impl OsuFile {
+    /// Lints the fields of the `[Editor]` section.
+    /// - Checks that `GridSize` is one of the [`KNOWN`][crate::osu_file::editor::GridSize::KNOWN] sizes.
+    /// - Checks that `DistanceSpacing` is within the [`RANGE`][DistanceSpacing::RANGE] the editor allows.
+    pub fn lint_editor(&self) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+
+        let editor = match &self.editor {
+            Some(editor) => editor,
+            None => return issues,
+        };
+
+        if let Some(grid_size) = editor.grid_size.as_ref().filter(|g| !g.is_known()) {
+            issues.push(issue(
+                LintRule::UnknownGridSize,
+                "GridSize",
+                format!(
+                    "`GridSize` is {}, which the editor can't be set to",
+                    Integer::from(grid_size.clone())
+                ),
+            ));
+        }
+
+        if let Some(spacing) = editor
+            .distance_spacing
+            .as_ref()
+            .filter(|d| !d.is_in_range())
+        {
+            issues.push(issue(
+                LintRule::DistanceSpacingOutOfRange,
+                "DistanceSpacing",
+                format!(
+                    "`DistanceSpacing` is {}, outside of {} to {}",
+                    Decimal::from(spacing.clone()),
+                    DistanceSpacing::RANGE.start(),
+                    DistanceSpacing::RANGE.end()
+                ),
+            ));
+        }
+
+        issues
+    }
+}
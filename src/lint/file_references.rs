@@ -0,0 +1,344 @@
+//! Module for linting the files referenced by a beatmap and its storyboard.
+
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::{FileReference, FileReferenceKind, LintIssue, LintRule, LintTarget};
+use crate::osu_file::{
+    events::{storyboard::sprites::ObjectType, Event, Events},
+    OsuFile,
+};
+
+/// Characters that aren't allowed in Windows file names.
+const ILLEGAL_CHARACTERS: &[char] = &['<', '>', ':', '"', '|', '?', '*'];
+
+/// Lists the files in a beatmap folder, used to check if the referenced files exist.
+pub trait AssetProber {
+    /// Paths of all files in the beatmap folder, relative to the folder.
+    fn files(&self) -> Vec<PathBuf>;
+
+    /// Width and height in pixels of the image at `path`, relative to the folder.
+    /// - Returns `None` if the size isn't known, which is the default.
+    fn image_size(&self, _path: &Path) -> Option<(u32, u32)> {
+        None
+    }
+}
+
+impl AssetProber for [PathBuf] {
+    fn files(&self) -> Vec<PathBuf> {
+        self.to_vec()
+    }
+}
+
+impl AssetProber for Vec<PathBuf> {
+    fn files(&self) -> Vec<PathBuf> {
+        self.clone()
+    }
+}
+
+impl AssetProber for HashMap<PathBuf, (u32, u32)> {
+    fn files(&self) -> Vec<PathBuf> {
+        self.keys().cloned().collect()
+    }
+
+    /// Looks up the size of the path as it is written.
+    fn image_size(&self, path: &Path) -> Option<(u32, u32)> {
+        self.get(path).copied()
+    }
+}
+
+/// [`AssetProber`] that lists the files of a directory on the filesystem, including the subdirectories.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct DirectoryProber {
+    pub root: PathBuf,
+}
+
+impl DirectoryProber {
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_owned(),
+        }
+    }
+
+    fn walk(&self, dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+
+            if path.is_dir() {
+                self.walk(&path, files)?;
+            } else if let Ok(relative) = path.strip_prefix(&self.root) {
+                files.push(relative.to_owned());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl AssetProber for DirectoryProber {
+    /// Lists the files, returning the files found so far if the directory can't be read.
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        let _ = self.walk(&self.root, &mut files);
+        files
+    }
+
+    /// Reads the size from the header of PNG and JPEG images.
+    fn image_size(&self, path: &Path) -> Option<(u32, u32)> {
+        let path = normalize(&path.to_string_lossy());
+        let bytes = fs::read(self.root.join(path)).ok()?;
+
+        png_size(&bytes).or_else(|| jpeg_size(&bytes))
+    }
+}
+
+fn png_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    // the IHDR chunk is always first, right after the signature
+    if !bytes.starts_with(b"\x89PNG\r\n\x1a\n") || bytes.get(12..16)? != b"IHDR" {
+        return None;
+    }
+
+    let width = u32::from_be_bytes(bytes.get(16..20)?.try_into().ok()?);
+    let height = u32::from_be_bytes(bytes.get(20..24)?.try_into().ok()?);
+
+    Some((width, height))
+}
+
+fn jpeg_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+
+    let mut i = 2;
+    loop {
+        if *bytes.get(i)? != 0xFF {
+            return None;
+        }
+
+        let marker = *bytes.get(i + 1)?;
+        let length = u16::from_be_bytes([*bytes.get(i + 2)?, *bytes.get(i + 3)?]) as usize;
+
+        // start of frame markers, which share their range with the huffman table, reserved and arithmetic coding markers
+        if matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+            let height = u16::from_be_bytes([*bytes.get(i + 5)?, *bytes.get(i + 6)?]);
+            let width = u16::from_be_bytes([*bytes.get(i + 7)?, *bytes.get(i + 8)?]);
+
+            return Some((width as u32, height as u32));
+        }
+
+        i += 2 + length;
+    }
+}
+
+/// Normalizes the path for comparing, using `/` as the separator and removing leading `./`.
+fn normalize(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut path = path.as_str();
+
+    while let Some(stripped) = path.strip_prefix("./") {
+        path = stripped;
+    }
+
+    path.to_string()
+}
+
+/// File names of each frame of an animation, which has the frame index added before the extension.
+fn animation_frames(path: &str, frame_count: u32) -> Vec<String> {
+    let file_name_start = path.rfind('/').map_or(0, |i| i + 1);
+    let (stem, extension) = match path[file_name_start..].rfind('.') {
+        Some(i) => path.split_at(file_name_start + i),
+        None => (path, ""),
+    };
+
+    (0..frame_count)
+        .map(|i| format!("{stem}{i}{extension}"))
+        .collect()
+}
+
+fn events_file_references(events: &Events, references: &mut Vec<FileReference>) {
+    for event in &events.0 {
+        let (path, kind) = match event {
+            Event::Background(bg) => (&bg.file_name, FileReferenceKind::Background),
+            Event::Video(video) => (&video.file_name, FileReferenceKind::Video),
+            Event::SpriteLegacy(sprite) => (&sprite.file_name, FileReferenceKind::Sprite),
+            Event::AnimationLegacy(animation) => (
+                &animation.file_name,
+                FileReferenceKind::Animation { frame_count: None },
+            ),
+            Event::SampleLegacy(sample) => (&sample.file_name, FileReferenceKind::Sample),
+            Event::StoryboardObject(object) => match &object.object_type {
+                ObjectType::Sprite(sprite) => (&sprite.filepath, FileReferenceKind::Sprite),
+                ObjectType::Animation(animation) => (
+                    &animation.filepath,
+                    FileReferenceKind::Animation {
+                        frame_count: Some(animation.frame_count),
+                    },
+                ),
+            },
+            Event::AudioSample(sample) => (&sample.filepath, FileReferenceKind::Sample),
+            _ => continue,
+        };
+
+        references.push(FileReference {
+            path: path.get().to_owned(),
+            kind,
+        });
+    }
+}
+
+/// Checks a single path for the rules that don't need the beatmap folder.
+fn lint_path(reference: &FileReference, issues: &mut Vec<LintIssue>) {
+    let path = reference.path.to_string_lossy();
+    let mut push = |rule: LintRule, message: String| {
+        issues.push(LintIssue {
+            rule,
+            severity: rule.severity(),
+            target: LintTarget::FileReference(reference.clone()),
+            message,
+        })
+    };
+
+    let mut chars = path.chars();
+    let has_drive_letter = matches!(
+        (chars.next(), chars.next()),
+        (Some(letter), Some(':')) if letter.is_ascii_alphabetic()
+    );
+
+    if has_drive_letter {
+        push(
+            LintRule::DriveLetter,
+            format!("`{path}` starts with a drive letter"),
+        );
+    } else if path.starts_with('/') || path.starts_with('\\') {
+        push(
+            LintRule::AbsolutePath,
+            format!("`{path}` is an absolute path"),
+        );
+    }
+
+    if path.split(['/', '\\']).any(|component| component == "..") {
+        push(
+            LintRule::ParentTraversal,
+            format!("`{path}` points outside of the beatmap folder"),
+        );
+    }
+
+    let after_drive_letter = if has_drive_letter { &path[2..] } else { &path };
+    let illegal = after_drive_letter
+        .chars()
+        .filter(|c| ILLEGAL_CHARACTERS.contains(c) || c.is_control())
+        .collect::<String>();
+
+    if !illegal.is_empty() {
+        push(
+            LintRule::IllegalCharacters,
+            format!("`{path}` contains illegal characters `{illegal}`"),
+        );
+    }
+}
+
+/// Checks that the files exist in the beatmap folder with the same case.
+fn lint_existence(
+    reference: &FileReference,
+    files: &HashMap<String, Vec<String>>,
+    issues: &mut Vec<LintIssue>,
+) {
+    let path = normalize(&reference.path.to_string_lossy());
+    let paths = match reference.kind {
+        FileReferenceKind::Animation {
+            frame_count: Some(frame_count),
+        } => animation_frames(&path, frame_count),
+        _ => vec![path],
+    };
+
+    for path in paths {
+        let (rule, message) = match files.get(&path.to_lowercase()) {
+            Some(matches) if matches.contains(&path) => continue,
+            Some(matches) => (
+                LintRule::CaseMismatch,
+                format!(
+                    "`{path}` only matches `{}` if the case is ignored",
+                    matches[0]
+                ),
+            ),
+            None => (
+                LintRule::MissingFile,
+                format!("`{path}` isn't in the beatmap folder"),
+            ),
+        };
+
+        issues.push(LintIssue {
+            rule,
+            severity: rule.severity(),
+            target: LintTarget::FileReference(reference.clone()),
+            message,
+        });
+    }
+}
+
+impl OsuFile {
+    /// Returns the files referenced by the beatmap and the storyboard, without duplicates.
+    /// - Includes `AudioFilename`, and the files used by the events in the .osu file and the .osb file.
+    pub fn file_references(&self) -> Vec<FileReference> {
+        let mut references = Vec::new();
+
+        let audio_filename = self
+            .general
+            .as_ref()
+            .and_then(|g| g.audio_filename.clone())
+            .map(PathBuf::from);
+        if let Some(path) = audio_filename {
+            if !path.as_os_str().is_empty() {
+                references.push(FileReference {
+                    path,
+                    kind: FileReferenceKind::Audio,
+                });
+            }
+        }
+
+        if let Some(events) = &self.events {
+            events_file_references(events, &mut references);
+        }
+        if let Some(events) = self.osb.as_ref().and_then(|osb| osb.events.as_ref()) {
+            events_file_references(events, &mut references);
+        }
+
+        let mut deduped: Vec<FileReference> = Vec::with_capacity(references.len());
+        for reference in references {
+            if !deduped.contains(&reference) {
+                deduped.push(reference);
+            }
+        }
+
+        deduped
+    }
+
+    /// Lints the [`file_references`][Self::file_references].
+    /// - Checks for absolute paths, `..` traversal, drive letters and characters that aren't allowed on Windows.
+    /// - With a `prober`, also checks that each file exists in the beatmap folder with the same case.
+    pub fn lint_file_references(&self, prober: Option<&dyn AssetProber>) -> Vec<LintIssue> {
+        let references = self.file_references();
+        let mut issues = Vec::new();
+
+        for reference in &references {
+            lint_path(reference, &mut issues);
+        }
+
+        if let Some(prober) = prober {
+            let mut files: HashMap<String, Vec<String>> = HashMap::new();
+            for file in prober.files() {
+                let file = normalize(&file.to_string_lossy());
+                files.entry(file.to_lowercase()).or_default().push(file);
+            }
+
+            for reference in &references {
+                lint_existence(reference, &files, &mut issues);
+            }
+        }
+
+        issues
+    }
+}
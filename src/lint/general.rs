@@ -0,0 +1,48 @@
+//! Module for linting the fields of the `[General]` section.
+
+use super::{LintIssue, LintRule, LintTarget};
+use crate::osu_file::{general::Countdown, Integer, OsuFile};
+
+pub(super) fn issue(rule: LintRule, field: &'static str, message: String) -> LintIssue {
+    LintIssue {
+        rule,
+        severity: rule.severity(),
+        target: LintTarget::Field(field),
+        message,
+    }
+}
+
+impl OsuFile {
+    /// Lints the fields of the `[General]` section.
+    /// - Checks that `Countdown` is one of the known speeds, which can only be parsed with [`ParseOptions::lenient`][crate::osu_file::ParseOptions::lenient].
+    /// - Checks that `CountdownOffset` isn't set while the countdown is off.
+    pub fn lint_general(&self) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+
+        let general = match &self.general {
+            Some(general) => general,
+            None => return issues,
+        };
+
+        if let Some(Countdown::Other(countdown)) = general.countdown {
+            issues.push(issue(
+                LintRule::UnknownCountdown,
+                "Countdown",
+                format!("`Countdown` is {countdown}, which isn't one of the known speeds"),
+            ));
+        }
+
+        let countdown_offset = general.countdown_offset.clone().map(Integer::from);
+        if let (Some(Countdown::Off), Some(offset)) = (general.countdown, countdown_offset) {
+            if offset != 0 {
+                issues.push(issue(
+                    LintRule::CountdownOffsetWithoutCountdown,
+                    "CountdownOffset",
+                    format!("`CountdownOffset` is {offset}, but the countdown is off"),
+                ));
+            }
+        }
+
+        issues
+    }
+}
@@ -0,0 +1,38 @@
+//! Module for linting the fields of the `[Metadata]` section.
+
+use super::{general::issue, LintIssue, LintRule};
+use crate::osu_file::{difficulty::CircleSizeValue, general::Mode, OsuFile};
+
+impl OsuFile {
+    /// Lints the fields of the `[Metadata]` section.
+    /// - Checks that the key count at the start of `Version`, such as `4K`, matches `CircleSize` in osu!mania.
+    pub fn lint_metadata(&self) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+
+        let version = match self.metadata.as_ref().and_then(|m| m.version.as_ref()) {
+            Some(version) => version,
+            None => return issues,
+        };
+
+        let mode = self.mode_or_default();
+        if let (Mode::Mania, Some(name_keys)) = (mode, version.key_count()) {
+            let keys = match &self.difficulty {
+                Some(difficulty) => difficulty.key_count(mode),
+                None => crate::osu_file::Difficulty::new().key_count(mode),
+            };
+
+            match keys {
+                CircleSizeValue::Keys(keys) if keys != name_keys => {
+                    issues.push(issue(
+                        LintRule::KeyCountMismatch,
+                        "Version",
+                        format!("`Version` is for {name_keys}K, but `CircleSize` sets {keys} keys"),
+                    ));
+                }
+                _ => (),
+            }
+        }
+
+        issues
+    }
+}
@@ -0,0 +1,11 @@
+//! Module for checking an .osu file for problems that don't stop it from being parsed.
+
+mod editor;
+pub mod file_references;
+mod general;
+mod metadata;
+mod timing_points;
+pub mod types;
+
+pub use file_references::*;
+pub use types::*;
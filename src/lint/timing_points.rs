@@ -0,0 +1,68 @@
+//! Module for linting the `[TimingPoints]` section.
+
+use rust_decimal::Decimal;
+
+use super::{LintIssue, LintRule, LintTarget};
+use crate::osu_file::{timingpoints::TimingPoint, OsuFile};
+
+fn issue(rule: LintRule, index: usize, message: String) -> LintIssue {
+    LintIssue {
+        rule,
+        severity: rule.severity(),
+        target: LintTarget::TimingPoint(index),
+        message,
+    }
+}
+
+fn timing_point_issues(index: usize, timing_point: &TimingPoint) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+
+    if let Some(effects) = timing_point.effects {
+        let unknown_bits = effects.unknown_bits();
+        if unknown_bits != 0 {
+            issues.push(issue(
+                LintRule::UnknownEffectsBits,
+                index,
+                format!(
+                    "Effects are {}, which has the unknown bits {unknown_bits:#b} set",
+                    effects.raw()
+                ),
+            ));
+        }
+    }
+
+    let beat_length = timing_point.beat_length;
+    if timing_point.uninherited && beat_length <= Decimal::ZERO {
+        issues.push(issue(
+            LintRule::NonPositiveBeatLength,
+            index,
+            format!("Beat length is {beat_length}, the game uses the shortest beat length it allows instead"),
+        ));
+    } else if !timing_point.uninherited && beat_length >= Decimal::ZERO {
+        issues.push(issue(
+            LintRule::NonNegativeInheritedBeatLength,
+            index,
+            format!("Beat length is {beat_length}, the game uses a slider velocity multiplier of 1 instead"),
+        ));
+    }
+
+    issues
+}
+
+impl OsuFile {
+    /// Lints the timing points.
+    /// - Checks that the effects only have the [`KNOWN_BITS`][crate::osu_file::timingpoints::Effects::KNOWN_BITS] set, since the other bits are kept but do nothing.
+    /// - Checks that uninherited timing points have a positive beat length, and inherited ones a negative beat length, since other values can't be turned into a BPM or slider velocity.
+    pub fn lint_timing_points(&self) -> Vec<LintIssue> {
+        let timing_points = match &self.timing_points {
+            Some(timing_points) => &timing_points.0,
+            None => return Vec::new(),
+        };
+
+        timing_points
+            .iter()
+            .enumerate()
+            .flat_map(|(index, timing_point)| timing_point_issues(index, timing_point))
+            .collect()
+    }
+}
@@ -0,0 +1,130 @@
+use std::path::PathBuf;
+
+/// How serious a [`LintIssue`] is.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Severity {
+    /// Might cause problems for some players.
+    Warning,
+    /// Breaks the beatmap or gets it rejected.
+    Error,
+}
+
+/// The rule that found a [`LintIssue`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub enum LintRule {
+    /// File reference is an absolute path.
+    AbsolutePath,
+    /// File reference uses `..` to leave the beatmap folder.
+    ParentTraversal,
+    /// File reference starts with a Windows drive letter.
+    DriveLetter,
+    /// File reference contains characters that aren't allowed in Windows file names.
+    IllegalCharacters,
+    /// File reference only matches a file if the case is ignored, which breaks on case-sensitive filesystems.
+    CaseMismatch,
+    /// File reference doesn't match any file in the beatmap folder.
+    MissingFile,
+    /// `CountdownOffset` is set while `Countdown` is off, so it does nothing.
+    CountdownOffsetWithoutCountdown,
+    /// `Countdown` isn't one of the known speeds.
+    UnknownCountdown,
+    /// `GridSize` isn't one of the sizes the editor can be set to.
+    UnknownGridSize,
+    /// `DistanceSpacing` is outside of the range the editor allows.
+    DistanceSpacingOutOfRange,
+    /// Timing point effects have bits set that aren't known flags.
+    UnknownEffectsBits,
+    /// Uninherited timing point has a `beat_length` of zero or less, which the game clamps to the shortest beat it allows.
+    NonPositiveBeatLength,
+    /// Inherited timing point has a `beat_length` of zero or more, which the game treats as a slider velocity multiplier of `1`.
+    NonNegativeInheritedBeatLength,
+    /// Metadata field that is shared by the mapset is different from the first difficulty.
+    MetadataMismatch,
+    /// Audio field is different from the first difficulty, so the difficulties don't play the same song.
+    AudioMismatch,
+    /// Storyboard variable is defined with different values by the storyboards of a difficulty.
+    StoryboardVariableConflict,
+    /// Key count at the start of the difficulty name, such as `4K`, is different from the key count set by `CircleSize` in osu!mania.
+    KeyCountMismatch,
+}
+
+impl LintRule {
+    /// Default severity of the rule.
+    pub fn severity(&self) -> Severity {
+        match self {
+            LintRule::AbsolutePath
+            | LintRule::ParentTraversal
+            | LintRule::DriveLetter
+            | LintRule::IllegalCharacters
+            | LintRule::NonPositiveBeatLength
+            | LintRule::MetadataMismatch
+            | LintRule::AudioMismatch => Severity::Error,
+            LintRule::CaseMismatch
+            | LintRule::MissingFile
+            | LintRule::CountdownOffsetWithoutCountdown
+            | LintRule::UnknownCountdown
+            | LintRule::UnknownGridSize
+            | LintRule::DistanceSpacingOutOfRange
+            | LintRule::UnknownEffectsBits
+            | LintRule::NonNegativeInheritedBeatLength
+            | LintRule::StoryboardVariableConflict
+            | LintRule::KeyCountMismatch => Severity::Warning,
+        }
+    }
+}
+
+/// What a file reference is used for.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub enum FileReferenceKind {
+    /// `AudioFilename` in the `[General]` section.
+    Audio,
+    Background,
+    Video,
+    Sprite,
+    /// Storyboard animation, with the frame count if it's known.
+    /// - Each frame is a separate file with the frame index added before the extension.
+    Animation {
+        frame_count: Option<u32>,
+    },
+    /// Storyboard sound sample.
+    Sample,
+}
+
+/// A file referenced by the beatmap or the storyboard.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FileReference {
+    /// Path as written in the file.
+    pub path: PathBuf,
+    pub kind: FileReferenceKind,
+}
+
+/// What a [`LintIssue`] was found in.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub enum LintTarget {
+    FileReference(FileReference),
+    /// Field in a key-value section such as `[General]`, by the name written in the file.
+    Field(&'static str),
+    /// Timing point, by its index in the `[TimingPoints]` section.
+    TimingPoint(usize),
+    /// Storyboard variable, by its name without the `$`.
+    StoryboardVariable(String),
+}
+
+/// A problem found by linting.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LintIssue {
+    pub rule: LintRule,
+    pub severity: Severity,
+    pub target: LintTarget,
+    /// Human readable description of the problem.
+    pub message: String,
+}
@@ -0,0 +1,25 @@
+use std::string::FromUtf8Error;
+
+use thiserror::Error;
+
+use crate::osu_file::{self, Error};
+
+use super::BeatmapKey;
+
+/// Error used when there was a problem loading a [`Mapset`][super::Mapset] from a [`BeatmapSource`][super::BeatmapSource].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum MapsetLoadError<E> {
+    /// The storage backend returned an error.
+    #[error("The storage backend returned an error: {0}")]
+    Storage(E),
+    /// The beatmap doesn't exist in the storage backend.
+    #[error("The beatmap {0:?} doesn't exist in the storage")]
+    NotFound(BeatmapKey),
+    /// The beatmap isn't valid UTF-8.
+    #[error(transparent)]
+    InvalidUtf8(#[from] FromUtf8Error),
+    /// The beatmap failed to parse.
+    #[error(transparent)]
+    ParseError(#[from] Error<osu_file::ParseError>),
+}
@@ -0,0 +1,135 @@
+//! Module for handling a whole mapset, which is a group of difficulties sharing the same song.
+
+pub mod error;
+pub mod storage;
+pub mod storyboard;
+pub mod validate;
+
+pub use error::*;
+pub use storage::*;
+pub use storyboard::*;
+pub use validate::*;
+
+use crate::osu_file::{ConversionError, ConversionReport, Osb, OsuFile, Version};
+
+/// A single difficulty in a [`Mapset`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct MapsetDifficulty {
+    /// File name of the .osu file.
+    pub file_name: String,
+    /// The parsed .osu file.
+    pub osu_file: OsuFile,
+}
+
+/// A group of difficulties with the storyboard shared between them.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Mapset {
+    /// Difficulties in the mapset.
+    pub difficulties: Vec<MapsetDifficulty>,
+    /// The .osb storyboard shared between all difficulties.
+    pub osb: Option<Osb>,
+}
+
+impl Mapset {
+    /// New `Mapset` with no difficulties.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a difficulty to the mapset.
+    pub fn push_difficulty<S: Into<String>>(&mut self, file_name: S, osu_file: OsuFile) {
+        self.difficulties.push(MapsetDifficulty {
+            file_name: file_name.into(),
+            osu_file,
+        });
+    }
+
+    /// Gets the difficulty with the file name.
+    pub fn difficulty(&self, file_name: &str) -> Option<&OsuFile> {
+        self.difficulties
+            .iter()
+            .find(|d| d.file_name == file_name)
+            .map(|d| &d.osu_file)
+    }
+
+    /// Gets the difficulty with the file name as mutable.
+    pub fn difficulty_mut(&mut self, file_name: &str) -> Option<&mut OsuFile> {
+        self.difficulties
+            .iter_mut()
+            .find(|d| d.file_name == file_name)
+            .map(|d| &mut d.osu_file)
+    }
+
+    /// Loads the difficulties with the file names from the storage backend.
+    pub fn load<S: BeatmapSource>(
+        source: &S,
+        file_names: &[&str],
+    ) -> Result<Self, MapsetLoadError<S::Error>> {
+        let mut mapset = Mapset::new();
+
+        for file_name in file_names {
+            let key = BeatmapKey::FileName(file_name.to_string());
+            let osu_file = fetch_osu_file(source, &key)?;
+
+            mapset.push_difficulty(*file_name, osu_file);
+        }
+
+        Ok(mapset)
+    }
+
+    /// Stores every difficulty in the storage backend, using the file name as the key.
+    pub fn store<S: BeatmapSink>(&self, sink: &mut S) -> Result<(), S::Error> {
+        for difficulty in &self.difficulties {
+            sink.store(
+                &BeatmapKey::FileName(difficulty.file_name.clone()),
+                difficulty.osu_file.to_string().as_bytes(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Converts each difficulty to the `version` with [`OsuFile::convert_to`], returning the result by file name.
+    /// - A difficulty that can't be converted is left as it is, and the others are still converted.
+    pub fn convert_to(
+        &mut self,
+        version: Version,
+    ) -> Vec<(String, Result<ConversionReport, ConversionError>)> {
+        self.difficulties
+            .iter_mut()
+            .map(|d| (d.file_name.clone(), d.osu_file.convert_to(version)))
+            .collect()
+    }
+}
+
+/// Fetches and parses a single .osu file from the storage backend.
+pub fn fetch_osu_file<S: BeatmapSource>(
+    source: &S,
+    key: &BeatmapKey,
+) -> Result<OsuFile, MapsetLoadError<S::Error>> {
+    let bytes = source
+        .fetch(key)
+        .map_err(MapsetLoadError::Storage)?
+        .ok_or_else(|| MapsetLoadError::NotFound(key.clone()))?;
+    let s = String::from_utf8(bytes)?;
+
+    Ok(s.parse()?)
+}
+
+/// Parses every beatmap with the keys from the storage backend.
+/// - Beatmaps are fetched and parsed lazily, one at a time.
+/// - Errors are returned per beatmap, so one broken beatmap doesn't stop the rest of the corpus.
+pub fn parse_corpus<'a, S, I>(
+    source: &'a S,
+    keys: I,
+) -> impl Iterator<Item = (BeatmapKey, Result<OsuFile, MapsetLoadError<S::Error>>)> + 'a
+where
+    S: BeatmapSource,
+    I: IntoIterator<Item = BeatmapKey>,
+    I::IntoIter: 'a,
+{
+    keys.into_iter().map(move |key| {
+        let osu_file = fetch_osu_file(source, &key);
+        (key, osu_file)
+    })
+}
@@ -0,0 +1,75 @@
+//! Traits for plugging beatmap storage backends into the crate.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+
+use crate::osu_file::Integer;
+
+/// Key used to look up a beatmap in a storage backend.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum BeatmapKey {
+    /// Hash of the .osu file contents, usually the MD5 hash osu! uses.
+    Hash(String),
+    /// Beatmap ID from the `[Metadata]` section.
+    BeatmapId(Integer),
+    /// File name of the .osu file in the mapset.
+    FileName(String),
+}
+
+/// A storage backend beatmaps can be fetched from.
+pub trait BeatmapSource {
+    /// Error returned by the backend.
+    type Error;
+
+    /// Fetches the serialized bytes of a beatmap.
+    /// - Returns `Ok(None)` if the beatmap doesn't exist in the backend.
+    fn fetch(&self, key: &BeatmapKey) -> Result<Option<Vec<u8>>, Self::Error>;
+}
+
+/// A storage backend beatmaps can be stored in.
+pub trait BeatmapSink {
+    /// Error returned by the backend.
+    type Error;
+
+    /// Stores the serialized bytes of a beatmap, replacing any existing beatmap with the same key.
+    fn store(&mut self, key: &BeatmapKey, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// In-memory storage backend.
+/// - Useful for testing, or as a cache in front of a slower backend.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStorage(pub HashMap<BeatmapKey, Vec<u8>>);
+
+impl BeatmapSource for MemoryStorage {
+    type Error = Infallible;
+
+    fn fetch(&self, key: &BeatmapKey) -> Result<Option<Vec<u8>>, Self::Error> {
+        Ok(self.0.get(key).cloned())
+    }
+}
+
+impl BeatmapSink for MemoryStorage {
+    type Error = Infallible;
+
+    fn store(&mut self, key: &BeatmapKey, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.0.insert(key.clone(), bytes.to_vec());
+        Ok(())
+    }
+}
+
+impl<S: BeatmapSource> BeatmapSource for &S {
+    type Error = S::Error;
+
+    fn fetch(&self, key: &BeatmapKey) -> Result<Option<Vec<u8>>, Self::Error> {
+        (*self).fetch(key)
+    }
+}
+
+impl<S: BeatmapSink> BeatmapSink for &mut S {
+    type Error = S::Error;
+
+    fn store(&mut self, key: &BeatmapKey, bytes: &[u8]) -> Result<(), Self::Error> {
+        (**self).store(key, bytes)
+    }
+}
@@ -0,0 +1,143 @@
+//! Module for combining the storyboard of a difficulty with the .osb storyboard shared by the mapset.
+
+use super::Mapset;
+use crate::osu_file::{
+    events::{storyboard::sprites::Layer, Event},
+    osb::Variable,
+    Osb, OsuFile,
+};
+
+/// Layers in the order they are drawn, from the bottom.
+const LAYER_ORDER: [Layer; 5] = [
+    Layer::Background,
+    Layer::Fail,
+    Layer::Pass,
+    Layer::Foreground,
+    Layer::Overlay,
+];
+
+/// Which file a [`StoryboardElement`] comes from.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum StoryboardSource {
+    /// The .osb file shared by the mapset.
+    Osb,
+    /// The `[Events]` section of the difficulty.
+    Difficulty,
+}
+
+/// A storyboard element in a [`CombinedStoryboard`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StoryboardElement {
+    pub layer: Layer,
+    pub source: StoryboardSource,
+    pub event: Event,
+}
+
+/// Variable defined with different values by the storyboards being combined.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct VariableConflict {
+    pub name: String,
+    /// Values of the variable, in the order they were found.
+    pub values: Vec<String>,
+}
+
+/// The storyboard of a difficulty as the game shows it, made by [`Mapset::combined_storyboard`].
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct CombinedStoryboard {
+    /// Whether the storyboard is shown in widescreen, from `WidescreenStoryboard` of the difficulty.
+    pub widescreen: bool,
+    /// Storyboard elements in the order they are drawn, from the bottom.
+    /// - Layers are drawn from `Background` to `Overlay`.
+    /// - In each layer, the elements of the .osb file are drawn below the elements of the difficulty.
+    pub elements: Vec<StoryboardElement>,
+    /// Variables defined with different values by the .osb files being combined.
+    pub variable_conflicts: Vec<VariableConflict>,
+}
+
+impl CombinedStoryboard {
+    /// Elements in the layer, in the order they are drawn.
+    pub fn layer(&self, layer: Layer) -> impl Iterator<Item = &StoryboardElement> {
+        self.elements.iter().filter(move |e| e.layer == layer)
+    }
+
+    /// Combines the storyboard of the difficulty with the .osb storyboard.
+    /// - Events that aren't storyboard elements, such as backgrounds and breaks, are left out.
+    pub fn new(osu_file: &OsuFile, osb: Option<&Osb>) -> Self {
+        let widescreen = osu_file
+            .general
+            .as_ref()
+            .and_then(|g| g.widescreen_storyboard.clone())
+            .map(bool::from)
+            .unwrap_or_default();
+
+        let sources = [
+            (
+                StoryboardSource::Osb,
+                osb.and_then(|osb| osb.events.as_ref()),
+            ),
+            (StoryboardSource::Difficulty, osu_file.events.as_ref()),
+        ];
+
+        let mut elements = Vec::new();
+        for layer in LAYER_ORDER {
+            for (source, events) in sources {
+                let events = events.into_iter().flat_map(|events| &events.0);
+
+                elements.extend(
+                    events
+                        .filter(|event| event.storyboard_layer() == Some(layer))
+                        .map(|event| StoryboardElement {
+                            layer,
+                            source,
+                            event: event.clone(),
+                        }),
+                );
+            }
+        }
+
+        let variables = [osb, osu_file.osb.as_ref()]
+            .into_iter()
+            .flatten()
+            .flat_map(|osb| osb.variables.iter().flatten());
+
+        Self {
+            widescreen,
+            elements,
+            variable_conflicts: variable_conflicts(variables),
+        }
+    }
+}
+
+fn variable_conflicts<'a>(variables: impl Iterator<Item = &'a Variable>) -> Vec<VariableConflict> {
+    let mut found: Vec<VariableConflict> = Vec::new();
+
+    for variable in variables {
+        match found.iter_mut().find(|v| v.name == variable.name) {
+            Some(found) => {
+                if !found.values.contains(&variable.value) {
+                    found.values.push(variable.value.clone());
+                }
+            }
+            None => found.push(VariableConflict {
+                name: variable.name.clone(),
+                values: vec![variable.value.clone()],
+            }),
+        }
+    }
+
+    found.retain(|v| v.values.len() > 1);
+    found
+}
+
+impl Mapset {
+    /// Combines the storyboard of the difficulty with the file name and the shared .osb storyboard, as the game would show it.
+    /// - Uses the .osb storyboard appended to the difficulty if the mapset doesn't have one.
+    /// - Variables defined by both .osb storyboards with different values are reported as conflicts.
+    /// - Returns `None` if there's no difficulty with the file name.
+    pub fn combined_storyboard(&self, diff: &str) -> Option<CombinedStoryboard> {
+        let osu_file = self.difficulty(diff)?;
+        let osb = self.osb.as_ref().or(osu_file.osb.as_ref());
+
+        Some(CombinedStoryboard::new(osu_file, osb))
+    }
+}
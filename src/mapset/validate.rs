@@ -0,0 +1,298 @@
+//! Module for validating a whole mapset at once, for tools such as bots that check mapsets when they are uploaded.
+//! - Difficulties are linted on separate threads, then checked against each other.
+
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::thread;
+
+use super::{Mapset, MapsetDifficulty};
+use crate::lint::{FileReferenceKind, LintIssue, LintRule, LintTarget, Severity};
+#[cfg(feature = "unicode-normalization")]
+use crate::osu_file::metadata::Normalization;
+use crate::osu_file::{FieldValue, OsuFile};
+
+/// Metadata fields that every difficulty of a mapset should have the same value for.
+const SHARED_METADATA: [&str; 8] = [
+    "Title",
+    "TitleUnicode",
+    "Artist",
+    "ArtistUnicode",
+    "Creator",
+    "Source",
+    "Tags",
+    "BeatmapSetID",
+];
+
+/// Fields of the `[General]` section that every difficulty of a mapset should have the same value for.
+const SHARED_AUDIO: [&str; 2] = ["AudioFilename", "PreviewTime"];
+
+/// [`LintIssue`] found by [`Mapset::validate_parallel`], with where it was found.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ValidationIssue {
+    /// File name of the difficulty.
+    pub file_name: String,
+    /// Line number starting from `1`, in the difficulty as it's written by [`Display`][std::fmt::Display].
+    /// - `None` if the issue isn't about a single line, such as storyboard variables.
+    pub line: Option<usize>,
+    pub issue: LintIssue,
+}
+
+/// Issues found by [`Mapset::validate_parallel`].
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ValidationReport {
+    /// Issues sorted by the file name, then by the line.
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Issues grouped by the file name of the difficulty.
+    pub fn by_file(&self) -> BTreeMap<&str, Vec<&ValidationIssue>> {
+        let mut files = BTreeMap::<_, Vec<_>>::new();
+
+        for issue in &self.issues {
+            files
+                .entry(issue.file_name.as_str())
+                .or_default()
+                .push(issue);
+        }
+
+        files
+    }
+
+    /// Returns `true` if any of the issues are [`Severity::Error`].
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.issue.severity == Severity::Error)
+    }
+}
+
+fn field_text(value: &FieldValue) -> String {
+    let join = |values: Vec<String>| values.join(",");
+
+    match value {
+        FieldValue::Integer(value) => value.to_string(),
+        FieldValue::Decimal(value) => value.to_string(),
+        FieldValue::Bool(value) => value.to_string(),
+        FieldValue::String(value) => value.clone(),
+        FieldValue::Path(value) => value.display().to_string(),
+        FieldValue::IntegerList(values) => join(values.iter().map(ToString::to_string).collect()),
+        FieldValue::StringList(values) => join(values.clone()),
+    }
+}
+
+/// Index of the line the issue is about in `written`, which is the difficulty as it's written.
+fn issue_line(written: &str, target: &LintTarget) -> Option<usize> {
+    let is_field = |line: &str, name: &str| matches!(line.split_once(':'), Some((key, _)) if key.trim() == name);
+
+    match target {
+        LintTarget::Field(name) => written.lines().position(|line| is_field(line, name)),
+        LintTarget::FileReference(reference) => match reference.kind {
+            FileReferenceKind::Audio => written
+                .lines()
+                .position(|line| is_field(line, "AudioFilename")),
+            _ => {
+                let path = reference.path.to_string_lossy();
+                written
+                    .lines()
+                    .position(|line| line.contains(path.as_ref()))
+            }
+        },
+        LintTarget::TimingPoint(index) => {
+            let section = written
+                .lines()
+                .position(|line| line.trim() == "[TimingPoints]")?;
+
+            written
+                .lines()
+                .enumerate()
+                .skip(section + 1)
+                .take_while(|(_, line)| !line.trim_start().starts_with('['))
+                .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with("//"))
+                .nth(*index)
+                .map(|(i, _)| i)
+        }
+        LintTarget::StoryboardVariable(_) => None,
+    }
+}
+
+/// Lints of a single difficulty that don't need the beatmap folder.
+fn lint_difficulty(osu_file: &OsuFile) -> Vec<LintIssue> {
+    let mut issues = osu_file.lint_general();
+    issues.extend(osu_file.lint_editor());
+    issues.extend(osu_file.lint_timing_points());
+    issues.extend(osu_file.lint_file_references(None));
+    issues
+}
+
+/// Checks that the `fields` of each difficulty have the same value as the first difficulty.
+fn shared_field_issues(
+    difficulties: &[MapsetDifficulty],
+    fields: &[&'static str],
+    rule: LintRule,
+    get: impl Fn(&OsuFile, &str) -> Option<FieldValue>,
+) -> Vec<(usize, LintIssue)> {
+    let (first, rest) = match difficulties.split_first() {
+        Some(split) => split,
+        None => return Vec::new(),
+    };
+
+    let mut issues = Vec::new();
+    for field in fields {
+        let expected = get(&first.osu_file, field);
+
+        for (i, difficulty) in rest.iter().enumerate() {
+            let value = get(&difficulty.osu_file, field);
+            if value == expected {
+                continue;
+            }
+
+            let text = |value: &Option<FieldValue>| {
+                value
+                    .as_ref()
+                    .map_or("not set".to_string(), |v| format!("`{}`", field_text(v)))
+            };
+            issues.push((
+                i + 1,
+                LintIssue {
+                    rule,
+                    severity: rule.severity(),
+                    target: LintTarget::Field(field),
+                    message: format!(
+                        "`{field}` is {}, but it's {} in `{}`",
+                        text(&value),
+                        text(&expected),
+                        first.file_name
+                    ),
+                },
+            ));
+        }
+    }
+
+    issues
+}
+
+impl Mapset {
+    /// Lints every difficulty on separate threads, then checks the difficulties against each other.
+    /// - Each difficulty is checked with [`OsuFile::lint_general`], [`OsuFile::lint_editor`], [`OsuFile::lint_timing_points`] and [`OsuFile::lint_file_references`] without the beatmap folder.
+    /// - The shared metadata, such as `Title` and `Creator`, and the `AudioFilename` and `PreviewTime` are compared with the first difficulty.
+    /// - Storyboard variables defined with different values by the .osb storyboards of a difficulty are reported, see [`Mapset::combined_storyboard`].
+    /// - With the `unicode-normalization` feature, the metadata is compared after `Normalization::Nfc`, so text saved with different normalization forms isn't a mismatch.
+    pub fn validate_parallel(&self) -> ValidationReport {
+        #[cfg(feature = "unicode-normalization")]
+        {
+            self.validate_parallel_with(Normalization::Nfc)
+        }
+        #[cfg(not(feature = "unicode-normalization"))]
+        {
+            self.validate_parallel_by(|osu_file, field| osu_file.metadata.as_ref()?.get(field))
+        }
+    }
+
+    /// Same as [`validate_parallel`][Self::validate_parallel], with the metadata compared after the `normalization`.
+    #[cfg(feature = "unicode-normalization")]
+    pub fn validate_parallel_with(&self, normalization: Normalization) -> ValidationReport {
+        self.validate_parallel_by(|osu_file, field| {
+            osu_file
+                .metadata
+                .as_ref()?
+                .get_normalized(field, normalization)
+        })
+    }
+
+    /// [`validate_parallel`][Self::validate_parallel] with the shared metadata fields read by `metadata_field`.
+    fn validate_parallel_by(
+        &self,
+        metadata_field: impl Fn(&OsuFile, &str) -> Option<FieldValue>,
+    ) -> ValidationReport {
+        let thread_count = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        let chunk_size = self.difficulties.len().div_ceil(thread_count).max(1);
+
+        let mut per_difficulty: Vec<Vec<LintIssue>> = thread::scope(|scope| {
+            let handles = self
+                .difficulties
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|difficulty| lint_difficulty(&difficulty.osu_file))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        let set_issues = shared_field_issues(
+            &self.difficulties,
+            &SHARED_METADATA,
+            LintRule::MetadataMismatch,
+            metadata_field,
+        )
+        .into_iter()
+        .chain(shared_field_issues(
+            &self.difficulties,
+            &SHARED_AUDIO,
+            LintRule::AudioMismatch,
+            |osu_file, field| osu_file.general.as_ref()?.get(field),
+        ));
+        for (i, issue) in set_issues {
+            per_difficulty[i].push(issue);
+        }
+
+        for (i, difficulty) in self.difficulties.iter().enumerate() {
+            let storyboard = match self.combined_storyboard(&difficulty.file_name) {
+                Some(storyboard) => storyboard,
+                None => continue,
+            };
+
+            per_difficulty[i].extend(storyboard.variable_conflicts.into_iter().map(|conflict| {
+                let rule = LintRule::StoryboardVariableConflict;
+
+                LintIssue {
+                    rule,
+                    severity: rule.severity(),
+                    message: format!(
+                        "`${}` is defined as {}",
+                        conflict.name,
+                        conflict
+                            .values
+                            .iter()
+                            .map(|value| format!("`{value}`"))
+                            .collect::<Vec<_>>()
+                            .join(" and ")
+                    ),
+                    target: LintTarget::StoryboardVariable(conflict.name),
+                }
+            }));
+        }
+
+        let mut issues = self
+            .difficulties
+            .iter()
+            .zip(per_difficulty)
+            .flat_map(|(difficulty, issues)| {
+                let written = difficulty.osu_file.to_string();
+
+                issues
+                    .into_iter()
+                    .map(|issue| ValidationIssue {
+                        file_name: difficulty.file_name.clone(),
+                        line: issue_line(&written, &issue.target).map(|i| i + 1),
+                        issue,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        issues.sort_by(|a, b| (&a.file_name, a.line).cmp(&(&b.file_name, b.line)));
+
+        ValidationReport { issues }
+    }
+}
@@ -0,0 +1,147 @@
+//! Module for comparing values that hold decimals while allowing small differences, such as rounding in the last digit.
+//! - Meant for snapshot tests and diffs, where a value that was calculated and written back can differ slightly from the original.
+
+use rust_decimal::Decimal;
+
+use super::{
+    hitobjects::{CurvePoint, HitObject, HitObjectParams, SlideParams},
+    timingpoints::TimingPoint,
+    HitObjects, OsuFile, Position, TimingPoints,
+};
+
+/// Epsilon used by [`assert_osu_eq!`][crate::assert_osu_eq] when none is given, which is `0.000001`.
+pub const DEFAULT_EPSILON: Decimal = Decimal::from_parts(1, 0, 0, false, 6);
+
+/// Equality that allows the decimals in the values to differ by up to an epsilon.
+pub trait ApproxEq {
+    /// Returns `true` if the values are equal, other than decimals that differ by `epsilon` or less.
+    fn approx_eq(&self, other: &Self, epsilon: Decimal) -> bool;
+}
+
+impl ApproxEq for Decimal {
+    fn approx_eq(&self, other: &Self, epsilon: Decimal) -> bool {
+        (self - other).abs() <= epsilon
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Option<T> {
+    fn approx_eq(&self, other: &Self, epsilon: Decimal) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.approx_eq(b, epsilon),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for [T] {
+    fn approx_eq(&self, other: &Self, epsilon: Decimal) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.approx_eq(b, epsilon))
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Vec<T> {
+    fn approx_eq(&self, other: &Self, epsilon: Decimal) -> bool {
+        self.as_slice().approx_eq(other, epsilon)
+    }
+}
+
+impl ApproxEq for Position {
+    fn approx_eq(&self, other: &Self, epsilon: Decimal) -> bool {
+        self.x.approx_eq(&other.x, epsilon) && self.y.approx_eq(&other.y, epsilon)
+    }
+}
+
+impl ApproxEq for CurvePoint {
+    fn approx_eq(&self, other: &Self, epsilon: Decimal) -> bool {
+        self.0.approx_eq(&other.0, epsilon)
+    }
+}
+
+impl ApproxEq for SlideParams {
+    fn approx_eq(&self, other: &Self, epsilon: Decimal) -> bool {
+        self.curve_type == other.curve_type
+            && self.curve_points.approx_eq(&other.curve_points, epsilon)
+            && self.slides == other.slides
+            && self.length.approx_eq(&other.length, epsilon)
+            && self.edge_sounds == other.edge_sounds
+            && self.edge_sets == other.edge_sets
+    }
+}
+
+impl ApproxEq for HitObject {
+    fn approx_eq(&self, other: &Self, epsilon: Decimal) -> bool {
+        let params_eq = match (&self.obj_params, &other.obj_params) {
+            (HitObjectParams::Slider(a), HitObjectParams::Slider(b)) => a.approx_eq(b, epsilon),
+            (a, b) => a == b,
+        };
+
+        params_eq
+            && self.position.approx_eq(&other.position, epsilon)
+            && self.time == other.time
+            && self.new_combo == other.new_combo
+            && self.combo_skip_count == other.combo_skip_count
+            && self.hitsound == other.hitsound
+            && self.hitsample == other.hitsample
+    }
+}
+
+impl ApproxEq for HitObjects {
+    fn approx_eq(&self, other: &Self, epsilon: Decimal) -> bool {
+        self.0.approx_eq(&other.0, epsilon)
+    }
+}
+
+impl ApproxEq for TimingPoint {
+    fn approx_eq(&self, other: &Self, epsilon: Decimal) -> bool {
+        self.time == other.time
+            && self.beat_length.approx_eq(&other.beat_length, epsilon)
+            && self.meter == other.meter
+            && self.sample_set == other.sample_set
+            && self.sample_index == other.sample_index
+            && self.volume == other.volume
+            && self.uninherited == other.uninherited
+            && self.effects == other.effects
+    }
+}
+
+impl ApproxEq for TimingPoints {
+    fn approx_eq(&self, other: &Self, epsilon: Decimal) -> bool {
+        self.0.approx_eq(&other.0, epsilon)
+    }
+}
+
+impl ApproxEq for super::Difficulty {
+    fn approx_eq(&self, other: &Self, epsilon: Decimal) -> bool {
+        let fields = |d: &Self| -> [Option<Decimal>; 6] {
+            [
+                d.hp_drain_rate.clone().map(Into::into),
+                d.circle_size.clone().map(Into::into),
+                d.overall_difficulty.clone().map(Into::into),
+                d.approach_rate.clone().map(Into::into),
+                d.slider_multiplier.clone().map(Into::into),
+                d.slider_tickrate.clone().map(Into::into),
+            ]
+        };
+
+        fields(self).approx_eq(&fields(other), epsilon)
+    }
+}
+
+impl ApproxEq for OsuFile {
+    /// Compares `[Difficulty]`, `[TimingPoints]` and `[HitObjects]` with [`ApproxEq`], and everything else with `Eq`.
+    fn approx_eq(&self, other: &Self, epsilon: Decimal) -> bool {
+        let without_decimals = |osu_file: &Self| {
+            let mut osu_file = osu_file.clone();
+            osu_file.difficulty = None;
+            osu_file.timing_points = None;
+            osu_file.hitobjects = None;
+            osu_file
+        };
+
+        self.difficulty.approx_eq(&other.difficulty, epsilon)
+            && self.timing_points.approx_eq(&other.timing_points, epsilon)
+            && self.hitobjects.approx_eq(&other.hitobjects, epsilon)
+            && without_decimals(self) == without_decimals(other)
+    }
+}
@@ -5,7 +5,7 @@ use crate::helper::macros::verbose_error_to_error;
 
 #[derive(Debug, Error)]
 #[error(transparent)]
-pub struct ParseError(#[from] ParseColourError);
+pub struct ParseError(#[from] pub(crate) ParseColourError);
 
 /// Error used when there was a problem parsing a `str` as a `Colour`.
 #[derive(Debug, Error, EnumString, IntoStaticStr)]
@@ -16,15 +16,32 @@ use crate::parsers::comma;
 pub use error::*;
 pub use types::*;
 
-use super::{Error, Version, VersionedDefault, VersionedFromStr, VersionedToString, MIN_VERSION};
+use super::{
+    Error, ParseWarning, Version, VersionedDefault, VersionedFromStr, VersionedToString,
+    MIN_VERSION,
+};
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Colours(pub Vec<Colour>);
 
 impl VersionedFromStr for Colours {
     type Err = Error<ParseError>;
 
     fn from_str(s: &str, version: Version) -> std::result::Result<Option<Self>, Self::Err> {
+        Self::from_str_skipping_malformed(s, version, 0, None)
+    }
+}
+
+impl Colours {
+    /// [`from_str`][VersionedFromStr::from_str] that skips the lines that fail to parse if there are `warnings`, adding a warning for each of them.
+    /// - `line_number` is the line index where the section starts.
+    pub(crate) fn from_str_skipping_malformed(
+        s: &str,
+        version: Version,
+        line_number: usize,
+        mut warnings: Option<&mut Vec<ParseWarning>>,
+    ) -> std::result::Result<Option<Self>, Error<ParseError>> {
         match version {
             MIN_VERSION..=4 => Ok(None),
             _ => {
@@ -35,8 +52,24 @@ impl VersionedFromStr for Colours {
                         continue;
                     }
 
-                    let colour =
-                        Error::new_from_result_into(Colour::from_str(s, version), line_index)?;
+                    let colour = match Colour::from_str(s, version) {
+                        Ok(colour) => colour,
+                        Err(err) => {
+                            let in_value = matches!(err, ParseColourError::ParseRgbError(_));
+                            let err: Error<ParseError> = Error::new_into(err, line_index);
+
+                            if let Some(warnings) = warnings.as_deref_mut() {
+                                warnings.push(ParseWarning::malformed_line(line_number, s, &err));
+                                continue;
+                            }
+
+                            return Err(if in_value {
+                                err.with_value_column(s)
+                            } else {
+                                err
+                            });
+                        }
+                    };
                     if let Some(colour) = colour {
                         colours.push(colour);
                     }
@@ -74,6 +107,7 @@ impl VersionedDefault for Colours {
 
 /// Struct representing a single `colour` component in the `Colours` section.
 #[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum Colour {
     /// Additive combo colours.
@@ -8,6 +8,7 @@ use crate::parsers::consume_rest_type;
 use super::*;
 
 #[derive(Clone, Copy, Hash, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Struct representing the RGB colours with each colour having value from 0 ~ 255.
 pub struct Rgb {
     /// Red colour.
@@ -0,0 +1,467 @@
+//! Module for converting an .osu file to another version, reporting what couldn't be converted by section.
+//! - Each file is converted on its own, so a batch converter can convert the files that work and fix the rest by hand.
+
+use std::collections::HashSet;
+
+use thiserror::Error;
+
+use super::{
+    events::{storyboard::sprites::Object, AudioSample, Event, LegacyConversionError},
+    hitobjects::HitObject,
+    timingpoints::TimingPoint,
+    Events, Loss, LossKind, LossTarget, ObjectId, OsuFile, SectionName, Version, VersionedFromStr,
+    VersionedToString, FIELD_MIGRATIONS, LATEST_VERSION, MIN_VERSION,
+};
+
+/// Error used when [`OsuFile::convert_to`] can't convert the file at all.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConversionError {
+    /// The version to convert to isn't supported.
+    #[error(
+        "Can't convert to version {0}, expected versions from {MIN_VERSION} ~ {LATEST_VERSION}"
+    )]
+    UnsupportedVersion(Version),
+}
+
+/// What couldn't be converted.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConversionIssueKind {
+    /// Legacy event, by its index in the `[Events]` section, that couldn't be upgraded to the modern event.
+    /// - The event is kept as it is.
+    LegacyEventNotUpgraded {
+        event: usize,
+        error: LegacyConversionError,
+    },
+    /// Something that is dropped or changed when the file is written in the version, see [`OsuFile::lossiness_report`].
+    Lost(Loss),
+}
+
+/// Something that couldn't be converted, and the section it's in.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ConversionIssue {
+    pub section: SectionName,
+    pub kind: ConversionIssueKind,
+}
+
+impl ConversionIssue {
+    /// What can be done by hand to keep what would be lost.
+    pub fn suggested_fix(&self) -> String {
+        match &self.kind {
+            ConversionIssueKind::LegacyEventNotUpgraded { event, error } => format!(
+                "Event {event} can't be upgraded ({error}), recreate it as a storyboard object or remove it"
+            ),
+            ConversionIssueKind::Lost(loss) => {
+                let what = match loss.target {
+                    LossTarget::Section(section) => format!("the `[{}]` section", section.as_str()),
+                    LossTarget::Field { section, name } => {
+                        format!("`{name}` in `[{}]`", section.as_str())
+                    }
+                    LossTarget::Event(event) => format!("event {event}"),
+                    LossTarget::Command { event, command } => {
+                        format!("command {command} of event {event}")
+                    }
+                    LossTarget::TimingPoint(index) => format!("timing point {index}"),
+                    LossTarget::HitObject(index) => format!("hitobject {index}"),
+                };
+
+                match loss.kind {
+                    LossKind::Dropped => format!(
+                        "Version doesn't support {what}, keep the original file or recreate it in a way the version supports"
+                    ),
+                    LossKind::Altered => format!(
+                        "Version changes {what} when writing it, check its values after converting"
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// What [`OsuFile::convert_to`] did, and what it couldn't convert.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ConversionReport {
+    /// Version of the file before converting.
+    pub from: Version,
+    /// Version the file was converted to.
+    pub to: Version,
+    /// Indices of the [`FIELD_MIGRATIONS`] that moved a field.
+    pub migrated_fields: Vec<usize>,
+    /// Number of legacy events that were upgraded to the modern events.
+    pub upgraded_events: usize,
+    /// What couldn't be converted, in the order of the sections.
+    pub issues: Vec<ConversionIssue>,
+}
+
+impl ConversionReport {
+    /// Returns `true` if everything was converted.
+    pub fn is_lossless(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Issues in the `section`.
+    pub fn section_issues(&self, section: SectionName) -> impl Iterator<Item = &ConversionIssue> {
+        self.issues.iter().filter(move |i| i.section == section)
+    }
+
+    /// Everything that is dropped by the conversion.
+    pub fn dropped(&self) -> impl Iterator<Item = &Loss> {
+        self.issues.iter().filter_map(|i| match &i.kind {
+            ConversionIssueKind::Lost(loss) if loss.kind == LossKind::Dropped => Some(loss),
+            _ => None,
+        })
+    }
+}
+
+/// Kind of the loss of the whole `section`, if it's lost.
+fn section_loss(losses: &[Loss], section: SectionName) -> Option<LossKind> {
+    losses
+        .iter()
+        .find(|loss| loss.target == LossTarget::Section(section))
+        .map(|loss| loss.kind)
+}
+
+/// The section after writing it in the `version` and parsing it back.
+fn read_back_section<T>(section: &T, version: Version) -> Option<T>
+where
+    T: VersionedFromStr + VersionedToString,
+{
+    T::from_str(&section.to_string(version)?, version)
+        .ok()
+        .flatten()
+}
+
+/// Drops the fields of a `key: value` section that are dropped, and reads back the fields that are altered.
+macro_rules! apply_field_losses {
+    ($losses:expr, $section:expr, $type:ty, $name:expr, $version:expr) => {
+        if $losses.iter().any(|loss| loss_section(&loss.target) == $name) {
+            let read_back = $section.as_ref().and_then(|section| {
+                <$type>::from_str(&section.to_string($version)?, $version)
+                    .ok()
+                    .flatten()
+            });
+
+            match section_loss($losses, $name) {
+                Some(LossKind::Dropped) => $section = None,
+                Some(LossKind::Altered) => $section = read_back,
+                None => {
+                    if let Some(section) = &mut $section {
+                        for loss in $losses {
+                            let name = match loss.target {
+                                LossTarget::Field { section, name } if section == $name => name,
+                                _ => continue,
+                            };
+
+                            match (loss.kind, read_back.as_ref().and_then(|r| r.get(name))) {
+                                // the value is read back as the same kind of value, so setting it can't fail
+                                (LossKind::Altered, Some(value)) => {
+                                    let _ = section.set(name, value);
+                                }
+                                _ => {
+                                    section.remove(name);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
+
+/// Drops the events that are dropped, and reads back the events that are altered or have lost commands.
+/// - Storyboard objects that are read back keep their extensions.
+fn apply_event_losses(events: &mut Events, losses: &[Loss], version: Version) {
+    let mut dropped = vec![false; events.0.len()];
+    let mut altered = vec![false; events.0.len()];
+
+    for loss in losses {
+        let (i, lost) = match (&loss.target, loss.kind) {
+            (LossTarget::Event(i), LossKind::Dropped) => (*i, &mut dropped),
+            (LossTarget::Event(i), LossKind::Altered)
+            | (LossTarget::Command { event: i, .. }, _) => (*i, &mut altered),
+            _ => continue,
+        };
+
+        if let Some(lost) = lost.get_mut(i) {
+            *lost = true;
+        }
+    }
+
+    let original = std::mem::take(&mut events.0);
+    for (i, event) in original.into_iter().enumerate() {
+        if dropped[i] {
+            continue;
+        }
+        if !altered[i] {
+            events.0.push(event);
+            continue;
+        }
+
+        let read_back =
+            read_back_section(&Events(vec![event.clone()]), version).unwrap_or_default();
+        events
+            .0
+            .extend(read_back.0.into_iter().map(|mut read_back| {
+                if let (Event::StoryboardObject(read_back), Event::StoryboardObject(original)) =
+                    (&mut read_back, &event)
+                {
+                    read_back.extensions = original.extensions.clone();
+                }
+                read_back
+            }));
+    }
+}
+
+/// Drops a section that is a list of items, or replaces it with what is read back, if the whole section is lost.
+fn apply_section_loss<S>(
+    section: &mut Option<S>,
+    name: SectionName,
+    losses: &[Loss],
+    version: Version,
+) where
+    S: VersionedFromStr + VersionedToString,
+{
+    match section_loss(losses, name) {
+        Some(LossKind::Dropped) => *section = None,
+        Some(LossKind::Altered) => {
+            *section = section
+                .as_ref()
+                .and_then(|section| read_back_section(section, version))
+        }
+        None => (),
+    }
+}
+
+/// Replaces the altered items, found by `item_index`, with what is read back after writing each of them on its own.
+/// - The altered items are looked up by their [`ObjectId`], and each is replaced by what is read back from it, so `restore` copies what isn't written from the right item.
+fn apply_item_losses<S, T>(
+    section: &mut Option<S>,
+    losses: &[Loss],
+    version: Version,
+    items: fn(&mut S) -> &mut Vec<T>,
+    item_index: fn(&LossTarget) -> Option<usize>,
+    id: fn(&T) -> ObjectId,
+    restore: fn(&mut T, &T),
+) where
+    S: Default + VersionedFromStr + VersionedToString,
+    T: Clone,
+{
+    let section = match section.as_mut() {
+        Some(section) => section,
+        None => return,
+    };
+    let altered = {
+        let items = items(section);
+        losses
+            .iter()
+            .filter_map(|loss| item_index(&loss.target))
+            .filter_map(|i| items.get(i).map(id))
+            .collect::<HashSet<_>>()
+    };
+
+    for item in items(section)
+        .iter_mut()
+        .filter(|item| altered.contains(&id(item)))
+    {
+        let mut single = S::default();
+        items(&mut single).push(item.clone());
+
+        let mut read_back = match read_back_section(&single, version) {
+            Some(read_back) => read_back,
+            None => continue,
+        };
+        if let [read_back] = items(&mut read_back).as_mut_slice() {
+            restore(read_back, item);
+            std::mem::swap(item, read_back);
+        }
+    }
+}
+
+fn loss_section(target: &LossTarget) -> SectionName {
+    match target {
+        LossTarget::Section(section) | LossTarget::Field { section, .. } => *section,
+        LossTarget::Event(_) | LossTarget::Command { .. } => SectionName::Events,
+        LossTarget::TimingPoint(_) => SectionName::TimingPoints,
+        LossTarget::HitObject(_) => SectionName::HitObjects,
+    }
+}
+
+impl OsuFile {
+    /// Converts the file to the `version`, so it's written in that version.
+    /// - Moves the fields in [`FIELD_MIGRATIONS`] to their place in the version, see [`migrate_fields`][Self::migrate_fields].
+    /// - Upgrades the legacy events when converting to the latest version, see [`Events::upgrade_legacy`][super::Events::upgrade_legacy].
+    /// - Everything that would be dropped or changed by writing the file in the version is reported, and kept in the file as it is.
+    pub fn convert_to(&mut self, version: Version) -> Result<ConversionReport, ConversionError> {
+        if !(MIN_VERSION..=LATEST_VERSION).contains(&version) {
+            return Err(ConversionError::UnsupportedVersion(version));
+        }
+
+        let migrated_fields = FIELD_MIGRATIONS
+            .iter()
+            .enumerate()
+            .filter(|(_, migration)| {
+                let to = migration.location(version);
+                let from = if to == migration.old {
+                    migration.new
+                } else {
+                    migration.old
+                };
+
+                self.has_field(from)
+            })
+            .map(|(i, _)| i)
+            .collect();
+        self.migrate_fields(version);
+
+        let mut issues = Vec::new();
+        let mut upgraded_events = 0;
+        if let (Some(events), LATEST_VERSION) = (&mut self.events, version) {
+            upgraded_events = events.upgrade_legacy();
+
+            for (i, event) in events.0.iter().enumerate() {
+                let error = match event {
+                    Event::SpriteLegacy(sprite) => Object::try_from(sprite.clone()).err(),
+                    Event::SampleLegacy(sample) => AudioSample::try_from(sample.clone()).err(),
+                    _ => None,
+                };
+
+                if let Some(error) = error {
+                    issues.push(ConversionIssue {
+                        section: SectionName::Events,
+                        kind: ConversionIssueKind::LegacyEventNotUpgraded { event: i, error },
+                    });
+                }
+            }
+        }
+
+        issues.extend(
+            self.lossiness_report(version)
+                .into_iter()
+                .map(|loss| ConversionIssue {
+                    section: loss_section(&loss.target),
+                    kind: ConversionIssueKind::Lost(loss),
+                }),
+        );
+        issues.sort_by_key(|issue| issue.section);
+
+        let from = self.version;
+        self.version = version;
+        // every section is written in the new version
+        self.mark_existing_dirty(&SectionName::ALL);
+
+        Ok(ConversionReport {
+            from,
+            to: version,
+            migrated_fields,
+            upgraded_events,
+            issues,
+        })
+    }
+
+    /// Converts a copy of the file to the `version` with [`convert_to`][Self::convert_to], then changes it the way writing it in the version does.
+    /// - Everything that can't be written in the version is dropped, such as colour transformation events in version 14.
+    /// - Values that are read back differently are replaced with what is read back, keeping the [`ObjectId`][super::ObjectId]s, extensions and comments of the objects.
+    /// - Times stay the same in the game, since they are written with the offset of the version, such as the 24ms of versions 3 and 4.
+    /// - Returns the converted file and what was dropped or changed.
+    pub fn convert_to_version(
+        &self,
+        version: Version,
+    ) -> Result<(OsuFile, ConversionReport), ConversionError> {
+        let mut converted = self.clone();
+        let report = converted.convert_to(version)?;
+
+        let losses = report
+            .issues
+            .iter()
+            .filter_map(|issue| match &issue.kind {
+                ConversionIssueKind::Lost(loss) => Some(loss.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        converted.apply_losses(&losses, version);
+
+        Ok((converted, report))
+    }
+
+    /// Drops what the `losses` drop, and replaces what they alter with what is read back after writing it in the `version`.
+    fn apply_losses(&mut self, losses: &[Loss], version: Version) {
+        apply_field_losses!(
+            losses,
+            self.general,
+            super::General,
+            SectionName::General,
+            version
+        );
+        apply_field_losses!(
+            losses,
+            self.editor,
+            super::Editor,
+            SectionName::Editor,
+            version
+        );
+        apply_field_losses!(
+            losses,
+            self.metadata,
+            super::Metadata,
+            SectionName::Metadata,
+            version
+        );
+        apply_field_losses!(
+            losses,
+            self.difficulty,
+            super::Difficulty,
+            SectionName::Difficulty,
+            version
+        );
+
+        if let Some(events) = &mut self.events {
+            apply_event_losses(events, losses, version);
+        }
+
+        apply_section_loss(
+            &mut self.timing_points,
+            SectionName::TimingPoints,
+            losses,
+            version,
+        );
+        apply_item_losses(
+            &mut self.timing_points,
+            losses,
+            version,
+            |timing_points| &mut timing_points.0,
+            |target| match target {
+                LossTarget::TimingPoint(i) => Some(*i),
+                _ => None,
+            },
+            TimingPoint::id,
+            |read_back, original| {
+                read_back.extensions = original.extensions.clone();
+                read_back.comments = original.comments.clone();
+            },
+        );
+        apply_section_loss(&mut self.colours, SectionName::Colours, losses, version);
+        apply_section_loss(
+            &mut self.hitobjects,
+            SectionName::HitObjects,
+            losses,
+            version,
+        );
+        apply_item_losses(
+            &mut self.hitobjects,
+            losses,
+            version,
+            |hitobjects| &mut hitobjects.0,
+            |target| match target {
+                LossTarget::HitObject(i) => Some(*i),
+                _ => None,
+            },
+            HitObject::id,
+            |read_back, original| {
+                read_back.extensions = original.extensions.clone();
+                read_back.comments = original.comments.clone();
+            },
+        );
+    }
+}
@@ -0,0 +1,264 @@
+//! Module for turning errors into plain data, for tools such as language servers that send them to an editor.
+//! - With the `serde` feature, [`Diagnostic`], [`Error`], the error enums, [`ParseWarning`][super::ParseWarning] and the lint types are `Serialize`.
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer};
+
+use super::{
+    colours, difficulty, editor,
+    events::{
+        self,
+        storyboard::error::{ParseCommandError, ParseObjectError},
+        ParseAnimationLegacyError, ParseAudioSampleError, ParseBackgroundError, ParseBreakError,
+        ParseColourTransformationError, ParseLayerLegacyError, ParseOriginTypeLegacyError,
+        ParseSampleLegacyError, ParseSpriteLegacyError, ParseVideoError,
+    },
+    general, hitobjects, metadata, osb, timingpoints, Error, LimitsExceeded, ParseError,
+    ParseSectionError,
+};
+use crate::interop::ParseCueError;
+
+/// Byte range in the input.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Error as plain data.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Diagnostic {
+    /// Name of the error variant, such as `UnknownSection`, which only changes if the variant is renamed.
+    pub kind: String,
+    /// Message of the error.
+    pub message: String,
+    /// Messages of the errors that caused this error, from the closest to the furthest.
+    pub causes: Vec<String>,
+    /// Line number starting from `1`.
+    pub line: Option<usize>,
+    /// Column where the error starts, in characters starting from `1`.
+    /// - Without a more precise position, this is the first character on the line that isn't whitespace.
+    pub column: Option<usize>,
+    /// The part of the line that caused the error, or the line without the whitespace around it.
+    pub span: Option<Span>,
+}
+
+/// Error that names its variants, which is the [`kind`][Diagnostic::kind] of its diagnostics.
+pub trait ErrorKind: std::error::Error {
+    /// Name of the variant, such as `UnknownSection`.
+    fn kind(&self) -> &'static str;
+}
+
+/// Serializes the error enums as a [`Diagnostic`] without a position.
+macro_rules! serialize_as_diagnostic {
+    ($($error:ty),* $(,)?) => {$(
+        #[cfg(feature = "serde")]
+        impl Serialize for $error {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                Diagnostic::from_error(self).serialize(serializer)
+            }
+        }
+    )*};
+}
+
+/// Implements [`ErrorKind`] with the names from the derived [`IntoStaticStr`][strum_macros::IntoStaticStr].
+macro_rules! error_kind {
+    ($($error:ty),* $(,)?) => {$(
+        impl ErrorKind for $error {
+            fn kind(&self) -> &'static str {
+                self.into()
+            }
+        }
+
+        serialize_as_diagnostic!($error);
+    )*};
+}
+
+/// Implements [`ErrorKind`] for errors that only wrap another error, with the kind of the wrapped error.
+macro_rules! wrapped_error_kind {
+    ($($error:ty),* $(,)?) => {$(
+        impl ErrorKind for $error {
+            fn kind(&self) -> &'static str {
+                self.0.kind()
+            }
+        }
+
+        serialize_as_diagnostic!($error);
+    )*};
+}
+
+error_kind!(
+    ParseError,
+    ParseSectionError,
+    LimitsExceeded,
+    general::ParseError,
+    editor::ParseError,
+    metadata::ParseError,
+    difficulty::ParseError,
+    events::ParseError,
+    ParseBackgroundError,
+    ParseVideoError,
+    ParseBreakError,
+    ParseColourTransformationError,
+    ParseSpriteLegacyError,
+    ParseAnimationLegacyError,
+    ParseSampleLegacyError,
+    ParseOriginTypeLegacyError,
+    ParseLayerLegacyError,
+    ParseObjectError,
+    ParseCommandError,
+    ParseAudioSampleError,
+    timingpoints::ParseTimingPointError,
+    colours::ParseRgbError,
+    hitobjects::ParseColonSetError,
+    hitobjects::ParseCurvePointError,
+    hitobjects::ParseHitObjectError,
+    hitobjects::ParseHitObjectTypeNumberError,
+    hitobjects::ParseHitSampleError,
+    osb::ParseVariableError,
+    ParseCueError,
+);
+
+wrapped_error_kind!(
+    timingpoints::ParseError,
+    colours::ParseError,
+    hitobjects::ParseError
+);
+
+// the variants that wrap another error can't be named by `IntoStaticStr`, since they're disabled for `EnumString`
+impl ErrorKind for colours::ParseColourError {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::ParseRgbError(_) => "ParseRgbError",
+            _ => self.into(),
+        }
+    }
+}
+
+impl ErrorKind for osb::ParseError {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::ParseVariableError(_) => "ParseVariableError",
+            Self::ParseEventsError(_) => "ParseEventsError",
+            _ => self.into(),
+        }
+    }
+}
+
+serialize_as_diagnostic!(colours::ParseColourError, osb::ParseError);
+
+impl Diagnostic {
+    /// Creates a diagnostic without a position from an error.
+    pub fn from_error<E: ErrorKind + ?Sized>(error: &E) -> Self {
+        let mut causes = Vec::new();
+        let mut source = error.source();
+        while let Some(error) = source {
+            causes.push(error.to_string());
+            source = error.source();
+        }
+
+        Self {
+            kind: error.kind().to_string(),
+            message: error.to_string(),
+            causes,
+            line: None,
+            column: None,
+            span: None,
+        }
+    }
+}
+
+impl<E: ErrorKind> Error<E> {
+    /// Creates a diagnostic with the position of the line in `input`, which is the input that was parsed.
+    /// - The column and span are `None` if `input` doesn't have the line.
+    pub fn to_diagnostic(&self, input: &str) -> Diagnostic {
+        let mut diagnostic = Diagnostic {
+            line: Some(self.line_index() + 1),
+            ..Diagnostic::from_error(self.error())
+        };
+
+        let mut start = 0;
+        for (i, line) in input.split('\n').enumerate() {
+            if i == self.line_index() {
+                let trimmed = line.trim_start();
+                let column = self.column().unwrap_or_else(|| {
+                    let start = line[..line.len() - trimmed.len()].chars().count();
+                    start..start + trimmed.trim_end().chars().count()
+                });
+                let byte_index = |column: usize| {
+                    line.char_indices()
+                        .nth(column)
+                        .map_or(line.len(), |(i, _)| i)
+                };
+
+                diagnostic.column = Some(column.start + 1);
+                diagnostic.span = Some(Span {
+                    start: start + byte_index(column.start),
+                    end: start + byte_index(column.end),
+                });
+                break;
+            }
+
+            start += line.len() + 1;
+        }
+
+        diagnostic
+    }
+}
+
+impl Diagnostic {
+    /// Renders the diagnostic with the line from `input` and the span underlined, in the style of `rustc` and `ariadne`.
+    /// - Only the message and causes are rendered if the diagnostic doesn't have a position.
+    ///
+    /// ```text
+    /// error: Invalid `meter` value
+    ///  --> 3:10
+    ///   |
+    /// 3 | 1000,500,x,2,0,60,1,0
+    ///   |          ^
+    /// ```
+    pub fn render(&self, input: &str) -> String {
+        let mut rendered = format!("error: {}", self.message);
+
+        if let (Some(line_number), Some(column), Some(span)) = (self.line, self.column, self.span) {
+            let line = input
+                .split('\n')
+                .nth(line_number - 1)
+                .unwrap_or_default()
+                .trim_end_matches('\r');
+            let width = input
+                .get(span.start..span.end)
+                .map_or(0, |s| s.chars().count())
+                .max(1);
+            let gutter = " ".repeat(line_number.to_string().len());
+
+            rendered.push_str(&format!(
+                "\n{gutter}--> {line_number}:{column}\n{gutter} |\n{line_number} | {line}\n{gutter} | {}{}",
+                " ".repeat(column - 1),
+                "^".repeat(width),
+            ));
+        }
+
+        for cause in &self.causes {
+            rendered.push_str(&format!("\n  = caused by: {cause}"));
+        }
+
+        rendered
+    }
+}
+
+/// Serializes as a [`Diagnostic`] without the span, since the input isn't known.
+/// - The column is only included if the error has one.
+#[cfg(feature = "serde")]
+impl<E: ErrorKind> Serialize for Error<E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Diagnostic {
+            line: Some(self.line_index() + 1),
+            column: self.column().map(|column| column.start + 1),
+            ..Diagnostic::from_error(self.error())
+        }
+        .serialize(serializer)
+    }
+}
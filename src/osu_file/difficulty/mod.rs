@@ -1,9 +1,12 @@
 pub mod error;
 
-use rust_decimal::Decimal;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use rust_decimal_macros::dec;
 
 use crate::helper::macros::*;
 
+use super::general::Mode;
+
 pub use error::*;
 
 versioned_field!(HPDrainRate, Decimal, no_versions, |s| { s.parse() } -> rust_decimal::Error,,);
@@ -37,3 +40,59 @@ general_section!(
         SliderTickRate: 1,
     }
 );
+
+/// What the `CS` setting means in a mode, from [`Difficulty::key_count`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum CircleSizeValue {
+    /// Number of keys in osu!mania.
+    Keys(u8),
+    /// Size of the circles in the other modes.
+    CircleSize(Decimal),
+}
+
+impl Difficulty {
+    /// Lowest number of keys in osu!mania.
+    pub const MIN_KEYS: u8 = 1;
+    /// Highest number of keys in osu!mania.
+    pub const MAX_KEYS: u8 = 18;
+
+    /// Settings of a new beatmap of the `mode`, which are `5` for each of `HP`, `CS`, `OD` and `AR`.
+    /// - osu!mania starts with 4 keys, and has no `AR`.
+    /// - osu!taiko has no `AR`, since notes scroll by the slider velocity.
+    pub fn default_for(mode: Mode) -> Difficulty {
+        let mut difficulty = Difficulty::new();
+        difficulty.hp_drain_rate = Some(dec!(5).into());
+        difficulty.circle_size = Some(match mode {
+            Mode::Mania => dec!(4).into(),
+            _ => dec!(5).into(),
+        });
+        difficulty.overall_difficulty = Some(dec!(5).into());
+        if !matches!(mode, Mode::Mania | Mode::Taiko) {
+            difficulty.approach_rate = Some(dec!(5).into());
+        }
+        difficulty.slider_multiplier = Some(dec!(1.4).into());
+        difficulty.slider_tickrate = Some(dec!(1).into());
+
+        difficulty
+    }
+
+    /// What `CS` means in the `mode`, which is the number of keys in osu!mania.
+    /// - Keys are rounded to the nearest whole number with ties going to the even number, as the game does, and clamped to [`MIN_KEYS`][Self::MIN_KEYS] ~ [`MAX_KEYS`][Self::MAX_KEYS].
+    /// - A missing `CS` is `5`.
+    pub fn key_count(&self, mode: Mode) -> CircleSizeValue {
+        let circle_size = self.circle_size.clone().map_or(dec!(5), Into::into);
+
+        match mode {
+            Mode::Mania => {
+                let keys = circle_size
+                    .round()
+                    .to_i64()
+                    .unwrap_or_default()
+                    .clamp(Self::MIN_KEYS as i64, Self::MAX_KEYS as i64);
+
+                CircleSizeValue::Keys(keys as u8)
+            }
+            _ => CircleSizeValue::CircleSize(circle_size),
+        }
+    }
+}
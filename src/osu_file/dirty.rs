@@ -0,0 +1,123 @@
+//! Module for finding the sections of an [`OsuFile`] that changed since it was parsed.
+//! - Each section has a generation counter, which is bumped by the `_mut` accessors and [`OsuFile::mark_dirty`].
+//! - Changes made through the public fields directly aren't seen, since they don't go through the counters.
+
+use super::{Colours, Events, HitObjects, OsuFile, SectionName, TimingPoints};
+
+/// Generation counters of the sections, in the order of [`SectionName::ALL`].
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct SectionGenerations {
+    /// Number of times each section was changed.
+    current: [u64; SectionName::ALL.len()],
+    /// `current` when the file was parsed or marked clean, or `None` if it wasn't.
+    clean: Option<[u64; SectionName::ALL.len()]>,
+}
+
+fn section_index(section: SectionName) -> usize {
+    SectionName::ALL
+        .iter()
+        .position(|name| *name == section)
+        .unwrap()
+}
+
+macro_rules! section_mut {
+    ($(#[$meta:meta])* $name:ident, $field:ident, $section:ident, $ty:ty) => {
+        $(#[$meta])*
+        pub fn $name(&mut self) -> &mut Option<$ty> {
+            self.mark_dirty(SectionName::$section);
+            &mut self.$field
+        }
+    };
+}
+
+impl OsuFile {
+    section_mut!(
+        /// Mutable access to the `[General]` section, which marks it as changed.
+        general_mut, general, General, super::General
+    );
+    section_mut!(
+        /// Mutable access to the `[Editor]` section, which marks it as changed.
+        editor_mut, editor, Editor, super::Editor
+    );
+    section_mut!(
+        /// Mutable access to the `[Metadata]` section, which marks it as changed.
+        metadata_mut, metadata, Metadata, super::Metadata
+    );
+    section_mut!(
+        /// Mutable access to the `[Difficulty]` section, which marks it as changed.
+        difficulty_mut, difficulty, Difficulty, super::Difficulty
+    );
+    section_mut!(
+        /// Mutable access to the `[Events]` section, which marks it as changed.
+        events_mut, events, Events, Events
+    );
+    section_mut!(
+        /// Mutable access to the `[TimingPoints]` section, which marks it as changed.
+        timing_points_mut, timing_points, TimingPoints, TimingPoints
+    );
+    section_mut!(
+        /// Mutable access to the `[Colours]` section, which marks it as changed.
+        colours_mut, colours, Colours, Colours
+    );
+    section_mut!(
+        /// Mutable access to the `[HitObjects]` section, which marks it as changed.
+        hitobjects_mut, hitobjects, HitObjects, HitObjects
+    );
+
+    pub(crate) fn section_exists(&self, section: SectionName) -> bool {
+        match section {
+            SectionName::General => self.general.is_some(),
+            SectionName::Editor => self.editor.is_some(),
+            SectionName::Metadata => self.metadata.is_some(),
+            SectionName::Difficulty => self.difficulty.is_some(),
+            SectionName::Events => self.events.is_some(),
+            SectionName::TimingPoints => self.timing_points.is_some(),
+            SectionName::Colours => self.colours.is_some(),
+            SectionName::HitObjects => self.hitobjects.is_some(),
+        }
+    }
+
+    /// Marks the section as changed, for changes made through the public fields.
+    pub fn mark_dirty(&mut self, section: SectionName) {
+        let generation = &mut self.generations.0.current[section_index(section)];
+        *generation = generation.wrapping_add(1);
+    }
+
+    /// Marks the `sections` that exist as changed, used by the methods that change the file.
+    pub(crate) fn mark_existing_dirty(&mut self, sections: &[SectionName]) {
+        for section in sections {
+            if self.section_exists(*section) {
+                self.mark_dirty(*section);
+            }
+        }
+    }
+
+    /// Sections that were changed since the file was parsed or [`mark_clean`][Self::mark_clean] was called.
+    /// - A section counts as changed once it's accessed with its `_mut` accessor, such as [`hitobjects_mut`][Self::hitobjects_mut], or marked with [`mark_dirty`][Self::mark_dirty], even if its contents end up the same.
+    /// - Files that weren't parsed report every section that exists.
+    /// - Only compares the counters of the sections, so it doesn't depend on the size of the file.
+    pub fn dirty_sections(&self) -> Vec<SectionName> {
+        let generations = &self.generations.0;
+
+        SectionName::ALL
+            .into_iter()
+            .enumerate()
+            .filter(|(i, section)| match &generations.clean {
+                Some(clean) => clean[*i] != generations.current[*i],
+                None => generations.current[*i] != 0 || self.section_exists(*section),
+            })
+            .map(|(_, section)| section)
+            .collect()
+    }
+
+    /// Returns `true` if any section was changed since the file was parsed or [`mark_clean`][Self::mark_clean] was called.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty_sections().is_empty()
+    }
+
+    /// Marks every section as unchanged, such as after saving the file.
+    pub fn mark_clean(&mut self) {
+        let generations = &mut self.generations.0;
+        generations.clean = Some(generations.current);
+    }
+}
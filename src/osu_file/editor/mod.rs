@@ -4,6 +4,7 @@ use std::num::{IntErrorKind, ParseIntError};
 
 use nom::{bytes::complete::take_till, combinator::map_res, multi::separated_list0, Finish};
 use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 
 use crate::parsers::comma;
 
@@ -62,6 +63,27 @@ versioned_field!(CurrentTime, Integer, no_versions, |s| { s.parse() } -> ParseIn
 }
 ,);
 
+impl DistanceSpacing {
+    /// Range of distance snap multipliers the editor allows.
+    pub const RANGE: std::ops::RangeInclusive<Decimal> = dec!(0.1)..=dec!(6);
+
+    /// Returns `true` if the multiplier is within [`RANGE`][Self::RANGE].
+    pub fn is_in_range(&self) -> bool {
+        Self::RANGE.contains(&self.0)
+    }
+}
+
+impl GridSize {
+    /// Grid sizes the editor can be set to, from the smallest.
+    /// - Other sizes are still parsed and written back the same, but are reported by [`OsuFile::lint_editor`][crate::osu_file::OsuFile::lint_editor].
+    pub const KNOWN: [Integer; 4] = [4, 8, 16, 32];
+
+    /// Returns `true` if the size is one of the [`KNOWN`][Self::KNOWN] sizes.
+    pub fn is_known(&self) -> bool {
+        Self::KNOWN.contains(&self.0)
+    }
+}
+
 general_section!(
     /// A struct representing the editor section of the .osu file.
     pub struct Editor {
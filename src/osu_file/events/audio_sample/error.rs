@@ -26,7 +26,7 @@ pub enum ParseAudioSampleError {
     MissingFilepath,
 }
 
-verbose_error_to_error!(ParseAudioSampleError);
+verbose_error_to_error!(ParseAudioSampleError, ParseAudioSampleError::WrongEvent);
 
 #[derive(Debug, Error)]
 #[non_exhaustive]
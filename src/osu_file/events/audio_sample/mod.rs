@@ -4,10 +4,9 @@ pub use error::*;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    combinator::{complete, eof, map_res},
+    combinator::{complete, eof, map_opt, map_res},
     error::context,
     sequence::{preceded, tuple},
-    Parser,
 };
 
 use crate::{
@@ -15,10 +14,11 @@ use crate::{
         FilePath, Integer, InvalidRepr, Version, VersionedDefault, VersionedFrom,
         VersionedFromRepr, VersionedFromStr, VersionedToString, VersionedTryFrom,
     },
-    parsers::{comma, comma_field, comma_field_type},
+    parsers::{comma, comma_field, comma_field_type, file_path_field},
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AudioSample {
     pub time: Integer,
     pub layer: Layer,
@@ -46,20 +46,20 @@ impl VersionedFromStr for AudioSample {
                     ParseAudioSampleError::InvalidLayer.into(),
                     comma_field_type(),
                 ),
-                // for now all of those unwraps are safe, can change on the future versions though
-                |layer| Layer::from_repr(layer, version).map(|layer| layer.unwrap()),
+                |layer| Layer::from_repr(layer, version).and_then(|layer| layer.ok_or(InvalidRepr)),
             ),
         );
-        let filepath = comma_field().map(|p| p.into());
+        let filepath = file_path_field();
         let volume = alt((
-            eof.map(|_| Volume::default(version).unwrap()),
+            map_opt(eof, |_| Volume::default(version)),
             preceded(
                 context(ParseAudioSampleError::MissingVolume.into(), comma()),
                 context(
                     ParseAudioSampleError::InvalidVolume.into(),
-                    map_res::<_, _, _, _, ParseVolumeError, _, _>(comma_field(), |s| {
-                        Ok(Volume::from_str(s, version)?.unwrap())
-                    }),
+                    map_opt(
+                        map_res(comma_field(), |s| Volume::from_str(s, version)),
+                        |volume| volume,
+                    ),
                 ),
             ),
         ));
@@ -105,6 +105,7 @@ impl VersionedToString for AudioSample {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Volume(u8);
 
 impl VersionedDefault for Volume {
@@ -164,6 +165,7 @@ impl VersionedToString for Volume {
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum Layer {
     Background,
@@ -0,0 +1,130 @@
+//! Module for viewing the `[Events]` section as a tree of events, commands and the commands nested in loops and triggers.
+//! - Meant for storyboard debugging tools, and for checking how the indentation of commands was parsed.
+
+use super::{
+    storyboard::{
+        cmds::{Command, CommandProperties},
+        sprites::ObjectType,
+    },
+    Event, EventWithCommands, Events,
+};
+use crate::helper::write_json_string;
+use crate::osu_file::{Version, VersionedToString};
+
+/// Node of the tree from [`Events::to_debug_tree`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct DebugNode {
+    /// Kind of the node, such as `Sprite`, `Fade` or `Loop`.
+    pub kind: &'static str,
+    /// Line written for the node without the indentation or nested commands.
+    pub line: String,
+    /// Commands of an event, or the commands in a loop or trigger.
+    pub children: Vec<DebugNode>,
+}
+
+impl DebugNode {
+    /// Writes the node and its children as a JSON object with the `kind`, `line` and `children` keys.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        self.write_json(&mut json);
+        json
+    }
+
+    fn write_json(&self, json: &mut String) {
+        json.push_str("{\"kind\":");
+        write_json_string(json, self.kind);
+        json.push_str(",\"line\":");
+        write_json_string(json, &self.line);
+        json.push_str(",\"children\":[");
+
+        for (i, child) in self.children.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            child.write_json(json);
+        }
+
+        json.push_str("]}");
+    }
+}
+
+fn first_line(s: Option<String>) -> String {
+    s.and_then(|s| s.lines().next().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn command_node(command: &Command, version: Version) -> DebugNode {
+    let (kind, children): (_, &[Command]) = match &command.properties {
+        CommandProperties::Fade { .. } => ("Fade", &[]),
+        CommandProperties::Move { .. } => ("Move", &[]),
+        CommandProperties::MoveX { .. } => ("MoveX", &[]),
+        CommandProperties::MoveY { .. } => ("MoveY", &[]),
+        CommandProperties::Scale { .. } => ("Scale", &[]),
+        CommandProperties::VectorScale { .. } => ("VectorScale", &[]),
+        CommandProperties::Rotate { .. } => ("Rotate", &[]),
+        CommandProperties::Colour { .. } => ("Colour", &[]),
+        CommandProperties::Parameter { .. } => ("Parameter", &[]),
+        CommandProperties::Loop { commands, .. } => ("Loop", commands),
+        CommandProperties::Trigger { commands, .. } => ("Trigger", commands),
+    };
+
+    DebugNode {
+        kind,
+        line: first_line(command.to_string(version)),
+        children: command_nodes(children, version),
+    }
+}
+
+fn command_nodes(commands: &[Command], version: Version) -> Vec<DebugNode> {
+    commands
+        .iter()
+        .map(|command| command_node(command, version))
+        .collect()
+}
+
+impl Events {
+    /// Tree of the events, with the commands of each event and the commands in loops and triggers as children.
+    /// - The root node has the kind `Events` and an empty line.
+    /// - Lines are written in `version`, and are empty for events that can't be written in it.
+    pub fn to_debug_tree(&self, version: Version) -> DebugNode {
+        let children = self
+            .0
+            .iter()
+            .map(|event| {
+                let (kind, commands) = match event {
+                    Event::Comment(_) => ("Comment", None),
+                    Event::Background(background) => ("Background", Some(background.commands())),
+                    Event::Video(video) => ("Video", Some(video.commands())),
+                    Event::Break(_) => ("Break", None),
+                    Event::ColourTransformation(_) => ("ColourTransformation", None),
+                    Event::SpriteLegacy(sprite) => ("SpriteLegacy", Some(sprite.commands())),
+                    Event::AnimationLegacy(animation) => {
+                        ("AnimationLegacy", Some(animation.commands()))
+                    }
+                    Event::SampleLegacy(sample) => ("SampleLegacy", Some(sample.commands())),
+                    Event::StoryboardObject(object) => {
+                        let kind = match object.object_type {
+                            ObjectType::Sprite(_) => "Sprite",
+                            ObjectType::Animation(_) => "Animation",
+                        };
+
+                        (kind, Some(object.commands()))
+                    }
+                    Event::AudioSample(_) => ("Sample", None),
+                };
+
+                DebugNode {
+                    kind,
+                    line: first_line(event.to_string(version)),
+                    children: command_nodes(commands.unwrap_or_default(), version),
+                }
+            })
+            .collect();
+
+        DebugNode {
+            kind: "Events",
+            line: String::new(),
+            children,
+        }
+    }
+}
@@ -1,9 +1,10 @@
+use strum_macros::IntoStaticStr;
 use thiserror::Error;
 
 use super::{storyboard::error::*, *};
 
 /// Errors used when there was a problem parsing an [`Event`][super::Event] from a `str`.
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
 #[non_exhaustive]
 pub enum ParseError {
     /// When the line isn't in a `key: value` format.
@@ -45,3 +46,24 @@ pub enum ParseError {
     #[error("Unknown event type")]
     UnknownEventType,
 }
+
+/// Error used when converting between a legacy event and the modern event fails.
+#[derive(Debug, Error, Clone, Copy, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LegacyConversionError {
+    /// The layer doesn't exist in the converted event, such as the legacy `Video` layer.
+    #[error("The layer doesn't exist in the converted event")]
+    UnsupportedLayer,
+    /// The origin is an undocumented value, which has no legacy equivalent.
+    #[error("Undocumented origin `{0}` has no legacy equivalent")]
+    UnknownOrigin(Integer),
+    /// Only sprites can be converted into a legacy sprite.
+    #[error("The storyboard object isn't a sprite")]
+    NotSprite,
+    /// The legacy sample has commands, which an audio sample can't have.
+    #[error("The sample has commands, which an audio sample can't have")]
+    SampleHasCommands,
+    /// The time doesn't fit in the converted event.
+    #[error("The time is out of range")]
+    TimeOutOfRange,
+}
@@ -0,0 +1,203 @@
+//! Module for converting between legacy events and the modern storyboard events.
+//! - `AnimationLegacy` has no conversion, since it doesn't have the frame count, frame delay and loop type of an animation.
+
+use either::Either;
+use rust_decimal::{prelude::ToPrimitive, Decimal, RoundingStrategy};
+
+use super::{
+    audio_sample,
+    normal_event::types::{LayerLegacy, OriginTypeLegacy},
+    storyboard::sprites::{Layer, Object, ObjectType, Origin, OriginType, Sprite},
+    AudioSample, Event, Events, LegacyConversionError, SampleLegacy, SpriteLegacy, Volume,
+};
+use crate::osu_file::{Extensions, Position, VersionedDefault, LATEST_VERSION};
+
+fn layer(layer: LayerLegacy) -> Result<Layer, LegacyConversionError> {
+    match layer {
+        LayerLegacy::Background => Ok(Layer::Background),
+        LayerLegacy::Fail => Ok(Layer::Fail),
+        LayerLegacy::Pass => Ok(Layer::Pass),
+        LayerLegacy::Foreground => Ok(Layer::Foreground),
+        LayerLegacy::Overlay => Ok(Layer::Overlay),
+        LayerLegacy::Video => Err(LegacyConversionError::UnsupportedLayer),
+    }
+}
+
+fn layer_legacy(layer: Layer) -> LayerLegacy {
+    match layer {
+        Layer::Background => LayerLegacy::Background,
+        Layer::Fail => LayerLegacy::Fail,
+        Layer::Pass => LayerLegacy::Pass,
+        Layer::Foreground => LayerLegacy::Foreground,
+        Layer::Overlay => LayerLegacy::Overlay,
+    }
+}
+
+fn origin_type(origin: OriginTypeLegacy) -> OriginType {
+    match origin {
+        OriginTypeLegacy::TopLeft => OriginType::TopLeft,
+        OriginTypeLegacy::Centre => OriginType::Centre,
+        OriginTypeLegacy::CentreLeft => OriginType::CentreLeft,
+        OriginTypeLegacy::TopRight => OriginType::TopRight,
+        OriginTypeLegacy::BottomCentre => OriginType::BottomCentre,
+        OriginTypeLegacy::TopCentre => OriginType::TopCentre,
+        OriginTypeLegacy::Custom => OriginType::Custom,
+        OriginTypeLegacy::CentreRight => OriginType::CentreRight,
+        OriginTypeLegacy::BottomLeft => OriginType::BottomLeft,
+        OriginTypeLegacy::BottomRight => OriginType::BottomRight,
+    }
+}
+
+fn origin_type_legacy(origin: Origin) -> Result<OriginTypeLegacy, LegacyConversionError> {
+    let origin = match origin.type_ {
+        Either::Left(origin) => origin,
+        Either::Right(origin) => return Err(LegacyConversionError::UnknownOrigin(origin)),
+    };
+
+    Ok(match origin {
+        OriginType::TopLeft => OriginTypeLegacy::TopLeft,
+        OriginType::Centre => OriginTypeLegacy::Centre,
+        OriginType::CentreLeft => OriginTypeLegacy::CentreLeft,
+        OriginType::TopRight => OriginTypeLegacy::TopRight,
+        OriginType::BottomCentre => OriginTypeLegacy::BottomCentre,
+        OriginType::TopCentre => OriginTypeLegacy::TopCentre,
+        OriginType::Custom => OriginTypeLegacy::Custom,
+        OriginType::CentreRight => OriginTypeLegacy::CentreRight,
+        OriginType::BottomLeft => OriginTypeLegacy::BottomLeft,
+        OriginType::BottomRight => OriginTypeLegacy::BottomRight,
+    })
+}
+
+fn sample_layer(layer: LayerLegacy) -> Result<audio_sample::Layer, LegacyConversionError> {
+    match layer {
+        LayerLegacy::Background => Ok(audio_sample::Layer::Background),
+        LayerLegacy::Fail => Ok(audio_sample::Layer::Fail),
+        LayerLegacy::Pass => Ok(audio_sample::Layer::Pass),
+        LayerLegacy::Foreground => Ok(audio_sample::Layer::Foreground),
+        LayerLegacy::Overlay | LayerLegacy::Video => Err(LegacyConversionError::UnsupportedLayer),
+    }
+}
+
+fn sample_layer_legacy(layer: audio_sample::Layer) -> LayerLegacy {
+    match layer {
+        audio_sample::Layer::Background => LayerLegacy::Background,
+        audio_sample::Layer::Fail => LayerLegacy::Fail,
+        audio_sample::Layer::Pass => LayerLegacy::Pass,
+        audio_sample::Layer::Foreground => LayerLegacy::Foreground,
+    }
+}
+
+impl TryFrom<SpriteLegacy> for Object {
+    type Error = LegacyConversionError;
+
+    /// Converts the legacy sprite into a sprite object.
+    /// - A missing position becomes `0,0`, which is where the game shows it.
+    /// - The origin stays written as a number.
+    /// - Fails if the sprite is in the `Video` layer.
+    fn try_from(sprite: SpriteLegacy) -> Result<Self, Self::Error> {
+        Ok(Object {
+            layer: layer(sprite.layer)?,
+            layer_shorthand: false,
+            origin: Origin::from(origin_type(sprite.origin)),
+            position: sprite.position.unwrap_or(Position {
+                x: Decimal::ZERO,
+                y: Decimal::ZERO,
+            }),
+            object_type: ObjectType::Sprite(Sprite {
+                filepath: sprite.file_name,
+            }),
+            commands: sprite.commands,
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+impl TryFrom<Object> for SpriteLegacy {
+    type Error = LegacyConversionError;
+
+    /// Converts the sprite object into a legacy sprite.
+    /// - Fails if the object is an animation or the origin is undocumented.
+    fn try_from(object: Object) -> Result<Self, Self::Error> {
+        let file_name = match object.object_type {
+            ObjectType::Sprite(sprite) => sprite.filepath,
+            ObjectType::Animation(_) => return Err(LegacyConversionError::NotSprite),
+        };
+
+        Ok(SpriteLegacy {
+            layer: layer_legacy(object.layer),
+            origin: origin_type_legacy(object.origin)?,
+            file_name,
+            position: Some(object.position),
+            commands: object.commands,
+        })
+    }
+}
+
+impl TryFrom<SampleLegacy> for AudioSample {
+    type Error = LegacyConversionError;
+
+    /// Converts the legacy sample into an audio sample.
+    /// - The time is rounded to the nearest millisecond.
+    /// - A missing volume becomes the default volume of `100`.
+    /// - Fails if the sample is in the `Overlay` or `Video` layer, or if it has commands.
+    fn try_from(sample: SampleLegacy) -> Result<Self, Self::Error> {
+        if !sample.commands.is_empty() {
+            return Err(LegacyConversionError::SampleHasCommands);
+        }
+
+        let time = sample
+            .time
+            .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+            .to_i32()
+            .ok_or(LegacyConversionError::TimeOutOfRange)?;
+
+        Ok(AudioSample {
+            time,
+            layer: sample_layer(sample.layer)?,
+            filepath: sample.file_name,
+            volume: sample
+                .volume
+                .unwrap_or_else(|| Volume::default(LATEST_VERSION).unwrap()),
+        })
+    }
+}
+
+impl From<AudioSample> for SampleLegacy {
+    fn from(sample: AudioSample) -> Self {
+        SampleLegacy {
+            time: Decimal::from(sample.time),
+            layer: sample_layer_legacy(sample.layer),
+            file_name: sample.filepath,
+            volume: Some(sample.volume),
+            commands: Vec::new(),
+        }
+    }
+}
+
+impl Events {
+    /// Converts the legacy sprites and samples into [`Object`] and [`AudioSample`] events, used when upgrading to version 14.
+    /// - Events that can't be converted are kept as they are, see the `TryFrom` implementations for when that happens.
+    /// - Returns how many events were converted.
+    pub fn upgrade_legacy(&mut self) -> usize {
+        let mut converted = 0;
+
+        for event in &mut self.0 {
+            let upgraded = match event {
+                Event::SpriteLegacy(sprite) => Object::try_from(sprite.clone())
+                    .ok()
+                    .map(Event::StoryboardObject),
+                Event::SampleLegacy(sample) => AudioSample::try_from(sample.clone())
+                    .ok()
+                    .map(Event::AudioSample),
+                _ => None,
+            };
+
+            if let Some(upgraded) = upgraded {
+                *event = upgraded;
+                converted += 1;
+            }
+        }
+
+        converted
+    }
+}
@@ -1,7 +1,12 @@
 pub mod audio_sample;
+pub mod debug_tree;
 pub mod error;
 pub mod normal_event;
 pub mod storyboard;
+pub mod time_range;
+
+mod legacy;
+mod semantic;
 
 use nom::branch::alt;
 use nom::combinator::{cut, eof, peek, success};
@@ -14,18 +19,23 @@ use crate::helper::trait_ext::MapOptStringNewLine;
 use crate::osb::Variable;
 use crate::parsers::comma;
 
+use self::normal_event::types::LayerLegacy;
 use self::storyboard::cmds::Command;
 use self::storyboard::error::CommandPushError;
-use self::storyboard::{error::ParseObjectError, sprites::Object};
+use self::storyboard::{error::ParseObjectError, sprites, sprites::Object};
 
 use super::Version;
-use super::{types::Error, Integer, VersionedDefault, VersionedFromStr, VersionedToString};
+use super::{
+    types::Error, Integer, Position, VersionedDefault, VersionedFromStr, VersionedToString,
+};
 
 pub use audio_sample::*;
 pub use error::*;
 pub use normal_event::*;
+pub use time_range::*;
 
 #[derive(Default, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Events(pub Vec<Event>);
 
 const OLD_VERSION_TIME_OFFSET: Integer = 24;
@@ -312,6 +322,67 @@ impl Events {
 
         Some(s.map_string_new_line())
     }
+
+    /// Copy of the events where the origin and layer of storyboard objects are written by name, or as numbers if `named` is `false`.
+    pub(crate) fn with_named_enums(&self, named: bool) -> Events {
+        let mut events = self.clone();
+
+        for event in &mut events.0 {
+            if let Event::StoryboardObject(object) = event {
+                object.origin.shorthand = !named;
+                object.layer_shorthand = !named;
+            }
+        }
+
+        events
+    }
+
+    /// Copy of the events where the headers of videos and breaks are written as numbers, or by name if `numeric` is `false`.
+    pub(crate) fn with_numeric_headers(&self, numeric: bool) -> Events {
+        let mut events = self.clone();
+
+        for event in &mut events.0 {
+            match event {
+                Event::Video(video) => video.set_short_hand(numeric),
+                Event::Break(b) => b.set_short_hand(numeric),
+                _ => (),
+            }
+        }
+
+        events
+    }
+
+    /// Copy of the events where backgrounds and videos without a position are written with `0,0`.
+    pub(crate) fn with_positions(&self) -> Events {
+        let mut events = self.clone();
+
+        for event in &mut events.0 {
+            let position = match event {
+                Event::Background(background) => &mut background.position,
+                Event::Video(video) => &mut video.position,
+                _ => continue,
+            };
+
+            if position.is_none() {
+                *position = Some(Position::default());
+            }
+        }
+
+        events
+    }
+
+    /// Copy of the events where the file names of backgrounds are wrapped in double quotes.
+    pub(crate) fn with_quoted_backgrounds(&self) -> Events {
+        let mut events = self.clone();
+
+        for event in &mut events.0 {
+            if let Event::Background(background) = event {
+                background.file_name.set_quoted(true);
+            }
+        }
+
+        events
+    }
 }
 
 impl VersionedToString for Events {
@@ -327,6 +398,7 @@ impl VersionedDefault for Events {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 /// All possible events types.
 pub enum Event {
@@ -349,6 +421,33 @@ impl VersionedToString for Event {
 }
 
 impl Event {
+    /// Storyboard layer the event is in, or `None` if it isn't a storyboard element.
+    /// - Audio samples use the layer with the same name.
+    pub fn storyboard_layer(&self) -> Option<sprites::Layer> {
+        let legacy = |layer: LayerLegacy| match layer {
+            LayerLegacy::Background => Some(sprites::Layer::Background),
+            LayerLegacy::Fail => Some(sprites::Layer::Fail),
+            LayerLegacy::Pass => Some(sprites::Layer::Pass),
+            LayerLegacy::Foreground => Some(sprites::Layer::Foreground),
+            LayerLegacy::Overlay => Some(sprites::Layer::Overlay),
+            LayerLegacy::Video => None,
+        };
+
+        match self {
+            Event::SpriteLegacy(sprite) => legacy(sprite.layer),
+            Event::AnimationLegacy(animation) => legacy(animation.layer),
+            Event::SampleLegacy(sample) => legacy(sample.layer),
+            Event::StoryboardObject(object) => Some(object.layer),
+            Event::AudioSample(sample) => Some(match sample.layer {
+                audio_sample::Layer::Background => sprites::Layer::Background,
+                audio_sample::Layer::Fail => sprites::Layer::Fail,
+                audio_sample::Layer::Pass => sprites::Layer::Pass,
+                audio_sample::Layer::Foreground => sprites::Layer::Foreground,
+            }),
+            _ => None,
+        }
+    }
+
     pub fn to_string_variables(&self, version: Version, variables: &[Variable]) -> Option<String> {
         match self {
             Event::Comment(comment) => Some(format!("//{comment}")),
@@ -376,7 +475,7 @@ fn commands_to_string_variables(
     for cmd in cmds {
         builder.push(format!(
             "{}{}",
-            " ".repeat(indentation),
+            cmd.indent_char.repeat(indentation),
             cmd.to_string_variables(version, variables).unwrap()
         ));
 
@@ -401,7 +500,7 @@ fn commands_to_string_variables(
 
                 builder.push(format!(
                     "{}{}",
-                    " ".repeat(indentation),
+                    cmd.indent_char.repeat(indentation),
                     cmd.to_string_variables(version, variables).unwrap()
                 ));
                 match &cmd.properties {
@@ -442,45 +541,45 @@ fn commands_to_string_variables(
     Some(builder.join("\n"))
 }
 
+/// Depth of the last command, following the last command of every loop and trigger.
+fn last_depth(cmds: &[Command]) -> usize {
+    match cmds.last() {
+        Some(cmd) => match &cmd.properties {
+            CommandProperties::Loop { commands, .. }
+            | CommandProperties::Trigger { commands, .. } => 1 + last_depth(commands),
+            _ => 1,
+        },
+        None => 0,
+    }
+}
+
 pub trait EventWithCommands {
+    /// Pushes the command at the `indentation` depth, which is the number of indentation characters before it.
+    /// - The depth can only increase by 1 from the previous command, and only after a loop or trigger.
     fn try_push_cmd(&mut self, cmd: Command, indentation: usize) -> Result<(), CommandPushError> {
-        if indentation == 1 {
-            // first match no loop required
-            self.commands_mut().push(cmd);
-            Ok(())
-        } else {
-            let mut last_cmd = match self.commands_mut().last_mut() {
-                Some(last_cmd) => last_cmd,
-                None => return Err(CommandPushError::InvalidIndentation(1, indentation)),
-            };
+        let previous = last_depth(self.commands());
 
-            for i in 1..indentation {
-                last_cmd = if let CommandProperties::Loop { commands, .. }
-                | CommandProperties::Trigger { commands, .. } =
-                    &mut last_cmd.properties
-                {
-                    if i + 1 == indentation {
-                        // last item
-                        commands.push(cmd);
-                        return Ok(());
-                    } else {
-                        match commands.last_mut() {
-                            Some(sub_cmd) => sub_cmd,
-                            None => {
-                                return Err(CommandPushError::InvalidIndentation(
-                                    i - 1,
-                                    indentation,
-                                ))
-                            }
-                        }
-                    }
-                } else {
-                    return Err(CommandPushError::InvalidIndentation(1, indentation));
-                };
-            }
+        if indentation == 0 || indentation > previous + 1 {
+            return Err(CommandPushError::IndentationJump {
+                previous,
+                depth: indentation,
+            });
+        }
+
+        let mut cmds = self.commands_mut();
 
-            unreachable!();
+        for depth in 1..indentation {
+            cmds = match cmds.last_mut().map(|cmd| &mut cmd.properties) {
+                Some(
+                    CommandProperties::Loop { commands, .. }
+                    | CommandProperties::Trigger { commands, .. },
+                ) => commands,
+                _ => return Err(CommandPushError::InvalidIndentation(depth, indentation)),
+            };
         }
+
+        cmds.push(cmd);
+        Ok(())
     }
 
     fn commands(&self) -> &[Command];
@@ -26,7 +26,7 @@ pub enum ParseBackgroundError {
     InvalidY,
 }
 
-verbose_error_to_error!(ParseBackgroundError);
+verbose_error_to_error!(ParseBackgroundError, ParseBackgroundError::WrongEventType);
 
 #[derive(Debug, Error, IntoStaticStr, EnumString)]
 #[non_exhaustive]
@@ -49,7 +49,7 @@ pub enum ParseVideoError {
     InvalidY,
 }
 
-verbose_error_to_error!(ParseVideoError);
+verbose_error_to_error!(ParseVideoError, ParseVideoError::WrongEventType);
 
 #[derive(Debug, Error, IntoStaticStr, EnumString)]
 #[non_exhaustive]
@@ -66,7 +66,7 @@ pub enum ParseBreakError {
     InvalidEndTime,
 }
 
-verbose_error_to_error!(ParseBreakError);
+verbose_error_to_error!(ParseBreakError, ParseBreakError::WrongEventType);
 
 #[derive(Debug, Error, IntoStaticStr, EnumString)]
 #[non_exhaustive]
@@ -95,7 +95,10 @@ pub enum ParseColourTransformationError {
     InvalidBlue,
 }
 
-verbose_error_to_error!(ParseColourTransformationError);
+verbose_error_to_error!(
+    ParseColourTransformationError,
+    ParseColourTransformationError::WrongEventType
+);
 
 #[derive(Debug, Error, IntoStaticStr, EnumString)]
 #[non_exhaustive]
@@ -122,7 +125,10 @@ pub enum ParseSpriteLegacyError {
     InvalidY,
 }
 
-verbose_error_to_error!(ParseSpriteLegacyError);
+verbose_error_to_error!(
+    ParseSpriteLegacyError,
+    ParseSpriteLegacyError::WrongEventType
+);
 
 #[derive(Debug, Error, IntoStaticStr, EnumString)]
 #[non_exhaustive]
@@ -149,7 +155,10 @@ pub enum ParseAnimationLegacyError {
     InvalidY,
 }
 
-verbose_error_to_error!(ParseAnimationLegacyError);
+verbose_error_to_error!(
+    ParseAnimationLegacyError,
+    ParseAnimationLegacyError::WrongEventType
+);
 
 #[derive(Debug, Error, IntoStaticStr, EnumString)]
 #[non_exhaustive]
@@ -172,7 +181,10 @@ pub enum ParseSampleLegacyError {
     InvalidLayer,
 }
 
-verbose_error_to_error!(ParseSampleLegacyError);
+verbose_error_to_error!(
+    ParseSampleLegacyError,
+    ParseSampleLegacyError::WrongEventType
+);
 
 #[derive(Debug, Error, IntoStaticStr)]
 #[non_exhaustive]
@@ -11,8 +11,8 @@ use rust_decimal::Decimal;
 use crate::{
     osu_file::{FilePath, Integer, Position, Version, VersionedFromStr, VersionedToString},
     parsers::{
-        comma, comma_field, comma_field_type, comma_field_versioned_type, consume_rest_type,
-        consume_rest_versioned_type,
+        comma, comma_field_type, comma_field_versioned_type, consume_rest_type,
+        consume_rest_versioned_type, file_path_field,
     },
 };
 
@@ -45,9 +45,12 @@ fn time_to_string(time: Integer, version: Version) -> String {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Background {
     pub start_time: Integer,
     pub file_name: FilePath,
+    /// Offset from the centre of the screen, which is `None` if it was left out like old editors do.
+    /// - A missing position is the same as `0,0`, and can be written out with [`WriteOptions::always_write_event_position`][crate::osu_file::WriteOptions::always_write_event_position].
     pub position: Option<Position>,
     pub commands: Vec<Command>,
 }
@@ -118,9 +121,12 @@ impl EventWithCommands for Background {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Video {
     pub start_time: Integer,
     pub file_name: FilePath,
+    /// Offset from the centre of the screen, which is `None` if it was left out like old editors do.
+    /// - A missing position is the same as `0,0`, and can be written out with [`WriteOptions::always_write_event_position`][crate::osu_file::WriteOptions::always_write_event_position].
     pub position: Option<Position>,
     pub commands: Vec<Command>,
     short_hand: bool,
@@ -136,6 +142,16 @@ impl Video {
             short_hand: true,
         }
     }
+
+    /// Returns `true` if the header is written as `1`, or `false` if it's written as `Video`.
+    pub fn is_short_hand(&self) -> bool {
+        self.short_hand
+    }
+
+    /// Sets if the header is written as `1` or `Video`.
+    pub fn set_short_hand(&mut self, short_hand: bool) {
+        self.short_hand = short_hand;
+    }
 }
 
 pub const VIDEO_HEADER: &str = "1";
@@ -209,6 +225,7 @@ impl EventWithCommands for Video {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Break {
     pub start_time: Integer,
     pub end_time: Integer,
@@ -223,6 +240,16 @@ impl Break {
             short_hand: true,
         }
     }
+
+    /// Returns `true` if the header is written as `2`, or `false` if it's written as `Break`.
+    pub fn is_short_hand(&self) -> bool {
+        self.short_hand
+    }
+
+    /// Sets if the header is written as `2` or `Break`.
+    pub fn set_short_hand(&mut self, short_hand: bool) {
+        self.short_hand = short_hand;
+    }
 }
 
 pub const BREAK_HEADER: &str = "2";
@@ -274,6 +301,7 @@ impl VersionedToString for Break {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColourTransformation {
     pub start_time: Integer,
     pub red: u8,
@@ -353,6 +381,7 @@ impl VersionedToString for ColourTransformation {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Legacy version of Sprite event.
 pub struct SpriteLegacy {
     pub layer: LayerLegacy,
@@ -437,6 +466,7 @@ impl EventWithCommands for SpriteLegacy {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AnimationLegacy {
     pub layer: LayerLegacy,
     pub origin: OriginTypeLegacy,
@@ -517,6 +547,7 @@ impl EventWithCommands for AnimationLegacy {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SampleLegacy {
     pub time: Decimal,
     pub layer: LayerLegacy,
@@ -552,7 +583,7 @@ impl VersionedFromStr for SampleLegacy {
                 ),
                 preceded(
                     context(ParseSampleLegacyError::MissingFileName.into(), comma()),
-                    comma_field().map(|f| f.into()),
+                    file_path_field(),
                 ),
                 alt((
                     eof.map(|_| None),
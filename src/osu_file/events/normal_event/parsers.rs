@@ -19,7 +19,7 @@ pub fn file_name_and_position<'a>(
 ) -> impl FnMut(
     &'a str,
 ) -> IResult<&'a str, (FilePath, Option<Position>), nom::error::VerboseError<&'a str>> {
-    let file_name = comma_field().map(|f| f.into());
+    let file_name = file_path_field();
     let coordinates = alt((
         eof.map(|_| None),
         tuple((
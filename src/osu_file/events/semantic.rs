@@ -0,0 +1,367 @@
+//! Module for comparing [`Events`] while ignoring how they are written.
+
+use std::iter;
+
+use rust_decimal::Decimal;
+
+use super::{
+    storyboard::{
+        cmds::{Colours, Command, CommandProperties, ContinuingFields, IndentChar},
+        sprites::ObjectType,
+    },
+    Background, Break, Event, Events, Video,
+};
+use crate::osu_file::{FilePath, Integer, Position};
+
+fn file_path(path: &FilePath) -> FilePath {
+    let mut path = path.clone();
+    path.set_quoted(false);
+    path
+}
+
+fn position(position: &Option<Position>) -> Option<Position> {
+    Some(position.unwrap_or(Position {
+        x: Decimal::ZERO,
+        y: Decimal::ZERO,
+    }))
+}
+
+/// Splits the values into commands that each go from one value to the next, one after another with the same duration.
+/// - A single value is a command that doesn't change the value.
+fn split<T: Clone>(
+    start_time: Integer,
+    end_time: Option<Integer>,
+    values: Vec<T>,
+    properties: impl Fn(Option<Integer>, T, T) -> CommandProperties,
+) -> Vec<Command> {
+    let end_time = end_time.unwrap_or(start_time);
+    let duration = end_time - start_time;
+
+    let pairs = if values.len() == 1 {
+        vec![(values[0].clone(), values[0].clone())]
+    } else {
+        values
+            .windows(2)
+            .map(|w| (w[0].clone(), w[1].clone()))
+            .collect()
+    };
+
+    pairs
+        .into_iter()
+        .zip(0..)
+        .map(|((from, to), i)| Command {
+            start_time: Some(start_time + duration * i),
+            properties: properties(Some(end_time + duration * i), from, to),
+            indent_char: IndentChar::Space,
+        })
+        .collect()
+}
+
+fn values<T: Clone>(start: &T, continuing: &[T]) -> Vec<T> {
+    iter::once(start).chain(continuing).cloned().collect()
+}
+
+/// Fills in the missing second fields with the previous value.
+fn continuing_values<T: Copy>(fields: &ContinuingFields<T>) -> Vec<(T, T)> {
+    let mut values = vec![fields.start];
+
+    for (first, second) in &fields.continuing {
+        let previous = values.last().unwrap().1;
+        values.push((*first, second.unwrap_or(previous)));
+    }
+
+    values
+}
+
+/// Fills in the missing green and blue values with the previous value.
+fn colour_values(colours: &Colours) -> Vec<(u8, u8, u8)> {
+    let mut values = vec![colours.start];
+
+    for (red, green, blue) in &colours.continuing {
+        let (_, previous_green, previous_blue) = *values.last().unwrap();
+        values.push((
+            *red,
+            green.unwrap_or(previous_green),
+            blue.unwrap_or(previous_blue),
+        ));
+    }
+
+    values
+}
+
+/// Expands the commands with continuing values into separate commands.
+fn commands(cmds: &[Command]) -> Vec<Command> {
+    cmds.iter().flat_map(command).collect()
+}
+
+fn command(cmd: &Command) -> Vec<Command> {
+    let start_time = match cmd.start_time {
+        Some(start_time) => start_time,
+        None => {
+            return vec![Command {
+                indent_char: IndentChar::Space,
+                ..cmd.clone()
+            }]
+        }
+    };
+
+    match &cmd.properties {
+        CommandProperties::Fade {
+            easing,
+            end_time,
+            start_opacity,
+            continuing_opacities,
+        } => split(
+            start_time,
+            *end_time,
+            values(start_opacity, continuing_opacities),
+            |end_time, from, to| CommandProperties::Fade {
+                easing: *easing,
+                end_time,
+                start_opacity: from,
+                continuing_opacities: vec![to],
+            },
+        ),
+        CommandProperties::Move {
+            easing,
+            end_time,
+            positions_xy,
+        } => split(
+            start_time,
+            *end_time,
+            continuing_values(positions_xy),
+            |end_time, from, to| CommandProperties::Move {
+                easing: *easing,
+                end_time,
+                positions_xy: ContinuingFields {
+                    start: from,
+                    continuing: vec![(to.0, Some(to.1))],
+                },
+            },
+        ),
+        CommandProperties::MoveX {
+            easing,
+            end_time,
+            start_x,
+            continuing_x,
+        } => split(
+            start_time,
+            *end_time,
+            values(start_x, continuing_x),
+            |end_time, from, to| CommandProperties::MoveX {
+                easing: *easing,
+                end_time,
+                start_x: from,
+                continuing_x: vec![to],
+            },
+        ),
+        CommandProperties::MoveY {
+            easing,
+            end_time,
+            start_y,
+            continuing_y,
+        } => split(
+            start_time,
+            *end_time,
+            values(start_y, continuing_y),
+            |end_time, from, to| CommandProperties::MoveY {
+                easing: *easing,
+                end_time,
+                start_y: from,
+                continuing_y: vec![to],
+            },
+        ),
+        CommandProperties::Scale {
+            easing,
+            end_time,
+            start_scale,
+            continuing_scales,
+        } => split(
+            start_time,
+            *end_time,
+            values(start_scale, continuing_scales),
+            |end_time, from, to| CommandProperties::Scale {
+                easing: *easing,
+                end_time,
+                start_scale: from,
+                continuing_scales: vec![to],
+            },
+        ),
+        CommandProperties::VectorScale {
+            easing,
+            end_time,
+            scales_xy,
+        } => split(
+            start_time,
+            *end_time,
+            continuing_values(scales_xy),
+            |end_time, from, to| CommandProperties::VectorScale {
+                easing: *easing,
+                end_time,
+                scales_xy: ContinuingFields {
+                    start: from,
+                    continuing: vec![(to.0, Some(to.1))],
+                },
+            },
+        ),
+        CommandProperties::Rotate {
+            easing,
+            end_time,
+            start_rotation,
+            continuing_rotations,
+        } => split(
+            start_time,
+            *end_time,
+            values(start_rotation, continuing_rotations),
+            |end_time, from, to| CommandProperties::Rotate {
+                easing: *easing,
+                end_time,
+                start_rotation: from,
+                continuing_rotations: vec![to],
+            },
+        ),
+        CommandProperties::Colour {
+            easing,
+            end_time,
+            colours,
+        } => split(
+            start_time,
+            *end_time,
+            colour_values(colours),
+            |end_time, from, to| CommandProperties::Colour {
+                easing: *easing,
+                end_time,
+                colours: Colours {
+                    start: from,
+                    continuing: vec![(to.0, Some(to.1), Some(to.2))],
+                },
+            },
+        ),
+        // parameters don't go from one value to the next, so they are only compared as-is
+        CommandProperties::Parameter {
+            easing,
+            end_time,
+            parameter,
+            continuing_parameters,
+        } => vec![Command {
+            start_time: cmd.start_time,
+            properties: CommandProperties::Parameter {
+                easing: *easing,
+                end_time: Some(end_time.unwrap_or(start_time)),
+                parameter: *parameter,
+                continuing_parameters: continuing_parameters.clone(),
+            },
+            indent_char: IndentChar::Space,
+        }],
+        CommandProperties::Loop {
+            loop_count,
+            commands: loop_commands,
+        } => vec![Command {
+            start_time: cmd.start_time,
+            properties: CommandProperties::Loop {
+                loop_count: *loop_count,
+                commands: commands(loop_commands),
+            },
+            indent_char: IndentChar::Space,
+        }],
+        CommandProperties::Trigger {
+            trigger_type,
+            end_time,
+            group_number,
+            commands: trigger_commands,
+        } => vec![Command {
+            start_time: cmd.start_time,
+            properties: CommandProperties::Trigger {
+                trigger_type: *trigger_type,
+                end_time: *end_time,
+                group_number: *group_number,
+                commands: commands(trigger_commands),
+            },
+            indent_char: IndentChar::Space,
+        }],
+    }
+}
+
+/// Returns the event written in a single way.
+fn normalize(event: &Event) -> Event {
+    match event {
+        Event::Background(bg) => Event::Background(Background {
+            start_time: bg.start_time,
+            file_name: file_path(&bg.file_name),
+            position: position(&bg.position),
+            commands: commands(&bg.commands),
+        }),
+        Event::Video(video) => {
+            let mut normalized = Video::new(
+                video.start_time,
+                file_path(&video.file_name),
+                position(&video.position),
+            );
+            normalized.commands = commands(&video.commands);
+
+            Event::Video(normalized)
+        }
+        Event::Break(b) => Event::Break(Break::new(b.start_time, b.end_time)),
+        Event::SpriteLegacy(sprite) => {
+            let mut sprite = sprite.clone();
+            sprite.file_name = file_path(&sprite.file_name);
+            sprite.position = position(&sprite.position);
+            sprite.commands = commands(&sprite.commands);
+
+            Event::SpriteLegacy(sprite)
+        }
+        Event::AnimationLegacy(animation) => {
+            let mut animation = animation.clone();
+            animation.file_name = file_path(&animation.file_name);
+            animation.position = position(&animation.position);
+            animation.commands = commands(&animation.commands);
+
+            Event::AnimationLegacy(animation)
+        }
+        Event::SampleLegacy(sample) => {
+            let mut sample = sample.clone();
+            sample.file_name = file_path(&sample.file_name);
+            sample.commands = commands(&sample.commands);
+
+            Event::SampleLegacy(sample)
+        }
+        Event::StoryboardObject(object) => {
+            let mut object = object.clone();
+            object.origin.shorthand = false;
+            object.layer_shorthand = false;
+            object.commands = commands(&object.commands);
+            match &mut object.object_type {
+                ObjectType::Sprite(sprite) => sprite.filepath = file_path(&sprite.filepath),
+                ObjectType::Animation(animation) => {
+                    animation.filepath = file_path(&animation.filepath)
+                }
+            }
+
+            Event::StoryboardObject(object)
+        }
+        Event::AudioSample(sample) => {
+            let mut sample = sample.clone();
+            sample.filepath = file_path(&sample.filepath);
+
+            Event::AudioSample(sample)
+        }
+        event => event.clone(),
+    }
+}
+
+impl Events {
+    /// Compares the events while ignoring differences in how they are written.
+    /// - Short and long headers such as `1` and `Video`, and numbered and named origins and layers.
+    /// - Quoted and unquoted file paths.
+    /// - Missing positions and `0,0` positions.
+    /// - Commands with continuing values and the same commands written out separately, and missing end times.
+    /// - The indentation character of commands.
+    pub fn semantically_eq(&self, other: &Events) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(&other.0)
+                .all(|(a, b)| normalize(a) == normalize(b))
+    }
+}
@@ -0,0 +1,145 @@
+//! Module for the area a storyboard [`Object`] covers on the screen.
+//! - Uses the storyboard coordinates, where the screen is `640x480` with the 4:3 area starting at `0,0`.
+
+use std::path::PathBuf;
+
+use either::Either;
+
+use super::{
+    sprites::{Object, ObjectType, OriginType},
+    state::{ObjectState, Timeline},
+};
+use crate::{lint::AssetProber, osu_file::Integer};
+
+/// Number of samples taken between the command times by [`Object::max_bounding_box`].
+const SAMPLES_PER_SEGMENT: usize = 8;
+
+/// Axis aligned box in storyboard coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoundingBox {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl BoundingBox {
+    pub fn width(&self) -> f64 {
+        self.max_x - self.min_x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max_y - self.min_y
+    }
+
+    /// Smallest box containing both boxes.
+    pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+        BoundingBox {
+            min_x: self.min_x.min(other.min_x),
+            min_y: self.min_y.min(other.min_y),
+            max_x: self.max_x.max(other.max_x),
+            max_y: self.max_y.max(other.max_y),
+        }
+    }
+
+    /// Returns `true` if the boxes overlap, including touching edges.
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.min_x <= other.max_x
+            && other.min_x <= self.max_x
+            && self.min_y <= other.max_y
+            && other.min_y <= self.max_y
+    }
+}
+
+/// Where the origin is in the image, as a fraction of the width and height.
+/// - `Custom` and undocumented origins are the top left, as the game does.
+fn origin_offset(origin: Either<OriginType, Integer>) -> (f64, f64) {
+    match origin {
+        Either::Left(OriginType::Centre) => (0.5, 0.5),
+        Either::Left(OriginType::CentreLeft) => (0.0, 0.5),
+        Either::Left(OriginType::TopRight) => (1.0, 0.0),
+        Either::Left(OriginType::BottomCentre) => (0.5, 1.0),
+        Either::Left(OriginType::TopCentre) => (0.5, 0.0),
+        Either::Left(OriginType::CentreRight) => (1.0, 0.5),
+        Either::Left(OriginType::BottomLeft) => (0.0, 1.0),
+        Either::Left(OriginType::BottomRight) => (1.0, 1.0),
+        Either::Left(OriginType::TopLeft | OriginType::Custom) | Either::Right(_) => (0.0, 0.0),
+    }
+}
+
+impl Object {
+    /// Image used for the size of the object, which is the first frame of an animation.
+    fn image_path(&self) -> PathBuf {
+        match &self.object_type {
+            ObjectType::Sprite(sprite) => sprite.filepath.get().to_owned(),
+            ObjectType::Animation(animation) => animation
+                .frame_file_names()
+                .into_iter()
+                .next()
+                .unwrap_or_else(|| animation.filepath.get().to_owned()),
+        }
+    }
+
+    fn bounding_box(&self, state: &ObjectState, (width, height): (u32, u32)) -> BoundingBox {
+        let (origin_x, origin_y) = origin_offset(self.origin.type_);
+        let scale_x = width as f64 * state.scale * state.vector_scale.0;
+        let scale_y = height as f64 * state.scale * state.vector_scale.1;
+        let (sin, cos) = state.rotation.sin_cos();
+
+        let corners = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)].map(|(x, y)| {
+            let x = (x - origin_x) * scale_x;
+            let y = (y - origin_y) * scale_y;
+
+            // the y axis points down, so this goes clockwise on the screen
+            (
+                state.position.x + x * cos - y * sin,
+                state.position.y + x * sin + y * cos,
+            )
+        });
+
+        let fold = |f: fn(f64, f64) -> f64, start: f64, axis: fn(&(f64, f64)) -> f64| {
+            corners.iter().map(axis).fold(start, f)
+        };
+
+        BoundingBox {
+            min_x: fold(f64::min, f64::INFINITY, |c| c.0),
+            min_y: fold(f64::min, f64::INFINITY, |c| c.1),
+            max_x: fold(f64::max, f64::NEG_INFINITY, |c| c.0),
+            max_y: fold(f64::max, f64::NEG_INFINITY, |c| c.1),
+        }
+    }
+
+    /// Area the image of the object covers at `time`, with the scale, vector scale, rotation and origin applied.
+    /// - The image size comes from [`AssetProber::image_size`].
+    /// - Returns `None` if the size isn't known or `time` is outside of the [`lifetime`][Self::lifetime].
+    pub fn bounding_box_at<P: AssetProber + ?Sized>(
+        &self,
+        time: Integer,
+        prober: &P,
+    ) -> Option<BoundingBox> {
+        let size = prober.image_size(&self.image_path())?;
+        let state = self.state_at(time)?;
+
+        Some(self.bounding_box(&state, size))
+    }
+
+    /// Area the image of the object covers at any point during its [`lifetime`][Self::lifetime].
+    /// - The state is sampled at the command times and evenly between them, so the overshoot of elastic and back easings can be slightly larger than this.
+    /// - Returns `None` if the size isn't known or the object has no commands.
+    pub fn max_bounding_box<P: AssetProber + ?Sized>(&self, prober: &P) -> Option<BoundingBox> {
+        let size = prober.image_size(&self.image_path())?;
+        let timeline = Timeline::new(self);
+        let key_times = timeline.key_times();
+
+        let mut times = key_times.clone();
+        for pair in key_times.windows(2) {
+            let step = (pair[1] - pair[0]) / SAMPLES_PER_SEGMENT as f64;
+            times.extend((1..SAMPLES_PER_SEGMENT).map(|i| pair[0] + step * i as f64));
+        }
+
+        times
+            .into_iter()
+            .map(|time| self.bounding_box(&timeline.state_at(self, time), size))
+            .reduce(|a, b| a.union(&b))
+    }
+}
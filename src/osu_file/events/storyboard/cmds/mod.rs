@@ -9,7 +9,6 @@ use crate::osb::Variable;
 use crate::osu_file::{Integer, Version, VersionedFromStr, VersionedToString};
 use crate::parsers::*;
 use crate::VersionedFrom;
-use rust_decimal::Decimal;
 use nom::branch::alt;
 use nom::bytes::complete::{tag, take_while};
 use nom::combinator::*;
@@ -17,14 +16,19 @@ use nom::error::context;
 use nom::multi::many0;
 use nom::sequence::*;
 use nom::Parser;
+use rust_decimal::Decimal;
 
 pub use error::*;
 pub use types::*;
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Command {
     pub start_time: Option<Integer>,
     pub properties: CommandProperties,
+    /// Character used to indent the command, which is kept when writing it back.
+    /// - Lines mixing both characters use the first one.
+    pub indent_char: IndentChar,
 }
 
 fn continuing_to_string<T>(continuing: &[T]) -> String
@@ -283,6 +287,7 @@ impl Command {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum CommandProperties {
     Fade {
@@ -459,6 +464,7 @@ impl VersionedFromStr for Command {
         )
         .map(|(start_time, loop_count)| Command {
             start_time,
+            indent_char: IndentChar::Space,
             properties: CommandProperties::Loop {
                 loop_count,
                 commands: Vec::new(),
@@ -505,9 +511,10 @@ impl VersionedFromStr for Command {
                 cut(tuple((
                     context(
                         ParseCommandError::InvalidTriggerType.into(),
-                        map_res(comma_field(), |s| {
-                            TriggerType::from_str(s, version).map(|t| t.unwrap())
-                        }),
+                        map_opt(
+                            map_res(comma_field(), |s| TriggerType::from_str(s, version)),
+                            |t| t,
+                        ),
                     ),
                     alt((
                         tuple((comma(), peek(comma()))).map(|_| None),
@@ -530,6 +537,7 @@ impl VersionedFromStr for Command {
             .map(
                 |(trigger_type, start_time, (end_time, group_number))| Command {
                     start_time,
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Trigger {
                         trigger_type,
                         end_time,
@@ -577,6 +585,7 @@ impl VersionedFromStr for Command {
                 |((easing, (start_time, end_time)), start_r, start_g, start_b, continuing)| {
                     Command {
                         start_time,
+                        indent_char: IndentChar::Space,
                         properties: CommandProperties::Colour {
                             easing,
                             end_time,
@@ -613,6 +622,7 @@ impl VersionedFromStr for Command {
             .map(
                 |((easing, (start_time, end_time)), parameter, continuing_parameters)| Command {
                     start_time,
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Parameter {
                         easing,
                         end_time,
@@ -653,6 +663,7 @@ impl VersionedFromStr for Command {
         .map(
             |((easing, (start_time, end_time)), start_x, start_y, continuing)| Command {
                 start_time,
+                indent_char: IndentChar::Space,
                 properties: CommandProperties::Move {
                     easing,
                     end_time,
@@ -674,6 +685,7 @@ impl VersionedFromStr for Command {
         .map(
             |((easing, (start_time, end_time)), start_x, start_y, continuing)| Command {
                 start_time,
+                indent_char: IndentChar::Space,
                 properties: CommandProperties::VectorScale {
                     easing,
                     end_time,
@@ -693,6 +705,7 @@ impl VersionedFromStr for Command {
         .map(
             |((easing, (start_time, end_time)), start_opacity, continuing_opacities)| Command {
                 start_time,
+                indent_char: IndentChar::Space,
                 properties: CommandProperties::Fade {
                     easing,
                     end_time,
@@ -710,6 +723,7 @@ impl VersionedFromStr for Command {
         .map(
             |((easing, (start_time, end_time)), start_x, continuing_x)| Command {
                 start_time,
+                indent_char: IndentChar::Space,
                 properties: CommandProperties::MoveX {
                     easing,
                     end_time,
@@ -727,6 +741,7 @@ impl VersionedFromStr for Command {
         .map(
             |((easing, (start_time, end_time)), start_y, continuing_y)| Command {
                 start_time,
+                indent_char: IndentChar::Space,
                 properties: CommandProperties::MoveY {
                     easing,
                     end_time,
@@ -744,6 +759,7 @@ impl VersionedFromStr for Command {
         .map(
             |((easing, (start_time, end_time)), start_scale, continuing_scales)| Command {
                 start_time,
+                indent_char: IndentChar::Space,
                 properties: CommandProperties::Scale {
                     easing,
                     end_time,
@@ -761,6 +777,7 @@ impl VersionedFromStr for Command {
         .map(
             |((easing, (start_time, end_time)), start_rotation, continuing_rotations)| Command {
                 start_time,
+                indent_char: IndentChar::Space,
                 properties: CommandProperties::Rotate {
                     easing,
                     end_time,
@@ -791,6 +808,11 @@ impl VersionedFromStr for Command {
             )),
         )(s)?;
 
-        Ok(Some(parse.1))
+        let mut command = parse.1;
+        if s.starts_with('_') {
+            command.indent_char = IndentChar::Underscore;
+        }
+
+        Ok(Some(command))
     }
 }
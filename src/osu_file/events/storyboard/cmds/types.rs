@@ -1,6 +1,7 @@
 use super::*;
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ContinuingFields<T> {
     pub(crate) start: (T, T),
     pub(crate) continuing: Vec<(T, Option<T>)>,
@@ -43,8 +44,11 @@ impl<T> ContinuingFields<T> {
         if let Some(last_continuing) = self.continuing.last() {
             if last_continuing.1.is_none() {
                 // find last 1 field
-                let last_field = if let Some(last_continuing_with_1) =
-                    self.continuing.get(self.continuing.len() - 2)
+                let last_field = if let Some(last_continuing_with_1) = self
+                    .continuing
+                    .len()
+                    .checked_sub(2)
+                    .and_then(|i| self.continuing.get(i))
                 {
                     last_continuing_with_1.1.as_ref()
                 } else {
@@ -64,7 +68,7 @@ impl<T> ContinuingFields<T> {
         fields: (T, Option<T>),
     ) -> Result<(), ContinuingSetError> {
         // if index isn't the last index, field 1 being none will return an error
-        if index != self.continuing.len() - 1 && fields.1.is_none() {
+        if index + 1 != self.continuing.len() && fields.1.is_none() {
             Err(ContinuingSetError::InvalidSecondFieldOption)
         } else {
             match self.continuing.get_mut(index) {
@@ -97,6 +101,7 @@ where
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Colours {
     pub start: (u8, u8, u8),
     pub continuing: Vec<(u8, Option<u8>, Option<u8>)>,
@@ -138,8 +143,11 @@ impl Colours {
         if let Some(last_continuing) = self.continuing.last() {
             if last_continuing.1.is_none() {
                 // find last field
-                let last_field = if let Some(last_continuing_with_1) =
-                    self.continuing.get(self.continuing.len() - 2)
+                let last_field = if let Some(last_continuing_with_1) = self
+                    .continuing
+                    .len()
+                    .checked_sub(2)
+                    .and_then(|i| self.continuing.get(i))
                 {
                     last_continuing_with_1.1.as_ref()
                 } else {
@@ -150,8 +158,11 @@ impl Colours {
                 self.continuing.last_mut().unwrap().1 = last_field.copied();
             } else if last_continuing.2.is_none() {
                 // find last field
-                let last_field = if let Some(last_continuing_with_2) =
-                    self.continuing.get(self.continuing.len() - 2)
+                let last_field = if let Some(last_continuing_with_2) = self
+                    .continuing
+                    .len()
+                    .checked_sub(2)
+                    .and_then(|i| self.continuing.get(i))
                 {
                     last_continuing_with_2.2.as_ref()
                 } else {
@@ -171,7 +182,7 @@ impl Colours {
         fields: (u8, Option<u8>, Option<u8>),
     ) -> Result<(), ContinuingRGBSetError> {
         // if index isn't the last index, b or g being none will return an error
-        let index_is_last = index == self.continuing.len() - 1;
+        let index_is_last = index + 1 == self.continuing.len();
 
         if !index_is_last && fields.1.is_none() {
             Err(ContinuingRGBSetError::InvalidFieldOption(
@@ -216,3 +227,28 @@ impl VersionedToString for Colours {
         Some(builder.join(","))
     }
 }
+
+/// Character used to indent storyboard commands.
+/// - Each character is one level of nesting, so `__` and ` _` are both a depth of 2.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum IndentChar {
+    #[default]
+    Space,
+    Underscore,
+}
+
+impl IndentChar {
+    pub fn as_char(&self) -> char {
+        match self {
+            IndentChar::Space => ' ',
+            IndentChar::Underscore => '_',
+        }
+    }
+
+    /// Indentation for a command at `depth`.
+    pub fn repeat(&self, depth: usize) -> String {
+        self.as_char().to_string().repeat(depth)
+    }
+}
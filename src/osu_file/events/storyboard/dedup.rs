@@ -0,0 +1,139 @@
+//! Module for merging storyboard sprites that are the same image shown one after another.
+//! - Generated storyboards often create a new sprite each time an image is shown, which can be a single sprite instead.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use either::Either;
+
+use super::{
+    cmds::{Command, CommandProperties},
+    sprites::{Layer, Object, ObjectType, OriginType},
+    state::Timeline,
+};
+use crate::osu_file::{
+    events::{Event, Events},
+    Integer, Position, Version, VersionedToString,
+};
+
+/// What was saved by [`Events::dedup_objects`].
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct DedupReport {
+    /// Number of objects that were merged into an earlier object and removed.
+    pub removed_objects: usize,
+    /// Number of bytes saved by not writing the removed objects' lines, in the version given to [`Events::dedup_objects`].
+    pub saved_bytes: usize,
+}
+
+/// Objects with the same key look the same if they have the same state.
+type Key = (PathBuf, Layer, Either<OriginType, Integer>, Position);
+
+fn key(object: &Object) -> Option<Key> {
+    match &object.object_type {
+        ObjectType::Sprite(sprite) => Some((
+            sprite.filepath.get().to_owned(),
+            object.layer,
+            object.origin.type_,
+            object.position.clone(),
+        )),
+        // frames are timed from the start of the object, so they can't be merged
+        ObjectType::Animation(_) => None,
+    }
+}
+
+/// Colours, parameters and triggers aren't part of the [`Timeline`], so objects with them aren't merged.
+fn has_unmergeable_commands(commands: &[Command]) -> bool {
+    commands.iter().any(|command| match &command.properties {
+        CommandProperties::Colour { .. }
+        | CommandProperties::Parameter { .. }
+        | CommandProperties::Trigger { .. } => true,
+        CommandProperties::Loop { commands, .. } => has_unmergeable_commands(commands),
+        _ => false,
+    })
+}
+
+fn as_object(event: &Event) -> Option<&Object> {
+    match event {
+        Event::StoryboardObject(object) => Some(object),
+        _ => None,
+    }
+}
+
+impl Events {
+    /// Returns `true` if the object at `index` can be merged into the object at `target` without changing how the storyboard looks.
+    fn can_merge(&self, removed: &[bool], target: usize, index: usize) -> bool {
+        let (target_object, object) = match (as_object(&self.0[target]), as_object(&self.0[index]))
+        {
+            (Some(target_object), Some(object)) => (target_object, object),
+            _ => return false,
+        };
+
+        if has_unmergeable_commands(&target_object.commands)
+            || has_unmergeable_commands(&object.commands)
+        {
+            return false;
+        }
+
+        let timeline = Timeline::new(object);
+        if !Timeline::new(target_object).can_append(target_object, &timeline) {
+            return false;
+        }
+
+        let (start, end) = match timeline.lifetime() {
+            Some(lifetime) => lifetime,
+            None => return false,
+        };
+
+        // the object is drawn in the place of the target, so it can't move below objects shown at the same time
+        (target + 1..index)
+            .filter(|i| !removed[*i])
+            .filter_map(|i| as_object(&self.0[i]))
+            .filter(|other| other.layer == object.layer)
+            .all(|other| match other.lifetime() {
+                Some((other_start, other_end)) => other_end < start || end < other_start,
+                None => true,
+            })
+    }
+
+    /// Merges storyboard sprites into an earlier sprite with the same image, layer, origin and position, when it doesn't change how the storyboard looks.
+    /// - The earlier sprite has to fade out completely before the later one starts, and both have to use the same kinds of commands.
+    /// - Sprites with colour, parameter or trigger commands, and animations, aren't merged.
+    /// - `version` is only used to count the saved bytes.
+    pub fn dedup_objects(&mut self, version: Version) -> DedupReport {
+        let mut report = DedupReport::default();
+        let mut removed = vec![false; self.0.len()];
+        let mut last_by_key = HashMap::new();
+
+        for index in 0..self.0.len() {
+            let key = match as_object(&self.0[index]).and_then(key) {
+                Some(key) => key,
+                None => continue,
+            };
+
+            if let Some(&target) = last_by_key.get(&key) {
+                if self.can_merge(&removed, target, index) {
+                    let object = as_object(&self.0[index]).unwrap();
+                    let commands = object.commands.clone();
+
+                    report.removed_objects += 1;
+                    report.saved_bytes += object
+                        .to_string(version)
+                        .map_or(0, |s| s.lines().next().map_or(0, |line| line.len() + 1));
+
+                    if let Event::StoryboardObject(target) = &mut self.0[target] {
+                        target.commands.extend(commands);
+                    }
+                    removed[index] = true;
+                    continue;
+                }
+            }
+
+            last_by_key.insert(key, index);
+        }
+
+        let mut removed = removed.into_iter();
+        self.0.retain(|_| !removed.next().unwrap());
+
+        report
+    }
+}
@@ -8,8 +8,12 @@ use crate::helper::macros::verbose_error_to_error;
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum CommandPushError {
-    #[error("Invalid indentation, expected {0}, got {1}")]
+    /// The command is nested under a command that isn't a loop or trigger.
+    #[error("Invalid indentation, expected at most {0}, got {1}")]
     InvalidIndentation(usize, usize),
+    /// The depth increased by more than 1 from the previous command.
+    #[error("Indentation jumped from {previous} to {depth}, it can only increase by 1")]
+    IndentationJump { previous: usize, depth: usize },
 }
 
 #[derive(Debug, Error, IntoStaticStr, EnumString)]
@@ -49,7 +53,7 @@ pub enum ParseObjectError {
     InvalidLoopType,
 }
 
-verbose_error_to_error!(ParseObjectError);
+verbose_error_to_error!(ParseObjectError, ParseObjectError::UnknownObjectType);
 
 #[derive(Debug, Error)]
 #[error("The filepath needs to be a path relative to where the .osu file is, not a full path such as `C:\\folder\\image.png`")]
@@ -192,7 +196,7 @@ pub enum ParseCommandError {
     InvalidContinuingRotation,
 }
 
-verbose_error_to_error!(ParseCommandError);
+verbose_error_to_error!(ParseCommandError, ParseCommandError::UnknownCommandType);
 
 #[derive(Debug, Error)]
 #[non_exhaustive]
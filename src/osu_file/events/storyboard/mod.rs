@@ -1,4 +1,7 @@
+pub mod bounds;
+pub mod cmds;
+pub mod dedup;
 pub mod error;
 pub mod sprites;
+pub mod state;
 pub mod types;
-pub mod cmds;
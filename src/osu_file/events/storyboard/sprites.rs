@@ -3,18 +3,19 @@ use std::path::{Path, PathBuf};
 use either::Either;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::combinator::{cut, fail};
+use nom::combinator::{cut, fail, map_opt};
 use nom::error::context;
 use nom::sequence::{preceded, tuple};
 use nom::Parser;
 
 use crate::events::EventWithCommands;
 use crate::osu_file::{
-    FilePath, Position, Version, VersionedDefault, VersionedFromStr, VersionedToString,
+    Extensions, FilePath, InvalidRepr, Position, Version, VersionedDefault, VersionedFromRepr,
+    VersionedFromStr, VersionedToString,
 };
 use crate::parsers::{
     comma, comma_field, comma_field_type, comma_field_versioned_type, consume_rest_versioned_type,
-    nothing,
+    file_path_field, nothing,
 };
 use crate::{Integer, VersionedFrom, VersionedTryFrom};
 
@@ -22,6 +23,7 @@ use super::cmds::*;
 use super::error::*;
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum Layer {
     Background,
@@ -34,14 +36,45 @@ pub enum Layer {
 impl VersionedFromStr for Layer {
     type Err = ParseLayerError;
 
-    fn from_str(s: &str, _: Version) -> std::result::Result<Option<Self>, Self::Err> {
+    fn from_str(s: &str, version: Version) -> std::result::Result<Option<Self>, Self::Err> {
         match s {
             "Background" => Ok(Some(Layer::Background)),
             "Fail" => Ok(Some(Layer::Fail)),
             "Pass" => Ok(Some(Layer::Pass)),
             "Foreground" => Ok(Some(Layer::Foreground)),
             "Overlay" => Ok(Some(Layer::Overlay)),
-            _ => Err(ParseLayerError::UnknownVariant),
+            _ => {
+                let s = s.parse().map_err(|_| ParseLayerError::UnknownVariant)?;
+
+                Layer::from_repr(s, version).map_err(|_| ParseLayerError::UnknownVariant)
+            }
+        }
+    }
+}
+
+impl VersionedFromRepr for Layer {
+    fn from_repr(repr: usize, _: Version) -> Result<Option<Self>, InvalidRepr> {
+        let repr = match repr {
+            0 => Layer::Background,
+            1 => Layer::Fail,
+            2 => Layer::Pass,
+            3 => Layer::Foreground,
+            4 => Layer::Overlay,
+            _ => return Err(InvalidRepr),
+        };
+
+        Ok(Some(repr))
+    }
+}
+
+impl VersionedFrom<Layer> for usize {
+    fn from(value: Layer, _: Version) -> Option<Self> {
+        match value {
+            Layer::Background => Some(0),
+            Layer::Fail => Some(1),
+            Layer::Pass => Some(2),
+            Layer::Foreground => Some(3),
+            Layer::Overlay => Some(4),
         }
     }
 }
@@ -61,12 +94,19 @@ impl VersionedToString for Layer {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Object {
     pub layer: Layer,
+    /// Writes the layer as a number, such as `3` instead of `Foreground`.
+    /// - Set when parsing from how the layer was written.
+    pub layer_shorthand: bool,
     pub origin: Origin,
     pub position: Position,
     pub object_type: ObjectType,
     pub commands: Vec<Command>,
+    /// Data attached by the user, which isn't written to the file.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extensions: Extensions,
 }
 
 impl VersionedToString for Object {
@@ -86,11 +126,18 @@ impl EventWithCommands for Object {
 
     fn to_string_cmd(&self, version: Version) -> Option<String> {
         let pos_str = format!("{},{}", self.position.x, self.position.y);
+        let layer = if self.layer_shorthand {
+            <usize as VersionedFrom<Layer>>::from(self.layer, version)
+                .unwrap()
+                .to_string()
+        } else {
+            self.layer.to_string(version).unwrap()
+        };
 
         let obj = match &self.object_type {
             ObjectType::Sprite(sprite) => format!(
                 "Sprite,{},{},{},{}",
-                self.layer.to_string(version).unwrap(),
+                layer,
                 self.origin.to_string(version).unwrap(),
                 sprite.filepath.to_string(version).unwrap(),
                 pos_str
@@ -98,7 +145,7 @@ impl EventWithCommands for Object {
             ObjectType::Animation(anim) => {
                 format!(
                     "Animation,{},{},{},{},{},{},{}",
-                    self.layer.to_string(version).unwrap(),
+                    layer,
                     self.origin.to_string(version).unwrap(),
                     anim.filepath.to_string(version).unwrap(),
                     pos_str,
@@ -123,7 +170,11 @@ impl VersionedFromStr for Object {
                 context(ParseObjectError::MissingLayer.into(), comma()),
                 context(
                     ParseObjectError::InvalidLayer.into(),
-                    comma_field_versioned_type(version),
+                    map_opt(comma_field(), |s: &str| {
+                        let layer = Layer::from_str(s, version).ok().flatten()?;
+
+                        Some((layer, s.parse::<usize>().is_ok()))
+                    }),
                 ),
             )
         };
@@ -139,9 +190,8 @@ impl VersionedFromStr for Object {
         let file_path = || {
             preceded(
                 context(ParseObjectError::MissingFilePath.into(), comma()),
-                comma_field(),
+                file_path_field(),
             )
-            .map(|p| p.into())
         };
         let position = || {
             tuple((
@@ -184,19 +234,21 @@ impl VersionedFromStr for Object {
                     consume_rest_versioned_type(version),
                 ),
             ),
-            nothing().map(|_| LoopType::default(version).unwrap()),
+            map_opt(nothing(), |_| LoopType::default(version)),
         ));
 
         let (_, object) = alt((
             preceded(
                 tag("Sprite"),
                 cut(tuple((layer(), origin(), file_path(), position()))).map(
-                    |(layer, origin, filepath, position)| Object {
+                    |((layer, layer_shorthand), origin, filepath, position)| Object {
                         layer,
+                        layer_shorthand,
                         origin,
                         position,
                         object_type: ObjectType::Sprite(Sprite { filepath }),
                         commands: Vec::new(),
+                        extensions: Extensions::new(),
                     },
                 ),
             ),
@@ -213,8 +265,17 @@ impl VersionedFromStr for Object {
                 ))),
             )
             .map(
-                |(layer, origin, filepath, position, frame_count, frame_delay, loop_type)| Object {
+                |(
+                    (layer, layer_shorthand),
+                    origin,
+                    filepath,
+                    position,
+                    frame_count,
+                    frame_delay,
+                    loop_type,
+                )| Object {
                     layer,
+                    layer_shorthand,
                     origin,
                     position,
                     object_type: ObjectType::Animation(Animation {
@@ -224,6 +285,7 @@ impl VersionedFromStr for Object {
                         filepath,
                     }),
                     commands: Vec::new(),
+                    extensions: Extensions::new(),
                 },
             ),
             context(ParseObjectError::UnknownObjectType.into(), fail),
@@ -234,6 +296,7 @@ impl VersionedFromStr for Object {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Animation {
     pub frame_count: u32,
     pub frame_delay: rust_decimal::Decimal,
@@ -246,13 +309,12 @@ impl Animation {
         let mut file_names = Vec::with_capacity(self.frame_count as usize);
 
         let filepath = self.filepath.get();
-        let mut file_name = filepath.file_name().unwrap().to_string_lossy().to_string();
+        let file_name = filepath
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
         let file_extension = match filepath.extension() {
-            Some(extension) => {
-                let extension = format!(".{}", extension.to_string_lossy());
-                file_name = file_name.strip_suffix(&extension).unwrap().to_string();
-                extension
-            }
+            Some(extension) => format!(".{}", extension.to_string_lossy()),
             None => String::new(),
         };
 
@@ -265,6 +327,7 @@ impl Animation {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sprite {
     pub filepath: FilePath,
 }
@@ -282,6 +345,7 @@ impl Sprite {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum ObjectType {
     Sprite(Sprite),
@@ -289,6 +353,7 @@ pub enum ObjectType {
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Origin {
     /// Origin type.
     /// - `Left` variant would be the valid enum variants.
@@ -298,6 +363,7 @@ pub struct Origin {
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum OriginType {
     TopLeft,
@@ -403,7 +469,8 @@ impl VersionedFromStr for Origin {
             _ => {
                 let s = s.parse()?;
                 let type_ = match <OriginType as VersionedTryFrom<Integer>>::try_from(s, version) {
-                    Ok(type_) => Either::Left(type_.unwrap()),
+                    Ok(Some(type_)) => Either::Left(type_),
+                    Ok(None) => Either::Right(s),
                     Err(err) => match err {
                         OriginTryFromIntError::UnknownVariant => Either::Right(s),
                     },
@@ -456,6 +523,7 @@ impl VersionedFrom<OriginType> for Integer {
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum LoopType {
     LoopForever,
@@ -0,0 +1,554 @@
+//! Module for evaluating the commands of a storyboard [`Object`] at a point in time.
+//! - Loops are evaluated modulo their duration instead of being expanded, triggers are skipped since they depend on gameplay.
+//! - Colour and parameter commands don't change the evaluated state.
+
+use std::f64::consts::PI;
+
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+
+use super::{
+    cmds::{Command, CommandProperties},
+    sprites::Object,
+    types::Easing,
+};
+use crate::osu_file::{Integer, Position};
+
+/// State of a storyboard object at a point in time, from its commands.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObjectState {
+    pub position: Position<f64>,
+    /// Uniform scale from the `S` commands.
+    pub scale: f64,
+    /// Scale of each axis from the `V` commands, applied on top of [`scale`][Self::scale].
+    pub vector_scale: (f64, f64),
+    /// Rotation in radians, clockwise.
+    pub rotation: f64,
+    pub opacity: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Channel {
+    X,
+    Y,
+    Scale,
+    VectorScale,
+    Rotation,
+    Opacity,
+}
+
+/// Most repeats of a looped segment that are listed one by one, for the key times and loops inside loops.
+const MAX_LISTED_REPEATS: u32 = 1000;
+
+/// Part of a command going from one value to the next, with absolute times.
+/// - Channels with a single value only use the first value of the pairs.
+/// - Segments in a loop are played `repeats` times, each one `period` milliseconds after the last.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Segment {
+    channel: Channel,
+    start: f64,
+    end: f64,
+    easing: Easing,
+    from: (f64, f64),
+    to: (f64, f64),
+    period: f64,
+    repeats: u32,
+}
+
+impl Segment {
+    /// The repeat that starts `repeat` periods after the first one.
+    fn repeat(&self, repeat: u32) -> Segment {
+        let offset = self.period * f64::from(repeat);
+
+        Segment {
+            start: self.start + offset,
+            end: self.end + offset,
+            period: 0.0,
+            repeats: 1,
+            ..*self
+        }
+    }
+
+    /// The last repeat that starts at or before `time`, or `None` if the first one starts after it.
+    fn latest_at(&self, time: f64) -> Option<Segment> {
+        if self.start > time {
+            return None;
+        }
+
+        let last = self.repeats.saturating_sub(1);
+        let repeat = if self.period > 0.0 {
+            // the cast saturates, and is clamped to the last repeat
+            (((time - self.start) / self.period).floor() as u32).min(last)
+        } else {
+            last
+        };
+
+        Some(self.repeat(repeat))
+    }
+
+    /// End time of the last repeat.
+    fn last_end(&self) -> f64 {
+        self.end + self.period * f64::from(self.repeats.saturating_sub(1))
+    }
+}
+
+fn to_f64(value: &Decimal) -> f64 {
+    value.to_f64().unwrap_or_default()
+}
+
+fn single(start: &Decimal, continuing: &[Decimal]) -> Vec<(f64, f64)> {
+    std::iter::once(start)
+        .chain(continuing)
+        .map(|value| (to_f64(value), 0.0))
+        .collect()
+}
+
+/// A missing second value in the continuing fields is the previous second value.
+fn pairs(start: &(Decimal, Decimal), continuing: &[(Decimal, Option<Decimal>)]) -> Vec<(f64, f64)> {
+    let mut values = vec![(to_f64(&start.0), to_f64(&start.1))];
+
+    for (first, second) in continuing {
+        let previous = values[values.len() - 1].1;
+        values.push((to_f64(first), second.as_ref().map_or(previous, to_f64)));
+    }
+
+    values
+}
+
+/// Splits the command into segments, where each continuing value lasts as long as the first one.
+fn push_chain(
+    segments: &mut Vec<Segment>,
+    channel: Channel,
+    (start, end): (f64, f64),
+    easing: Easing,
+    values: Vec<(f64, f64)>,
+) {
+    if values.len() == 1 {
+        segments.push(Segment {
+            channel,
+            start,
+            end,
+            easing,
+            from: values[0],
+            to: values[0],
+            period: 0.0,
+            repeats: 1,
+        });
+        return;
+    }
+
+    let duration = end - start;
+
+    for (i, pair) in values.windows(2).enumerate() {
+        let start = start + duration * i as f64;
+
+        segments.push(Segment {
+            channel,
+            start,
+            end: start + duration,
+            easing,
+            from: pair[0],
+            to: pair[1],
+            period: 0.0,
+            repeats: 1,
+        });
+    }
+}
+
+fn flatten(commands: &[Command], offset: f64, segments: &mut Vec<Segment>) {
+    for command in commands {
+        let start = offset + command.start_time.unwrap_or_default() as f64;
+        let times = |end_time: &Option<Integer>| {
+            (
+                start,
+                end_time.map_or(start, |end| offset + end as f64).max(start),
+            )
+        };
+
+        match &command.properties {
+            CommandProperties::Fade {
+                easing,
+                end_time,
+                start_opacity,
+                continuing_opacities,
+            } => push_chain(
+                segments,
+                Channel::Opacity,
+                times(end_time),
+                *easing,
+                single(start_opacity, continuing_opacities),
+            ),
+            CommandProperties::Move {
+                easing,
+                end_time,
+                positions_xy,
+            } => {
+                let values = pairs(
+                    positions_xy.start_values(),
+                    positions_xy.continuing_fields(),
+                );
+                let xs = values.iter().map(|(x, _)| (*x, 0.0)).collect();
+                let ys = values.iter().map(|(_, y)| (*y, 0.0)).collect();
+
+                push_chain(segments, Channel::X, times(end_time), *easing, xs);
+                push_chain(segments, Channel::Y, times(end_time), *easing, ys);
+            }
+            CommandProperties::MoveX {
+                easing,
+                end_time,
+                start_x,
+                continuing_x,
+            } => push_chain(
+                segments,
+                Channel::X,
+                times(end_time),
+                *easing,
+                single(start_x, continuing_x),
+            ),
+            CommandProperties::MoveY {
+                easing,
+                end_time,
+                start_y,
+                continuing_y,
+            } => push_chain(
+                segments,
+                Channel::Y,
+                times(end_time),
+                *easing,
+                single(start_y, continuing_y),
+            ),
+            CommandProperties::Scale {
+                easing,
+                end_time,
+                start_scale,
+                continuing_scales,
+            } => push_chain(
+                segments,
+                Channel::Scale,
+                times(end_time),
+                *easing,
+                single(start_scale, continuing_scales),
+            ),
+            CommandProperties::VectorScale {
+                easing,
+                end_time,
+                scales_xy,
+            } => push_chain(
+                segments,
+                Channel::VectorScale,
+                times(end_time),
+                *easing,
+                pairs(scales_xy.start_values(), scales_xy.continuing_fields()),
+            ),
+            CommandProperties::Rotate {
+                easing,
+                end_time,
+                start_rotation,
+                continuing_rotations,
+            } => push_chain(
+                segments,
+                Channel::Rotation,
+                times(end_time),
+                *easing,
+                single(start_rotation, continuing_rotations),
+            ),
+            CommandProperties::Loop {
+                loop_count,
+                commands,
+            } => {
+                let mut inner = Vec::new();
+                flatten(commands, 0.0, &mut inner);
+
+                let inner_start = inner.iter().map(|s| s.start).fold(f64::INFINITY, f64::min);
+                let inner_end = inner
+                    .iter()
+                    .map(Segment::last_end)
+                    .fold(f64::NEG_INFINITY, f64::max);
+                let duration = (inner_end - inner_start).max(0.0);
+
+                // the game plays a loop at least once
+                let loop_count = (*loop_count).max(1);
+
+                for segment in inner {
+                    if segment.repeats == 1 {
+                        segments.push(Segment {
+                            start: segment.start + start,
+                            end: segment.end + start,
+                            period: duration,
+                            repeats: loop_count,
+                            ..segment
+                        });
+                        continue;
+                    }
+
+                    // a loop inside a loop can't repeat evenly, so only its first repeats are listed
+                    for i in 0..loop_count.min(MAX_LISTED_REPEATS) {
+                        let loop_start = start + duration * f64::from(i);
+
+                        segments.push(Segment {
+                            start: segment.start + loop_start,
+                            end: segment.end + loop_start,
+                            ..segment
+                        });
+                    }
+                }
+            }
+            CommandProperties::Colour { .. }
+            | CommandProperties::Parameter { .. }
+            | CommandProperties::Trigger { .. } => (),
+        }
+    }
+}
+
+fn bounce_out(t: f64) -> f64 {
+    if t < 1.0 / 2.75 {
+        7.5625 * t * t
+    } else if t < 2.0 / 2.75 {
+        let t = t - 1.5 / 2.75;
+        7.5625 * t * t + 0.75
+    } else if t < 2.5 / 2.75 {
+        let t = t - 2.25 / 2.75;
+        7.5625 * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / 2.75;
+        7.5625 * t * t + 0.984375
+    }
+}
+
+impl Easing {
+    /// Eases the progress `t` from `0` to `1`, with the same curves as the game.
+    /// - Undocumented easings are linear.
+    pub fn apply(&self, t: f64) -> f64 {
+        const ELASTIC: f64 = 2.0 * PI / 0.3;
+        const ELASTIC_OFFSET: f64 = 0.3 / 4.0;
+        const BACK: f64 = 1.70158;
+        const BACK_IN_OUT: f64 = BACK * 1.525;
+
+        match self {
+            Easing::Linear | Easing::Other(_) => t,
+            Easing::EasingIn | Easing::QuadIn => t * t,
+            Easing::EasingOut | Easing::QuadOut => t * (2.0 - t),
+            Easing::QuadInOut if t < 0.5 => 2.0 * t * t,
+            Easing::QuadInOut => 1.0 - (-2.0 * t + 2.0).powi(2) / 2.0,
+            Easing::CubicIn => t.powi(3),
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut if t < 0.5 => 4.0 * t.powi(3),
+            Easing::CubicInOut => 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0,
+            Easing::QuartIn => t.powi(4),
+            Easing::QuartOut => 1.0 - (1.0 - t).powi(4),
+            Easing::QuartInOut if t < 0.5 => 8.0 * t.powi(4),
+            Easing::QuartInOut => 1.0 - (-2.0 * t + 2.0).powi(4) / 2.0,
+            Easing::QuintIn => t.powi(5),
+            Easing::QuintOut => 1.0 - (1.0 - t).powi(5),
+            Easing::QuintInOut if t < 0.5 => 16.0 * t.powi(5),
+            Easing::QuintInOut => 1.0 - (-2.0 * t + 2.0).powi(5) / 2.0,
+            Easing::SineIn => 1.0 - (t * PI / 2.0).cos(),
+            Easing::SineOut => (t * PI / 2.0).sin(),
+            Easing::SineInOut => -((PI * t).cos() - 1.0) / 2.0,
+            Easing::ExpoIn if t <= 0.0 => 0.0,
+            Easing::ExpoIn => 2f64.powf(10.0 * t - 10.0),
+            Easing::ExpoOut if t >= 1.0 => 1.0,
+            Easing::ExpoOut => 1.0 - 2f64.powf(-10.0 * t),
+            Easing::ExpoInOut if t <= 0.0 || t >= 1.0 => t.clamp(0.0, 1.0),
+            Easing::ExpoInOut if t < 0.5 => 2f64.powf(20.0 * t - 10.0) / 2.0,
+            Easing::ExpoInOut => (2.0 - 2f64.powf(-20.0 * t + 10.0)) / 2.0,
+            Easing::CircIn => 1.0 - (1.0 - t * t).max(0.0).sqrt(),
+            Easing::CircOut => (1.0 - (t - 1.0).powi(2)).max(0.0).sqrt(),
+            Easing::CircInOut if t < 0.5 => (1.0 - (1.0 - (2.0 * t).powi(2)).max(0.0).sqrt()) / 2.0,
+            Easing::CircInOut => ((1.0 - (-2.0 * t + 2.0).powi(2)).max(0.0).sqrt() + 1.0) / 2.0,
+            Easing::ElasticIn => {
+                -(2f64.powf(10.0 * t - 10.0)) * ((1.0 - ELASTIC_OFFSET - t) * ELASTIC).sin()
+            }
+            Easing::ElasticOut => {
+                2f64.powf(-10.0 * t) * ((t - ELASTIC_OFFSET) * ELASTIC).sin() + 1.0
+            }
+            Easing::ElasticHalfOut => {
+                2f64.powf(-10.0 * t) * ((0.5 * t - ELASTIC_OFFSET) * ELASTIC).sin() + 1.0
+            }
+            Easing::ElasticQuarterOut => {
+                2f64.powf(-10.0 * t) * ((0.25 * t - ELASTIC_OFFSET) * ELASTIC).sin() + 1.0
+            }
+            Easing::ElasticInOut if t < 0.5 => {
+                let t = t * 2.0;
+                -0.5 * 2f64.powf(10.0 * t - 10.0)
+                    * ((1.0 - ELASTIC_OFFSET * 1.5 - t) * ELASTIC / 1.5).sin()
+            }
+            Easing::ElasticInOut => {
+                let t = t * 2.0 - 1.0;
+                0.5 * 2f64.powf(-10.0 * t) * ((t - ELASTIC_OFFSET * 1.5) * ELASTIC / 1.5).sin()
+                    + 1.0
+            }
+            Easing::BackIn => t * t * ((BACK + 1.0) * t - BACK),
+            Easing::BackOut => {
+                let t = t - 1.0;
+                t * t * ((BACK + 1.0) * t + BACK) + 1.0
+            }
+            Easing::BackInOut if t < 0.5 => {
+                let t = t * 2.0;
+                0.5 * t * t * ((BACK_IN_OUT + 1.0) * t - BACK_IN_OUT)
+            }
+            Easing::BackInOut => {
+                let t = t * 2.0 - 2.0;
+                0.5 * (t * t * ((BACK_IN_OUT + 1.0) * t + BACK_IN_OUT) + 2.0)
+            }
+            Easing::BounceIn => 1.0 - bounce_out(1.0 - t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut if t < 0.5 => 0.5 - 0.5 * bounce_out(1.0 - 2.0 * t),
+            Easing::BounceInOut => 0.5 + 0.5 * bounce_out(2.0 * t - 1.0),
+        }
+    }
+}
+
+/// Commands of an object as segments, which are evaluated by channel.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Timeline {
+    segments: Vec<Segment>,
+}
+
+impl Timeline {
+    pub(crate) fn new(object: &Object) -> Self {
+        Self::from_commands(&object.commands)
+    }
+
+    pub(crate) fn from_commands(commands: &[Command]) -> Self {
+        let mut segments = Vec::new();
+        flatten(commands, 0.0, &mut segments);
+
+        Self { segments }
+    }
+
+    /// First start time and last end time of the commands.
+    pub(crate) fn lifetime(&self) -> Option<(f64, f64)> {
+        let start = self.segments.iter().map(|s| s.start).reduce(f64::min)?;
+        let end = self
+            .segments
+            .iter()
+            .map(Segment::last_end)
+            .reduce(f64::max)?;
+
+        Some((start, end))
+    }
+
+    /// Start and end times of all segments, sorted and without duplicates.
+    /// - Only the first [`MAX_LISTED_REPEATS`] and the last repeat of looped segments are included.
+    pub(crate) fn key_times(&self) -> Vec<f64> {
+        let mut times = self
+            .segments
+            .iter()
+            .flat_map(|s| {
+                (0..s.repeats.min(MAX_LISTED_REPEATS))
+                    .chain(std::iter::once(s.repeats.saturating_sub(1)))
+                    .map(|i| s.repeat(i))
+            })
+            .flat_map(|s| [s.start, s.end])
+            .collect::<Vec<_>>();
+
+        times.sort_by(f64::total_cmp);
+        times.dedup();
+        times
+    }
+
+    /// Value of the channel at `time`, or `None` if there are no commands for it.
+    /// - Before the first command the start value of the first command is used, as the game does.
+    /// - When commands overlap, the one that started last wins.
+    fn value(&self, channel: Channel, time: f64) -> Option<(f64, f64)> {
+        let segments = self.segments.iter().filter(|s| s.channel == channel);
+        let first = segments
+            .clone()
+            .reduce(|a, b| if b.start < a.start { b } else { a })?;
+
+        let segment = match segments.filter_map(|s| s.latest_at(time)).reduce(|a, b| {
+            if b.start >= a.start {
+                b
+            } else {
+                a
+            }
+        }) {
+            Some(segment) => segment,
+            None => return Some(first.from),
+        };
+
+        if time >= segment.end {
+            return Some(segment.to);
+        }
+
+        let t = segment
+            .easing
+            .apply((time - segment.start) / (segment.end - segment.start));
+        let lerp = |from: f64, to: f64| from + (to - from) * t;
+
+        Some((
+            lerp(segment.from.0, segment.to.0),
+            lerp(segment.from.1, segment.to.1),
+        ))
+    }
+
+    fn uses(&self, channel: Channel) -> bool {
+        self.segments.iter().any(|s| s.channel == channel)
+    }
+
+    /// Returns `true` if the segments of `later` can be added to this timeline without changing how either of them is evaluated.
+    /// - `later` has to start after this timeline ends, which has to end fully transparent so nothing is shown in between.
+    /// - Both have to change the same channels, and `later` has to start all of them at its start so no value is carried over.
+    pub(crate) fn can_append(&self, object: &Object, later: &Timeline) -> bool {
+        let (end, start) = match (self.lifetime(), later.lifetime()) {
+            (Some((_, end)), Some((start, _))) => (end, start),
+            _ => return false,
+        };
+
+        let channels = [
+            Channel::X,
+            Channel::Y,
+            Channel::Scale,
+            Channel::VectorScale,
+            Channel::Rotation,
+            Channel::Opacity,
+        ];
+
+        end < start
+            && self.state_at(object, end).opacity == 0.0
+            && channels.iter().all(|c| self.uses(*c) == later.uses(*c))
+            && channels.iter().filter(|c| later.uses(**c)).all(|c| {
+                later
+                    .segments
+                    .iter()
+                    .any(|s| s.channel == *c && s.start == start)
+            })
+    }
+
+    pub(crate) fn state_at(&self, object: &Object, time: f64) -> ObjectState {
+        let value = |channel, default| self.value(channel, time).map_or(default, |(v, _)| v);
+
+        ObjectState {
+            position: Position::new(
+                value(Channel::X, to_f64(&object.position.x)),
+                value(Channel::Y, to_f64(&object.position.y)),
+            ),
+            scale: value(Channel::Scale, 1.0),
+            vector_scale: self.value(Channel::VectorScale, time).unwrap_or((1.0, 1.0)),
+            rotation: value(Channel::Rotation, 0.0),
+            opacity: value(Channel::Opacity, 1.0),
+        }
+    }
+}
+
+impl Object {
+    /// Time from the start of the first command to the end of the last command, which is when the game shows the object.
+    /// - Returns `None` if the object has no commands that change its state.
+    pub fn lifetime(&self) -> Option<(f64, f64)> {
+        Timeline::new(self).lifetime()
+    }
+
+    /// State of the object at `time` from its commands.
+    /// - Returns `None` outside of the [`lifetime`][Self::lifetime].
+    pub fn state_at(&self, time: Integer) -> Option<ObjectState> {
+        let timeline = Timeline::new(self);
+        let time = time as f64;
+        let (start, end) = timeline.lifetime()?;
+
+        if time < start || time > end {
+            return None;
+        }
+
+        Some(timeline.state_at(self, time))
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
 use super::error::*;
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum TriggerType {
     HitSound {
@@ -67,30 +68,44 @@ impl VersionedFromStr for TriggerType {
                         for field in fields {
                             loop {
                                 match field_parse_attempt_index {
-                                        0 => if let Ok(field) = SampleSet::from_str(&field, version).map(|s| s.unwrap()) {
+                                    0 => {
+                                        if let Ok(Some(field)) =
+                                            SampleSet::from_str(&field, version)
+                                        {
                                             sample_set = Some(field);
                                             field_parse_attempt_index += 1;
                                             break;
                                         }
-                                        1 => if let Ok(field) = SampleSet::from_str(&field, version).map(|f| f.unwrap()) {
+                                    }
+                                    1 => {
+                                        if let Ok(Some(field)) =
+                                            SampleSet::from_str(&field, version)
+                                        {
                                             additions_sample_set = Some(field);
                                             field_parse_attempt_index += 1;
                                             break;
                                         }
-                                        2 => if let Ok(field) = Addition::from_str(&field, version).map(|a| a.unwrap()) {
+                                    }
+                                    2 => {
+                                        if let Ok(Some(field)) = Addition::from_str(&field, version)
+                                        {
                                             addition = Some(field);
                                             field_parse_attempt_index += 1;
                                             break;
                                         }
-                                        3 => if let Ok(field) = field.parse() {
+                                    }
+                                    3 => {
+                                        if let Ok(field) = field.parse() {
                                             custom_sample_set = Some(field);
                                             field_parse_attempt_index += 1;
                                             break;
                                         } else {
-                                            return Err(ParseTriggerTypeError::UnknownHitSoundType)
+                                            return Err(ParseTriggerTypeError::UnknownHitSoundType);
                                         }
-                                        _ => unreachable!("The check for field size is already done so this is impossible to reach")
                                     }
+                                    // all of the field types are already used up
+                                    _ => return Err(ParseTriggerTypeError::UnknownHitSoundType),
+                                }
                                 field_parse_attempt_index += 1;
                             }
                         }
@@ -133,6 +148,7 @@ impl VersionedToString for TriggerType {
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum SampleSet {
     All,
@@ -169,6 +185,7 @@ impl VersionedToString for SampleSet {
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum Addition {
     Whistle,
@@ -202,6 +219,7 @@ impl VersionedToString for Addition {
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum Easing {
     Linear,
@@ -335,6 +353,7 @@ impl VersionedToString for Easing {
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum Parameter {
     ImageFlipHorizontal,
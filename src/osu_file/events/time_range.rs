@@ -0,0 +1,172 @@
+//! Module for finding the events that are active in a window of time, for preview and scrubbing tools.
+
+use std::cell::OnceCell;
+
+use rust_decimal::prelude::ToPrimitive;
+
+use super::{
+    storyboard::{cmds::Command, state::Timeline},
+    Event, Events,
+};
+use crate::osu_file::Integer;
+
+/// Time from the start to the end of the event, where events without an end last until the end of the beatmap.
+/// - Returns `None` for comments and for events with commands that don't have any commands.
+fn event_interval(event: &Event) -> Option<(f64, f64)> {
+    let commands_lifetime = |commands: &[Command]| Timeline::from_commands(commands).lifetime();
+
+    match event {
+        Event::Comment(_) => None,
+        // backgrounds are shown for the whole beatmap unless they are storyboarded
+        Event::Background(background) => {
+            commands_lifetime(&background.commands).or(Some((f64::NEG_INFINITY, f64::INFINITY)))
+        }
+        Event::Video(video) => Some((video.start_time as f64, f64::INFINITY)),
+        Event::Break(b) => Some((b.start_time as f64, b.end_time as f64)),
+        Event::ColourTransformation(colour) => {
+            Some((colour.start_time as f64, colour.start_time as f64))
+        }
+        Event::SpriteLegacy(sprite) => commands_lifetime(&sprite.commands),
+        Event::AnimationLegacy(animation) => commands_lifetime(&animation.commands),
+        Event::SampleLegacy(sample) => {
+            let time = sample.time.to_f64()?;
+            Some((time, time))
+        }
+        Event::StoryboardObject(object) => object.lifetime(),
+        Event::AudioSample(sample) => Some((sample.time as f64, sample.time as f64)),
+    }
+}
+
+fn intersects((start, end): (f64, f64), window_start: Integer, window_end: Integer) -> bool {
+    start <= window_end as f64 && end >= window_start as f64
+}
+
+impl Events {
+    /// Events that are active at any time from `start` to `end`, including both, in the order they are in the section.
+    /// - Storyboard objects are active from the start of their first command to the end of their last, see [`Object::lifetime`][super::storyboard::sprites::Object::lifetime].
+    /// - Backgrounds without commands and videos last until the end of the beatmap, and samples and colour transformations only last for their time.
+    /// - Scans every event, so use [`time_index`][Self::time_index] when querying the same events many times.
+    pub fn in_time_range(&self, start: Integer, end: Integer) -> Vec<&Event> {
+        self.0
+            .iter()
+            .filter(|event| event_interval(event).is_some_and(|i| intersects(i, start, end)))
+            .collect()
+    }
+
+    /// Index for querying the events by time many times, which is built on the first query.
+    pub fn time_index(&self) -> EventTimeIndex<'_> {
+        EventTimeIndex {
+            events: self,
+            tree: OnceCell::new(),
+        }
+    }
+}
+
+/// Interval of an event, by the index of the event.
+#[derive(Clone, Copy, Debug)]
+struct Interval {
+    start: f64,
+    end: f64,
+    index: usize,
+}
+
+/// Intervals sorted by their start, as a balanced tree where each subtree is a range of the intervals with its root in the middle.
+/// - `max_ends` has the latest end in the subtree rooted at each interval, so subtrees that end before the window are skipped.
+#[derive(Clone, Debug)]
+struct IntervalTree {
+    intervals: Vec<Interval>,
+    max_ends: Vec<f64>,
+}
+
+impl IntervalTree {
+    fn new(events: &Events) -> Self {
+        let mut intervals = events
+            .0
+            .iter()
+            .enumerate()
+            .filter_map(|(index, event)| {
+                event_interval(event).map(|(start, end)| Interval { start, end, index })
+            })
+            .collect::<Vec<_>>();
+        intervals.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+        let mut tree = Self {
+            max_ends: vec![f64::NEG_INFINITY; intervals.len()],
+            intervals,
+        };
+        tree.fill_max_ends(0, tree.intervals.len());
+        tree
+    }
+
+    fn fill_max_ends(&mut self, lo: usize, hi: usize) -> f64 {
+        if lo >= hi {
+            return f64::NEG_INFINITY;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        let max_end = self.intervals[mid]
+            .end
+            .max(self.fill_max_ends(lo, mid))
+            .max(self.fill_max_ends(mid + 1, hi));
+        self.max_ends[mid] = max_end;
+        max_end
+    }
+
+    fn query(&self, lo: usize, hi: usize, start: f64, end: f64, found: &mut Vec<usize>) {
+        if lo >= hi {
+            return;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        if self.max_ends[mid] < start {
+            return;
+        }
+
+        self.query(lo, mid, start, end, found);
+
+        let interval = self.intervals[mid];
+        // the intervals after the root start even later
+        if interval.start > end {
+            return;
+        }
+        if interval.end >= start {
+            found.push(interval.index);
+        }
+
+        self.query(mid + 1, hi, start, end, found);
+    }
+}
+
+/// Index of [`Events`] by time, from [`Events::time_index`].
+/// - The index is built on the first query, and is kept for the lifetime of the borrow, so the events can't change while it's used.
+#[derive(Clone, Debug)]
+pub struct EventTimeIndex<'a> {
+    events: &'a Events,
+    tree: OnceCell<IntervalTree>,
+}
+
+impl<'a> EventTimeIndex<'a> {
+    /// Same as [`Events::in_time_range`], without scanning every event.
+    pub fn in_time_range(&self, start: Integer, end: Integer) -> Vec<&'a Event> {
+        self.indices_in_time_range(start, end)
+            .into_iter()
+            .map(|i| &self.events.0[i])
+            .collect()
+    }
+
+    /// Indices of the events returned by [`in_time_range`][Self::in_time_range], in increasing order.
+    pub fn indices_in_time_range(&self, start: Integer, end: Integer) -> Vec<usize> {
+        let tree = self.tree.get_or_init(|| IntervalTree::new(self.events));
+
+        let mut found = Vec::new();
+        tree.query(
+            0,
+            tree.intervals.len(),
+            start as f64,
+            end as f64,
+            &mut found,
+        );
+        found.sort_unstable();
+        found
+    }
+}
@@ -0,0 +1,220 @@
+//! Module for identifying hitobjects, timing points and storyboard objects, and attaching data of any type to them.
+//! - The id and data are stored in the object itself, so they follow the object through sorting, offsetting and the other transforms.
+//! - Meant for tools that need to keep their own state per object, such as an editor's selection or an analysis result.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use super::{
+    events::{storyboard::sprites::Object, Event},
+    hitobjects::HitObject,
+    timingpoints::TimingPoint,
+    Events, HitObjects, TimingPoints, Untracked,
+};
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Opaque id of a hitobject, timing point or storyboard object, given to it when it's parsed or created.
+/// - Clones keep the id, so an object can be found again in a copy of the file that was changed.
+/// - Set a [`new`][Self::new] id on a clone to use it as a different object, such as when duplicating objects.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ObjectId(u64);
+
+impl ObjectId {
+    /// New id that is different from every other id made by this process.
+    pub fn new() -> Self {
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Default for ObjectId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// [`ObjectId`] of an object, and a map from a type to a value of that type attached to the object.
+/// - Holds at most one value of each type, so wrap values in a type of your own to avoid clashing with other tools.
+/// - Ignored by `Eq` and `Hash`, and isn't written to the file.
+/// - Values are shared between clones of the object, so cloning is cheap.
+#[derive(Clone, Default, Hash, PartialEq, Eq)]
+pub struct Extensions {
+    id: Untracked<ObjectId>,
+    values: Untracked<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
+}
+
+impl Extensions {
+    /// New extensions with a [`new`][ObjectId::new] id and no values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(&self) -> ObjectId {
+        self.id.0
+    }
+
+    pub fn set_id(&mut self, id: ObjectId) {
+        self.id.0 = id;
+    }
+
+    /// Value of the type `T`, if one was inserted.
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.values
+            .0
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Inserts the value, returning the value of the same type it replaced.
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) -> Option<Arc<T>> {
+        self.values
+            .0
+            .insert(TypeId::of::<T>(), Arc::new(value))
+            .and_then(|value| value.downcast().ok())
+    }
+
+    /// Removes the value of the type `T`, returning it.
+    pub fn remove<T: Any + Send + Sync>(&mut self) -> Option<Arc<T>> {
+        self.values
+            .0
+            .remove(&TypeId::of::<T>())
+            .and_then(|value| value.downcast().ok())
+    }
+
+    /// Returns `true` if a value of the type `T` was inserted.
+    pub fn contains<T: Any + Send + Sync>(&self) -> bool {
+        self.values.0.contains_key(&TypeId::of::<T>())
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.0.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.values.0.clear();
+    }
+}
+
+impl Debug for Extensions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Extensions")
+            .field("len", &self.values.0.len())
+            .finish()
+    }
+}
+
+impl HitObject {
+    /// Id of the hitobject, which is kept by clones.
+    pub fn id(&self) -> ObjectId {
+        self.extensions.id()
+    }
+}
+
+impl TimingPoint {
+    /// Id of the timing point, which is kept by clones.
+    pub fn id(&self) -> ObjectId {
+        self.extensions.id()
+    }
+}
+
+impl Object {
+    /// Id of the storyboard object, which is kept by clones.
+    pub fn id(&self) -> ObjectId {
+        self.extensions.id()
+    }
+}
+
+impl HitObjects {
+    /// Map from the id of each hitobject to its index.
+    pub fn id_map(&self) -> HashMap<ObjectId, usize> {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(i, h)| (h.id(), i))
+            .collect()
+    }
+
+    /// Index of the hitobject with the `id`.
+    pub fn position_by_id(&self, id: ObjectId) -> Option<usize> {
+        self.0.iter().position(|h| h.id() == id)
+    }
+
+    pub fn get_by_id(&self, id: ObjectId) -> Option<&HitObject> {
+        self.0.iter().find(|h| h.id() == id)
+    }
+
+    pub fn get_by_id_mut(&mut self, id: ObjectId) -> Option<&mut HitObject> {
+        self.0.iter_mut().find(|h| h.id() == id)
+    }
+}
+
+impl TimingPoints {
+    /// Map from the id of each timing point to its index.
+    pub fn id_map(&self) -> HashMap<ObjectId, usize> {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.id(), i))
+            .collect()
+    }
+
+    /// Index of the timing point with the `id`.
+    pub fn position_by_id(&self, id: ObjectId) -> Option<usize> {
+        self.0.iter().position(|t| t.id() == id)
+    }
+
+    pub fn get_by_id(&self, id: ObjectId) -> Option<&TimingPoint> {
+        self.0.iter().find(|t| t.id() == id)
+    }
+
+    pub fn get_by_id_mut(&mut self, id: ObjectId) -> Option<&mut TimingPoint> {
+        self.0.iter_mut().find(|t| t.id() == id)
+    }
+}
+
+fn storyboard_object(event: &Event) -> Option<&Object> {
+    match event {
+        Event::StoryboardObject(object) => Some(object),
+        _ => None,
+    }
+}
+
+impl Events {
+    /// Map from the id of each storyboard object to the index of its event.
+    /// - Only [`Event::StoryboardObject`] has an id.
+    pub fn id_map(&self) -> HashMap<ObjectId, usize> {
+        self.0
+            .iter()
+            .enumerate()
+            .filter_map(|(i, event)| storyboard_object(event).map(|object| (object.id(), i)))
+            .collect()
+    }
+
+    /// Index of the event of the storyboard object with the `id`.
+    pub fn position_by_id(&self, id: ObjectId) -> Option<usize> {
+        self.0
+            .iter()
+            .position(|event| storyboard_object(event).is_some_and(|object| object.id() == id))
+    }
+
+    pub fn get_by_id(&self, id: ObjectId) -> Option<&Object> {
+        self.0
+            .iter()
+            .filter_map(storyboard_object)
+            .find(|object| object.id() == id)
+    }
+
+    pub fn get_by_id_mut(&mut self, id: ObjectId) -> Option<&mut Object> {
+        self.0.iter_mut().find_map(|event| match event {
+            Event::StoryboardObject(object) if object.id() == id => Some(object),
+            _ => None,
+        })
+    }
+}
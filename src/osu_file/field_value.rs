@@ -0,0 +1,98 @@
+//! Module for accessing the fields of the key-value sections by name, such as with [`General::get`][super::General::get].
+//! - Meant for generic tools like config editors and diff viewers, which would otherwise have to list every field.
+
+use std::path::PathBuf;
+
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+use super::{Integer, VersionedFromStr, VersionedToString, LATEST_VERSION};
+
+/// Value of a field in the `[General]`, `[Editor]`, `[Metadata]` or `[Difficulty]` section.
+/// - Fields that are enums, such as `Countdown`, use how they are written in the file, which is an [`Integer`][FieldValue::Integer] or a [`String`][FieldValue::String].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FieldValue {
+    Integer(Integer),
+    Decimal(Decimal),
+    Bool(bool),
+    String(String),
+    Path(PathBuf),
+    IntegerList(Vec<Integer>),
+    StringList(Vec<String>),
+}
+
+/// Conversion between the type of a field and a [`FieldValue`].
+pub trait FieldValueType: Sized {
+    fn to_field_value(&self) -> FieldValue;
+
+    /// Converts the value back, returning `None` if it's the wrong kind of value for the field.
+    fn from_field_value(value: FieldValue) -> Option<Self>;
+}
+
+macro_rules! field_value_from {
+    ($($variant:ident($type:ty),)*) => {
+        $(
+            impl From<$type> for FieldValue {
+                fn from(value: $type) -> Self {
+                    FieldValue::$variant(value)
+                }
+            }
+
+            impl TryFrom<FieldValue> for $type {
+                type Error = FieldValue;
+
+                /// Gets the value back, returning the [`FieldValue`] if it's another kind of value.
+                fn try_from(value: FieldValue) -> Result<Self, Self::Error> {
+                    match value {
+                        FieldValue::$variant(value) => Ok(value),
+                        value => Err(value),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+field_value_from!(
+    Integer(Integer),
+    Decimal(Decimal),
+    Bool(bool),
+    String(String),
+    Path(PathBuf),
+    IntegerList(Vec<Integer>),
+    StringList(Vec<String>),
+);
+
+/// [`FieldValueType::to_field_value`] for enum fields, using how the value is written in the latest version.
+pub(crate) fn enum_to_field_value<T: VersionedToString>(value: &T) -> FieldValue {
+    let s = value.to_string(LATEST_VERSION).unwrap_or_default();
+
+    match s.parse() {
+        Ok(integer) => FieldValue::Integer(integer),
+        Err(_) => FieldValue::String(s),
+    }
+}
+
+/// [`FieldValueType::from_field_value`] for enum fields, parsing the value like it's written in the latest version.
+pub(crate) fn enum_from_field_value<T: VersionedFromStr>(value: FieldValue) -> Option<T> {
+    let s = match value {
+        FieldValue::Integer(integer) => integer.to_string(),
+        FieldValue::String(s) => s,
+        _ => return None,
+    };
+
+    T::from_str(&s, LATEST_VERSION).ok().flatten()
+}
+
+/// Error used when setting a field by name fails.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum SetFieldError {
+    /// There's no field with the name in the section.
+    #[error("There's no field named `{0}` in the section")]
+    UnknownField(String),
+    /// The value is the wrong kind of value for the field, such as a [`FieldValue::String`] for a number.
+    #[error("The value isn't valid for the field `{0}`")]
+    InvalidValue(String),
+}
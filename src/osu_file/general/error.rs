@@ -1,10 +1,11 @@
 use std::num::ParseIntError;
 
+use strum_macros::IntoStaticStr;
 use thiserror::Error;
 
 use crate::helper::{macros::unreachable_err_impl, ParseZeroOneBoolError};
 
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
 #[non_exhaustive]
 /// Error used when there was a problem parsing the `General` section.
 pub enum ParseError {
@@ -5,18 +5,18 @@ use std::fmt::Debug;
 use std::num::{IntErrorKind, ParseIntError};
 use std::path::PathBuf;
 
-use rust_decimal::Decimal;
 use crate::parsers::comma;
 use nom::bytes::complete::take_till;
 use nom::combinator::map_res;
 use nom::multi::separated_list0;
 use nom::Finish;
+use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 
 use crate::helper;
 use crate::helper::macros::*;
 
-use crate::osu_file::Integer;
+use crate::osu_file::{Error, Integer, ParseWarning, Version};
 
 pub use error::*;
 pub use types::*;
@@ -160,3 +160,116 @@ general_section!(
     ParseError,
     " ",
 );
+
+impl General {
+    /// Fields written in a new beatmap of the `mode`.
+    /// - `StackLeniency` is only set for osu!standard, the only mode with stacking.
+    /// - `SpecialStyle` is only set for osu!mania.
+    /// - Other fields use [`VersionedDefault::default_for_mode`][crate::osu_file::VersionedDefault::default_for_mode], and are `None` if they don't exist in the `version`.
+    pub fn default_for(version: Version, mode: Mode) -> General {
+        use crate::osu_file::VersionedDefault;
+
+        let mut general = General::new();
+        general.audio_filename = AudioFilename::default_for_mode(version, mode);
+        general.audio_lead_in = AudioLeadIn::default_for_mode(version, mode);
+        general.audio_hash = AudioHash::default_for_mode(version, mode);
+        general.preview_time = PreviewTime::default_for_mode(version, mode);
+        general.countdown = Countdown::default_for_mode(version, mode);
+        general.sample_set = SampleSet::default_for_mode(version, mode);
+        general.mode = Mode::default_for_mode(version, mode);
+        general.letterbox_in_breaks = LetterboxInBreaks::default_for_mode(version, mode);
+        general.widescreen_storyboard = WidescreenStoryboard::default_for_mode(version, mode);
+
+        match mode {
+            Mode::Osu => general.stack_leniency = StackLeniency::default_for_mode(version, mode),
+            Mode::Mania => general.special_style = SpecialStyle::default_for_mode(version, mode),
+            _ => (),
+        }
+
+        general
+    }
+
+    /// Parses the section like [`from_str`][Self::from_str], but keeps `Countdown`, `Mode` and `OverlayPosition` values that aren't known as their `Other` variants.
+    /// - Used with [`ParseOptions::lenient`][crate::osu_file::ParseOptions::lenient].
+    pub fn from_str_lenient(
+        s: &str,
+        version: Version,
+    ) -> Result<Option<General>, Error<ParseError>> {
+        Self::from_str_lenient_with_warnings(s, version, 0, &mut Vec::new())
+    }
+
+    /// [`from_str_lenient`][Self::from_str_lenient] that adds a warning for each value that isn't known.
+    /// - `line_number` is the line index where the section starts.
+    pub(crate) fn from_str_lenient_with_warnings(
+        s: &str,
+        version: Version,
+        line_number: usize,
+        warnings: &mut Vec<ParseWarning>,
+    ) -> Result<Option<General>, Error<ParseError>> {
+        let mut other_countdown = None;
+        let mut other_mode = None;
+        let mut other_overlay_position = None;
+
+        // unknown values are swapped for a known one while parsing, which keeps the line indexes of other errors
+        let s = s
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let (key, value) = match line.split_once(':') {
+                    Some(field) => field,
+                    None => return line,
+                };
+
+                let known = match key {
+                    "Countdown" => match Countdown::from_str_lenient(value.trim(), version) {
+                        Ok(Some(countdown @ Countdown::Other(_))) => {
+                            other_countdown = Some(countdown);
+                            "Countdown: 1"
+                        }
+                        _ => return line,
+                    },
+                    "Mode" => match Mode::from_str_lenient(value.trim(), version) {
+                        Ok(Some(mode @ Mode::Other(_))) => {
+                            other_mode = Some(mode);
+                            "Mode: 0"
+                        }
+                        _ => return line,
+                    },
+                    "OverlayPosition" => {
+                        match OverlayPosition::from_str_lenient(value.trim(), version) {
+                            Ok(Some(position @ OverlayPosition::Other(_))) => {
+                                other_overlay_position = Some(position);
+                                "OverlayPosition: NoChange"
+                            }
+                            _ => return line,
+                        }
+                    }
+                    _ => return line,
+                };
+
+                warnings.push(ParseWarning::UnknownValue {
+                    line_index: line_number + i,
+                    field: key.to_string(),
+                    value: value.trim().to_string(),
+                });
+                known
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut general = General::from_str(&s, version)?;
+        if let Some(general) = &mut general {
+            if let Some(countdown) = other_countdown {
+                general.countdown = Some(countdown);
+            }
+            if let Some(mode) = other_mode {
+                general.mode = Some(mode);
+            }
+            if let Some(position) = other_overlay_position {
+                general.overlay_position = Some(position);
+            }
+        }
+
+        Ok(general)
+    }
+}
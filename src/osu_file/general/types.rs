@@ -1,22 +1,30 @@
 use crate::osu_file::{
-    InvalidRepr, Version, VersionedDefault, VersionedFromRepr, VersionedFromStr, VersionedToString,
-    MIN_VERSION,
+    field_value::{enum_from_field_value, enum_to_field_value},
+    FieldValue, FieldValueType, InvalidRepr, Version, VersionedDefault, VersionedFromRepr,
+    VersionedFromStr, VersionedToString, MIN_VERSION,
 };
 
+use crate::schema::DescribeField;
+
 use super::error::*;
 
 /// Speed of the countdown before the first hitobject.
+/// - Written as a number, `0` to `3`.
 #[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum Countdown {
     /// No countdown.
-    NoCountdown,
+    Off,
     /// Normal speed.
     Normal,
     /// Half speed.
     Half,
     /// Double speed.
     Double,
+    /// Value that isn't one of the known speeds.
+    /// - Only parsed with [`ParseOptions::lenient`][crate::osu_file::ParseOptions::lenient], otherwise it's an error.
+    Other(u8),
 }
 
 impl VersionedFromRepr for Countdown {
@@ -24,7 +32,7 @@ impl VersionedFromRepr for Countdown {
         match version {
             MIN_VERSION..=4 => Ok(None),
             _ => match repr {
-                0 => Ok(Some(Countdown::NoCountdown)),
+                0 => Ok(Some(Countdown::Off)),
                 1 => Ok(Some(Countdown::Normal)),
                 2 => Ok(Some(Countdown::Half)),
                 3 => Ok(Some(Countdown::Double)),
@@ -37,6 +45,7 @@ impl VersionedFromRepr for Countdown {
 impl VersionedFromStr for Countdown {
     type Err = ParseCountdownSpeedError;
 
+    /// Parses the countdown, failing on numbers that aren't one of the known speeds.
     fn from_str(s: &str, version: Version) -> std::result::Result<Option<Self>, Self::Err> {
         match version {
             MIN_VERSION..=4 => Ok(None),
@@ -48,11 +57,37 @@ impl VersionedFromStr for Countdown {
     }
 }
 
+impl Countdown {
+    /// No countdown, which was renamed to [`Off`][Countdown::Off].
+    #[deprecated(note = "renamed to `Countdown::Off`")]
+    #[allow(non_upper_case_globals)]
+    pub const NoCountdown: Countdown = Countdown::Off;
+
+    /// Parses the countdown like [`from_str`][VersionedFromStr::from_str], but keeps numbers that aren't one of the known speeds as [`Other`][Countdown::Other].
+    pub fn from_str_lenient(
+        s: &str,
+        version: Version,
+    ) -> std::result::Result<Option<Self>, ParseCountdownSpeedError> {
+        match Countdown::from_str(s, version) {
+            Err(ParseCountdownSpeedError::UnknownVariant) => Ok(Some(Countdown::Other(s.parse()?))),
+            result => result,
+        }
+    }
+}
+
 impl VersionedToString for Countdown {
     fn to_string(&self, version: Version) -> Option<String> {
+        let countdown = match self {
+            Countdown::Off => 0,
+            Countdown::Normal => 1,
+            Countdown::Half => 2,
+            Countdown::Double => 3,
+            Countdown::Other(countdown) => *countdown,
+        };
+
         match version {
             MIN_VERSION..=4 => None,
-            _ => Some((*self as usize).to_string()),
+            _ => Some(countdown.to_string()),
         }
     }
 }
@@ -68,6 +103,7 @@ impl VersionedDefault for Countdown {
 
 /// Sample set that will be used if timing points do not override it
 #[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum SampleSet {
     /// The `Normal` sample set.
@@ -129,6 +165,7 @@ impl VersionedDefault for SampleSet {
 
 /// Game mode of the .osu file
 #[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum Mode {
     /// Osu! gamemode.
@@ -139,6 +176,9 @@ pub enum Mode {
     Catch,
     /// Osu!Mania gamemode.
     Mania,
+    /// Number that isn't one of the known gamemodes.
+    /// - Only parsed with [`ParseOptions::lenient`][crate::osu_file::ParseOptions::lenient], otherwise it's an error.
+    Other(u8),
 }
 
 impl VersionedFromStr for Mode {
@@ -171,9 +211,35 @@ impl VersionedFromStr for Mode {
     }
 }
 
+impl Mode {
+    /// Parses the gamemode like [`from_str`][VersionedFromStr::from_str], but keeps numbers that aren't one of the known gamemodes as [`Other`][Mode::Other].
+    pub fn from_str_lenient(
+        s: &str,
+        version: Version,
+    ) -> std::result::Result<Option<Self>, ParseGameModeError> {
+        match Mode::from_str(s, version) {
+            Err(ParseGameModeError::UnknownVariant) => Ok(Some(Mode::Other(s.parse()?))),
+            result => result,
+        }
+    }
+}
+
+impl From<Mode> for u8 {
+    /// Number of the gamemode as it's written in the file, which replaces casting with `as u8`.
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Osu => 0,
+            Mode::Taiko => 1,
+            Mode::Catch => 2,
+            Mode::Mania => 3,
+            Mode::Other(mode) => mode,
+        }
+    }
+}
+
 impl VersionedToString for Mode {
     fn to_string(&self, _: Version) -> Option<String> {
-        Some((*self as usize).to_string())
+        Some(u8::from(*self).to_string())
     }
 }
 
@@ -181,10 +247,15 @@ impl VersionedDefault for Mode {
     fn default(_: Version) -> Option<Self> {
         Some(Self::Osu)
     }
+
+    fn default_for_mode(_: Version, mode: Mode) -> Option<Self> {
+        Some(mode)
+    }
 }
 
 /// Draw order of hit circle overlays compared to hit numbers
-#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum OverlayPosition {
     /// Use skin setting.
@@ -193,6 +264,9 @@ pub enum OverlayPosition {
     Below,
     /// Draw overlays on top of numbers.
     Above,
+    /// Name that isn't one of the known positions.
+    /// - Only parsed with [`ParseOptions::lenient`][crate::osu_file::ParseOptions::lenient], otherwise it's an error.
+    Other(String),
 }
 
 impl VersionedToString for OverlayPosition {
@@ -204,6 +278,7 @@ impl VersionedToString for OverlayPosition {
                     OverlayPosition::NoChange => "NoChange",
                     OverlayPosition::Below => "Below",
                     OverlayPosition::Above => "Above",
+                    OverlayPosition::Other(position) => position,
                 }
                 .to_string(),
             ),
@@ -227,6 +302,21 @@ impl VersionedFromStr for OverlayPosition {
     }
 }
 
+impl OverlayPosition {
+    /// Parses the position like [`from_str`][VersionedFromStr::from_str], but keeps names that aren't one of the known positions as [`Other`][OverlayPosition::Other].
+    pub fn from_str_lenient(
+        s: &str,
+        version: Version,
+    ) -> std::result::Result<Option<Self>, ParseOverlayPositionError> {
+        match OverlayPosition::from_str(s, version) {
+            Err(ParseOverlayPositionError::UnknownVariant) => {
+                Ok(Some(OverlayPosition::Other(s.to_string())))
+            }
+            result => result,
+        }
+    }
+}
+
 impl VersionedDefault for OverlayPosition {
     fn default(version: Version) -> Option<Self> {
         match version {
@@ -235,3 +325,44 @@ impl VersionedDefault for OverlayPosition {
         }
     }
 }
+
+macro_rules! enum_field_value {
+    ($($type:ty),*) => {
+        $(
+            impl FieldValueType for $type {
+                fn to_field_value(&self) -> FieldValue {
+                    enum_to_field_value(self)
+                }
+
+                fn from_field_value(value: FieldValue) -> Option<Self> {
+                    enum_from_field_value(value)
+                }
+            }
+        )*
+    };
+}
+
+enum_field_value!(Countdown, SampleSet, Mode, OverlayPosition);
+
+macro_rules! enum_describe_field {
+    ($($type:ty: $sample:expr),*) => {
+        $(
+            impl DescribeField for $type {
+                fn sample() -> Self {
+                    $sample
+                }
+
+                fn default_value(version: Version) -> Option<Self> {
+                    <$type as VersionedDefault>::default(version)
+                }
+            }
+        )*
+    };
+}
+
+enum_describe_field!(
+    Countdown: Countdown::Normal,
+    SampleSet: SampleSet::Normal,
+    Mode: Mode::Osu,
+    OverlayPosition: OverlayPosition::NoChange
+);
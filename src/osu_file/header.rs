@@ -0,0 +1,38 @@
+//! Module for the `osu file format v..` line as it was written in the parsed file.
+
+use super::{OsuFile, Version};
+
+/// Zero-width characters that some editors leave around the header, which aren't whitespace.
+const ZERO_WIDTH_CHARS: [char; 5] = ['\u{feff}', '\u{200b}', '\u{200c}', '\u{200d}', '\u{2060}'];
+
+/// Returns `true` for the characters allowed before and after the header, which are whitespace, the BOM and other zero-width characters.
+pub(crate) fn is_header_junk(c: char) -> bool {
+    c.is_whitespace() || ZERO_WIDTH_CHARS.contains(&c)
+}
+
+/// Header of a parsed .osu file, with what came before it.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FileHeader {
+    /// Everything before `osu file format`, such as a BOM, zero-width characters or empty lines.
+    pub leading: String,
+    /// The header line without the line ending, including anything after the version such as spaces.
+    pub line: String,
+    /// Version in the header.
+    pub version: Version,
+}
+
+impl FileHeader {
+    /// Returns `true` if the header is written the same as [`Display`][std::fmt::Display] writes it.
+    pub fn is_normalized(&self) -> bool {
+        self.leading.is_empty() && self.line == format!("osu file format v{}", self.version)
+    }
+}
+
+impl OsuFile {
+    /// Header of the file as it was parsed.
+    /// - Returns `None` if the file wasn't parsed.
+    /// - Written back with [`WriteOptions::preserve_header`][super::WriteOptions::preserve_header].
+    pub fn header(&self) -> Option<&FileHeader> {
+        self.header.0.as_ref()
+    }
+}
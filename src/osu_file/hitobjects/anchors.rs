@@ -0,0 +1,719 @@
+//! Module for editing the anchors of a [`slider`][super::SlideParams].
+//! - Splitting and reversing work on the path the game draws, which is cut or extended in a straight line to the slider's `length`.
+//! - Positions made by splitting and reversing are rounded to whole osu!pixels, as the game writes them.
+
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use rust_decimal::Decimal;
+
+use super::{CurvePoint, CurveType, NodeSample, SlideParams, SliderEditError};
+use crate::osu_file::Position;
+
+type Point = Position<f64>;
+
+/// Samples taken along each control point of a Bézier curve when measuring it.
+const BEZIER_SAMPLES_PER_POINT: usize = 64;
+
+/// Most points [`SlideParams::path_points`] gives along a slide, besides its end.
+pub const MAX_PATH_POINTS: usize = 10_000;
+
+/// A section of the slider path between red anchors, as indexes into the head followed by the `curve_points`.
+/// - Index `0` is the head of the slider and index `1` is the first of the `curve_points`.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SliderSegment {
+    /// Index of the first point.
+    pub start: usize,
+    /// Index of the last point, inclusive.
+    pub end: usize,
+}
+
+/// Two sliders made by [`SlideParams::split`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SliderSplit {
+    /// Slider from the head to the split point, which keeps the head position.
+    pub first: SlideParams,
+    /// Position of the head of the [`second`][Self::second] slider, which is the split point.
+    pub second_position: Position,
+    /// Slider from the split point to the end.
+    pub second: SlideParams,
+}
+
+fn lerp(a: Point, b: Point, t: f64) -> Point {
+    Position::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t)
+}
+
+fn distance(a: Point, b: Point) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+/// Splits the Bézier curve at `t`, returning the control points of both halves.
+fn de_casteljau(points: &[Point], t: f64) -> (Vec<Point>, Vec<Point>) {
+    let mut left = Vec::with_capacity(points.len());
+    let mut right = Vec::with_capacity(points.len());
+    let mut current = points.to_vec();
+
+    while let (Some(first), Some(last)) = (current.first(), current.last()) {
+        left.push(*first);
+        right.push(*last);
+        current = current.windows(2).map(|w| lerp(w[0], w[1], t)).collect();
+    }
+
+    right.reverse();
+    (left, right)
+}
+
+fn bezier_point(points: &[Point], t: f64) -> Point {
+    *de_casteljau(points, t).0.last().unwrap()
+}
+
+/// Centre and radius of the circle through the 3 points, or `None` if they are on a line.
+fn circle(a: Point, b: Point, c: Point) -> Option<(Point, f64)> {
+    let d = 2.0 * (a.x * (b.y - c.y) + b.x * (c.y - a.y) + c.x * (a.y - b.y));
+
+    if d.abs() < 1e-9 {
+        return None;
+    }
+
+    let (a_sq, b_sq, c_sq) = (
+        a.x * a.x + a.y * a.y,
+        b.x * b.x + b.y * b.y,
+        c.x * c.x + c.y * c.y,
+    );
+    let centre = Position::new(
+        (a_sq * (b.y - c.y) + b_sq * (c.y - a.y) + c_sq * (a.y - b.y)) / d,
+        (a_sq * (c.x - b.x) + b_sq * (a.x - c.x) + c_sq * (b.x - a.x)) / d,
+    );
+
+    Some((centre, distance(centre, a)))
+}
+
+/// A piece of the slider path, which is the section between red anchors.
+#[derive(Clone, Debug, PartialEq)]
+enum Piece {
+    /// Bézier curve through the control points, which is a straight line with 2 points.
+    Bezier(Vec<Point>),
+    /// Arc of a circle, going `angle` radians from `start_angle`.
+    Arc {
+        centre: Point,
+        radius: f64,
+        start_angle: f64,
+        angle: f64,
+    },
+}
+
+impl Piece {
+    /// Arc through the 3 points, or `None` if they are on a line.
+    fn arc(a: Point, b: Point, c: Point) -> Option<Self> {
+        let (centre, radius) = circle(a, b, c)?;
+        let angle_of = |p: Point| (p.y - centre.y).atan2(p.x - centre.x);
+
+        let start_angle = angle_of(a);
+        let mut angle = (angle_of(c) - start_angle).rem_euclid(TAU);
+
+        // goes the other way around if the points are clockwise
+        let cross = (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x);
+        if cross < 0.0 {
+            angle -= TAU;
+        }
+
+        Some(Piece::Arc {
+            centre,
+            radius,
+            start_angle,
+            angle,
+        })
+    }
+
+    fn arc_point(centre: Point, radius: f64, angle: f64) -> Point {
+        Position::new(
+            centre.x + radius * angle.cos(),
+            centre.y + radius * angle.sin(),
+        )
+    }
+
+    /// Distance along the curve at each sample, with the `t` of the sample.
+    fn bezier_samples(points: &[Point]) -> Vec<(f64, f64)> {
+        let steps = BEZIER_SAMPLES_PER_POINT * points.len().saturating_sub(1).max(1);
+        let mut samples = vec![(0.0, 0.0)];
+        let mut last = points[0];
+        let mut length = 0.0;
+
+        for step in 1..=steps {
+            let t = step as f64 / steps as f64;
+            let point = bezier_point(points, t);
+
+            length += distance(last, point);
+            last = point;
+            samples.push((length, t));
+        }
+
+        samples
+    }
+
+    fn length(&self) -> f64 {
+        match self {
+            Piece::Bezier(points) if points.len() == 2 => distance(points[0], points[1]),
+            Piece::Bezier(points) => Self::bezier_samples(points).last().unwrap().0,
+            Piece::Arc { radius, angle, .. } => radius * angle.abs(),
+        }
+    }
+
+    fn start(&self) -> Point {
+        match self {
+            Piece::Bezier(points) => points[0],
+            Piece::Arc {
+                centre,
+                radius,
+                start_angle,
+                ..
+            } => Self::arc_point(*centre, *radius, *start_angle),
+        }
+    }
+
+    fn end(&self) -> Point {
+        match self {
+            Piece::Bezier(points) => *points.last().unwrap(),
+            Piece::Arc {
+                centre,
+                radius,
+                start_angle,
+                angle,
+            } => Self::arc_point(*centre, *radius, start_angle + angle),
+        }
+    }
+
+    /// Direction the path is going at the end, which isn't normalized.
+    fn end_direction(&self) -> Point {
+        match self {
+            Piece::Bezier(points) => {
+                let end = *points.last().unwrap();
+                let before = points.iter().rev().find(|p| **p != end).unwrap_or(&end);
+
+                Position::new(end.x - before.x, end.y - before.y)
+            }
+            Piece::Arc {
+                start_angle, angle, ..
+            } => {
+                let end_angle = start_angle + angle;
+                let sign = angle.signum();
+
+                Position::new(-end_angle.sin() * sign, end_angle.cos() * sign)
+            }
+        }
+    }
+
+    /// Splits the piece `at` the distance along it.
+    fn split(&self, at: f64) -> (Piece, Piece) {
+        match self {
+            Piece::Bezier(points) => {
+                let t = if points.len() == 2 {
+                    at / self.length()
+                } else {
+                    let samples = Self::bezier_samples(points);
+                    let index = samples.partition_point(|(length, _)| *length < at).max(1);
+                    let (before, after) =
+                        (samples[index - 1], samples[index.min(samples.len() - 1)]);
+
+                    if after.0 > before.0 {
+                        before.1 + (after.1 - before.1) * (at - before.0) / (after.0 - before.0)
+                    } else {
+                        after.1
+                    }
+                };
+                let (left, right) = de_casteljau(points, t.clamp(0.0, 1.0));
+
+                (Piece::Bezier(left), Piece::Bezier(right))
+            }
+            Piece::Arc {
+                centre,
+                radius,
+                start_angle,
+                angle,
+            } => {
+                let split_angle = angle.signum() * at / radius;
+                let arc = |start_angle, angle| Piece::Arc {
+                    centre: *centre,
+                    radius: *radius,
+                    start_angle,
+                    angle,
+                };
+
+                (
+                    arc(*start_angle, split_angle),
+                    arc(start_angle + split_angle, angle - split_angle),
+                )
+            }
+        }
+    }
+
+    fn reversed(&self) -> Piece {
+        match self {
+            Piece::Bezier(points) => Piece::Bezier(points.iter().rev().copied().collect()),
+            Piece::Arc {
+                centre,
+                radius,
+                start_angle,
+                angle,
+            } => Piece::Arc {
+                centre: *centre,
+                radius: *radius,
+                start_angle: start_angle + angle,
+                angle: -angle,
+            },
+        }
+    }
+
+    /// Control points of the piece as Bézier curves, where arcs are made of cubic curves of up to a quarter circle.
+    fn into_beziers(self) -> Vec<Vec<Point>> {
+        let (centre, radius, start_angle, angle) = match self {
+            Piece::Bezier(points) => return vec![points],
+            Piece::Arc {
+                centre,
+                radius,
+                start_angle,
+                angle,
+            } => (centre, radius, start_angle, angle),
+        };
+
+        let count = (angle.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+        let sweep = angle / count as f64;
+        let handle = 4.0 / 3.0 * (sweep / 4.0).tan() * radius;
+
+        (0..count)
+            .map(|i| {
+                let from = start_angle + sweep * i as f64;
+                let to = from + sweep;
+                let (p0, p3) = (
+                    Self::arc_point(centre, radius, from),
+                    Self::arc_point(centre, radius, to),
+                );
+
+                vec![
+                    p0,
+                    Position::new(p0.x - handle * from.sin(), p0.y + handle * from.cos()),
+                    Position::new(p3.x + handle * to.sin(), p3.y - handle * to.cos()),
+                    p3,
+                ]
+            })
+            .collect()
+    }
+}
+
+/// Splits the pieces `at` the distance along the path.
+fn split_pieces(pieces: Vec<Piece>, at: f64) -> (Vec<Piece>, Vec<Piece>) {
+    let mut first = Vec::new();
+    let mut second = Vec::new();
+    let mut remaining = at;
+    let mut pieces = pieces.into_iter();
+
+    for piece in pieces.by_ref() {
+        let length = piece.length();
+
+        if remaining < length {
+            let (before, after) = piece.split(remaining);
+
+            if remaining > 0.0 {
+                first.push(before);
+            }
+            second.push(after);
+            break;
+        }
+
+        remaining -= length;
+        first.push(piece);
+    }
+
+    second.extend(pieces);
+    (first, second)
+}
+
+/// Cuts or extends the pieces in a straight line so the path is `length` long, like the game does.
+fn fit_pieces(mut pieces: Vec<Piece>, length: f64) -> Vec<Piece> {
+    let total = pieces.iter().map(Piece::length).sum::<f64>();
+
+    if length < total {
+        return split_pieces(pieces, length).0;
+    }
+
+    if let Some(last) = pieces.last() {
+        let direction = last.end_direction();
+        let direction_length = direction.x.hypot(direction.y);
+
+        if length > total && direction_length > 0.0 {
+            let end = last.end();
+            let scale = (length - total) / direction_length;
+
+            pieces.push(Piece::Bezier(vec![
+                end,
+                Position::new(end.x + direction.x * scale, end.y + direction.y * scale),
+            ]));
+        }
+    }
+
+    pieces
+}
+
+fn to_position(point: Point) -> Result<Position, SliderEditError> {
+    Position::try_from(point.map(f64::round)).map_err(|_| SliderEditError::InvalidPosition)
+}
+
+/// Turns the pieces back into the curve type and the points, with the head first.
+fn from_pieces(
+    pieces: Vec<Piece>,
+    linear: bool,
+) -> Result<(CurveType, Vec<Position>), SliderEditError> {
+    let is_arc = matches!(pieces.as_slice(), [Piece::Arc { .. }]);
+    let is_linear = linear
+        && pieces
+            .iter()
+            .all(|p| matches!(p, Piece::Bezier(p) if p.len() == 2));
+
+    let (curve_type, points) = if is_arc {
+        let arc = &pieces[0];
+        let middle = arc.split(arc.length() / 2.0).0.end();
+
+        (
+            CurveType::PerfectCircle,
+            vec![arc.start(), middle, arc.end()],
+        )
+    } else if is_linear {
+        let mut points = pieces
+            .first()
+            .map(Piece::start)
+            .into_iter()
+            .collect::<Vec<_>>();
+        points.extend(pieces.iter().map(Piece::end));
+
+        (CurveType::Linear, points)
+    } else {
+        // the first point of each curve repeats the end of the last one, which makes it a red anchor
+        let points = pieces
+            .into_iter()
+            .flat_map(Piece::into_beziers)
+            .flatten()
+            .collect();
+
+        (CurveType::Bezier, points)
+    };
+
+    if points.len() < 2 {
+        return Err(SliderEditError::ZeroLength);
+    }
+
+    let points = points
+        .into_iter()
+        .map(to_position)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((curve_type, points))
+}
+
+impl SlideParams {
+    /// Points of the slider path, which is the head followed by the `curve_points`.
+    fn points(&self, head: Position) -> Vec<Position> {
+        std::iter::once(head)
+            .chain(self.curve_points.iter().map(|p| p.0))
+            .collect()
+    }
+
+    /// Indexes into `curve_points` where a red anchor starts.
+    /// - Red anchors are written as the same point twice in a row, so the index is the first of the two.
+    /// - Only [`Bezier`][CurveType::Bezier] sliders use red anchors, other curve types treat the points as white anchors.
+    pub fn red_anchors(&self) -> Vec<usize> {
+        if self.curve_type != CurveType::Bezier {
+            return Vec::new();
+        }
+
+        self.curve_points
+            .windows(2)
+            .enumerate()
+            .filter(|(_, w)| w[0] == w[1])
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Segments of the slider path, which are drawn as separate curves.
+    /// - [`Bezier`][CurveType::Bezier] sliders are split at the red anchors.
+    /// - [`Linear`][CurveType::Linear] sliders have a segment between each pair of points.
+    /// - Other curve types are a single segment.
+    pub fn segments(&self, head: Position) -> Vec<SliderSegment> {
+        let points = self.points(head);
+        let last = points.len() - 1;
+
+        match self.curve_type {
+            CurveType::Linear => (0..last)
+                .map(|start| SliderSegment {
+                    start,
+                    end: start + 1,
+                })
+                .collect(),
+            CurveType::Bezier => {
+                let mut segments = Vec::new();
+                let mut start = 0;
+
+                for (i, w) in points.windows(2).enumerate() {
+                    if w[0] == w[1] {
+                        if i > start {
+                            segments.push(SliderSegment { start, end: i });
+                        }
+                        start = i + 1;
+                    }
+                }
+                if last > start {
+                    segments.push(SliderSegment { start, end: last });
+                }
+
+                segments
+            }
+            _ => vec![SliderSegment {
+                start: 0,
+                end: last,
+            }],
+        }
+    }
+
+    /// Inserts an anchor at `index` of `curve_points`, moving the anchors after it.
+    /// - `length` is kept, so the game cuts or extends the new path to the same length.
+    pub fn insert_anchor(
+        &mut self,
+        index: usize,
+        position: Position,
+    ) -> Result<(), SliderEditError> {
+        if index > self.curve_points.len() {
+            return Err(SliderEditError::AnchorOutOfRange);
+        }
+
+        self.curve_points.insert(index, CurvePoint(position));
+        Ok(())
+    }
+
+    /// Removes the anchor at `index` of `curve_points`, returning its position.
+    /// - Removing one of the points of a red anchor turns it into a white anchor.
+    /// - `length` is kept, so the game cuts or extends the new path to the same length.
+    pub fn remove_anchor(&mut self, index: usize) -> Result<Position, SliderEditError> {
+        if index >= self.curve_points.len() {
+            return Err(SliderEditError::AnchorOutOfRange);
+        }
+        if self.curve_points.len() == 1 {
+            return Err(SliderEditError::LastAnchor);
+        }
+
+        Ok(self.curve_points.remove(index).0)
+    }
+
+    /// Moves the anchor at `index` of `curve_points` to the position.
+    /// - Moving a red anchor moves both of its points, so it stays a red anchor.
+    /// - `length` is kept, so the game cuts or extends the new path to the same length.
+    pub fn move_anchor(&mut self, index: usize, position: Position) -> Result<(), SliderEditError> {
+        let old = self
+            .curve_points
+            .get(index)
+            .ok_or(SliderEditError::AnchorOutOfRange)?
+            .0;
+
+        let mut start = index;
+        while start > 0 && self.curve_points[start - 1].0 == old {
+            start -= 1;
+        }
+        let mut end = index;
+        while end + 1 < self.curve_points.len() && self.curve_points[end + 1].0 == old {
+            end += 1;
+        }
+
+        for point in &mut self.curve_points[start..=end] {
+            point.0 = position;
+        }
+
+        Ok(())
+    }
+
+    /// Pieces of the path the game draws, before it's cut or extended to the `length`.
+    fn pieces(&self, head: Position) -> Result<Vec<Piece>, SliderEditError> {
+        let points = self
+            .points(head)
+            .into_iter()
+            .map(Point::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| SliderEditError::InvalidPosition)?;
+
+        if self.curve_type == CurveType::Centripetal {
+            return Err(SliderEditError::UnsupportedCurve);
+        }
+
+        // perfect circles that aren't 3 points on a circle are drawn as Bézier curves
+        if let (CurveType::PerfectCircle, [a, b, c]) = (self.curve_type, points.as_slice()) {
+            if let Some(arc) = Piece::arc(*a, *b, *c) {
+                return Ok(vec![arc]);
+            }
+        }
+
+        let curve_type = match self.curve_type {
+            CurveType::Linear => CurveType::Linear,
+            _ => CurveType::Bezier,
+        };
+        let segments = SlideParams {
+            curve_type,
+            ..self.clone()
+        }
+        .segments(head);
+
+        Ok(segments
+            .into_iter()
+            .map(|s| Piece::Bezier(points[s.start..=s.end].to_vec()))
+            .collect())
+    }
+
+    fn length_f64(&self) -> Result<f64, SliderEditError> {
+        match f64::try_from(self.length) {
+            Ok(length) if length > 0.0 => Ok(length),
+            _ => Err(SliderEditError::ZeroLength),
+        }
+    }
+
+    /// Splits the slider starting at `head` into two sliders, `progress` of the way along its length.
+    /// - `progress` is between `0` and `1`, not including either.
+    /// - The second slider starts `progress` of the way through the duration of the slider, which needs the timing points to work out.
+    /// - The head samples stay on the first slider and the tail samples go to the second, with the new nodes using the default samples.
+    /// - Fails if the slider has repeats or no length.
+    pub fn split(&self, head: Position, progress: Decimal) -> Result<SliderSplit, SliderEditError> {
+        if progress <= Decimal::ZERO || progress >= Decimal::ONE {
+            return Err(SliderEditError::ProgressOutOfRange);
+        }
+        if self.slides > 1 {
+            return Err(SliderEditError::HasRepeats);
+        }
+
+        let first_length = self.length * progress;
+        let at = f64::try_from(first_length).map_err(|_| SliderEditError::InvalidPosition)?;
+
+        let pieces = fit_pieces(self.pieces(head)?, self.length_f64()?);
+        let (first_pieces, second_pieces) = split_pieces(pieces, at);
+
+        let linear = self.curve_type == CurveType::Linear;
+        let (first_type, mut first_points) = from_pieces(first_pieces, linear)?;
+        let (second_type, second_points) = from_pieces(second_pieces, linear)?;
+
+        // the head doesn't move, even if it isn't a whole osu!pixel
+        first_points[0] = head;
+
+        let samples = self.node_samples();
+        let has_samples = !self.edge_sounds.is_empty() || !self.edge_sets.is_empty();
+
+        let slider = |curve_type, points: Vec<Position>, length, samples: [NodeSample; 2]| {
+            let mut slider = SlideParams {
+                curve_type,
+                curve_points: points[1..].iter().copied().map(CurvePoint).collect(),
+                slides: 1,
+                length,
+                edge_sounds: Vec::new(),
+                edge_sets: Vec::new(),
+            };
+            if has_samples {
+                slider.set_node_samples(&samples);
+            }
+            slider
+        };
+
+        Ok(SliderSplit {
+            first: slider(
+                first_type,
+                first_points,
+                first_length,
+                [samples[0], NodeSample::default()],
+            ),
+            second_position: second_points[0],
+            second: slider(
+                second_type,
+                second_points,
+                self.length - first_length,
+                [NodeSample::default(), samples[samples.len() - 1]],
+            ),
+        })
+    }
+
+    /// Points along the path of the slider starting at `head`, `spacing` osu!pixels apart, from the head to the end of one slide.
+    /// - The path is cut or extended to the `length` first, like the game does.
+    /// - The end of the path is always included, even if it's closer than `spacing` to the point before it.
+    /// - Long sliders have their points spread further apart than `spacing`, so there are at most [`MAX_PATH_POINTS`] before the end.
+    /// - Fails if the slider has no length or `spacing` isn't positive.
+    pub fn path_points(
+        &self,
+        head: Position,
+        spacing: f64,
+    ) -> Result<Vec<Position<f64>>, SliderEditError> {
+        if spacing.is_nan() || spacing <= 0.0 {
+            return Err(SliderEditError::InvalidSpacing);
+        }
+
+        let pieces = fit_pieces(self.pieces(head)?, self.length_f64()?);
+        let total = pieces.iter().map(Piece::length).sum::<f64>();
+        // one less than the cap, so rounding can't add a point past it
+        let spacing = spacing.max(total / (MAX_PATH_POINTS - 1) as f64);
+        let mut points = Vec::new();
+        // distance from the start of the current piece to the next point
+        let mut next = 0.0;
+
+        for piece in &pieces {
+            let length = piece.length();
+
+            while next < length {
+                points.push(if next == 0.0 {
+                    piece.start()
+                } else {
+                    piece.split(next).0.end()
+                });
+                next += spacing;
+            }
+
+            next -= length;
+        }
+
+        if let Some(end) = pieces.last().map(Piece::end) {
+            points.push(end);
+        }
+
+        Ok(points)
+    }
+
+    /// Position where the slider starting at `head` ends, after all of its repeats.
+    /// - The path is cut or extended to the `length` first, like the game does.
+    /// - Sliders with an even number of slides end back at the head.
+    /// - Fails if the slider has no length.
+    pub fn end_position(&self, head: Position) -> Result<Position<f64>, SliderEditError> {
+        let pieces = fit_pieces(self.pieces(head)?, self.length_f64()?);
+
+        if self.slides % 2 == 0 {
+            return pieces
+                .first()
+                .map(Piece::start)
+                .ok_or(SliderEditError::ZeroLength);
+        }
+
+        pieces
+            .last()
+            .map(Piece::end)
+            .ok_or(SliderEditError::ZeroLength)
+    }
+
+    /// Reverses the slider starting at `head`, so it starts where it used to end, returning the new head position.
+    /// - The path is cut or extended to the `length` first, so the new head is where the slider used to end.
+    /// - The samples of the nodes are reversed with it.
+    /// - Fails if the slider has no length.
+    pub fn reverse(&mut self, head: Position) -> Result<Position, SliderEditError> {
+        let pieces = fit_pieces(self.pieces(head)?, self.length_f64()?);
+        let pieces = pieces.iter().rev().map(Piece::reversed).collect::<Vec<_>>();
+
+        let (curve_type, points) = from_pieces(pieces, self.curve_type == CurveType::Linear)?;
+
+        if !self.edge_sounds.is_empty() || !self.edge_sets.is_empty() {
+            let mut samples = self.node_samples();
+            samples.reverse();
+            self.set_node_samples(&samples);
+        }
+
+        self.curve_type = curve_type;
+        self.curve_points = points[1..].iter().copied().map(CurvePoint).collect();
+
+        Ok(points[0])
+    }
+}
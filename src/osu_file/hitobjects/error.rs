@@ -7,7 +7,7 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 #[error(transparent)]
-pub struct ParseError(#[from] ParseHitObjectError);
+pub struct ParseError(#[from] pub(crate) ParseHitObjectError);
 
 #[derive(Debug, Error)]
 #[error("Expected combo skip count to be 3 bits")]
@@ -24,7 +24,6 @@ pub enum ParseColonSetError {
     InvalidSet(#[from] ParseSampleSetError),
 }
 
-
 #[derive(Debug, Error, IntoStaticStr, EnumString)]
 #[non_exhaustive]
 pub enum ParseCurvePointError {
@@ -41,9 +40,15 @@ pub enum ParseCurvePointError {
 #[non_exhaustive]
 /// Error used when there was a problem parsing a `str` into a [`HitObject`][super::HitObject].
 pub enum ParseHitObjectError {
+    /// Missing `x` field.
+    #[error("Missing `x` field")]
+    MissingX,
     /// Invalid `x` value.
     #[error("Invalid `x` value")]
     InvalidX,
+    /// Missing `y` field.
+    #[error("Missing `y` field")]
+    MissingY,
     /// Invalid `y` value.
     #[error("Invalid `y` value")]
     InvalidY,
@@ -53,6 +58,15 @@ pub enum ParseHitObjectError {
     /// Invalid `time` value.
     #[error("Invalid `time` value")]
     InvalidTime,
+    /// Missing `obj_type` field.
+    #[error("Missing `obj_type` field")]
+    MissingObjType,
+    /// Missing `hitsound` field.
+    #[error("Missing `hitsound` field")]
+    MissingHitSound,
+    /// Missing `curve_type` field.
+    #[error("Missing `curve_type` field")]
+    MissingCurveType,
     /// Invalid `curve_type` value.
     #[error("Invalid `curve_type` value")]
     InvalidCurveType,
@@ -65,12 +79,21 @@ pub enum ParseHitObjectError {
     /// Invalid `edge_set` value.
     #[error("Invalid `edge_set` value")]
     InvalidEdgeSet(#[from] ParseColonSetError),
+    /// Missing `slides_count` field.
+    #[error("Missing `slides_count` field")]
+    MissingSlidesCount,
     /// Invalid `slides_count` value.
     #[error("Invalid `slides_count` value")]
     InvalidSlidesCount,
+    /// Missing `length` field.
+    #[error("Missing `length` field")]
+    MissingLength,
     /// Invalid `length` value.
     #[error("Invalid `length` value")]
     InvalidLength,
+    /// Missing `end_time` field.
+    #[error("Missing `end_time` field")]
+    MissingEndTime,
     /// Invalid `end_time` value.
     #[error("Invalid `end_time` value")]
     InvalidEndTime,
@@ -85,7 +108,32 @@ pub enum ParseHitObjectError {
     #[error(transparent)]
     InvalidHitSound(#[from] ParseHitSoundError),
     #[error(transparent)]
-    InvalidHitObjectTypeNumber(#[from] ParseHitObjectTypeNumberError)
+    InvalidHitObjectTypeNumber(#[from] ParseHitObjectTypeNumberError),
+}
+
+impl ParseHitObjectError {
+    /// Index of the comma separated field that caused the error, where `field_count` is the number of fields in the line.
+    /// - Missing fields don't have an index.
+    pub(crate) fn field_index(&self, field_count: usize) -> Option<usize> {
+        match self {
+            ParseHitObjectError::InvalidX => Some(0),
+            ParseHitObjectError::InvalidY => Some(1),
+            ParseHitObjectError::InvalidTime => Some(2),
+            ParseHitObjectError::UnknownObjType
+            | ParseHitObjectError::InvalidComboSkipCount(_)
+            | ParseHitObjectError::InvalidHitObjectTypeNumber(_) => Some(3),
+            ParseHitObjectError::InvalidHitSound(_) => Some(4),
+            ParseHitObjectError::InvalidCurveType
+            | ParseHitObjectError::InvalidCurvePoint(_)
+            | ParseHitObjectError::InvalidEndTime => Some(5),
+            ParseHitObjectError::InvalidSlidesCount => Some(6),
+            ParseHitObjectError::InvalidLength => Some(7),
+            ParseHitObjectError::InvalidEdgeSound => Some(8),
+            ParseHitObjectError::InvalidEdgeSet(_) => Some(9),
+            ParseHitObjectError::InvalidHitSample(_) => field_count.checked_sub(1),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Error, IntoStaticStr)]
@@ -97,7 +145,7 @@ pub enum ParseHitObjectTypeNumberError {
     #[error("There was a problem parsing the `str` into an integer first")]
     ParseValueError(#[from] ParseIntError),
     #[error(transparent)]
-    ComboSkipCountTooHigh(#[from] ComboSkipCountTooHigh)
+    ComboSkipCountTooHigh(#[from] ComboSkipCountTooHigh),
 }
 
 #[derive(Debug, Error, EnumString, IntoStaticStr)]
@@ -160,4 +208,52 @@ pub enum ParseCurveTypeError {
 pub enum ParseHitSoundError {
     #[error(transparent)]
     ParseIntError(#[from] ParseIntError),
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+/// Error used when editing the anchors of a [`slider`][super::SlideParams] failed.
+pub enum SliderEditError {
+    /// The anchor index is past the end of `curve_points`.
+    #[error("The anchor index is out of range")]
+    AnchorOutOfRange,
+    /// Removing the anchor would leave the slider without any `curve_points`.
+    #[error("The slider needs at least one anchor besides the head")]
+    LastAnchor,
+    /// The progress isn't between `0` and `1`, not including either.
+    #[error("The progress is expected to be between 0 and 1")]
+    ProgressOutOfRange,
+    /// The slider has repeats, so splitting it wouldn't keep the same path.
+    #[error("Sliders with repeats can't be split")]
+    HasRepeats,
+    /// The slider has no `length` or its path has no length, so there's nothing to split or reverse.
+    #[error("The slider has no length")]
+    ZeroLength,
+    /// Centripetal catmull-rom sliders can't be split or reversed.
+    #[error("Centripetal catmull-rom sliders can't be edited")]
+    UnsupportedCurve,
+    /// A position couldn't be converted for the calculations.
+    #[error("A position is out of range for the calculations")]
+    InvalidPosition,
+    /// The spacing between the points of the path isn't positive.
+    #[error("The spacing between points is expected to be positive")]
+    InvalidSpacing,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+/// Error used when [`HitObjects::try_insert`][super::HitObjects::try_insert] would make the hitobjects invalid.
+pub enum HitObjectInsertError {
+    /// The hitobject is before the last hitobject by more than the tolerance.
+    #[error("The hitobject at {time} is before the last hitobject at {previous}")]
+    OutOfOrder { time: u32, previous: u32 },
+    /// The slider has `edge_sounds`, but not one for each node.
+    #[error("Expected {expected} edge sounds, found {found}")]
+    EdgeSoundsLength { expected: usize, found: usize },
+    /// The slider has `edge_sets`, but not one for each node.
+    #[error("Expected {expected} edge sets, found {found}")]
+    EdgeSetsLength { expected: usize, found: usize },
+    /// The x position of the osu!mania hitobject isn't in any of the columns.
+    #[error("The column {column} is outside of the {key_count} keys")]
+    ColumnOutOfBounds { column: i64, key_count: u8 },
+}
@@ -0,0 +1,113 @@
+//! Module for editing the hitsounds of many [`HitObjects`][super::HitObjects] at once.
+
+use std::ops::Range;
+
+use rust_decimal::prelude::ToPrimitive;
+
+use super::{HitObjectParams, HitObjects, HitSound};
+use crate::osu_file::timingpoints::TimingPoints;
+
+/// How far in milliseconds an object can be from the beat snap to count as on it, since object times are rounded.
+const SNAP_LENIENCY_MS: f64 = 1.0;
+
+/// Number of beat snap steps from `start` to `time`, following the BPM changes in between.
+fn snap_steps(timing_points: &TimingPoints, start: u32, time: u32, beat_snap: u32) -> Option<f64> {
+    let uninherited = timing_points
+        .0
+        .iter()
+        .filter(|t| t.uninherited)
+        .filter_map(|t| Some((t.time as f64, t.beat_length.to_f64().filter(|b| *b > 0.0)?)));
+
+    let (start, time) = (start as f64, time as f64);
+    // the first timing point is used for the time before it
+    let mut beat_length = None;
+    let mut changes = Vec::new();
+
+    for (tp_time, tp_beat_length) in uninherited {
+        if tp_time <= start || beat_length.is_none() {
+            beat_length = Some(tp_beat_length);
+        }
+        if tp_time > start && tp_time <= time {
+            changes.push((tp_time, tp_beat_length));
+        }
+    }
+
+    let mut beat_length = beat_length?;
+    let mut position = start;
+    let mut steps = 0.0;
+
+    for (tp_time, tp_beat_length) in changes {
+        steps += (tp_time - position) / (beat_length / beat_snap as f64);
+        position = tp_time;
+        beat_length = tp_beat_length;
+    }
+
+    let step_length = beat_length / beat_snap as f64;
+    steps += (time - position) / step_length;
+
+    // off the snap if the closest step is too far away
+    if (steps.round() - steps).abs() * step_length > SNAP_LENIENCY_MS {
+        None
+    } else {
+        Some(steps.round())
+    }
+}
+
+impl HitObjects {
+    /// Applies a repeating hitsound `pattern` onto the objects on the beat snap, starting from `start`.
+    /// - `beat_snap` is the beat snap divisor, where `1` is every beat and `2` is every half beat.
+    /// - The pattern steps forward every beat snap, even if there's no object on it, so objects keep the hitsound of their position in the pattern.
+    /// - Objects before `start` or off the beat snap are skipped.
+    /// - The head edge sound of sliders is also set if the slider has edge sounds.
+    /// - Returns how many objects were changed.
+    pub fn apply_hitsound_pattern(
+        &mut self,
+        pattern: &[HitSound],
+        start: u32,
+        beat_snap: u32,
+        timing: &TimingPoints,
+    ) -> usize {
+        if pattern.is_empty() || beat_snap == 0 {
+            return 0;
+        }
+
+        let mut changed = 0;
+
+        for obj in self.0.iter_mut().filter(|obj| obj.time >= start) {
+            let steps = match snap_steps(timing, start, obj.time, beat_snap) {
+                Some(steps) => steps,
+                None => continue,
+            };
+            let hitsound = pattern[steps as usize % pattern.len()];
+
+            if let HitObjectParams::Slider(params) = &mut obj.obj_params {
+                if let Some(head) = params.edge_sounds.first_mut() {
+                    *head = hitsound;
+                }
+            }
+
+            obj.hitsound = hitsound;
+            changed += 1;
+        }
+
+        changed
+    }
+
+    /// Removes the hitsound additions of the objects with the `time` inside of `range`, including the slider edge sounds.
+    /// - Returns how many objects were changed.
+    pub fn clear_hitsounds(&mut self, range: Range<u32>) -> usize {
+        let mut changed = 0;
+
+        for obj in self.0.iter_mut().filter(|obj| range.contains(&obj.time)) {
+            obj.hitsound = HitSound::default();
+
+            if let HitObjectParams::Slider(params) = &mut obj.obj_params {
+                params.edge_sounds.fill(HitSound::default());
+            }
+
+            changed += 1;
+        }
+
+        changed
+    }
+}
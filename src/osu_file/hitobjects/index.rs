@@ -0,0 +1,291 @@
+//! Module for finding [`HitObjects`][super::HitObjects] near a point with a spatial index.
+
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use super::{HitObject, HitObjectParams};
+use crate::osu_file::{OsuFile, Position};
+
+/// Cell size used by [`OsuFile::spatial_index`], which is about the size of a hitcircle.
+pub const DEFAULT_CELL_SIZE: f64 = 64.0;
+
+/// Most cells a slider segment is added to, past which the object is checked by every query instead.
+const MAX_SEGMENT_CELLS: u128 = 4096;
+
+/// An object in the [`Grid`].
+#[derive(Clone, Debug, PartialEq)]
+struct Entry {
+    index: usize,
+    start: u32,
+    end: u32,
+    /// Points of the object, which is the control points of a slider joined as a path.
+    points: Vec<Position<f64>>,
+}
+
+impl Entry {
+    fn distance_to(&self, point: Position<f64>) -> f64 {
+        match self.points.as_slice() {
+            [single] => distance(*single, point),
+            points => points
+                .windows(2)
+                .map(|w| segment_distance(w[0], w[1], point))
+                .fold(f64::INFINITY, f64::min),
+        }
+    }
+}
+
+fn distance(a: Position<f64>, b: Position<f64>) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+fn segment_distance(a: Position<f64>, b: Position<f64>, point: Position<f64>) -> f64 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length_squared = dx * dx + dy * dy;
+
+    if length_squared == 0.0 {
+        return distance(a, point);
+    }
+
+    let t = (((point.x - a.x) * dx + (point.y - a.y) * dy) / length_squared).clamp(0.0, 1.0);
+
+    distance(Position::new(a.x + t * dx, a.y + t * dy), point)
+}
+
+/// Spatial index of hitobjects, for finding the objects within a radius of a point during a time window.
+/// - Sliders are indexed along the path through their control points, which follows the shape of linear sliders exactly and the other curves roughly.
+/// - Spinners aren't indexed, since they don't have a position on the playfield.
+/// - Sliders with a segment across thousands of cells, such as with a control point far outside of the playfield, are checked by every query instead of being added to the cells.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid {
+    cell_size: f64,
+    entries: Vec<Entry>,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+    /// Smallest and largest cell on each axis that has an object, or `None` if the index is empty.
+    bounds: Option<CellBox>,
+    /// Objects with a segment over more than [`MAX_SEGMENT_CELLS`], which aren't in the cells.
+    wide: Vec<usize>,
+}
+
+/// Cells from `min` to `max` on each axis, inclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct CellBox {
+    min: (i64, i64),
+    max: (i64, i64),
+}
+
+impl CellBox {
+    /// Number of cells in the box.
+    fn len(self) -> u128 {
+        let side = |min: i64, max: i64| (i128::from(max) - i128::from(min) + 1) as u128;
+
+        side(self.min.0, self.max.0).saturating_mul(side(self.min.1, self.max.1))
+    }
+
+    fn contains(self, (x, y): (i64, i64)) -> bool {
+        (self.min.0..=self.max.0).contains(&x) && (self.min.1..=self.max.1).contains(&y)
+    }
+
+    fn cells(self) -> impl Iterator<Item = (i64, i64)> {
+        let (min_y, max_y) = (self.min.1, self.max.1);
+
+        (self.min.0..=self.max.0).flat_map(move |x| (min_y..=max_y).map(move |y| (x, y)))
+    }
+
+    fn union(self, other: CellBox) -> CellBox {
+        CellBox {
+            min: (self.min.0.min(other.min.0), self.min.1.min(other.min.1)),
+            max: (self.max.0.max(other.max.0), self.max.1.max(other.max.1)),
+        }
+    }
+
+    /// Cells in both boxes, or `None` if they don't overlap.
+    fn intersection(self, other: CellBox) -> Option<CellBox> {
+        let cells = CellBox {
+            min: (self.min.0.max(other.min.0), self.min.1.max(other.min.1)),
+            max: (self.max.0.min(other.max.0), self.max.1.min(other.max.1)),
+        };
+
+        (cells.min.0 <= cells.max.0 && cells.min.1 <= cells.max.1).then_some(cells)
+    }
+}
+
+impl Grid {
+    /// Builds the index from the hitobjects, where the cells are `cell_size` osu!pixels wide.
+    /// - Sliders only last for their start time, since the duration needs the timing points; use [`OsuFile::spatial_index`] to include it.
+    /// - Returns an empty index if `cell_size` isn't positive.
+    pub fn new(hitobjects: &[HitObject], cell_size: f64) -> Self {
+        Self::with_end_times(hitobjects, cell_size, |obj, _| match obj.obj_params {
+            HitObjectParams::OsuManiaHold { end_time } => end_time,
+            _ => obj.time,
+        })
+    }
+
+    fn with_end_times<F>(hitobjects: &[HitObject], cell_size: f64, end_time: F) -> Self
+    where
+        F: Fn(&HitObject, usize) -> u32,
+    {
+        let mut grid = Grid {
+            cell_size,
+            entries: Vec::new(),
+            cells: HashMap::new(),
+            bounds: None,
+            wide: Vec::new(),
+        };
+
+        if !(cell_size > 0.0 && cell_size.is_finite()) {
+            return grid;
+        }
+
+        let position = |position: Position| Position::<f64>::try_from(position).ok();
+
+        for (index, obj) in hitobjects.iter().enumerate() {
+            let points = match &obj.obj_params {
+                HitObjectParams::Spinner { .. } => continue,
+                HitObjectParams::Slider(params) => {
+                    let curve_points = params.curve_points.iter().map(|p| p.0);
+
+                    std::iter::once(obj.position)
+                        .chain(curve_points)
+                        .filter_map(position)
+                        .collect()
+                }
+                _ => position(obj.position).into_iter().collect::<Vec<_>>(),
+            };
+
+            if points.is_empty() {
+                continue;
+            }
+
+            grid.insert(Entry {
+                index,
+                start: obj.time,
+                end: end_time(obj, index).max(obj.time),
+                points,
+            });
+        }
+
+        grid
+    }
+
+    fn cell(&self, value: f64) -> i64 {
+        (value / self.cell_size).floor() as i64
+    }
+
+    /// Cells overlapping the box around the points, grown by `margin`.
+    fn cells_around(&self, points: &[Position<f64>], margin: f64) -> CellBox {
+        let min_x = points.iter().map(|p| p.x).fold(f64::INFINITY, f64::min) - margin;
+        let max_x = points.iter().map(|p| p.x).fold(f64::NEG_INFINITY, f64::max) + margin;
+        let min_y = points.iter().map(|p| p.y).fold(f64::INFINITY, f64::min) - margin;
+        let max_y = points.iter().map(|p| p.y).fold(f64::NEG_INFINITY, f64::max) + margin;
+
+        CellBox {
+            min: (self.cell(min_x), self.cell(min_y)),
+            max: (self.cell(max_x), self.cell(max_y)),
+        }
+    }
+
+    fn insert(&mut self, entry: Entry) {
+        let entry_index = self.entries.len();
+
+        // each segment only covers the cells around itself, so long sliders don't fill their whole bounding box
+        let segments = if entry.points.len() == 1 {
+            vec![vec![entry.points[0]]]
+        } else {
+            entry.points.windows(2).map(|w| w.to_vec()).collect()
+        };
+
+        let cells = segments
+            .iter()
+            .map(|segment| self.cells_around(segment, 0.0))
+            .collect::<Vec<_>>();
+        if cells.iter().any(|cells| cells.len() > MAX_SEGMENT_CELLS) {
+            self.wide.push(entry_index);
+            self.entries.push(entry);
+            return;
+        }
+
+        for cells in cells {
+            self.bounds = Some(match self.bounds {
+                Some(bounds) => bounds.union(cells),
+                None => cells,
+            });
+
+            for cell in cells.cells() {
+                let cell = self.cells.entry(cell).or_default();
+
+                if cell.last() != Some(&entry_index) {
+                    cell.push(entry_index);
+                }
+            }
+        }
+
+        self.entries.push(entry);
+    }
+
+    /// Indexes of the objects within `radius` osu!pixels of `point` that are active at some point during `time`, in ascending order.
+    /// - An object is active from its start time to its end time.
+    /// - Only the cells that have objects are searched, so a large `radius` doesn't search past them.
+    pub fn query(&self, point: Position, radius: f64, time: RangeInclusive<u32>) -> Vec<usize> {
+        let point = match Position::<f64>::try_from(point) {
+            Ok(point) => point,
+            Err(_) => return Vec::new(),
+        };
+        let cells = self
+            .bounds
+            .and_then(|bounds| bounds.intersection(self.cells_around(&[point], radius.max(0.0))));
+
+        let candidates: Vec<usize> = match cells {
+            // a box with more cells than the index has is searched through the cells of the index instead
+            Some(cells) if cells.len() > self.cells.len() as u128 => self
+                .cells
+                .iter()
+                .filter(|(cell, _)| cells.contains(**cell))
+                .flat_map(|(_, entries)| entries.iter().copied())
+                .collect(),
+            Some(cells) => cells
+                .cells()
+                .filter_map(|cell| self.cells.get(&cell))
+                .flatten()
+                .copied()
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let mut found = candidates
+            .iter()
+            .chain(&self.wide)
+            .map(|entry| &self.entries[*entry])
+            .filter(|entry| entry.start <= *time.end() && entry.end >= *time.start())
+            .filter(|entry| entry.distance_to(point) <= radius)
+            .map(|entry| entry.index)
+            .collect::<Vec<_>>();
+
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+
+    /// Number of objects in the index.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no objects in the index.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl OsuFile {
+    /// Builds a [`Grid`] from the hitobjects with the [`DEFAULT_CELL_SIZE`], including the duration of sliders.
+    /// - Returns `None` if there's no `[HitObjects]` section.
+    pub fn spatial_index(&self) -> Option<Grid> {
+        let hitobjects = &self.hitobjects.as_ref()?.0;
+        let timed_objects = self.timed_objects();
+
+        Some(Grid::with_end_times(
+            hitobjects,
+            DEFAULT_CELL_SIZE,
+            |_, index| timed_objects[index].end_time() as u32,
+        ))
+    }
+}
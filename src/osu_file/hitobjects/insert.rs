@@ -0,0 +1,59 @@
+//! Module for adding hitobjects while keeping the section valid.
+
+use super::{HitObject, HitObjectInsertError, HitObjectParams, HitObjects};
+use crate::osu_file::InsertOptions;
+
+impl HitObject {
+    /// Checks the hitobject on its own, which is the edge sounds and edge sets of sliders and the column in osu!mania.
+    fn check_insert(&self, options: &InsertOptions) -> Result<(), HitObjectInsertError> {
+        if let HitObjectParams::Slider(params) = &self.obj_params {
+            let expected = params.node_count();
+
+            // both can be left out, but if they are written each node needs one
+            let found = params.edge_sounds.len();
+            if found != 0 && found != expected {
+                return Err(HitObjectInsertError::EdgeSoundsLength { expected, found });
+            }
+            let found = params.edge_sets.len();
+            if found != 0 && found != expected {
+                return Err(HitObjectInsertError::EdgeSetsLength { expected, found });
+            }
+        }
+
+        if let Some(key_count) = options.key_count {
+            let column = self.unclamped_mania_column(key_count);
+
+            if column < 0 || column >= i64::from(key_count) {
+                return Err(HitObjectInsertError::ColumnOutOfBounds { column, key_count });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl HitObjects {
+    /// Adds the hitobject to the end after checking it, returning its index.
+    /// - The time can't be before the latest hitobject by more than [`InsertOptions::time_tolerance`], so hitobjects already out of order within the tolerance don't let later ones move further back.
+    /// - Sliders need either no edge sounds and edge sets, or one for each node.
+    /// - With [`InsertOptions::key_count`], the hitobject has to be in one of the columns, see [`InsertOptions::for_file`].
+    pub fn try_insert(
+        &mut self,
+        obj: HitObject,
+        options: &InsertOptions,
+    ) -> Result<usize, HitObjectInsertError> {
+        if let Some(previous) = self.0.iter().map(|obj| obj.time).max() {
+            if obj.time.saturating_add(options.time_tolerance) < previous {
+                return Err(HitObjectInsertError::OutOfOrder {
+                    time: obj.time,
+                    previous,
+                });
+            }
+        }
+
+        obj.check_insert(options)?;
+
+        self.0.push(obj);
+        Ok(self.0.len() - 1)
+    }
+}
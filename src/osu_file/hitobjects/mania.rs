@@ -0,0 +1,48 @@
+//! Module for finding the osu!mania columns of hitobjects.
+
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use rust_decimal_macros::dec;
+
+use super::{HitObject, HitObjects};
+
+impl HitObject {
+    /// Column of the hitobject from its x position, which can be outside of the columns.
+    pub(super) fn unclamped_mania_column(&self, key_count: u8) -> i64 {
+        let column = self
+            .position
+            .x
+            .checked_mul(Decimal::from(key_count))
+            .and_then(|x| (x / dec!(512)).floor().to_i64());
+
+        match column {
+            Some(column) => column,
+            // too far out to be a column, so it's past the side the position is on
+            None if self.position.x.is_sign_negative() => i64::MIN,
+            None => i64::MAX,
+        }
+    }
+
+    /// Column of the hitobject in an osu!mania beatmap with `key_count` keys, starting from `0` on the left.
+    /// - The column is `floor(x * key_count / 512)`, and positions outside of the playfield are clamped to the first or last column like the game does.
+    /// - A `key_count` of `0` gives column `0`.
+    pub fn mania_column(&self, key_count: u8) -> usize {
+        let last = i64::from(key_count.saturating_sub(1));
+        self.unclamped_mania_column(key_count).clamp(0, last) as usize
+    }
+}
+
+impl HitObjects {
+    /// Groups the hitobjects by their [`mania_column`][HitObject::mania_column], keeping the order they are in.
+    /// - Returns `key_count` columns, some of which can be empty.
+    pub fn to_mania_columns(&self, key_count: u8) -> Vec<Vec<&HitObject>> {
+        let mut columns = vec![Vec::new(); usize::from(key_count)];
+
+        for obj in &self.0 {
+            if let Some(column) = columns.get_mut(obj.mania_column(key_count)) {
+                column.push(obj);
+            }
+        }
+
+        columns
+    }
+}
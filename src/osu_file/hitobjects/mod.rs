@@ -1,4 +1,11 @@
+pub mod anchors;
 pub mod error;
+mod hitsounds;
+pub mod index;
+mod insert;
+mod mania;
+pub mod query;
+pub mod sorted;
 pub mod types;
 
 use std::str::FromStr;
@@ -9,36 +16,89 @@ use rust_decimal_macros::dec;
 use crate::helper::*;
 use crate::OsuFile;
 
+pub use anchors::*;
 pub use error::*;
+pub use index::*;
+pub use query::*;
+pub use sorted::*;
 pub use types::*;
 
 use super::Error;
+use super::Extensions;
 use super::Integer;
+use super::LineComments;
+use super::ParseWarning;
 use super::Position;
 use super::Version;
 use super::VersionedDefault;
 use super::VersionedFromStr;
 use super::VersionedToString;
 use super::VersionedTryFrom;
+use super::LATEST_VERSION;
 
 #[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HitObjects(pub Vec<HitObject>);
 
 impl VersionedFromStr for HitObjects {
     type Err = Error<ParseError>;
 
     fn from_str(s: &str, version: Version) -> std::result::Result<Option<Self>, Self::Err> {
+        Self::from_str_skipping_malformed(s, version, 0, None, &mut Vec::new())
+    }
+}
+
+impl HitObjects {
+    /// [`from_str`][VersionedFromStr::from_str] that skips the lines that fail to parse if there are `warnings`, adding a warning for each of them.
+    /// - `line_number` is the line index where the section starts.
+    /// - The comments of a section without hitobjects are put in `section_comments`, since they aren't around a hitobject.
+    pub(crate) fn from_str_skipping_malformed(
+        s: &str,
+        version: Version,
+        line_number: usize,
+        mut warnings: Option<&mut Vec<ParseWarning>>,
+        section_comments: &mut Vec<String>,
+    ) -> std::result::Result<Option<Self>, Error<ParseError>> {
         let mut hitobjects = Vec::new();
+        let mut comments = Vec::new();
 
         for (line_index, s) in s.lines().enumerate() {
             if s.trim().is_empty() {
                 continue;
             }
 
-            hitobjects.push(Error::new_from_result_into(
-                HitObject::from_str(s, version).map(|v| v.unwrap()),
-                line_index,
-            )?);
+            if let Some(comment) = s.trim_start().strip_prefix("//") {
+                comments.push(comment.to_string());
+                continue;
+            }
+
+            let hitobject = match HitObject::from_str(s, version) {
+                Ok(hitobject) => hitobject,
+                Err(err) => {
+                    let field = err.field_index(s.split(',').count());
+                    let err: Error<ParseError> = Error::new_into(err, line_index);
+
+                    if let Some(warnings) = warnings.as_deref_mut() {
+                        warnings.push(ParseWarning::malformed_line(line_number, s, &err));
+                        continue;
+                    }
+
+                    return Err(match field {
+                        Some(field) => err.with_field_column(s, field),
+                        None => err,
+                    });
+                }
+            };
+
+            if let Some(mut hitobject) = hitobject {
+                hitobject.comments.before = std::mem::take(&mut comments);
+                hitobjects.push(hitobject);
+            }
+        }
+
+        match hitobjects.last_mut() {
+            Some(last) => last.comments.after = comments,
+            None => *section_comments = comments,
         }
 
         Ok(Some(HitObjects(hitobjects)))
@@ -50,7 +110,7 @@ impl VersionedToString for HitObjects {
         Some(
             self.0
                 .iter()
-                .filter_map(|o| o.to_string(version))
+                .filter_map(|o| o.to_string(version).map(|line| o.comments.wrap(line)))
                 .collect::<Vec<_>>()
                 .join("\n"),
         )
@@ -63,6 +123,22 @@ impl VersionedDefault for HitObjects {
     }
 }
 
+impl HitObjects {
+    /// Compares the hitobjects while ignoring differences in how they are written.
+    /// - A missing hitsample and a `0:0:0:0:` hitsample.
+    /// - Missing slider edge sounds and edge sets and the default ones.
+    /// - The combo skip count of objects that aren't a new combo.
+    /// - `//` comments around the hitobjects.
+    pub fn semantically_eq(&self, other: &HitObjects) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(&other.0)
+                .all(|(a, b)| a.normalized() == b.normalized())
+    }
+}
+
 /// A struct that represents a hitobject.
 ///
 /// All hitobjects will have the properties: `x`, `y`, `time`, `type`, `hitsound`, `hitsample`.
@@ -70,6 +146,7 @@ impl VersionedDefault for HitObjects {
 /// The `type` property is a `u8` integer with each bit flags containing some information, which are split into the functions and enums:
 /// [hitobject_type][Self::obj_params], [new_combo][Self::new_combo], [combo_skip_count][Self::combo_skip_count]
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub struct HitObject {
     /// The position of the hitobject.
@@ -88,9 +165,39 @@ pub struct HitObject {
     pub hitsound: HitSound,
     /// The [hitsample][HitSample] property of the hitobject.
     pub hitsample: Option<HitSample>,
+    /// Data attached by the user, which isn't written to the file.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extensions: Extensions,
+    /// `//` comment lines written around the hitobject in the `[HitObjects]` section.
+    pub comments: LineComments,
 }
 
 impl HitObject {
+    /// Returns the hitobject written in a single way, used by [`HitObjects::semantically_eq`].
+    fn normalized(&self) -> HitObject {
+        let mut obj = self.clone();
+        obj.comments = LineComments::default();
+
+        let mut hitsample = obj
+            .hitsample
+            .unwrap_or_else(|| HitSample::default(LATEST_VERSION).unwrap());
+        if hitsample.filename.as_deref() == Some("") {
+            hitsample.filename = None;
+        }
+        obj.hitsample = Some(hitsample);
+
+        if !obj.new_combo {
+            obj.combo_skip_count = ComboSkipCount::default();
+        }
+
+        if let HitObjectParams::Slider(params) = &mut obj.obj_params {
+            let samples = params.node_samples();
+            params.set_node_samples(&samples);
+        }
+
+        obj
+    }
+
     fn type_to_string(&self) -> String {
         let mut bit_flag: u8 = 0;
 
@@ -111,6 +218,23 @@ impl HitObject {
         bit_flag.to_string()
     }
 
+    /// Time of the hitobject as [`Millis`][crate::osu_file::Millis].
+    /// - Times that don't fit in an [`Integer`] are saturated.
+    pub fn millis(&self) -> crate::osu_file::Millis {
+        crate::osu_file::Millis(Integer::try_from(self.time).unwrap_or(Integer::MAX))
+    }
+
+    /// Position where the hitobject ends, which is where the slider ball is after the last repeat for sliders.
+    /// - Other hitobjects end at their [`position`][Self::position].
+    /// - See [`SlideParams::end_position`] for when it fails.
+    pub fn end_position(&self) -> Result<Position<f64>, SliderEditError> {
+        match &self.obj_params {
+            HitObjectParams::Slider(slider) => slider.end_position(self.position),
+            _ => Position::<f64>::try_from(self.position)
+                .map_err(|_| SliderEditError::InvalidPosition),
+        }
+    }
+
     pub fn hitcircle_default() -> Self {
         Self {
             position: Default::default(),
@@ -120,6 +244,8 @@ impl HitObject {
             combo_skip_count: Default::default(),
             hitsound: Default::default(),
             hitsample: Default::default(),
+            extensions: Extensions::new(),
+            comments: LineComments::default(),
         }
     }
 
@@ -134,6 +260,8 @@ impl HitObject {
             combo_skip_count: Default::default(),
             hitsound: Default::default(),
             hitsample: Default::default(),
+            extensions: Extensions::new(),
+            comments: LineComments::default(),
         }
     }
 
@@ -151,6 +279,8 @@ impl HitObject {
             combo_skip_count: Default::default(),
             hitsound: Default::default(),
             hitsample: Default::default(),
+            extensions: Extensions::new(),
+            comments: LineComments::default(),
         }
     }
 }
@@ -159,104 +289,166 @@ impl VersionedFromStr for HitObject {
     type Err = ParseHitObjectError;
 
     fn from_str(s: &str, version: Version) -> std::result::Result<Option<Self>, Self::Err> {
-        let split: Vec<&str> = s.split(',').collect();
+        let mut split = s.split(',');
 
         let position = Position {
-            x: split[0]
+            x: split
+                .next()
+                .ok_or(ParseHitObjectError::MissingX)?
                 .parse::<Decimal>()
                 .map_err(|_| ParseHitObjectError::InvalidX)?,
-            y: split[1]
+            y: split
+                .next()
+                .ok_or(ParseHitObjectError::MissingY)?
                 .parse::<Decimal>()
                 .map_err(|_| ParseHitObjectError::InvalidY)?,
         };
 
-        let time = split[2]
+        let time = split
+            .next()
+            .ok_or(ParseHitObjectError::MissingTime)?
             .parse::<u32>()
             .map(|t| add_old_version_time_offset(t, version))
             .map_err(|_| ParseHitObjectError::InvalidTime)?;
 
-        let obj_type_number = split[3].parse::<HitObjectTypeNumber>()?;
-        let hitsound = HitSound::from_str(split[4], version)?.unwrap();
+        let obj_type_number = split
+            .next()
+            .ok_or(ParseHitObjectError::MissingObjType)?
+            .parse::<HitObjectTypeNumber>()
+            .map_err(|err| match err {
+                ParseHitObjectTypeNumberError::InvalidObjType => {
+                    ParseHitObjectError::UnknownObjType
+                }
+                err => err.into(),
+            })?;
+        let hitsound = HitSound::from_str(
+            split.next().ok_or(ParseHitObjectError::MissingHitSound)?,
+            version,
+        )?
+        .unwrap_or_default();
+
+        // the hitsample is always the last field and is optional
+        let hitsample = |s: Option<&str>| -> Result<Option<HitSample>, ParseHitObjectError> {
+            match s {
+                Some(s) if !s.is_empty() => Ok(HitSample::from_str(s, version)?),
+                _ => Ok(None),
+            }
+        };
 
-        match obj_type_number.obj_type {
+        let (obj_params, hitsample) = match obj_type_number.obj_type {
             // hitcircle syntax:
             // x,y,time,type,hitsound(,[[hitsample|0:0:0:0:]|''])
-            HitObjectType::HitCircle => Ok(Some(Self {
-                position,
-                time,
-                obj_params: HitObjectParams::HitCircle,
-                new_combo: obj_type_number.new_combo,
-                combo_skip_count: obj_type_number.combo_skip_count,
-                hitsound,
-                hitsample: if split.len() == 6 {
-                    Some(HitSample::from_str(split[5], version)?.unwrap())
-                } else {
-                    None
-                }
-            })),
+            HitObjectType::HitCircle => (HitObjectParams::HitCircle, hitsample(split.next())?),
             // slider syntax:
-            // x,y,time,type,hitSound,curveType|curvePoints,slides,length,edgeSounds,edgeSets,hitSample
-            // 0 1 2    3    4        5                     6      7      8          9        10
+            // x,y,time,type,hitSound,curveType|curvePoints,slides,length(,edgeSounds,edgeSets,hitSample)
             HitObjectType::Slider => {
-                if split.len() != 11 {
-                    return Err(ParseHitObjectError::InvalidLength)
-                }
-
-                let mut subsplit = split[5].split('|');
-                let curve_type = subsplit
+                let mut curve = split
                     .next()
-                    .ok_or_else(|| ParseHitObjectError::InvalidCurveType)?;
-                let curve_type = CurveType
-                    ::from_str(curve_type, version)
+                    .ok_or(ParseHitObjectError::MissingCurveType)?
+                    .split('|');
+                let curve_type = curve.next().ok_or(ParseHitObjectError::MissingCurveType)?;
+                let curve_type = CurveType::from_str(curve_type, version)
                     .map_err(|_| ParseHitObjectError::InvalidCurveType)?
-                    .unwrap();
+                    .ok_or(ParseHitObjectError::InvalidCurveType)?;
+                let curve_points = curve
+                    .map(|p| CurvePoint::from_str(p, version))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect();
+
+                let slides = split
+                    .next()
+                    .ok_or(ParseHitObjectError::MissingSlidesCount)?
+                    .parse::<Integer>()
+                    .map_err(|_| ParseHitObjectError::InvalidSlidesCount)?;
+                let length = split
+                    .next()
+                    .ok_or(ParseHitObjectError::MissingLength)?
+                    .parse::<Decimal>()
+                    .map_err(|_| ParseHitObjectError::InvalidLength)?;
 
-                let params = SlideParams {
-                    curve_type,
-                    curve_points: subsplit
-                        .map(|p| CurvePoint::from_str(p, version))
-                        .collect::<Result<Vec<Option<CurvePoint>>, ParseCurvePointError>>()?
-                        .iter()
-                        .map(|p| p.unwrap())
-                        .collect::<Vec<CurvePoint>>(),
-                    slides: split[6]
-                        .parse::<Integer>()
-                        .map_err(|_| ParseHitObjectError::InvalidSlidesCount)?,
-                    length: split[7]
-                        .parse::<Decimal>()
-                        .map_err(|_| ParseHitObjectError::InvalidLength)?,
-                    edge_sounds: split[8]
+                let edge_sounds = match split.next() {
+                    Some(s) if !s.is_empty() => s
                         .split('|')
                         .map(|s| HitSound::from_str(s, version))
-                        .collect::<Result<Vec<Option<HitSound>>, ParseHitSoundError>>()?
-                        .iter()
-                        .map(|s| s.unwrap())
-                        .collect::<Vec<HitSound>>(),
-                    edge_sets: split[9]
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|_| ParseHitObjectError::InvalidEdgeSound)?
+                        .into_iter()
+                        .flatten()
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                let edge_sets = match split.next() {
+                    Some(s) if !s.is_empty() => s
                         .split('|')
                         .map(|s| EdgeSet::from_str(s, version))
-                        .collect::<Result<Vec<Option<EdgeSet>>, ParseColonSetError>>()?
-                        .iter()
-                        .map(|s| s.unwrap())
-                        .collect::<Vec<EdgeSet>>()
+                        .collect::<Result<Vec<_>, _>>()?
+                        .into_iter()
+                        .flatten()
+                        .collect(),
+                    _ => Vec::new(),
                 };
-                Ok(Some(Self {
-                    position,
-                    time,
-                    obj_params: HitObjectParams::Slider(params),
-                    new_combo: obj_type_number.new_combo,
-                    combo_skip_count: obj_type_number.combo_skip_count,
-                    hitsound,
-                    hitsample: Some(HitSample::from_str(split[10], version)?.unwrap())
-                }))
-            },
-            HitObjectType::Spinner => { },
-            HitObjectType::OsuManiaHold => { }
-        }
+
+                let params = SlideParams {
+                    curve_type,
+                    curve_points,
+                    slides,
+                    length,
+                    edge_sounds,
+                    edge_sets,
+                };
+
+                (HitObjectParams::Slider(params), hitsample(split.next())?)
+            }
+            // spinner syntax:
+            // x,y,time,type,hitSound,endTime(,hitSample)
+            HitObjectType::Spinner => {
+                let end_time = split
+                    .next()
+                    .ok_or(ParseHitObjectError::MissingEndTime)?
+                    .parse::<u32>()
+                    .map(|t| add_old_version_time_offset(t, version))
+                    .map_err(|_| ParseHitObjectError::InvalidEndTime)?;
+
+                (
+                    HitObjectParams::Spinner { end_time },
+                    hitsample(split.next())?,
+                )
+            }
+            // osu!mania hold syntax:
+            // x,y,time,type,hitSound,endTime(:hitSample)
+            HitObjectType::OsuManiaHold => {
+                let field = split.next().ok_or(ParseHitObjectError::MissingEndTime)?;
+                let (end_time, rest) = match field.split_once(':') {
+                    Some((end_time, rest)) => (end_time, Some(rest)),
+                    None => (field, None),
+                };
+                let end_time = end_time
+                    .parse::<u32>()
+                    .map(|t| add_old_version_time_offset(t, version))
+                    .map_err(|_| ParseHitObjectError::InvalidEndTime)?;
+
+                (HitObjectParams::OsuManiaHold { end_time }, hitsample(rest)?)
+            }
+        };
+
+        Ok(Some(Self {
+            position,
+            time,
+            obj_params,
+            new_combo: obj_type_number.new_combo,
+            combo_skip_count: obj_type_number.combo_skip_count,
+            hitsound,
+            hitsample,
+            extensions: Extensions::new(),
+            comments: LineComments::default(),
+        }))
     }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum HitObjectParams {
     HitCircle,
@@ -269,14 +461,14 @@ pub enum HitObjectType {
     HitCircle,
     Slider,
     Spinner,
-    OsuManiaHold
+    OsuManiaHold,
 }
 
 pub struct HitObjectTypeNumber {
     number: u8,
     new_combo: bool,
     combo_skip_count: ComboSkipCount,
-    obj_type: HitObjectType
+    obj_type: HitObjectType,
 }
 
 impl FromStr for HitObjectTypeNumber {
@@ -284,7 +476,7 @@ impl FromStr for HitObjectTypeNumber {
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         let number = value.parse::<u8>()?;
-        
+
         let hitcircle = number >> 0 & 1;
         let slider = number >> 1 & 1;
         let new_combo = (number >> 2 & 1) != 0;
@@ -301,18 +493,21 @@ impl FromStr for HitObjectTypeNumber {
             number,
             new_combo,
             combo_skip_count,
-            obj_type: match true {
-                hitcircle => HitObjectType::HitCircle,
-                slider => HitObjectType::Slider,
-                spinner => HitObjectType::Spinner,
-                mania_hold_note => HitObjectType::OsuManiaHold,
-            }
+            obj_type: if hitcircle == 1 {
+                HitObjectType::HitCircle
+            } else if slider == 1 {
+                HitObjectType::Slider
+            } else if spinner == 1 {
+                HitObjectType::Spinner
+            } else {
+                HitObjectType::OsuManiaHold
+            },
         })
     }
 }
 
-
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SlideParams {
     pub curve_type: CurveType,
     pub curve_points: Vec<CurvePoint>,
@@ -320,4 +515,47 @@ pub struct SlideParams {
     pub length: Decimal,
     pub edge_sounds: Vec<HitSound>,
     pub edge_sets: Vec<EdgeSet>,
-}
\ No newline at end of file
+}
+
+impl SlideParams {
+    /// Number of nodes the slider has, which is the head, each repeat, and the tail.
+    pub fn node_count(&self) -> usize {
+        self.slides.max(0) as usize + 1
+    }
+
+    /// Returns the samples for each node of the slider, from the head to the tail.
+    /// - Zips `edge_sounds` and `edge_sets` together.
+    /// - Nodes missing from `edge_sounds` or `edge_sets` use the default values.
+    pub fn node_samples(&self) -> Vec<NodeSample> {
+        (0..self.node_count())
+            .map(|i| NodeSample {
+                hitsound: self.edge_sounds.get(i).copied().unwrap_or_default(),
+                edge_set: self.edge_sets.get(i).copied().unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// Sets `edge_sounds` and `edge_sets` from the samples of each node.
+    /// - The samples are written as-is, so it should contain [`node_count`][Self::node_count] samples to stay valid.
+    pub fn set_node_samples(&mut self, samples: &[NodeSample]) {
+        self.edge_sounds = samples.iter().map(|s| s.hitsound).collect();
+        self.edge_sets = samples.iter().map(|s| s.edge_set).collect();
+    }
+
+    /// Scales `length` so the slider keeps its duration when the slider velocity changes from `old_sv` to `new_sv`.
+    /// - Any value proportional to the velocity can be used, such as the SV multiplier or `SliderMultiplier` times the SV multiplier.
+    /// - Does nothing if either velocity isn't positive, or if the new length would overflow.
+    pub fn rescale_length_for_new_sv(&mut self, old_sv: Decimal, new_sv: Decimal) {
+        if old_sv <= Decimal::ZERO || new_sv <= Decimal::ZERO {
+            return;
+        }
+
+        if let Some(length) = self
+            .length
+            .checked_mul(new_sv)
+            .and_then(|length| length.checked_div(old_sv))
+        {
+            self.length = length.normalize();
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Module for querying [`HitObjects`][super::HitObjects] with filters.
+
+use std::ops::Range;
+
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+
+use super::{HitObject, HitObjectParams, HitObjects};
+use crate::osu_file::timingpoints::{slider_velocity_at, TimingPoints};
+
+/// A filter used in a [`Query`].
+/// - Filters can be combined with [`Filter::and`], [`Filter::or`] and [`Filter::not`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Filter {
+    /// Matches objects with the `time` inside of the range.
+    TimeRange(Range<u32>),
+    /// Matches hitcircles.
+    IsHitCircle,
+    /// Matches sliders.
+    IsSlider,
+    /// Matches spinners.
+    IsSpinner,
+    /// Matches osu!mania hold notes.
+    IsOsuManiaHold,
+    /// Matches objects with the `new_combo` flag set to the value.
+    NewCombo(bool),
+    /// Matches objects that are at least this many milliseconds after the previous object.
+    /// - The first object always matches.
+    MinGapToPrev(u32),
+    /// Matches if both filters match.
+    And(Box<Filter>, Box<Filter>),
+    /// Matches if either of the filters match.
+    Or(Box<Filter>, Box<Filter>),
+    /// Matches if the filter doesn't match.
+    Not(Box<Filter>),
+}
+
+impl Filter {
+    /// Combines the filter with another one, matching if both match.
+    pub fn and(self, other: Filter) -> Self {
+        Filter::And(Box::new(self), Box::new(other))
+    }
+
+    /// Combines the filter with another one, matching if either match.
+    pub fn or(self, other: Filter) -> Self {
+        Filter::Or(Box::new(self), Box::new(other))
+    }
+
+    /// Inverts the filter.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        Filter::Not(Box::new(self))
+    }
+
+    /// Checks if the object at `index` matches the filter.
+    /// - An `index` past the end of `hitobjects` doesn't match any filter, including [`not`][Self::not] filters.
+    pub fn matches(&self, hitobjects: &[HitObject], index: usize) -> bool {
+        let obj = match hitobjects.get(index) {
+            Some(obj) => obj,
+            None => return false,
+        };
+
+        match self {
+            Filter::TimeRange(range) => range.contains(&obj.time),
+            Filter::IsHitCircle => matches!(obj.obj_params, HitObjectParams::HitCircle),
+            Filter::IsSlider => matches!(obj.obj_params, HitObjectParams::Slider(_)),
+            Filter::IsSpinner => matches!(obj.obj_params, HitObjectParams::Spinner { .. }),
+            Filter::IsOsuManiaHold => {
+                matches!(obj.obj_params, HitObjectParams::OsuManiaHold { .. })
+            }
+            Filter::NewCombo(new_combo) => obj.new_combo == *new_combo,
+            Filter::MinGapToPrev(gap) => match index.checked_sub(1) {
+                Some(prev) => obj.time.saturating_sub(hitobjects[prev].time) >= *gap,
+                None => true,
+            },
+            Filter::And(left, right) => {
+                left.matches(hitobjects, index) && right.matches(hitobjects, index)
+            }
+            Filter::Or(left, right) => {
+                left.matches(hitobjects, index) || right.matches(hitobjects, index)
+            }
+            Filter::Not(filter) => !filter.matches(hitobjects, index),
+        }
+    }
+}
+
+/// A query over [`HitObjects`], created with [`HitObjects::query`].
+/// - Each builder method narrows down the query, as if the filters were combined with [`Filter::and`].
+/// - The results are indices into the [`HitObjects`], which can be used for bulk mutation.
+#[derive(Clone, Debug)]
+pub struct Query<'a> {
+    hitobjects: &'a HitObjects,
+    filter: Option<Filter>,
+}
+
+impl<'a> Query<'a> {
+    /// Creates a new query matching every object.
+    pub fn new(hitobjects: &'a HitObjects) -> Self {
+        Self {
+            hitobjects,
+            filter: None,
+        }
+    }
+
+    /// Narrows down the query with a custom [`Filter`].
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = Some(match self.filter {
+            Some(current) => current.and(filter),
+            None => filter,
+        });
+        self
+    }
+
+    /// Only matches objects with the `time` inside of the range.
+    pub fn time_range(self, range: Range<u32>) -> Self {
+        self.filter(Filter::TimeRange(range))
+    }
+
+    /// Only matches hitcircles.
+    pub fn is_hitcircle(self) -> Self {
+        self.filter(Filter::IsHitCircle)
+    }
+
+    /// Only matches sliders.
+    pub fn is_slider(self) -> Self {
+        self.filter(Filter::IsSlider)
+    }
+
+    /// Only matches spinners.
+    pub fn is_spinner(self) -> Self {
+        self.filter(Filter::IsSpinner)
+    }
+
+    /// Only matches osu!mania hold notes.
+    pub fn is_osu_mania_hold(self) -> Self {
+        self.filter(Filter::IsOsuManiaHold)
+    }
+
+    /// Only matches objects with the `new_combo` flag set to the value.
+    pub fn new_combo(self, new_combo: bool) -> Self {
+        self.filter(Filter::NewCombo(new_combo))
+    }
+
+    /// Only matches objects that are at least `gap` milliseconds after the previous object.
+    pub fn min_gap_to_prev(self, gap: u32) -> Self {
+        self.filter(Filter::MinGapToPrev(gap))
+    }
+
+    /// Returns the indices of the matching objects.
+    pub fn indices(&self) -> Vec<usize> {
+        let objs = &self.hitobjects.0;
+
+        (0..objs.len())
+            .filter(|i| match &self.filter {
+                Some(filter) => filter.matches(objs, *i),
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Returns an iterator over the matching objects.
+    pub fn iter(&self) -> impl Iterator<Item = &'a HitObject> + '_ {
+        let objs = &self.hitobjects.0;
+
+        self.indices().into_iter().map(move |i| &objs[i])
+    }
+
+    /// Returns the number of matching objects.
+    pub fn count(&self) -> usize {
+        self.indices().len()
+    }
+}
+
+impl HitObject {
+    /// End time in milliseconds, which is the start time for hitcircles.
+    /// - Slider durations are worked out from the `timing_points` and `slider_multiplier`, which is `SliderMultiplier` in the `[Difficulty]` section.
+    pub fn end_time(&self, timing_points: &TimingPoints, slider_multiplier: Decimal) -> f64 {
+        match &self.obj_params {
+            HitObjectParams::Slider(params) => {
+                let velocity = slider_velocity_at(&timing_points.0, slider_multiplier, self.time)
+                    .checked_mul(Decimal::ONE_HUNDRED);
+                let duration = params
+                    .length
+                    .checked_mul(Decimal::from(params.slides.max(1)))
+                    .zip(velocity)
+                    .and_then(|(distance, velocity)| distance.checked_div(velocity))
+                    .and_then(|duration| duration.to_f64())
+                    .unwrap_or_default();
+
+                self.time as f64 + duration.max(0.0)
+            }
+            HitObjectParams::Spinner { end_time } | HitObjectParams::OsuManiaHold { end_time } => {
+                *end_time as f64
+            }
+            _ => self.time as f64,
+        }
+    }
+}
+
+impl HitObjects {
+    /// Objects with the `time` from `start` up to but not including `end`, in milliseconds.
+    pub fn objects_between(&self, start: u32, end: u32) -> impl Iterator<Item = &HitObject> {
+        self.0
+            .iter()
+            .filter(move |obj| (start..end).contains(&obj.time))
+    }
+
+    /// Hitcircles in the order they are in.
+    pub fn iter_circles(&self) -> impl Iterator<Item = &HitObject> {
+        self.0
+            .iter()
+            .filter(|obj| matches!(obj.obj_params, HitObjectParams::HitCircle))
+    }
+
+    /// Sliders in the order they are in.
+    pub fn iter_sliders(&self) -> impl Iterator<Item = &HitObject> {
+        self.0
+            .iter()
+            .filter(|obj| matches!(obj.obj_params, HitObjectParams::Slider(_)))
+    }
+
+    /// Spinners in the order they are in.
+    pub fn iter_spinners(&self) -> impl Iterator<Item = &HitObject> {
+        self.0
+            .iter()
+            .filter(|obj| matches!(obj.obj_params, HitObjectParams::Spinner { .. }))
+    }
+
+    /// Objects being played at `time`, from their start time to their [`end_time`][HitObject::end_time].
+    pub fn at_time<'a>(
+        &'a self,
+        time: u32,
+        timing_points: &'a TimingPoints,
+        slider_multiplier: Decimal,
+    ) -> impl Iterator<Item = &'a HitObject> {
+        self.0.iter().filter(move |obj| {
+            obj.time <= time && time as f64 <= obj.end_time(timing_points, slider_multiplier)
+        })
+    }
+
+    /// Creates a [`Query`] over the hitobjects.
+    pub fn query(&self) -> Query {
+        Query::new(self)
+    }
+
+    /// Calls `f` on every object that the indices point to.
+    /// - Indices that are out of bounds are ignored.
+    pub fn modify_indices<F>(&mut self, indices: &[usize], mut f: F)
+    where
+        F: FnMut(&mut HitObject),
+    {
+        for i in indices {
+            if let Some(obj) = self.0.get_mut(*i) {
+                f(obj);
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Module for hitobjects that are always kept in chronological order.
+
+use std::ops::Range;
+
+use super::{HitObject, HitObjects};
+
+/// [`HitObjects`] that are always sorted by time.
+/// - Hitobjects with the same time keep the order they were added in.
+/// - Queries use binary searches, so they don't need to sort the hitobjects first.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "HitObjects", from = "HitObjects"))]
+pub struct SortedHitObjects(Vec<HitObject>);
+
+impl SortedHitObjects {
+    /// Creates an empty list of hitobjects.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The hitobjects in chronological order.
+    pub fn as_slice(&self) -> &[HitObject] {
+        &self.0
+    }
+
+    /// Iterates over the hitobjects in chronological order.
+    pub fn iter(&self) -> std::slice::Iter<HitObject> {
+        self.0.iter()
+    }
+
+    /// Number of hitobjects.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Checks if there are no hitobjects.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Inserts the hitobject after the ones at or before its time.
+    /// - Returns the index of the inserted hitobject.
+    pub fn insert(&mut self, hitobject: HitObject) -> usize {
+        let index = self.0.partition_point(|h| h.time <= hitobject.time);
+        self.0.insert(index, hitobject);
+
+        index
+    }
+
+    /// Removes the hitobject at `index`.
+    /// - Returns `None` if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Option<HitObject> {
+        (index < self.0.len()).then(|| self.0.remove(index))
+    }
+
+    /// Hitobjects with the time inside of `range`.
+    pub fn objects_in_range(&self, range: Range<u32>) -> &[HitObject] {
+        let start = self.0.partition_point(|h| h.time < range.start);
+        let end = self.0.partition_point(|h| h.time < range.end).max(start);
+
+        &self.0[start..end]
+    }
+
+    /// Last hitobject at or before `time`.
+    pub fn last_at_or_before(&self, time: u32) -> Option<&HitObject> {
+        let index = self.0.partition_point(|h| h.time <= time);
+
+        index.checked_sub(1).map(|i| &self.0[i])
+    }
+
+    /// First hitobject after `time`.
+    pub fn first_after(&self, time: u32) -> Option<&HitObject> {
+        let index = self.0.partition_point(|h| h.time <= time);
+
+        self.0.get(index)
+    }
+}
+
+impl From<HitObjects> for SortedHitObjects {
+    /// Sorts the hitobjects by time, keeping the order of hitobjects with the same time.
+    fn from(hitobjects: HitObjects) -> Self {
+        let mut hitobjects = hitobjects.0;
+        hitobjects.sort_by_key(|h| h.time);
+
+        Self(hitobjects)
+    }
+}
+
+impl From<SortedHitObjects> for HitObjects {
+    fn from(hitobjects: SortedHitObjects) -> Self {
+        HitObjects(hitobjects.0)
+    }
+}
+
+impl<'a> IntoIterator for &'a SortedHitObjects {
+    type Item = &'a HitObject;
+    type IntoIter = std::slice::Iter<'a, HitObject>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
@@ -1,14 +1,12 @@
-use std::num::{NonZeroUsize, ParseIntError};
 use rust_decimal::Decimal;
+use std::num::{NonZeroUsize, ParseIntError};
 
-use crate::{
-    helper::nth_bit_state_i64,
-    osu_file::*
-};
+use crate::{helper::nth_bit_state_i64, osu_file::*};
 
 use super::error::*;
 
 #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ComboSkipCount(u8);
 
 impl ComboSkipCount {
@@ -49,6 +47,7 @@ impl VersionedFrom<ComboSkipCount> for u8 {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Sample sets used for the `edgeSounds`.
 pub struct EdgeSet {
     /// Sample set of the normal sound.
@@ -68,12 +67,32 @@ impl VersionedFromStr for EdgeSet {
 
         Ok(Some(Self {
             normal_set: split[0].parse::<SampleSet>()?,
-            addition_set: split[1].parse::<SampleSet>()?
+            addition_set: split[1].parse::<SampleSet>()?,
         }))
     }
 }
 
+impl Default for EdgeSet {
+    fn default() -> Self {
+        Self {
+            normal_set: SampleSet::NoCustomSampleSet,
+            addition_set: SampleSet::NoCustomSampleSet,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+/// Samples played on a single node of a [`slider`][super::SlideParams].
+/// - Nodes are the head, each repeat, and the tail of the slider.
+pub struct NodeSample {
+    /// Sounds played on the node.
+    pub hitsound: HitSound,
+    /// Sample sets used by the node.
+    pub edge_set: EdgeSet,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Anchor point used to construct the [`slider`][super::SlideParams].
 pub struct CurvePoint(pub Position);
 
@@ -89,10 +108,10 @@ impl VersionedFromStr for CurvePoint {
         Ok(Some(Self(Position {
             x: split[0]
                 .parse::<Decimal>()
-                .map_err(|_| { ParseCurvePointError::InvalidX })?,
+                .map_err(|_| ParseCurvePointError::InvalidX)?,
             y: split[1]
                 .parse::<Decimal>()
-                .map_err(|_| { ParseCurvePointError::InvalidY })?
+                .map_err(|_| ParseCurvePointError::InvalidY)?,
         })))
     }
 }
@@ -105,6 +124,7 @@ impl VersionedToString for CurvePoint {
 
 /// Used for `normal_set` and `addition_set` for the `[hitobject]`[super::HitObject].
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum SampleSet {
     /// No custom sample set.
@@ -154,6 +174,7 @@ impl FromStr for SampleSet {
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Volume of the sample from `1` to `100`. If [volume][Self::volume] returns `None`, the timing point's volume will be used instead.
 pub struct Volume(Option<u8>);
 
@@ -234,6 +255,7 @@ impl VersionedFromStr for Volume {
 }
 
 #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Flags that determine which sounds will play when the object is hit.
 /// # Possible sounds
 /// [`normal`][Self::normal] [`whistle`][Self::whistle] [`finish`][Self::finish] [`clap`][Self::clap]
@@ -341,6 +363,7 @@ impl VersionedFrom<u8> for HitSound {
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 /// Type of curve used to construct the [`Slider`][super::SlideParams].
 pub enum CurveType {
@@ -382,6 +405,7 @@ impl VersionedToString for CurveType {
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum SampleIndex {
     TimingPointSampleIndex,
@@ -435,6 +459,7 @@ impl VersionedFromStr for SampleIndex {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Information about which samples are played when the object is hit.
 /// It is closely related to [`hitSound`][HitSound].
 pub struct HitSample {
@@ -445,38 +470,60 @@ pub struct HitSample {
     pub filename: Option<String>,
 }
 
+/// Where the sound of a [`HitSample`] comes from, found with [`HitSample::resolved_source`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum SampleSource<'a> {
+    /// File in the beatmap directory, by its name.
+    File(&'a str),
+    /// Sample from the sample sets and index.
+    /// - [`SampleSet::NoCustomSampleSet`] and [`SampleIndex::TimingPointSampleIndex`] are taken from the timing point.
+    Bank {
+        normal_set: SampleSet,
+        addition_set: SampleSet,
+        index: SampleIndex,
+    },
+}
+
+impl HitSample {
+    /// Where the sound is played from.
+    /// - A non-empty `filename` is played instead of the sample sets and index.
+    /// - `slider_node` is `true` for the sounds on the head, repeats and tail of a slider, which always use the sample sets and index.
+    pub fn resolved_source(&self, slider_node: bool) -> SampleSource<'_> {
+        match self.filename.as_deref() {
+            Some(filename) if !filename.is_empty() && !slider_node => SampleSource::File(filename),
+            _ => SampleSource::Bank {
+                normal_set: self.normal_set,
+                addition_set: self.addition_set,
+                index: self.index,
+            },
+        }
+    }
+}
+
 impl VersionedFromStr for HitSample {
     type Err = ParseHitSampleError;
 
     fn from_str(s: &str, version: Version) -> std::result::Result<Option<Self>, Self::Err> {
-        let split: Vec<&str> = s.split(':').collect();
-        
+        let split: Vec<&str> = s.splitn(5, ':').collect();
+
         if split.len() < 4 {
             return Err(ParseHitSampleError::InvalidLength);
         }
 
         Ok(Some(Self {
-            normal_set: SampleSet
-                ::from_str(split[0], version)
-                .map_err(|_| { ParseHitSampleError::InvalidNormalSet })?
-                .unwrap(),
-            addition_set: SampleSet
-                ::from_str(split[1], version)
-                .map_err(|_| { ParseHitSampleError::InvalidAdditionSet })?
-                .unwrap(),
-            index: SampleIndex
-                ::from_str(split[2], version)
-                .map_err(|_| { ParseHitSampleError::InvalidIndex })?
-                .unwrap(),
-            volume: Volume
-                ::from_str(split[3], version)
-                .map_err(|_| { ParseHitSampleError::InvalidVolume })?
-                .unwrap(),
-            filename: if split.len() == 5 {
-                Some(String::from_str(split[4]).unwrap())
-            } else {
-                None
-            }
+            normal_set: split[0]
+                .parse::<SampleSet>()
+                .map_err(|_| ParseHitSampleError::InvalidNormalSet)?,
+            addition_set: split[1]
+                .parse::<SampleSet>()
+                .map_err(|_| ParseHitSampleError::InvalidAdditionSet)?,
+            index: SampleIndex::from_str(split[2], version)
+                .map_err(|_| ParseHitSampleError::InvalidIndex)?
+                .ok_or(ParseHitSampleError::InvalidIndex)?,
+            volume: Volume::from_str(split[3], version)
+                .map_err(|_| ParseHitSampleError::InvalidVolume)?
+                .ok_or(ParseHitSampleError::InvalidVolume)?,
+            filename: split.get(4).map(|filename| filename.to_string()),
         }))
     }
 }
@@ -0,0 +1,277 @@
+//! Module for finding what is lost when an [`OsuFile`] is written in another version.
+//! - Everything is written in the target version and parsed back, so only changes that can't be read back the same are reported.
+
+use super::{
+    events::{storyboard::cmds::Command, Event, EventWithCommands},
+    timingpoints::TimingPoint,
+    Colours, Events, HitObjects, OsuFile, SectionName, TimingPoints, Version, VersionedFromStr,
+    VersionedToString,
+};
+
+/// What is lost, found by [`OsuFile::lossiness_report`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LossTarget {
+    /// The whole section.
+    Section(SectionName),
+    /// Field of a key-value section, by the name written in the file.
+    Field {
+        section: SectionName,
+        name: &'static str,
+    },
+    /// Event, by its index in the `[Events]` section.
+    Event(usize),
+    /// Command of an event, by the index of the event and the index of the command in it.
+    /// - Commands inside of loops and triggers are part of the loop or trigger command.
+    Command { event: usize, command: usize },
+    /// Timing point, by its index in the `[TimingPoints]` section.
+    TimingPoint(usize),
+    /// Hitobject, by its index in the `[HitObjects]` section.
+    HitObject(usize),
+}
+
+/// How something is lost.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum LossKind {
+    /// Not written at all.
+    Dropped,
+    /// Written, but read back as a different value.
+    Altered,
+}
+
+/// Something that is lost when writing an [`OsuFile`] in another version.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Loss {
+    pub target: LossTarget,
+    pub kind: LossKind,
+}
+
+impl Loss {
+    fn new(target: LossTarget, kind: LossKind) -> Self {
+        Self { target, kind }
+    }
+}
+
+macro_rules! field_losses {
+    ($losses:ident, $section:expr, $type:ty, $name:expr, $version:expr) => {
+        if let Some(section) = &$section {
+            let written = section
+                .to_string($version)
+                .map(|s| <$type>::from_str(&s, $version));
+
+            match written {
+                Some(Ok(Some(written))) => {
+                    for (field, value) in section.fields() {
+                        let kind = match written.get(field) {
+                            Some(written) if written == value => continue,
+                            Some(_) => LossKind::Altered,
+                            None => LossKind::Dropped,
+                        };
+
+                        $losses.push(Loss::new(
+                            LossTarget::Field {
+                                section: $name,
+                                name: field,
+                            },
+                            kind,
+                        ));
+                    }
+                }
+                Some(_) => $losses.push(Loss::new(LossTarget::Section($name), LossKind::Altered)),
+                None => $losses.push(Loss::new(LossTarget::Section($name), LossKind::Dropped)),
+            }
+        }
+    };
+}
+
+fn commands_mut(event: &mut Event) -> Option<&mut Vec<Command>> {
+    match event {
+        Event::Background(background) => Some(background.commands_mut()),
+        Event::Video(video) => Some(video.commands_mut()),
+        Event::SpriteLegacy(sprite) => Some(sprite.commands_mut()),
+        Event::AnimationLegacy(animation) => Some(animation.commands_mut()),
+        Event::SampleLegacy(sample) => Some(sample.commands_mut()),
+        Event::StoryboardObject(object) => Some(object.commands_mut()),
+        _ => None,
+    }
+}
+
+/// Splits the event into the event without its commands, and its commands.
+fn split_commands(event: &Event) -> (Events, Vec<Command>) {
+    let mut event = event.clone();
+    let commands = commands_mut(&mut event).map(std::mem::take);
+
+    (Events(vec![event]), commands.unwrap_or_default())
+}
+
+fn event_losses(losses: &mut Vec<Loss>, index: usize, event: &Event, version: Version) {
+    let written = match event.to_string(version) {
+        Some(written) => written,
+        None => {
+            losses.push(Loss::new(LossTarget::Event(index), LossKind::Dropped));
+            return;
+        }
+    };
+
+    let read_back = match Events::from_str(&written, version) {
+        Ok(Some(events)) if events.0.len() == 1 => events.0.into_iter().next().unwrap(),
+        _ => {
+            losses.push(Loss::new(LossTarget::Event(index), LossKind::Altered));
+            return;
+        }
+    };
+
+    let (event, commands) = split_commands(event);
+    let (read_back, read_back_commands) = split_commands(&read_back);
+
+    if !event.semantically_eq(&read_back) {
+        losses.push(Loss::new(LossTarget::Event(index), LossKind::Altered));
+    }
+
+    // dropped commands aren't read back, so the commands that are written are matched in order
+    let mut read_back_commands = read_back_commands.iter();
+    for (command_index, command) in commands.iter().enumerate() {
+        let target = LossTarget::Command {
+            event: index,
+            command: command_index,
+        };
+
+        if command.to_string(version).is_none() {
+            losses.push(Loss::new(target, LossKind::Dropped));
+        } else if read_back_commands.next() != Some(command) {
+            losses.push(Loss::new(target, LossKind::Altered));
+        }
+    }
+}
+
+/// Compares the items of a list section with the items read back after writing it.
+/// - `read_back` is `None` if the section isn't written, and `Some(None)` if it can't be read back.
+/// - The whole section is reported if the number of items changed, since the items can't be matched up.
+fn list_losses<T>(
+    losses: &mut Vec<Loss>,
+    section: SectionName,
+    original: &[T],
+    read_back: Option<Option<Vec<T>>>,
+    target: fn(usize) -> LossTarget,
+    eq: impl Fn(&T, &T) -> bool,
+) {
+    let read_back = match read_back {
+        Some(Some(read_back)) if read_back.len() == original.len() => read_back,
+        Some(_) => {
+            losses.push(Loss::new(LossTarget::Section(section), LossKind::Altered));
+            return;
+        }
+        None => {
+            losses.push(Loss::new(LossTarget::Section(section), LossKind::Dropped));
+            return;
+        }
+    };
+
+    for (i, (original, read_back)) in original.iter().zip(&read_back).enumerate() {
+        if !eq(original, read_back) {
+            losses.push(Loss::new(target(i), LossKind::Altered));
+        }
+    }
+}
+
+impl OsuFile {
+    /// Finds everything that would be dropped or changed by writing the file in the `version`.
+    /// - Each section is written in `version` and parsed back, then compared with the current values.
+    /// - Differences in how values are written, such as numbered and named origins, aren't reported.
+    /// - Sections that are `None` and custom sections aren't checked.
+    pub fn lossiness_report(&self, version: Version) -> Vec<Loss> {
+        let mut losses = Vec::new();
+
+        field_losses!(
+            losses,
+            self.general,
+            super::General,
+            SectionName::General,
+            version
+        );
+        field_losses!(
+            losses,
+            self.editor,
+            super::Editor,
+            SectionName::Editor,
+            version
+        );
+        field_losses!(
+            losses,
+            self.metadata,
+            super::Metadata,
+            SectionName::Metadata,
+            version
+        );
+        field_losses!(
+            losses,
+            self.difficulty,
+            super::Difficulty,
+            SectionName::Difficulty,
+            version
+        );
+
+        if let Some(events) = &self.events {
+            for (i, event) in events.0.iter().enumerate() {
+                event_losses(&mut losses, i, event, version);
+            }
+        }
+
+        if let Some(timing_points) = &self.timing_points {
+            let read_back = timing_points.to_string(version).map(|s| {
+                TimingPoints::from_str(&s, version)
+                    .ok()
+                    .flatten()
+                    .map(|t| t.0)
+            });
+            let single = |timing_point: &TimingPoint| TimingPoints(vec![timing_point.clone()]);
+
+            // short timing points can be written with more fields, which isn't a loss
+            list_losses(
+                &mut losses,
+                SectionName::TimingPoints,
+                &timing_points.0,
+                read_back,
+                LossTarget::TimingPoint,
+                |a, b| single(a).semantically_eq(&single(b)),
+            );
+        }
+
+        if let Some(colours) = &self.colours {
+            match colours
+                .to_string(version)
+                .map(|s| Colours::from_str(&s, version))
+            {
+                Some(Ok(Some(read_back))) if read_back == *colours => (),
+                Some(_) => losses.push(Loss::new(
+                    LossTarget::Section(SectionName::Colours),
+                    LossKind::Altered,
+                )),
+                None => losses.push(Loss::new(
+                    LossTarget::Section(SectionName::Colours),
+                    LossKind::Dropped,
+                )),
+            }
+        }
+
+        if let Some(hitobjects) = &self.hitobjects {
+            let read_back = hitobjects.to_string(version).map(|s| {
+                HitObjects::from_str(&s, version)
+                    .ok()
+                    .flatten()
+                    .map(|h| h.0)
+            });
+
+            list_losses(
+                &mut losses,
+                SectionName::HitObjects,
+                &hitobjects.0,
+                read_back,
+                LossTarget::HitObject,
+                |a, b| a == b,
+            );
+        }
+
+        losses
+    }
+}
@@ -0,0 +1,198 @@
+//! Module for reading the information used to index a beatmap, without parsing the whole file.
+//! - Meant for song libraries with many beatmaps, where the events, timing points and hitobjects don't need to be parsed.
+
+use rust_decimal::Decimal;
+
+use super::{
+    file_version,
+    timingpoints::{TimingPoint, TimingPoints},
+    Error, Integer, OsuFile, ParseError, Version, VersionedFromStr,
+};
+use crate::helper::add_old_version_time_offset;
+
+/// Lowest and highest BPM of the uninherited timing points, up to the last hitobject.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct BpmRange {
+    pub min: Decimal,
+    pub max: Decimal,
+}
+
+/// Number of each kind of hitobject.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct ObjectCounts {
+    pub hitcircles: usize,
+    pub sliders: usize,
+    pub spinners: usize,
+    pub osu_mania_holds: usize,
+}
+
+impl ObjectCounts {
+    pub fn total(&self) -> usize {
+        self.hitcircles + self.sliders + self.spinners + self.osu_mania_holds
+    }
+}
+
+/// The parts of an .osu file used to index it, from [`OsuFileMeta::parse`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct OsuFileMeta {
+    /// Version of the file format.
+    pub version: Version,
+    pub general: Option<super::General>,
+    pub metadata: Option<super::Metadata>,
+    pub difficulty: Option<super::Difficulty>,
+    /// BPM range of the `[TimingPoints]` section, the same as the `min` and `max` of [`TimingPoints::display_bpm`] up to the start of the last hitobject.
+    /// - `None` if there are no uninherited timing points with a positive beat length.
+    pub bpm: Option<BpmRange>,
+    pub object_counts: ObjectCounts,
+}
+
+/// Section that is being scanned.
+enum Scanning {
+    /// Section that is parsed fully once it ends, by its name and the line index its content starts at.
+    Fields(String, usize),
+    TimingPoints,
+    HitObjects,
+    Skipped,
+}
+
+impl OsuFileMeta {
+    /// Reads the version, `[General]`, `[Metadata]` and `[Difficulty]` sections, the BPM range and the number of hitobjects in a single pass.
+    /// - `[General]` fields that moved to `[Editor]` in the file's version are dropped, like they are moved out of it by a full parse.
+    /// - Only the `key: value` sections are parsed, the `[TimingPoints]` and `[HitObjects]` sections are only scanned for the fields needed, and the other sections are skipped.
+    /// - Lines that can't be scanned in the `[TimingPoints]` and `[HitObjects]` sections are skipped instead of being an error, so a file that can be read with this can still fail to parse as an [`OsuFile`][super::OsuFile].
+    pub fn parse(s: &str) -> Result<Self, Error<ParseError>> {
+        let (s, (leading, version)) = file_version(s)?;
+
+        let mut meta = OsuFileMeta {
+            version,
+            general: None,
+            metadata: None,
+            difficulty: None,
+            bpm: None,
+            object_counts: ObjectCounts::default(),
+        };
+
+        let mut scanning = Scanning::Skipped;
+        let mut content = String::new();
+        let mut uninherited = Vec::new();
+        let mut last_object_time = None;
+        let first_line = leading.matches('\n').count();
+
+        for (i, line) in s.lines().enumerate() {
+            let line_index = first_line + i;
+            let trimmed = line.trim();
+
+            if let Some(name) = trimmed
+                .strip_prefix('[')
+                .and_then(|name| name.strip_suffix(']'))
+            {
+                meta.finish_section(&scanning, &content)?;
+                content.clear();
+
+                scanning = match name {
+                    "General" | "Metadata" | "Difficulty" => {
+                        Scanning::Fields(name.to_string(), line_index + 1)
+                    }
+                    "TimingPoints" => Scanning::TimingPoints,
+                    "HitObjects" => Scanning::HitObjects,
+                    _ => Scanning::Skipped,
+                };
+                continue;
+            }
+
+            match scanning {
+                Scanning::Fields(..) => {
+                    content.push_str(line);
+                    content.push('\n');
+                }
+                Scanning::TimingPoints => {
+                    // only uninherited timing points have a BPM
+                    if let Ok(Some(timing_point)) = TimingPoint::from_str(trimmed, version) {
+                        if timing_point.uninherited {
+                            uninherited.push(timing_point);
+                        }
+                    }
+                }
+                Scanning::HitObjects => {
+                    if let Some(time) = meta.scan_hitobject(trimmed) {
+                        last_object_time = last_object_time.max(Some(time));
+                    }
+                }
+                Scanning::Skipped => (),
+            }
+        }
+
+        meta.finish_section(&scanning, &content)?;
+        meta.migrate_general();
+
+        let last_object_time =
+            last_object_time.map_or(0, |time| Integer::try_from(time).unwrap_or(Integer::MAX));
+        meta.bpm = TimingPoints(uninherited)
+            .display_bpm(last_object_time)
+            .map(|bpm| BpmRange {
+                min: bpm.min,
+                max: bpm.max,
+            });
+
+        Ok(meta)
+    }
+
+    fn finish_section(
+        &mut self,
+        scanning: &Scanning,
+        content: &str,
+    ) -> Result<(), Error<ParseError>> {
+        let (name, line_index) = match scanning {
+            Scanning::Fields(name, line_index) => (name.as_str(), *line_index),
+            _ => return Ok(()),
+        };
+        let (content, version) = (content.trim_end(), self.version);
+
+        match name {
+            "General" => {
+                self.general =
+                    Error::processing_line(super::General::from_str(content, version), line_index)?
+            }
+            "Metadata" => {
+                self.metadata =
+                    Error::processing_line(super::Metadata::from_str(content, version), line_index)?
+            }
+            _ => {
+                self.difficulty = Error::processing_line(
+                    super::Difficulty::from_str(content, version),
+                    line_index,
+                )?
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Moves the `[General]` fields out the same way as [`OsuFile::migrate_parsed_fields`], so it matches the fully parsed `[General]` section.
+    fn migrate_general(&mut self) {
+        let mut osu_file = OsuFile::new(self.version);
+        osu_file.general = self.general.take();
+        osu_file.migrate_parsed_fields();
+        self.general = osu_file.general;
+    }
+
+    /// Counts a hitobject by the type bits of `x,y,time,type,...`, returning its time.
+    fn scan_hitobject(&mut self, line: &str) -> Option<u32> {
+        let mut fields = line.split(',').map(str::trim).skip(2);
+        let time = fields.next()?.parse::<u32>().ok();
+        let obj_type = fields.next()?.parse::<u8>().ok()?;
+
+        let counts = &mut self.object_counts;
+        if obj_type & 1 != 0 {
+            counts.hitcircles += 1;
+        } else if obj_type & 2 != 0 {
+            counts.sliders += 1;
+        } else if obj_type & 8 != 0 {
+            counts.spinners += 1;
+        } else if obj_type & 128 != 0 {
+            counts.osu_mania_holds += 1;
+        }
+
+        time.map(|time| add_old_version_time_offset(time, self.version))
+    }
+}
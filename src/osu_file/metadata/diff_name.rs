@@ -0,0 +1,83 @@
+//! Module for reading the difficulty name in [`Version`][super::Version], which ranking tools use to find the key count and guest mappers.
+//! - Difficulty names are free text, so these follow the common ways of writing them.
+
+/// Words that mark a difficulty made by more than one mapper, compared in lowercase.
+const COLLAB_MARKERS: [&str; 2] = ["collab", "collaboration"];
+
+/// Separators between the names of mappers.
+const NAME_SEPARATORS: [&str; 4] = [" & ", " x ", " and ", ", "];
+
+/// Splits the key count from the start of the name, such as `4K Hard` or `[7K] Insane`.
+fn split_key_count(name: &str) -> Option<(u8, &str)> {
+    let (prefix, rest) = match name.strip_prefix('[') {
+        Some(name) => name.split_once(']')?,
+        None => name.split_once(' ').unwrap_or((name, "")),
+    };
+
+    let keys = prefix
+        .strip_suffix('K')
+        .or_else(|| prefix.strip_suffix('k'))?;
+    if keys.is_empty() || !keys.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    Some((keys.parse().ok()?, rest.trim_start()))
+}
+
+impl super::Version {
+    /// Key count at the start of the name, such as `4` for `4K Hard` or `[7K] Insane`.
+    pub fn key_count(&self) -> Option<u8> {
+        split_key_count(&self.0).map(|(keys, _)| keys)
+    }
+
+    /// Name without the key count at the start, such as `Hard` for `4K Hard`.
+    pub fn without_key_count(&self) -> &str {
+        split_key_count(&self.0).map_or(&self.0, |(_, rest)| rest)
+    }
+
+    /// Mappers named with a possessive, such as `Name` for `Name's Insane` or both names for `A & B's Extra`.
+    /// - Names are split by `&`, `x`, `and` and `,`.
+    /// - Returns an empty `Vec` if there's no possessive, which is usually a difficulty by the mapset's `Creator`.
+    pub fn guest_creators(&self) -> Vec<String> {
+        let name = format!("{} ", self.without_key_count());
+
+        let end = ["'s ", "\u{2019}s ", "' ", "\u{2019} "]
+            .iter()
+            .filter_map(|possessive| {
+                let end = name.find(possessive)?;
+                // `'` on its own is only a possessive after an `s`, like `Chris' Insane`
+                let valid = possessive.contains('s') || name[..end].ends_with('s');
+                valid.then_some(end)
+            })
+            .min();
+        let creators = match end {
+            Some(end) => &name[..end],
+            None => return Vec::new(),
+        };
+
+        let mut names = vec![creators.to_string()];
+        for separator in NAME_SEPARATORS {
+            names = names
+                .iter()
+                .flat_map(|name| name.split(separator))
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+
+        names
+    }
+
+    /// Returns `true` if the name marks a difficulty made by more than one mapper.
+    /// - Either the name has a word like `Collab`, or more than one [`guest_creators`][Self::guest_creators].
+    pub fn is_collab(&self) -> bool {
+        let has_marker = self
+            .0
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| COLLAB_MARKERS.contains(&word));
+
+        has_marker || self.guest_creators().len() > 1
+    }
+}
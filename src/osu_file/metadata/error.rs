@@ -1,10 +1,11 @@
+use strum_macros::IntoStaticStr;
 use thiserror::Error;
 
 use std::num::ParseIntError;
 
 use crate::helper::macros::unreachable_err_impl;
 
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
 #[non_exhaustive]
 /// Error used when there was a problem parsing the `Metadata` section.
 pub enum ParseError {
@@ -1,4 +1,7 @@
+pub mod diff_name;
 pub mod error;
+#[cfg(feature = "unicode-normalization")]
+pub mod normalization;
 
 use nom::{
     bytes::complete::{tag, take_till},
@@ -9,7 +12,10 @@ use nom::{
 use super::Integer;
 use crate::helper::macros::*;
 
+pub use diff_name::*;
 pub use error::*;
+#[cfg(feature = "unicode-normalization")]
+pub use normalization::*;
 
 versioned_field!(Title, String, no_versions, |s| { Ok(s.to_string()) } -> (),,);
 versioned_field!(TitleUnicode, String, no_versions, |s| { Ok(s.to_string()) } -> (),,);
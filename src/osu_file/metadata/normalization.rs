@@ -0,0 +1,76 @@
+//! Module for comparing [`Metadata`][super::Metadata] text that is written with different Unicode normalization forms.
+//! - Text saved on different operating systems can look the same but be made of different characters, such as `é` as one character or as `e` and a combining accent.
+
+use std::borrow::Cow;
+
+use unicode_normalization::{is_nfc_quick, is_nfkc_quick, IsNormalized, UnicodeNormalization};
+
+use crate::osu_file::FieldValue;
+
+/// Unicode normalization form used to compare text.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Normalization {
+    /// Text is compared as it is.
+    #[default]
+    None,
+    /// Canonical composition, which only joins characters that are the same, such as `e` and a combining accent into `é`.
+    Nfc,
+    /// Compatibility composition, which also joins characters that look alike, such as full-width `Ａ` into `A`.
+    Nfkc,
+}
+
+impl Normalization {
+    /// Normalizes `s`, borrowing it if it's already normalized.
+    pub fn apply(self, s: &str) -> Cow<str> {
+        match self {
+            Normalization::None => Cow::Borrowed(s),
+            Normalization::Nfc => match is_nfc_quick(s.chars()) {
+                IsNormalized::Yes => Cow::Borrowed(s),
+                _ => Cow::Owned(s.nfc().collect()),
+            },
+            Normalization::Nfkc => match is_nfkc_quick(s.chars()) {
+                IsNormalized::Yes => Cow::Borrowed(s),
+                _ => Cow::Owned(s.nfkc().collect()),
+            },
+        }
+    }
+
+    /// Normalizes the text in a field value, leaving other values as they are.
+    pub fn apply_to_value(self, value: FieldValue) -> FieldValue {
+        match value {
+            FieldValue::String(s) => FieldValue::String(self.apply(&s).into_owned()),
+            FieldValue::StringList(list) => {
+                FieldValue::StringList(list.iter().map(|s| self.apply(s).into_owned()).collect())
+            }
+            value => value,
+        }
+    }
+}
+
+impl super::Metadata {
+    /// Gets a field by the name written in the file with its text normalized, see [`get`][Self::get].
+    pub fn get_normalized(&self, name: &str, normalization: Normalization) -> Option<FieldValue> {
+        self.get(name)
+            .map(|value| normalization.apply_to_value(value))
+    }
+
+    /// Copy of the metadata with the text of every field normalized.
+    pub fn normalized(&self, normalization: Normalization) -> Self {
+        let mut metadata = self.clone();
+
+        for (name, value) in self.fields() {
+            // the value has the type of the field, so setting it can't fail
+            metadata
+                .set(name, normalization.apply_to_value(value))
+                .unwrap();
+        }
+
+        metadata
+    }
+
+    /// Returns `true` if the metadata is the same after normalizing the text of both.
+    pub fn eq_normalized(&self, other: &Self, normalization: Normalization) -> bool {
+        self.normalized(normalization) == other.normalized(normalization)
+    }
+}
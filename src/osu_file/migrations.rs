@@ -0,0 +1,155 @@
+//! Module for fields that were moved to another section or renamed between versions.
+//! - Fields are read from either place, and moved to where the version writes them.
+
+use super::{FieldValue, OsuFile, SectionName, Version};
+
+/// Place a field is written, by the section and the name written in the file.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct FieldLocation {
+    pub section: SectionName,
+    pub name: &'static str,
+}
+
+/// Field that is written in another place from a version onwards.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct FieldMigration {
+    /// Place the field is written before [`version`][Self::version].
+    pub old: FieldLocation,
+    /// Place the field is written from [`version`][Self::version] onwards.
+    pub new: FieldLocation,
+    /// First version that writes the field in the [`new`][Self::new] place.
+    pub version: Version,
+}
+
+impl FieldMigration {
+    /// Place the field is written in the `version`.
+    pub fn location(&self, version: Version) -> FieldLocation {
+        if version < self.version {
+            self.old
+        } else {
+            self.new
+        }
+    }
+}
+
+/// Fields that were moved, from the oldest version.
+pub const FIELD_MIGRATIONS: &[FieldMigration] = &[
+    FieldMigration {
+        old: FieldLocation {
+            section: SectionName::General,
+            name: "EditorBookmarks",
+        },
+        new: FieldLocation {
+            section: SectionName::Editor,
+            name: "Bookmarks",
+        },
+        version: 6,
+    },
+    FieldMigration {
+        old: FieldLocation {
+            section: SectionName::General,
+            name: "EditorDistanceSpacing",
+        },
+        new: FieldLocation {
+            section: SectionName::Editor,
+            name: "DistanceSpacing",
+        },
+        version: 6,
+    },
+];
+
+impl OsuFile {
+    /// Unsets the field, removing the section if it was the last field in it.
+    fn remove_field(&mut self, location: FieldLocation) -> Option<FieldValue> {
+        macro_rules! remove {
+            ($section:expr, $type:ty) => {{
+                let section = $section.as_mut()?;
+                let value = section.remove(location.name)?;
+                if *section == <$type>::new() {
+                    $section = None;
+                }
+                Some(value)
+            }};
+        }
+
+        match location.section {
+            SectionName::General => remove!(self.general, super::General),
+            SectionName::Editor => remove!(self.editor, super::Editor),
+            SectionName::Metadata => remove!(self.metadata, super::Metadata),
+            SectionName::Difficulty => remove!(self.difficulty, super::Difficulty),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn has_field(&self, location: FieldLocation) -> bool {
+        let value = match location.section {
+            SectionName::General => self.general.as_ref().and_then(|s| s.get(location.name)),
+            SectionName::Editor => self.editor.as_ref().and_then(|s| s.get(location.name)),
+            SectionName::Metadata => self.metadata.as_ref().and_then(|s| s.get(location.name)),
+            SectionName::Difficulty => self.difficulty.as_ref().and_then(|s| s.get(location.name)),
+            _ => None,
+        };
+
+        value.is_some()
+    }
+
+    /// Sets the field, adding the section if it's missing.
+    fn set_field(&mut self, location: FieldLocation, value: FieldValue) {
+        // both places of a migration use the same kind of value, so setting it can't fail
+        let _ = match location.section {
+            SectionName::General => self
+                .general
+                .get_or_insert_with(super::General::new)
+                .set(location.name, value),
+            SectionName::Editor => self
+                .editor
+                .get_or_insert_with(super::Editor::new)
+                .set(location.name, value),
+            SectionName::Metadata => self
+                .metadata
+                .get_or_insert_with(super::Metadata::new)
+                .set(location.name, value),
+            SectionName::Difficulty => self
+                .difficulty
+                .get_or_insert_with(super::Difficulty::new)
+                .set(location.name, value),
+            _ => Ok(()),
+        };
+    }
+
+    /// Moves the field from `from` to `to`, keeping the value already at `to` if there is one.
+    pub(crate) fn move_field(&mut self, from: FieldLocation, to: FieldLocation) {
+        if let Some(value) = self.remove_field(from) {
+            if !self.has_field(to) {
+                self.set_field(to, value);
+            }
+        }
+    }
+
+    /// Moves the fields in [`FIELD_MIGRATIONS`] to the place they are written in the `version`, so they aren't dropped when writing it.
+    /// - If the field is set in both places, the value in the place for the `version` is kept.
+    pub fn migrate_fields(&mut self, version: Version) {
+        for migration in FIELD_MIGRATIONS {
+            let to = migration.location(version);
+            let from = if to == migration.old {
+                migration.new
+            } else {
+                migration.old
+            };
+
+            self.move_field(from, to);
+        }
+
+        self.mark_existing_dirty(&[SectionName::General, SectionName::Editor]);
+    }
+
+    /// Moves fields written in their old place to the new place, if the file's version only writes the new place.
+    /// - Fields in the new place in older versions are kept there, since they are still written.
+    pub(crate) fn migrate_parsed_fields(&mut self) {
+        for migration in FIELD_MIGRATIONS {
+            if self.version >= migration.version {
+                self.move_field(migration.old, migration.new);
+            }
+        }
+    }
+}
@@ -1,42 +1,79 @@
+pub mod approx;
 pub mod colours;
+pub mod conversion;
+pub mod diagnostic;
 pub mod difficulty;
+mod dirty;
 pub mod editor;
 pub mod events;
+pub mod extensions;
+pub mod field_value;
 pub mod general;
+pub mod header;
 pub mod hitobjects;
+pub mod lossiness;
+pub mod meta;
 pub mod metadata;
+pub mod migrations;
+pub mod options;
 pub mod osb;
+pub mod parser;
+mod preserve;
+pub mod section;
+mod stream;
 pub mod timingpoints;
 pub mod types;
 
+use std::convert::Infallible;
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
 use std::str::FromStr;
 
-use nom::branch::alt;
-use nom::bytes::complete::{tag, take_till};
-use nom::character::complete::multispace0;
-use nom::combinator::{map_res, success};
-use nom::multi::many0;
+use nom::bytes::complete::{tag, take_till, take_while};
+use nom::combinator::map_res;
 use nom::sequence::{preceded, tuple};
+use rust_decimal::{prelude::ToPrimitive, Decimal, RoundingStrategy};
+use strum_macros::IntoStaticStr;
 use thiserror::Error;
 
+use crate::helper::{trim_fields_into, trim_hitobject_fields_into};
 use crate::parsers::square_section;
+use dirty::SectionGenerations;
+use events::storyboard::dedup::DedupReport;
+use header::is_header_junk;
+use preserve::{unknown_fields_removed, Formatting};
 
+pub use approx::{ApproxEq, DEFAULT_EPSILON};
 pub use colours::Colours;
+pub use conversion::{ConversionError, ConversionIssue, ConversionIssueKind, ConversionReport};
+pub use diagnostic::{Diagnostic, ErrorKind, Span};
 pub use difficulty::Difficulty;
 pub use editor::Editor;
 pub use events::Events;
+pub use extensions::{Extensions, ObjectId};
+pub use field_value::{FieldValue, FieldValueType, SetFieldError};
 pub use general::General;
+pub use header::FileHeader;
 pub use hitobjects::HitObjects;
+pub use lossiness::{Loss, LossKind, LossTarget};
+pub use meta::{BpmRange, ObjectCounts, OsuFileMeta};
 pub use metadata::Metadata;
+pub use migrations::{FieldLocation, FieldMigration, FIELD_MIGRATIONS};
+pub use options::{
+    CommandTimePolicy, InsertOptions, LimitsExceeded, ParseLimits, ParseOptions, ParseWarning,
+    WriteOptions,
+};
 pub use osb::Osb;
+use parser::ParseBuffers;
+pub use parser::Parser;
+pub use section::*;
 pub use timingpoints::TimingPoints;
 
 pub use types::*;
 
 /// An .osu file represented as a struct.
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub struct OsuFile {
     /// Version of the file format.
@@ -67,10 +104,31 @@ pub struct OsuFile {
     /// Hit objects.
     /// Comma-separated lists.
     pub hitobjects: Option<HitObjects>,
+    /// Sections that aren't part of the .osu file format.
+    /// - Kept from parsing with [`ParseOptions::sections`] or [`ParseOptions::keep_unknown_sections`].
+    pub custom_sections: Vec<RawSection>,
+    /// `//` comment lines of the `[TimingPoints]` and `[HitObjects]` sections when they have no timing points or hitobjects.
+    pub section_comments: SectionComments,
+    /// Generation counters of the sections, used by [`dirty_sections`][Self::dirty_sections].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    generations: Untracked<SectionGenerations>,
+    /// Warnings from parsing with [`ParseOptions::lenient`], [`ParseOptions::skip_malformed_lines`] or [`ParseOptions::command_time_policy`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    parse_warnings: Untracked<Vec<ParseWarning>>,
+    /// What was saved by [`ParseOptions::dedup_storyboard`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    storyboard_dedup: Untracked<Option<DedupReport>>,
+    /// Header from parsing, see [`header`][Self::header].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    header: Untracked<Option<FileHeader>>,
+    /// Input from parsing with [`ParseOptions::preserve_formatting`], [`ParseOptions::preserve_colour_formatting`] or [`ParseOptions::preserve_events_formatting`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    formatting: Untracked<Option<Box<Formatting>>>,
 }
 
 impl OsuFile {
     /// New `OsuFile` with no data.
+    /// - Fields that track how the file was parsed are private, so this is used instead of a struct literal.
     pub fn new(version: Version) -> Self {
         Self {
             version,
@@ -83,12 +141,19 @@ impl OsuFile {
             colours: None,
             hitobjects: None,
             osb: None,
+            custom_sections: Vec::new(),
+            section_comments: SectionComments::default(),
+            generations: Default::default(),
+            parse_warnings: Default::default(),
+            storyboard_dedup: Default::default(),
+            header: Default::default(),
+            formatting: Default::default(),
         }
     }
 
     /// Appends .osb file.
     pub fn append_osb(&mut self, s: &str) -> Result<(), Error<osb::ParseError>> {
-        self.osb = Osb::from_str(s, self.version)?;
+        self.osb = <Osb as VersionedFromStr>::from_str(s, self.version)?;
 
         Ok(())
     }
@@ -104,63 +169,293 @@ impl OsuFile {
     pub fn default(version: Version) -> OsuFile {
         OsuFile::new(version)
     }
-}
 
-impl Display for OsuFile {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut sections = Vec::new();
+    /// Minimal beatmap of the `mode` that can be opened by the game, with no timing points or hitobjects.
+    /// - See [`General::default_for`] and [`Difficulty::default_for`] for the values that are set.
+    /// - `[Metadata]`, `[Events]`, `[TimingPoints]` and `[HitObjects]` are empty, and `[Editor]` and `[Colours]` are left out.
+    pub fn default_for(version: Version, mode: general::Mode) -> OsuFile {
+        let mut osu_file = OsuFile::new(version);
+        osu_file.general = Some(general::General::default_for(version, mode));
+        osu_file.metadata = Some(metadata::Metadata::new());
+        osu_file.difficulty = Some(difficulty::Difficulty::default_for(mode));
+        osu_file.events = Events::default_for_mode(version, mode);
+        osu_file.timing_points = TimingPoints::default_for_mode(version, mode);
+        osu_file.hitobjects = HitObjects::default_for_mode(version, mode);
 
-        if let Some(general) = &self.general {
-            if let Some(general) = general.to_string(self.version) {
-                sections.push(("General", general));
-            }
+        osu_file
+    }
+
+    /// Values that had to be changed or lines that were skipped to be parsed with [`ParseOptions::lenient`], [`ParseOptions::skip_malformed_lines`] or [`ParseOptions::command_time_policy`], in the order they were found.
+    pub fn parse_warnings(&self) -> &[ParseWarning] {
+        &self.parse_warnings.0
+    }
+
+    /// What was saved by merging storyboard sprites with [`ParseOptions::dedup_storyboard`].
+    /// - Returns `None` if the option wasn't used or there's no `[Events]` section.
+    pub fn storyboard_dedup_report(&self) -> Option<&DedupReport> {
+        self.storyboard_dedup.0.as_ref()
+    }
+
+    /// Parses the custom section `S`.
+    /// - Returns `None` if the section doesn't exist.
+    /// - The line index of the error is from the start of the section.
+    pub fn section<S: Section>(&self) -> Result<Option<S>, Error<S::ParseError>> {
+        match self.custom_sections.iter().find(|s| s.name == S::NAME) {
+            Some(section) => S::from_str(&section.content, self.version),
+            None => Ok(None),
         }
-        if let Some(editor) = &self.editor {
-            if let Some(editor) = editor.to_string(self.version) {
-                sections.push(("Editor", editor));
+    }
+
+    /// Sets the custom section `S`, replacing the existing section with the same name.
+    /// - Removes the section if `section` can't be written in the file's version.
+    pub fn set_section<S: Section>(&mut self, section: &S) {
+        let content = section.to_string(self.version);
+        let existing = self.custom_sections.iter().position(|s| s.name == S::NAME);
+
+        match (content, existing) {
+            (Some(content), Some(i)) => self.custom_sections[i].content = content,
+            (Some(content), None) => self.custom_sections.push(RawSection {
+                name: S::NAME.to_string(),
+                content,
+                after: Some(SectionName::HitObjects),
+            }),
+            (None, Some(i)) => {
+                self.custom_sections.remove(i);
             }
+            (None, None) => (),
         }
-        if let Some(metadata) = &self.metadata {
-            if let Some(metadata) = metadata.to_string(self.version) {
-                sections.push(("Metadata", metadata));
+    }
+
+    /// Serializes the file with the given `version`, only including the sections in `sections`.
+    /// - The file format header is always included.
+    /// - Sections are written in the standard order no matter the order of `sections`.
+    /// - Sections that are `None` or don't exist in `version` are skipped.
+    pub fn to_string_sections(&self, version: Version, sections: &[SectionName]) -> String {
+        self.to_string_with_custom_sections(version, sections, false)
+    }
+
+    /// Serializes the file with the [`WriteOptions`].
+    pub fn to_string_with_options(&self, options: &WriteOptions) -> String {
+        // older files written by osu! don't always quote them
+        let quote_backgrounds = options.quote_background_file_names && self.version >= 14;
+
+        let s = match &self.events {
+            Some(events)
+                if options.prefer_named_enums.is_some()
+                    || options.prefer_numeric_event_headers.is_some()
+                    || options.always_write_event_position
+                    || quote_backgrounds =>
+            {
+                let mut events = match options.prefer_named_enums {
+                    Some(named) => events.with_named_enums(named),
+                    None => events.clone(),
+                };
+                if let Some(numeric) = options.prefer_numeric_event_headers {
+                    events = events.with_numeric_headers(numeric);
+                }
+                if options.always_write_event_position {
+                    events = events.with_positions();
+                }
+                if quote_backgrounds {
+                    events = events.with_quoted_backgrounds();
+                }
+
+                let mut osu_file = self.clone();
+                osu_file.events = Some(events);
+                osu_file.to_string()
             }
-        }
-        if let Some(difficulty) = &self.difficulty {
-            if let Some(difficulty) = difficulty.to_string(self.version) {
-                sections.push(("Difficulty", difficulty));
+            _ => self.to_string(),
+        };
+
+        match self.header() {
+            // the preserved formatting already starts with the header
+            Some(header)
+                if options.preserve_header
+                    && header.version == self.version
+                    && self.preserved_formatting().is_none() =>
+            {
+                let written = format!("osu file format v{}", self.version);
+                format!("{}{}{}", header.leading, header.line, &s[written.len()..])
             }
+            _ => s,
         }
-        if let Some(events) = &self.events {
-            if let Some(events) = events.to_string(self.version) {
-                sections.push(("Events", events));
+    }
+
+    fn to_string_with_custom_sections(
+        &self,
+        version: Version,
+        sections: &[SectionName],
+        custom_sections: bool,
+    ) -> String {
+        let mut section_strs = Vec::new();
+
+        self.for_each_section::<Infallible>(version, sections, custom_sections, |name, content| {
+            section_strs.push(format!("[{name}]\n{content}"));
+            Ok(())
+        })
+        .unwrap();
+
+        format!(
+            "osu file format v{version}\n\n{}",
+            section_strs.join("\n\n")
+        )
+    }
+
+    /// Calls `f` with the name and content of each section in the order they are written.
+    /// - Each section is only made into a string right before `f` is called with it.
+    fn for_each_section<E>(
+        &self,
+        version: Version,
+        sections: &[SectionName],
+        custom_sections: bool,
+        mut f: impl FnMut(&str, &str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let mut custom = |f: &mut dyn FnMut(&str, &str) -> Result<(), E>, after| {
+            if custom_sections {
+                for section in self.custom_sections.iter().filter(|s| s.after == after) {
+                    f(&section.name, &section.content)?;
+                }
             }
-        }
-        if let Some(timing_points) = &self.timing_points {
-            if let Some(timing_points) = timing_points.to_string(self.version) {
-                sections.push(("TimingPoints", timing_points));
+            Ok(())
+        };
+
+        custom(&mut f, None)?;
+
+        for section in SectionName::ALL {
+            if !sections.contains(&section) {
+                custom(&mut f, Some(section))?;
+                continue;
             }
-        }
-        if let Some(colours) = &self.colours {
-            if let Some(colours) = colours.to_string(self.version) {
-                sections.push(("Colours", colours));
+
+            let section_str = match section {
+                SectionName::General => self.general.as_ref().and_then(|s| s.to_string(version)),
+                SectionName::Editor => self.editor.as_ref().and_then(|s| s.to_string(version)),
+                SectionName::Metadata => self.metadata.as_ref().and_then(|s| s.to_string(version)),
+                SectionName::Difficulty => {
+                    self.difficulty.as_ref().and_then(|s| s.to_string(version))
+                }
+                // sections kept with `preserve_colour_formatting` and `preserve_events_formatting`
+                SectionName::Events => self
+                    .preserved_section_text(section, version)
+                    .or_else(|| self.events.as_ref().and_then(|s| s.to_string(version))),
+                SectionName::TimingPoints => self
+                    .timing_points
+                    .as_ref()
+                    .and_then(|s| s.to_string(version))
+                    .map(|s| with_comments(&self.section_comments.timing_points, s)),
+                SectionName::Colours => self
+                    .preserved_section_text(section, version)
+                    .or_else(|| self.colours.as_ref().and_then(|s| s.to_string(version))),
+                SectionName::HitObjects => self
+                    .hitobjects
+                    .as_ref()
+                    .and_then(|s| s.to_string(version))
+                    .map(|s| with_comments(&self.section_comments.hitobjects, s)),
+            };
+
+            if let Some(section_str) = &section_str {
+                f(section.as_str(), section_str)?;
             }
+
+            custom(&mut f, Some(section))?;
         }
-        if let Some(hitobjects) = &self.hitobjects {
-            if let Some(hitobjects) = hitobjects.to_string(self.version) {
-                sections.push(("HitObjects", hitobjects));
-            }
+
+        Ok(())
+    }
+}
+
+/// Writes the `comments` as `//` lines before the `section`.
+fn with_comments(comments: &[String], section: String) -> String {
+    comments
+        .iter()
+        .map(|comment| format!("//{comment}"))
+        .chain(Some(section).filter(|s| !s.is_empty()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `//` comment lines of sections that aren't around a timing point or hitobject, which are written at the start of the sections.
+/// - Only kept for sections without timing points or hitobjects, since comments are otherwise kept in [`TimingPoint::comments`][timingpoints::TimingPoint::comments] and [`HitObject::comments`][hitobjects::HitObject::comments].
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct SectionComments {
+    pub timing_points: Vec<String>,
+    pub hitobjects: Vec<String>,
+}
+
+/// Names of the sections in an .osu file.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum SectionName {
+    General,
+    Editor,
+    Metadata,
+    Difficulty,
+    Events,
+    TimingPoints,
+    Colours,
+    HitObjects,
+}
+
+impl SectionName {
+    /// All sections in the order they are written in an .osu file.
+    pub const ALL: [SectionName; 8] = [
+        SectionName::General,
+        SectionName::Editor,
+        SectionName::Metadata,
+        SectionName::Difficulty,
+        SectionName::Events,
+        SectionName::TimingPoints,
+        SectionName::Colours,
+        SectionName::HitObjects,
+    ];
+
+    /// Returns all sections except the ones in `excluded`.
+    pub fn all_except(excluded: &[SectionName]) -> Vec<SectionName> {
+        SectionName::ALL
+            .into_iter()
+            .filter(|s| !excluded.contains(s))
+            .collect()
+    }
+
+    /// Name of the section as written inside of the square brackets.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SectionName::General => "General",
+            SectionName::Editor => "Editor",
+            SectionName::Metadata => "Metadata",
+            SectionName::Difficulty => "Difficulty",
+            SectionName::Events => "Events",
+            SectionName::TimingPoints => "TimingPoints",
+            SectionName::Colours => "Colours",
+            SectionName::HitObjects => "HitObjects",
         }
+    }
+}
 
-        write!(
-            f,
-            "osu file format v{}\n\n{}",
-            self.version,
-            sections
-                .iter()
-                .map(|(name, content)| format!("[{name}]\n{content}"))
-                .collect::<Vec<_>>()
-                .join("\n\n")
-        )
+impl FromStr for SectionName {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SectionName::ALL
+            .into_iter()
+            .find(|section| section.as_str() == s)
+            .ok_or(ParseError::UnknownSection)
+    }
+}
+
+impl Display for OsuFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.preserved_formatting() {
+            Some(formatting) => write!(f, "{}", self.to_string_preserved(formatting)),
+            None => write!(
+                f,
+                "{}",
+                self.to_string_with_custom_sections(self.version, &SectionName::ALL, true)
+            ),
+        }
     }
 }
 
@@ -168,40 +463,48 @@ impl FromStr for OsuFile {
     type Err = Error<ParseError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let version_text = preceded(
-            alt((tag("\u{feff}"), success(""))),
-            tag::<_, _, nom::error::Error<_>>("osu file format v"),
-        );
-        let version_number = map_res(take_till(|c| c == '\r' || c == '\n'), |s: &str| s.parse());
-
-        let (s, (trailing_ws, version)) = match tuple((
-            multispace0,
-            preceded(version_text, version_number),
-        ))(s)
-        {
-            Ok(ok) => ok,
-            Err(err) => {
-                // wrong line?
-                let err = if let nom::Err::Error(err) = err {
-                    // can find out error by checking the error type
-                    match err.code {
-                        nom::error::ErrorKind::Tag => ParseError::FileVersionDefinedWrong,
-                        nom::error::ErrorKind::MapRes => ParseError::InvalidFileVersion,
-                        _ => {
-                            unreachable!("Not possible to have the error kind {:#?}", err.code)
-                        }
-                    }
-                } else {
-                    unreachable!("Not possible to reach when the errors are already handled, error type is {:#?}", err)
-                };
+        Self::from_str_with_options(s, &ParseOptions::default())
+    }
+}
 
-                return Err(err.into());
-            }
-        };
+impl OsuFile {
+    /// Parses an .osu file with the [`ParseOptions`].
+    /// - Fields written in the place older versions used, such as `EditorBookmarks` in a version 14 file, are moved to their place in the version, see [`FIELD_MIGRATIONS`].
+    pub fn from_str_with_options(
+        s: &str,
+        options: &ParseOptions,
+    ) -> Result<Self, Error<ParseError>> {
+        Self::parse_with_buffers(s, options, &mut ParseBuffers::default())
+    }
 
-        if !(MIN_VERSION..=LATEST_VERSION).contains(&version) {
-            return Err(ParseError::InvalidFileVersion.into());
-        }
+    /// Parses an .osu file with [`ParseOptions::lenient`] and [`ParseOptions::skip_malformed_lines`], returning the warnings for what was changed or skipped.
+    /// - For files with junk lines, such as a hitobject with a missing field, which would otherwise fail the whole parse.
+    /// - Fails only for problems that aren't in a single line that can be skipped, such as a missing header.
+    pub fn from_str_lenient(s: &str) -> Result<(Self, Vec<ParseWarning>), Error<ParseError>> {
+        let osu_file = Self::from_str_with_options(
+            s,
+            &ParseOptions::new().lenient(true).skip_malformed_lines(true),
+        )?;
+        let warnings = osu_file.parse_warnings.0.clone();
+
+        Ok((osu_file, warnings))
+    }
+
+    /// Parses an .osu file, using the `buffers` for the text of the sections so their capacity can be reused.
+    pub(crate) fn parse_with_buffers(
+        s: &str,
+        options: &ParseOptions,
+        buffers: &mut ParseBuffers,
+    ) -> Result<Self, Error<ParseError>> {
+        options.limits.check_input(s).map_err(Error::error_into)?;
+
+        let input = s;
+        let (s, (leading, version)) = file_version(s)?;
+        let header = FileHeader {
+            leading: leading.to_string(),
+            line: input[leading.len()..input.len() - s.len()].to_string(),
+            version,
+        };
 
         let pre_section_count = s
             .lines()
@@ -225,101 +528,490 @@ impl FromStr for OsuFile {
             return Err(Error::new(ParseError::UnexpectedLine, i));
         }
 
-        let s = s
-            .lines()
-            .skip(pre_section_count)
-            .collect::<Vec<_>>()
-            .join("\n");
-
-        let (_, sections) = many0(square_section())(&s).unwrap();
-
-        let mut section_parsed = Vec::with_capacity(8);
+        let body = &mut buffers.body;
+        body.clear();
+        for (i, line) in s.lines().skip(pre_section_count).enumerate() {
+            if i > 0 {
+                body.push('\n');
+            }
+            body.push_str(line);
+        }
 
-        let (
-            mut general,
-            mut editor,
-            mut metadata,
-            mut difficulty,
-            mut events,
-            mut timing_points,
-            mut colours,
-            mut hitobjects,
-        ) = (None, None, None, None, None, None, None, None);
+        let section_buffer = std::mem::take(&mut buffers.section);
+        let mut parser = SectionsParser::with_buffer(version, options, section_buffer);
+        let mut line_number = leading.matches('\n').count() + pre_section_count;
 
-        let mut line_number = trailing_ws.lines().count() + pre_section_count;
+        let mut rest = body.as_str();
+        while let Ok((next, (ws, section_name, ws2, section))) = square_section()(rest) {
+            if next.len() == rest.len() {
+                break;
+            }
+            rest = next;
 
-        for (ws, section_name, ws2, section) in sections {
             line_number += ws.lines().count();
 
-            if section_parsed.contains(&section_name) {
-                return Err(Error::new(ParseError::DuplicateSections, line_number));
-            }
-
             let section_name_line = line_number;
             line_number += ws2.lines().count();
 
-            match section_name {
-                "General" => {
-                    general =
-                        Error::processing_line(General::from_str(section, version), line_number)?;
-                }
-                "Editor" => {
-                    editor =
-                        Error::processing_line(Editor::from_str(section, version), line_number)?;
-                }
-                "Metadata" => {
-                    metadata =
-                        Error::processing_line(Metadata::from_str(section, version), line_number)?;
+            parser.parse(section_name, section, section_name_line, line_number)?;
+
+            line_number += section.lines().count().saturating_sub(1);
+        }
+
+        let (mut osu_file, section_buffer) = parser.finish_with_buffer();
+        buffers.section = section_buffer;
+        osu_file.header = Untracked(Some(header));
+
+        if options.preserve_formatting {
+            osu_file.keep_formatting(input.to_string(), None);
+        }
+
+        Ok(osu_file)
+    }
+}
+
+/// Parses the `osu file format v..` line, returning the rest of the input, what is before the line, and the version.
+/// - Whitespace, the BOM and other zero-width characters are allowed before the line and after the version, see [`is_header_junk`].
+fn file_version(s: &str) -> Result<(&str, (&str, Version)), Error<ParseError>> {
+    let version_text = tag::<_, _, nom::error::Error<_>>("osu file format v");
+    let version_number = map_res(take_till(|c| c == '\r' || c == '\n'), |s: &str| {
+        s.trim_end_matches(is_header_junk).parse()
+    });
+
+    let (s, (leading, version)) = match tuple((
+        take_while(is_header_junk),
+        preceded(version_text, version_number),
+    ))(s)
+    {
+        Ok(ok) => ok,
+        Err(err) => {
+            // wrong line?
+            let err = if let nom::Err::Error(err) = err {
+                // can find out error by checking the error type
+                match err.code {
+                    nom::error::ErrorKind::Tag => ParseError::FileVersionDefinedWrong,
+                    nom::error::ErrorKind::MapRes => ParseError::InvalidFileVersion,
+                    _ => {
+                        unreachable!("Not possible to have the error kind {:#?}", err.code)
+                    }
                 }
-                "Difficulty" => {
-                    difficulty = Error::processing_line(
-                        Difficulty::from_str(section, version),
-                        line_number,
-                    )?;
+            } else {
+                unreachable!("Not possible to reach when the errors are already handled, error type is {:#?}", err)
+            };
+
+            return Err(err.into());
+        }
+    };
+
+    if !(MIN_VERSION..=LATEST_VERSION).contains(&version) {
+        return Err(ParseError::InvalidFileVersion.into());
+    }
+
+    Ok((s, (leading, version)))
+}
+
+/// `key: value` fields that are decimals, which are never a comma separated list.
+const DECIMAL_FIELDS: [&str; 10] = [
+    "StackLeniency",
+    "DistanceSpacing",
+    "BeatDivisor",
+    "TimelineZoom",
+    "HPDrainRate",
+    "CircleSize",
+    "OverallDifficulty",
+    "ApproachRate",
+    "SliderMultiplier",
+    "SliderTickRate",
+];
+
+/// Replaces `,` used as the decimal separator in the [`DECIMAL_FIELDS`] with `.`, adding a warning for each field.
+/// - Only values with a single `,` between digits are changed, so `0,7` becomes `0.7`.
+fn comma_decimals(section: &str, line_number: usize, warnings: &mut Vec<ParseWarning>) -> String {
+    let is_comma_decimal = |value: &str| {
+        let value = value.strip_prefix('-').unwrap_or(value);
+
+        match value.split_once(',') {
+            Some((whole, fraction)) => [whole, fraction]
+                .iter()
+                .all(|s| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())),
+            None => false,
+        }
+    };
+
+    section
+        .lines()
+        .enumerate()
+        .map(|(i, line)| match line.split_once(':') {
+            Some((key, value))
+                if DECIMAL_FIELDS.contains(&key.trim()) && is_comma_decimal(value.trim()) =>
+            {
+                warnings.push(ParseWarning::CommaDecimalSeparator {
+                    line_index: line_number + i,
+                    field: key.trim().to_string(),
+                });
+
+                format!("{key}:{}", value.replace(',', "."))
+            }
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Drops the 4th alpha value of the colours, adding a warning for each colour.
+/// - Only values with 4 numbers are changed, and the spacing of the other numbers is kept.
+fn colour_alphas(section: &str, line_number: usize, warnings: &mut Vec<ParseWarning>) -> String {
+    let is_number = |s: &&str| {
+        let s = s.trim();
+        !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+    };
+
+    section
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let (key, value) = match line.split_once(':') {
+                Some(field) => field,
+                None => return line.to_string(),
+            };
+            let values = value.split(',').collect::<Vec<_>>();
+
+            if values.len() == 4 && values.iter().all(is_number) {
+                warnings.push(ParseWarning::ColourAlphaDropped {
+                    line_index: line_number + i,
+                });
+
+                format!("{key}:{}", values[..3].join(","))
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Changes the decimal times of storyboard commands with the `policy`, adding a warning for each time.
+/// - Only indented command lines are changed, in the start and end time fields, or the start time of loops.
+fn command_times(
+    section: &str,
+    line_number: usize,
+    policy: CommandTimePolicy,
+    warnings: &mut Vec<ParseWarning>,
+) -> String {
+    let whole_time = |time: Decimal| match policy {
+        CommandTimePolicy::Reject => None,
+        CommandTimePolicy::Truncate => time.trunc().to_i64(),
+        CommandTimePolicy::Round => time
+            .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+            .to_i64(),
+    };
+
+    section
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let command = line.trim_start_matches(|c| c == ' ' || c == '_');
+            if command.len() == line.len() {
+                return line.to_string();
+            }
+
+            let mut fields = command.split(',').map(str::to_string).collect::<Vec<_>>();
+            let time_fields = match fields[0].as_str() {
+                "L" => 1..2,
+                _ => 2..4,
+            };
+
+            for field in fields
+                .iter_mut()
+                .take(time_fields.end)
+                .skip(time_fields.start)
+            {
+                if !field.contains('.') {
+                    continue;
                 }
-                "Events" => {
-                    events =
-                        Error::processing_line(Events::from_str(section, version), line_number)?;
+
+                if let Some(time) = field.trim().parse().ok().and_then(whole_time) {
+                    warnings.push(ParseWarning::CommandTimeChanged {
+                        line_index: line_number + i,
+                        time: field.clone(),
+                    });
+                    *field = time.to_string();
                 }
-                "TimingPoints" => {
-                    timing_points = Error::processing_line(
-                        TimingPoints::from_str(section, version),
+            }
+
+            format!(
+                "{}{}",
+                &line[..line.len() - command.len()],
+                fields.join(",")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Returns `true` if the section was already parsed, by the `parsed_sections` of [`SectionsParser`] and the custom sections of `osu_file`.
+fn is_parsed(parsed_sections: &[bool], osu_file: &OsuFile, section_name: &str) -> bool {
+    match SectionName::ALL
+        .iter()
+        .position(|name| name.as_str() == section_name)
+    {
+        Some(i) => parsed_sections[i],
+        None => osu_file
+            .custom_sections
+            .iter()
+            .any(|section| section.name == section_name),
+    }
+}
+
+/// Sections written back as they were with [`ParseOptions::preserve_colour_formatting`] and [`ParseOptions::preserve_events_formatting`].
+fn preserved_sections(options: &ParseOptions) -> Vec<SectionName> {
+    let mut sections = Vec::new();
+    if options.preserve_events_formatting {
+        sections.push(SectionName::Events);
+    }
+    if options.preserve_colour_formatting {
+        sections.push(SectionName::Colours);
+    }
+
+    sections
+}
+
+/// Sections parsed so far, so the file can be parsed one section at a time.
+struct SectionsParser<'a> {
+    version: Version,
+    options: &'a ParseOptions,
+    osu_file: OsuFile,
+    /// Sections in [`SectionName::ALL`] that were parsed, where custom sections are in `osu_file`.
+    parsed_sections: [bool; SectionName::ALL.len()],
+    last_section: Option<SectionName>,
+    /// Buffer for a section changed before it's parsed, such as with [`ParseOptions::trim_fields`].
+    buffer: String,
+    /// Sections kept with [`ParseOptions::preserve_colour_formatting`] and [`ParseOptions::preserve_events_formatting`], as they were in the input.
+    preserved_source: String,
+}
+
+impl<'a> SectionsParser<'a> {
+    fn new(version: Version, options: &'a ParseOptions) -> Self {
+        Self::with_buffer(version, options, String::new())
+    }
+
+    /// Creates the parser with a `buffer` for the sections, so its capacity can be reused.
+    fn with_buffer(version: Version, options: &'a ParseOptions, buffer: String) -> Self {
+        Self {
+            version,
+            options,
+            osu_file: OsuFile::new(version),
+            parsed_sections: [false; SectionName::ALL.len()],
+            last_section: None,
+            buffer,
+            preserved_source: String::new(),
+        }
+    }
+
+    /// Parses the section.
+    /// - `section_name_line` is the line index of the section name, and `line_number` is the line index where `section` starts.
+    fn parse(
+        &mut self,
+        section_name: &str,
+        section: &str,
+        section_name_line: usize,
+        mut line_number: usize,
+    ) -> Result<(), Error<ParseError>> {
+        let (version, options) = (self.version, self.options);
+        let osu_file = &mut self.osu_file;
+        let original_section = section;
+
+        let known_fields;
+        let section = match section_name {
+            "General" | "Editor" | "Metadata" | "Difficulty" if options.preserve_formatting => {
+                known_fields = unknown_fields_removed(section_name, section);
+                let known = known_fields.trim_start_matches('\n');
+                // the fields have to start on the first line
+                line_number += known_fields.len() - known.len();
+                known
+            }
+            _ => section,
+        };
+
+        let trimmed;
+        let section = match section_name {
+            "Colours" if options.lenient => {
+                trimmed = if options.trim_fields {
+                    trim_fields_into(section, &mut self.buffer);
+                    colour_alphas(&self.buffer, line_number, &mut osu_file.parse_warnings.0)
+                } else {
+                    colour_alphas(section, line_number, &mut osu_file.parse_warnings.0)
+                };
+                trimmed.as_str()
+            }
+            "TimingPoints" | "Colours" if options.trim_fields => {
+                trim_fields_into(section, &mut self.buffer);
+                self.buffer.as_str()
+            }
+            "HitObjects" if options.trim_fields => {
+                trim_hitobject_fields_into(section, &mut self.buffer);
+                self.buffer.as_str()
+            }
+            "General" | "Editor" | "Difficulty" if options.lenient => {
+                trimmed = comma_decimals(section, line_number, &mut osu_file.parse_warnings.0);
+                trimmed.as_str()
+            }
+            "Events" if options.command_time_policy != CommandTimePolicy::Reject => {
+                trimmed = command_times(
+                    section,
+                    line_number,
+                    options.command_time_policy,
+                    &mut osu_file.parse_warnings.0,
+                );
+                trimmed.as_str()
+            }
+            _ => section,
+        };
+
+        if is_parsed(&self.parsed_sections, osu_file, section_name) {
+            return Err(Error::new(ParseError::DuplicateSections, section_name_line));
+        }
+
+        if let "Events" | "TimingPoints" | "HitObjects" = section_name {
+            Error::processing_line(options.limits.check_objects(section), line_number)?;
+        }
+
+        if let Ok(name) = section_name.parse() {
+            if preserved_sections(options).contains(&name) {
+                self.preserved_source.push_str(&format!(
+                    "[{section_name}]\n{}\n\n",
+                    original_section.trim_end()
+                ));
+            }
+        }
+
+        match section_name {
+            "General" => {
+                let general = if options.lenient {
+                    General::from_str_lenient_with_warnings(
+                        section,
+                        version,
                         line_number,
-                    )?;
-                }
-                "Colours" => {
-                    colours =
-                        Error::processing_line(Colours::from_str(section, version), line_number)?;
+                        &mut osu_file.parse_warnings.0,
+                    )
+                } else {
+                    General::from_str(section, version)
+                };
+                osu_file.general = Error::processing_line(general, line_number)?;
+            }
+            "Editor" => {
+                osu_file.editor =
+                    Error::processing_line(Editor::from_str(section, version), line_number)?;
+            }
+            "Metadata" => {
+                osu_file.metadata =
+                    Error::processing_line(Metadata::from_str(section, version), line_number)?;
+            }
+            "Difficulty" => {
+                osu_file.difficulty =
+                    Error::processing_line(Difficulty::from_str(section, version), line_number)?;
+            }
+            "Events" => {
+                osu_file.events =
+                    Error::processing_line(Events::from_str(section, version), line_number)?;
+
+                if options.dedup_storyboard {
+                    osu_file.storyboard_dedup.0 = osu_file
+                        .events
+                        .as_mut()
+                        .map(|events| events.dedup_objects(version));
                 }
-                "HitObjects" => {
-                    hitobjects = Error::processing_line(
-                        HitObjects::from_str(section, version),
+            }
+            "TimingPoints" => {
+                let warnings = options
+                    .skip_malformed_lines
+                    .then_some(&mut osu_file.parse_warnings.0);
+                let timing_points = TimingPoints::from_str_skipping_malformed(
+                    section,
+                    version,
+                    line_number,
+                    warnings,
+                    &mut osu_file.section_comments.timing_points,
+                );
+                osu_file.timing_points = Error::processing_line(timing_points, line_number)?;
+            }
+            "Colours" => {
+                let warnings = options
+                    .skip_malformed_lines
+                    .then_some(&mut osu_file.parse_warnings.0);
+                let colours =
+                    Colours::from_str_skipping_malformed(section, version, line_number, warnings);
+                osu_file.colours = Error::processing_line(colours, line_number)?;
+            }
+            "HitObjects" => {
+                let warnings = options
+                    .skip_malformed_lines
+                    .then_some(&mut osu_file.parse_warnings.0);
+                let hitobjects = HitObjects::from_str_skipping_malformed(
+                    section,
+                    version,
+                    line_number,
+                    warnings,
+                    &mut osu_file.section_comments.hitobjects,
+                );
+                osu_file.hitobjects = Error::processing_line(hitobjects, line_number)?;
+            }
+            _ => {
+                match options.sections.parse(section_name, section, version) {
+                    Some(result) => Error::processing_line::<_, ParseError>(
+                        result.map_err(|err| {
+                            err.map_error(|source| ParseError::ParseCustomSectionError {
+                                name: section_name.to_string(),
+                                source,
+                            })
+                        }),
                         line_number,
-                    )?;
+                    )?,
+                    None if options.keep_unknown_sections || options.preserve_formatting => (),
+                    None => return Err(Error::new(ParseError::UnknownSection, section_name_line)),
                 }
-                _ => return Err(Error::new(ParseError::UnknownSection, section_name_line)),
+
+                osu_file.custom_sections.push(RawSection {
+                    name: section_name.to_string(),
+                    content: section.trim_end().to_string(),
+                    after: self.last_section,
+                });
             }
+        }
 
-            section_parsed.push(section_name);
-            line_number += section.lines().count() - 1;
+        if let Ok(section_name) = section_name.parse::<SectionName>() {
+            self.last_section = Some(section_name);
+            if let Some(i) = SectionName::ALL
+                .iter()
+                .position(|name| *name == section_name)
+            {
+                self.parsed_sections[i] = true;
+            }
         }
 
-        Ok(OsuFile {
-            version,
-            general,
-            editor,
-            metadata,
-            difficulty,
-            events,
-            timing_points,
-            colours,
-            hitobjects,
-            osb: None,
-        })
+        Ok(())
+    }
+
+    fn finish(self) -> OsuFile {
+        self.finish_with_buffer().0
+    }
+
+    /// Finishes the file, returning the buffer so it can be used for the next file.
+    fn finish_with_buffer(mut self) -> (OsuFile, String) {
+        self.osu_file.migrate_parsed_fields();
+        self.osu_file.mark_clean();
+
+        let sections = preserved_sections(self.options);
+        if !sections.is_empty() {
+            self.osu_file
+                .keep_formatting(std::mem::take(&mut self.preserved_source), Some(sections));
+        }
+
+        (self.osu_file, self.buffer)
     }
 }
 
-#[derive(Debug, Error)]
+#[derive(Debug, Error, IntoStaticStr)]
 #[non_exhaustive]
 /// Error for when there's a problem parsing an .osu file.
 pub enum ParseError {
@@ -395,4 +1087,19 @@ pub enum ParseError {
         #[from]
         source: hitobjects::ParseError,
     },
+    /// Error parsing a section registered in [`ParseOptions::sections`].
+    #[error("Failed to parse the section `{name}`")]
+    ParseCustomSectionError {
+        name: String,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// The input goes over one of the [`ParseOptions::limits`].
+    #[error(transparent)]
+    LimitsExceeded(#[from] LimitsExceeded),
+    /// Error reading the input in [`OsuFile::from_reader`].
+    #[error("Failed to read the input")]
+    ReadError {
+        #[from]
+        source: std::io::Error,
+    },
 }
@@ -0,0 +1,421 @@
+//! Module for options that changes how an .osu file is parsed, written and edited.
+
+use strum_macros::IntoStaticStr;
+use thiserror::Error;
+
+use super::{types::Error as LineError, Section, SectionRegistry};
+
+/// Options used by [`OsuFile::from_str_with_options`][super::OsuFile::from_str_with_options].
+/// - The [`Default`] options parse the same way as [`FromStr`][std::str::FromStr].
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// Trims spaces and tabs around each field before parsing in the `[TimingPoints]`, `[Colours]` and `[HitObjects]` sections.
+    /// - Fields are split by `,`, `|` and `:`, so `256, 192 ,1000` is parsed as `256,192,1000`.
+    /// - Hitsample file names in the `[HitObjects]` section aren't trimmed, since they can start or end with spaces.
+    /// - Sections are written back without the extra whitespace, unless [`preserve_formatting`][Self::preserve_formatting] is also set, which writes the lines that are unchanged with their original spacing.
+    pub trim_fields: bool,
+    /// Sections that are parsed and kept in [`OsuFile::custom_sections`][super::OsuFile::custom_sections] instead of being an unknown section error.
+    pub sections: SectionRegistry,
+    /// Keeps sections that aren't registered in [`sections`][Self::sections] in [`OsuFile::custom_sections`][super::OsuFile::custom_sections] without parsing them.
+    /// - Unknown sections are an error if this is `false`.
+    pub keep_unknown_sections: bool,
+    /// Limits on the size of the input, checked before parsing so large inputs are refused early.
+    pub limits: ParseLimits,
+    /// Keeps values that aren't known instead of failing, so they are written back the same.
+    /// - `Countdown` numbers other than `0` to `3` are kept as [`Countdown::Other`][super::general::Countdown::Other].
+    /// - `Mode` numbers other than `0` to `3` are kept as [`Mode::Other`][super::general::Mode::Other], and unknown `OverlayPosition` names as [`OverlayPosition::Other`][super::general::OverlayPosition::Other].
+    /// - Decimal fields in the `[General]`, `[Editor]` and `[Difficulty]` sections can use `,` as the decimal separator, such as `0,7`, which is written back with `.`.
+    /// - Colours in the `[Colours]` section can have a 4th alpha value, such as `255,128,255,200`, which is dropped.
+    /// - Values that had to be changed to be parsed are reported in [`OsuFile::parse_warnings`][super::OsuFile::parse_warnings].
+    pub lenient: bool,
+    /// Skips the lines in the `[TimingPoints]`, `[Colours]` and `[HitObjects]` sections that fail to parse, instead of failing.
+    /// - Skipped lines are reported in [`OsuFile::parse_warnings`][super::OsuFile::parse_warnings], and aren't written back.
+    pub skip_malformed_lines: bool,
+    /// Keeps the original text of the `[Colours]` section, which is written back as is while the colours are unchanged.
+    /// - Keeps spacing such as `Combo1 : 255, 128,255` and alpha values dropped by [`lenient`][Self::lenient].
+    /// - Only used when writing in the version the file was parsed in.
+    pub preserve_colour_formatting: bool,
+    /// Merges storyboard sprites in the `[Events]` section with [`Events::dedup_objects`][super::Events::dedup_objects].
+    /// - What was saved is in [`OsuFile::storyboard_dedup_report`][super::OsuFile::storyboard_dedup_report].
+    pub dedup_storyboard: bool,
+    /// How decimal times of storyboard commands are parsed, which are an error by default.
+    /// - Times that had to be changed are reported in [`OsuFile::parse_warnings`][super::OsuFile::parse_warnings].
+    pub command_time_policy: CommandTimePolicy,
+    /// Keeps the original text of the `[Events]` section, which is written back as is while the events are unchanged.
+    /// - Keeps decimal command times changed by [`command_time_policy`][Self::command_time_policy].
+    /// - Only used when writing in the version the file was parsed in.
+    pub preserve_events_formatting: bool,
+    /// Keeps the whole input, so [`Display`][std::fmt::Display] writes the lines that are unchanged since parsing as they were.
+    /// - Keeps whitespace, blank lines, comments, the order of fields and fields that aren't known, which are otherwise an error.
+    /// - Only the lines of changed fields, timing points and hitobjects are written again, and changed `[Events]` and `[Colours]` sections are written whole.
+    /// - Comment lines in `[TimingPoints]` and `[HitObjects]` are kept where they are.
+    /// - Keeps sections that aren't known the same as [`keep_unknown_sections`][Self::keep_unknown_sections].
+    /// - Only used when writing in the version the file was parsed in.
+    /// - The input isn't kept by [`OsuFile::from_reader_with_options`][super::OsuFile::from_reader_with_options], where fields that aren't known are dropped.
+    pub preserve_formatting: bool,
+}
+
+impl ParseOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets [`trim_fields`][Self::trim_fields].
+    pub fn trim_fields(mut self, trim_fields: bool) -> Self {
+        self.trim_fields = trim_fields;
+        self
+    }
+
+    /// Registers the section `S` in [`sections`][Self::sections].
+    pub fn register_section<S>(mut self) -> Self
+    where
+        S: Section,
+        S::ParseError: std::error::Error + Send + Sync + 'static,
+    {
+        self.sections.register::<S>();
+        self
+    }
+
+    /// Sets [`keep_unknown_sections`][Self::keep_unknown_sections].
+    pub fn keep_unknown_sections(mut self, keep_unknown_sections: bool) -> Self {
+        self.keep_unknown_sections = keep_unknown_sections;
+        self
+    }
+
+    /// Sets [`limits`][Self::limits].
+    pub fn limits(mut self, limits: ParseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets [`lenient`][Self::lenient].
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Sets [`skip_malformed_lines`][Self::skip_malformed_lines].
+    pub fn skip_malformed_lines(mut self, skip_malformed_lines: bool) -> Self {
+        self.skip_malformed_lines = skip_malformed_lines;
+        self
+    }
+
+    /// Sets [`preserve_colour_formatting`][Self::preserve_colour_formatting].
+    pub fn preserve_colour_formatting(mut self, preserve_colour_formatting: bool) -> Self {
+        self.preserve_colour_formatting = preserve_colour_formatting;
+        self
+    }
+
+    /// Sets [`dedup_storyboard`][Self::dedup_storyboard].
+    pub fn dedup_storyboard(mut self, dedup_storyboard: bool) -> Self {
+        self.dedup_storyboard = dedup_storyboard;
+        self
+    }
+
+    /// Sets [`command_time_policy`][Self::command_time_policy].
+    pub fn command_time_policy(mut self, command_time_policy: CommandTimePolicy) -> Self {
+        self.command_time_policy = command_time_policy;
+        self
+    }
+
+    /// Sets [`preserve_events_formatting`][Self::preserve_events_formatting].
+    pub fn preserve_events_formatting(mut self, preserve_events_formatting: bool) -> Self {
+        self.preserve_events_formatting = preserve_events_formatting;
+        self
+    }
+
+    /// Sets [`preserve_formatting`][Self::preserve_formatting].
+    pub fn preserve_formatting(mut self, preserve_formatting: bool) -> Self {
+        self.preserve_formatting = preserve_formatting;
+        self
+    }
+}
+
+/// Options used by [`OsuFile::to_string_with_options`][super::OsuFile::to_string_with_options].
+/// - The [`Default`] options write the same way as [`Display`][std::fmt::Display].
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WriteOptions {
+    /// Writes the origin and layer of storyboard objects by name with `Some(true)`, such as `Centre`, or as numbers with `Some(false)`, such as `1`.
+    /// - With `None` they are written the same way as they were parsed.
+    /// - Undocumented origins are always written as numbers.
+    pub prefer_named_enums: Option<bool>,
+    /// Writes the headers of videos and breaks as numbers with `Some(true)`, such as `1`, or by name with `Some(false)`, such as `Video`.
+    /// - With `None` they are written the same way as they were parsed.
+    pub prefer_numeric_event_headers: Option<bool>,
+    /// Writes the header as it was parsed, with the BOM, zero-width characters and spacing around it, see [`OsuFile::header`][super::OsuFile::header].
+    /// - Only used when writing in the version the file was parsed in.
+    pub preserve_header: bool,
+    /// Writes the position of backgrounds and videos that were parsed without one as `0,0`, like the new editors do.
+    /// - If `false`, the position is only written if it was in the parsed file, see [`Background::position`][super::events::Background::position].
+    pub always_write_event_position: bool,
+    /// Writes the file names of backgrounds wrapped in double quotes in version 14 files, like osu! does.
+    /// - Older versions and `false` write the file names the same way as they were parsed, see [`FilePath::is_quoted`][super::FilePath::is_quoted].
+    pub quote_background_file_names: bool,
+}
+
+impl WriteOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets [`prefer_named_enums`][Self::prefer_named_enums].
+    pub fn prefer_named_enums(mut self, prefer_named_enums: bool) -> Self {
+        self.prefer_named_enums = Some(prefer_named_enums);
+        self
+    }
+
+    /// Sets [`prefer_numeric_event_headers`][Self::prefer_numeric_event_headers].
+    pub fn prefer_numeric_event_headers(mut self, prefer_numeric_event_headers: bool) -> Self {
+        self.prefer_numeric_event_headers = Some(prefer_numeric_event_headers);
+        self
+    }
+
+    /// Sets [`preserve_header`][Self::preserve_header].
+    pub fn preserve_header(mut self, preserve_header: bool) -> Self {
+        self.preserve_header = preserve_header;
+        self
+    }
+
+    /// Sets [`always_write_event_position`][Self::always_write_event_position].
+    pub fn always_write_event_position(mut self, always_write_event_position: bool) -> Self {
+        self.always_write_event_position = always_write_event_position;
+        self
+    }
+
+    /// Sets [`quote_background_file_names`][Self::quote_background_file_names].
+    pub fn quote_background_file_names(mut self, quote_background_file_names: bool) -> Self {
+        self.quote_background_file_names = quote_background_file_names;
+        self
+    }
+}
+
+/// Rules checked by [`HitObjects::try_insert`][super::HitObjects::try_insert] and [`TimingPoints::try_insert`][super::TimingPoints::try_insert].
+/// - The [`Default`] options only allow inserting in chronological order.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct InsertOptions {
+    /// Milliseconds that an inserted object can be before the last one, for files that are only roughly in order.
+    pub time_tolerance: u32,
+    /// Number of osu!mania keys, where hitobjects have to be in one of the columns.
+    /// - With `None` the position isn't checked.
+    pub key_count: Option<u8>,
+}
+
+impl InsertOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Options for inserting into `osu_file`, which checks the columns with the key count if it's an osu!mania beatmap.
+    pub fn for_file(osu_file: &super::OsuFile) -> Self {
+        let key_count = match (osu_file.mode_or_default(), &osu_file.difficulty) {
+            (mode @ super::general::Mode::Mania, Some(difficulty)) => {
+                match difficulty.key_count(mode) {
+                    super::difficulty::CircleSizeValue::Keys(keys) => Some(keys),
+                    super::difficulty::CircleSizeValue::CircleSize(_) => None,
+                }
+            }
+            _ => None,
+        };
+
+        Self {
+            key_count,
+            ..Self::default()
+        }
+    }
+
+    /// Sets [`time_tolerance`][Self::time_tolerance].
+    pub fn time_tolerance(mut self, time_tolerance: u32) -> Self {
+        self.time_tolerance = time_tolerance;
+        self
+    }
+
+    /// Sets [`key_count`][Self::key_count].
+    pub fn key_count(mut self, key_count: u8) -> Self {
+        self.key_count = Some(key_count);
+        self
+    }
+}
+
+/// How decimal times of storyboard commands, such as `_F,0,1000.5,2000,0,1`, are parsed.
+/// - osu!stable only reads whole milliseconds, but some storyboard tools write decimals.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CommandTimePolicy {
+    /// Decimal times are an error.
+    #[default]
+    Reject,
+    /// The fractional part is dropped, so `1000.7` is read as `1000`.
+    Truncate,
+    /// Rounded to the nearest millisecond, with halves rounded away from zero.
+    Round,
+}
+
+/// Problem that was worked around while parsing with [`ParseOptions::lenient`], [`ParseOptions::skip_malformed_lines`] or [`ParseOptions::command_time_policy`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub enum ParseWarning {
+    /// Decimal field used `,` as the decimal separator, which was read as `.`.
+    CommaDecimalSeparator {
+        /// Line index of the field.
+        line_index: usize,
+        /// Name of the field, such as `SliderMultiplier`.
+        field: String,
+    },
+    /// Field in the `[General]` section had a value that isn't known, which was kept as the `Other` variant.
+    UnknownValue {
+        /// Line index of the field.
+        line_index: usize,
+        /// Name of the field, such as `Mode`.
+        field: String,
+        /// Value as written in the file.
+        value: String,
+    },
+    /// Storyboard command time was a decimal, which was changed with [`ParseOptions::command_time_policy`].
+    CommandTimeChanged {
+        /// Line index of the command.
+        line_index: usize,
+        /// Time as written in the file.
+        time: String,
+    },
+    /// Colour had a 4th alpha value, which was dropped since osu! doesn't use it in any version.
+    ColourAlphaDropped {
+        /// Line index of the colour.
+        line_index: usize,
+    },
+    /// Line failed to parse and was skipped with [`ParseOptions::skip_malformed_lines`].
+    MalformedLineSkipped {
+        /// Line index of the skipped line.
+        line_index: usize,
+        /// The line as written in the file.
+        line: String,
+        /// Message of the error the line failed with.
+        message: String,
+    },
+}
+
+impl ParseWarning {
+    /// [`MalformedLineSkipped`][Self::MalformedLineSkipped] for the `line` that failed with `err`, in a section that starts at `line_number`.
+    pub(crate) fn malformed_line<E: std::fmt::Display>(
+        line_number: usize,
+        line: &str,
+        err: &LineError<E>,
+    ) -> Self {
+        ParseWarning::MalformedLineSkipped {
+            line_index: line_number + err.line_index(),
+            line: line.to_string(),
+            message: err.error().to_string(),
+        }
+    }
+}
+
+/// Limits used by [`ParseOptions::limits`], for parsing files from untrusted sources.
+/// - `None` is no limit, which is the default for all of them.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParseLimits {
+    /// Maximum size of the input in bytes.
+    pub max_input_bytes: Option<usize>,
+    /// Maximum length of a line in bytes.
+    pub max_line_length: Option<usize>,
+    /// Maximum number of entries in each of the `[Events]`, `[TimingPoints]` and `[HitObjects]` sections.
+    pub max_objects: Option<usize>,
+}
+
+impl ParseLimits {
+    /// Creates the limits without any limit set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets [`max_input_bytes`][Self::max_input_bytes].
+    pub fn max_input_bytes(mut self, max_input_bytes: usize) -> Self {
+        self.max_input_bytes = Some(max_input_bytes);
+        self
+    }
+
+    /// Sets [`max_line_length`][Self::max_line_length].
+    pub fn max_line_length(mut self, max_line_length: usize) -> Self {
+        self.max_line_length = Some(max_line_length);
+        self
+    }
+
+    /// Sets [`max_objects`][Self::max_objects].
+    pub fn max_objects(mut self, max_objects: usize) -> Self {
+        self.max_objects = Some(max_objects);
+        self
+    }
+
+    /// Checks the size of the input and the length of each line.
+    pub(crate) fn check_input(&self, s: &str) -> Result<(), LineError<LimitsExceeded>> {
+        if let Some(limit) = self.max_input_bytes {
+            if s.len() > limit {
+                return Err(LineError::new(
+                    LimitsExceeded::InputBytes {
+                        limit,
+                        size: s.len(),
+                    },
+                    0,
+                ));
+            }
+        }
+
+        if let Some(limit) = self.max_line_length {
+            if let Some((line_index, line)) =
+                s.lines().enumerate().find(|(_, line)| line.len() > limit)
+            {
+                return Err(LineError::new(
+                    LimitsExceeded::LineLength {
+                        limit,
+                        length: line.len(),
+                    },
+                    line_index,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks the number of entries in the section, returning the index of the first line over the limit.
+    pub(crate) fn check_objects(&self, section: &str) -> Result<(), LineError<LimitsExceeded>> {
+        let limit = match self.max_objects {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+
+        match section
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .nth(limit)
+        {
+            Some((line_index, _)) => Err(LineError::new(
+                LimitsExceeded::Objects { limit },
+                line_index,
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Error used when the input goes over one of the [`ParseLimits`].
+#[derive(Debug, Error, IntoStaticStr)]
+#[non_exhaustive]
+pub enum LimitsExceeded {
+    /// The input is larger than [`ParseLimits::max_input_bytes`].
+    #[error("The input is {size} bytes, which is over the limit of {limit} bytes")]
+    InputBytes { limit: usize, size: usize },
+    /// A line is longer than [`ParseLimits::max_line_length`].
+    #[error("The line is {length} bytes long, which is over the limit of {limit} bytes")]
+    LineLength { limit: usize, length: usize },
+    /// A section has more entries than [`ParseLimits::max_objects`].
+    #[error("The section has more than {limit} entries")]
+    Objects { limit: usize },
+}
@@ -1,21 +1,55 @@
 pub mod error;
 pub mod types;
 
+use std::str::FromStr;
+
 use nom::multi::many0;
 
 use crate::parsers::square_section;
 
-use super::{Error, Events, Version, VersionedFromStr, VersionedToString};
+use super::{Error, Events, Version, VersionedFromStr, VersionedToString, LATEST_VERSION};
 
 pub use error::*;
 pub use types::*;
 
+/// An .osb storyboard file, which is shared by all difficulties of a mapset.
+/// - A standalone .osb file has no version, so [`FromStr`] and [`to_osb_string`][Self::to_osb_string] use the [`LATEST_VERSION`].
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Osb {
+    /// Variables from the `[Variables]` section, which are replaced in the events when parsing and put back when writing.
     pub variables: Option<Vec<Variable>>,
     pub events: Option<Events>,
 }
 
+impl Osb {
+    /// Events of the .osb file followed by the `events` of a difficulty, which is the order the game loads them in.
+    /// - Elements of the .osb file are drawn below the elements of the difficulty in the same layer, see [`CombinedStoryboard`][crate::mapset::CombinedStoryboard].
+    pub fn merge_events(&self, events: Option<&Events>) -> Events {
+        let mut merged = self.events.clone().unwrap_or_default();
+        merged.0.extend(
+            events
+                .into_iter()
+                .flat_map(|events| events.0.iter().cloned()),
+        );
+        merged
+    }
+
+    /// Writes the standalone .osb file, in the [`LATEST_VERSION`].
+    pub fn to_osb_string(&self) -> String {
+        VersionedToString::to_string(self, LATEST_VERSION).unwrap()
+    }
+}
+
+impl FromStr for Osb {
+    type Err = Error<ParseError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // only `None` for versions before storyboards
+        <Osb as VersionedFromStr>::from_str(s, LATEST_VERSION).map(Option::unwrap)
+    }
+}
+
 impl VersionedFromStr for Osb {
     type Err = Error<ParseError>;
 
@@ -9,6 +9,7 @@ use super::*;
 use crate::osu_file::VersionedFromStr;
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Variable {
     pub name: String,
     pub value: String,
@@ -0,0 +1,47 @@
+//! Module for parsing many .osu files in a row, such as when scanning a corpus of beatmaps.
+
+use super::{Error, OsuFile, ParseError, ParseOptions};
+
+/// Parses .osu files with the same [`ParseOptions`], reusing buffers between the files.
+/// - Reuses the buffer the sections are read from, and the buffer for sections changed before they are parsed, such as with [`ParseOptions::trim_fields`].
+/// - The buffers keep the capacity needed by the largest file parsed so far.
+/// - The parsed values, such as the hitobjects, are allocated for each file since they are returned.
+#[derive(Clone, Debug, Default)]
+pub struct Parser {
+    options: ParseOptions,
+    buffers: ParseBuffers,
+}
+
+/// Buffers reused by [`Parser`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ParseBuffers {
+    /// Sections of the file, without the header.
+    pub body: String,
+    /// Section changed before it's parsed.
+    pub section: String,
+}
+
+impl Parser {
+    /// Creates a parser with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a parser with the [`ParseOptions`].
+    pub fn with_options(options: ParseOptions) -> Self {
+        Self {
+            options,
+            buffers: ParseBuffers::default(),
+        }
+    }
+
+    /// Options used for every file.
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    /// Parses an .osu file, the same as [`OsuFile::from_str_with_options`].
+    pub fn parse(&mut self, s: &str) -> Result<OsuFile, Error<ParseError>> {
+        OsuFile::parse_with_buffers(s, &self.options, &mut self.buffers)
+    }
+}
@@ -0,0 +1,594 @@
+//! Module for writing an .osu file back the same way it was parsed with [`ParseOptions::preserve_formatting`][super::ParseOptions::preserve_formatting].
+//! - Also writes the sections kept with [`ParseOptions::preserve_colour_formatting`][super::ParseOptions::preserve_colour_formatting] and [`ParseOptions::preserve_events_formatting`][super::ParseOptions::preserve_events_formatting].
+//! - Only hashes of the parsed sections are kept to find what changed, instead of a copy of the file.
+
+use std::collections::hash_map::DefaultHasher;
+use std::convert::Infallible;
+use std::hash::{Hash, Hasher};
+
+use super::{
+    difficulty, editor, general, metadata, with_comments, FieldValue, OsuFile, SectionName,
+    Version, VersionedToString,
+};
+
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn section_index(section: SectionName) -> usize {
+    SectionName::ALL
+        .iter()
+        .position(|name| *name == section)
+        .unwrap()
+}
+
+/// Hashes of a section right after parsing.
+#[derive(Clone, Debug, Default)]
+struct ParsedSection {
+    /// Hash of the section, or `None` if it has to be written again even if it's unchanged.
+    whole: Option<u64>,
+    /// Hash of each field of a `key: value` section, by its name.
+    fields: Vec<(&'static str, u64)>,
+    /// Hash of each item of a section with an item on each line.
+    items: Vec<u64>,
+}
+
+/// Input from parsing with [`ParseOptions::preserve_formatting`][super::ParseOptions::preserve_formatting], [`ParseOptions::preserve_colour_formatting`][super::ParseOptions::preserve_colour_formatting] or [`ParseOptions::preserve_events_formatting`][super::ParseOptions::preserve_events_formatting].
+#[derive(Clone, Debug)]
+pub(crate) struct Formatting {
+    /// The input as it was parsed, or only the kept sections of it.
+    source: String,
+    /// Version the file was parsed in, the source is only used when writing in it.
+    version: Version,
+    /// Sections written from the source while they are unchanged, or `None` for the whole file.
+    sections: Option<Vec<SectionName>>,
+    /// Hashes of the sections in the order of [`SectionName::ALL`].
+    parsed: Vec<ParsedSection>,
+    /// Hashes of the custom sections, by their name.
+    custom: Vec<(String, u64)>,
+}
+
+impl Formatting {
+    fn new(source: String, osu_file: &OsuFile, sections: Option<Vec<SectionName>>) -> Self {
+        // fields and items are only matched up when writing the whole file
+        let whole_file = sections.is_none();
+        let parsed = SectionName::ALL
+            .into_iter()
+            .map(|section| ParsedSection {
+                whole: Some(osu_file.section_hash(section)),
+                fields: if whole_file {
+                    osu_file.field_hashes(section)
+                } else {
+                    Vec::new()
+                },
+                items: if whole_file {
+                    osu_file.item_hashes(section)
+                } else {
+                    Vec::new()
+                },
+            })
+            .collect();
+        let custom = osu_file
+            .custom_sections
+            .iter()
+            .map(|section| (section.name.clone(), hash_of(&section.content)))
+            .collect();
+
+        Self {
+            source,
+            version: osu_file.version,
+            sections,
+            parsed,
+            custom,
+        }
+    }
+
+    fn parsed(&self, section: SectionName) -> &ParsedSection {
+        &self.parsed[section_index(section)]
+    }
+}
+
+/// Replaces the lines of fields that aren't known in a `key: value` section with empty lines, so the line numbers stay the same.
+/// - The lines are still written back from the source.
+pub(crate) fn unknown_fields_removed(section_name: &str, section: &str) -> String {
+    let field_names = match section_name {
+        "General" => general::General::FIELD_NAMES,
+        "Editor" => editor::Editor::FIELD_NAMES,
+        "Metadata" => metadata::Metadata::FIELD_NAMES,
+        "Difficulty" => difficulty::Difficulty::FIELD_NAMES,
+        _ => return section.to_string(),
+    };
+
+    section
+        .split('\n')
+        .map(|line| match line.split_once(':') {
+            Some((name, _)) if !field_names.contains(&name.trim()) => "",
+            _ => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A section in the source, with the byte ranges of its `[Name]` line and its content.
+struct SourceSection<'a> {
+    name: &'a str,
+    start: usize,
+    content: usize,
+    end: usize,
+}
+
+/// Finds the sections in the source, which start at lines starting with `[`.
+fn source_sections(source: &str) -> Vec<SourceSection<'_>> {
+    let mut sections: Vec<SourceSection> = Vec::new();
+    let mut offset = 0;
+
+    for line in source.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+
+        let name = match line.strip_prefix('[').and_then(|s| s.split_once(']')) {
+            Some((name, _)) => name,
+            None => continue,
+        };
+
+        if let Some(last) = sections.last_mut() {
+            last.end = start;
+        }
+        sections.push(SourceSection {
+            name,
+            start,
+            content: offset,
+            end: source.len(),
+        });
+    }
+
+    sections
+}
+
+/// Splits a line into the text and the line ending.
+fn split_line_ending(line: &str) -> (&str, &str) {
+    let text = line.trim_end_matches(|c| c == '\r' || c == '\n');
+    (text, &line[text.len()..])
+}
+
+/// Pushes `line` on a new line of `out`.
+fn push_line(out: &mut String, line: &str, newline: &str) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push_str(newline);
+    }
+    out.push_str(line);
+}
+
+/// Rewrites the lines of the `key: value` fields that changed, keeping the other lines as they are.
+/// - `written` is the section written by the crate, which has a line for each field that is set.
+/// - Fields that were removed are dropped, and fields that were added are written after the last line.
+fn merge_fields(
+    body: &str,
+    written: &str,
+    field_names: &[&str],
+    changed: impl Fn(&str) -> bool,
+    newline: &str,
+) -> String {
+    let written = written
+        .lines()
+        .filter_map(|line| Some((line.split_once(':')?.0.trim(), line)))
+        .collect::<Vec<_>>();
+
+    let mut out = String::new();
+    let mut seen = Vec::new();
+
+    for line in body.split_inclusive('\n') {
+        let (text, ending) = split_line_ending(line);
+        let name = text
+            .split_once(':')
+            .map(|(name, _)| name.trim())
+            .filter(|name| field_names.contains(name));
+
+        match name {
+            Some(name) if changed(name) => {
+                seen.push(name);
+                if let Some((_, new_line)) = written.iter().find(|(n, _)| *n == name) {
+                    out.push_str(new_line);
+                    out.push_str(ending);
+                }
+            }
+            Some(name) => {
+                seen.push(name);
+                out.push_str(line);
+            }
+            None => out.push_str(line),
+        }
+    }
+
+    for (name, line) in &written {
+        if !seen.contains(name) {
+            push_line(&mut out, line, newline);
+        }
+    }
+
+    out.trim_end_matches(|c| c == '\r' || c == '\n').to_string()
+}
+
+/// Returns `true` if the line is a `//` comment, which isn't an item in a section with an item on each line.
+fn is_comment(line: &str) -> bool {
+    line.trim_start().starts_with("//")
+}
+
+/// Rewrites the lines of the items that changed in a section with an item on each line, keeping the other lines as they are.
+/// - Items are matched in order with the lines that aren't blank or comments by their hashes, so adding, removing or moving an item doesn't rewrite the lines after it.
+/// - Items that don't match a line take the place of the next line that wasn't matched, as changed items do, or are written before the next matched line, and items added at the end are written after the last line.
+/// - `items` and `parsed` are the hashes of the items now and after parsing.
+/// - Comment lines are kept from `body`, so the comments written with the items are skipped.
+/// - Returns `None` if the lines can't be matched with the items.
+fn merge_items(
+    body: &str,
+    written: &str,
+    items: &[u64],
+    parsed: &[u64],
+    newline: &str,
+) -> Option<String> {
+    let written = written
+        .lines()
+        .filter(|line| !is_comment(line))
+        .collect::<Vec<_>>();
+    let line_count = body
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !is_comment(line))
+        .count();
+    if written.len() != items.len() || line_count != parsed.len() {
+        return None;
+    }
+
+    // item on each line, matched with the first line after the line of the previous match
+    let mut line_items = vec![None; parsed.len()];
+    let mut matched = vec![false; items.len()];
+    let mut next = 0;
+    for (i, item) in items.iter().enumerate() {
+        if let Some(offset) = parsed[next..].iter().position(|parsed| parsed == item) {
+            line_items[next + offset] = Some(i);
+            matched[i] = true;
+            next += offset + 1;
+        }
+    }
+
+    let mut out = String::new();
+    let mut index = 0;
+    // next item to write
+    let mut item = 0;
+
+    for line in body.split_inclusive('\n') {
+        if line.trim().is_empty() || is_comment(line) {
+            out.push_str(line);
+            continue;
+        }
+
+        let (_, ending) = split_line_ending(line);
+        match line_items[index] {
+            Some(i) => {
+                for line in &written[item..i] {
+                    out.push_str(line);
+                    out.push_str(newline);
+                }
+                out.push_str(line);
+                item = i + 1;
+            }
+            None if item < items.len() && !matched[item] => {
+                out.push_str(written[item]);
+                out.push_str(ending);
+                item += 1;
+            }
+            None => (),
+        }
+        index += 1;
+    }
+
+    for line in &written[item..] {
+        push_line(&mut out, line, newline);
+    }
+
+    Some(out.trim_end_matches(|c| c == '\r' || c == '\n').to_string())
+}
+
+/// Uses `newline` as the line ending of `s`, which is written with `\n`.
+fn with_newline(s: &str, newline: &str) -> String {
+    match newline {
+        "\n" => s.to_string(),
+        _ => s.replace('\n', newline),
+    }
+}
+
+/// Hashes of the `key: value` fields of a section that are set.
+fn fields_of<T>(
+    section: &Option<T>,
+    fields: impl Fn(&T) -> Vec<(&'static str, FieldValue)>,
+) -> Vec<(&'static str, u64)> {
+    section
+        .iter()
+        .flat_map(fields)
+        .map(|(name, value)| (name, hash_of(&value)))
+        .collect()
+}
+
+impl OsuFile {
+    /// Keeps the `source` to write the `sections` back the same while they're unchanged, or the whole file if `sections` is `None`.
+    pub(crate) fn keep_formatting(&mut self, source: String, sections: Option<Vec<SectionName>>) {
+        let mut formatting = Formatting::new(source, self, sections);
+
+        // objects merged while parsing are still in the source
+        if self
+            .storyboard_dedup
+            .0
+            .as_ref()
+            .is_some_and(|report| report.removed_objects > 0)
+        {
+            formatting.parsed[section_index(SectionName::Events)].whole = None;
+        }
+
+        self.formatting.0 = Some(Box::new(formatting));
+    }
+
+    pub(crate) fn section_hash(&self, section: SectionName) -> u64 {
+        match section {
+            SectionName::General => hash_of(&self.general),
+            SectionName::Editor => hash_of(&self.editor),
+            SectionName::Metadata => hash_of(&self.metadata),
+            SectionName::Difficulty => hash_of(&self.difficulty),
+            SectionName::Events => hash_of(&self.events),
+            SectionName::TimingPoints => hash_of(&self.timing_points),
+            SectionName::Colours => hash_of(&self.colours),
+            SectionName::HitObjects => hash_of(&self.hitobjects),
+        }
+    }
+
+    fn field_hashes(&self, section: SectionName) -> Vec<(&'static str, u64)> {
+        match section {
+            SectionName::General => fields_of(&self.general, general::General::fields),
+            SectionName::Editor => fields_of(&self.editor, editor::Editor::fields),
+            SectionName::Metadata => fields_of(&self.metadata, metadata::Metadata::fields),
+            SectionName::Difficulty => fields_of(&self.difficulty, difficulty::Difficulty::fields),
+            _ => Vec::new(),
+        }
+    }
+
+    fn item_hashes(&self, section: SectionName) -> Vec<u64> {
+        match section {
+            SectionName::TimingPoints => self
+                .timing_points
+                .iter()
+                .flat_map(|timing_points| timing_points.0.iter().map(hash_of))
+                .collect(),
+            SectionName::HitObjects => self
+                .hitobjects
+                .iter()
+                .flat_map(|hitobjects| hitobjects.0.iter().map(hash_of))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns `true` if the section is unchanged since it was parsed, so it can be written from the source.
+    fn is_unchanged(&self, section: SectionName, formatting: &Formatting) -> bool {
+        formatting.parsed(section).whole == Some(self.section_hash(section))
+    }
+
+    /// Formatting from parsing with [`ParseOptions::preserve_formatting`][super::ParseOptions::preserve_formatting], which is only used when writing in the version the file was parsed in.
+    pub(crate) fn preserved_formatting(&self) -> Option<&Formatting> {
+        self.formatting.0.as_deref().filter(|formatting| {
+            formatting.sections.is_none() && formatting.version == self.version
+        })
+    }
+
+    /// Text of the section from the source, if it was kept and is unchanged since parsing.
+    /// - Returns `None` if the `version` isn't the version the file was parsed in.
+    pub(crate) fn preserved_section_text(
+        &self,
+        section: SectionName,
+        version: Version,
+    ) -> Option<String> {
+        let formatting = self.formatting.0.as_deref()?;
+        let kept = formatting
+            .sections
+            .as_ref()
+            .map_or(true, |sections| sections.contains(&section));
+
+        if !kept
+            || version != formatting.version
+            || !self.section_exists(section)
+            || !self.is_unchanged(section, formatting)
+        {
+            return None;
+        }
+
+        let source = &formatting.source;
+        let source_section = source_sections(source)
+            .into_iter()
+            .find(|s| s.name == section.as_str())?;
+        let content = source[source_section.content..source_section.end].trim_end();
+
+        Some(content.replace("\r\n", "\n"))
+    }
+
+    /// Writes the section from the source, where `body` is its content without trailing whitespace.
+    /// - Returns `None` if the section was removed since parsing.
+    fn preserved_section(
+        &self,
+        name: &str,
+        body: &str,
+        formatting: &Formatting,
+        newline: &str,
+    ) -> Option<String> {
+        let version = self.version;
+
+        let section = match name.parse::<SectionName>() {
+            Ok(section) => section,
+            Err(_) => {
+                let content = self
+                    .custom_sections
+                    .iter()
+                    .find(|section| section.name == name)
+                    .map(|section| &section.content)?;
+                let parsed = formatting
+                    .custom
+                    .iter()
+                    .find(|(parsed, _)| parsed == name)
+                    .map(|(_, hash)| *hash);
+
+                return if parsed == Some(hash_of(content)) {
+                    Some(body.to_string())
+                } else {
+                    Some(with_newline(content, newline))
+                };
+            }
+        };
+
+        if !self.section_exists(section) {
+            return None;
+        }
+        if self.is_unchanged(section, formatting) {
+            return Some(body.to_string());
+        }
+
+        let parsed = formatting.parsed(section);
+        let field_changed = |name: &str, value: Option<FieldValue>| {
+            let parsed = parsed
+                .fields
+                .iter()
+                .find(|(parsed, _)| *parsed == name)
+                .map(|(_, hash)| *hash);
+
+            parsed != value.map(|value| hash_of(&value))
+        };
+        let item_section = |written: Option<String>, items: Vec<u64>| {
+            let written = written?;
+            merge_items(body, &written, &items, &parsed.items, newline)
+                .or_else(|| Some(with_newline(&written, newline)))
+        };
+
+        match section {
+            SectionName::General => {
+                let general = self.general.as_ref()?;
+                Some(merge_fields(
+                    body,
+                    &general.to_string(version)?,
+                    general::General::FIELD_NAMES,
+                    |name| field_changed(name, general.get(name)),
+                    newline,
+                ))
+            }
+            SectionName::Editor => {
+                let editor = self.editor.as_ref()?;
+                Some(merge_fields(
+                    body,
+                    &editor.to_string(version)?,
+                    editor::Editor::FIELD_NAMES,
+                    |name| field_changed(name, editor.get(name)),
+                    newline,
+                ))
+            }
+            SectionName::Metadata => {
+                let metadata = self.metadata.as_ref()?;
+                Some(merge_fields(
+                    body,
+                    &metadata.to_string(version)?,
+                    metadata::Metadata::FIELD_NAMES,
+                    |name| field_changed(name, metadata.get(name)),
+                    newline,
+                ))
+            }
+            SectionName::Difficulty => {
+                let difficulty = self.difficulty.as_ref()?;
+                Some(merge_fields(
+                    body,
+                    &difficulty.to_string(version)?,
+                    difficulty::Difficulty::FIELD_NAMES,
+                    |name| field_changed(name, difficulty.get(name)),
+                    newline,
+                ))
+            }
+            SectionName::TimingPoints => item_section(
+                self.timing_points
+                    .as_ref()
+                    .and_then(|s| s.to_string(version))
+                    .map(|s| with_comments(&self.section_comments.timing_points, s)),
+                self.item_hashes(section),
+            ),
+            SectionName::HitObjects => item_section(
+                self.hitobjects
+                    .as_ref()
+                    .and_then(|s| s.to_string(version))
+                    .map(|s| with_comments(&self.section_comments.hitobjects, s)),
+                self.item_hashes(section),
+            ),
+            SectionName::Events => self
+                .events
+                .as_ref()
+                .and_then(|s| s.to_string(version))
+                .map(|s| with_newline(&s, newline)),
+            SectionName::Colours => self
+                .colours
+                .as_ref()
+                .and_then(|s| s.to_string(version))
+                .map(|s| with_newline(&s, newline)),
+        }
+    }
+
+    /// Writes the file with the lines that weren't changed since parsing written the same as in the source.
+    /// - Sections that were added since parsing are written after the last section.
+    pub(crate) fn to_string_preserved(&self, formatting: &Formatting) -> String {
+        let source = formatting.source.as_str();
+        let newline = if source.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let sections = source_sections(source);
+
+        let prefix_end = sections
+            .first()
+            .map_or(source.len(), |section| section.start);
+        let mut out = source[..prefix_end].to_string();
+        let mut written = Vec::with_capacity(sections.len());
+
+        for section in &sections {
+            written.push(section.name);
+
+            let content = &source[section.content..section.end];
+            let (body, trailing) = content.split_at(content.trim_end().len());
+
+            let body = match self.preserved_section(section.name, body, formatting, newline) {
+                Some(body) => body,
+                None => continue,
+            };
+
+            out.push_str(&source[section.start..section.content]);
+            out.push_str(&body);
+            out.push_str(trailing);
+        }
+
+        self.for_each_section::<Infallible>(
+            self.version,
+            &SectionName::ALL,
+            true,
+            |name, content| {
+                if written.contains(&name) {
+                    return Ok(());
+                }
+
+                if !out.trim_end().is_empty() {
+                    let trimmed_len = out.trim_end().len();
+                    out.truncate(trimmed_len);
+                    out.push_str(newline);
+                    out.push_str(newline);
+                }
+                out.push_str(&format!("[{name}]{newline}"));
+                out.push_str(&with_newline(content, newline));
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        out
+    }
+}
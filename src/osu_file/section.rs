@@ -0,0 +1,178 @@
+//! Module for sections that aren't part of the .osu file format, like sections used by tools.
+
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+
+use super::types::{Error, Version};
+use super::SectionName;
+use crate::parsers::get_colon_field_value_lines;
+
+pub use osu_file_parser_derive::OsuSection;
+
+/// A section that can be stored in an [`OsuFile`][super::OsuFile].
+/// - Can be derived with [`OsuSection`] for sections with `key: value` pairs.
+/// - Registering the section in [`ParseOptions`][super::ParseOptions] parses it while parsing the file, so errors are found early.
+pub trait Section: Sized {
+    type ParseError;
+
+    /// Name of the section as written inside of the square brackets.
+    const NAME: &'static str;
+
+    fn from_str(s: &str, version: Version) -> Result<Option<Self>, Error<Self::ParseError>>;
+
+    fn to_string(&self, version: Version) -> Option<String>;
+}
+
+/// A section that isn't parsed by the crate, stored as the text in the file.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RawSection {
+    /// Name of the section as written inside of the square brackets.
+    pub name: String,
+    /// Contents of the section without the section header.
+    pub content: String,
+    /// The section that came before this section in the file, which is where the section is written back.
+    /// - `None` if the section is written before all of the other sections.
+    /// - Other [`RawSection`]s aren't counted.
+    pub after: Option<SectionName>,
+}
+
+/// Parses the section to check for errors, throwing away the result.
+type SectionParser =
+    fn(&str, Version) -> Result<(), Error<Box<dyn std::error::Error + Send + Sync>>>;
+
+#[derive(Clone, Copy)]
+struct RegisteredSection {
+    name: &'static str,
+    parser: SectionParser,
+}
+
+impl Debug for RegisteredSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RegisteredSection")
+            .field(&self.name)
+            .finish()
+    }
+}
+
+// compared by name only, since function pointers can't be compared reliably
+impl PartialEq for RegisteredSection {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for RegisteredSection {}
+
+impl Hash for RegisteredSection {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+fn parse_section<S>(
+    s: &str,
+    version: Version,
+) -> Result<(), Error<Box<dyn std::error::Error + Send + Sync>>>
+where
+    S: Section,
+    S::ParseError: std::error::Error + Send + Sync + 'static,
+{
+    S::from_str(s, version)
+        .map(|_| ())
+        .map_err(Error::error_into)
+}
+
+/// [`Section`]s that are parsed with the file instead of returning [`ParseError::UnknownSection`][super::ParseError::UnknownSection].
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct SectionRegistry {
+    sections: Vec<RegisteredSection>,
+}
+
+impl SectionRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the section `S`.
+    pub fn register<S>(&mut self)
+    where
+        S: Section,
+        S::ParseError: std::error::Error + Send + Sync + 'static,
+    {
+        if !self.contains(S::NAME) {
+            self.sections.push(RegisteredSection {
+                name: S::NAME,
+                parser: parse_section::<S>,
+            });
+        }
+    }
+
+    /// Returns `true` if a section with the `name` is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.sections.iter().any(|s| s.name == name)
+    }
+
+    /// Names of the registered sections in the order they were registered.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.sections.iter().map(|s| s.name).collect()
+    }
+
+    /// Parses the section with the registered [`Section`], returning `None` if `name` isn't registered.
+    pub(crate) fn parse(
+        &self,
+        name: &str,
+        s: &str,
+        version: Version,
+    ) -> Option<Result<(), Error<Box<dyn std::error::Error + Send + Sync>>>> {
+        self.sections
+            .iter()
+            .find(|section| section.name == name)
+            .map(|section| (section.parser)(s, version))
+    }
+}
+
+/// Splits a section into `key: value` pairs, returning the key, value and the line index in the section.
+/// - Used by the [`OsuSection`] derive.
+pub fn key_value_fields(s: &str) -> Result<Vec<(&str, &str, usize)>, Error<ParseSectionError>> {
+    let (s, fields) = match get_colon_field_value_lines(s) {
+        Ok(result) => result,
+        Err(_) => return Err(Error::new(ParseSectionError::InvalidColonSet, 0)),
+    };
+
+    let mut line_index = 0;
+    let mut key_values = Vec::with_capacity(fields.len());
+
+    for (key, _, value, ws) in fields {
+        key_values.push((key, value, line_index));
+        line_index += ws.lines().count();
+    }
+
+    if !s.trim().is_empty() {
+        return Err(Error::new(ParseSectionError::InvalidColonSet, line_index));
+    }
+
+    Ok(key_values)
+}
+
+#[derive(Debug, thiserror::Error, strum_macros::IntoStaticStr)]
+#[non_exhaustive]
+/// Error used when there was a problem parsing a section derived with [`OsuSection`].
+pub enum ParseSectionError {
+    /// When the line isn't in a `key: value` format.
+    #[error("Invalid colon set, expected format of `key: value`")]
+    InvalidColonSet,
+    /// Invalid key name was used.
+    #[error("The key doesn't exist in the section")]
+    InvalidKey,
+    /// Duplicate field in the section were found.
+    #[error("Duplicate field were found in the section")]
+    DuplicateField,
+    /// A field failed to parse.
+    #[error("Failed to parse the value of `{key}`")]
+    InvalidValue {
+        key: String,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
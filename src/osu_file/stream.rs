@@ -0,0 +1,229 @@
+//! Module for parsing and writing an .osu file through readers and writers, one section at a time.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+use super::{
+    file_version, is_header_junk, Error, FileHeader, LimitsExceeded, OsuFile, ParseError,
+    ParseLimits, ParseOptions, SectionName, SectionsParser, Untracked,
+};
+
+/// Reads the input line by line, checking the [`ParseLimits`] before the whole line is read.
+struct LineReader<R> {
+    reader: R,
+    limits: ParseLimits,
+    buf: Vec<u8>,
+    line_index: usize,
+    bytes_read: usize,
+}
+
+impl<R: BufRead> LineReader<R> {
+    fn new(reader: R, limits: ParseLimits) -> Self {
+        Self {
+            reader,
+            limits,
+            buf: Vec::new(),
+            line_index: 0,
+            bytes_read: 0,
+        }
+    }
+
+    /// Reads the next line without the line ending, returning it with its line index.
+    fn next_line(&mut self) -> Result<Option<(usize, &str)>, Error<ParseError>> {
+        let line_index = self.line_index;
+        let read_err = |err: io::Error| Error::new(ParseError::from(err), line_index);
+
+        self.buf.clear();
+        let read = match self.limits.max_line_length {
+            // stops reading a bit after the limit, leaving room for `\r\n`
+            Some(limit) => {
+                Read::take(&mut self.reader, limit as u64 + 2).read_until(b'\n', &mut self.buf)
+            }
+            None => self.reader.read_until(b'\n', &mut self.buf),
+        }
+        .map_err(read_err)?;
+
+        if read == 0 {
+            return Ok(None);
+        }
+
+        self.bytes_read += read;
+        self.line_index += 1;
+
+        if let Some(limit) = self.limits.max_input_bytes {
+            if self.bytes_read > limit {
+                return Err(Error::new_into(
+                    LimitsExceeded::InputBytes {
+                        limit,
+                        size: self.bytes_read,
+                    },
+                    line_index,
+                ));
+            }
+        }
+
+        let mut line = self.buf.as_slice();
+        if let Some(stripped) = line.strip_suffix(b"\n") {
+            line = stripped.strip_suffix(b"\r").unwrap_or(stripped);
+        }
+
+        if let Some(limit) = self.limits.max_line_length {
+            if line.len() > limit {
+                return Err(Error::new_into(
+                    LimitsExceeded::LineLength {
+                        limit,
+                        length: line.len(),
+                    },
+                    line_index,
+                ));
+            }
+        }
+
+        let line = std::str::from_utf8(line)
+            .map_err(|err| read_err(io::Error::new(io::ErrorKind::InvalidData, err)))?;
+
+        Ok(Some((line_index, line)))
+    }
+}
+
+/// Section that is still being read.
+struct PendingSection {
+    name: String,
+    name_line: usize,
+    content: String,
+    content_line: Option<usize>,
+}
+
+impl PendingSection {
+    fn push(&mut self, line: &str, line_index: usize) {
+        match self.content_line {
+            // empty lines before the content are skipped
+            None if line.trim().is_empty() => (),
+            None => {
+                self.content_line = Some(line_index);
+                self.content.push_str(line.trim_start());
+            }
+            Some(_) => {
+                self.content.push('\n');
+                self.content.push_str(line);
+            }
+        }
+    }
+
+    fn parse(self, parser: &mut SectionsParser) -> Result<(), Error<ParseError>> {
+        let content_line = self.content_line.unwrap_or(self.name_line + 1);
+
+        parser.parse(&self.name, &self.content, self.name_line, content_line)
+    }
+}
+
+impl OsuFile {
+    /// Parses an .osu file from a reader.
+    /// - Only one section is kept in memory as text at a time.
+    /// - The reader is buffered, so it doesn't have to be a [`BufRead`].
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, Error<ParseError>> {
+        Self::from_reader_with_options(reader, &ParseOptions::default())
+    }
+
+    /// Parses an .osu file from a reader with the [`ParseOptions`].
+    /// - Only one section is kept in memory as text at a time.
+    /// - [`ParseLimits::max_line_length`] stops reading a line once it's over the limit, so the length in the error is how much was read.
+    pub fn from_reader_with_options<R: Read>(
+        reader: R,
+        options: &ParseOptions,
+    ) -> Result<Self, Error<ParseError>> {
+        let mut lines = LineReader::new(BufReader::new(reader), options.limits);
+
+        let mut leading = String::new();
+        let header = loop {
+            match lines.next_line()? {
+                // lines with only a BOM or zero-width characters are before the header too, like in `from_str`
+                Some((_, line)) if line.chars().all(is_header_junk) => {
+                    leading.push_str(line);
+                    leading.push('\n');
+                }
+                Some((_, line)) => {
+                    let (rest, (junk, version)) = file_version(line)?;
+                    leading.push_str(junk);
+
+                    break FileHeader {
+                        leading,
+                        line: line[junk.len()..line.len() - rest.len()].to_string(),
+                        version,
+                    };
+                }
+                None => return Err(ParseError::FileVersionDefinedWrong.into()),
+            }
+        };
+        let version = header.version;
+
+        let mut parser = SectionsParser::new(version, options);
+        let mut current: Option<PendingSection> = None;
+
+        while let Some((line_index, line)) = lines.next_line()? {
+            if let Some(header) = line.strip_prefix('[') {
+                if let Some(section) = current.take() {
+                    section.parse(&mut parser)?;
+                }
+
+                let (name, rest) = header.split_once(']').ok_or_else(|| {
+                    Error::new(
+                        ParseError::SectionNameNoCloseBracket(header.to_string()),
+                        line_index,
+                    )
+                })?;
+
+                let mut section = PendingSection {
+                    name: name.to_string(),
+                    name_line: line_index,
+                    content: String::new(),
+                    content_line: None,
+                };
+                section.push(rest, line_index);
+                current = Some(section);
+
+                continue;
+            }
+
+            match &mut current {
+                Some(section) => section.push(line, line_index),
+                None => {
+                    let line = line.trim();
+
+                    if !line.is_empty() && !line.starts_with("//") {
+                        return Err(Error::new(ParseError::UnexpectedLine, line_index));
+                    }
+                }
+            }
+        }
+
+        if let Some(section) = current {
+            section.parse(&mut parser)?;
+        }
+
+        let mut osu_file = parser.finish();
+        osu_file.header = Untracked(Some(header));
+
+        Ok(osu_file)
+    }
+
+    /// Writes the .osu file into the writer, the same as [`Display`][std::fmt::Display].
+    /// - Only one section is made into a string at a time.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        if let Some(formatting) = self.preserved_formatting() {
+            return writer.write_all(self.to_string_preserved(formatting).as_bytes());
+        }
+
+        write!(writer, "osu file format v{}\n\n", self.version)?;
+
+        let mut first = true;
+
+        self.for_each_section(self.version, &SectionName::ALL, true, |name, content| {
+            if !first {
+                writer.write_all(b"\n\n")?;
+            }
+            first = false;
+
+            write!(writer, "[{name}]\n{content}")
+        })
+    }
+}
@@ -0,0 +1,91 @@
+//! Module for finding the timing points in effect at a time.
+
+use super::{sorted::chronological_order, TimingPoint, TimingPoints};
+use crate::osu_file::Integer;
+
+/// Timing points in effect at a time, found with [`TimingPoints::active_at`] or [`TimingPointIndex::active_at`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActiveTimingPoints<'a> {
+    /// Uninherited timing point that sets the BPM and meter.
+    /// - Before the first uninherited timing point, the first one is used, like the game does.
+    pub uninherited: Option<&'a TimingPoint>,
+    /// Inherited timing point that sets the slider velocity.
+    /// - `None` if an uninherited timing point came after the last inherited one, since it resets the slider velocity.
+    pub inherited: Option<&'a TimingPoint>,
+}
+
+/// Timing points sorted like [`SortedTimingPoints`][super::SortedTimingPoints], for finding the ones in effect at many times in `O(log n)`.
+/// - Uninherited timing points come before the inherited ones at the same time, and otherwise timing points at the same time keep their order, where the later one wins.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimingPointIndex<'a> {
+    points: Vec<&'a TimingPoint>,
+    /// Index of the last uninherited and inherited timing points at or before each point.
+    last: Vec<(Option<usize>, Option<usize>)>,
+    first_uninherited: Option<usize>,
+}
+
+impl<'a> TimingPointIndex<'a> {
+    /// Builds the index, sorting the timing points in the same order as [`SortedTimingPoints`][super::SortedTimingPoints].
+    pub fn new(timing_points: &'a [TimingPoint]) -> Self {
+        let mut points = timing_points.iter().collect::<Vec<_>>();
+        points.sort_by_key(|t| chronological_order(t));
+
+        let mut last = Vec::with_capacity(points.len());
+        let (mut uninherited, mut inherited) = (None, None);
+        for (i, timing_point) in points.iter().enumerate() {
+            if timing_point.uninherited {
+                uninherited = Some(i);
+            } else {
+                inherited = Some(i);
+            }
+            last.push((uninherited, inherited));
+        }
+
+        Self {
+            first_uninherited: points.iter().position(|t| t.uninherited),
+            points,
+            last,
+        }
+    }
+
+    /// Timing points in effect at `time`.
+    pub fn active_at(&self, time: Integer) -> ActiveTimingPoints<'a> {
+        let end = self.points.partition_point(|t| t.time <= time);
+
+        let (uninherited, inherited) = match end.checked_sub(1) {
+            Some(last) => {
+                let (uninherited, inherited) = self.last[last];
+                match (uninherited, inherited) {
+                    (Some(u), Some(i)) if u > i => (Some(u), None),
+                    (None, inherited) => (self.first_uninherited, inherited),
+                    last => last,
+                }
+            }
+            None => (self.first_uninherited, None),
+        };
+
+        ActiveTimingPoints {
+            uninherited: uninherited.map(|i| self.points[i]),
+            inherited: inherited.map(|i| self.points[i]),
+        }
+    }
+}
+
+impl TimingPoints {
+    /// Sorts the timing points by time, which the file format expects but some files don't follow.
+    /// - Uninherited timing points are put before the inherited ones at the same time, like [`SortedTimingPoints::insert`][super::SortedTimingPoints::insert] does, and otherwise timing points at the same time keep their order.
+    pub fn sort(&mut self) {
+        self.0.sort_by_key(chronological_order);
+    }
+
+    /// Builds a [`TimingPointIndex`] for looking up the timing points in effect at many times.
+    pub fn index(&self) -> TimingPointIndex<'_> {
+        TimingPointIndex::new(&self.0)
+    }
+
+    /// Timing points in effect at `time`, even if the timing points aren't sorted.
+    /// - Builds an index each call, use [`index`][Self::index] for repeated lookups.
+    pub fn active_at(&self, time: Integer) -> ActiveTimingPoints<'_> {
+        self.index().active_at(time)
+    }
+}
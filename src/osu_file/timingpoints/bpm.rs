@@ -0,0 +1,129 @@
+//! Module for BPM information that's shown to the user.
+
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use super::{TimingPoint, TimingPoints};
+use crate::osu_file::Integer;
+
+/// BPM and how long it lasts for in the beatmap.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct BpmDuration {
+    /// The BPM.
+    pub bpm: Decimal,
+    /// Total duration in milliseconds the BPM is used for.
+    pub duration: Integer,
+}
+
+/// BPM information as shown by the osu! client.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct DisplayBpm {
+    /// The BPM that lasts the longest, which is the main BPM osu! shows.
+    pub most_common: Decimal,
+    /// Lowest BPM used up to the last hitobject.
+    pub min: Decimal,
+    /// Highest BPM used up to the last hitobject.
+    pub max: Decimal,
+    /// BPMs weighted by how long they last, sorted by the duration from longest to shortest.
+    pub histogram: Vec<BpmDuration>,
+}
+
+impl DisplayBpm {
+    /// Rounds the BPM to a whole number the same way osu! displays it.
+    /// - Rounds to the nearest integer with midpoints rounding away from zero.
+    pub fn round_bpm(bpm: Decimal) -> Decimal {
+        bpm.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+    }
+
+    /// Returns `true` if the beatmap uses more than one BPM after rounding.
+    pub fn is_variable(&self) -> bool {
+        Self::round_bpm(self.min) != Self::round_bpm(self.max)
+    }
+
+    /// Formats the BPM the way osu! shows it.
+    /// - `180` for a single BPM.
+    /// - `120-240 (180)` for a variable BPM, with the most common BPM in brackets.
+    pub fn to_display_string(&self) -> String {
+        let most_common = Self::round_bpm(self.most_common);
+
+        if self.is_variable() {
+            format!(
+                "{}-{} ({most_common})",
+                Self::round_bpm(self.min),
+                Self::round_bpm(self.max)
+            )
+        } else {
+            most_common.to_string()
+        }
+    }
+}
+
+/// BPM of the timing point, with the beat length rounded to 3 decimal places.
+/// - Beat lengths that only differ after that are the same BPM, so they're grouped together.
+/// - Returns `None` if the beat length rounds to `0` or the BPM doesn't fit in a [`Decimal`].
+fn rounded_bpm(timing_point: &TimingPoint) -> Option<Decimal> {
+    TimingPoint::checked_beat_duration_ms_to_bpm(timing_point.beat_length.round_dp(3))
+}
+
+impl TimingPoints {
+    /// Calculates the BPM information shown by osu!.
+    /// - `last_object_time` is the time of the last hitobject, timing after that doesn't count towards the most common BPM, the lowest BPM or the highest BPM.
+    /// - The first uninherited timing point is treated as starting from `0`, like osu!stable does.
+    /// - Beat lengths are rounded to 3 decimal places for every BPM, so BPMs are grouped the same way in the histogram as they are reported.
+    /// - Timing points with a BPM that doesn't fit in a [`Decimal`], such as from a beat length that rounds to `0`, are skipped.
+    /// - Returns `None` if there are no uninherited timing points with a positive beat length.
+    pub fn display_bpm(&self, last_object_time: Integer) -> Option<DisplayBpm> {
+        let uninherited = self
+            .0
+            .iter()
+            .filter(|t| t.uninherited && t.beat_length > Decimal::ZERO)
+            .filter_map(|t| Some((t, rounded_bpm(t)?)))
+            .collect::<Vec<_>>();
+
+        let mut grouped: Vec<(Decimal, Integer)> = Vec::new();
+        let mut used = Vec::new();
+
+        for (i, (timing_point, bpm)) in uninherited.iter().enumerate() {
+            let start_time = if i == 0 { 0 } else { timing_point.time };
+            let end_time = match uninherited.get(i + 1) {
+                Some((next, _)) => next.time,
+                None => last_object_time,
+            };
+
+            let bpm = *bpm;
+            let duration = if start_time > last_object_time {
+                0
+            } else {
+                used.push(bpm);
+                end_time
+                    .min(last_object_time)
+                    .saturating_sub(start_time)
+                    .max(0)
+            };
+
+            match grouped.iter_mut().find(|(b, _)| *b == bpm) {
+                Some((_, total)) => *total += duration,
+                None => grouped.push((bpm, duration)),
+            }
+        }
+
+        // stable sort keeps the earliest BPM first on ties
+        grouped.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        let histogram = grouped
+            .into_iter()
+            .map(|(bpm, duration)| BpmDuration { bpm, duration })
+            .collect::<Vec<_>>();
+
+        let most_common = histogram.first()?.bpm;
+        // the first timing point starts from `0`, so there's always one before the last hitobject
+        let min = used.iter().copied().min()?;
+        let max = used.iter().copied().max()?;
+
+        Some(DisplayBpm {
+            most_common,
+            min,
+            max,
+            histogram,
+        })
+    }
+}
@@ -0,0 +1,188 @@
+//! Module for converting between [`Millis`] and [`Beats`] using the BPM changes of a beatmap.
+
+use rust_decimal::{prelude::ToPrimitive, Decimal, RoundingStrategy};
+use rust_decimal_macros::dec;
+
+use super::{TimingPoint, TimingPoints};
+use crate::osu_file::{Beats, Millis};
+
+/// Beat length used when there are no uninherited timing points, which is `60` BPM.
+const DEFAULT_BEAT_LENGTH: Decimal = dec!(1000);
+
+/// Slider velocity in osu! pixels per millisecond at `time`, divided by `100`.
+/// - Follows the same rules as the slider timing, where SV multipliers are clamped to `0.1 ~ 10`.
+/// - Velocities too large to fit in a [`Decimal`], such as from a tiny beat length, are clamped to the largest value.
+pub(crate) fn slider_velocity_at(
+    timing_points: &[TimingPoint],
+    slider_multiplier: Decimal,
+    time: u32,
+) -> Decimal {
+    let mut beat_length = timing_points
+        .iter()
+        .find(|t| t.uninherited && t.beat_length > Decimal::ZERO)
+        .map_or(DEFAULT_BEAT_LENGTH, |t| t.beat_length);
+    let mut slider_velocity = Decimal::ONE;
+
+    for timing_point in timing_points {
+        if i64::from(timing_point.time) > i64::from(time) {
+            break;
+        }
+
+        if timing_point.uninherited {
+            if timing_point.beat_length > Decimal::ZERO {
+                beat_length = timing_point.beat_length;
+            }
+            slider_velocity = Decimal::ONE;
+        } else if timing_point.beat_length < Decimal::ZERO {
+            slider_velocity = dec!(-100)
+                .checked_div(timing_point.beat_length)
+                .map_or(dec!(10), |sv| sv.clamp(dec!(0.1), dec!(10)));
+        } else {
+            slider_velocity = Decimal::ONE;
+        }
+    }
+
+    slider_multiplier
+        .checked_mul(slider_velocity)
+        .and_then(|velocity| velocity.checked_div(beat_length))
+        .unwrap_or(if slider_multiplier.is_sign_negative() {
+            Decimal::MIN
+        } else {
+            Decimal::MAX
+        })
+}
+
+/// Milliseconds from `start` to `end`, which can't overflow unlike subtracting the [`Millis`].
+fn millis_between(start: Millis, end: Millis) -> Decimal {
+    Decimal::from(i64::from(end.0) - i64::from(start.0))
+}
+
+/// A section of the beatmap with the same BPM.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+struct TimingSection {
+    start: Millis,
+    start_beat: Beats,
+    beat_length: Decimal,
+}
+
+/// Converts between [`Millis`] and [`Beats`] using the uninherited timing points.
+/// - Beat `0` is at the first uninherited timing point, times before it use its BPM and have negative beats.
+/// - Timing points with a beat length that isn't positive are ignored.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct TimingContext {
+    sections: Vec<TimingSection>,
+}
+
+impl TimingContext {
+    /// Creates the context, returning `None` if there are no uninherited timing points with a positive beat length.
+    pub fn new(timing_points: &[TimingPoint]) -> Option<Self> {
+        let mut sections: Vec<TimingSection> = Vec::new();
+
+        for timing_point in timing_points
+            .iter()
+            .filter(|t| t.uninherited && t.beat_length > Decimal::ZERO)
+        {
+            let start = Millis(timing_point.time);
+            let start_beat = match sections.last() {
+                Some(prev) => {
+                    prev.start_beat + Beats(millis_between(prev.start, start) / prev.beat_length)
+                }
+                None => Beats(Decimal::ZERO),
+            };
+
+            // a later timing point at the same time replaces the earlier one
+            if sections.last().is_some_and(|prev| prev.start == start) {
+                sections.pop();
+            }
+
+            sections.push(TimingSection {
+                start,
+                start_beat,
+                beat_length: timing_point.beat_length,
+            });
+        }
+
+        if sections.is_empty() {
+            None
+        } else {
+            Some(Self { sections })
+        }
+    }
+
+    fn section_at_time(&self, time: Millis) -> &TimingSection {
+        self.sections
+            .iter()
+            .rev()
+            .find(|s| s.start <= time)
+            .unwrap_or(&self.sections[0])
+    }
+
+    fn section_at_beat(&self, beats: Beats) -> &TimingSection {
+        self.sections
+            .iter()
+            .rev()
+            .find(|s| s.start_beat <= beats)
+            .unwrap_or(&self.sections[0])
+    }
+
+    /// Beat length in milliseconds at `time`.
+    pub fn beat_length_at(&self, time: Millis) -> Decimal {
+        self.section_at_time(time).beat_length
+    }
+
+    /// Converts the time into beats.
+    pub fn to_beats(&self, time: Millis) -> Beats {
+        let section = self.section_at_time(time);
+
+        section.start_beat + Beats(millis_between(section.start, time) / section.beat_length)
+    }
+
+    /// Converts the beats into a time, rounded to the nearest millisecond.
+    /// - Returns `None` if the time doesn't fit in [`Millis`].
+    pub fn to_millis(&self, beats: Beats) -> Option<Millis> {
+        let section = self.section_at_beat(beats);
+        let offset = beats
+            .checked_sub(section.start_beat)?
+            .0
+            .checked_mul(section.beat_length)?
+            .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero);
+
+        section.start.checked_add(Millis(offset.to_i32()?))
+    }
+
+    /// Time after `beats` have passed from `time`.
+    /// - Returns `None` if the time doesn't fit in [`Millis`].
+    pub fn add_beats(&self, time: Millis, beats: Beats) -> Option<Millis> {
+        self.to_millis(self.to_beats(time).checked_add(beats)?)
+    }
+
+    /// Snaps the time to the closest beat snap, where `divisor` is `1` for every beat and `2` for every half beat.
+    /// - Returns the time unchanged if `divisor` is `0`.
+    /// - Returns `None` if the snapped time doesn't fit in [`Millis`].
+    pub fn snap(&self, time: Millis, divisor: u32) -> Option<Millis> {
+        if divisor == 0 {
+            return Some(time);
+        }
+
+        let divisor = Decimal::from(divisor);
+        let beats = (self.to_beats(time).0 * divisor)
+            .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+            / divisor;
+
+        self.to_millis(Beats(beats))
+    }
+}
+
+impl TimingPoints {
+    /// Creates a [`TimingContext`] for converting between [`Millis`] and [`Beats`].
+    pub fn timing_context(&self) -> Option<TimingContext> {
+        TimingContext::new(&self.0)
+    }
+}
+
+impl TimingPoint {
+    /// Time of the timing point as [`Millis`].
+    pub fn millis(&self) -> Millis {
+        Millis(self.time)
+    }
+}
@@ -3,9 +3,11 @@ use std::num::ParseIntError;
 use strum_macros::{EnumString, IntoStaticStr};
 use thiserror::Error;
 
+use crate::osu_file::Integer;
+
 #[derive(Debug, Error)]
 #[error(transparent)]
-pub struct ParseError(#[from] ParseTimingPointError);
+pub struct ParseError(#[from] pub(crate) ParseTimingPointError);
 
 /// Error used when there was a problem parsing the [`TimingPoint`][super::TimingPoint].
 #[derive(Debug, Error, EnumString, IntoStaticStr)]
@@ -37,7 +39,24 @@ pub enum ParseTimingPointError {
     InvalidUninherited,
     /// Invalid field count.
     #[error("The number of fields in the timing point is invalid.")]
-    InvalidFieldCount
+    InvalidFieldCount,
+}
+
+impl ParseTimingPointError {
+    /// Index of the comma separated field that caused the error.
+    pub(crate) fn field_index(&self) -> Option<usize> {
+        match self {
+            ParseTimingPointError::InvalidTime => Some(0),
+            ParseTimingPointError::InvalidBeatLength => Some(1),
+            ParseTimingPointError::InvalidMeter => Some(2),
+            ParseTimingPointError::InvalidSampleSet => Some(3),
+            ParseTimingPointError::InvalidSampleIndex => Some(4),
+            ParseTimingPointError::InvalidVolume => Some(5),
+            ParseTimingPointError::InvalidUninherited => Some(6),
+            ParseTimingPointError::InvalidEffects => Some(7),
+            ParseTimingPointError::InvalidFieldCount => None,
+        }
+    }
 }
 
 /// There was some problem parsing the [`SampleSet`][super::SampleSet].
@@ -69,3 +88,30 @@ pub enum VolumeError {
     #[error(transparent)]
     ParseVolumeError(#[from] ParseIntError),
 }
+
+/// Error for when a [`TimingPoint`][super::TimingPoint] is created with a value that can't be used to calculate the BPM or slider velocity.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TimingPointValueError {
+    /// The beat duration of an uninherited timing point is zero or negative.
+    #[error("The beat duration must be positive")]
+    NonPositiveBeatDuration,
+    /// The slider velocity multiplier of an inherited timing point is zero or negative.
+    #[error("The slider velocity multiplier must be positive")]
+    NonPositiveSliderVelocity,
+    /// The BPM is too small for its beat duration to fit in a `Decimal`.
+    #[error("The BPM is too small to convert to a beat duration")]
+    BpmOutOfRange,
+    /// The slider velocity multiplier is too small for its `beat_length` to fit in a `Decimal`.
+    #[error("The slider velocity multiplier is too small to convert to a beat length")]
+    SliderVelocityOutOfRange,
+}
+
+/// Error used when [`TimingPoints::try_insert`][super::TimingPoints::try_insert] would make the timing points out of order.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TimingPointInsertError {
+    /// The timing point is before the last timing point by more than the tolerance.
+    #[error("The timing point at {time} is before the last timing point at {previous}")]
+    OutOfOrder { time: Integer, previous: Integer },
+}
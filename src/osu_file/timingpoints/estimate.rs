@@ -0,0 +1,118 @@
+//! Module for working out the timing of a song from times tapped along to it, for timing tools.
+
+use rust_decimal::{prelude::FromPrimitive, Decimal};
+
+use super::{
+    Effects, SampleIndex, SampleSet, TimingPoint, TimingPointValueError, TimingPoints, Volume,
+};
+use crate::osu_file::{Integer, LATEST_VERSION};
+
+/// Beat length and offset fitted to tapped times by [`estimate_bpm`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EstimatedTiming {
+    /// Length of a beat in milliseconds.
+    pub beat_length: f64,
+    /// Time of the beat of the first tap in milliseconds.
+    pub offset: f64,
+    /// Root mean square of the distance between the taps and their beats in milliseconds, which is lower for steadier taps.
+    pub error: f64,
+}
+
+impl EstimatedTiming {
+    /// Beats per minute of the [`beat_length`][Self::beat_length].
+    pub fn bpm(&self) -> f64 {
+        60000.0 / self.beat_length
+    }
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Fits a beat length and offset to the tapped times in milliseconds with least squares.
+/// - Each tap is given the beat closest to it, using the median time between taps, so skipped beats are allowed.
+/// - Taps at the same time are counted once, and the order of the taps doesn't matter.
+/// - Returns `None` if there are less than 2 different times.
+pub fn estimate_bpm(taps: &[Integer]) -> Option<EstimatedTiming> {
+    let mut taps = taps.to_vec();
+    taps.sort_unstable();
+    taps.dedup();
+
+    if taps.len() < 2 {
+        return None;
+    }
+
+    let mut intervals = taps
+        .windows(2)
+        .map(|w| (i64::from(w[1]) - i64::from(w[0])) as f64)
+        .collect::<Vec<_>>();
+    let interval = median(&mut intervals);
+
+    let mut beat = 0.0;
+    let mut points = vec![(0.0, taps[0] as f64)];
+    for w in taps.windows(2) {
+        beat += ((i64::from(w[1]) - i64::from(w[0])) as f64 / interval)
+            .round()
+            .max(1.0);
+        points.push((beat, w[1] as f64));
+    }
+
+    let count = points.len() as f64;
+    let mean_beat = points.iter().map(|(b, _)| b).sum::<f64>() / count;
+    let mean_time = points.iter().map(|(_, t)| t).sum::<f64>() / count;
+    let (covariance, variance) =
+        points
+            .iter()
+            .fold((0.0, 0.0), |(covariance, variance), (b, t)| {
+                (
+                    covariance + (b - mean_beat) * (t - mean_time),
+                    variance + (b - mean_beat).powi(2),
+                )
+            });
+
+    let beat_length = covariance / variance;
+    let offset = mean_time - beat_length * mean_beat;
+    let error = (points
+        .iter()
+        .map(|(b, t)| (t - (offset + beat_length * b)).powi(2))
+        .sum::<f64>()
+        / count)
+        .sqrt();
+
+    Some(EstimatedTiming {
+        beat_length,
+        offset,
+        error,
+    })
+}
+
+impl TimingPoints {
+    /// Creates the timing points for the [`EstimatedTiming`], which is a single uninherited timing point at the offset.
+    /// - The offset is rounded to the nearest millisecond, and the timing point uses the editor's default hitsound settings.
+    pub fn from_estimate(
+        estimate: &EstimatedTiming,
+        meter: Integer,
+    ) -> Result<TimingPoints, TimingPointValueError> {
+        let beat_length = Decimal::from_f64(estimate.beat_length)
+            .ok_or(TimingPointValueError::NonPositiveBeatDuration)?;
+
+        let timing_point = TimingPoint::try_new_uninherited(
+            estimate.offset.round() as Integer,
+            beat_length,
+            meter,
+            SampleSet::Normal,
+            SampleIndex::OsuDefaultHitsounds,
+            Volume::new(100, LATEST_VERSION).unwrap(),
+            Effects::new(false, false),
+        )?;
+
+        Ok(TimingPoints(vec![timing_point]))
+    }
+}
@@ -0,0 +1,139 @@
+//! Module for adding timing points the way the editor does, keeping the hitsound settings of the timing point before it.
+
+use rust_decimal::Decimal;
+
+use super::{
+    Effects, SampleIndex, SampleSet, TimingPoint, TimingPointInsertError, TimingPointValueError,
+    TimingPoints, Volume,
+};
+use crate::osu_file::{InsertOptions, Integer, LATEST_VERSION};
+
+/// Settings copied to a new timing point from the timing point in effect at its time.
+struct Inherited {
+    meter: Integer,
+    sample_set: SampleSet,
+    sample_index: SampleIndex,
+    volume: Volume,
+    kiai: bool,
+}
+
+impl TimingPoints {
+    /// Settings of the timing point in effect at `time`, which is the last one at or before it.
+    /// - Before the first timing point, the first timing point is used, and without any timing points the editor's defaults are used.
+    fn inherited_at(&self, time: Integer) -> Inherited {
+        let previous = self
+            .0
+            .iter()
+            .rev()
+            .find(|t| t.time <= time)
+            .or_else(|| self.0.iter().min_by_key(|t| t.time));
+
+        match previous {
+            Some(previous) => Inherited {
+                meter: previous.meter,
+                sample_set: previous.sample_set,
+                sample_index: previous.sample_index,
+                volume: previous.volume,
+                kiai: previous
+                    .effects
+                    .as_ref()
+                    .is_some_and(Effects::kiai_time_enabled),
+            },
+            None => Inherited {
+                meter: 4,
+                sample_set: SampleSet::Normal,
+                sample_index: SampleIndex::OsuDefaultHitsounds,
+                volume: Volume::new(100, LATEST_VERSION).unwrap(),
+                kiai: false,
+            },
+        }
+    }
+
+    /// Inserts the timing point after the ones at or before its time, or before the inherited ones at its time if it's uninherited.
+    fn insert_sorted(&mut self, timing_point: TimingPoint) -> usize {
+        let index = self
+            .0
+            .iter()
+            .position(|t| {
+                t.time > timing_point.time
+                    || (t.time == timing_point.time && timing_point.uninherited && !t.uninherited)
+            })
+            .unwrap_or(self.0.len());
+
+        self.0.insert(index, timing_point);
+        index
+    }
+
+    /// Inserts an uninherited timing point at `time` with the `bpm` and `meter`, returning its index.
+    /// - The sample set, sample index, volume and kiai are copied from the timing point in effect at `time`, as the editor does.
+    /// - The timing point is placed before any inherited timing points at the same time, which the game expects.
+    pub fn insert_uninherited(
+        &mut self,
+        time: Integer,
+        bpm: Decimal,
+        meter: Integer,
+    ) -> Result<usize, TimingPointValueError> {
+        if bpm <= Decimal::ZERO {
+            return Err(TimingPointValueError::NonPositiveBeatDuration);
+        }
+
+        let beat_duration_ms = TimingPoint::checked_bpm_to_beat_duration_ms(bpm)
+            .ok_or(TimingPointValueError::BpmOutOfRange)?;
+
+        let inherited = self.inherited_at(time);
+        let timing_point = TimingPoint::try_new_uninherited(
+            time,
+            beat_duration_ms,
+            meter,
+            inherited.sample_set,
+            inherited.sample_index,
+            inherited.volume,
+            Effects::new(inherited.kiai, false),
+        )?;
+
+        Ok(self.insert_sorted(timing_point))
+    }
+
+    /// Inserts an inherited timing point at `time` with the slider velocity multiplier `sv`, returning its index.
+    /// - The meter, sample set, sample index, volume and kiai are copied from the timing point in effect at `time`, as the editor does.
+    pub fn insert_inherited(
+        &mut self,
+        time: Integer,
+        sv: Decimal,
+    ) -> Result<usize, TimingPointValueError> {
+        let inherited = self.inherited_at(time);
+        let timing_point = TimingPoint::try_new_inherited(
+            time,
+            sv,
+            inherited.meter,
+            inherited.sample_set,
+            inherited.sample_index,
+            inherited.volume,
+            Effects::new(inherited.kiai, false),
+        )?;
+
+        Ok(self.insert_sorted(timing_point))
+    }
+
+    /// Adds the timing point to the end after checking that it isn't before the latest timing point by more than [`InsertOptions::time_tolerance`], returning its index.
+    /// - Unlike [`insert_uninherited`][Self::insert_uninherited] and [`insert_inherited`][Self::insert_inherited], the timing point is added as it is.
+    pub fn try_insert(
+        &mut self,
+        timing_point: TimingPoint,
+        options: &InsertOptions,
+    ) -> Result<usize, TimingPointInsertError> {
+        if let Some(previous) = self.0.iter().map(|timing_point| timing_point.time).max() {
+            let earliest = i64::from(previous) - i64::from(options.time_tolerance);
+
+            if i64::from(timing_point.time) < earliest {
+                return Err(TimingPointInsertError::OutOfOrder {
+                    time: timing_point.time,
+                    previous,
+                });
+            }
+        }
+
+        self.0.push(timing_point);
+        Ok(self.0.len() - 1)
+    }
+}
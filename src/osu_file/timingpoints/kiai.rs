@@ -0,0 +1,51 @@
+//! Module for finding the sections of the map where kiai time is enabled.
+
+use std::ops::Range;
+
+use super::TimingPoints;
+use crate::osu_file::Integer;
+
+impl TimingPoints {
+    /// Times where kiai time is enabled, from the timing point that turns it on to the one that turns it off.
+    /// - Works even if the timing points aren't sorted, and timing points at the same time keep their order, where the later one wins.
+    /// - Kiai time that is still on at the last timing point ends at [`Integer::MAX`].
+    /// - Timing points without effects turn kiai time off.
+    pub fn kiai_sections(&self) -> Vec<Range<Integer>> {
+        let mut timing_points = self.0.iter().collect::<Vec<_>>();
+        timing_points.sort_by_key(|t| t.time);
+
+        let mut sections: Vec<Range<Integer>> = Vec::new();
+        let mut start = None;
+
+        for timing_point in timing_points {
+            let enabled = timing_point
+                .effects
+                .as_ref()
+                .is_some_and(|effects| effects.kiai_time_enabled());
+
+            match (start, enabled) {
+                (None, true) => start = Some(timing_point.time),
+                (Some(section_start), false) => {
+                    start = None;
+                    if section_start == timing_point.time {
+                        continue;
+                    }
+                    match sections.last_mut() {
+                        Some(last) if last.end == section_start => last.end = timing_point.time,
+                        _ => sections.push(section_start..timing_point.time),
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        if let Some(section_start) = start {
+            match sections.last_mut() {
+                Some(last) if last.end == section_start => last.end = Integer::MAX,
+                _ => sections.push(section_start..Integer::MAX),
+            }
+        }
+
+        sections
+    }
+}
@@ -1,50 +1,95 @@
+pub mod active;
+pub mod bpm;
+pub mod context;
 pub mod error;
+pub mod estimate;
+mod insert;
+mod kiai;
+pub mod sorted;
 pub mod types;
 
-use rust_decimal::{prelude::ToPrimitive, Decimal};
+use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 
 use super::{
-    Error, Integer, Version, VersionedDefault, VersionedFrom, VersionedFromStr, VersionedToString,
+    Error, Extensions, Integer, LineComments, ParseWarning, Version, VersionedDefault,
+    VersionedFrom, VersionedFromStr, VersionedToString, LATEST_VERSION,
 };
 
+pub use active::*;
+pub use bpm::*;
+pub use context::*;
 pub use error::*;
+pub use estimate::*;
+pub use sorted::*;
 pub use types::*;
 
 #[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimingPoints(pub Vec<TimingPoint>);
 
 impl VersionedFromStr for TimingPoints {
     type Err = Error<ParseError>;
 
     fn from_str(s: &str, version: Version) -> std::result::Result<Option<Self>, Self::Err> {
+        Self::from_str_skipping_malformed(s, version, 0, None, &mut Vec::new())
+    }
+}
+
+impl TimingPoints {
+    /// [`from_str`][VersionedFromStr::from_str] that skips the lines that fail to parse if there are `warnings`, adding a warning for each of them.
+    /// - `line_number` is the line index where the section starts.
+    /// - The comments of a section without timing points are put in `section_comments`, since they aren't around a timing point.
+    pub(crate) fn from_str_skipping_malformed(
+        s: &str,
+        version: Version,
+        line_number: usize,
+        mut warnings: Option<&mut Vec<ParseWarning>>,
+        section_comments: &mut Vec<String>,
+    ) -> std::result::Result<Option<Self>, Error<ParseError>> {
         let mut timing_points = Vec::new();
+        let mut comments = Vec::new();
 
         for (line_index, s) in s.lines().enumerate() {
             if s.trim().is_empty() {
                 continue;
             }
 
-            timing_points.push(Error::new_from_result_into(
-                TimingPoint::from_str(s, version),
-                line_index,
-            )?);
-        }
+            if let Some(comment) = s.trim_start().strip_prefix("//") {
+                comments.push(comment.to_string());
+                continue;
+            }
 
-        if let Some(s) = timing_points.get(0) {
-            if s.is_some() {
-                Ok(Some(TimingPoints(
-                    timing_points
-                        .into_iter()
-                        .map(|v| v.unwrap())
-                        .collect::<Vec<_>>(),
-                )))
-            } else {
-                Ok(None)
+            let timing_point = match TimingPoint::from_str(s, version) {
+                Ok(timing_point) => timing_point,
+                Err(err) => {
+                    let field = err.field_index();
+                    let err: Error<ParseError> = Error::new_into(err, line_index);
+
+                    if let Some(warnings) = warnings.as_deref_mut() {
+                        warnings.push(ParseWarning::malformed_line(line_number, s, &err));
+                        continue;
+                    }
+
+                    return Err(match field {
+                        Some(field) => err.with_field_column(s, field),
+                        None => err,
+                    });
+                }
+            };
+
+            if let Some(mut timing_point) = timing_point {
+                timing_point.comments.before = std::mem::take(&mut comments);
+                timing_points.push(timing_point);
             }
-        } else {
-            Ok(Some(TimingPoints(Vec::new())))
         }
+
+        match timing_points.last_mut() {
+            Some(last) => last.comments.after = comments,
+            None => *section_comments = comments,
+        }
+
+        Ok(Some(TimingPoints(timing_points)))
     }
 }
 
@@ -54,10 +99,39 @@ impl VersionedDefault for TimingPoints {
     }
 }
 
+impl TimingPoints {
+    /// Compares the timing points while ignoring differences in how they are written.
+    /// - Missing effects and effects with no flags.
+    /// - Different ways of writing the same beat length, such as `300` and `3e2`.
+    /// - Short timing points and the same timing points with the default values written out.
+    /// - `//` comments around the timing points.
+    pub fn semantically_eq(&self, other: &TimingPoints) -> bool {
+        let normalized = |timing_point: &TimingPoint| {
+            let mut timing_point = timing_point.clone();
+            timing_point.comments = LineComments::default();
+            if timing_point.effects.is_none() {
+                timing_point.effects = <Effects as VersionedFrom<u32>>::from(0, LATEST_VERSION);
+            }
+            timing_point
+        };
+
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(&other.0)
+                .all(|(a, b)| normalized(a) == normalized(b))
+    }
+}
+
 /// Struct representing a timing point.
 /// Each timing point influences a specified portion of the map, commonly called a `timing section`.
 /// The .osu file format requires these to be sorted in chronological order.
+/// - Older versions write timing points with fewer fields, see [`min_timing_point_fields`].
+/// - The missing fields are a meter of `4`, the `Normal` sample set, sample index `1`, a volume of `100`, uninherited and no effects.
+/// - Short timing points are written back with the same number of fields, unless one of the missing fields was changed or the version needs more fields.
 #[derive(Clone, Hash, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimingPoint {
     // for some reason decimal is parsed anyway in the beatmap???
     pub time: Integer,
@@ -68,22 +142,58 @@ pub struct TimingPoint {
     pub volume: Volume,
     pub uninherited: bool,
     pub effects: Option<Effects>,
+    /// Data attached by the user, which isn't written to the file.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extensions: Extensions,
+    /// `//` comment lines written around the timing point in the `[TimingPoints]` section.
+    pub comments: LineComments,
+    /// `beat_length` as it was written when it was parsed, if writing the parsed value gives a different text.
+    /// - Used instead of the value while `beat_length` is unchanged, so untouched timing points are written the same.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    beat_length_text: Untracked<Option<(Decimal, String)>>,
+    /// Number of fields the timing point was written with, if it was shorter than all of the fields.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    field_count: Untracked<Option<usize>>,
 }
 
 impl TimingPoint {
     /// Converts beat duration in milliseconds to BPM.
+    /// - Panics if `beat_duration_ms` is `0` or the BPM doesn't fit in a [`Decimal`], see [`checked_beat_duration_ms_to_bpm`][Self::checked_beat_duration_ms_to_bpm].
     pub fn beat_duration_ms_to_bpm(
         beat_duration_ms: rust_decimal::Decimal,
     ) -> rust_decimal::Decimal {
         rust_decimal::Decimal::ONE / beat_duration_ms * dec!(60000)
     }
 
+    /// Converts beat duration in milliseconds to BPM.
+    /// - Returns `None` if `beat_duration_ms` is `0` or the BPM doesn't fit in a [`Decimal`], such as for a tiny beat duration.
+    pub fn checked_beat_duration_ms_to_bpm(beat_duration_ms: Decimal) -> Option<Decimal> {
+        Decimal::ONE
+            .checked_div(beat_duration_ms)?
+            .checked_mul(dec!(60000))
+    }
+
     /// Converts BPM to beat duration in milliseconds.
+    /// - Panics if `bpm` is `0` or the beat duration doesn't fit in a [`Decimal`], see [`checked_bpm_to_beat_duration_ms`][Self::checked_bpm_to_beat_duration_ms].
     pub fn bpm_to_beat_duration_ms(bpm: rust_decimal::Decimal) -> rust_decimal::Decimal {
         rust_decimal::Decimal::ONE / (bpm / dec!(60000))
     }
 
+    /// Converts BPM to beat duration in milliseconds.
+    /// - Returns `None` if `bpm` is `0` or the beat duration doesn't fit in a [`Decimal`], such as for a tiny BPM.
+    pub fn checked_bpm_to_beat_duration_ms(bpm: Decimal) -> Option<Decimal> {
+        Decimal::ONE.checked_div(bpm.checked_div(dec!(60000))?)
+    }
+
+    /// Converts a slider velocity multiplier to the negative `beat_length` of an inherited timing point.
+    fn checked_sv_to_beat_length(slider_velocity_multiplier: Decimal) -> Option<Decimal> {
+        Decimal::ONE
+            .checked_div(slider_velocity_multiplier)?
+            .checked_mul(dec!(-100))
+    }
+
     /// New instance of `TimingPoint` that is inherited.
+    /// - Panics if `slider_velocity_multiplier` is `0`, see [`try_new_inherited`][Self::try_new_inherited].
     pub fn new_inherited(
         time: Integer,
         slider_velocity_multiplier: rust_decimal::Decimal,
@@ -104,6 +214,10 @@ impl TimingPoint {
             volume,
             uninherited: false,
             effects: Some(effects),
+            extensions: Extensions::new(),
+            comments: LineComments::default(),
+            beat_length_text: Untracked(None),
+            field_count: Untracked(None),
         }
     }
 
@@ -126,11 +240,90 @@ impl TimingPoint {
             volume,
             uninherited: true,
             effects: Some(effects),
+            extensions: Extensions::new(),
+            comments: LineComments::default(),
+            beat_length_text: Untracked(None),
+            field_count: Untracked(None),
+        }
+    }
+
+    /// New instance of `TimingPoint` that is inherited, checking that `slider_velocity_multiplier` is positive.
+    pub fn try_new_inherited(
+        time: Integer,
+        slider_velocity_multiplier: Decimal,
+        meter: Integer,
+        sample_set: SampleSet,
+        sample_index: SampleIndex,
+        volume: Volume,
+        effects: Effects,
+    ) -> Result<Self, TimingPointValueError> {
+        if slider_velocity_multiplier <= Decimal::ZERO {
+            return Err(TimingPointValueError::NonPositiveSliderVelocity);
+        }
+        if Self::checked_sv_to_beat_length(slider_velocity_multiplier).is_none() {
+            return Err(TimingPointValueError::SliderVelocityOutOfRange);
+        }
+
+        Ok(Self::new_inherited(
+            time,
+            slider_velocity_multiplier,
+            meter,
+            sample_set,
+            sample_index,
+            volume,
+            effects,
+        ))
+    }
+
+    /// New instance of `TimingPoint` that is uninherited, checking that `beat_duration_ms` is positive.
+    pub fn try_new_uninherited(
+        time: Integer,
+        beat_duration_ms: Decimal,
+        meter: Integer,
+        sample_set: SampleSet,
+        sample_index: SampleIndex,
+        volume: Volume,
+        effects: Effects,
+    ) -> Result<Self, TimingPointValueError> {
+        if beat_duration_ms <= Decimal::ZERO {
+            return Err(TimingPointValueError::NonPositiveBeatDuration);
+        }
+
+        Ok(Self::new_uninherited(
+            time,
+            beat_duration_ms,
+            meter,
+            sample_set,
+            sample_index,
+            volume,
+            effects,
+        ))
+    }
+
+    /// BPM of an uninherited timing point.
+    /// - Returns `None` if the timing point is inherited, `beat_length` isn't positive or the BPM doesn't fit in a [`Decimal`], instead of panicking.
+    pub fn checked_bpm(&self) -> Option<Decimal> {
+        if !self.uninherited || self.beat_length <= Decimal::ZERO {
+            return None;
+        }
+
+        Self::checked_beat_duration_ms_to_bpm(self.beat_length)
+    }
+
+    /// Slider velocity multiplier of an inherited timing point.
+    /// - Returns `None` if the timing point is uninherited, `beat_length` isn't negative or the multiplier doesn't fit in a [`Decimal`], instead of panicking.
+    /// - The game uses a multiplier of `1` for inherited timing points that don't have a negative `beat_length`, and clamps the multiplier between `0.1` and `10`.
+    pub fn checked_sv(&self) -> Option<Decimal> {
+        if self.uninherited || self.beat_length >= Decimal::ZERO {
+            return None;
         }
+
+        Decimal::ONE.checked_div(self.beat_length.checked_div(dec!(-100))?)
     }
 
     /// Calculates BPM using the `beatLength` field when unherited.
     /// - Returns `None` if the timing point is inherited or `beat_length` isn't a valid decimal.
+    /// - Panics if `beat_length` is `0`, see [`checked_bpm`][Self::checked_bpm].
     pub fn calc_bpm(&self) -> Option<rust_decimal::Decimal> {
         if self.uninherited {
             Some(Self::beat_duration_ms_to_bpm(self.beat_length))
@@ -140,6 +333,7 @@ impl TimingPoint {
     }
     /// Calculates the slider velocity multiplier when the timing point is inherited.
     /// - Returns `None` if the timing point is uninherited or `beat_length` isn't a valid decimal.
+    /// - Panics if `beat_length` is `0`, see [`checked_sv`][Self::checked_sv].
     pub fn calc_slider_velocity_multiplier(&self) -> Option<rust_decimal::Decimal> {
         if self.uninherited {
             None
@@ -149,62 +343,143 @@ impl TimingPoint {
     }
 }
 
-const OLD_VERSION_TIME_OFFSET: rust_decimal::Decimal = dec!(24);
+const OLD_VERSION_TIME_OFFSET: Integer = 24;
+
+/// Number of fields in a timing point.
+const TIMING_POINT_FIELD_COUNT: usize = 8;
+
+/// Values used for the fields missing from a short timing point, from the `meter` field onwards, as written in the file.
+const DEFAULT_FIELDS: [&str; TIMING_POINT_FIELD_COUNT - 2] = ["4", "1", "1", "100", "1", "0"];
+
+/// Smallest number of fields a timing point can have in the file format `version`.
+/// - Versions 3 and 4 only need `time,beat_length`.
+/// - Later versions need the fields up to the volume, the uninherited and effects fields can be left out.
+pub fn min_timing_point_fields(version: Version) -> usize {
+    if version < 5 {
+        2
+    } else {
+        6
+    }
+}
 
 impl VersionedFromStr for TimingPoint {
     type Err = ParseTimingPointError;
 
     fn from_str(s: &str, version: Version) -> std::result::Result<Option<Self>, Self::Err> {
-        let meter_fallback = 4;
-        let sample_set_fallback = SampleSet::Normal;
-        let sample_index_fallback = <SampleIndex as VersionedFrom<u32>>::from(1, version).unwrap();
-        let volume_fallback = <Volume as VersionedFrom<Integer>>::from(100, version).unwrap();
-
-        // make this simple bruh
-        let split_by_comma: Vec<&str> = s.split(",").collect();
+        let split_by_comma: Vec<&str> = s.split(',').collect();
+        let field_count = split_by_comma.len();
 
-        if split_by_comma.len() != 8 {
+        if !(min_timing_point_fields(version)..=TIMING_POINT_FIELD_COUNT).contains(&field_count) {
             return Err(ParseTimingPointError::InvalidFieldCount);
         }
 
+        // missing fields are parsed from their default values
+        let field = |i: usize| {
+            split_by_comma
+                .get(i)
+                .copied()
+                .unwrap_or(DEFAULT_FIELDS[i - 2])
+        };
+
+        let beat_length_str = split_by_comma[1];
+        let beat_length = beat_length_str
+            .parse::<Decimal>()
+            .or_else(|_| Decimal::from_scientific(beat_length_str))
+            .map_err(|_| ParseTimingPointError::InvalidBeatLength)?;
+        let beat_length_text = (beat_length.to_string() != beat_length_str)
+            .then(|| (beat_length, beat_length_str.to_string()));
+
         Ok(Some(TimingPoint {
             time: {
                 let t = split_by_comma[0]
                     .parse::<Integer>()
-                    .map_err(|_| { ParseTimingPointError::InvalidTime })?;
+                    .map_err(|_| ParseTimingPointError::InvalidTime)?;
 
                 if (3..=4).contains(&version) {
-                    t + OLD_VERSION_TIME_OFFSET.to_i32().unwrap()
+                    t.saturating_add(OLD_VERSION_TIME_OFFSET)
                 } else {
                     t
                 }
             },
-            beat_length: split_by_comma[1]
-                .parse::<Decimal>()
-                .map_err(|_| { ParseTimingPointError::InvalidBeatLength })?,
-            meter: split_by_comma[2]
+            beat_length,
+            meter: field(2)
                 .parse::<Integer>()
-                .map_err(|_| { ParseTimingPointError::InvalidMeter })?,
-            sample_set: SampleSet
-                ::from_str(split_by_comma[3], version)
-                .map_err(|_| { ParseTimingPointError::InvalidSampleSet })?
-                .unwrap(),
-            sample_index: SampleIndex
-                ::from_str(split_by_comma[4], version)
-                .map_err(|_| { ParseTimingPointError::InvalidSampleIndex })?
-                .unwrap(),
-            volume: Volume
-                ::from_str(split_by_comma[5], version)
-                .map_err(|_| { ParseTimingPointError::InvalidVolume })?
-                .unwrap(),
-            uninherited: match split_by_comma[6] {
+                .map_err(|_| ParseTimingPointError::InvalidMeter)?,
+            sample_set: SampleSet::from_str(field(3), version)
+                .map_err(|_| ParseTimingPointError::InvalidSampleSet)?
+                .ok_or(ParseTimingPointError::InvalidSampleSet)?,
+            sample_index: SampleIndex::from_str(field(4), version)
+                .map_err(|_| ParseTimingPointError::InvalidSampleIndex)?
+                .ok_or(ParseTimingPointError::InvalidSampleIndex)?,
+            volume: Volume::from_str(field(5), version)
+                .map_err(|_| ParseTimingPointError::InvalidVolume)?
+                .ok_or(ParseTimingPointError::InvalidVolume)?,
+            uninherited: match field(6) {
                 "0" => Ok(false),
                 "1" => Ok(true),
-                _ => Err(ParseTimingPointError::InvalidUninherited)
+                _ => Err(ParseTimingPointError::InvalidUninherited),
             }?,
-            effects: Effects
-                ::from_str(split_by_comma[7], version)
-                .map_err(|_| { ParseTimingPointError::InvalidVolume })?
+            effects: Effects::from_str(field(7), version)
+                .map_err(|_| ParseTimingPointError::InvalidEffects)?,
+            extensions: Extensions::new(),
+            comments: LineComments::default(),
+            beat_length_text: Untracked(beat_length_text),
+            field_count: Untracked((field_count < TIMING_POINT_FIELD_COUNT).then_some(field_count)),
         }))
     }
-}
\ No newline at end of file
+}
+
+impl VersionedToString for TimingPoint {
+    fn to_string(&self, version: Version) -> Option<String> {
+        let time = if (3..=4).contains(&version) {
+            self.time.saturating_sub(OLD_VERSION_TIME_OFFSET)
+        } else {
+            self.time
+        };
+        let beat_length = match &self.beat_length_text.0 {
+            Some((value, text)) if *value == self.beat_length => text.clone(),
+            _ => self.beat_length.to_string(),
+        };
+
+        let fields = [
+            time.to_string(),
+            beat_length,
+            self.meter.to_string(),
+            self.sample_set.to_string(version).unwrap(),
+            self.sample_index.to_string(version).unwrap(),
+            self.volume.to_string(version).unwrap(),
+            if self.uninherited { "1" } else { "0" }.to_string(),
+            self.effects
+                .and_then(|effects| effects.to_string(version))
+                .unwrap_or_else(|| "0".to_string()),
+        ];
+
+        // short timing points stay short, unless a missing field was changed from its default
+        let field_count = match self.field_count.0 {
+            Some(field_count) => (field_count..TIMING_POINT_FIELD_COUNT)
+                .filter(|i| fields[*i] != DEFAULT_FIELDS[i - 2])
+                .last()
+                .map_or(field_count, |i| i + 1)
+                .max(min_timing_point_fields(version)),
+            None => TIMING_POINT_FIELD_COUNT,
+        };
+
+        Some(fields[..field_count].join(","))
+    }
+}
+
+impl VersionedToString for TimingPoints {
+    fn to_string(&self, version: Version) -> Option<String> {
+        Some(
+            self.0
+                .iter()
+                .filter_map(|timing_point| {
+                    timing_point
+                        .to_string(version)
+                        .map(|line| timing_point.comments.wrap(line))
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+}
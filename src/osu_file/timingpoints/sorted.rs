@@ -0,0 +1,133 @@
+//! Module for timing points that are always kept in chronological order.
+
+use std::ops::Range;
+
+use rust_decimal::Decimal;
+
+use super::{ActiveTimingPoints, TimingPoint, TimingPointIndex, TimingPoints};
+use crate::osu_file::Integer;
+
+/// Order of the timing points, which is by time with the uninherited ones before the inherited ones at the same time.
+/// - Used by [`SortedTimingPoints`], [`TimingPointIndex`] and [`TimingPoints::sort`], so they agree on which timing point is in effect.
+pub(super) fn chronological_order(timing_point: &TimingPoint) -> (Integer, bool) {
+    (timing_point.time, !timing_point.uninherited)
+}
+
+/// [`TimingPoints`] that are always sorted by time.
+/// - Timing points with the same time keep the order they were added in, except that uninherited timing points are inserted before the inherited ones at their time.
+/// - Queries use binary searches, so they don't need to sort the timing points first.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "TimingPoints", from = "TimingPoints"))]
+pub struct SortedTimingPoints(Vec<TimingPoint>);
+
+impl SortedTimingPoints {
+    /// Creates an empty list of timing points.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The timing points in chronological order.
+    pub fn as_slice(&self) -> &[TimingPoint] {
+        &self.0
+    }
+
+    /// Iterates over the timing points in chronological order.
+    pub fn iter(&self) -> std::slice::Iter<TimingPoint> {
+        self.0.iter()
+    }
+
+    /// Number of timing points.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Checks if there are no timing points.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Inserts the timing point after the ones at or before its time, or before the inherited ones at its time if it's uninherited.
+    /// - Returns the index of the inserted timing point.
+    pub fn insert(&mut self, timing_point: TimingPoint) -> usize {
+        let order = chronological_order(&timing_point);
+        let index = self.0.partition_point(|t| chronological_order(t) <= order);
+        self.0.insert(index, timing_point);
+
+        index
+    }
+
+    /// Removes the timing point at `index`.
+    /// - Returns `None` if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Option<TimingPoint> {
+        (index < self.0.len()).then(|| self.0.remove(index))
+    }
+
+    /// Timing point in effect at `time`, which is the last one at or before it.
+    pub fn at(&self, time: Integer) -> Option<&TimingPoint> {
+        let index = self.0.partition_point(|t| t.time <= time);
+
+        index.checked_sub(1).map(|i| &self.0[i])
+    }
+
+    /// Uninherited timing point in effect at `time`.
+    /// - Before the first uninherited timing point, the first one is used, the same way as the game does.
+    pub fn uninherited_at(&self, time: Integer) -> Option<&TimingPoint> {
+        let index = self.0.partition_point(|t| t.time <= time);
+
+        self.0[..index]
+            .iter()
+            .rev()
+            .find(|t| t.uninherited)
+            .or_else(|| self.0[index..].iter().find(|t| t.uninherited))
+    }
+
+    /// Builds a [`TimingPointIndex`] for looking up the timing points in effect at many times.
+    pub fn index(&self) -> TimingPointIndex<'_> {
+        TimingPointIndex::new(&self.0)
+    }
+
+    /// Timing points in effect at `time`.
+    /// - Builds an index each call, use [`index`][Self::index] for repeated lookups.
+    pub fn active_at(&self, time: Integer) -> ActiveTimingPoints<'_> {
+        self.index().active_at(time)
+    }
+
+    /// BPM at `time`, from the uninherited timing point in effect at that time.
+    /// - Returns `None` if there are no uninherited timing points, or the beat length isn't positive.
+    pub fn bpm_at(&self, time: Integer) -> Option<Decimal> {
+        self.uninherited_at(time)?.checked_bpm()
+    }
+
+    /// Timing points with the time inside of `range`.
+    pub fn in_range(&self, range: Range<Integer>) -> &[TimingPoint] {
+        let start = self.0.partition_point(|t| t.time < range.start);
+        let end = self.0.partition_point(|t| t.time < range.end).max(start);
+
+        &self.0[start..end]
+    }
+}
+
+impl From<TimingPoints> for SortedTimingPoints {
+    /// Sorts the timing points with [`TimingPoints::sort`].
+    fn from(mut timing_points: TimingPoints) -> Self {
+        timing_points.sort();
+
+        Self(timing_points.0)
+    }
+}
+
+impl From<SortedTimingPoints> for TimingPoints {
+    fn from(timing_points: SortedTimingPoints) -> Self {
+        TimingPoints(timing_points.0)
+    }
+}
+
+impl<'a> IntoIterator for &'a SortedTimingPoints {
+    type Item = &'a TimingPoint;
+    type IntoIter = std::slice::Iter<'a, TimingPoint>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
@@ -5,6 +5,7 @@ use crate::osu_file::VersionedFromStr;
 
 /// Default sample set for hitobjects.
 #[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum SampleSet {
     /// Beatmap's default.
@@ -73,6 +74,7 @@ impl VersionedToString for SampleSet {
 /// - The unused bits will come in effect when to_string is called.
 /// - You can clear the unused bits by calling [`Effects::clear_unused_bits`].
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Effects(u32);
 
 impl VersionedFromStr for Effects {
@@ -102,10 +104,23 @@ impl VersionedToString for Effects {
 }
 
 impl Effects {
+    /// Bits of the `kiai_time_enabled` and `no_first_barline_in_taiko_mania` flags.
+    pub const KNOWN_BITS: u32 = 0b1001;
+
     /// Clears the unused bits.
     /// - This will keep the `kiai_time_enabled` and `no_first_barline_in_taiko_mania` flags untouched.
     pub fn clear_unused_bits(&mut self) {
-        self.0 &= 0b1001;
+        self.0 &= Self::KNOWN_BITS;
+    }
+
+    /// Value of the flags as written in the file, including the unused bits.
+    pub fn raw(&self) -> u32 {
+        self.0
+    }
+
+    /// Bits that are set but aren't one of the [`KNOWN_BITS`][Self::KNOWN_BITS].
+    pub fn unknown_bits(&self) -> u32 {
+        self.0 & !Self::KNOWN_BITS
     }
 
     pub fn kiai_time_enabled(&self) -> bool {
@@ -146,6 +161,7 @@ impl Effects {
 
 /// Custom sample index for hitobjects.
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum SampleIndex {
     /// Osu!'s default hitsounds.
@@ -202,6 +218,7 @@ impl VersionedDefault for SampleIndex {
 
 /// The volume percentage in the range of 0 ~ 100.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Volume(Integer);
 
 impl VersionedFromStr for Volume {
@@ -1,5 +1,7 @@
 use std::{
     fmt::{Debug, Display},
+    hash::{Hash, Hasher},
+    ops::{Add, AddAssign, Neg, Range, Sub, SubAssign},
     path::{Path, PathBuf},
 };
 
@@ -15,13 +17,132 @@ pub const MIN_VERSION: Version = 3;
 
 pub type Version = u8;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A time or a duration in milliseconds.
+/// - Kept separate from [`Beats`] so the two can't be mixed up, use [`TimingContext`][super::timingpoints::TimingContext] to convert between them.
+/// - The operators overflow like [`Integer`] does, use [`checked_add`][Self::checked_add] and [`checked_sub`][Self::checked_sub] for times from untrusted files.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Millis(pub Integer);
+
+/// A time or a duration in beats.
+/// - Kept separate from [`Millis`] so the two can't be mixed up, use [`TimingContext`][super::timingpoints::TimingContext] to convert between them.
+/// - The operators panic on overflow like [`Decimal`] does, use [`checked_add`][Self::checked_add] and [`checked_sub`][Self::checked_sub] for times from untrusted files.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Beats(pub Decimal);
+
+macro_rules! time_unit_ops {
+    ($name:ident, $inner:ty) => {
+        impl From<$inner> for $name {
+            fn from(value: $inner) -> Self {
+                $name(value)
+            }
+        }
+
+        impl From<$name> for $inner {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl $name {
+            /// Adds `rhs`, returning `None` if the result doesn't fit.
+            pub fn checked_add(self, rhs: Self) -> Option<Self> {
+                self.0.checked_add(rhs.0).map($name)
+            }
+
+            /// Subtracts `rhs`, returning `None` if the result doesn't fit.
+            pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+                self.0.checked_sub(rhs.0).map($name)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                $name(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                $name(self.0 - rhs.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl Neg for $name {
+            type Output = $name;
+
+            fn neg(self) -> Self::Output {
+                $name(-self.0)
+            }
+        }
+    };
+}
+
+time_unit_ops!(Millis, Integer);
+time_unit_ops!(Beats, Decimal);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The position of something in `osu!pixels` with the `x` `y` form.
-pub struct Position {
+/// - The coordinate type defaults to [`Decimal`], which is what the file stores.
+/// - [`Position<Integer>`] and [`Position<f64>`] can be converted from and into for calculations.
+pub struct Position<T = Decimal> {
     /// x coordinate.
-    pub x: Decimal,
+    pub x: T,
     /// y coordinate.
-    pub y: Decimal,
+    pub y: T,
+}
+
+impl<T> Position<T> {
+    /// Creates a new `Position`.
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Converts both coordinates with `f`.
+    pub fn map<U, F>(self, mut f: F) -> Position<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        Position {
+            x: f(self.x),
+            y: f(self.y),
+        }
+    }
+
+    /// Converts both coordinates with `f`, failing if either of them fail.
+    pub fn try_map<U, E, F>(self, mut f: F) -> Result<Position<U>, E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        Ok(Position {
+            x: f(self.x)?,
+            y: f(self.y)?,
+        })
+    }
 }
 
 impl Default for Position {
@@ -33,11 +154,63 @@ impl Default for Position {
     }
 }
 
+impl<T: Display> VersionedToString for Position<T> {
+    /// Returns the position as `x,y`, which is how positions are written in the `[Events]` and `[HitObjects]` sections.
+    fn to_string(&self, _: Version) -> Option<String> {
+        Some(format!("{},{}", self.x, self.y))
+    }
+}
+
+impl From<Position<Integer>> for Position {
+    fn from(position: Position<Integer>) -> Self {
+        position.map(Decimal::from)
+    }
+}
+
+impl TryFrom<Position> for Position<Integer> {
+    type Error = PositionNotInteger;
+
+    /// Fails if a coordinate has a fractional part or is out of range of [`Integer`].
+    fn try_from(position: Position) -> Result<Self, Self::Error> {
+        position.try_map(|v| {
+            if v.fract().is_zero() {
+                Integer::try_from(v).map_err(|_| PositionNotInteger)
+            } else {
+                Err(PositionNotInteger)
+            }
+        })
+    }
+}
+
+impl TryFrom<Position> for Position<f64> {
+    type Error = rust_decimal::Error;
+
+    fn try_from(position: Position) -> Result<Self, Self::Error> {
+        position.try_map(f64::try_from)
+    }
+}
+
+impl TryFrom<Position<f64>> for Position {
+    type Error = rust_decimal::Error;
+
+    /// Fails if a coordinate is `NaN`, infinite, or out of range of [`Decimal`].
+    fn try_from(position: Position<f64>) -> Result<Self, Self::Error> {
+        position.try_map(Decimal::try_from)
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("The position can't be represented with `Integer` coordinates")]
+/// Error when converting a [`Position`] into [`Position<Integer>`].
+pub struct PositionNotInteger;
+
 #[derive(Debug)]
 /// Error with line index.
 pub struct Error<E> {
     /// Line index of the error.
     line_index: usize,
+    /// Range of characters in the line that caused the error.
+    column: Option<Range<usize>>,
     /// The error.
     error: E,
 }
@@ -64,7 +237,11 @@ impl<E> Error<E> {
     /// Creates a new `Error` instance with the given line index and error.
     /// - If you have a higher error that `E` needs to convert from, use `new_into` instead.
     pub fn new(error: E, line_index: usize) -> Self {
-        Self { line_index, error }
+        Self {
+            line_index,
+            column: None,
+            error,
+        }
     }
 
     /// Creates a new `Error` instance with the given line index and error.
@@ -75,6 +252,7 @@ impl<E> Error<E> {
     {
         Error {
             line_index,
+            column: None,
             error: error.into(),
         }
     }
@@ -84,6 +262,7 @@ impl<E> Error<E> {
     pub fn new_from_result<T>(result: Result<T, E>, line_index: usize) -> Result<T, Error<E>> {
         result.map_err(|err| Error {
             line_index,
+            column: None,
             error: err,
         })
     }
@@ -99,6 +278,7 @@ impl<E> Error<E> {
     {
         result.map_err(|err| Error {
             line_index,
+            column: None,
             error: err.into(),
         })
     }
@@ -107,10 +287,19 @@ impl<E> Error<E> {
     pub fn error_into<E2>(self) -> Error<E2>
     where
         E2: From<E>,
+    {
+        self.map_error(E2::from)
+    }
+
+    /// Converts the inner error with `f`, keeping the line index and column.
+    pub fn map_error<E2, F>(self, f: F) -> Error<E2>
+    where
+        F: FnOnce(E) -> E2,
     {
         Error {
             line_index: self.line_index,
-            error: self.error.into(),
+            column: self.column,
+            error: f(self.error),
         }
     }
 
@@ -119,10 +308,7 @@ impl<E> Error<E> {
     where
         E2: From<E>,
     {
-        result.map_err(|err| Error {
-            line_index: err.line_index,
-            error: err.error.into(),
-        })
+        result.map_err(Error::error_into)
     }
 
     /// Increases `Error`'s processing line using the `Result<_, Error<E>>` type.
@@ -137,6 +323,7 @@ impl<E> Error<E> {
     {
         result.map_err(|err| Error {
             line_index: err.line_index + line_index,
+            column: err.column,
             error: err.error.into(),
         })
     }
@@ -146,10 +333,58 @@ impl<E> Error<E> {
         self.line_index
     }
 
+    /// Get the range of characters in the line that caused the error, starting from `0`.
+    /// - `None` if the error isn't about a specific part of the line, in which case the whole line is used.
+    pub fn column(&self) -> Option<Range<usize>> {
+        self.column.clone()
+    }
+
+    /// Sets the range of characters in the line that caused the error.
+    pub fn with_column(mut self, column: Range<usize>) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Sets the column to the `field`th comma separated field of `line`, where `field` starts from `0`.
+    /// - The column is left unchanged if `line` doesn't have the field.
+    pub(crate) fn with_field_column(self, line: &str, field: usize) -> Self {
+        let mut start = 0;
+
+        for (i, value) in line.split(',').enumerate() {
+            let len = value.chars().count();
+            if i == field {
+                return self.with_column(start..start + len);
+            }
+            start += len + 1;
+        }
+
+        self
+    }
+
+    /// Sets the column to the value of a `key: value` line, without the whitespace around it.
+    /// - The column is left unchanged if `line` doesn't have a `:`.
+    pub(crate) fn with_value_column(self, line: &str) -> Self {
+        match line.split_once(':') {
+            Some((key, value)) => {
+                let trimmed = value.trim_start();
+                let start =
+                    key.chars().count() + 1 + value.chars().count() - trimmed.chars().count();
+
+                self.with_column(start..start + trimmed.trim_end().chars().count())
+            }
+            None => self,
+        }
+    }
+
     /// Get a reference to the error's error.
     pub fn error(&self) -> &E {
         &self.error
     }
+
+    /// Returns the error without the line index.
+    pub fn into_error(self) -> E {
+        self.error
+    }
 }
 
 impl<E> Display for Error<E>
@@ -166,6 +401,7 @@ impl<E> From<E> for Error<E> {
     fn from(error: E) -> Self {
         Self {
             line_index: 0,
+            column: None,
             error,
         }
     }
@@ -195,15 +431,35 @@ pub trait VersionedDefault: Sized {
     /// - The output is version specific.
     /// - Returns Some if the version is supported, otherwise None.
     fn default(version: Version) -> Option<Self>;
+
+    /// Returns a default value for the object in a beatmap of the `mode`.
+    /// - Same as [`default`][Self::default] for values that don't depend on the mode.
+    fn default_for_mode(version: Version, mode: super::general::Mode) -> Option<Self> {
+        let _ = mode;
+        Self::default(version)
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// File path type that is used in most of the crate.
-pub struct FilePath(PathBuf);
+/// - Paths wrapped in double quotes are stored without the quotes, with [`is_quoted`][Self::is_quoted] set.
+pub struct FilePath {
+    path: PathBuf,
+    quoted: bool,
+}
 
 impl FilePath {
+    /// Creates a path that is written wrapped in double quotes.
+    pub fn new_quoted<P: AsRef<Path>>(path: P) -> Self {
+        FilePath {
+            path: path.as_ref().to_owned(),
+            quoted: true,
+        }
+    }
+
     pub fn get(&self) -> &Path {
-        &self.0
+        &self.path
     }
 
     pub fn set<P>(&mut self, path: P)
@@ -212,20 +468,31 @@ impl FilePath {
     {
         let path = path.as_ref().to_owned();
 
-        self.0 = path;
+        self.path = path;
+    }
+
+    /// Returns `true` if the path is written wrapped in double quotes.
+    pub fn is_quoted(&self) -> bool {
+        self.quoted
+    }
+
+    /// Sets if the path is written wrapped in double quotes.
+    /// - Paths containing spaces are always quoted.
+    pub fn set_quoted(&mut self, quoted: bool) {
+        self.quoted = quoted;
     }
 }
 
 impl VersionedToString for FilePath {
     /// Returns a string representation of the file path.
-    /// - It will contain quotes if the path contains spaces.
+    /// - It will contain quotes if it was quoted or if the path contains spaces.
     fn to_string(&self, _: Version) -> Option<String> {
         let quotes = {
-            let path = self.0.to_string_lossy();
+            let path = self.path.to_string_lossy();
 
-            path.contains(' ') && !(path.starts_with('"') && path.ends_with('"'))
+            self.quoted || (path.contains(' ') && !(path.starts_with('"') && path.ends_with('"')))
         };
-        let path = self.0.display();
+        let path = self.path.display();
 
         let path = if quotes {
             format!("\"{path}\"")
@@ -241,7 +508,43 @@ impl<P: AsRef<Path>> From<P> for FilePath {
     fn from(path: P) -> Self {
         let path = path.as_ref().to_owned();
 
-        FilePath(path)
+        FilePath {
+            path,
+            quoted: false,
+        }
+    }
+}
+
+/// `//` comment lines around an item in the `[TimingPoints]` and `[HitObjects]` sections, stored without the `//`.
+/// - These sections don't have comments in files written by osu!, but some files have them and they are kept where they were.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineComments {
+    /// Comments on the lines before the item.
+    pub before: Vec<String>,
+    /// Comments on the lines after the item, which are only parsed for the last item of the section.
+    pub after: Vec<String>,
+}
+
+impl LineComments {
+    /// Returns `true` if there are no comments.
+    pub fn is_empty(&self) -> bool {
+        self.before.is_empty() && self.after.is_empty()
+    }
+
+    /// Writes `line` with the comments around it.
+    pub(crate) fn wrap(&self, line: String) -> String {
+        if self.is_empty() {
+            return line;
+        }
+
+        self.before
+            .iter()
+            .map(|comment| format!("//{comment}"))
+            .chain(std::iter::once(line))
+            .chain(self.after.iter().map(|comment| format!("//{comment}")))
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 }
 
@@ -270,3 +573,20 @@ pub trait VersionedTryFrom<T>: Sized {
 
     fn try_from(value: T, version: Version) -> Result<Option<Self>, Self::Error>;
 }
+
+/// Data about how a value was parsed, which is ignored when comparing and hashing.
+/// - Two values with the same contents are equal no matter how they were made.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Untracked<T>(pub T);
+
+impl<T> PartialEq for Untracked<T> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> Eq for Untracked<T> {}
+
+impl<T> Hash for Untracked<T> {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
@@ -5,14 +5,14 @@ use nom::{
     bytes::complete::{tag, take_till, take_until, take_while},
     character::complete::multispace0,
     character::complete::{char, space0},
-    combinator::{eof, map_res, rest},
+    combinator::{eof, map, map_opt, map_res, peek, rest},
     error::{FromExternalError, ParseError},
     multi::{many0, separated_list0},
     sequence::{delimited, preceded, terminated, tuple},
     IResult,
 };
 
-use crate::osu_file::{Version, VersionedFromStr};
+use crate::osu_file::{FilePath, Version, VersionedFromStr};
 
 // pub fn leading_ws<'a, F: 'a, O, E: ParseError<&'a str>>(
 //     inner: F,
@@ -65,9 +65,8 @@ where
     T: VersionedFromStr,
 {
     let item = take_while(|c: char| !['|', ',', '\r', '\n'].contains(&c));
-    let item = map_res(item, move |s: &str| {
-        T::from_str(s, version).map(|v| v.unwrap())
-    });
+    // `None` means the value doesn't exist in this version, which fails the parser
+    let item = map_opt(map_res(item, move |s: &str| T::from_str(s, version)), |v| v);
 
     separated_list0(tag("|"), item)
 }
@@ -86,6 +85,39 @@ where
     take_while(|c: char| c != ',')
 }
 
+/// A comma separated field that can be wrapped in double quotes, allowing commas inside of the quotes.
+/// - Returns the field without the quotes, and if it was quoted.
+/// - Falls back to [`comma_field`] if the quotes don't wrap the whole field.
+pub fn quoted_comma_field<'a, E>() -> impl FnMut(&'a str) -> IResult<&str, (&str, bool), E>
+where
+    E: ParseError<&'a str>,
+{
+    alt((
+        map(
+            terminated(
+                delimited(tag("\""), take_till(|c| c == '"'), tag("\"")),
+                peek(alt((tag(","), eof))),
+            ),
+            |field| (field, true),
+        ),
+        map(comma_field(), |field| (field, false)),
+    ))
+}
+
+/// A [`quoted_comma_field`] as a [`FilePath`], keeping if it was quoted.
+pub fn file_path_field<'a, E>() -> impl FnMut(&'a str) -> IResult<&str, FilePath, E>
+where
+    E: ParseError<&'a str>,
+{
+    map(quoted_comma_field(), |(path, quoted)| {
+        if quoted {
+            FilePath::new_quoted(path)
+        } else {
+            FilePath::from(path)
+        }
+    })
+}
+
 pub fn comma_field_type<'a, E, T>() -> impl FnMut(&'a str) -> IResult<&str, T, E>
 where
     E: ParseError<&'a str> + FromExternalError<&'a str, <T as FromStr>::Err>,
@@ -101,9 +133,10 @@ where
     E: ParseError<&'a str> + FromExternalError<&'a str, <T as VersionedFromStr>::Err>,
     T: VersionedFromStr,
 {
-    map_res(comma_field(), move |i| {
-        T::from_str(i, version).map(|i| i.unwrap())
-    })
+    map_opt(
+        map_res(comma_field(), move |i| T::from_str(i, version)),
+        |i| i,
+    )
 }
 
 pub fn consume_rest_type<'a, E, T>() -> impl FnMut(&'a str) -> IResult<&str, T, E>
@@ -121,9 +154,7 @@ where
     E: ParseError<&'a str> + FromExternalError<&'a str, <T as VersionedFromStr>::Err>,
     T: VersionedFromStr,
 {
-    map_res(rest, move |s: &str| {
-        T::from_str(s, version).map(|s| s.unwrap())
-    })
+    map_opt(map_res(rest, move |s: &str| T::from_str(s, version)), |s| s)
 }
 
 pub fn nothing<'a, E>() -> impl FnMut(&'a str) -> IResult<&str, &str, E>
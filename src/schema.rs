@@ -0,0 +1,303 @@
+//! Module for describing the .osu file format as the crate models it, for documentation sites and validators that aren't written in Rust.
+//! - The fields are described from the same definitions they are parsed with, so the description stays in sync with the crate.
+
+#[cfg(feature = "serde")]
+use rust_decimal::prelude::ToPrimitive;
+#[cfg(feature = "serde")]
+use serde_json::{json, Map, Value};
+
+use crate::osu_file::{
+    colours::Colours, difficulty, editor, events::Events, general, hitobjects::HitObjects,
+    metadata, timingpoints::TimingPoints, FieldValue, FieldValueType, SectionName, Version,
+    VersionedDefault, VersionedToString, LATEST_VERSION, MIN_VERSION,
+};
+
+/// Field type of a `key: value` section that can be described with [`FieldSchema`].
+/// - Implemented by `versioned_field!` for the fields it defines.
+pub(crate) trait DescribeField: VersionedToString + FieldValueType {
+    /// Any value of the field, used to find how the field is written.
+    fn sample() -> Self;
+
+    /// Default value of the field in `version`, or `None` if there isn't one.
+    fn default_value(version: Version) -> Option<Self>;
+}
+
+/// Kind of value a field has, which is the variant of its [`FieldValue`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValueType {
+    Integer,
+    Decimal,
+    Bool,
+    String,
+    Path,
+    IntegerList,
+    StringList,
+}
+
+impl ValueType {
+    fn of(value: &FieldValue) -> Self {
+        match value {
+            FieldValue::Integer(_) => ValueType::Integer,
+            FieldValue::Decimal(_) => ValueType::Decimal,
+            FieldValue::Bool(_) => ValueType::Bool,
+            FieldValue::String(_) => ValueType::String,
+            FieldValue::Path(_) => ValueType::Path,
+            FieldValue::IntegerList(_) => ValueType::IntegerList,
+            FieldValue::StringList(_) => ValueType::StringList,
+        }
+    }
+
+    /// JSON schema of the value.
+    #[cfg(feature = "serde")]
+    fn json_schema(&self) -> Map<String, Value> {
+        let schema = match self {
+            ValueType::Integer => json!({ "type": "integer" }),
+            ValueType::Decimal => json!({ "type": "number" }),
+            ValueType::Bool => json!({ "type": "boolean" }),
+            ValueType::String | ValueType::Path => json!({ "type": "string" }),
+            ValueType::IntegerList => json!({ "type": "array", "items": { "type": "integer" } }),
+            ValueType::StringList => json!({ "type": "array", "items": { "type": "string" } }),
+        };
+
+        match schema {
+            Value::Object(schema) => schema,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Field of a `key: value` section.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FieldSchema {
+    /// Name of the field as written in the file, such as `AudioFilename`.
+    pub name: &'static str,
+    /// Kind of value of the field.
+    pub value_type: ValueType,
+    /// Versions the field is written in.
+    pub versions: Vec<Version>,
+    /// Default value in the latest version the field is written in, or `None` if the field doesn't have a default.
+    pub default: Option<FieldValue>,
+}
+
+impl FieldSchema {
+    /// Describes the field `T`, which is written as `name`.
+    pub(crate) fn of<T: DescribeField>(name: &'static str) -> Self {
+        let sample = T::sample();
+        let versions = (MIN_VERSION..=LATEST_VERSION)
+            .filter(|version| sample.to_string(*version).is_some())
+            .collect::<Vec<_>>();
+        let default = versions
+            .last()
+            .and_then(|version| T::default_value(*version))
+            .map(|value| value.to_field_value());
+
+        Self {
+            name,
+            value_type: ValueType::of(&sample.to_field_value()),
+            versions,
+            default,
+        }
+    }
+}
+
+/// Field of a line in a [`SectionKind::Lines`] section, in the order they are written.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct LineFieldSchema {
+    /// Name of the field in the [`line_format`][SectionSchema::line_format].
+    pub name: &'static str,
+    /// Kind of value of the field, where [`ValueType::String`] is for values written with their own format, such as `objectParams`.
+    pub value_type: ValueType,
+}
+
+/// How the content of a section is written.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SectionKind {
+    /// `key: value` fields, such as `[General]`.
+    Fields,
+    /// An item on each line, such as `[HitObjects]`.
+    Lines,
+}
+
+/// Section of the file.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SectionSchema {
+    pub name: SectionName,
+    pub kind: SectionKind,
+    /// Versions the section is written in.
+    pub versions: Vec<Version>,
+    /// Fields of a [`SectionKind::Fields`] section in the order they are written, empty for the other sections.
+    pub fields: Vec<FieldSchema>,
+    /// How each line of a [`SectionKind::Lines`] section is written, such as `time,beatLength,...` for `[TimingPoints]`.
+    pub line_format: Option<&'static str>,
+    /// Fields of each line of a [`SectionKind::Lines`] section, empty for the other sections.
+    pub line_fields: Vec<LineFieldSchema>,
+}
+
+fn all_versions() -> Vec<Version> {
+    (MIN_VERSION..=LATEST_VERSION).collect()
+}
+
+fn fields_section(name: SectionName, fields: Vec<FieldSchema>) -> SectionSchema {
+    SectionSchema {
+        name,
+        kind: SectionKind::Fields,
+        versions: all_versions(),
+        fields,
+        line_format: None,
+        line_fields: Vec::new(),
+    }
+}
+
+fn lines_section<T: VersionedDefault>(
+    name: SectionName,
+    line_format: &'static str,
+    line_fields: &[(&'static str, ValueType)],
+) -> SectionSchema {
+    SectionSchema {
+        name,
+        kind: SectionKind::Lines,
+        versions: all_versions()
+            .into_iter()
+            .filter(|version| T::default(*version).is_some())
+            .collect(),
+        fields: Vec::new(),
+        line_format: Some(line_format),
+        line_fields: line_fields
+            .iter()
+            .map(|&(name, value_type)| LineFieldSchema { name, value_type })
+            .collect(),
+    }
+}
+
+/// Describes every section in the order they are written in an .osu file.
+pub fn sections() -> Vec<SectionSchema> {
+    vec![
+        fields_section(SectionName::General, general::General::field_schemas()),
+        fields_section(SectionName::Editor, editor::Editor::field_schemas()),
+        fields_section(SectionName::Metadata, metadata::Metadata::field_schemas()),
+        fields_section(
+            SectionName::Difficulty,
+            difficulty::Difficulty::field_schemas(),
+        ),
+        lines_section::<Events>(
+            SectionName::Events,
+            "eventType,startTime,eventParams",
+            &[
+                ("eventType", ValueType::String),
+                ("startTime", ValueType::Integer),
+                ("eventParams", ValueType::String),
+            ],
+        ),
+        lines_section::<TimingPoints>(
+            SectionName::TimingPoints,
+            "time,beatLength,meter,sampleSet,sampleIndex,volume,uninherited,effects",
+            &[
+                ("time", ValueType::Integer),
+                ("beatLength", ValueType::Decimal),
+                ("meter", ValueType::Integer),
+                ("sampleSet", ValueType::Integer),
+                ("sampleIndex", ValueType::Integer),
+                ("volume", ValueType::Integer),
+                ("uninherited", ValueType::Bool),
+                ("effects", ValueType::Integer),
+            ],
+        ),
+        lines_section::<Colours>(
+            SectionName::Colours,
+            "name : red,green,blue",
+            &[
+                ("name", ValueType::String),
+                ("red", ValueType::Integer),
+                ("green", ValueType::Integer),
+                ("blue", ValueType::Integer),
+            ],
+        ),
+        lines_section::<HitObjects>(
+            SectionName::HitObjects,
+            "x,y,time,type,hitSound,objectParams,hitSample",
+            &[
+                ("x", ValueType::Decimal),
+                ("y", ValueType::Decimal),
+                ("time", ValueType::Integer),
+                ("type", ValueType::Integer),
+                ("hitSound", ValueType::Integer),
+                ("objectParams", ValueType::String),
+                ("hitSample", ValueType::String),
+            ],
+        ),
+    ]
+}
+
+#[cfg(feature = "serde")]
+fn json_value(value: &FieldValue) -> Value {
+    match value {
+        FieldValue::Integer(value) => json!(value),
+        FieldValue::Decimal(value) => json!(value.to_f64()),
+        FieldValue::Bool(value) => json!(value),
+        FieldValue::String(value) => json!(value),
+        FieldValue::Path(value) => json!(value.to_string_lossy()),
+        FieldValue::IntegerList(values) => json!(values),
+        FieldValue::StringList(values) => json!(values),
+    }
+}
+
+/// Describes the file format as a JSON schema of an object with a property for each section.
+/// - `key: value` sections are objects with a property for each field, and the other sections are arrays of the lines.
+/// - The versions a section or field is written in are in the `x-versions` keyword, the format of the lines in `x-line-format`, and the fields of the lines in `x-line-fields` in the order they are written.
+#[cfg(feature = "serde")]
+pub fn describe() -> String {
+    let mut properties = Map::new();
+
+    for section in sections() {
+        let mut schema = match section.kind {
+            SectionKind::Fields => {
+                let mut fields = Map::new();
+                for field in &section.fields {
+                    let mut schema = field.value_type.json_schema();
+                    schema.insert("x-versions".to_string(), json!(field.versions));
+                    if let Some(default) = &field.default {
+                        schema.insert("default".to_string(), json_value(default));
+                    }
+                    fields.insert(field.name.to_string(), Value::Object(schema));
+                }
+
+                json!({
+                    "type": "object",
+                    "properties": fields,
+                    "additionalProperties": false,
+                })
+            }
+            SectionKind::Lines => {
+                let line_fields = section
+                    .line_fields
+                    .iter()
+                    .map(|field| {
+                        let mut schema = field.value_type.json_schema();
+                        schema.insert("name".to_string(), json!(field.name));
+                        Value::Object(schema)
+                    })
+                    .collect::<Vec<_>>();
+
+                json!({
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "x-line-format": section.line_format,
+                    "x-line-fields": line_fields,
+                })
+            }
+        };
+        schema["x-versions"] = json!(section.versions);
+
+        properties.insert(section.name.as_str().to_string(), schema);
+    }
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": ".osu file",
+        "type": "object",
+        "properties": properties,
+    })
+    .to_string()
+}
@@ -0,0 +1,446 @@
+//! Module for simulating the HP bar in osu!standard.
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal_macros::dec;
+
+use super::{Judgement, TimedJudgement};
+use crate::{
+    analysis::objects::field,
+    osu_file::{events::Event, general::Mode, hitobjects::HitObjectParams, OsuFile, Version},
+};
+
+/// Size of the HP bar, all of the HP values below are in this scale.
+const HP_BAR_MAXIMUM: f64 = 200.0;
+const HP_HIT_300: f64 = 6.0;
+const HP_HIT_100: f64 = 2.2;
+const HP_HIT_50: f64 = 0.4;
+const HP_SLIDER_EDGE: f64 = 4.0;
+const HP_SLIDER_TICK: f64 = 3.0;
+const HP_SPINNER_SPIN: f64 = 1.7;
+const HP_SPINNER_BONUS: f64 = 2.0;
+const HP_COMBO_GEKI: f64 = 14.0;
+const HP_COMBO_KATU: f64 = 10.0;
+const HP_COMBO_MU: f64 = 6.0;
+
+/// Drain rate the search starts from, in HP per millisecond.
+const INITIAL_DRAIN_RATE: f64 = 0.05;
+
+/// Upper limit of attempts when searching for the drain rate, so broken beatmaps can't loop forever.
+const MAX_DRAIN_RATE_ITERATIONS: usize = 10_000;
+
+/// HP at a point in time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HpPoint {
+    /// Time in milliseconds.
+    pub time: f64,
+    /// HP from `0.0` to `1.0`.
+    pub hp: f64,
+}
+
+/// Hitobject reduced to what the simulation needs.
+#[derive(Clone, Debug, PartialEq)]
+struct SimObject {
+    start: f64,
+    end: f64,
+    /// The next object starts a new combo, or this is the last object.
+    combo_end: bool,
+    /// Times of the slider head, each repeat and the tail.
+    slider_edges: Vec<f64>,
+    slider_ticks: Vec<f64>,
+    spinner_spins: u64,
+}
+
+/// Simulation of the HP bar, following osu!stable's algorithm.
+/// - Passive drain is searched for when created, so a full combo of `300`s can just about keep the HP up with the beatmap's `HP` setting.
+/// - Drain stops during breaks, before the first hitobject and after the last hitobject.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HpSimulation {
+    version: Version,
+    hp_drain_rate: f64,
+    drain_rate: f64,
+    hp_multiplier_normal: f64,
+    hp_multiplier_combo_end: f64,
+    objects: Vec<SimObject>,
+    breaks: Vec<(f64, f64)>,
+}
+
+/// Scales `difficulty` from `0` ~ `5` ~ `10` into `min` ~ `mid` ~ `max`.
+fn difficulty_range(difficulty: f64, min: f64, mid: f64, max: f64) -> f64 {
+    if difficulty > 5.0 {
+        mid + (max - mid) * (difficulty - 5.0) / 5.0
+    } else if difficulty < 5.0 {
+        mid - (mid - min) * (5.0 - difficulty) / 5.0
+    } else {
+        mid
+    }
+}
+
+impl HpSimulation {
+    /// Creates the simulation for the beatmap.
+    /// - Returns `None` if the beatmap isn't made for osu!standard.
+    pub fn new(osu_file: &OsuFile) -> Option<Self> {
+        if osu_file.mode_or_default() != Mode::Osu {
+            return None;
+        }
+
+        let hp_drain_rate = match &osu_file.difficulty {
+            Some(difficulty) => field(&difficulty.hp_drain_rate, dec!(5)),
+            None => dec!(5),
+        }
+        .to_f64()
+        .unwrap_or(5.0);
+
+        let timed_objects = osu_file.timed_objects();
+        let objects = timed_objects
+            .iter()
+            .enumerate()
+            .map(|(i, timed)| {
+                let start = timed.obj.time as f64;
+                let (slider_edges, slider_ticks) = match &timed.slider {
+                    Some(slider) => (slider.edge_times(start), slider.tick_times(start)),
+                    None => (Vec::new(), Vec::new()),
+                };
+                let spinner_spins = match timed.obj.obj_params {
+                    HitObjectParams::Spinner { end_time } => {
+                        osu_file.required_spins(end_time.saturating_sub(timed.obj.time) as u64)
+                    }
+                    _ => 0,
+                };
+
+                SimObject {
+                    start,
+                    end: timed.end_time(),
+                    combo_end: timed_objects
+                        .get(i + 1)
+                        .map(|next| next.obj.new_combo)
+                        .unwrap_or(true),
+                    slider_edges,
+                    slider_ticks,
+                    spinner_spins,
+                }
+            })
+            .collect();
+
+        let mut breaks = match &osu_file.events {
+            Some(events) => events
+                .0
+                .iter()
+                .filter_map(|e| match e {
+                    Event::Break(b) => Some((b.start_time as f64, b.end_time as f64)),
+                    _ => None,
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        breaks.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        let mut simulation = HpSimulation {
+            version: osu_file.version,
+            hp_drain_rate,
+            drain_rate: 0.0,
+            hp_multiplier_normal: 1.0,
+            hp_multiplier_combo_end: 1.0,
+            objects,
+            breaks,
+        };
+        simulation.compute_drain_rate();
+
+        Some(simulation)
+    }
+
+    /// Passive drain in HP per millisecond, where `1.0` is the full HP bar.
+    pub fn drain_rate(&self) -> f64 {
+        self.drain_rate / HP_BAR_MAXIMUM
+    }
+
+    /// HP lost from a [`Judgement::Miss`], where `1.0` is the full HP bar.
+    pub fn miss_penalty(&self) -> f64 {
+        difficulty_range(self.hp_drain_rate, 6.0, 25.0, 40.0) / HP_BAR_MAXIMUM
+    }
+
+    fn slider_miss_penalty(&self) -> f64 {
+        difficulty_range(self.hp_drain_rate, 4.0, 15.0, 28.0) / HP_BAR_MAXIMUM
+    }
+
+    /// Searches for the drain rate by playing the beatmap perfectly, lowering the drain until the HP stays above the limits for the `HP` setting.
+    fn compute_drain_rate(&mut self) {
+        let lowest_hp_ever = difficulty_range(self.hp_drain_rate, 195.0, 160.0, 60.0);
+        let lowest_hp_combo_end = difficulty_range(self.hp_drain_rate, 198.0, 170.0, 80.0);
+        let lowest_hp_end = difficulty_range(self.hp_drain_rate, 198.0, 180.0, 80.0);
+        let hp_recovery_available = difficulty_range(self.hp_drain_rate, 8.0, 4.0, 0.0);
+
+        let drain_start = match self.objects.first() {
+            Some(first) => first.start,
+            None => return,
+        };
+
+        let mut test_drop = INITIAL_DRAIN_RATE;
+
+        for _ in 0..MAX_DRAIN_RATE_ITERATIONS {
+            let mut hp = HP_BAR_MAXIMUM;
+            let mut hp_uncapped = HP_BAR_MAXIMUM;
+            let mut last_time = drain_start;
+            let mut fail = false;
+            let mut combo_too_low_count = 0;
+
+            let reduce = |hp: &mut f64, hp_uncapped: &mut f64, amount: f64| {
+                *hp = (*hp - amount).max(0.0);
+                *hp_uncapped = (*hp_uncapped - amount).max(0.0);
+            };
+            let increase = |hp: &mut f64, hp_uncapped: &mut f64, amount: f64| {
+                *hp = (*hp + amount).min(HP_BAR_MAXIMUM);
+                *hp_uncapped += amount;
+            };
+
+            for obj in &self.objects {
+                let break_time = self.break_time_between(last_time, obj.start);
+
+                reduce(
+                    &mut hp,
+                    &mut hp_uncapped,
+                    test_drop * (obj.start - last_time - break_time),
+                );
+                last_time = obj.end;
+
+                if hp <= lowest_hp_ever {
+                    fail = true;
+                    test_drop *= 0.96;
+                    break;
+                }
+
+                let hp_reduction = test_drop * (obj.end - obj.start);
+                let hp_overkill = (hp_reduction - hp).max(0.0);
+                reduce(&mut hp, &mut hp_uncapped, hp_reduction);
+
+                let nested = obj.slider_edges.len() as f64 * HP_SLIDER_EDGE
+                    + obj.slider_ticks.len() as f64 * HP_SLIDER_TICK
+                    + obj.spinner_spins as f64 * HP_SPINNER_SPIN;
+                increase(
+                    &mut hp,
+                    &mut hp_uncapped,
+                    self.hp_multiplier_normal * nested,
+                );
+
+                if hp_overkill > 0.0 && hp - hp_overkill <= lowest_hp_ever {
+                    fail = true;
+                    test_drop *= 0.96;
+                    break;
+                }
+
+                if obj.combo_end {
+                    increase(
+                        &mut hp,
+                        &mut hp_uncapped,
+                        self.hp_multiplier_combo_end * HP_COMBO_GEKI
+                            + self.hp_multiplier_normal * HP_HIT_300,
+                    );
+
+                    if hp < lowest_hp_combo_end {
+                        combo_too_low_count += 1;
+
+                        if combo_too_low_count > 2 {
+                            self.hp_multiplier_combo_end *= 1.07;
+                            self.hp_multiplier_normal *= 1.03;
+                            fail = true;
+                            break;
+                        }
+                    }
+                } else {
+                    increase(
+                        &mut hp,
+                        &mut hp_uncapped,
+                        self.hp_multiplier_normal * HP_HIT_300,
+                    );
+                }
+            }
+
+            if !fail && hp < lowest_hp_end {
+                fail = true;
+                test_drop *= 0.94;
+                self.hp_multiplier_combo_end *= 1.01;
+                self.hp_multiplier_normal *= 1.01;
+            }
+
+            let recovery = (hp_uncapped - HP_BAR_MAXIMUM) / self.objects.len() as f64;
+            if !fail && recovery < hp_recovery_available {
+                fail = true;
+                test_drop *= 0.96;
+                self.hp_multiplier_combo_end *= 1.02;
+                self.hp_multiplier_normal *= 1.01;
+            }
+
+            if !fail {
+                break;
+            }
+        }
+
+        self.drain_rate = test_drop;
+    }
+
+    /// Time taken by the first break that's fully between `from` and `to`.
+    /// - From version `8`, drain also stops between the end of the previous object and the break.
+    fn break_time_between(&self, from: f64, to: f64) -> f64 {
+        self.breaks
+            .iter()
+            .find(|(start, end)| *start >= from && *end <= to)
+            .map_or(0.0, |(start, end)| {
+                if self.version < 8 {
+                    end - start
+                } else {
+                    end - from
+                }
+            })
+    }
+
+    /// Time between `from` and `to` where the HP drains.
+    fn drain_duration(&self, from: f64, to: f64) -> f64 {
+        let (drain_start, drain_end) = match (self.objects.first(), self.objects.last()) {
+            (Some(first), Some(last)) => (first.start, last.end),
+            _ => return 0.0,
+        };
+
+        let from = from.max(drain_start);
+        let to = to.min(drain_end);
+
+        if to <= from {
+            return 0.0;
+        }
+
+        let in_breaks = self
+            .breaks
+            .iter()
+            .map(|(start, end)| (to.min(*end) - from.max(*start)).max(0.0))
+            .sum::<f64>();
+
+        (to - from - in_breaks).max(0.0)
+    }
+
+    fn hp_change(&self, judgement: Judgement) -> f64 {
+        let normal = |hp: f64| self.hp_multiplier_normal * hp / HP_BAR_MAXIMUM;
+
+        match judgement {
+            Judgement::Hit300 => normal(HP_HIT_300),
+            Judgement::Hit100 => normal(HP_HIT_100),
+            Judgement::Hit50 => normal(HP_HIT_50),
+            Judgement::Miss => -self.miss_penalty(),
+            Judgement::SliderEdge => normal(HP_SLIDER_EDGE),
+            Judgement::SliderTick => normal(HP_SLIDER_TICK),
+            Judgement::SliderMiss => -self.slider_miss_penalty(),
+            Judgement::SpinnerSpin => normal(HP_SPINNER_SPIN),
+            Judgement::SpinnerBonus => normal(HP_SPINNER_BONUS),
+        }
+    }
+
+    /// Simulates the HP over the judgements of a play.
+    /// - `judgements` are expected to be sorted by time, with one [object result][Judgement::is_object_result] for each hitobject in order.
+    /// - The combo end bonus is given after the object result of the last hitobject in a combo.
+    /// - Returns the HP at the start, after each judgement, and at the start and end of breaks.
+    /// - The HP drains linearly between the points.
+    pub fn hp_timeline(&self, judgements: &[TimedJudgement]) -> Vec<HpPoint> {
+        let start_time = match (self.objects.first(), judgements.first()) {
+            (Some(obj), Some(j)) => obj.start.min(j.time),
+            (Some(obj), None) => obj.start,
+            (None, Some(j)) => j.time,
+            (None, None) => return Vec::new(),
+        };
+
+        let mut hp = 1.0;
+        let mut last_time = start_time;
+        let mut points = vec![HpPoint {
+            time: start_time,
+            hp,
+        }];
+
+        let mut object_index = 0;
+        // worst object result in the current combo, with `0` being a `300`
+        let mut combo_worst = 0;
+
+        for j in judgements {
+            for (start, end) in &self.breaks {
+                for boundary in [*start, *end] {
+                    if boundary > last_time && boundary < j.time {
+                        points.push(HpPoint {
+                            time: boundary,
+                            hp: (hp - self.drain_rate() * self.drain_duration(last_time, boundary))
+                                .max(0.0),
+                        });
+                    }
+                }
+            }
+
+            hp = (hp - self.drain_rate() * self.drain_duration(last_time, j.time)).max(0.0);
+            hp = (hp + self.hp_change(j.judgement)).clamp(0.0, 1.0);
+            last_time = last_time.max(j.time);
+
+            if j.judgement.is_object_result() {
+                combo_worst = combo_worst.max(match j.judgement {
+                    Judgement::Hit300 => 0,
+                    Judgement::Hit100 => 1,
+                    Judgement::Hit50 => 2,
+                    _ => 3,
+                });
+
+                if matches!(self.objects.get(object_index), Some(o) if o.combo_end) {
+                    let bonus = match combo_worst {
+                        0 => HP_COMBO_GEKI,
+                        1 => HP_COMBO_KATU,
+                        2 => HP_COMBO_MU,
+                        _ => 0.0,
+                    };
+                    hp = (hp + self.hp_multiplier_combo_end * bonus / HP_BAR_MAXIMUM).min(1.0);
+                    combo_worst = 0;
+                }
+
+                object_index += 1;
+            }
+
+            points.push(HpPoint { time: j.time, hp });
+        }
+
+        points
+    }
+
+    /// Judgements of a perfect play, with every hitobject and nested object hit.
+    /// - Spinners are spun just enough to be cleared, without any bonus spins.
+    pub fn perfect_judgements(&self) -> Vec<TimedJudgement> {
+        let mut judgements = Vec::new();
+
+        let mut push =
+            |time: f64, judgement: Judgement| judgements.push(TimedJudgement { time, judgement });
+
+        for obj in &self.objects {
+            for edge in &obj.slider_edges {
+                push(*edge, Judgement::SliderEdge);
+            }
+            for tick in &obj.slider_ticks {
+                push(*tick, Judgement::SliderTick);
+            }
+            for spin in 1..=obj.spinner_spins {
+                let time =
+                    obj.start + (obj.end - obj.start) * spin as f64 / obj.spinner_spins as f64;
+                push(time, Judgement::SpinnerSpin);
+            }
+
+            push(obj.end, Judgement::Hit300);
+        }
+
+        // stable sort keeps the object result after the nested objects at the same time
+        judgements.sort_by(|a, b| a.time.total_cmp(&b.time));
+
+        judgements
+    }
+}
+
+impl OsuFile {
+    /// Creates a [`HpSimulation`] for the beatmap.
+    /// - Returns `None` if the beatmap isn't made for osu!standard.
+    pub fn hp_simulation(&self) -> Option<HpSimulation> {
+        HpSimulation::new(self)
+    }
+
+    /// Simulates the HP over the judgements of a play.
+    /// - Shorthand for [`HpSimulation::hp_timeline`], returning `None` if the beatmap isn't made for osu!standard.
+    pub fn hp_timeline(&self, judgements: &[TimedJudgement]) -> Option<Vec<HpPoint>> {
+        Some(self.hp_simulation()?.hp_timeline(judgements))
+    }
+}
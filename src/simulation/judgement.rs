@@ -0,0 +1,69 @@
+//! Module for the judgements given to the player during gameplay.
+
+/// A judgement given to the player during gameplay.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Judgement {
+    /// `300` on a hitobject.
+    Hit300,
+    /// `100` on a hitobject.
+    Hit100,
+    /// `50` on a hitobject.
+    Hit50,
+    /// Missed a hitobject.
+    Miss,
+    /// Hit the head, a repeat or the tail of a slider.
+    SliderEdge,
+    /// Hit a slider tick.
+    SliderTick,
+    /// Missed the head, a repeat, the tail or a tick of a slider.
+    SliderMiss,
+    /// A full spin of a spinner before it's cleared.
+    SpinnerSpin,
+    /// A full spin of a spinner after it's cleared.
+    SpinnerBonus,
+}
+
+impl Judgement {
+    /// Returns `true` if the judgement is the result of a whole hitobject.
+    pub fn is_object_result(self) -> bool {
+        matches!(
+            self,
+            Judgement::Hit300 | Judgement::Hit100 | Judgement::Hit50 | Judgement::Miss
+        )
+    }
+}
+
+/// A [`Judgement`] and when it happened.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimedJudgement {
+    /// Time in milliseconds.
+    pub time: f64,
+    pub judgement: Judgement,
+}
+
+/// Accuracy of the object results in the judgements, from `0.0` to `1.0`.
+/// - Uses osu!standard's formula, weighting `300`, `100` and `50` by their score.
+/// - Judgements of nested objects are ignored.
+/// - Returns `None` if there are no object results.
+pub fn accuracy<I: IntoIterator<Item = Judgement>>(judgements: I) -> Option<f64> {
+    let (score, count) = judgements
+        .into_iter()
+        .filter(|j| j.is_object_result())
+        .fold((0u64, 0u64), |(score, count), j| {
+            let value = match j {
+                Judgement::Hit300 => 300,
+                Judgement::Hit100 => 100,
+                Judgement::Hit50 => 50,
+                _ => 0,
+            };
+
+            (score + value, count + 1)
+        });
+
+    if count == 0 {
+        None
+    } else {
+        Some(score as f64 / (count * 300) as f64)
+    }
+}
@@ -0,0 +1,10 @@
+//! Module for simulating gameplay of a beatmap.
+//! - Follows osu!stable's gameplay rules, using only the parsed data.
+
+pub mod hp;
+pub mod judgement;
+pub mod replay;
+
+pub use hp::*;
+pub use judgement::*;
+pub use replay::*;
@@ -0,0 +1,389 @@
+//! Module for aligning the inputs of a replay with the hitobjects of a beatmap.
+//! - Decoding `.osr` files isn't done by this crate, the frames are expected to be already decoded with absolute times.
+
+use std::f64::consts::TAU;
+use std::ops::{BitOr, BitOrAssign};
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal_macros::dec;
+
+use super::Judgement;
+use crate::{
+    analysis::{
+        objects::{field, TimedObject},
+        Mods,
+    },
+    osu_file::{general::Mode, hitobjects::HitObjectParams, OsuFile},
+};
+
+/// Height of the playfield in `osu!pixels`, used to flip the objects with hard rock.
+const PLAYFIELD_HEIGHT: f64 = 384.0;
+
+/// Centre of the playfield, which spinners are spun around.
+const PLAYFIELD_CENTRE: (f64, f64) = (256.0, 192.0);
+
+/// Keys held down in a [`ReplayFrame`], stored as the same bit flags as `.osr` files.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Keys(pub u32);
+
+impl Keys {
+    pub const NONE: Keys = Keys(0);
+    pub const M1: Keys = Keys(1 << 0);
+    pub const M2: Keys = Keys(1 << 1);
+    /// Always set together with [`M1`][Self::M1] by osu!.
+    pub const K1: Keys = Keys(1 << 2);
+    /// Always set together with [`M2`][Self::M2] by osu!.
+    pub const K2: Keys = Keys(1 << 3);
+    pub const SMOKE: Keys = Keys(1 << 4);
+
+    const CLICKS: Keys = Keys(Self::M1.0 | Self::M2.0 | Self::K1.0 | Self::K2.0);
+
+    /// Returns `true` if all of the keys in `other` are held.
+    pub fn contains(self, other: Keys) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if any of the keys used to hit objects are held.
+    pub fn is_clicking(self) -> bool {
+        self.0 & Self::CLICKS.0 != 0
+    }
+
+    /// Returns `true` if a key used to hit objects is held that wasn't held in `previous`.
+    pub fn is_new_click(self, previous: Keys) -> bool {
+        self.0 & !previous.0 & Self::CLICKS.0 != 0
+    }
+}
+
+impl BitOr for Keys {
+    type Output = Keys;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Keys(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Keys {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// A single frame of a replay.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ReplayFrame {
+    /// Time in milliseconds from the start of the beatmap.
+    pub time: f64,
+    /// Cursor x position in `osu!pixels`.
+    pub x: f64,
+    /// Cursor y position in `osu!pixels`.
+    pub y: f64,
+    pub keys: Keys,
+}
+
+/// Inputs of a play, used with [`align`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Replay {
+    pub mods: Mods,
+    /// Frames sorted by time.
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    /// Creates a replay from the mods and the frames.
+    pub fn new(mods: Mods, frames: Vec<ReplayFrame>) -> Self {
+        Self { mods, frames }
+    }
+
+    /// Returns the last frame at or before `time`.
+    fn frame_at(&self, time: f64) -> Option<&ReplayFrame> {
+        let index = self.frames.partition_point(|f| f.time <= time);
+        index.checked_sub(1).map(|i| &self.frames[i])
+    }
+}
+
+/// A [`Judgement`] given to a hitobject from a replay.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AlignedJudgement {
+    /// Index of the hitobject in [`HitObjects`][crate::osu_file::hitobjects::HitObjects].
+    pub object_index: usize,
+    /// Time of the judgement in milliseconds.
+    pub time: f64,
+    pub judgement: Judgement,
+    /// How early or late the object was hit in milliseconds, negative being early.
+    /// - Only set for hitcircles and slider heads that were hit.
+    pub offset: Option<f64>,
+}
+
+impl From<AlignedJudgement> for super::TimedJudgement {
+    fn from(j: AlignedJudgement) -> Self {
+        Self {
+            time: j.time,
+            judgement: j.judgement,
+        }
+    }
+}
+
+/// Hit windows in milliseconds on either side of the object time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct HitWindows {
+    hit_300: f64,
+    hit_100: f64,
+    hit_50: f64,
+}
+
+impl HitWindows {
+    fn new(od: f64) -> Self {
+        Self {
+            hit_300: 80.0 - 6.0 * od,
+            hit_100: 140.0 - 8.0 * od,
+            hit_50: 200.0 - 10.0 * od,
+        }
+    }
+
+    fn judgement(&self, offset: f64) -> Option<Judgement> {
+        let offset = offset.abs();
+
+        if offset <= self.hit_300 {
+            Some(Judgement::Hit300)
+        } else if offset <= self.hit_100 {
+            Some(Judgement::Hit100)
+        } else if offset <= self.hit_50 {
+            Some(Judgement::Hit50)
+        } else {
+            None
+        }
+    }
+}
+
+/// Applies hard rock and easy to a difficulty setting.
+fn apply_mods(value: f64, mods: Mods, hard_rock_multiplier: f64) -> f64 {
+    if mods.contains(Mods::HARD_ROCK) {
+        (value * hard_rock_multiplier).min(10.0)
+    } else if mods.contains(Mods::EASY) {
+        value * 0.5
+    } else {
+        value
+    }
+}
+
+/// Assigns judgements to the hitobjects from the inputs of an osu!standard replay.
+/// - Hitcircles and slider heads are hit by a new click inside of the circle and inside of the `50` hit window.
+/// - Only the earliest hitobject that isn't judged yet can be hit, like the note lock in osu!stable.
+/// - Slider repeats, ticks and tails are hit if a key is held at their time, the cursor position isn't checked.
+/// - Spinners count the full spins around the centre of the playfield while a key is held.
+/// - Stacking isn't applied to the object positions.
+/// - Returns `None` if the beatmap isn't made for osu!standard.
+pub fn align(replay: &Replay, osu_file: &OsuFile) -> Option<Vec<AlignedJudgement>> {
+    if osu_file.mode_or_default() != Mode::Osu {
+        return None;
+    }
+
+    let (cs, od) = match &osu_file.difficulty {
+        Some(difficulty) => (
+            field(&difficulty.circle_size, dec!(5)),
+            field(&difficulty.overall_difficulty, dec!(5)),
+        ),
+        None => (dec!(5), dec!(5)),
+    };
+    let cs = apply_mods(cs.to_f64().unwrap_or(5.0), replay.mods, 1.3);
+    let od = apply_mods(od.to_f64().unwrap_or(5.0), replay.mods, 1.4);
+
+    let radius = 54.4 - 4.48 * cs;
+    let windows = HitWindows::new(od);
+
+    let objects = osu_file.timed_objects();
+    let position = |obj: &TimedObject| {
+        let x = obj.obj.position.x.to_f64().unwrap_or_default();
+        let y = obj.obj.position.y.to_f64().unwrap_or_default();
+
+        if replay.mods.contains(Mods::HARD_ROCK) {
+            (x, PLAYFIELD_HEIGHT - y)
+        } else {
+            (x, y)
+        }
+    };
+
+    // time and offset of the click that hit each hitcircle and slider head
+    let mut heads: Vec<Option<(f64, f64)>> = vec![None; objects.len()];
+    let clickable = objects
+        .iter()
+        .enumerate()
+        .filter(|(_, obj)| {
+            matches!(
+                obj.obj.obj_params,
+                HitObjectParams::HitCircle | HitObjectParams::Slider(_)
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut next = 0;
+    let mut previous_keys = Keys::NONE;
+
+    for frame in &replay.frames {
+        let new_click = frame.keys.is_new_click(previous_keys);
+        previous_keys = frame.keys;
+
+        if !new_click {
+            continue;
+        }
+
+        // objects that can't be hit anymore
+        while let Some((_, obj)) = clickable.get(next) {
+            if frame.time > obj.obj.time as f64 + windows.hit_50 {
+                next += 1;
+            } else {
+                break;
+            }
+        }
+
+        if let Some((index, obj)) = clickable.get(next) {
+            let offset = frame.time - obj.obj.time as f64;
+            let (x, y) = position(obj);
+
+            if offset.abs() <= windows.hit_50 && (frame.x - x).hypot(frame.y - y) <= radius {
+                heads[*index] = Some((frame.time, offset));
+                next += 1;
+            }
+        }
+    }
+
+    let is_held = |time: f64| matches!(replay.frame_at(time), Some(f) if f.keys.is_clicking());
+
+    let mut judgements = Vec::new();
+
+    for (object_index, obj) in objects.iter().enumerate() {
+        let start = obj.obj.time as f64;
+        let mut push = |time: f64, judgement: Judgement, offset: Option<f64>| {
+            judgements.push(AlignedJudgement {
+                object_index,
+                time,
+                judgement,
+                offset,
+            })
+        };
+
+        match (&obj.obj.obj_params, &obj.slider) {
+            (HitObjectParams::Slider(_), Some(slider)) => {
+                let mut hits = 0;
+                let mut total = 1;
+
+                match heads[object_index] {
+                    Some((time, offset)) => {
+                        hits += 1;
+                        push(time, Judgement::SliderEdge, Some(offset));
+                    }
+                    None => push(start + windows.hit_50, Judgement::SliderMiss, None),
+                }
+
+                let edges = slider.edge_times(start).into_iter().skip(1);
+                let ticks = slider.tick_times(start).into_iter();
+                let mut nested = edges
+                    .map(|t| (t, Judgement::SliderEdge))
+                    .chain(ticks.map(|t| (t, Judgement::SliderTick)))
+                    .collect::<Vec<_>>();
+                nested.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+                for (time, judgement) in nested {
+                    total += 1;
+
+                    if is_held(time) {
+                        hits += 1;
+                        push(time, judgement, None);
+                    } else {
+                        push(time, Judgement::SliderMiss, None);
+                    }
+                }
+
+                let judgement = if hits == total {
+                    Judgement::Hit300
+                } else if hits * 2 >= total {
+                    Judgement::Hit100
+                } else if hits > 0 {
+                    Judgement::Hit50
+                } else {
+                    Judgement::Miss
+                };
+                push(obj.end_time(), judgement, None);
+            }
+            (HitObjectParams::Spinner { end_time }, _) => {
+                let end = *end_time as f64;
+                let required =
+                    osu_file.required_spins(end_time.saturating_sub(obj.obj.time) as u64);
+
+                let mut rotation: f64 = 0.0;
+                let mut spins = 0;
+                let mut last_angle = None;
+
+                for frame in replay
+                    .frames
+                    .iter()
+                    .filter(|f| f.time >= start && f.time <= end)
+                {
+                    if !frame.keys.is_clicking() {
+                        last_angle = None;
+                        continue;
+                    }
+
+                    let angle = (frame.y - PLAYFIELD_CENTRE.1).atan2(frame.x - PLAYFIELD_CENTRE.0);
+
+                    if let Some(last_angle) = last_angle {
+                        let mut delta = angle - last_angle;
+                        if delta > TAU / 2.0 {
+                            delta -= TAU;
+                        } else if delta < -TAU / 2.0 {
+                            delta += TAU;
+                        }
+                        rotation += delta;
+                    }
+                    last_angle = Some(angle);
+
+                    while rotation.abs() >= (spins + 1) as f64 * TAU {
+                        spins += 1;
+
+                        let judgement = if spins <= required {
+                            Judgement::SpinnerSpin
+                        } else {
+                            Judgement::SpinnerBonus
+                        };
+                        push(frame.time, judgement, None);
+                    }
+                }
+
+                let progress = if required == 0 {
+                    1.0
+                } else {
+                    spins as f64 / required as f64
+                };
+                let judgement = if progress >= 1.0 {
+                    Judgement::Hit300
+                } else if progress > 0.9 {
+                    Judgement::Hit100
+                } else if progress > 0.75 {
+                    Judgement::Hit50
+                } else {
+                    Judgement::Miss
+                };
+                push(end, judgement, None);
+            }
+            _ => match heads[object_index] {
+                Some((time, offset)) => {
+                    let judgement = windows.judgement(offset).unwrap_or(Judgement::Miss);
+                    push(time, judgement, Some(offset));
+                }
+                None => push(start + windows.hit_50, Judgement::Miss, None),
+            },
+        }
+    }
+
+    // stable sort keeps the nested objects before the object result at the same time
+    judgements.sort_by(|a, b| a.time.total_cmp(&b.time));
+
+    Some(judgements)
+}
+
+impl OsuFile {
+    /// Assigns judgements to the hitobjects from the inputs of an osu!standard replay.
+    /// - Shorthand for [`align`].
+    pub fn align_replay(&self, replay: &Replay) -> Option<Vec<AlignedJudgement>> {
+        align(replay, self)
+    }
+}
@@ -0,0 +1,64 @@
+use pretty_assertions::assert_eq;
+use rust_decimal_macros::dec;
+
+use crate::{analysis::CachedAnalysis, osu_file::OsuFile};
+
+const OSU_FILE: &str = "osu file format v14
+
+[Difficulty]
+SliderMultiplier:1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+256,192,0,1,0,0:0:0:0:
+256,192,1000,2,0,L|356:192,1,100
+256,192,2000,12,0,2500,0:0:0:0:";
+
+#[test]
+fn cached_analysis() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+    let mut cached = CachedAnalysis::new(osu_file.clone());
+
+    assert_eq!(cached.end_times(), &[0.0, 1500.0, 2500.0]);
+    assert_eq!(cached.max_combo(), osu_file.max_combo());
+    assert_eq!(cached.display_bpm().unwrap().most_common, dec!(120));
+    assert_eq!(cached.stack_heights(), &[0, 0, 0]);
+    assert_eq!(
+        cached.timeline().collect::<Vec<_>>(),
+        osu_file.timeline().collect::<Vec<_>>()
+    );
+
+    // the slider is faster without the `SliderMultiplier`
+    cached.edit(|osu_file| *osu_file.difficulty_mut() = None);
+    assert!((cached.end_times()[1] - (1000.0 + 500.0 / 1.4)).abs() < 1e-6);
+
+    // the slider is close enough in time to stack the circle under it
+    cached.edit(|osu_file| osu_file.hitobjects_mut().as_mut().unwrap().0[1].time = 500);
+    assert_eq!(cached.stack_heights(), &[1, 0, 0]);
+
+    cached.edit(|osu_file| {
+        let hitobjects = osu_file.hitobjects_mut().as_mut().unwrap();
+        hitobjects.0.truncate(1);
+    });
+    assert_eq!(cached.end_times(), &[0.0]);
+    assert_eq!(cached.max_combo(), 1);
+    assert_eq!(cached.stack_heights(), &[0]);
+
+    cached.edit(|osu_file| *osu_file.timing_points_mut() = None);
+    assert_eq!(cached.display_bpm(), None);
+
+    // changes through the public fields are seen too
+    let mut cached = CachedAnalysis::new(osu_file.clone());
+    assert_eq!(cached.end_times(), &[0.0, 1500.0, 2500.0]);
+    assert_eq!(cached.timeline().count(), osu_file.timeline().count());
+
+    cached.edit(|osu_file| osu_file.hitobjects.as_mut().unwrap().0.truncate(1));
+    assert_eq!(cached.end_times(), &[0.0]);
+    assert_eq!(cached.max_combo(), 1);
+    assert_eq!(
+        cached.timeline().collect::<Vec<_>>(),
+        cached.osu_file().timeline().collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,23 @@
+use pretty_assertions::assert_eq;
+
+use crate::{analysis::density_graph, osu_file::OsuFile};
+
+#[test]
+fn density_graph_buckets() {
+    let osu_file = "osu file format v14
+
+[HitObjects]
+256,192,0,1,0,0:0:0:0:
+256,192,100,1,0,0:0:0:0:
+256,192,200,1,0,0:0:0:0:
+256,192,240,1,0,0:0:0:0:
+256,192,300,1,0,0:0:0:0:
+256,192,600,12,0,1000,0:0:0:0:
+"
+    .parse::<OsuFile>()
+    .unwrap();
+
+    assert_eq!(density_graph(&osu_file, 4), vec![1.0, 0.25, 0.25, 0.0]);
+    assert_eq!(density_graph(&osu_file, 0), Vec::<f64>::new());
+    assert_eq!(density_graph(&OsuFile::new(14), 2), vec![0.0, 0.0]);
+}
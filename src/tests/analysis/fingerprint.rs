@@ -0,0 +1,80 @@
+use rust_decimal::Decimal;
+
+use crate::{
+    analysis::Fingerprint,
+    osu_file::{hitobjects::HitObject, OsuFile, Position},
+};
+
+fn circles(objs: &[(u32, i32, i32)]) -> Vec<HitObject> {
+    objs.iter()
+        .map(|(time, x, y)| {
+            let mut obj = HitObject::hitcircle_default();
+            obj.time = *time;
+            obj.position = Position::new(Decimal::from(*x), Decimal::from(*y));
+            obj
+        })
+        .collect()
+}
+
+const PATTERN: &[(u32, i32, i32)] = &[
+    (1000, 100, 100),
+    (1250, 200, 100),
+    (1500, 200, 200),
+    (1625, 100, 200),
+    (1750, 100, 300),
+    (2250, 300, 300),
+    (2500, 400, 100),
+    (2625, 400, 100),
+];
+
+#[test]
+fn invariant_to_offset_rate_and_translation() {
+    let i = Fingerprint::new(&circles(PATTERN));
+
+    let moved = PATTERN
+        .iter()
+        .map(|(time, x, y)| (time * 2 + 500, x + 50, y - 20))
+        .collect::<Vec<_>>();
+    let o = Fingerprint::new(&circles(&moved));
+
+    assert_eq!(i, o);
+    assert_eq!(i.hash_u64(), o.hash_u64());
+    assert_eq!(1.0, i.similarity(&o));
+}
+
+#[test]
+fn partial_similarity() {
+    let i = Fingerprint::new(&circles(PATTERN));
+
+    let mut changed = PATTERN.to_vec();
+    changed[7] = (2900, 10, 10);
+    let o = Fingerprint::new(&circles(&changed));
+
+    let similarity = i.similarity(&o);
+    assert!(similarity > 0.0 && similarity < 1.0);
+    assert_ne!(i.hash_u64(), o.hash_u64());
+}
+
+#[test]
+fn metadata_edit() {
+    let i = include_str!("../osu_files/files/v14.osu")
+        .parse::<OsuFile>()
+        .unwrap();
+
+    let mut o = i.clone();
+    if let Some(metadata) = &mut o.metadata {
+        metadata.title = None;
+    }
+
+    assert_eq!(i.fingerprint(), o.fingerprint());
+    assert_eq!(1.0, i.similarity(&o));
+}
+
+#[test]
+fn empty() {
+    let empty = Fingerprint::default();
+    let i = Fingerprint::new(&circles(PATTERN));
+
+    assert_eq!(1.0, empty.similarity(&Fingerprint::default()));
+    assert_eq!(0.0, empty.similarity(&i));
+}
@@ -0,0 +1,43 @@
+use crate::{
+    analysis::{position_heatmap, MAX_RESOLUTION},
+    osu_file::OsuFile,
+};
+
+const OSU_FILE: &str = "osu file format v14
+
+[HitObjects]
+0,0,1000,1,0,0:0:0:0:
+10,10,1500,1,0,0:0:0:0:
+600,-20,2000,1,0,0:0:0:0:
+0,383,2500,2,0,L|511:383,1,511
+256,192,5000,12,0,6000,0:0:0:0:
+";
+
+#[test]
+fn position_heatmap_counts() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+    let heatmap = position_heatmap(&osu_file, 4);
+
+    assert_eq!((heatmap.width, heatmap.height), (4, 3));
+    assert_eq!(heatmap.cell_size(), 128.0);
+    // both circles in the top left, and the circle outside of the playfield in the top right
+    assert_eq!(heatmap.get(0, 0), Some(2));
+    assert_eq!(heatmap.get(3, 0), Some(1));
+    // slider along the bottom edge, sampled once per cell and at its end
+    assert_eq!(heatmap.get(0, 2), Some(1));
+    assert_eq!(heatmap.get(3, 2), Some(2));
+    // spinners aren't counted
+    assert_eq!(heatmap.get(2, 1), Some(0));
+    assert_eq!(heatmap.get(4, 0), None);
+    assert_eq!(heatmap.max(), 2);
+    assert_eq!(heatmap.cells.iter().sum::<u32>(), 8);
+}
+
+#[test]
+fn position_heatmap_huge_resolution() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+    let heatmap = position_heatmap(&osu_file, usize::MAX);
+
+    assert_eq!(heatmap.width, MAX_RESOLUTION);
+    assert_eq!(heatmap.cells.len(), heatmap.width * heatmap.height);
+}
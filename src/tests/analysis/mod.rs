@@ -0,0 +1,8 @@
+mod cache;
+mod density;
+mod fingerprint;
+mod heatmap;
+mod score;
+mod stacking;
+mod timeline;
+mod visibility;
@@ -0,0 +1,75 @@
+use crate::{
+    analysis::{Mods, ScoreMode},
+    osu_file::{general::Mode, OsuFile},
+};
+
+const OSU_FILE: &str = "osu file format v14
+
+[General]
+Mode: 0
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+256,192,1500,1,0,0:0:0:0:
+256,192,2000,2,0,L|256:472,1,280
+256,192,3500,1,0,0:0:0:0:
+";
+
+#[test]
+fn max_combo() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+
+    // 2 circles, slider head + 1 tick + tail, circle
+    assert_eq!(6, osu_file.max_combo());
+}
+
+#[test]
+fn max_score_osu() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+
+    assert_eq!(Some(1606), osu_file.max_score(Mods::NONE, ScoreMode::Osu));
+    assert_eq!(Some(1625), osu_file.max_score(Mods::HIDDEN, ScoreMode::Osu));
+}
+
+#[test]
+fn max_score_other_modes() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+
+    assert_eq!(
+        Some(1_000_000),
+        osu_file.max_score(Mods::NONE, ScoreMode::Mania)
+    );
+    assert_eq!(
+        Some(250_000),
+        osu_file.max_score(Mods::NO_FAIL | Mods::EASY, ScoreMode::Mania)
+    );
+
+    // osu!mania beatmaps can't be played in osu!standard
+    let osu_file = OSU_FILE
+        .replace("Mode: 0", "Mode: 3")
+        .parse::<OsuFile>()
+        .unwrap();
+    assert_eq!(None, osu_file.max_score(Mods::NONE, ScoreMode::Osu));
+}
+
+#[test]
+fn mods_score_multiplier() {
+    let mods = Mods::DOUBLE_TIME | Mods::NIGHTCORE | Mods::HIDDEN;
+
+    assert!(mods.contains(Mods::NIGHTCORE));
+    assert_eq!(
+        rust_decimal_macros::dec!(1.1872),
+        mods.score_multiplier(Mode::Osu)
+    );
+}
@@ -0,0 +1,75 @@
+use pretty_assertions::assert_eq;
+
+use crate::{analysis::stack_heights, osu_file::OsuFile};
+
+fn osu_file(general: &str, hitobjects: &str) -> OsuFile {
+    format!(
+        "osu file format v14
+
+[General]
+{general}
+
+[Difficulty]
+ApproachRate:5
+SliderMultiplier:1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+{hitobjects}"
+    )
+    .parse()
+    .unwrap()
+}
+
+#[test]
+fn stacked_circles() {
+    let osu_file = osu_file(
+        "StackLeniency:0.7",
+        "256,192,0,1,0,0:0:0:0:
+256,192,100,1,0,0:0:0:0:
+256,192,200,1,0,0:0:0:0:
+100,100,300,1,0,0:0:0:0:",
+    );
+
+    assert_eq!(stack_heights(&osu_file), vec![2, 1, 0, 0]);
+}
+
+#[test]
+fn stack_threshold() {
+    // 840ms apart is the most that is stacked at approach rate 5 with a leniency of 0.7
+    let hitobjects = "256,192,0,1,0,0:0:0:0:
+256,192,900,1,0,0:0:0:0:";
+
+    assert_eq!(
+        stack_heights(&osu_file("StackLeniency:0.7", hitobjects)),
+        vec![0, 0]
+    );
+    assert_eq!(
+        stack_heights(&osu_file("StackLeniency:1", hitobjects)),
+        vec![1, 0]
+    );
+}
+
+#[test]
+fn slider_end_stack() {
+    let osu_file = osu_file(
+        "StackLeniency:0.7",
+        "256,192,0,2,0,L|356:192,1,100
+356,192,1000,1,0,0:0:0:0:",
+    );
+
+    assert_eq!(stack_heights(&osu_file), vec![0, -1]);
+}
+
+#[test]
+fn not_osu_standard() {
+    let osu_file = osu_file(
+        "Mode: 1",
+        "256,192,0,1,0,0:0:0:0:
+256,192,100,1,0,0:0:0:0:",
+    );
+
+    assert_eq!(stack_heights(&osu_file), vec![0, 0]);
+}
@@ -0,0 +1,63 @@
+use pretty_assertions::assert_eq;
+
+use crate::{analysis::TimelineItem, osu_file::OsuFile};
+
+#[test]
+fn timeline() {
+    let i = r#"osu file format v14
+
+[Editor]
+Bookmarks: 1500
+
+[Events]
+2,3000,4000
+Sample,500,0,"hit.wav",70
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+1000,-100,4,2,0,100,0,1
+2000,-100,4,2,0,100,0,0
+
+[HitObjects]
+256,192,0,1,0,0:0:0:0:
+256,192,1000,12,0,2500,0:0:0:0:"#;
+    let osu_file = i.parse::<OsuFile>().unwrap();
+
+    let timeline = osu_file
+        .timeline()
+        .map(|entry| {
+            let item = match entry.item {
+                TimelineItem::HitObjectStart { index, .. } => format!("start {index}"),
+                TimelineItem::HitObjectEnd { index, .. } => format!("end {index}"),
+                TimelineItem::BreakStart(_) => "break start".to_string(),
+                TimelineItem::BreakEnd(_) => "break end".to_string(),
+                TimelineItem::TimingChange(_) => "timing".to_string(),
+                TimelineItem::KiaiToggle { enabled, .. } => format!("kiai {enabled}"),
+                TimelineItem::SampleLegacy(_) | TimelineItem::AudioSample(_) => {
+                    "sample".to_string()
+                }
+                TimelineItem::Bookmark => "bookmark".to_string(),
+            };
+
+            (entry.time, item)
+        })
+        .collect::<Vec<_>>();
+
+    let expected = [
+        (0.0, "timing"),
+        (0.0, "start 0"),
+        (500.0, "sample"),
+        (1000.0, "timing"),
+        (1000.0, "kiai true"),
+        (1000.0, "start 1"),
+        (1500.0, "bookmark"),
+        (2000.0, "timing"),
+        (2000.0, "kiai false"),
+        (2500.0, "end 1"),
+        (3000.0, "break start"),
+        (4000.0, "break end"),
+    ]
+    .map(|(time, item)| (time, item.to_string()));
+
+    assert_eq!(expected.to_vec(), timeline);
+}
@@ -0,0 +1,65 @@
+use crate::{
+    analysis::{effective_approach_rate, visibility, Mods},
+    osu_file::OsuFile,
+};
+
+fn osu_file(difficulty: &str) -> OsuFile {
+    format!(
+        "osu file format v14
+
+[Difficulty]
+{difficulty}
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+256,192,3000,1,0,0:0:0:0:"
+    )
+    .parse()
+    .unwrap()
+}
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{a} != {b}");
+}
+
+#[test]
+fn approach_rate() {
+    let osu_file = osu_file("OverallDifficulty:8\nApproachRate:9");
+    let objects = visibility(&osu_file, Mods::NONE);
+
+    assert_eq!(objects.len(), 2);
+    assert_close(objects[0].appear_time, 1000.0 - 600.0);
+    assert_close(objects[0].fade_in, 400.0);
+    assert_close(objects[1].hit_time, 3000.0);
+
+    // hard rock can't raise the approach rate above 10
+    let objects = visibility(&osu_file, Mods::HARD_ROCK);
+    assert_close(objects[0].appear_time, 1000.0 - 450.0);
+    assert_close(objects[0].fade_in, 400.0);
+
+    let objects = visibility(&osu_file, Mods::HIDDEN | Mods::EASY);
+    assert_close(objects[0].appear_time, 1000.0 - 1200.0 - 600.0 * 0.5 / 5.0);
+    assert_close(objects[0].fade_in, (1200.0 + 600.0 * 0.5 / 5.0) * 0.4);
+}
+
+#[test]
+fn approach_rate_from_overall_difficulty() {
+    let osu_file = osu_file("OverallDifficulty:5");
+
+    assert_close(visibility(&osu_file, Mods::NONE)[0].appear_time, -200.0);
+}
+
+#[test]
+fn effective_approach_rate_with_speed() {
+    let osu_file = osu_file("ApproachRate:9");
+
+    assert_close(effective_approach_rate(&osu_file, Mods::NONE), 9.0);
+    assert_close(
+        effective_approach_rate(&osu_file, Mods::DOUBLE_TIME),
+        5.0 + 800.0 / 150.0,
+    );
+    assert_close(
+        effective_approach_rate(&osu_file, Mods::HALF_TIME),
+        5.0 + 400.0 / 150.0,
+    );
+}
@@ -0,0 +1,43 @@
+use rust_decimal_macros::dec;
+
+use crate::{
+    assert_osu_eq,
+    osu_file::{ApproxEq, OsuFile},
+};
+
+const FILE: &str = "osu file format v14
+
+[Difficulty]
+SliderMultiplier:1.4
+
+[TimingPoints]
+0,333.333333333333,4,2,0,100,1,0
+
+[HitObjects]
+100,100,0,2,0,B|200:200.5,1,140.000000001";
+
+#[test]
+fn approx_eq() {
+    let osu_file = FILE.parse::<OsuFile>().unwrap();
+    let noisy = FILE
+        .replace("333.333333333333", "333.333333333334")
+        .replace("140.000000001", "140")
+        .replace("1.4\n", "1.4000000001\n")
+        .parse::<OsuFile>()
+        .unwrap();
+
+    assert_ne!(osu_file, noisy);
+    assert!(osu_file.approx_eq(&noisy, dec!(0.000001)));
+    assert!(!osu_file.approx_eq(&noisy, dec!(0.0000000000001)));
+    assert_osu_eq!(osu_file, noisy);
+
+    let moved = FILE.replace("200.5", "201").parse::<OsuFile>().unwrap();
+    assert!(!osu_file.approx_eq(&moved, dec!(0.1)));
+    assert_osu_eq!(osu_file, moved, dec!(0.5));
+}
+
+#[test]
+#[should_panic]
+fn assert_osu_eq_fails() {
+    assert_osu_eq!(dec!(1), dec!(1.1), dec!(0.01));
+}
@@ -0,0 +1,122 @@
+use std::path::{Path, PathBuf};
+
+use pretty_assertions::assert_eq;
+
+use crate::{
+    assets::{match_hitsounds, HitsoundKey, SampleBank, SampleSound},
+    osu_file::OsuFile,
+};
+
+const OSU_FILE: &str = r#"osu file format v14
+
+[General]
+SampleSet: Soft
+
+[Events]
+Sample,0,0,"normal-hitfinish.wav",100
+
+[TimingPoints]
+0,500,4,0,0,100,1,0
+1000,-100,4,3,2,100,0,0
+
+[HitObjects]
+256,192,500,1,0,0:0:0:0:
+256,192,1000,1,2,0:1:0:0:
+256,192,1500,1,0,2:0:3:0:hit.wav"#;
+
+#[test]
+fn hitsound_key_from_path() {
+    let key = |path: &str| HitsoundKey::from_path(Path::new(path));
+
+    assert_eq!(
+        Some(HitsoundKey::new(
+            SampleBank::Normal,
+            SampleSound::HitNormal,
+            1
+        )),
+        key("normal-hitnormal.wav")
+    );
+    assert_eq!(
+        Some(HitsoundKey::new(
+            SampleBank::Normal,
+            SampleSound::HitNormal,
+            1
+        )),
+        key("normal-hitnormal1.wav")
+    );
+    assert_eq!(
+        Some(HitsoundKey::new(
+            SampleBank::Drum,
+            SampleSound::SliderTick,
+            12
+        )),
+        key("Drum-SliderTick12.OGG")
+    );
+    assert_eq!(None, key("normal-hitnormal0.wav"));
+    assert_eq!(None, key("normal-hitnormalx.wav"));
+    assert_eq!(None, key("normal-hitnormal.flac"));
+    assert_eq!(None, key("sb/normal-hitnormal.wav"));
+
+    assert_eq!(
+        "soft-hitclap",
+        HitsoundKey::new(SampleBank::Soft, SampleSound::HitClap, 1).file_stem()
+    );
+    assert_eq!(
+        "soft-hitclap2",
+        HitsoundKey::new(SampleBank::Soft, SampleSound::HitClap, 2).file_stem()
+    );
+}
+
+#[test]
+fn used_hitsounds() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+
+    assert_eq!(
+        vec![
+            HitsoundKey::new(SampleBank::Normal, SampleSound::HitWhistle, 2),
+            HitsoundKey::new(SampleBank::Soft, SampleSound::HitNormal, 3),
+            HitsoundKey::new(SampleBank::Drum, SampleSound::HitNormal, 2),
+        ],
+        osu_file.used_hitsounds().into_iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn match_hitsounds_conflicts_and_unused() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+    let files = [
+        "drum-hitnormal2.wav",
+        "Drum-HitNormal2.ogg",
+        "normal-hitwhistle2.wav",
+        "soft-hitnormal3.wav",
+        "soft-hitnormal.wav",
+        "normal-hitclap.wav",
+        "normal-hitfinish.wav",
+        "hit.wav",
+        "bg.jpg",
+        "sb/drum-hitnormal2.wav",
+    ]
+    .map(PathBuf::from);
+
+    let result = match_hitsounds(&files, &osu_file);
+
+    assert_eq!(6, result.matched.len());
+    assert_eq!(
+        vec![(
+            &HitsoundKey::new(SampleBank::Drum, SampleSound::HitNormal, 2),
+            [
+                PathBuf::from("drum-hitnormal2.wav"),
+                PathBuf::from("Drum-HitNormal2.ogg")
+            ]
+            .as_slice()
+        )],
+        result.conflicts().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![
+            PathBuf::from("soft-hitnormal.wav"),
+            PathBuf::from("normal-hitclap.wav")
+        ],
+        result.unused
+    );
+}
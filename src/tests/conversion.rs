@@ -0,0 +1,142 @@
+use pretty_assertions::assert_eq;
+
+use crate::mapset::Mapset;
+use crate::osu_file::{
+    events::LegacyConversionError, ConversionError, ConversionIssue, ConversionIssueKind, Loss,
+    LossKind, LossTarget, OsuFile, SectionName,
+};
+
+const V5: &str = "osu file format v5
+
+[General]
+AudioFilename: audio.mp3
+AudioHash: 0123456789abcdef
+EditorBookmarks: 100,200
+";
+
+const V3: &str = "osu file format v3
+
+[Events]
+4,0,1,\"bg.png\",320,240
+4,5,1,\"video.png\",320,240
+";
+
+#[test]
+fn convert_migrates_and_reports_dropped_fields() {
+    let mut osu_file = V5.parse::<OsuFile>().unwrap();
+    let report = osu_file.convert_to(14).unwrap();
+
+    assert_eq!(osu_file.version, 14);
+    assert_eq!(report.from, 5);
+    assert_eq!(report.migrated_fields, vec![0]);
+    assert!(!report.is_lossless());
+    assert_eq!(
+        report.dropped().collect::<Vec<_>>(),
+        vec![&Loss {
+            target: LossTarget::Field {
+                section: SectionName::General,
+                name: "AudioHash",
+            },
+            kind: LossKind::Dropped,
+        }]
+    );
+}
+
+#[test]
+fn convert_reports_legacy_events() {
+    let mut osu_file = V3.parse::<OsuFile>().unwrap();
+    let report = osu_file.convert_to(14).unwrap();
+
+    assert_eq!(report.upgraded_events, 1);
+    assert!(report.section_issues(SectionName::Events).any(|issue| issue
+        == &ConversionIssue {
+            section: SectionName::Events,
+            kind: ConversionIssueKind::LegacyEventNotUpgraded {
+                event: 1,
+                error: LegacyConversionError::UnsupportedLayer,
+            },
+        }));
+    assert_eq!(report.section_issues(SectionName::General).count(), 0);
+}
+
+#[test]
+fn convert_unsupported_version() {
+    let mut osu_file = V5.parse::<OsuFile>().unwrap();
+
+    assert_eq!(
+        osu_file.convert_to(15),
+        Err(ConversionError::UnsupportedVersion(15))
+    );
+    assert_eq!(osu_file.version, 5);
+}
+
+#[test]
+fn mapset_convert() {
+    let mut mapset = Mapset::new();
+    mapset.push_difficulty("a.osu", V5.parse::<OsuFile>().unwrap());
+    mapset.push_difficulty("b.osu", V3.parse::<OsuFile>().unwrap());
+
+    let results = mapset.convert_to(14);
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].0, "a.osu");
+    assert!(results.iter().all(|(_, result)| result.is_ok()));
+    assert_eq!(mapset.difficulty("b.osu").unwrap().version, 14);
+}
+
+#[test]
+fn convert_to_version() {
+    let osu_file = "osu file format v13
+
+[Events]
+3,100,163,162,255
+2,1000,2000"
+        .parse::<OsuFile>()
+        .unwrap();
+
+    let (converted, report) = osu_file.convert_to_version(14).unwrap();
+
+    assert_eq!(converted.version, 14);
+    assert_eq!(converted.events.as_ref().unwrap().0.len(), 1);
+    assert!(report
+        .dropped()
+        .any(|loss| loss.target == LossTarget::Event(0)));
+    assert_eq!(osu_file.events.as_ref().unwrap().0.len(), 2);
+}
+
+#[test]
+fn convert_to_version_keeps_ids_and_comments() {
+    let osu_file = "osu file format v13
+
+[HitObjects]
+// intro
+256,192,1000,1,0,0:0:0:0:"
+        .parse::<OsuFile>()
+        .unwrap();
+    let id = osu_file.hitobjects.as_ref().unwrap().0[0].extensions.id();
+
+    let (converted, report) = osu_file.convert_to_version(14).unwrap();
+    let hitobject = &converted.hitobjects.as_ref().unwrap().0[0];
+
+    assert!(report.is_lossless());
+    assert_eq!(hitobject.extensions.id(), id);
+    assert_eq!(hitobject.comments.before, vec![" intro".to_string()]);
+}
+
+#[test]
+fn convert_to_version_time_offset() {
+    let osu_file = "osu file format v4
+
+[TimingPoints]
+1000,500"
+        .parse::<OsuFile>()
+        .unwrap();
+
+    let (converted, _) = osu_file.convert_to_version(14).unwrap();
+
+    assert_eq!(converted.timing_points.as_ref().unwrap().0[0].time, 1024);
+    assert!(converted.to_string().contains("\n1024,500,"));
+    let (back, _) = converted.convert_to_version(4).unwrap();
+    assert_eq!(back.timing_points.as_ref().unwrap().0[0].time, 1024);
+    assert!(back.to_string().contains("\n1000,500"));
+}
@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use rust_decimal_macros::dec;
+
+use crate::{
+    cookbook::{add_sprite, bpm_at, convert_version, set_slider_velocity},
+    osu_file::{events::Event, OsuFile, Position, SectionName},
+};
+
+const OSU_FILE: &str = r#"osu file format v14
+
+[Events]
+Sprite,Foreground,Centre,"star.png",320,240
+
+[TimingPoints]
+0,500,4,2,0,60,1,0
+1000,-100,4,2,0,60,0,0
+2000,250,4,2,0,60,1,0"#;
+
+#[test]
+fn set_slider_velocity_replaces_inherited() {
+    let mut osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+
+    set_slider_velocity(&mut osu_file, 1000, dec!(0.5));
+    set_slider_velocity(&mut osu_file, 2000, dec!(2));
+    assert_eq!(osu_file.dirty_sections(), vec![SectionName::TimingPoints]);
+
+    let timing_points = &osu_file.timing_points.unwrap().0;
+    assert_eq!(timing_points.len(), 4);
+    assert_eq!(
+        timing_points[1].calc_slider_velocity_multiplier(),
+        Some(dec!(0.5))
+    );
+    assert!(timing_points[2].uninherited);
+    assert_eq!(
+        timing_points[3].calc_slider_velocity_multiplier(),
+        Some(dec!(2))
+    );
+}
+
+#[test]
+fn add_sprite_absolute_path() {
+    let mut osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+
+    assert!(add_sprite(
+        &mut osu_file,
+        Path::new("/star.png"),
+        Position::default(),
+        0,
+        1000
+    )
+    .is_err());
+    assert!(!osu_file.is_dirty());
+
+    add_sprite(
+        &mut osu_file,
+        Path::new("star.png"),
+        Position::default(),
+        0,
+        1000,
+    )
+    .unwrap();
+    assert_eq!(osu_file.dirty_sections(), vec![SectionName::Events]);
+    assert_eq!(osu_file.events.unwrap().0.len(), 2);
+}
+
+#[test]
+fn convert_and_bpm() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+
+    assert!(convert_version(&osu_file, 14).starts_with("osu file format v14"));
+    assert!(matches!(
+        osu_file.events.as_ref().unwrap().0[0],
+        Event::SpriteLegacy(_)
+    ));
+
+    assert_eq!(bpm_at(&osu_file, -100), Some(dec!(120)));
+    assert_eq!(bpm_at(&osu_file, 2500), Some(dec!(240)));
+}
@@ -0,0 +1,176 @@
+use std::{convert::Infallible, num::ParseIntError};
+
+use pretty_assertions::assert_eq;
+
+use crate::osu_file::{
+    OsuFile, OsuSection, ParseError, ParseOptions, ParseSectionError, RawSection, SectionName,
+    Version, VersionedFromStr, VersionedToString,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Round(String);
+
+impl VersionedFromStr for Round {
+    type Err = Infallible;
+
+    fn from_str(s: &str, _: Version) -> Result<Option<Self>, Self::Err> {
+        Ok(Some(Round(s.to_string())))
+    }
+}
+
+impl VersionedToString for Round {
+    fn to_string(&self, _: Version) -> Option<String> {
+        Some(self.0.clone())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Delay(i32);
+
+impl VersionedFromStr for Delay {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str, _: Version) -> Result<Option<Self>, Self::Err> {
+        Ok(Some(Delay(s.parse()?)))
+    }
+}
+
+impl VersionedToString for Delay {
+    fn to_string(&self, _: Version) -> Option<String> {
+        Some(self.0.to_string())
+    }
+}
+
+#[derive(OsuSection, Debug, PartialEq, Eq)]
+#[osu(name = "Tournament")]
+struct Tournament {
+    pub round: Option<Round>,
+    #[osu(key = "StartDelay")]
+    pub delay: Option<Delay>,
+}
+
+const OSU_FILE: &str = "osu file format v14
+
+[General]
+AudioLeadIn: 0
+
+[Tournament]
+Round: Grand Finals
+StartDelay: 500";
+
+#[test]
+fn custom_section_round_trip() {
+    let options = ParseOptions::new().register_section::<Tournament>();
+    let mut osu_file = OsuFile::from_str_with_options(OSU_FILE, &options).unwrap();
+
+    assert_eq!(
+        vec![RawSection {
+            name: "Tournament".to_string(),
+            content: "Round: Grand Finals\nStartDelay: 500".to_string(),
+            after: Some(SectionName::General),
+        }],
+        osu_file.custom_sections
+    );
+    assert_eq!(OSU_FILE, osu_file.to_string());
+
+    let tournament = osu_file.section::<Tournament>().unwrap().unwrap();
+    assert_eq!(
+        Tournament {
+            round: Some(Round("Grand Finals".to_string())),
+            delay: Some(Delay(500)),
+        },
+        tournament
+    );
+
+    osu_file.set_section(&Tournament {
+        round: Some(Round("Finals".to_string())),
+        delay: None,
+    });
+    assert_eq!("Round: Finals", osu_file.custom_sections[0].content);
+}
+
+#[test]
+fn custom_section_errors() {
+    let err = OSU_FILE.parse::<OsuFile>().unwrap_err();
+    assert!(matches!(err.error(), ParseError::UnknownSection));
+
+    let options = ParseOptions::new().register_section::<Tournament>();
+    let mut osu_file = OsuFile::from_str_with_options(OSU_FILE, &options).unwrap();
+    osu_file.custom_sections[0].content = "Round: Finals\nStartDelay: abc".to_string();
+
+    let err = osu_file.section::<Tournament>().unwrap_err();
+    assert_eq!(1, err.line_index());
+    assert!(matches!(
+        err.error(),
+        ParseSectionError::InvalidValue { key, .. } if key == "StartDelay"
+    ));
+
+    // lines without a colon are an error instead of a panic
+    osu_file.custom_sections[0].content = "Round: Finals\nno colon".to_string();
+    let err = osu_file.section::<Tournament>().unwrap_err();
+    assert_eq!(1, err.line_index());
+    assert!(matches!(err.error(), ParseSectionError::InvalidColonSet));
+}
+
+#[test]
+fn unknown_sections_kept_in_place() {
+    let i = "osu file format v14
+
+[Mania]
+Columns: 7
+
+[General]
+AudioLeadIn: 0
+
+[Editor Data]
+some tool data
+more tool data
+
+[Metadata]
+Title:Title";
+
+    assert!(i.parse::<OsuFile>().is_err());
+
+    let options = ParseOptions::new().keep_unknown_sections(true);
+    let osu_file = OsuFile::from_str_with_options(i, &options).unwrap();
+
+    assert_eq!(
+        vec![
+            RawSection {
+                name: "Mania".to_string(),
+                content: "Columns: 7".to_string(),
+                after: None,
+            },
+            RawSection {
+                name: "Editor Data".to_string(),
+                content: "some tool data\nmore tool data".to_string(),
+                after: Some(SectionName::General),
+            },
+        ],
+        osu_file.custom_sections
+    );
+    assert_eq!(i, osu_file.to_string());
+}
+
+#[test]
+fn registered_section_parsed_with_file() {
+    let i = "osu file format v14
+
+[General]
+AudioLeadIn: 0
+
+[Tournament]
+Round: Finals
+StartDelay: abc";
+
+    let options = ParseOptions::new()
+        .register_section::<Tournament>()
+        .keep_unknown_sections(true);
+    let err = OsuFile::from_str_with_options(i, &options).unwrap_err();
+
+    assert_eq!(7, err.line_index());
+    assert!(matches!(
+        err.error(),
+        ParseError::ParseCustomSectionError { name, .. } if name == "Tournament"
+    ));
+}
@@ -0,0 +1,44 @@
+use pretty_assertions::assert_eq;
+
+use crate::osu_file::{general::Mode, OsuFile};
+
+#[test]
+fn default_for_mania() {
+    let osu_file = OsuFile::default_for(14, Mode::Mania);
+    let s = osu_file.to_string();
+
+    assert!(s.contains("Mode: 3\n"));
+    assert!(s.contains("SpecialStyle: 0\n"));
+    assert!(!s.contains("StackLeniency"));
+    assert!(s.contains("CircleSize:4\n"));
+    assert!(!s.contains("ApproachRate"));
+    assert!(s.contains("[HitObjects]"));
+
+    assert_eq!(s.parse::<OsuFile>().unwrap(), osu_file);
+}
+
+#[test]
+fn default_for_osu() {
+    let osu_file = OsuFile::default_for(14, Mode::Osu);
+    let general = osu_file.general.as_ref().unwrap();
+
+    assert_eq!(general.mode, Some(Mode::Osu));
+    assert!(general.stack_leniency.is_some());
+    assert_eq!(general.special_style, None);
+    assert!(osu_file
+        .difficulty
+        .as_ref()
+        .unwrap()
+        .approach_rate
+        .is_some());
+}
+
+#[test]
+fn default_for_taiko() {
+    let osu_file = OsuFile::default_for(14, Mode::Taiko);
+    let general = osu_file.general.as_ref().unwrap();
+
+    assert_eq!(general.mode, Some(Mode::Taiko));
+    assert_eq!(general.stack_leniency, None);
+    assert_eq!(osu_file.difficulty.as_ref().unwrap().approach_rate, None);
+}
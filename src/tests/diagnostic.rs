@@ -0,0 +1,84 @@
+use pretty_assertions::assert_eq;
+
+use crate::osu_file::{
+    timingpoints::TimingPoints, Diagnostic, Error, OsuFile, ParseError, Span, VersionedFromStr,
+};
+
+#[test]
+fn parse_error_diagnostic() {
+    let i = "osu file format v14
+
+[Colours]
+  Combo1 : foo ";
+    let err = i.parse::<OsuFile>().unwrap_err();
+
+    assert_eq!(
+        err.to_diagnostic(i),
+        Diagnostic {
+            kind: "ParseColoursError".to_string(),
+            message: "Invalid red value".to_string(),
+            causes: Vec::new(),
+            line: Some(4),
+            column: Some(12),
+            span: Some(Span { start: 44, end: 47 }),
+        }
+    );
+    assert_eq!(&i[44..47], "foo");
+}
+
+#[test]
+fn diagnostic_outside_input() {
+    let err = Error::new(ParseError::UnknownSection, 5);
+    let diagnostic = err.to_diagnostic("[Foo]");
+
+    assert_eq!(diagnostic.kind, "UnknownSection");
+    assert_eq!(diagnostic.line, Some(6));
+    assert_eq!(diagnostic.column, None);
+    assert_eq!(diagnostic.span, None);
+}
+
+#[test]
+fn wrapped_error_kind() {
+    let i = "1000,500,x,2,0,60,1,0";
+    let err = TimingPoints::from_str(i, 14).unwrap_err();
+
+    assert_eq!(err.to_diagnostic(i).kind, "InvalidMeter");
+}
+
+#[test]
+fn field_column() {
+    let i = "osu file format v14
+
+[TimingPoints]
+0,500,4,2,0,60,1,0
+1000,500,x,2,0,60,1,0";
+    let err = i.parse::<OsuFile>().unwrap_err();
+
+    assert_eq!(err.line_index(), 4);
+    assert_eq!(err.column(), Some(9..10));
+    assert_eq!(
+        err.to_diagnostic(i).render(i),
+        "error: Invalid `meter` value
+ --> 5:10
+  |
+5 | 1000,500,x,2,0,60,1,0
+  |          ^"
+    );
+}
+
+#[test]
+fn field_value_column() {
+    let i = "osu file format v14
+
+[Difficulty]
+HPDrainRate: 5
+CircleSize:  abc";
+    let err = i.parse::<OsuFile>().unwrap_err();
+    let diagnostic = err.to_diagnostic(i);
+
+    assert_eq!(diagnostic.column, Some(14));
+    assert_eq!(
+        &i[diagnostic.span.unwrap().start..diagnostic.span.unwrap().end],
+        "abc"
+    );
+}
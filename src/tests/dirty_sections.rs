@@ -0,0 +1,51 @@
+use crate::osu_file::{OsuFile, SectionName};
+
+const OSU_FILE: &str = "osu file format v14
+
+[General]
+AudioFilename: audio.mp3
+
+[TimingPoints]
+0,500,4,2,0,60,1,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:";
+
+#[test]
+fn dirty_sections() {
+    let mut osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+    assert!(!osu_file.is_dirty());
+
+    let hitobjects = osu_file.hitobjects_mut().as_mut().unwrap();
+    hitobjects.0[0].time = 2000;
+    *osu_file.timing_points_mut() = None;
+
+    assert_eq!(
+        osu_file.dirty_sections(),
+        vec![SectionName::TimingPoints, SectionName::HitObjects]
+    );
+
+    // files with the same contents are equal, even if one of them changed since parsing
+    let mut clean = osu_file.clone();
+    clean.mark_clean();
+    assert!(!clean.is_dirty());
+    assert_eq!(clean, osu_file);
+
+    // changes through the fields are only seen once marked
+    clean.general.as_mut().unwrap().audio_filename = None;
+    assert!(!clean.is_dirty());
+    clean.mark_dirty(SectionName::General);
+    assert_eq!(clean.dirty_sections(), vec![SectionName::General]);
+}
+
+#[test]
+fn dirty_sections_new_file() {
+    let mut osu_file = OsuFile::new(14);
+    assert!(!osu_file.is_dirty());
+
+    *osu_file.events_mut() = Some(Default::default());
+    assert_eq!(osu_file.dirty_sections(), vec![SectionName::Events]);
+
+    osu_file.mark_clean();
+    assert!(!osu_file.is_dirty());
+}
@@ -0,0 +1,2 @@
+mod svg;
+mod tensor;
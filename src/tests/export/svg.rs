@@ -0,0 +1,35 @@
+use crate::{export::svg_layout, osu_file::OsuFile};
+
+const OSU_FILE: &str = "osu file format v14
+
+[Difficulty]
+CircleSize:4
+
+[HitObjects]
+256,192,1000,5,0,0:0:0:0:
+100,50,2000,2,0,L|100:190,1,140
+256,192,5000,12,0,6000,0:0:0:0:
+";
+
+#[test]
+fn svg_layout_range() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+    let svg = svg_layout(&osu_file, 1500..6000);
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 512 384\""));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(svg.matches("class=\"hitobject\"").count(), 1);
+    assert_eq!(svg.matches("class=\"spinner\"").count(), 1);
+    assert!(svg.contains("points=\"100.00,50.00 "));
+    assert!(svg.contains(" 100.00,190.00\""));
+    assert!(svg.contains("stroke-width=\"72.96\""));
+    // earlier objects are drawn last so they are on top
+    assert!(svg.find("class=\"spinner\"") < svg.find("class=\"slider\""));
+}
+
+#[test]
+fn svg_layout_empty() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+
+    assert_eq!(svg_layout(&osu_file, 0..1000).lines().count(), 2,);
+}
@@ -0,0 +1,59 @@
+use crate::{
+    export::tensor::{self, OBJECT_FEATURES, TIMING_POINT_FEATURES},
+    osu_file::OsuFile,
+};
+
+const OSU_FILE: &str = "osu file format v14
+
+[Difficulty]
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+2000,-50,4,2,0,80,0,1
+
+[HitObjects]
+256,192,1000,5,0,0:0:0:0:
+100,50,2000,2,0,L|100:190,2,140
+256,192,5000,12,0,6000,0:0:0:0:
+";
+
+#[test]
+fn objects() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+    let tensor = tensor::objects(&osu_file);
+
+    assert_eq!(OBJECT_FEATURES.len(), tensor.columns());
+    assert_eq!(3, tensor.rows());
+
+    #[rustfmt::skip]
+    let expected: [&[f32]; 3] = [
+        &[1000.0, 256.0, 192.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 120.0, 0.0],
+        // 140 osu!pixels per beat at 2x slider velocity, travelled twice
+        &[2000.0, 100.0, 50.0, 0.0, 1.0, 0.0, 0.0, 0.0, 500.0, 140.0, 2.0, 2.0, 120.0, 1.0],
+        &[5000.0, 256.0, 192.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1000.0, 0.0, 0.0, 2.0, 120.0, 1.0],
+    ];
+
+    for (i, row) in expected.iter().enumerate() {
+        assert_eq!(Some(*row), tensor.row(i), "row {i}");
+    }
+
+    assert_eq!(Some(13), tensor.column_index("kiai"));
+}
+
+#[test]
+fn timing_points() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+    let tensor = tensor::timing_points(&osu_file);
+
+    assert_eq!(TIMING_POINT_FEATURES.len(), tensor.columns());
+    assert_eq!(
+        Some(&[0.0, 1.0, 500.0, 120.0, 1.0, 4.0, 100.0, 0.0][..]),
+        tensor.row(0)
+    );
+    assert_eq!(
+        Some(&[2000.0, 0.0, -50.0, 0.0, 2.0, 4.0, 80.0, 1.0][..]),
+        tensor.row(1)
+    );
+}
@@ -0,0 +1,109 @@
+use rust_decimal_macros::dec;
+
+use crate::{
+    osu_file::{events::Event, hitobjects::HitObject, Extensions, ObjectId, OsuFile},
+    transform::RetimeOptions,
+};
+
+#[derive(Debug, PartialEq)]
+struct Selected(usize);
+
+#[test]
+fn extensions() {
+    let mut extensions = Extensions::new();
+    assert!(extensions.insert(Selected(1)).is_none());
+    assert_eq!(
+        extensions.insert(Selected(2)).as_deref(),
+        Some(&Selected(1))
+    );
+    assert_eq!(extensions.get::<Selected>(), Some(&Selected(2)));
+    assert!(!extensions.contains::<u32>());
+    assert_eq!(
+        extensions.remove::<Selected>().as_deref(),
+        Some(&Selected(2))
+    );
+    assert!(extensions.is_empty());
+}
+
+#[test]
+fn extensions_follow_objects() {
+    let osu_file = "osu file format v14
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+256,192,2000,1,0,0:0:0:0:
+256,192,1000,1,0,0:0:0:0:"
+        .parse::<OsuFile>()
+        .unwrap();
+
+    let mut edited = osu_file.clone();
+    let hitobjects = &mut edited.hitobjects.as_mut().unwrap().0;
+    hitobjects[0].extensions.insert(Selected(0));
+    edited.timing_points.as_mut().unwrap().0[0]
+        .extensions
+        .insert(Selected(1));
+
+    // extensions aren't part of the value
+    assert_eq!(edited, osu_file);
+    assert_eq!(edited.to_string(), osu_file.to_string());
+
+    let hitobjects = &mut edited.hitobjects.as_mut().unwrap().0;
+    hitobjects.sort_by_key(|h| h.time);
+    edited.change_rate(dec!(2), &RetimeOptions::new()).unwrap();
+
+    let hitobjects = &edited.hitobjects.as_ref().unwrap().0;
+    assert_eq!(hitobjects[1].time, 1000);
+    assert_eq!(
+        hitobjects[1].extensions.get::<Selected>(),
+        Some(&Selected(0))
+    );
+    assert!(hitobjects[0].extensions.is_empty());
+    assert_eq!(
+        edited.timing_points.as_ref().unwrap().0[0]
+            .extensions
+            .get::<Selected>(),
+        Some(&Selected(1))
+    );
+}
+
+#[test]
+fn object_ids() {
+    let osu_file = "osu file format v14
+
+[Events]
+Sprite,Foreground,Centre,\"a.png\",320,240
+2,100,163
+Sprite,Foreground,Centre,\"b.png\",320,240
+
+[HitObjects]
+256,192,2000,1,0,0:0:0:0:
+256,192,1000,1,0,0:0:0:0:"
+        .parse::<OsuFile>()
+        .unwrap();
+
+    let hitobjects = osu_file.hitobjects.as_ref().unwrap();
+    let (first, second) = (hitobjects.0[0].id(), hitobjects.0[1].id());
+    assert_ne!(first, second);
+
+    let mut edited = osu_file.clone();
+    let edited_hitobjects = &mut edited.hitobjects.as_mut().unwrap().0;
+    edited_hitobjects.sort_by_key(|h| h.time);
+    edited_hitobjects.insert(0, HitObject::hitcircle_default());
+
+    let edited_hitobjects = edited.hitobjects.as_ref().unwrap();
+    assert_eq!(edited_hitobjects.position_by_id(first), Some(2));
+    assert_eq!(edited_hitobjects.id_map()[&second], 1);
+    assert_eq!(edited_hitobjects.get_by_id(first).unwrap().time, 2000);
+
+    let events = osu_file.events.as_ref().unwrap();
+    let ids = events.id_map();
+    assert_eq!(ids.len(), 2);
+    let sprite = events.0.iter().rev().find_map(|e| match e {
+        Event::StoryboardObject(object) => Some(object.id()),
+        _ => None,
+    });
+    assert_eq!(events.position_by_id(sprite.unwrap()), Some(2));
+    assert!(events.get_by_id(ObjectId::new()).is_none());
+}
@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+
+use rust_decimal_macros::dec;
+
+use crate::osu_file::{FieldValue, OsuFile, SetFieldError};
+
+#[test]
+fn get_and_set_by_name() {
+    let mut osu_file = "osu file format v14
+
+[General]
+AudioFilename: audio.mp3
+Countdown: 2
+SampleSet: Soft
+StackLeniency: 0.5
+
+[Metadata]
+Tags:a b c"
+        .parse::<OsuFile>()
+        .unwrap();
+
+    let general = osu_file.general.as_mut().unwrap();
+    assert_eq!(
+        general.fields(),
+        vec![
+            (
+                "AudioFilename",
+                FieldValue::Path(PathBuf::from("audio.mp3"))
+            ),
+            ("Countdown", FieldValue::Integer(2)),
+            ("SampleSet", FieldValue::String("Soft".to_string())),
+            ("StackLeniency", FieldValue::Decimal(dec!(0.5))),
+        ]
+    );
+    assert_eq!(general.get("PreviewTime"), None);
+    assert_eq!(general.get("audio_filename"), None);
+
+    general
+        .set("PreviewTime", FieldValue::Integer(1000))
+        .unwrap();
+    general
+        .set("SampleSet", FieldValue::String("Drum".to_string()))
+        .unwrap();
+    assert!(matches!(
+        general.set("PreviewTime", FieldValue::Bool(true)),
+        Err(SetFieldError::InvalidValue(_))
+    ));
+    assert!(matches!(
+        general.set("Unknown", FieldValue::Integer(0)),
+        Err(SetFieldError::UnknownField(_))
+    ));
+
+    let s = osu_file.to_string();
+    assert!(s.contains("PreviewTime: 1000"));
+    assert!(s.contains("SampleSet: Drum"));
+
+    let tags = osu_file.metadata.as_ref().unwrap().get("Tags");
+    assert_eq!(
+        tags,
+        Some(FieldValue::StringList(vec![
+            "a".to_string(),
+            "b".to_string(),
+            "c".to_string()
+        ]))
+    );
+}
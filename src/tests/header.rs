@@ -0,0 +1,41 @@
+use pretty_assertions::assert_eq;
+
+use crate::osu_file::{FileHeader, OsuFile, WriteOptions};
+
+const I: &str = "\u{feff}\u{200b}osu file format v14  \n\n[General]\nAudioFilename: audio.mp3\nfoo";
+
+#[test]
+fn header_extras() {
+    let osu_file = I.replace("\nfoo", "").parse::<OsuFile>().unwrap();
+
+    assert_eq!(
+        osu_file.header(),
+        Some(&FileHeader {
+            leading: "\u{feff}\u{200b}".to_string(),
+            line: "osu file format v14  ".to_string(),
+            version: 14,
+        })
+    );
+    assert!(!osu_file.header().unwrap().is_normalized());
+    assert!(osu_file.to_string().starts_with("osu file format v14\n"));
+
+    let options = WriteOptions::new().preserve_header(true);
+    assert_eq!(
+        osu_file.to_string_with_options(&options),
+        osu_file.to_string().replacen(
+            "osu file format v14",
+            "\u{feff}\u{200b}osu file format v14  ",
+            1
+        )
+    );
+
+    let from_reader = OsuFile::from_reader(I.replace("\nfoo", "").as_bytes()).unwrap();
+    assert_eq!(from_reader.header(), osu_file.header());
+}
+
+#[test]
+fn header_extras_line_index() {
+    let err = format!("\n{I}").parse::<OsuFile>().unwrap_err();
+
+    assert_eq!(err.line_index(), 5);
+}
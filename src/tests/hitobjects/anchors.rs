@@ -0,0 +1,176 @@
+use rust_decimal_macros::dec;
+
+use crate::osu_file::{
+    hitobjects::{
+        CurvePoint, CurveType, HitSound, SlideParams, SliderEditError, SliderSegment,
+        MAX_PATH_POINTS,
+    },
+    Position,
+};
+
+fn pos(x: i32, y: i32) -> Position {
+    Position::from(Position::new(x, y))
+}
+
+fn slider(curve_type: CurveType, points: &[(i32, i32)], length: i32) -> SlideParams {
+    SlideParams {
+        curve_type,
+        curve_points: points
+            .iter()
+            .map(|(x, y)| CurvePoint(pos(*x, *y)))
+            .collect(),
+        slides: 1,
+        length: length.into(),
+        edge_sounds: Vec::new(),
+        edge_sets: Vec::new(),
+    }
+}
+
+#[test]
+fn red_anchors() {
+    let mut params = slider(CurveType::Bezier, &[(100, 0), (100, 0), (100, 100)], 200);
+
+    assert_eq!(params.red_anchors(), vec![0]);
+    assert_eq!(
+        params.segments(pos(0, 0)),
+        vec![
+            SliderSegment { start: 0, end: 1 },
+            SliderSegment { start: 2, end: 3 },
+        ]
+    );
+
+    params.move_anchor(1, pos(50, 50)).unwrap();
+    assert_eq!(params.red_anchors(), vec![0]);
+    assert_eq!(params.curve_points[0], CurvePoint(pos(50, 50)));
+
+    params.remove_anchor(0).unwrap();
+    assert!(params.red_anchors().is_empty());
+    assert_eq!(params.segments(pos(0, 0)).len(), 1);
+
+    params.insert_anchor(2, pos(0, 100)).unwrap();
+    assert_eq!(params.curve_points.len(), 3);
+    assert_eq!(
+        params.insert_anchor(4, pos(0, 0)),
+        Err(SliderEditError::AnchorOutOfRange)
+    );
+}
+
+#[test]
+fn remove_last_anchor() {
+    let mut params = slider(CurveType::Linear, &[(100, 0)], 100);
+
+    assert_eq!(params.remove_anchor(0), Err(SliderEditError::LastAnchor));
+}
+
+#[test]
+fn split_linear() {
+    let params = slider(CurveType::Linear, &[(100, 0), (100, 100)], 200);
+    let split = params.split(pos(0, 0), dec!(0.25)).unwrap();
+
+    assert_eq!(split.first, slider(CurveType::Linear, &[(50, 0)], 50));
+    assert_eq!(split.second_position, pos(50, 0));
+    assert_eq!(
+        split.second,
+        slider(CurveType::Linear, &[(100, 0), (100, 100)], 150)
+    );
+
+    assert_eq!(
+        params.split(pos(0, 0), dec!(1)),
+        Err(SliderEditError::ProgressOutOfRange)
+    );
+    assert_eq!(
+        SlideParams {
+            slides: 2,
+            ..params
+        }
+        .split(pos(0, 0), dec!(0.5)),
+        Err(SliderEditError::HasRepeats)
+    );
+}
+
+#[test]
+fn split_perfect_circle() {
+    let params = slider(CurveType::PerfectCircle, &[(50, 50), (100, 0)], 100);
+    let split = params.split(pos(0, 0), dec!(0.5)).unwrap();
+
+    assert_eq!(split.first.curve_type, CurveType::PerfectCircle);
+    assert_eq!(split.second.curve_type, CurveType::PerfectCircle);
+    assert_eq!(split.second_position, pos(23, 42));
+    assert_eq!(split.first.curve_points.last().unwrap().0, pos(23, 42));
+}
+
+#[test]
+fn reverse() {
+    let mut params = slider(CurveType::Linear, &[(100, 0), (100, 100)], 150);
+    params.edge_sounds = vec![
+        HitSound::new(false, true, false, false),
+        HitSound::new(false, false, false, true),
+    ];
+
+    let head = params.reverse(pos(0, 0)).unwrap();
+
+    assert_eq!(head, pos(100, 50));
+    assert_eq!(
+        params.curve_points,
+        vec![CurvePoint(pos(100, 0)), CurvePoint(pos(0, 0))]
+    );
+    assert!(params.edge_sounds[0].clap());
+    assert!(params.edge_sounds[1].whistle());
+}
+
+#[test]
+fn end_position() {
+    let mut params = slider(CurveType::Linear, &[(100, 0), (100, 100)], 150);
+
+    assert_eq!(
+        params.end_position(pos(0, 0)).unwrap(),
+        Position::new(100.0, 50.0)
+    );
+    params.slides = 2;
+    assert_eq!(
+        params.end_position(pos(0, 0)).unwrap(),
+        Position::new(0.0, 0.0)
+    );
+    params.slides = 3;
+    assert_eq!(
+        params.end_position(pos(0, 0)).unwrap(),
+        Position::new(100.0, 50.0)
+    );
+
+    // extended in a straight line past the last anchor
+    params.length = 250.into();
+    assert_eq!(
+        params.end_position(pos(0, 0)).unwrap(),
+        Position::new(100.0, 150.0)
+    );
+
+    params.length = 0.into();
+    assert_eq!(
+        params.end_position(pos(0, 0)),
+        Err(SliderEditError::ZeroLength)
+    );
+}
+
+#[test]
+fn path_points() {
+    let params = slider(CurveType::Linear, &[(100, 0), (100, 100)], 150);
+
+    assert_eq!(
+        params.path_points(pos(0, 0), 50.0).unwrap(),
+        vec![
+            Position::new(0.0, 0.0),
+            Position::new(50.0, 0.0),
+            Position::new(100.0, 0.0),
+            Position::new(100.0, 50.0),
+        ]
+    );
+    assert_eq!(
+        params.path_points(pos(0, 0), 0.0),
+        Err(SliderEditError::InvalidSpacing)
+    );
+
+    // long sliders are spread out to stay under the cap
+    let params = slider(CurveType::Linear, &[(100, 0)], i32::MAX);
+    let points = params.path_points(pos(0, 0), 1.0).unwrap();
+    assert!(points.len() <= MAX_PATH_POINTS + 1);
+}
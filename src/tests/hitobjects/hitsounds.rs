@@ -0,0 +1,68 @@
+use crate::osu_file::{
+    hitobjects::{HitObject, HitObjects, HitSound},
+    timingpoints::TimingPoints,
+    VersionedFromStr,
+};
+
+fn hitobjects(times: &[u32]) -> HitObjects {
+    HitObjects(
+        times
+            .iter()
+            .map(|time| {
+                let mut obj = HitObject::hitcircle_default();
+                obj.time = *time;
+                obj
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn apply_hitsound_pattern() {
+    let timing_points = TimingPoints::from_str(
+        "0,500,4,2,0,100,1,0\n1000,-50,4,2,0,100,0,0\n2000,250,4,2,0,100,1,0",
+        14,
+    )
+    .unwrap()
+    .unwrap();
+    let mut hitobjects = hitobjects(&[0, 250, 500, 1000, 1100, 2000, 2250]);
+
+    let clap = HitSound::new(false, false, false, true);
+    let whistle = HitSound::new(false, true, false, false);
+    let pattern = [clap, HitSound::default(), whistle];
+
+    let changed = hitobjects.apply_hitsound_pattern(&pattern, 0, 2, &timing_points);
+
+    assert_eq!(6, changed);
+    assert_eq!(
+        vec![
+            clap,
+            HitSound::default(),
+            whistle,
+            HitSound::default(),
+            // off the beat snap
+            HitSound::default(),
+            whistle,
+            HitSound::default(),
+        ],
+        hitobjects.0.iter().map(|h| h.hitsound).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn clear_hitsounds() {
+    let mut hitobjects = hitobjects(&[0, 500, 1000]);
+    for obj in &mut hitobjects.0 {
+        obj.hitsound = HitSound::new(false, true, true, false);
+    }
+
+    assert_eq!(2, hitobjects.clear_hitsounds(0..1000));
+    assert_eq!(
+        vec![
+            HitSound::default(),
+            HitSound::default(),
+            HitSound::new(false, true, true, false)
+        ],
+        hitobjects.0.iter().map(|h| h.hitsound).collect::<Vec<_>>()
+    );
+}
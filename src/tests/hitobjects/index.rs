@@ -0,0 +1,80 @@
+use rust_decimal_macros::dec;
+
+use crate::osu_file::{
+    hitobjects::{Grid, HitObjects},
+    Position, VersionedFromStr,
+};
+
+fn grid() -> Grid {
+    let hitobjects = HitObjects::from_str(
+        "100,100,1000,1,0,0:0:0:0:
+300,100,2000,1,0,0:0:0:0:
+0,300,3000,2,0,L|400:300,1,400
+256,192,4000,12,0,5000,0:0:0:0:",
+        14,
+    )
+    .unwrap()
+    .unwrap();
+
+    Grid::new(&hitobjects.0, 64.0)
+}
+
+#[test]
+fn grid_query() {
+    let grid = grid();
+
+    // spinners aren't indexed
+    assert_eq!(3, grid.len());
+
+    assert_eq!(
+        vec![0],
+        grid.query(Position::new(dec!(110), dec!(100)), 20.0, 0..=5000)
+    );
+    assert_eq!(
+        vec![0, 1],
+        grid.query(Position::new(dec!(200), dec!(100)), 100.0, 0..=5000)
+    );
+    assert!(grid
+        .query(Position::new(dec!(256), dec!(192)), 10.0, 0..=5000)
+        .is_empty());
+}
+
+#[test]
+fn grid_query_slider_path() {
+    let grid = grid();
+
+    assert_eq!(
+        vec![2],
+        grid.query(Position::new(dec!(200), dec!(310)), 20.0, 0..=5000)
+    );
+    assert!(grid
+        .query(Position::new(dec!(200), dec!(310)), 20.0, 0..=2000)
+        .is_empty());
+    assert!(grid
+        .query(Position::new(dec!(200), dec!(340)), 20.0, 0..=5000)
+        .is_empty());
+}
+
+#[test]
+fn grid_query_unbounded() {
+    let grid = grid();
+
+    assert_eq!(
+        vec![0, 1, 2],
+        grid.query(Position::new(dec!(0), dec!(0)), f64::INFINITY, 0..=5000)
+    );
+    assert_eq!(
+        vec![0, 1, 2],
+        grid.query(Position::new(dec!(0), dec!(0)), 1e300, 0..=5000)
+    );
+
+    // a slider reaching far outside of the playfield is still found
+    let hitobjects = HitObjects::from_str("0,0,1000,2,0,L|1000000000:0,1,1000000000", 14)
+        .unwrap()
+        .unwrap();
+    let grid = Grid::new(&hitobjects.0, 64.0);
+    assert_eq!(
+        vec![0],
+        grid.query(Position::new(dec!(500), dec!(10)), 20.0, 0..=5000)
+    );
+}
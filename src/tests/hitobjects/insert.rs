@@ -0,0 +1,112 @@
+use pretty_assertions::assert_eq;
+
+use crate::osu_file::{
+    hitobjects::{HitObject, HitObjectInsertError, HitObjects},
+    InsertOptions, OsuFile, VersionedFromStr,
+};
+
+fn hitobject(s: &str) -> HitObject {
+    HitObject::from_str(s, 14).unwrap().unwrap()
+}
+
+#[test]
+fn try_insert_order() {
+    let mut hitobjects = HitObjects(Vec::new());
+    let options = InsertOptions::new();
+
+    assert_eq!(
+        hitobjects.try_insert(hitobject("256,192,1000,1,0,0:0:0:0:"), &options),
+        Ok(0)
+    );
+    assert_eq!(
+        hitobjects.try_insert(hitobject("256,192,990,1,0,0:0:0:0:"), &options),
+        Err(HitObjectInsertError::OutOfOrder {
+            time: 990,
+            previous: 1000,
+        })
+    );
+    assert_eq!(
+        hitobjects.try_insert(
+            hitobject("256,192,990,1,0,0:0:0:0:"),
+            &options.time_tolerance(10)
+        ),
+        Ok(1)
+    );
+    // the tolerance is from the latest hitobject, not the last one
+    assert_eq!(
+        hitobjects.try_insert(
+            hitobject("256,192,985,1,0,0:0:0:0:"),
+            &InsertOptions::new().time_tolerance(10)
+        ),
+        Err(HitObjectInsertError::OutOfOrder {
+            time: 985,
+            previous: 1000,
+        })
+    );
+}
+
+#[test]
+fn try_insert_edge_arrays() {
+    let mut hitobjects = HitObjects(Vec::new());
+    let options = InsertOptions::new();
+
+    assert_eq!(
+        hitobjects.try_insert(
+            hitobject("0,0,0,2,0,L|100:0,2,100,2|0,0:0|0:0|0:0"),
+            &options
+        ),
+        Err(HitObjectInsertError::EdgeSoundsLength {
+            expected: 3,
+            found: 2,
+        })
+    );
+    assert_eq!(
+        hitobjects.try_insert(hitobject("0,0,0,2,0,L|100:0,2,100,2|0|0,0:0"), &options),
+        Err(HitObjectInsertError::EdgeSetsLength {
+            expected: 3,
+            found: 1,
+        })
+    );
+    assert_eq!(
+        hitobjects.try_insert(hitobject("0,0,0,2,0,L|100:0,2,100"), &options),
+        Ok(0)
+    );
+}
+
+#[test]
+fn try_insert_mania_columns() {
+    let osu_file = "osu file format v14
+
+[General]
+Mode: 3
+
+[Difficulty]
+CircleSize:4
+
+[HitObjects]
+"
+    .parse::<OsuFile>()
+    .unwrap();
+    let options = InsertOptions::for_file(&osu_file);
+    let mut hitobjects = osu_file.hitobjects.unwrap();
+
+    assert_eq!(options.key_count, Some(4));
+    assert_eq!(
+        hitobjects.try_insert(hitobject("448,192,0,1,0,0:0:0:0:"), &options),
+        Ok(0)
+    );
+    assert_eq!(
+        hitobjects.try_insert(hitobject("512,192,0,1,0,0:0:0:0:"), &options),
+        Err(HitObjectInsertError::ColumnOutOfBounds {
+            column: 4,
+            key_count: 4,
+        })
+    );
+    assert_eq!(
+        hitobjects.try_insert(hitobject("-1,192,0,1,0,0:0:0:0:"), &options),
+        Err(HitObjectInsertError::ColumnOutOfBounds {
+            column: -1,
+            key_count: 4,
+        })
+    );
+}
@@ -0,0 +1,55 @@
+use pretty_assertions::assert_eq;
+use rust_decimal::Decimal;
+
+use crate::osu_file::{
+    hitobjects::{HitObject, HitObjects},
+    VersionedFromStr,
+};
+
+fn hitobject(s: &str) -> HitObject {
+    HitObject::from_str(s, 14).unwrap().unwrap()
+}
+
+#[test]
+fn mania_column() {
+    assert_eq!(hitobject("0,192,1000,1,0,0:0:0:0:").mania_column(4), 0);
+    assert_eq!(hitobject("127,192,1000,1,0,0:0:0:0:").mania_column(4), 0);
+    assert_eq!(hitobject("128,192,1000,1,0,0:0:0:0:").mania_column(4), 1);
+    assert_eq!(hitobject("448,192,1000,1,0,0:0:0:0:").mania_column(4), 3);
+    assert_eq!(hitobject("36,192,1000,1,0,0:0:0:0:").mania_column(7), 0);
+    assert_eq!(hitobject("475,192,1000,1,0,0:0:0:0:").mania_column(7), 6);
+
+    // outside of the playfield
+    assert_eq!(hitobject("-10,192,1000,1,0,0:0:0:0:").mania_column(4), 0);
+    assert_eq!(hitobject("600,192,1000,1,0,0:0:0:0:").mania_column(4), 3);
+    assert_eq!(hitobject("256,192,1000,1,0,0:0:0:0:").mania_column(0), 0);
+
+    // positions too far out to multiply by the key count
+    let mut obj = hitobject("256,192,1000,1,0,0:0:0:0:");
+    obj.position.x = Decimal::MAX;
+    assert_eq!(obj.mania_column(7), 6);
+    obj.position.x = Decimal::MIN;
+    assert_eq!(obj.mania_column(7), 0);
+}
+
+#[test]
+fn to_mania_columns() {
+    let hitobjects = HitObjects(vec![
+        hitobject("64,192,1000,1,0,0:0:0:0:"),
+        hitobject("448,192,1000,1,0,0:0:0:0:"),
+        hitobject("64,192,1500,1,0,0:0:0:0:"),
+        hitobject("192,192,2000,128,0,2500:0:0:0:0:"),
+    ]);
+
+    let columns = hitobjects.to_mania_columns(4);
+    let times = columns
+        .iter()
+        .map(|column| column.iter().map(|obj| obj.time).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        times,
+        vec![vec![1000, 1500], vec![2000], vec![], vec![1000]]
+    );
+    assert!(hitobjects.to_mania_columns(0).is_empty());
+}
@@ -1,2 +1,10 @@
+mod anchors;
 mod error;
+mod hitsounds;
+mod index;
+mod insert;
+mod mania;
+mod node_samples;
 mod parse;
+mod query;
+mod sorted;
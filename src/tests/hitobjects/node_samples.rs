@@ -0,0 +1,59 @@
+use std::num::NonZeroUsize;
+
+use rust_decimal_macros::dec;
+
+use crate::osu_file::{
+    hitobjects::{
+        CurveType, EdgeSet, HitSample, HitSound, NodeSample, SampleIndex, SampleSet, SampleSource,
+        SlideParams,
+    },
+    VersionedFromStr,
+};
+
+#[test]
+fn node_samples_round_trip() {
+    let mut params = SlideParams {
+        curve_type: CurveType::Bezier,
+        curve_points: Vec::new(),
+        slides: 2,
+        length: dec!(100),
+        edge_sounds: vec![HitSound::new(false, true, false, false)],
+        edge_sets: vec![EdgeSet {
+            normal_set: SampleSet::SoftSet,
+            addition_set: SampleSet::DrumSet,
+        }],
+    };
+
+    let samples = params.node_samples();
+
+    assert_eq!(samples.len(), 3);
+    assert!(samples[0].hitsound.whistle());
+    assert_eq!(samples[0].edge_set.normal_set, SampleSet::SoftSet);
+    assert_eq!(samples[2], NodeSample::default());
+
+    params.set_node_samples(&samples);
+
+    assert_eq!(params.edge_sounds.len(), 3);
+    assert_eq!(params.edge_sets.len(), 3);
+    assert_eq!(params.node_samples(), samples);
+}
+
+#[test]
+fn hit_sample_resolved_source() {
+    let mut sample = HitSample::from_str("2:3:4:80:", 14).unwrap().unwrap();
+    let bank = SampleSource::Bank {
+        normal_set: SampleSet::SoftSet,
+        addition_set: SampleSet::DrumSet,
+        index: SampleIndex::Index(NonZeroUsize::new(4).unwrap()),
+    };
+
+    // empty filenames fall back to the bank
+    assert_eq!(sample.resolved_source(false), bank);
+
+    sample.filename = Some("custom.wav".to_string());
+    assert_eq!(
+        sample.resolved_source(false),
+        SampleSource::File("custom.wav")
+    );
+    assert_eq!(sample.resolved_source(true), bank);
+}
@@ -1,4 +1,7 @@
-use crate::osu_file::{hitobjects::HitObject, VersionedFromStr, VersionedToString};
+use crate::osu_file::{
+    hitobjects::{HitObject, HitObjects},
+    OsuFile, VersionedFromStr, VersionedToString,
+};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -32,3 +35,59 @@ fn short_hand() {
     assert_eq!(hitcircle_str, hitcircle.to_string(14).unwrap());
     assert_eq!(slider_str, slider.to_string(14).unwrap());
 }
+
+#[test]
+fn hitobjects_comments() {
+    let i = "//first
+221,350,9780,1,0,0:0:0:0:
+// second
+//third
+256,192,33598,12,0,431279,0:0:0:0:
+//last";
+    let hitobjects = HitObjects::from_str(i, 14).unwrap().unwrap();
+
+    assert_eq!(hitobjects.0.len(), 2);
+    assert_eq!(hitobjects.0[0].comments.before, vec!["first".to_string()]);
+    assert_eq!(
+        hitobjects.0[1].comments.before,
+        vec![" second".to_string(), "third".to_string()]
+    );
+    assert_eq!(hitobjects.0[1].comments.after, vec!["last".to_string()]);
+    assert_eq!(hitobjects.to_string(14).unwrap(), i);
+
+    let without_comments = HitObjects::from_str(
+        "221,350,9780,1,0,0:0:0:0:\n256,192,33598,12,0,431279,0:0:0:0:",
+        14,
+    )
+    .unwrap()
+    .unwrap();
+    assert!(hitobjects.semantically_eq(&without_comments));
+}
+
+#[test]
+fn section_comments() {
+    let i = "osu file format v14
+
+[TimingPoints]
+\t// no timing points yet
+
+[HitObjects]
+  // indented
+//no hitobjects
+";
+    let osu_file = i.parse::<OsuFile>().unwrap();
+
+    assert!(osu_file.hitobjects.as_ref().unwrap().0.is_empty());
+    assert_eq!(
+        osu_file.section_comments.timing_points,
+        vec![" no timing points yet".to_string()]
+    );
+    assert_eq!(
+        osu_file.section_comments.hitobjects,
+        vec![" indented".to_string(), "no hitobjects".to_string()]
+    );
+
+    let written = osu_file.to_string();
+    assert!(written.contains("[HitObjects]\n// indented\n//no hitobjects"));
+    assert_eq!(written.parse::<OsuFile>().unwrap(), osu_file);
+}
@@ -0,0 +1,140 @@
+use rust_decimal_macros::dec;
+
+use crate::osu_file::{
+    hitobjects::{Filter, HitObject, HitObjects},
+    timingpoints::TimingPoints,
+    VersionedFromStr,
+};
+
+fn hitobjects() -> HitObjects {
+    let times = [0, 100, 150, 500, 1000];
+
+    HitObjects(
+        times
+            .iter()
+            .enumerate()
+            .map(|(i, time)| {
+                let mut obj = if i % 2 == 0 {
+                    HitObject::hitcircle_default()
+                } else {
+                    HitObject::spinner_default()
+                };
+                obj.time = *time;
+                obj.new_combo = i == 3;
+                obj
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn query_time_range() {
+    let hitobjects = hitobjects();
+
+    assert_eq!(
+        hitobjects.query().time_range(100..501).indices(),
+        vec![1, 2, 3]
+    );
+}
+
+#[test]
+fn query_chained() {
+    let hitobjects = hitobjects();
+
+    assert_eq!(
+        hitobjects
+            .query()
+            .is_spinner()
+            .min_gap_to_prev(100)
+            .indices(),
+        vec![1, 3]
+    );
+    assert_eq!(hitobjects.query().new_combo(true).indices(), vec![3]);
+}
+
+#[test]
+fn query_combinators() {
+    let hitobjects = hitobjects();
+
+    let filter = Filter::TimeRange(0..200)
+        .and(Filter::IsHitCircle)
+        .or(Filter::NewCombo(true));
+    assert_eq!(hitobjects.query().filter(filter).indices(), vec![0, 2, 3]);
+
+    let filter = Filter::IsHitCircle.not();
+    assert_eq!(
+        hitobjects.query().filter(filter.clone()).indices(),
+        vec![1, 3]
+    );
+
+    // objects past the end don't match, even inverted
+    assert!(!filter.matches(&hitobjects.0, 5));
+    assert!(!Filter::MinGapToPrev(0).matches(&hitobjects.0, 5));
+}
+
+#[test]
+fn query_modify_indices() {
+    let mut hitobjects = hitobjects();
+
+    let indices = hitobjects.query().is_hitcircle().indices();
+    hitobjects.modify_indices(&indices, |obj| obj.new_combo = true);
+
+    assert_eq!(hitobjects.query().new_combo(true).count(), 4);
+}
+
+#[test]
+fn iterators_and_at_time() {
+    let hitobjects = HitObjects::from_str(
+        "256,192,0,1,0,0:0:0:0:
+0,0,1000,2,0,L|140:0,1,140
+256,192,1200,12,0,3000,0:0:0:0:",
+        14,
+    )
+    .unwrap()
+    .unwrap();
+    let timing_points = TimingPoints::from_str("0,500,4,2,0,100,1,0", 14)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(hitobjects.iter_circles().count(), 1);
+    assert_eq!(
+        hitobjects
+            .iter_sliders()
+            .map(|h| h.time)
+            .collect::<Vec<_>>(),
+        vec![1000]
+    );
+    assert_eq!(
+        hitobjects
+            .iter_spinners()
+            .map(|h| h.time)
+            .collect::<Vec<_>>(),
+        vec![1200]
+    );
+    assert_eq!(
+        hitobjects
+            .objects_between(0, 1200)
+            .map(|h| h.time)
+            .collect::<Vec<_>>(),
+        vec![0, 1000]
+    );
+
+    assert_eq!(hitobjects.0[1].end_time(&timing_points, dec!(1.4)), 1500.0);
+    assert_eq!(
+        hitobjects
+            .at_time(1400, &timing_points, dec!(1.4))
+            .map(|h| h.time)
+            .collect::<Vec<_>>(),
+        vec![1000, 1200]
+    );
+    assert_eq!(
+        hitobjects.at_time(1600, &timing_points, dec!(1.4)).count(),
+        1
+    );
+    // a tiny beat length makes the slider velocity too large, so the slider ends straight away
+    let timing_points =
+        TimingPoints::from_str("0,0.0000000000000000000000000001,4,2,0,100,1,0", 14)
+            .unwrap()
+            .unwrap();
+    assert_eq!(hitobjects.0[1].end_time(&timing_points, dec!(1.4)), 1000.0);
+}
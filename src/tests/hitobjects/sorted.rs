@@ -0,0 +1,32 @@
+use crate::osu_file::hitobjects::{HitObject, HitObjects, SortedHitObjects};
+
+fn hitcircle(time: u32) -> HitObject {
+    let mut obj = HitObject::hitcircle_default();
+    obj.time = time;
+    obj
+}
+
+#[test]
+fn sorted_hitobjects() {
+    let mut hitobjects = SortedHitObjects::from(HitObjects(vec![
+        hitcircle(500),
+        hitcircle(0),
+        hitcircle(100),
+    ]));
+
+    assert_eq!(
+        hitobjects.iter().map(|h| h.time).collect::<Vec<_>>(),
+        vec![0, 100, 500]
+    );
+    assert_eq!(hitobjects.insert(hitcircle(100)), 2);
+    assert_eq!(hitobjects.insert(hitcircle(1000)), 4);
+
+    assert_eq!(hitobjects.objects_in_range(100..501).len(), 3);
+    assert!(hitobjects.objects_in_range(600..1000).is_empty());
+    assert_eq!(hitobjects.last_at_or_before(499).map(|h| h.time), Some(100));
+    assert_eq!(hitobjects.first_after(500).map(|h| h.time), Some(1000));
+    assert_eq!(hitobjects.remove(0).map(|h| h.time), Some(0));
+    assert_eq!(hitobjects.remove(10), None);
+
+    assert_eq!(HitObjects::from(hitobjects).0.len(), 4);
+}
@@ -0,0 +1,81 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    import::{hitobjects_from_onsets, ImportOptions},
+    osu_file::{
+        general::Mode,
+        hitobjects::HitObjectParams,
+        timingpoints::{TimingContext, TimingPoints},
+        Millis, VersionedFromStr,
+    },
+};
+
+fn timing() -> TimingContext {
+    TimingPoints::from_str("0,500,4,2,0,100,1,0", 14)
+        .unwrap()
+        .unwrap()
+        .timing_context()
+        .unwrap()
+}
+
+#[test]
+fn onsets_snapped() {
+    let onsets = [
+        Millis(1010),
+        Millis(-300),
+        Millis(130),
+        Millis(990),
+        Millis(2240),
+    ];
+    let hitobjects = hitobjects_from_onsets(&onsets, &timing(), &ImportOptions::new(Mode::Osu));
+
+    let objs = hitobjects
+        .0
+        .iter()
+        .map(|obj| (obj.time, obj.new_combo))
+        .collect::<Vec<_>>();
+
+    // 2240 snaps to 2250, which is in the second measure
+    assert_eq!(vec![(125, true), (1000, false), (2250, true)], objs);
+    assert!(hitobjects
+        .0
+        .iter()
+        .all(|obj| obj.obj_params == HitObjectParams::HitCircle));
+}
+
+#[test]
+fn onsets_mania_columns() {
+    let onsets = [Millis(0), Millis(250), Millis(500)];
+    let options = ImportOptions::new(Mode::Mania).key_count(2).beat_snap(2);
+    let hitobjects = hitobjects_from_onsets(&onsets, &timing(), &options);
+
+    let xs = hitobjects
+        .0
+        .iter()
+        .map(|obj| obj.position.x.to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(vec!["128", "384", "128"], xs);
+}
+
+#[cfg(feature = "midi")]
+#[test]
+fn midi_mania_holds() {
+    use crate::import::{hitobjects_from_midi, MidiNote};
+
+    let note = |start, end, key| MidiNote {
+        start: Millis(start),
+        end: Millis(end),
+        key,
+        velocity: 100,
+    };
+    let notes = [note(0, 1000, 60), note(0, 10, 61), note(5, 20, 64)];
+    let hitobjects = hitobjects_from_midi(&notes, &timing(), &ImportOptions::new(Mode::Mania));
+
+    assert_eq!(2, hitobjects.0.len());
+    assert_eq!(
+        HitObjectParams::OsuManiaHold { end_time: 1000 },
+        hitobjects.0[0].obj_params
+    );
+    assert_eq!(HitObjectParams::HitCircle, hitobjects.0[1].obj_params);
+}
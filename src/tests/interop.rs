@@ -0,0 +1,105 @@
+use pretty_assertions::assert_eq;
+
+use crate::interop::{
+    bookmarks_from_audacity_labels, bookmarks_from_csv, bookmarks_to_audacity_labels,
+    bookmarks_to_csv,
+};
+
+#[test]
+fn audacity_labels() {
+    let labels = bookmarks_to_audacity_labels(&[1500, 62000]);
+    assert_eq!(labels, "1.500000\t1.500000\t1\n62.000000\t62.000000\t2");
+    assert_eq!(
+        bookmarks_from_audacity_labels(&labels).unwrap(),
+        vec![1500, 62000]
+    );
+
+    // region labels with a frequency range line
+    let labels = "0.2504\t3.000000\tintro\n\\\t100.000000\t2000.000000\n";
+    assert_eq!(bookmarks_from_audacity_labels(labels).unwrap(), vec![250]);
+}
+
+#[test]
+fn csv() {
+    let csv = bookmarks_to_csv(&[0, 250, 10001]);
+    assert_eq!(csv, "0.000000\n0.250000\n10.001000");
+    assert_eq!(bookmarks_from_csv(&csv).unwrap(), vec![0, 250, 10001]);
+    assert_eq!(
+        bookmarks_from_csv("1.5, 2,\n3.0005").unwrap(),
+        vec![1500, 2000, 3000]
+    );
+
+    let err = bookmarks_from_csv("1\n2\nfoo").unwrap_err();
+    assert_eq!(err.line_index(), 2);
+    assert_eq!(
+        err.error().to_string(),
+        "Invalid time `foo`, expected a number of seconds"
+    );
+}
+
+#[cfg(feature = "rosu-pp")]
+#[test]
+fn calculator_beatmap() {
+    use rosu_pp::model::hit_object::HitObjectKind;
+
+    use crate::osu_file::OsuFile;
+
+    let osu_file = "osu file format v4
+
+[Difficulty]
+OverallDifficulty:8
+SliderMultiplier:1.4
+
+[Events]
+0,0,\"bg.png\",0,0
+2,1000,2000
+
+[TimingPoints]
+1000,500
+1500,-50
+
+[HitObjects]
+256,192,1000,1,0
+100,100,1500,2,0,L|200:100,2,100"
+        .parse::<OsuFile>()
+        .unwrap();
+
+    let beatmap = rosu_pp::Beatmap::from(&osu_file);
+
+    assert_eq!(beatmap.ar, 8.0);
+    assert_eq!(beatmap.breaks.len(), 1);
+    assert_eq!(beatmap.breaks[0].start_time, 1024.0);
+    assert_eq!(beatmap.timing_points.len(), 1);
+    assert_eq!(beatmap.timing_points[0].time, 1024.0);
+    assert_eq!(beatmap.difficulty_points[1].slider_velocity, 2.0);
+    assert_eq!(beatmap.hit_objects[0].start_time, 1024.0);
+    match &beatmap.hit_objects[1].kind {
+        HitObjectKind::Slider(slider) => {
+            assert_eq!(slider.repeats, 1);
+            assert_eq!(slider.control_points[1].pos.x, 100.0);
+            assert_eq!(slider.expected_dist, Some(100.0));
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(feature = "rosu-pp")]
+#[test]
+fn calculator_mode() {
+    use rosu_pp::model::mode::GameMode;
+
+    use crate::{
+        interop::UnknownModeError,
+        osu_file::{general::Mode, OsuFile, ParseOptions},
+    };
+
+    assert_eq!(GameMode::try_from(Mode::Mania), Ok(GameMode::Mania));
+    assert_eq!(GameMode::try_from(Mode::Other(4)), Err(UnknownModeError(4)));
+
+    let osu_file = OsuFile::from_str_with_options(
+        "osu file format v14\n\n[General]\nMode: 4",
+        &ParseOptions::new().lenient(true),
+    )
+    .unwrap();
+    assert_eq!(rosu_pp::Beatmap::from(&osu_file).mode, GameMode::Osu);
+}
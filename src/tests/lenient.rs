@@ -0,0 +1,225 @@
+use rust_decimal_macros::dec;
+
+use crate::osu_file::{
+    colours::{Colour, Rgb},
+    general::{Mode, OverlayPosition},
+    OsuFile, ParseOptions, ParseWarning,
+};
+
+const OSU_FILE: &str = "osu file format v14
+
+[General]
+AudioFilename: audio.mp3
+StackLeniency: 0,7
+
+[Editor]
+Bookmarks: 1000,2000
+
+[Difficulty]
+SliderMultiplier:1,4";
+
+#[test]
+fn comma_decimal_separator() {
+    assert!(OSU_FILE.parse::<OsuFile>().is_err());
+
+    let osu_file =
+        OsuFile::from_str_with_options(OSU_FILE, &ParseOptions::new().lenient(true)).unwrap();
+
+    let stack_leniency = osu_file.general.as_ref().unwrap().stack_leniency.clone();
+    assert_eq!(stack_leniency.map(Into::into), Some(dec!(0.7)));
+
+    let slider_multiplier = osu_file
+        .difficulty
+        .as_ref()
+        .unwrap()
+        .slider_multiplier
+        .clone();
+    assert_eq!(slider_multiplier.map(Into::into), Some(dec!(1.4)));
+
+    // lists aren't decimals, so they are left alone
+    assert!(osu_file.to_string().contains("Bookmarks: 1000,2000"));
+
+    assert_eq!(
+        osu_file.parse_warnings(),
+        &[
+            ParseWarning::CommaDecimalSeparator {
+                line_index: 4,
+                field: "StackLeniency".to_string(),
+            },
+            ParseWarning::CommaDecimalSeparator {
+                line_index: 10,
+                field: "SliderMultiplier".to_string(),
+            },
+        ]
+    );
+}
+
+const COLOURS: &str = "osu file format v14
+
+[Colours]
+Combo1 : 255, 128,255
+Combo2 : 0,0,0,128
+SliderBorder: 10,20,30";
+
+#[test]
+fn colour_alpha() {
+    assert!(COLOURS.parse::<OsuFile>().is_err());
+
+    let osu_file =
+        OsuFile::from_str_with_options(COLOURS, &ParseOptions::new().lenient(true)).unwrap();
+
+    assert_eq!(
+        osu_file.colours.as_ref().unwrap().0[1],
+        Colour::Combo(
+            2,
+            Rgb {
+                red: 0,
+                green: 0,
+                blue: 0,
+            }
+        )
+    );
+    assert_eq!(
+        osu_file.parse_warnings(),
+        &[ParseWarning::ColourAlphaDropped { line_index: 4 }]
+    );
+    assert!(osu_file.to_string().contains(
+        "Combo1 : 255,128,255
+Combo2 : 0,0,0
+SliderBorder : 10,20,30"
+    ));
+}
+
+#[test]
+fn preserve_colour_formatting() {
+    let options = ParseOptions::new()
+        .lenient(true)
+        .preserve_colour_formatting(true);
+    let mut osu_file = OsuFile::from_str_with_options(COLOURS, &options).unwrap();
+
+    assert_eq!(osu_file.to_string(), COLOURS);
+
+    // changed colours are written normally
+    osu_file.colours.as_mut().unwrap().0.pop();
+    assert!(osu_file.to_string().ends_with(
+        "Combo1 : 255,128,255
+Combo2 : 0,0,0"
+    ));
+}
+
+const UNKNOWN_VALUES: &str = "osu file format v14
+
+[General]
+AudioFilename: audio.mp3
+Mode: 4
+OverlayPosition: Sideways";
+
+#[test]
+fn unknown_mode_and_overlay_position() {
+    assert!(UNKNOWN_VALUES.parse::<OsuFile>().is_err());
+
+    let osu_file =
+        OsuFile::from_str_with_options(UNKNOWN_VALUES, &ParseOptions::new().lenient(true)).unwrap();
+    let general = osu_file.general.as_ref().unwrap();
+
+    assert_eq!(general.mode, Some(Mode::Other(4)));
+    assert_eq!(u8::from(Mode::Other(4)), 4);
+    assert_eq!(u8::from(Mode::Mania), 3);
+    assert_eq!(
+        general.overlay_position,
+        Some(OverlayPosition::Other("Sideways".to_string()))
+    );
+    assert_eq!(
+        osu_file.parse_warnings(),
+        &[
+            ParseWarning::UnknownValue {
+                line_index: 4,
+                field: "Mode".to_string(),
+                value: "4".to_string(),
+            },
+            ParseWarning::UnknownValue {
+                line_index: 5,
+                field: "OverlayPosition".to_string(),
+                value: "Sideways".to_string(),
+            },
+        ]
+    );
+
+    let written = osu_file.to_string();
+    assert!(written.contains("Mode: 4\n"));
+    assert!(written.contains("OverlayPosition: Sideways"));
+}
+
+const MALFORMED_LINES: &str = "osu file format v14
+
+[TimingPoints]
+0,500,4,2,0,60,1,0
+1000,500,x,2,0,60,1,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+256,192
+256,192,2000,1,0,0:0:0:0:";
+
+#[test]
+fn malformed_lines_skipped() {
+    assert!(MALFORMED_LINES.parse::<OsuFile>().is_err());
+
+    let (osu_file, warnings) = OsuFile::from_str_lenient(MALFORMED_LINES).unwrap();
+
+    assert_eq!(osu_file.timing_points.as_ref().unwrap().0.len(), 1);
+    assert_eq!(
+        osu_file
+            .hitobjects
+            .as_ref()
+            .unwrap()
+            .0
+            .iter()
+            .map(|h| h.time)
+            .collect::<Vec<_>>(),
+        vec![1000, 2000]
+    );
+    assert_eq!(
+        warnings,
+        vec![
+            ParseWarning::MalformedLineSkipped {
+                line_index: 4,
+                line: "1000,500,x,2,0,60,1,0".to_string(),
+                message: "Invalid `meter` value".to_string(),
+            },
+            ParseWarning::MalformedLineSkipped {
+                line_index: 8,
+                line: "256,192".to_string(),
+                message: "Missing `time` field".to_string(),
+            },
+        ]
+    );
+    assert_eq!(osu_file.parse_warnings(), warnings);
+}
+
+#[test]
+fn malformed_colour_lines_skipped() {
+    let osu_file = "osu file format v14
+
+[Colours]
+Combo1 : 255,0,0
+Combo2 : x
+Combo3 : 0,0
+Combo4 : 0,0,255";
+
+    let (osu_file, warnings) = OsuFile::from_str_lenient(osu_file).unwrap();
+
+    assert_eq!(osu_file.colours.as_ref().unwrap().0.len(), 2);
+    assert_eq!(
+        warnings
+            .iter()
+            .map(|warning| match warning {
+                ParseWarning::MalformedLineSkipped {
+                    line_index, line, ..
+                } => (*line_index, line.as_str()),
+                _ => panic!("unexpected warning {warning:?}"),
+            })
+            .collect::<Vec<_>>(),
+        vec![(4, "Combo2 : x"), (5, "Combo3 : 0,0")]
+    );
+}
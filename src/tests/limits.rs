@@ -0,0 +1,49 @@
+use crate::osu_file::{LimitsExceeded, OsuFile, ParseError, ParseLimits, ParseOptions};
+
+const OSU_FILE: &str = "osu file format v14
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+256,192,2000,1,0,0:0:0:0:
+256,192,3000,1,0,0:0:0:0:";
+
+fn parse(limits: ParseLimits) -> Result<OsuFile, crate::Error<ParseError>> {
+    OsuFile::from_str_with_options(OSU_FILE, &ParseOptions::new().limits(limits))
+}
+
+#[test]
+fn limits_not_exceeded() {
+    let limits = ParseLimits::new()
+        .max_input_bytes(OSU_FILE.len())
+        .max_line_length(25)
+        .max_objects(3);
+
+    assert!(parse(limits).is_ok());
+}
+
+#[test]
+fn limits_exceeded() {
+    let err = parse(ParseLimits::new().max_input_bytes(10)).unwrap_err();
+    assert_eq!(0, err.line_index());
+    assert!(matches!(
+        err.error(),
+        ParseError::LimitsExceeded(LimitsExceeded::InputBytes { limit: 10, .. })
+    ));
+
+    let err = parse(ParseLimits::new().max_line_length(24)).unwrap_err();
+    assert_eq!(3, err.line_index());
+    assert!(matches!(
+        err.error(),
+        ParseError::LimitsExceeded(LimitsExceeded::LineLength {
+            limit: 24,
+            length: 25
+        })
+    ));
+
+    let err = parse(ParseLimits::new().max_objects(2)).unwrap_err();
+    assert_eq!(5, err.line_index());
+    assert_eq!(
+        "The section has more than 2 entries",
+        err.error().to_string()
+    );
+}
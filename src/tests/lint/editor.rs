@@ -0,0 +1,42 @@
+use crate::{
+    lint::{LintRule, LintTarget},
+    osu_file::OsuFile,
+};
+
+#[test]
+fn grid_size_and_distance_spacing() {
+    let osu_file = "osu file format v14
+
+[Editor]
+DistanceSpacing: 8.5
+GridSize: 12"
+        .parse::<OsuFile>()
+        .unwrap();
+
+    assert!(osu_file.to_string().contains("GridSize: 12"));
+
+    let issues = osu_file.lint_editor();
+    let rules = issues
+        .iter()
+        .map(|i| (i.rule, i.target.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        rules,
+        vec![
+            (LintRule::UnknownGridSize, LintTarget::Field("GridSize")),
+            (
+                LintRule::DistanceSpacingOutOfRange,
+                LintTarget::Field("DistanceSpacing")
+            ),
+        ]
+    );
+
+    let osu_file = "osu file format v14
+
+[Editor]
+DistanceSpacing: 1.2
+GridSize: 16"
+        .parse::<OsuFile>()
+        .unwrap();
+    assert!(osu_file.lint_editor().is_empty());
+}
@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+
+use crate::{
+    lint::{FileReference, FileReferenceKind, LintRule, Severity},
+    osu_file::OsuFile,
+};
+
+const OSU_FILE: &str = r#"osu file format v14
+
+[General]
+AudioFilename: audio.mp3
+
+[Events]
+0,0,"C:\bg.jpg",0,0
+Video,0,"vid?.avi"
+Sprite,Background,Centre,"SB/star.png",320,240
+Sprite,Background,Centre,"SB/star.png",320,240
+Animation,Foreground,Centre,"sb/anim.png",320,240,2,100,LoopForever
+Sample,1000,0,"../hit.wav",70
+Sample,2000,0,"/hit.wav",70"#;
+
+#[test]
+fn file_references() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+
+    let reference = |path: &str, kind| FileReference {
+        path: PathBuf::from(path),
+        kind,
+    };
+    assert_eq!(
+        vec![
+            reference("audio.mp3", FileReferenceKind::Audio),
+            reference("C:\\bg.jpg", FileReferenceKind::Background),
+            reference("vid?.avi", FileReferenceKind::Video),
+            reference("SB/star.png", FileReferenceKind::Sprite),
+            reference(
+                "sb/anim.png",
+                FileReferenceKind::Animation {
+                    frame_count: Some(2)
+                }
+            ),
+            reference("../hit.wav", FileReferenceKind::Sample),
+            reference("/hit.wav", FileReferenceKind::Sample),
+        ],
+        osu_file.file_references()
+    );
+}
+
+#[test]
+fn lint_file_references() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+
+    let rules = |issues: Vec<crate::lint::LintIssue>| {
+        issues
+            .into_iter()
+            .map(|issue| (issue.rule, issue.severity))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        vec![
+            (LintRule::DriveLetter, Severity::Error),
+            (LintRule::IllegalCharacters, Severity::Error),
+            (LintRule::ParentTraversal, Severity::Error),
+            (LintRule::AbsolutePath, Severity::Error),
+        ],
+        rules(osu_file.lint_file_references(None))
+    );
+
+    let files = vec![
+        PathBuf::from("audio.mp3"),
+        PathBuf::from("sb/star.png"),
+        PathBuf::from("sb/anim0.png"),
+    ];
+    let issues = rules(osu_file.lint_file_references(Some(&files)));
+
+    assert_eq!(
+        vec![
+            (LintRule::MissingFile, Severity::Warning),
+            (LintRule::MissingFile, Severity::Warning),
+            (LintRule::CaseMismatch, Severity::Warning),
+            (LintRule::MissingFile, Severity::Warning),
+            (LintRule::MissingFile, Severity::Warning),
+            (LintRule::MissingFile, Severity::Warning),
+        ],
+        issues[4..]
+    );
+}
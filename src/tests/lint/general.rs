@@ -0,0 +1,53 @@
+use crate::{
+    lint::{LintRule, LintTarget},
+    osu_file::{general::Countdown, OsuFile, ParseOptions},
+};
+
+const OSU_FILE: &str = "osu file format v14
+
+[General]
+AudioFilename: audio.mp3
+Countdown: 5
+CountdownOffset: 2";
+
+#[test]
+fn countdown_other() {
+    assert!(OSU_FILE.parse::<OsuFile>().is_err());
+
+    let options = ParseOptions::new().lenient(true);
+    let osu_file = OsuFile::from_str_with_options(OSU_FILE, &options).unwrap();
+    let general = osu_file.general.as_ref().unwrap();
+
+    assert_eq!(general.countdown, Some(Countdown::Other(5)));
+    assert!(osu_file.to_string().contains("Countdown: 5\n"));
+
+    let issues = osu_file.lint_general();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].rule, LintRule::UnknownCountdown);
+    assert_eq!(issues[0].target, LintTarget::Field("Countdown"));
+}
+
+#[test]
+fn countdown_offset_without_countdown() {
+    let osu_file = OSU_FILE
+        .replace("Countdown: 5", "Countdown: 0")
+        .parse::<OsuFile>()
+        .unwrap();
+    #[allow(deprecated)]
+    let no_countdown = Countdown::NoCountdown;
+    assert_eq!(
+        osu_file.general.as_ref().unwrap().countdown,
+        Some(no_countdown)
+    );
+
+    let issues = osu_file.lint_general();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].rule, LintRule::CountdownOffsetWithoutCountdown);
+    assert_eq!(issues[0].target, LintTarget::Field("CountdownOffset"));
+
+    let osu_file = OSU_FILE
+        .replace("Countdown: 5", "Countdown: 1")
+        .parse::<OsuFile>()
+        .unwrap();
+    assert!(osu_file.lint_general().is_empty());
+}
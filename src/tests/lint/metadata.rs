@@ -0,0 +1,64 @@
+use crate::{
+    lint::{LintRule, LintTarget},
+    osu_file::OsuFile,
+};
+
+#[test]
+fn diff_name_semantics() {
+    let name = |s: &str| crate::osu_file::metadata::Version::from(s.to_string());
+
+    assert_eq!(name("4K Hard").key_count(), Some(4));
+    assert_eq!(name("[7K] Insane").key_count(), Some(7));
+    assert_eq!(name("[7K] Insane").without_key_count(), "Insane");
+    assert_eq!(name("Hard").key_count(), None);
+    assert_eq!(name("Kiai").key_count(), None);
+
+    assert_eq!(name("Name's Insane").guest_creators(), vec!["Name"]);
+    assert_eq!(name("4K Chris' Hard").guest_creators(), vec!["Chris"]);
+    assert_eq!(name("A & B's Extra").guest_creators(), vec!["A", "B"]);
+    assert!(name("Insane").guest_creators().is_empty());
+
+    assert!(name("A & B's Extra").is_collab());
+    assert!(name("Collab Expert").is_collab());
+    assert!(!name("Name's Insane").is_collab());
+}
+
+#[test]
+fn key_count_mismatch() {
+    let osu_file = "osu file format v14
+
+[General]
+Mode: 3
+
+[Metadata]
+Version:4K Hard
+
+[Difficulty]
+CircleSize:7"
+        .parse::<OsuFile>()
+        .unwrap();
+
+    let issues = osu_file.lint_metadata();
+    let rules = issues
+        .iter()
+        .map(|i| (i.rule, i.target.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        rules,
+        vec![(LintRule::KeyCountMismatch, LintTarget::Field("Version"))]
+    );
+
+    let osu_file = "osu file format v14
+
+[General]
+Mode: 3
+
+[Metadata]
+Version:[7K] Hard
+
+[Difficulty]
+CircleSize:7"
+        .parse::<OsuFile>()
+        .unwrap();
+    assert!(osu_file.lint_metadata().is_empty());
+}
@@ -0,0 +1,5 @@
+mod editor;
+mod file_references;
+mod general;
+mod metadata;
+mod timing_points;
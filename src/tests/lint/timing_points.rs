@@ -0,0 +1,61 @@
+use crate::{
+    lint::{LintRule, LintTarget},
+    osu_file::OsuFile,
+};
+
+#[test]
+fn unknown_effects_bits() {
+    let osu_file = "osu file format v14
+
+[TimingPoints]
+0,500,4,2,0,60,1,9
+1000,-100,4,2,0,60,0,5"
+        .parse::<OsuFile>()
+        .unwrap();
+
+    let timing_points = &osu_file.timing_points.as_ref().unwrap().0;
+    assert_eq!(timing_points[1].effects.unwrap().raw(), 5);
+    assert_eq!(timing_points[1].effects.unwrap().unknown_bits(), 0b100);
+    assert!(osu_file.to_string().ends_with("1000,-100,4,2,0,60,0,5"));
+
+    let issues = osu_file.lint_timing_points();
+    let rules = issues
+        .iter()
+        .map(|i| (i.rule, i.target.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        rules,
+        vec![(LintRule::UnknownEffectsBits, LintTarget::TimingPoint(1))]
+    );
+}
+
+#[test]
+fn non_positive_beat_lengths() {
+    let osu_file = "osu file format v14
+
+[TimingPoints]
+0,500,4,2,0,60,1,0
+100,0,4,2,0,60,1,0
+200,-50,4,2,0,60,0,0
+300,0,4,2,0,60,0,0
+400,-500,4,2,0,60,1,0"
+        .parse::<OsuFile>()
+        .unwrap();
+
+    let rules = osu_file
+        .lint_timing_points()
+        .iter()
+        .map(|i| (i.rule, i.target.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        rules,
+        vec![
+            (LintRule::NonPositiveBeatLength, LintTarget::TimingPoint(1)),
+            (
+                LintRule::NonNegativeInheritedBeatLength,
+                LintTarget::TimingPoint(3)
+            ),
+            (LintRule::NonPositiveBeatLength, LintTarget::TimingPoint(4)),
+        ]
+    );
+}
@@ -0,0 +1,56 @@
+use pretty_assertions::assert_eq;
+
+use crate::osu_file::{Loss, LossKind, LossTarget, OsuFile, SectionName};
+
+const V5: &str = "osu file format v5
+
+[General]
+AudioFilename: audio.mp3
+AudioHash: 0123456789abcdef
+EditorBookmarks: 100,200
+
+[Colours]
+Combo1 : 255,150,0
+";
+
+#[test]
+fn same_version_is_lossless() {
+    let osu_file = V5.parse::<OsuFile>().unwrap();
+
+    assert_eq!(osu_file.lossiness_report(5), Vec::new());
+}
+
+#[test]
+fn dropped_fields() {
+    let osu_file = V5.parse::<OsuFile>().unwrap();
+
+    assert_eq!(
+        osu_file.lossiness_report(14),
+        vec![
+            Loss {
+                target: LossTarget::Field {
+                    section: SectionName::General,
+                    name: "AudioHash",
+                },
+                kind: LossKind::Dropped,
+            },
+            Loss {
+                target: LossTarget::Field {
+                    section: SectionName::General,
+                    name: "EditorBookmarks",
+                },
+                kind: LossKind::Dropped,
+            },
+        ]
+    );
+}
+
+#[test]
+fn dropped_section() {
+    let osu_file = V5.parse::<OsuFile>().unwrap();
+
+    assert!(osu_file.lossiness_report(4).contains(&Loss {
+        target: LossTarget::Section(SectionName::Colours),
+        kind: LossKind::Dropped,
+    }));
+}
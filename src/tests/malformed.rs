@@ -0,0 +1,112 @@
+//! Malformed input should return an error instead of panicking.
+
+use crate::osu_file::{
+    events::Events, hitobjects::HitObject, timingpoints::TimingPoint, VersionedFromStr,
+};
+
+const HITOBJECTS: &[&str] = &[
+    "256,192,1000,1,0,0:0:0:0:",
+    "31,85,3049,2,0,B|129:55|123:136|228:86,1,172.51,2|0,3:2|0:2,0:2:0:0:",
+    "256,192,730,12,8,3983",
+    "329,192,16504,128,0,16620:0:0:0:0:",
+];
+
+const TIMING_POINTS: &[&str] = &["10000,333.33,4,0,0,100,1,1", "12000,-25,4,3,0,100,0,1"];
+
+const EVENTS: &[&str] = &[
+    "0,0,\"bg.jpg\",0,0",
+    "Video,-200,\"video.mp4\"",
+    "2,4627,5718",
+    "Sprite,Pass,Centre,\"Text\\Play2-HaveFunH.png\",320,240\n F,0,-28,,1\n L,0,2\n  F,0,0,100,0,1",
+    "Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopForever",
+    "Sample,24,0,\"Drum-hitnormal.wav\",60",
+];
+
+const VERSIONS: &[u8] = &[3, 14];
+
+/// Every prefix of the line, cut at each char boundary.
+fn truncations(s: &str) -> impl Iterator<Item = &str> {
+    s.char_indices().map(move |(i, _)| &s[..i])
+}
+
+/// The line with each char replaced by a few characters that commonly break the parsers.
+fn substitutions(s: &str) -> impl Iterator<Item = String> + '_ {
+    s.char_indices().flat_map(move |(i, c)| {
+        [",", ":", "|", "-", "", "99999999999999999999", "x"]
+            .into_iter()
+            .map(move |sub| format!("{}{sub}{}", &s[..i], &s[i + c.len_utf8()..]))
+    })
+}
+
+#[test]
+fn hitobjects_no_panic() {
+    for version in VERSIONS {
+        for line in HITOBJECTS {
+            for s in truncations(line) {
+                let _ = HitObject::from_str(s, *version);
+            }
+            for s in substitutions(line) {
+                let _ = HitObject::from_str(&s, *version);
+            }
+        }
+    }
+}
+
+#[test]
+fn timing_points_no_panic() {
+    for version in VERSIONS {
+        for line in TIMING_POINTS {
+            for s in truncations(line) {
+                let _ = TimingPoint::from_str(s, *version);
+            }
+            for s in substitutions(line) {
+                let _ = TimingPoint::from_str(&s, *version);
+            }
+        }
+    }
+}
+
+#[test]
+fn events_no_panic() {
+    for version in VERSIONS {
+        for line in EVENTS {
+            for s in truncations(line) {
+                let _ = Events::from_str(s, *version);
+            }
+            for s in substitutions(line) {
+                let _ = Events::from_str(&s, *version);
+            }
+        }
+    }
+}
+
+#[test]
+fn hitobject_missing_fields() {
+    let cases = [
+        ("", "Invalid `x` value"),
+        ("0,0", "Missing `time` field"),
+        ("0,0,0", "Missing `obj_type` field"),
+        ("0,0,0,1", "Missing `hitsound` field"),
+        ("0,0,0,2,0,B|1:1", "Missing `slides_count` field"),
+        ("0,0,0,2,0,B|1:1,1", "Missing `length` field"),
+        ("0,0,0,8,0", "Missing `end_time` field"),
+        ("0,0,0,128,0", "Missing `end_time` field"),
+        ("0,0,0,3,0", "Unknown object type"),
+    ];
+
+    for (i, o) in cases {
+        assert_eq!(
+            o,
+            HitObject::from_str(i, 14).unwrap_err().to_string(),
+            "{i}"
+        );
+    }
+}
+
+#[test]
+fn timing_point_invalid_effects() {
+    let i = "10000,333.33,4,0,0,100,1,x";
+    let o = TimingPoint::from_str(i, 14).unwrap_err();
+
+    assert_eq!("Invalid `effects` value", o.to_string());
+}
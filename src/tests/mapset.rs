@@ -0,0 +1,198 @@
+use crate::lint::{LintRule, LintTarget};
+use crate::mapset::{
+    parse_corpus, BeatmapKey, BeatmapSink, Mapset, MapsetLoadError, MemoryStorage,
+    StoryboardSource, VariableConflict,
+};
+#[cfg(feature = "unicode-normalization")]
+use crate::osu_file::metadata::Normalization;
+use crate::osu_file::{events::storyboard::sprites::Layer, Osb, OsuFile, VersionedFromStr};
+
+#[test]
+fn mapset_store_load() {
+    let osu_str = include_str!("./osu_files/files/v14.osu");
+    let osu_file = osu_str.parse::<OsuFile>().unwrap();
+
+    let mut mapset = Mapset::new();
+    mapset.push_difficulty("diff.osu", osu_file.clone());
+
+    let mut storage = MemoryStorage::default();
+    mapset.store(&mut storage).unwrap();
+
+    let loaded = Mapset::load(&storage, &["diff.osu"]).unwrap();
+
+    assert_eq!(loaded.difficulty("diff.osu"), Some(&osu_file));
+    assert!(matches!(
+        Mapset::load(&storage, &["missing.osu"]),
+        Err(MapsetLoadError::NotFound(_))
+    ));
+}
+
+#[test]
+fn corpus_parse() {
+    let mut storage = MemoryStorage::default();
+    storage
+        .store(
+            &BeatmapKey::BeatmapId(1),
+            include_str!("./osu_files/files/v14.osu").as_bytes(),
+        )
+        .unwrap();
+    storage
+        .store(&BeatmapKey::BeatmapId(2), b"not a beatmap")
+        .unwrap();
+
+    let results = parse_corpus(
+        &storage,
+        [BeatmapKey::BeatmapId(1), BeatmapKey::BeatmapId(2)],
+    )
+    .collect::<Vec<_>>();
+
+    assert!(results[0].1.is_ok());
+    assert!(matches!(results[1].1, Err(MapsetLoadError::ParseError(_))));
+}
+
+#[test]
+fn combined_storyboard() {
+    let mut osu_file = r#"osu file format v14
+
+[General]
+WidescreenStoryboard: 1
+
+[Events]
+0,0,"bg.jpg",0,0
+Sprite,Foreground,Centre,"diff_fg.png",320,240
+Sprite,Background,Centre,"diff_bg.png",320,240"#
+        .parse::<OsuFile>()
+        .unwrap();
+    osu_file.append_osb("[Variables]\n$a=2").unwrap();
+
+    let osb = r#"[Variables]
+$a=1
+
+[Events]
+Sprite,Foreground,Centre,"osb_fg.png",320,240
+Sprite,Background,Centre,"osb_bg.png",320,240
+Sample,0,0,"hit.wav",100"#;
+
+    let mut mapset = Mapset::new();
+    mapset.push_difficulty("diff.osu", osu_file);
+    mapset.osb = Osb::from_str(osb, 14).unwrap();
+
+    let storyboard = mapset.combined_storyboard("diff.osu").unwrap();
+
+    assert!(storyboard.widescreen);
+    assert_eq!(
+        vec![
+            (Layer::Background, StoryboardSource::Osb),
+            (Layer::Background, StoryboardSource::Osb),
+            (Layer::Background, StoryboardSource::Difficulty),
+            (Layer::Foreground, StoryboardSource::Osb),
+            (Layer::Foreground, StoryboardSource::Difficulty),
+        ],
+        storyboard
+            .elements
+            .iter()
+            .map(|e| (e.layer, e.source))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(2, storyboard.layer(Layer::Foreground).count());
+    assert_eq!(
+        vec![VariableConflict {
+            name: "a".to_string(),
+            values: vec!["1".to_string(), "2".to_string()],
+        }],
+        storyboard.variable_conflicts
+    );
+
+    assert_eq!(None, mapset.combined_storyboard("missing.osu"));
+}
+
+fn difficulty(audio: &str, title: &str, beat_length: &str) -> OsuFile {
+    format!(
+        "osu file format v14
+
+[General]
+AudioFilename: {audio}
+
+[Metadata]
+Title:{title}
+
+[TimingPoints]
+0,{beat_length},4,2,0,60,1,0"
+    )
+    .parse()
+    .unwrap()
+}
+
+#[test]
+fn validate_parallel() {
+    let mut mapset = Mapset::new();
+    mapset.push_difficulty("a.osu", difficulty("audio.mp3", "Song", "0"));
+    mapset.push_difficulty("b.osu", difficulty("other.mp3", "Other Song", "500"));
+    mapset.push_difficulty("c.osu", difficulty("audio.mp3", "Song", "500"));
+
+    let report = mapset.validate_parallel();
+    let issues = report
+        .issues
+        .iter()
+        .map(|i| {
+            (
+                i.file_name.as_str(),
+                i.line,
+                i.issue.rule,
+                i.issue.target.clone(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        issues,
+        vec![
+            (
+                "a.osu",
+                Some(10),
+                LintRule::NonPositiveBeatLength,
+                LintTarget::TimingPoint(0)
+            ),
+            (
+                "b.osu",
+                Some(4),
+                LintRule::AudioMismatch,
+                LintTarget::Field("AudioFilename")
+            ),
+            (
+                "b.osu",
+                Some(7),
+                LintRule::MetadataMismatch,
+                LintTarget::Field("Title")
+            ),
+        ]
+    );
+    assert!(report.has_errors());
+    assert_eq!(report.by_file().len(), 2);
+    assert_eq!(
+        report.issues[2].issue.message,
+        "`Title` is `Other Song`, but it's `Song` in `a.osu`"
+    );
+}
+
+#[cfg(feature = "unicode-normalization")]
+#[test]
+fn validate_normalized_metadata() {
+    let mut mapset = Mapset::new();
+    mapset.push_difficulty("a.osu", difficulty("audio.mp3", "Caf\u{e9}", "500"));
+    mapset.push_difficulty("b.osu", difficulty("audio.mp3", "Cafe\u{301}", "500"));
+
+    assert!(mapset.validate_parallel().issues.is_empty());
+
+    let report = mapset.validate_parallel_with(Normalization::None);
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].issue.rule, LintRule::MetadataMismatch);
+
+    let metadata = |i: usize| mapset.difficulties[i].osu_file.metadata.as_ref().unwrap();
+    assert!(metadata(0).eq_normalized(metadata(1), Normalization::Nfc));
+    assert!(!metadata(0).eq_normalized(metadata(1), Normalization::None));
+    assert_eq!(
+        Normalization::Nfkc.apply("\u{ff21}\u{ff22}"),
+        Normalization::Nfc.apply("AB")
+    );
+}
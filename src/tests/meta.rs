@@ -0,0 +1,76 @@
+use rust_decimal_macros::dec;
+
+use crate::osu_file::{hitobjects::HitObjectParams, BpmRange, OsuFile, OsuFileMeta};
+
+#[test]
+fn meta_matches_full_parse() {
+    let osu_str = include_str!("./osu_files/files/acid_rain.osu");
+    let osu_file = osu_str.parse::<OsuFile>().unwrap();
+    let meta = OsuFileMeta::parse(osu_str).unwrap();
+
+    assert_eq!(meta.version, osu_file.version);
+    assert_eq!(meta.general, osu_file.general);
+    assert_eq!(meta.metadata, osu_file.metadata);
+    assert_eq!(meta.difficulty, osu_file.difficulty);
+
+    let hitobjects = &osu_file.hitobjects.unwrap().0;
+    let sliders = hitobjects
+        .iter()
+        .filter(|h| matches!(h.obj_params, HitObjectParams::Slider(_)))
+        .count();
+    assert_eq!(meta.object_counts.total(), hitobjects.len());
+    assert_eq!(meta.object_counts.sliders, sliders);
+}
+
+#[test]
+fn meta_bpm_range() {
+    let meta = OsuFileMeta::parse(
+        "osu file format v14
+
+[Metadata]
+Title:Song
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+1000,-50,4,2,0,100,0,0
+2000,250,4,2,0,100,1,0
+3000,400
+
+[HitObjects]
+256,192,0,1,0,0:0:0:0:
+256,192,2500,12,0,3000,0:0:0:0:",
+    )
+    .unwrap();
+
+    // the 150 BPM timing point is after the last hitobject starts
+    assert_eq!(
+        meta.bpm,
+        Some(BpmRange {
+            min: dec!(120),
+            max: dec!(240),
+        })
+    );
+    assert_eq!(meta.object_counts.hitcircles, 1);
+    assert_eq!(meta.object_counts.spinners, 1);
+    assert!(meta.general.is_none());
+
+    let err = OsuFileMeta::parse("osu file format v14\n\n[Difficulty]\nCircleSize:x").unwrap_err();
+    assert_eq!(err.line_index(), 3);
+}
+
+#[test]
+fn meta_general_migrated() {
+    let osu_str = "osu file format v14
+
+[General]
+AudioFilename: audio.mp3
+EditorBookmarks: 100,200
+
+[HitObjects]
+256,192,0,1,0,0:0:0:0:";
+    let osu_file = osu_str.parse::<OsuFile>().unwrap();
+    let meta = OsuFileMeta::parse(osu_str).unwrap();
+
+    assert_eq!(meta.general, osu_file.general);
+    assert_eq!(meta.general.unwrap().editor_bookmarks, None);
+}
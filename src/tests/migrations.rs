@@ -0,0 +1,78 @@
+use pretty_assertions::assert_eq;
+use rust_decimal_macros::dec;
+
+use crate::osu_file::{FieldValue, OsuFile, SectionName, Version};
+
+fn convert_version(osu_file: &OsuFile, version: Version) -> String {
+    let mut osu_file = osu_file.clone();
+    osu_file.migrate_fields(version);
+    osu_file.version = version;
+
+    osu_file.to_string()
+}
+
+const V5: &str = "osu file format v5
+
+[General]
+AudioFilename: audio.mp3
+EditorBookmarks: 100,200
+EditorDistanceSpacing: 1.2";
+
+#[test]
+fn old_place_in_new_version() {
+    let osu_file = V5
+        .replace("format v5", "format v14")
+        .parse::<OsuFile>()
+        .unwrap();
+
+    let general = osu_file.general.as_ref().unwrap();
+    assert_eq!(general.editor_bookmarks, None);
+    assert_eq!(general.editor_distance_spacing, None);
+
+    let editor = osu_file.editor.as_ref().unwrap();
+    assert_eq!(
+        editor.get("Bookmarks"),
+        Some(FieldValue::IntegerList(vec![100, 200]))
+    );
+    assert_eq!(
+        editor.get("DistanceSpacing"),
+        Some(FieldValue::Decimal(dec!(1.2)))
+    );
+}
+
+#[test]
+fn convert_between_places() {
+    let osu_file = V5.parse::<OsuFile>().unwrap();
+    assert!(osu_file.editor.is_none());
+
+    let mut migrated = osu_file.clone();
+    migrated.migrate_fields(14);
+    assert_eq!(
+        migrated.dirty_sections(),
+        vec![SectionName::General, SectionName::Editor]
+    );
+
+    let v14 = convert_version(&osu_file, 14);
+    assert!(v14.ends_with(
+        "[Editor]
+Bookmarks: 100,200
+DistanceSpacing: 1.2"
+    ));
+
+    let v5 = convert_version(&v14.parse::<OsuFile>().unwrap(), 5);
+    assert_eq!(v5, V5);
+}
+
+#[test]
+fn new_place_wins() {
+    let mut osu_file = format!("{V5}\n\n[Editor]\nBookmarks: 300")
+        .parse::<OsuFile>()
+        .unwrap();
+    osu_file.migrate_fields(14);
+
+    assert_eq!(
+        osu_file.editor.as_ref().unwrap().get("Bookmarks"),
+        Some(FieldValue::IntegerList(vec![300]))
+    );
+    assert_eq!(osu_file.general.as_ref().unwrap().editor_bookmarks, None);
+}
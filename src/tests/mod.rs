@@ -1,8 +1,43 @@
+mod analysis;
+mod approx;
+mod assets;
+mod conversion;
+#[cfg(feature = "cookbook")]
+mod cookbook;
+mod custom_sections;
+mod defaults;
+mod diagnostic;
+mod dirty_sections;
 mod error_line_index;
+mod export;
+mod extensions;
+mod field_value;
+mod header;
 mod hitobjects;
+mod import;
+mod interop;
+mod lenient;
+mod limits;
+mod lint;
+mod lossiness;
+mod malformed;
+mod mapset;
+mod meta;
+mod migrations;
 mod osu_files;
+mod parser;
 mod parsers;
+mod position;
+mod preserve;
+mod schema;
+mod semantic_eq;
+#[cfg(feature = "serde")]
+mod serialization;
+mod simulation;
 mod storyboard;
+mod stream;
+mod timing_points;
+mod transform;
 
 use crate::osu_file::types::Decimal;
 use pretty_assertions::assert_eq;
@@ -12,15 +47,15 @@ use rust_decimal_macros::dec;
 
 use crate::osu_file::{
     colours::{Colour, Colours, Rgb},
-    difficulty::Difficulty,
+    difficulty::{CircleSizeValue, Difficulty},
     editor::{self, Editor},
-    events::{Background, Break, Event, Events},
+    events::{storyboard::sprites::ObjectType, Background, Break, Event, Events},
     general::{Countdown, General, Mode, OverlayPosition, SampleSet},
     metadata::Metadata,
     timingpoints,
     timingpoints::{Effects, SampleIndex, TimingPoint, TimingPoints, Volume},
     types::Position,
-    VersionedFromStr, VersionedToString,
+    FilePath, OsuFile, VersionedFromStr, VersionedToString, WriteOptions,
 };
 
 #[test]
@@ -165,6 +200,43 @@ SliderTickRate:1";
     assert_eq!(i_str, i.to_string(14).unwrap());
 }
 
+#[test]
+fn difficulty_key_count() {
+    let difficulty = |circle_size| Difficulty {
+        circle_size: Some(Decimal::from(circle_size).into()),
+        ..Difficulty::new()
+    };
+
+    assert_eq!(
+        difficulty(dec!(4)).key_count(Mode::Mania),
+        CircleSizeValue::Keys(4)
+    );
+    assert_eq!(
+        difficulty(dec!(7.5)).key_count(Mode::Mania),
+        CircleSizeValue::Keys(8)
+    );
+    assert_eq!(
+        difficulty(dec!(6.5)).key_count(Mode::Mania),
+        CircleSizeValue::Keys(6)
+    );
+    assert_eq!(
+        difficulty(dec!(0)).key_count(Mode::Mania),
+        CircleSizeValue::Keys(1)
+    );
+    assert_eq!(
+        difficulty(dec!(20)).key_count(Mode::Mania),
+        CircleSizeValue::Keys(18)
+    );
+    assert_eq!(
+        difficulty(dec!(4.2)).key_count(Mode::Osu),
+        CircleSizeValue::CircleSize(dec!(4.2))
+    );
+    assert_eq!(
+        Difficulty::new().key_count(Mode::Catch),
+        CircleSizeValue::CircleSize(dec!(5))
+    );
+}
+
 #[test]
 fn colours_parse_v14() {
     let i_str = "Combo1 : 255,128,255
@@ -240,7 +312,7 @@ fn events_parse_v14() {
         Event::Background(Background {
             commands: Vec::new(),
             start_time: 0,
-            file_name: Path::new("\"bg2.jpg\"").into(),
+            file_name: FilePath::new_quoted("bg2.jpg"),
             position: Some(Position {
                 x: dec!(0).into(),
                 y: dec!(0).into(),
@@ -263,6 +335,123 @@ fn events_parse_v14() {
     assert_eq!(i_str, i.to_string(14).unwrap());
 }
 
+#[test]
+fn events_quoted_file_name() {
+    let i_str = "0,0,\"bg, 2.jpg\",0,0
+Video,-200,\"video.mp4\"
+Sprite,Pass,Centre,\"sb\\a,b.png\",320,240
+Sample,0,0,sample.wav,60";
+    let i = Events::from_str(i_str, 14).unwrap().unwrap();
+
+    let file_names =
+        i.0.iter()
+            .map(|e| match e {
+                Event::Background(bg) => &bg.file_name,
+                Event::Video(video) => &video.file_name,
+                Event::StoryboardObject(obj) => match &obj.object_type {
+                    ObjectType::Sprite(sprite) => &sprite.filepath,
+                    _ => unreachable!(),
+                },
+                Event::AudioSample(sample) => &sample.filepath,
+                _ => unreachable!(),
+            })
+            .map(|f| (f.get().to_str().unwrap(), f.is_quoted()))
+            .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            ("bg, 2.jpg", true),
+            ("video.mp4", true),
+            ("sb\\a,b.png", true),
+            ("sample.wav", false),
+        ],
+        file_names
+    );
+    assert_eq!(i_str, i.to_string(14).unwrap());
+}
+
+#[test]
+fn event_header_style() {
+    let osu_file = "osu file format v14
+
+[Events]
+Video,-200,\"video.mp4\"
+2,100,163"
+        .parse::<OsuFile>()
+        .unwrap();
+
+    let events = &osu_file.events.as_ref().unwrap().0;
+    match (&events[0], &events[1]) {
+        (Event::Video(video), Event::Break(b)) => {
+            assert!(!video.is_short_hand());
+            assert!(b.is_short_hand());
+        }
+        _ => unreachable!(),
+    }
+
+    let numeric =
+        osu_file.to_string_with_options(&WriteOptions::new().prefer_numeric_event_headers(true));
+    let named =
+        osu_file.to_string_with_options(&WriteOptions::new().prefer_numeric_event_headers(false));
+
+    assert!(numeric.ends_with("1,-200,\"video.mp4\"\n2,100,163"));
+    assert!(named.ends_with("Video,-200,\"video.mp4\"\nBreak,100,163"));
+
+    let mut b = Break::new(100, 163);
+    b.set_short_hand(false);
+    assert_eq!(b.to_string(14).unwrap(), "Break,100,163");
+}
+
+#[test]
+fn event_position_omission() {
+    let osu_file = "osu file format v14
+
+[Events]
+0,0,\"bg.jpg\"
+Video,-200,\"video.mp4\",10,-20"
+        .parse::<OsuFile>()
+        .unwrap();
+
+    match &osu_file.events.as_ref().unwrap().0[0] {
+        Event::Background(bg) => assert_eq!(bg.position, None),
+        _ => unreachable!(),
+    }
+
+    let preserved = osu_file.to_string_with_options(&WriteOptions::new());
+    let normalized =
+        osu_file.to_string_with_options(&WriteOptions::new().always_write_event_position(true));
+
+    assert!(preserved.ends_with("0,0,\"bg.jpg\"\nVideo,-200,\"video.mp4\",10,-20"));
+    assert!(normalized.ends_with("0,0,\"bg.jpg\",0,0\nVideo,-200,\"video.mp4\",10,-20"));
+}
+
+#[test]
+fn background_file_name_quoting() {
+    let events = "
+
+[Events]
+0,0,bg.jpg,0,0
+0,0,\"bg2.jpg\",0,0
+Video,-200,video.mp4";
+    let v14 = format!("osu file format v14{events}")
+        .parse::<OsuFile>()
+        .unwrap();
+    let v9 = format!("osu file format v9{events}")
+        .parse::<OsuFile>()
+        .unwrap();
+    let options = WriteOptions::new().quote_background_file_names(true);
+
+    assert!(v14
+        .to_string_with_options(&WriteOptions::new())
+        .ends_with("0,0,bg.jpg,0,0\n0,0,\"bg2.jpg\",0,0\nVideo,-200,video.mp4"));
+    assert!(v14
+        .to_string_with_options(&options)
+        .ends_with("0,0,\"bg.jpg\",0,0\n0,0,\"bg2.jpg\",0,0\nVideo,-200,video.mp4"));
+    assert!(v9
+        .to_string_with_options(&options)
+        .ends_with("0,0,bg.jpg,0,0\n0,0,\"bg2.jpg\",0,0\nVideo,-200,video.mp4"));
+}
+
 #[test]
 fn colour_parse_error() {
     let i = "Combo1: foo";
@@ -1,4 +1,7 @@
-use crate::{assert_eq_osu_str, osu_file::OsuFile};
+use crate::{
+    assert_eq_osu_str,
+    osu_file::{Osb, OsuFile, ParseOptions, SectionName},
+};
 
 #[test]
 fn v3() {
@@ -310,6 +313,23 @@ fn osb_2() {
     assert_eq_osu_str(osu.osb_to_string().unwrap(), osb);
 }
 
+#[test]
+fn standalone_osb() {
+    let osb = include_str!("./files/variable.osb");
+    let parsed = osb.parse::<Osb>().unwrap();
+
+    assert_eq_osu_str(parsed.to_osb_string(), osb);
+
+    let osu_file = include_str!("./files/v14.osu").parse::<OsuFile>().unwrap();
+    let osb_events = parsed.events.as_ref().unwrap();
+    let osu_events = osu_file.events.as_ref().unwrap();
+    let merged = parsed.merge_events(Some(osu_events));
+
+    assert_eq!(merged.0.len(), osb_events.0.len() + osu_events.0.len());
+    assert_eq!(merged.0[..osb_events.0.len()], osb_events.0[..]);
+    assert_eq!(merged.0.last(), osu_events.0.last());
+}
+
 #[test]
 fn missing_effects_field() {
     let i = include_str!("./files/missing_effects_field.osu");
@@ -387,3 +407,67 @@ fn legacy_event_osb() {
 
     assert_eq_osu_str(osu.osb_to_string().unwrap(), osb);
 }
+
+#[test]
+fn to_string_sections() {
+    let i = include_str!("./files/v14.osu");
+    let o = i.parse::<OsuFile>().unwrap();
+
+    let s = o.to_string_sections(14, &SectionName::all_except(&[SectionName::Events]));
+
+    assert!(s.starts_with("osu file format v14\n\n[General]"));
+    assert!(!s.contains("[Events]"));
+    assert!(s.contains("\n\n[TimingPoints]\n"));
+    assert_eq!(o.to_string_sections(14, &SectionName::ALL), o.to_string());
+    assert_eq!(o.to_string_sections(14, &[]), "osu file format v14\n\n");
+}
+
+#[test]
+fn trim_fields() {
+    let i = include_str!("./files/v14.osu");
+
+    let mut in_spaced_section = false;
+    let spaced = i
+        .lines()
+        .map(|line| {
+            if line.starts_with('[') {
+                in_spaced_section = matches!(line, "[TimingPoints]" | "[Colours]" | "[HitObjects]");
+                line.to_string()
+            } else if in_spaced_section {
+                format!(" {} \t", line.replace(',', " ,\t"))
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    assert!(spaced.parse::<OsuFile>().is_err());
+
+    let options = ParseOptions::new().trim_fields(true);
+    let o = OsuFile::from_str_with_options(&spaced, &options).unwrap();
+
+    assert_eq!(i.parse::<OsuFile>().unwrap(), o);
+}
+
+#[test]
+fn trim_fields_hitsample_filenames() {
+    let i = "osu file format v14
+
+[HitObjects]
+256 , 192,1000,1,0,0 : 0:0:0: kick .wav
+256,192,2000 ,128,0,2500: 0:0:0:0:\tsnare.wav
+256,192,3000,1,0,0:0:0:0: \t";
+    let options = ParseOptions::new().trim_fields(true);
+    let o = OsuFile::from_str_with_options(i, &options).unwrap();
+    let hitobjects = &o.hitobjects.unwrap().0;
+
+    let filenames = hitobjects
+        .iter()
+        .map(|h| h.hitsample.as_ref().unwrap().filename.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        filenames,
+        vec![Some(" kick .wav"), Some("\tsnare.wav"), Some("")]
+    );
+}
@@ -0,0 +1,53 @@
+use pretty_assertions::assert_eq;
+
+use crate::osu_file::{OsuFile, ParseError, ParseOptions, Parser};
+
+#[test]
+fn parser_reuse() {
+    let files = [
+        include_str!("./osu_files/files/acid_rain.osu"),
+        include_str!("./osu_files/files/v14.osu"),
+        include_str!("./osu_files/files/acid_rain.osu"),
+    ];
+    let mut parser = Parser::new();
+
+    for file in files {
+        assert_eq!(
+            parser.parse(file).unwrap(),
+            file.parse::<OsuFile>().unwrap()
+        );
+    }
+
+    let err = parser
+        .parse("osu file format v14\n\n[Colours]\nfoobar")
+        .unwrap_err();
+    assert_eq!(err.line_index(), 3);
+
+    let mut parser = Parser::with_options(ParseOptions::new().keep_unknown_sections(true));
+    let osu_file = parser
+        .parse("osu file format v14\n\n[Custom]\nfoo")
+        .unwrap();
+    assert_eq!(osu_file.custom_sections.len(), 1);
+}
+
+#[test]
+fn parser_reuse_trimmed_sections() {
+    let options = ParseOptions::new().trim_fields(true);
+    let files = [
+        "osu file format v14\n\n[HitObjects]\n256, 192, 1000, 1, 0, 0:0:0:0:\n",
+        "osu file format v14\n\n[TimingPoints]\n0 , 500,4,2,0,100,1,0\n\n[HitObjects]\n1,2,3,1,0\n",
+    ];
+    let mut parser = Parser::with_options(options.clone());
+
+    for file in files {
+        assert_eq!(
+            parser.parse(file).unwrap(),
+            OsuFile::from_str_with_options(file, &options).unwrap()
+        );
+    }
+
+    let err = parser
+        .parse("osu file format v14\n\n[HitObjects]\n1,2,3,1,0\n\n[HitObjects]\n1,2,3,1,0")
+        .unwrap_err();
+    assert!(matches!(err.error(), ParseError::DuplicateSections));
+}
@@ -0,0 +1,31 @@
+use rust_decimal_macros::dec;
+
+use crate::osu_file::{Integer, Position, VersionedToString};
+
+#[test]
+fn integer_conversion() {
+    let i = Position::<Integer>::new(-10, 384);
+    let o = Position::from(i);
+
+    assert_eq!(Position::new(dec!(-10), dec!(384)), o);
+    assert_eq!(i, Position::<Integer>::try_from(o).unwrap());
+    assert!(Position::<Integer>::try_from(Position::new(dec!(0.5), dec!(0))).is_err());
+}
+
+#[test]
+fn f64_conversion() {
+    let i = Position::new(dec!(256.5), dec!(192));
+    let o = Position::<f64>::try_from(i).unwrap();
+
+    assert_eq!(Position::new(256.5, 192.0), o);
+    assert_eq!(i, Position::try_from(o).unwrap());
+    assert!(<Position>::try_from(Position::new(f64::NAN, 0.0)).is_err());
+}
+
+#[test]
+fn to_string() {
+    let i = Position::new(dec!(-1.5), dec!(10));
+
+    assert_eq!("-1.5,10", i.to_string(14).unwrap());
+    assert_eq!("3,4", Position::new(3, 4).to_string(14).unwrap());
+}
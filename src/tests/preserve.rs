@@ -0,0 +1,120 @@
+use pretty_assertions::assert_eq;
+
+use crate::osu_file::{FieldValue, OsuFile, ParseOptions};
+
+const SOURCE: &str = "\u{feff}osu file format v14\r
+// made by hand\r
+\r
+[General]\r
+AudioFilename:audio.mp3\r
+CustomKey: kept\r
+\r
+AudioLeadIn:   0\r
+\r
+\r
+[Metadata]\r
+Title:  Song\r
+\r
+[TimingPoints]\r
+0,500,4,2,0,100,1,0\r
+\r
+1000,-50,4,2,0,100,0,0\r
+\r
+[Extra]\r
+anything goes\r
+\r
+[HitObjects]\r
+256,192,1000,1,0,0:0:0:0:\r
+256,192,2000,1,0,0:0:0:0:\r
+";
+
+fn parse() -> OsuFile {
+    OsuFile::from_str_with_options(SOURCE, &ParseOptions::new().preserve_formatting(true)).unwrap()
+}
+
+#[test]
+fn preserve_formatting_round_trip() {
+    let osu_file = parse();
+
+    assert_eq!(osu_file.to_string(), SOURCE);
+
+    let mut buf = Vec::new();
+    osu_file.write_to(&mut buf).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), SOURCE);
+}
+
+#[test]
+fn preserve_formatting_changed_lines() {
+    let mut osu_file = parse();
+    let general = osu_file.general.as_mut().unwrap();
+    general
+        .set("AudioLeadIn", FieldValue::Integer(500))
+        .unwrap();
+    general.set("Countdown", FieldValue::Integer(0)).unwrap();
+    osu_file.hitobjects.as_mut().unwrap().0[1].time = 2500;
+
+    assert_eq!(
+        osu_file.to_string(),
+        SOURCE
+            .replace("AudioLeadIn:   0\r", "AudioLeadIn: 500\r\nCountdown: 0\r")
+            .replace("256,192,2000,", "256,192,2500,")
+    );
+}
+
+#[test]
+fn preserve_formatting_inserted_item() {
+    let mut osu_file = parse();
+    let timing_points = osu_file.timing_points.as_mut().unwrap();
+    let mut timing_point = timing_points.0[0].clone();
+    timing_point.time = -100;
+    timing_points.0.insert(0, timing_point);
+
+    // the lines after the new timing point are matched by their hashes, so the blank lines stay where they are
+    assert_eq!(
+        osu_file.to_string(),
+        SOURCE.replace(
+            "[TimingPoints]\r\n",
+            "[TimingPoints]\r\n-100,500,4,2,0,100,1,0\r\n"
+        )
+    );
+}
+
+#[test]
+fn preserve_formatting_removed_section() {
+    let mut osu_file = parse();
+    osu_file.metadata = None;
+
+    assert_eq!(
+        osu_file.to_string(),
+        SOURCE.replace("[Metadata]\r\nTitle:  Song\r\n\r\n", "")
+    );
+}
+
+const COMMENTS: &str = "osu file format v14
+
+[TimingPoints]
+// intro
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+// first
+256,192,1000,1,0,0:0:0:0:
+  // second
+256,192,2000,1,0,0:0:0:0:
+";
+
+#[test]
+fn preserve_formatting_comments() {
+    let mut osu_file =
+        OsuFile::from_str_with_options(COMMENTS, &ParseOptions::new().preserve_formatting(true))
+            .unwrap();
+
+    assert_eq!(osu_file.to_string(), COMMENTS);
+
+    // the comments don't stop the lines from being matched with the hitobjects
+    osu_file.hitobjects.as_mut().unwrap().0[1].time = 2500;
+    assert_eq!(
+        osu_file.to_string(),
+        COMMENTS.replace("256,192,2000,", "256,192,2500,")
+    );
+}
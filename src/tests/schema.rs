@@ -0,0 +1,71 @@
+use pretty_assertions::assert_eq;
+use rust_decimal_macros::dec;
+
+use crate::osu_file::{FieldValue, SectionName};
+#[cfg(feature = "serde")]
+use crate::schema::describe;
+use crate::schema::{sections, LineFieldSchema, SectionKind, ValueType};
+
+#[test]
+fn schema_sections() {
+    let sections = sections();
+
+    assert_eq!(
+        sections.iter().map(|s| s.name).collect::<Vec<_>>(),
+        SectionName::ALL
+    );
+
+    let general = &sections[0];
+    assert_eq!(general.kind, SectionKind::Fields);
+
+    let audio_hash = general
+        .fields
+        .iter()
+        .find(|f| f.name == "AudioHash")
+        .unwrap();
+    assert_eq!(audio_hash.value_type, ValueType::String);
+    assert_eq!(audio_hash.versions, (3..=13).collect::<Vec<_>>());
+    assert_eq!(audio_hash.default, Some(FieldValue::String(String::new())));
+
+    let stack_leniency = general
+        .fields
+        .iter()
+        .find(|f| f.name == "StackLeniency")
+        .unwrap();
+    assert_eq!(stack_leniency.value_type, ValueType::Decimal);
+    assert_eq!(stack_leniency.default, Some(FieldValue::Decimal(dec!(0.7))));
+
+    let colours = &sections[6];
+    assert_eq!(colours.kind, SectionKind::Lines);
+    assert_eq!(colours.versions, (5..=14).collect::<Vec<_>>());
+    assert!(colours.fields.is_empty());
+
+    let timing_points = &sections[5];
+    assert_eq!(timing_points.line_fields.len(), 8);
+    assert_eq!(
+        timing_points.line_fields[1],
+        LineFieldSchema {
+            name: "beatLength",
+            value_type: ValueType::Decimal,
+        }
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn schema_json() {
+    let schema = describe();
+
+    assert!(schema.starts_with(r#"{"$schema":"https://json-schema.org/draft/2020-12/schema""#));
+    assert!(schema.contains(
+        r#""PreviewTime":{"type":"integer","x-versions":[3,4,5,6,7,8,9,10,11,12,13,14],"default":-1}"#
+    ));
+    assert!(schema.contains(
+        r#""EditorBookmarks":{"type":"array","items":{"type":"integer"},"x-versions":[3,4,5,6,7,8,9,10,11,12,13,14]}"#
+    ));
+    assert!(schema.contains(r#""x-line-format":"x,y,time,type,hitSound,objectParams,hitSample""#));
+    assert!(schema.contains(
+        r#""x-line-fields":[{"type":"string","name":"eventType"},{"type":"integer","name":"startTime"},{"type":"string","name":"eventParams"}]"#
+    ));
+    assert!(schema.ends_with("}}"));
+}
@@ -0,0 +1,64 @@
+use crate::osu_file::{
+    events::Events, hitobjects::HitObjects, timingpoints::TimingPoints, VersionedFromStr,
+};
+
+#[test]
+fn events_semantically_eq() {
+    let a = r#"Video,0,"video.mp4"
+Sprite,Foreground,Centre,"sb/a.png",320,240
+ F,0,1000,2000,0,1,0.5
+ M,0,0,,0,0,100
+_S,0,0,500,1
+Animation,Background,Centre,"sb/b.png",0,0,2,100,LoopForever"#;
+    let b = r#"1,0,video.mp4,0,0
+Sprite,Foreground,1,sb/a.png,320,240
+ F,0,1000,2000,0,1
+ F,0,2000,3000,1,0.5
+ M,0,0,0,0,0,100,0
+ S,0,0,500,1,1
+Animation,0,1,sb/b.png,0,0,2,100,LoopForever"#;
+    let c = r#"1,0,video.mp4,0,0
+Sprite,Foreground,1,sb/a.png,320,240
+ F,0,1000,2000,0,1
+ F,0,2000,3000,1,0.6
+ M,0,0,0,0,0,100,0
+ S,0,0,500,1,1"#;
+
+    let a = Events::from_str(a, 14).unwrap().unwrap();
+    let b = Events::from_str(b, 14).unwrap().unwrap();
+    let c = Events::from_str(c, 14).unwrap().unwrap();
+
+    assert_ne!(a, b);
+    assert!(a.semantically_eq(&b));
+    assert!(!a.semantically_eq(&c));
+}
+
+#[test]
+fn hitobjects_semantically_eq() {
+    let a = "256,192,1000,1,0
+100,100,2000,2,0,B|200:100,1,100";
+    let b = "256,192,1000,17,0,0:0:0:0:
+100,100,2000,2,0,B|200:100,1,100,0|0,0:0|0:0,0:0:0:0:";
+
+    let a = HitObjects::from_str(a, 14).unwrap().unwrap();
+    let b = HitObjects::from_str(b, 14).unwrap().unwrap();
+
+    assert_ne!(a, b);
+    assert!(a.semantically_eq(&b));
+}
+
+#[test]
+fn timing_points_semantically_eq() {
+    let a = TimingPoints::from_str("0,500,4,2,0,100,1", 14)
+        .unwrap()
+        .unwrap();
+    let b = TimingPoints::from_str("0,500,4,2,0,100,1,0", 14)
+        .unwrap()
+        .unwrap();
+    let c = TimingPoints::from_str("0,500,4,2,0,100,1,1", 14)
+        .unwrap()
+        .unwrap();
+
+    assert!(a.semantically_eq(&b));
+    assert!(!a.semantically_eq(&c));
+}
@@ -0,0 +1,13 @@
+use crate::osu_file::OsuFile;
+
+#[test]
+fn osu_file_json_round_trip() {
+    let i = include_str!("./osu_files/files/acid_rain.osu");
+    let osu_file = i.parse::<OsuFile>().unwrap();
+
+    let json = serde_json::to_string(&osu_file).unwrap();
+    let deserialized = serde_json::from_str::<OsuFile>(&json).unwrap();
+
+    assert_eq!(osu_file, deserialized);
+    assert_eq!(osu_file.to_string(), deserialized.to_string());
+}
@@ -0,0 +1,92 @@
+use crate::{
+    osu_file::OsuFile,
+    simulation::{Judgement, TimedJudgement},
+};
+
+/// Two sections of circles with a break between them, with a new combo every 8 circles.
+fn osu_file(mode: u8) -> OsuFile {
+    let circle_times = (0..32)
+        .map(|i| 1000 + i * 250)
+        .chain((0..32).map(|i| 20000 + i * 250));
+    let hitobjects = circle_times
+        .enumerate()
+        .map(|(i, time)| {
+            let obj_type = if i % 8 == 0 { 5 } else { 1 };
+            format!("256,192,{time},{obj_type},0,0:0:0:0:")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "osu file format v14
+
+[General]
+Mode: {mode}
+
+[Difficulty]
+HPDrainRate:6
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[Events]
+2,10000,19000
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+{hitobjects}
+"
+    )
+    .parse()
+    .unwrap()
+}
+
+#[test]
+fn perfect_play_survives() {
+    let simulation = osu_file(0).hp_simulation().unwrap();
+    let timeline = simulation.hp_timeline(&simulation.perfect_judgements());
+
+    assert!(simulation.drain_rate() > 0.0);
+    assert!(timeline.iter().all(|p| p.hp > 0.5), "{timeline:?}");
+}
+
+#[test]
+fn misses_lose_hp() {
+    let simulation = osu_file(0).hp_simulation().unwrap();
+
+    let perfect = simulation.perfect_judgements();
+    let misses = perfect
+        .iter()
+        .map(|j| TimedJudgement {
+            judgement: Judgement::Miss,
+            ..*j
+        })
+        .collect::<Vec<_>>();
+
+    let perfect = simulation.hp_timeline(&perfect);
+    let misses = simulation.hp_timeline(&misses);
+
+    assert!(misses.last().unwrap().hp < perfect.last().unwrap().hp);
+    assert_eq!(0.0, misses.last().unwrap().hp);
+}
+
+#[test]
+fn no_drain_in_breaks() {
+    let simulation = osu_file(0).hp_simulation().unwrap();
+    let timeline = simulation.hp_timeline(&simulation.perfect_judgements());
+
+    let break_start = timeline.iter().find(|p| p.time == 10000.0).unwrap();
+    let break_end = timeline.iter().find(|p| p.time == 19000.0).unwrap();
+
+    assert_eq!(break_start.hp, break_end.hp);
+}
+
+#[test]
+fn other_modes() {
+    assert!(osu_file(1).hp_simulation().is_none());
+    assert!(osu_file(1).hp_timeline(&[]).is_none());
+}
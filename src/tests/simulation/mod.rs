@@ -0,0 +1,2 @@
+mod hp;
+mod replay;
@@ -0,0 +1,103 @@
+use crate::{
+    analysis::Mods,
+    osu_file::OsuFile,
+    simulation::{accuracy, align, Judgement, Keys, Replay, ReplayFrame},
+};
+
+const OSU_FILE: &str = "osu file format v14
+
+[General]
+Mode: 0
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+256,192,1500,1,0,0:0:0:0:
+256,192,2000,2,0,L|256:472,1,280
+100,100,3500,1,0,0:0:0:0:
+";
+
+fn frame(time: f64, x: f64, y: f64, keys: Keys) -> ReplayFrame {
+    ReplayFrame { time, x, y, keys }
+}
+
+fn replay(mods: Mods) -> Replay {
+    Replay::new(
+        mods,
+        vec![
+            frame(1010.0, 256.0, 192.0, Keys::M1),
+            frame(1020.0, 256.0, 192.0, Keys::NONE),
+            frame(1600.0, 256.0, 192.0, Keys::M1 | Keys::K1),
+            frame(1610.0, 256.0, 192.0, Keys::NONE),
+            frame(1995.0, 256.0, 192.0, Keys::M1),
+            frame(3001.0, 256.0, 192.0, Keys::NONE),
+            // outside of the circle
+            frame(3500.0, 200.0, 200.0, Keys::M2),
+        ],
+    )
+}
+
+#[test]
+fn align_std() {
+    let osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+    let judgements = align(&replay(Mods::NONE), &osu_file).unwrap();
+
+    let results = judgements
+        .iter()
+        .filter(|j| j.judgement.is_object_result())
+        .map(|j| (j.object_index, j.judgement, j.offset))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            (0, Judgement::Hit300, Some(10.0)),
+            (1, Judgement::Hit50, Some(100.0)),
+            (2, Judgement::Hit300, None),
+            (3, Judgement::Miss, None),
+        ],
+        results
+    );
+
+    let slider_nested = judgements
+        .iter()
+        .filter(|j| j.object_index == 2 && !j.judgement.is_object_result())
+        .map(|j| j.judgement)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            Judgement::SliderEdge,
+            Judgement::SliderTick,
+            Judgement::SliderEdge
+        ],
+        slider_nested
+    );
+
+    let accuracy = accuracy(judgements.iter().map(|j| j.judgement)).unwrap();
+    assert!((accuracy - 650.0 / 1200.0).abs() < 1e-9);
+}
+
+#[test]
+fn align_hard_rock_flips_objects() {
+    let osu_file = OSU_FILE
+        .replace("100,100,3500", "200,160,3500")
+        .parse::<OsuFile>()
+        .unwrap();
+
+    let judgements = osu_file.align_replay(&replay(Mods::NONE)).unwrap();
+    assert_eq!(Judgement::Miss, judgements.last().unwrap().judgement);
+
+    // flipped to 200,224
+    let judgements = osu_file.align_replay(&replay(Mods::HARD_ROCK)).unwrap();
+    assert_eq!(Judgement::Hit300, judgements.last().unwrap().judgement);
+}
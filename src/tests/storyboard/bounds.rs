@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::osu_file::events::storyboard::{bounds::BoundingBox, sprites::Object, types::Easing};
+use crate::osu_file::events::Event;
+use crate::osu_file::{Events, VersionedFromStr};
+
+fn object(s: &str) -> Object {
+    let events = Events::from_str(s, 14).unwrap().unwrap();
+
+    match events.0.into_iter().next().unwrap() {
+        Event::StoryboardObject(object) => object,
+        _ => unreachable!(),
+    }
+}
+
+fn prober() -> HashMap<PathBuf, (u32, u32)> {
+    HashMap::from([(PathBuf::from("star.png"), (100, 50))])
+}
+
+#[test]
+fn bounding_box_at() {
+    let object = object(
+        "Sprite,Foreground,Centre,\"star.png\",320,240
+ M,0,1000,2000,100,100,300,100
+ S,0,1000,,2",
+    );
+    let prober = prober();
+
+    // the object is only shown from the start of its first command to the end of its last
+    assert_eq!(object.bounding_box_at(0, &prober), None);
+    assert_eq!(
+        object.bounding_box_at(1000, &prober),
+        Some(BoundingBox {
+            min_x: 0.0,
+            min_y: 50.0,
+            max_x: 200.0,
+            max_y: 150.0,
+        })
+    );
+    assert_eq!(
+        object.bounding_box_at(1500, &prober),
+        Some(BoundingBox {
+            min_x: 100.0,
+            min_y: 50.0,
+            max_x: 300.0,
+            max_y: 150.0,
+        })
+    );
+    assert_eq!(object.bounding_box_at(2001, &prober), None);
+
+    let unknown = HashMap::<PathBuf, (u32, u32)>::new();
+    assert_eq!(object.bounding_box_at(1500, &unknown), None);
+}
+
+#[test]
+fn bounding_box_rotation_and_origin() {
+    let object = object(
+        "Sprite,Foreground,TopLeft,\"star.png\",0,0
+ R,0,0,,1.5707963267948966",
+    );
+    let bounding_box = object.bounding_box_at(0, &prober()).unwrap();
+
+    // a quarter turn clockwise around the top left corner
+    assert!((bounding_box.min_x - -50.0).abs() < 1e-9);
+    assert!((bounding_box.max_x - 0.0).abs() < 1e-9);
+    assert!((bounding_box.min_y - 0.0).abs() < 1e-9);
+    assert!((bounding_box.max_y - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn max_bounding_box() {
+    let object = object(
+        "Sprite,Foreground,Centre,\"star.png\",320,240
+ L,1000,2
+  MX,0,0,500,100,200
+ V,0,1000,,1,2",
+    );
+    let prober = prober();
+
+    // the loop moves the sprite twice, from 1000 to 2000
+    assert_eq!(object.lifetime(), Some((1000.0, 2000.0)));
+    assert_eq!(
+        object.state_at(1750).unwrap().position.x,
+        object.state_at(1250).unwrap().position.x
+    );
+    assert_eq!(
+        object.max_bounding_box(&prober),
+        Some(BoundingBox {
+            min_x: 50.0,
+            min_y: 190.0,
+            max_x: 250.0,
+            max_y: 290.0,
+        })
+    );
+}
+
+#[test]
+fn huge_loop_count() {
+    let object = object(
+        "Sprite,Foreground,Centre,\"star.png\",320,240
+ L,1000,4000000000
+  MX,0,0,500,100,200",
+    );
+
+    // the loop isn't expanded, so it doesn't take up memory for every repeat
+    assert_eq!(object.lifetime(), Some((1000.0, 2_000_000_001_000.0)));
+    assert_eq!(object.state_at(1250).unwrap().position.x, 150.0);
+    assert_eq!(object.state_at(1750).unwrap().position.x, 150.0);
+    assert_eq!(object.state_at(2_000_000_250).unwrap().position.x, 150.0);
+    assert!(object.max_bounding_box(&prober()).is_some());
+}
+
+#[test]
+fn easing_endpoints() {
+    for easing in [
+        Easing::Linear,
+        Easing::QuadInOut,
+        Easing::ExpoOut,
+        Easing::CircInOut,
+        Easing::ElasticOut,
+        Easing::BackInOut,
+        Easing::BounceIn,
+    ] {
+        assert!(easing.apply(0.0).abs() < 1e-3, "{easing:?}");
+        assert!((easing.apply(1.0) - 1.0).abs() < 1e-3, "{easing:?}");
+    }
+
+    assert_eq!(Easing::QuadIn.apply(0.5), 0.25);
+}
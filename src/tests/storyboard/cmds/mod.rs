@@ -10,7 +10,7 @@ use crate::osu_file::events::storyboard::sprites::*;
 use crate::osu_file::events::storyboard::types::*;
 use crate::osu_file::events::Event;
 use crate::osu_file::types::Position;
-use crate::osu_file::Events;
+use crate::osu_file::{Events, Extensions};
 use crate::osu_file::{VersionedFromStr, VersionedToString};
 use crate::Integer;
 use crate::VersionedFrom;
@@ -54,6 +54,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
     let s = Events(vec![
         Event::StoryboardObject(Object {
             layer: Layer::Pass,
+            layer_shorthand: false,
             origin: Origin {
                 type_: Either::Left(OriginType::Centre),
                 shorthand: false,
@@ -68,6 +69,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
             commands: vec![
                 Command {
                     start_time: Some(-28),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Fade {
                         easing: <Easing as VersionedFrom<Integer>>::from(0, 14).unwrap(),
                         end_time: None,
@@ -77,6 +79,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(100),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Move {
                         easing: <Easing as VersionedFrom<Integer>>::from(3, 14).unwrap(),
                         end_time: Some(120),
@@ -89,6 +92,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(100),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::MoveX {
                         easing: <Easing as VersionedFrom<Integer>>::from(3, 14).unwrap(),
                         end_time: Some(120),
@@ -98,6 +102,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(100),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::MoveY {
                         easing: <Easing as VersionedFrom<Integer>>::from(3, 14).unwrap(),
                         end_time: Some(120),
@@ -107,6 +112,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(-28),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Scale {
                         easing: <Easing as VersionedFrom<Integer>>::from(0, 14).unwrap(),
                         end_time: None,
@@ -116,6 +122,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(5000),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::VectorScale {
                         easing: <Easing as VersionedFrom<Integer>>::from(8, 14).unwrap(),
                         end_time: Some(5500),
@@ -128,6 +135,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(5000),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Rotate {
                         easing: <Easing as VersionedFrom<Integer>>::from(7, 14).unwrap(),
                         end_time: Some(5500),
@@ -137,6 +145,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(50000),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Colour {
                         easing: <Easing as VersionedFrom<Integer>>::from(6, 14).unwrap(),
                         end_time: Some(50001),
@@ -146,6 +155,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(300),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Parameter {
                         easing: <Easing as VersionedFrom<Integer>>::from(5, 14).unwrap(),
                         end_time: Some(350),
@@ -155,6 +165,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(300),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Parameter {
                         easing: <Easing as VersionedFrom<Integer>>::from(5, 14).unwrap(),
                         end_time: Some(350),
@@ -164,6 +175,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(300),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Parameter {
                         easing: <Easing as VersionedFrom<Integer>>::from(5, 14).unwrap(),
                         end_time: Some(350),
@@ -173,15 +185,18 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(500),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Loop {
                         loop_count: 10,
                         commands: vec![Command {
                             start_time: Some(10),
+                            indent_char: IndentChar::Space,
                             properties: CommandProperties::Loop {
                                 loop_count: 10,
                                 commands: vec![
                                     Command {
                                         start_time: Some(100),
+                                        indent_char: IndentChar::Space,
                                         properties: CommandProperties::Move {
                                             easing: <Easing as VersionedFrom<Integer>>::from(3, 14)
                                                 .unwrap(),
@@ -195,6 +210,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                                     },
                                     Command {
                                         start_time: Some(-28),
+                                        indent_char: IndentChar::Space,
                                         properties: CommandProperties::Scale {
                                             easing: <Easing as VersionedFrom<Integer>>::from(0, 14)
                                                 .unwrap(),
@@ -210,6 +226,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(0),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Trigger {
                         trigger_type: TriggerType::HitSound {
                             sample_set: None,
@@ -221,10 +238,12 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                         group_number: None,
                         commands: vec![Command {
                             start_time: Some(10),
+                            indent_char: IndentChar::Space,
                             properties: CommandProperties::Loop {
                                 loop_count: 10,
                                 commands: vec![Command {
                                     start_time: Some(100),
+                                    indent_char: IndentChar::Space,
                                     properties: CommandProperties::Move {
                                         easing: <Easing as VersionedFrom<Integer>>::from(3, 14)
                                             .unwrap(),
@@ -241,9 +260,11 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                     },
                 },
             ],
+            extensions: Extensions::new(),
         }),
         Event::StoryboardObject(Object {
             layer: Layer::Fail,
+            layer_shorthand: false,
             origin: Origin {
                 type_: Either::Left(OriginType::BottomCentre),
                 shorthand: false,
@@ -261,6 +282,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
             commands: vec![
                 Command {
                     start_time: Some(0),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Trigger {
                         trigger_type: TriggerType::HitSound {
                             sample_set: None,
@@ -275,6 +297,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(0),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Trigger {
                         trigger_type: TriggerType::HitSound {
                             sample_set: None,
@@ -289,6 +312,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(0),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Trigger {
                         trigger_type: TriggerType::HitSound {
                             sample_set: None,
@@ -303,6 +327,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(0),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Trigger {
                         trigger_type: TriggerType::HitSound {
                             sample_set: Some(SampleSet::Drum),
@@ -317,6 +342,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(0),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Trigger {
                         trigger_type: TriggerType::HitSound {
                             sample_set: Some(SampleSet::Soft),
@@ -331,6 +357,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(0),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Trigger {
                         trigger_type: TriggerType::HitSound {
                             sample_set: Some(SampleSet::All),
@@ -345,6 +372,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(0),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Trigger {
                         trigger_type: TriggerType::HitSound {
                             sample_set: Some(SampleSet::Drum),
@@ -359,6 +387,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(0),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Trigger {
                         trigger_type: TriggerType::HitSound {
                             sample_set: None,
@@ -373,6 +402,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(0),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Trigger {
                         trigger_type: TriggerType::Passing,
                         end_time: Some(10),
@@ -382,6 +412,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 },
                 Command {
                     start_time: Some(0),
+                    indent_char: IndentChar::Space,
                     properties: CommandProperties::Trigger {
                         trigger_type: TriggerType::Failing,
                         end_time: Some(10),
@@ -390,6 +421,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                     },
                 },
             ],
+            extensions: Extensions::new(),
         }),
     ]);
 
@@ -404,6 +436,7 @@ fn colours() {
 
     let cmd = Command {
         start_time: Some(0),
+        indent_char: IndentChar::Space,
         properties: CommandProperties::Colour {
             easing: <Easing as VersionedFrom<Integer>>::from(0, 14).unwrap(),
             end_time: Some(0),
@@ -424,6 +457,7 @@ fn parameters() {
 
     let cmd = Command {
         start_time: Some(0),
+        indent_char: IndentChar::Space,
         properties: CommandProperties::Parameter {
             easing: <Easing as VersionedFrom<Integer>>::from(0, 14).unwrap(),
             end_time: None,
@@ -467,6 +501,7 @@ fn trigger_group_number() {
 
     let cmd = Command {
         start_time: Some(0),
+        indent_char: IndentChar::Space,
         properties: CommandProperties::Trigger {
             trigger_type: TriggerType::HitSound {
                 sample_set: None,
@@ -490,6 +525,7 @@ fn move_command() {
 
     let cmd = Command {
         start_time: Some(0),
+        indent_char: IndentChar::Space,
         properties: CommandProperties::Move {
             easing: <Easing as VersionedFrom<Integer>>::from(0, 14).unwrap(),
             end_time: Some(0),
@@ -511,6 +547,7 @@ fn fade_chain() {
 
     let cmd = Command {
         start_time: Some(0),
+        indent_char: IndentChar::Space,
         properties: CommandProperties::Fade {
             easing: <Easing as VersionedFrom<Integer>>::from(0, 14).unwrap(),
             end_time: Some(0),
@@ -527,3 +564,47 @@ fn fade_chain() {
 
     assert_eq!(i, cmd);
 }
+
+#[test]
+fn underscore_indentation() {
+    let i = "Sprite,Foreground,Centre,\"sb/a.png\",320,240
+_L,0,2
+__F,0,0,500,0,1
+_F,0,1000,2000,1,0
+ M,0,0,500,0,0,100,100";
+    let events = Events::from_str(i, 14).unwrap().unwrap();
+
+    let cmds = match &events.0[0] {
+        Event::StoryboardObject(obj) => &obj.commands,
+        _ => unreachable!(),
+    };
+    assert_eq!(3, cmds.len());
+    assert_eq!(IndentChar::Underscore, cmds[0].indent_char);
+    assert_eq!(IndentChar::Space, cmds[2].indent_char);
+
+    assert_eq!(i, events.to_string(14).unwrap());
+}
+
+#[test]
+fn indentation_depth_errors() {
+    let jump = "Sprite,Foreground,Centre,\"sb/a.png\",320,240
+ L,0,2
+   F,0,0,500,0,1";
+    let not_loop = "Sprite,Foreground,Centre,\"sb/a.png\",320,240
+ F,0,0,500,0,1
+ _F,0,0,500,0,1";
+
+    let err = Events::from_str(jump, 14).unwrap_err();
+    assert_eq!(2, err.line_index());
+    assert_eq!(
+        "Indentation jumped from 1 to 3, it can only increase by 1",
+        err.error().to_string()
+    );
+
+    let err = Events::from_str(not_loop, 14).unwrap_err();
+    assert_eq!(2, err.line_index());
+    assert_eq!(
+        "Invalid indentation, expected at most 1, got 2",
+        err.error().to_string()
+    );
+}
@@ -0,0 +1,80 @@
+use pretty_assertions::assert_eq;
+
+use crate::osu_file::{
+    events::{storyboard::cmds::Command, Event},
+    CommandTimePolicy, OsuFile, ParseOptions, ParseWarning,
+};
+
+const OSU_FILE: &str = "osu file format v14
+
+[Events]
+Sprite,Foreground,Centre,\"star.png\",320,240
+ F,0,1000.5,2000.4,1,0
+ L,3000.7,2
+  F,0,0,500,1,0";
+
+fn commands(osu_file: &mut OsuFile) -> &mut Vec<Command> {
+    match &mut osu_file.events.as_mut().unwrap().0[0] {
+        Event::StoryboardObject(object) => &mut object.commands,
+        _ => unreachable!(),
+    }
+}
+
+fn times(osu_file: &mut OsuFile) -> Vec<Option<i32>> {
+    commands(osu_file)
+        .iter()
+        .map(|command| command.start_time)
+        .collect()
+}
+
+#[test]
+fn reject_by_default() {
+    assert!(OSU_FILE.parse::<OsuFile>().is_err());
+}
+
+#[test]
+fn truncate_and_round() {
+    let options = ParseOptions::new().command_time_policy(CommandTimePolicy::Truncate);
+    let mut osu_file = OsuFile::from_str_with_options(OSU_FILE, &options).unwrap();
+
+    assert_eq!(times(&mut osu_file), vec![Some(1000), Some(3000)]);
+    assert!(osu_file
+        .to_string()
+        .contains(" F,0,1000,2000,1,0\n L,3000,2"));
+    assert_eq!(
+        osu_file.parse_warnings(),
+        &[
+            ParseWarning::CommandTimeChanged {
+                line_index: 4,
+                time: "1000.5".to_string(),
+            },
+            ParseWarning::CommandTimeChanged {
+                line_index: 4,
+                time: "2000.4".to_string(),
+            },
+            ParseWarning::CommandTimeChanged {
+                line_index: 5,
+                time: "3000.7".to_string(),
+            },
+        ]
+    );
+
+    let options = ParseOptions::new().command_time_policy(CommandTimePolicy::Round);
+    let mut osu_file = OsuFile::from_str_with_options(OSU_FILE, &options).unwrap();
+
+    assert_eq!(times(&mut osu_file), vec![Some(1001), Some(3001)]);
+}
+
+#[test]
+fn preserve_events_formatting() {
+    let options = ParseOptions::new()
+        .command_time_policy(CommandTimePolicy::Round)
+        .preserve_events_formatting(true);
+    let mut osu_file = OsuFile::from_str_with_options(OSU_FILE, &options).unwrap();
+
+    assert_eq!(osu_file.to_string(), OSU_FILE);
+
+    // changed events are written normally
+    commands(&mut osu_file).pop();
+    assert!(osu_file.to_string().ends_with(" F,0,1001,2000,1,0"));
+}
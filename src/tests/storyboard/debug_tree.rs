@@ -0,0 +1,60 @@
+use pretty_assertions::assert_eq;
+
+use crate::osu_file::{events::debug_tree::DebugNode, Events, VersionedFromStr};
+
+fn node(kind: &'static str, line: &str, children: Vec<DebugNode>) -> DebugNode {
+    DebugNode {
+        kind,
+        line: line.to_string(),
+        children,
+    }
+}
+
+#[test]
+fn to_debug_tree() {
+    let events = Events::from_str(
+        "//Storyboard Layer 0 (Background)
+Sprite,Background,Centre,\"bg.png\",320,240
+ L,1000,2
+  F,0,0,500,0,1
+ S,0,0,,0.5",
+        14,
+    )
+    .unwrap()
+    .unwrap();
+
+    let tree = events.to_debug_tree(14);
+
+    assert_eq!(
+        tree,
+        node(
+            "Events",
+            "",
+            vec![
+                node("Comment", "//Storyboard Layer 0 (Background)", Vec::new()),
+                node(
+                    "Sprite",
+                    "Sprite,Background,Centre,\"bg.png\",320,240",
+                    vec![
+                        node(
+                            "Loop",
+                            "L,1000,2",
+                            vec![node("Fade", "F,0,0,500,0,1", Vec::new())]
+                        ),
+                        node("Scale", "S,0,0,,0.5", Vec::new()),
+                    ]
+                ),
+            ]
+        )
+    );
+
+    assert_eq!(
+        tree.children[1].children[0].to_json(),
+        r#"{"kind":"Loop","line":"L,1000,2","children":[{"kind":"Fade","line":"F,0,0,500,0,1","children":[]}]}"#
+    );
+
+    // quotes in the line are escaped
+    assert!(tree.children[1].to_json().starts_with(
+        r#"{"kind":"Sprite","line":"Sprite,Background,Centre,\"bg.png\",320,240","children":["#
+    ));
+}
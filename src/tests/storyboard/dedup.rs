@@ -0,0 +1,82 @@
+use pretty_assertions::assert_eq;
+
+use crate::osu_file::events::storyboard::dedup::DedupReport;
+use crate::osu_file::{Events, OsuFile, ParseOptions, VersionedFromStr, VersionedToString};
+
+const FADES: &str = "Sprite,Foreground,Centre,\"star.png\",320,240
+ F,0,0,1000,1,0
+Sprite,Foreground,Centre,\"star.png\",320,240
+ F,0,2000,3000,1,0
+Sprite,Foreground,Centre,\"star.png\",320,240
+ S,0,4000,,2
+ F,0,4000,5000,1,0";
+
+#[test]
+fn dedup_objects() {
+    let mut events = Events::from_str(FADES, 14).unwrap().unwrap();
+    let report = events.dedup_objects(14);
+
+    assert_eq!(
+        report,
+        DedupReport {
+            removed_objects: 1,
+            saved_bytes: 44,
+        }
+    );
+
+    // the last sprite also scales, which the merged sprite would carry over from the start
+    assert_eq!(
+        events.to_string(14).unwrap(),
+        "Sprite,Foreground,Centre,\"star.png\",320,240
+ F,0,0,1000,1,0
+ F,0,2000,3000,1,0
+Sprite,Foreground,Centre,\"star.png\",320,240
+ S,0,4000,,2
+ F,0,4000,5000,1,0"
+    );
+}
+
+#[test]
+fn dedup_keeps_draw_order() {
+    let s = "Sprite,Foreground,Centre,\"star.png\",320,240
+ F,0,0,1000,1,0
+Sprite,Foreground,Centre,\"cloud.png\",320,240
+ F,0,2000,3000,1,0
+Sprite,Foreground,Centre,\"star.png\",320,240
+ F,0,2000,3000,1,0";
+    let mut events = Events::from_str(s, 14).unwrap().unwrap();
+
+    // the second star would be drawn below the cloud
+    assert_eq!(events.dedup_objects(14), DedupReport::default());
+    assert_eq!(events.to_string(14).unwrap(), s);
+}
+
+#[test]
+fn dedup_needs_fade_out() {
+    let s = "Sprite,Foreground,Centre,\"star.png\",320,240
+ F,0,0,1000,1
+Sprite,Foreground,Centre,\"star.png\",320,240
+ F,0,2000,3000,1";
+    let mut events = Events::from_str(s, 14).unwrap().unwrap();
+
+    assert_eq!(events.dedup_objects(14).removed_objects, 0);
+}
+
+#[test]
+fn dedup_storyboard_option() {
+    let s = format!("osu file format v14\n\n[Events]\n{FADES}");
+
+    let osu_file = s.parse::<OsuFile>().unwrap();
+    assert_eq!(osu_file.storyboard_dedup_report(), None);
+    assert_eq!(osu_file.events.unwrap().0.len(), 3);
+
+    let osu_file =
+        OsuFile::from_str_with_options(&s, &ParseOptions::new().dedup_storyboard(true)).unwrap();
+    assert_eq!(
+        osu_file
+            .storyboard_dedup_report()
+            .map(|r| r.removed_objects),
+        Some(1)
+    );
+    assert_eq!(osu_file.events.unwrap().0.len(), 2);
+}
@@ -0,0 +1,101 @@
+use pretty_assertions::assert_eq;
+use rust_decimal_macros::dec;
+
+use crate::{
+    events::{
+        storyboard::sprites::{Layer, Object, ObjectType},
+        types::{LayerLegacy, OriginTypeLegacy},
+        AudioSample, Event, Events, LegacyConversionError, SampleLegacy, SpriteLegacy, Volume,
+    },
+    FilePath, Position, VersionedFromStr, VersionedToString,
+};
+
+#[test]
+fn sprite_legacy_to_object() {
+    let sprite = SpriteLegacy {
+        layer: LayerLegacy::Foreground,
+        origin: OriginTypeLegacy::BottomCentre,
+        file_name: FilePath::new_quoted("star.png"),
+        position: None,
+        commands: Vec::new(),
+    };
+
+    let object = Object::try_from(sprite.clone()).unwrap();
+    assert_eq!(Layer::Foreground, object.layer);
+    assert_eq!(Position::new(dec!(0), dec!(0)), object.position);
+    assert!(matches!(object.object_type, ObjectType::Sprite(_)));
+    assert_eq!(
+        "Sprite,Foreground,4,\"star.png\",0,0",
+        object.to_string(14).unwrap()
+    );
+
+    let back = SpriteLegacy::try_from(object).unwrap();
+    assert_eq!(
+        SpriteLegacy {
+            position: Some(Position::new(dec!(0), dec!(0))),
+            ..sprite.clone()
+        },
+        back
+    );
+
+    let video = SpriteLegacy {
+        layer: LayerLegacy::Video,
+        ..sprite
+    };
+    assert_eq!(
+        Err(LegacyConversionError::UnsupportedLayer),
+        Object::try_from(video)
+    );
+}
+
+#[test]
+fn sample_legacy_to_audio_sample() {
+    let sample = SampleLegacy {
+        time: dec!(1000.5),
+        layer: LayerLegacy::Pass,
+        file_name: "hit.wav".into(),
+        volume: None,
+        commands: Vec::new(),
+    };
+
+    let audio_sample = AudioSample::try_from(sample.clone()).unwrap();
+    assert_eq!(
+        "Sample,1001,2,hit.wav,100",
+        audio_sample.to_string(14).unwrap()
+    );
+
+    assert_eq!(
+        SampleLegacy {
+            time: dec!(1001),
+            volume: Some(Volume::new(100, 14).unwrap()),
+            ..sample.clone()
+        },
+        SampleLegacy::from(audio_sample)
+    );
+
+    let overlay = SampleLegacy {
+        layer: LayerLegacy::Overlay,
+        ..sample
+    };
+    assert_eq!(
+        Err(LegacyConversionError::UnsupportedLayer),
+        AudioSample::try_from(overlay)
+    );
+}
+
+#[test]
+fn events_upgrade_legacy() {
+    let mut events = Events::from_str(
+        "4,0,1,\"bg.png\",320,240
+4,5,1,\"video.png\",320,240
+6,500,1,\"hit.wav\",80",
+        3,
+    )
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(2, events.upgrade_legacy());
+    assert!(matches!(events.0[0], Event::StoryboardObject(_)));
+    assert!(matches!(events.0[1], Event::SpriteLegacy(_)));
+    assert!(matches!(events.0[2], Event::AudioSample(_)));
+}
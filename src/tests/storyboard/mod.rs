@@ -3,11 +3,17 @@ use crate::{
         types::{LayerLegacy, OriginTypeLegacy},
         AnimationLegacy, SampleLegacy, SpriteLegacy, Volume,
     },
-    Position, VersionedFromStr, VersionedToString,
+    FilePath, Position, VersionedFromStr, VersionedToString,
 };
 
+mod bounds;
 mod cmds;
+mod command_times;
+mod debug_tree;
+mod dedup;
+mod legacy;
 mod sprites;
+mod time_range;
 
 #[test]
 fn sprite_legacy_parse() {
@@ -19,7 +25,7 @@ fn sprite_legacy_parse() {
     let s = SpriteLegacy {
         layer: LayerLegacy::Background,
         origin: OriginTypeLegacy::Centre,
-        file_name: "\"Text\\Play2-HaveFunH.png\"".into(),
+        file_name: FilePath::new_quoted("Text\\Play2-HaveFunH.png"),
         position: Some(Position {
             x: 320.into(),
             y: 240.into(),
@@ -44,7 +50,7 @@ fn animation_legacy() {
     let s = AnimationLegacy {
         layer: LayerLegacy::Background,
         origin: OriginTypeLegacy::Centre,
-        file_name: "\"Other\\Play3\\explosion.png\"".into(),
+        file_name: FilePath::new_quoted("Other\\Play3\\explosion.png"),
         position: Some(Position {
             x: 418.into(),
             y: 108.into(),
@@ -68,7 +74,7 @@ fn sample_legacy() {
 
     let s = SampleLegacy {
         layer: LayerLegacy::Background,
-        file_name: "\"Text\\Play2-HaveFunH.png\"".into(),
+        file_name: FilePath::new_quoted("Text\\Play2-HaveFunH.png"),
         time: 55.into(),
         volume: Some(Volume::new(60, 3).unwrap()),
         commands: Vec::new(),
@@ -80,7 +86,7 @@ fn sample_legacy() {
         .unwrap();
     let s_without_volume = SampleLegacy {
         layer: LayerLegacy::Background,
-        file_name: "\"Text\\Play2-HaveFunH.png\"".into(),
+        file_name: FilePath::new_quoted("Text\\Play2-HaveFunH.png"),
         time: 55.into(),
         volume: None,
         commands: Vec::new(),
@@ -1,4 +1,4 @@
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 use either::Either;
 use rust_decimal_macros::dec;
@@ -6,7 +6,9 @@ use rust_decimal_macros::dec;
 use crate::osu_file::events::storyboard::sprites::*;
 use crate::osu_file::events::Event;
 use crate::osu_file::types::Position;
-use crate::osu_file::{Events, VersionedFromStr, VersionedToString};
+use crate::osu_file::{
+    Events, Extensions, FilePath, OsuFile, VersionedFromStr, VersionedToString, WriteOptions,
+};
 
 #[test]
 fn storyboard_sprites_parse() {
@@ -17,6 +19,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
     let s = Events(vec![
         Event::StoryboardObject(Object {
             layer: Layer::Pass,
+            layer_shorthand: false,
             origin: Origin {
                 type_: Either::Left(OriginType::Centre),
                 shorthand: false,
@@ -25,13 +28,15 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 x: dec!(320).into(),
                 y: dec!(240).into(),
             },
-            object_type: ObjectType::Sprite(
-                Sprite::new(Path::new("\"Text\\Play2-HaveFunH.png\"")).unwrap(),
-            ),
+            object_type: ObjectType::Sprite(Sprite {
+                filepath: FilePath::new_quoted("Text\\Play2-HaveFunH.png"),
+            }),
             commands: Vec::new(),
+            extensions: Extensions::new(),
         }),
         Event::StoryboardObject(Object {
             layer: Layer::Fail,
+            layer_shorthand: false,
             origin: Origin {
                 type_: Either::Left(OriginType::BottomCentre),
                 shorthand: false,
@@ -44,9 +49,10 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
                 frame_count: 12,
                 frame_delay: dec!(31),
                 loop_type: LoopType::LoopForever,
-                filepath: "\"Other\\Play3\\explosion.png\"".into(),
+                filepath: FilePath::new_quoted("Other\\Play3\\explosion.png"),
             }),
             commands: Vec::new(),
+            extensions: Extensions::new(),
         }),
     ]);
 
@@ -58,6 +64,7 @@ Animation,Fail,BottomCentre,\"Other\\Play3\\explosion.png\",418,108,12,31,LoopFo
 fn frame_file_names() {
     let animation = Object {
         layer: Layer::Background,
+        layer_shorthand: false,
         origin: Origin {
             type_: Either::Left(OriginType::BottomCentre),
             shorthand: false,
@@ -73,6 +80,7 @@ fn frame_file_names() {
             filepath: "testfile.png".into(),
         }),
         commands: Vec::new(),
+        extensions: Extensions::new(),
     };
 
     if let ObjectType::Animation(animation) = &animation.object_type {
@@ -91,3 +99,56 @@ fn frame_file_names() {
         unreachable!();
     }
 }
+
+#[test]
+fn numeric_layer_and_origin() {
+    let i_str = "Sprite,3,1,\"star.png\",320,240
+Sprite,Foreground,Centre,\"star.png\",320,240";
+    let i = Events::from_str(i_str, 14).unwrap().unwrap();
+
+    let layers = i
+        .0
+        .iter()
+        .map(|e| match e {
+            Event::StoryboardObject(obj) => (obj.layer, obj.layer_shorthand, obj.origin.shorthand),
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        layers,
+        vec![
+            (Layer::Foreground, true, true),
+            (Layer::Foreground, false, false)
+        ]
+    );
+    assert_eq!(i_str, i.to_string(14).unwrap());
+    assert!(Events::from_str("Sprite,5,1,\"star.png\",320,240", 14).is_err());
+}
+
+#[test]
+fn prefer_named_enums() {
+    let osu_file = "osu file format v14
+
+[Events]
+Sprite,3,1,\"star.png\",320,240
+Sprite,Foreground,Centre,\"star.png\",320,240"
+        .parse::<OsuFile>()
+        .unwrap();
+
+    let named = osu_file.to_string_with_options(&WriteOptions::new().prefer_named_enums(true));
+    let numbers = osu_file.to_string_with_options(&WriteOptions::new().prefer_named_enums(false));
+
+    assert!(named.ends_with(
+        "Sprite,Foreground,Centre,\"star.png\",320,240
+Sprite,Foreground,Centre,\"star.png\",320,240"
+    ));
+    assert!(numbers.ends_with(
+        "Sprite,3,1,\"star.png\",320,240
+Sprite,3,1,\"star.png\",320,240"
+    ));
+    assert_eq!(
+        osu_file.to_string_with_options(&WriteOptions::new()),
+        osu_file.to_string()
+    );
+}
@@ -0,0 +1,42 @@
+use crate::osu_file::{Events, VersionedFromStr};
+
+#[test]
+fn in_time_range() {
+    let events = Events::from_str(
+        "//Background and Video events
+0,0,\"bg.jpg\",0,0
+//Break Periods
+2,1000,2000
+//Storyboard Layer 0 (Background)
+Sprite,Background,Centre,\"a.png\",320,240
+ F,0,0,500,1,0
+Sprite,Background,Centre,\"b.png\",320,240
+ F,0,3000,4000,1,0
+Sprite,Background,Centre,\"c.png\",320,240
+ L,1500,2
+  F,0,0,100,1,0
+Sample,2500,0,\"hit.wav\",100",
+        14,
+    )
+    .unwrap()
+    .unwrap();
+
+    let index = events.time_index();
+    let windows = [
+        (0, 0),
+        (600, 900),
+        (1500, 1600),
+        (2001, 2999),
+        (3500, 10000),
+        (-100, -1),
+    ];
+    let expected: [&[usize]; 6] = [&[1, 5], &[1], &[1, 3, 7], &[1, 8], &[1, 6], &[1]];
+
+    for ((start, end), expected) in windows.into_iter().zip(expected) {
+        assert_eq!(index.indices_in_time_range(start, end), expected);
+        assert_eq!(
+            events.in_time_range(start, end),
+            index.in_time_range(start, end)
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use std::io::{self, Read};
+
+use pretty_assertions::assert_eq;
+
+use crate::osu_file::{LimitsExceeded, OsuFile, ParseError, ParseLimits, ParseOptions};
+
+#[test]
+fn from_reader() {
+    let i = include_str!("./osu_files/files/acid_rain.osu");
+    let osu_file = OsuFile::from_reader(i.as_bytes()).unwrap();
+
+    assert_eq!(i.parse::<OsuFile>().unwrap(), osu_file);
+
+    let mut written = Vec::new();
+    osu_file.write_to(&mut written).unwrap();
+
+    assert_eq!(osu_file.to_string(), String::from_utf8(written).unwrap());
+}
+
+/// Reader that reads a single byte at a time, without buffering.
+struct ByteReader<'a>(&'a [u8]);
+
+impl Read for ByteReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match (self.0.split_first(), buf.first_mut()) {
+            (Some((byte, rest)), Some(out)) => {
+                *out = *byte;
+                self.0 = rest;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+#[test]
+fn from_unbuffered_reader() {
+    let i = include_str!("./osu_files/files/acid_rain.osu");
+    let osu_file = OsuFile::from_reader(ByteReader(i.as_bytes())).unwrap();
+
+    assert_eq!(i.parse::<OsuFile>().unwrap(), osu_file);
+}
+
+#[test]
+fn from_reader_line_index() {
+    let i = "osu file format v14\n\n\n\n[Colours]\nCombo1 : 255,128,255\nfoobar";
+
+    let str_err = i.parse::<OsuFile>().unwrap_err();
+    let reader_err = OsuFile::from_reader(i.as_bytes()).unwrap_err();
+
+    assert_eq!(str_err.line_index(), reader_err.line_index());
+    assert_eq!(6, reader_err.line_index());
+}
+
+#[test]
+fn from_reader_bom_line() {
+    let i = "\u{feff}\n\u{200b}\nosu file format v14\n\n[General]\nAudioFilename: audio.mp3";
+    let osu_file = OsuFile::from_reader(i.as_bytes()).unwrap();
+
+    assert_eq!(i.parse::<OsuFile>().unwrap(), osu_file);
+    assert_eq!(osu_file.header().unwrap().leading, "\u{feff}\n\u{200b}\n");
+}
+
+#[test]
+fn from_reader_limits() {
+    let i = format!(
+        "osu file format v14\n\n[Metadata]\nTitle:{}\n",
+        "a".repeat(100)
+    );
+    let options = ParseOptions::new().limits(ParseLimits::new().max_line_length(50));
+
+    let err = OsuFile::from_reader_with_options(i.as_bytes(), &options).unwrap_err();
+
+    assert_eq!(3, err.line_index());
+    assert!(matches!(
+        err.error(),
+        ParseError::LimitsExceeded(LimitsExceeded::LineLength { limit: 50, .. })
+    ));
+}
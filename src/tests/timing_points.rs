@@ -0,0 +1,507 @@
+use pretty_assertions::assert_eq;
+use rust_decimal_macros::dec;
+
+use crate::osu_file::{
+    timingpoints::{
+        estimate_bpm, Effects, SampleIndex, SampleSet, SortedTimingPoints, TimingPoint,
+        TimingPointInsertError, TimingPointValueError, TimingPoints, Volume,
+    },
+    Beats, InsertOptions, Millis, VersionedFromStr, VersionedToString,
+};
+
+fn uninherited(time: i32, beat_length: rust_decimal::Decimal) -> TimingPoint {
+    TimingPoint::new_uninherited(
+        time,
+        beat_length,
+        4,
+        SampleSet::Normal,
+        SampleIndex::OsuDefaultHitsounds,
+        Volume::new(100, 14).unwrap(),
+        Effects::new(false, false),
+    )
+}
+
+fn inherited(time: i32) -> TimingPoint {
+    TimingPoint::new_inherited(
+        time,
+        dec!(1),
+        4,
+        SampleSet::Normal,
+        SampleIndex::OsuDefaultHitsounds,
+        Volume::new(100, 14).unwrap(),
+        Effects::new(false, false),
+    )
+}
+
+#[test]
+fn display_bpm_single() {
+    let timing_points = TimingPoints(vec![uninherited(500, dec!(500)), inherited(1000)]);
+    let bpm = timing_points.display_bpm(10000).unwrap();
+
+    assert_eq!(bpm.most_common, dec!(120));
+    assert_eq!(bpm.min, dec!(120));
+    assert_eq!(bpm.max, dec!(120));
+    assert_eq!(bpm.histogram.len(), 1);
+    assert_eq!(bpm.histogram[0].duration, 10000);
+    assert_eq!(bpm.to_display_string(), "120");
+}
+
+#[test]
+fn display_bpm_variable() {
+    let timing_points = TimingPoints(vec![
+        uninherited(0, dec!(500)),
+        uninherited(1000, dec!(250)),
+        uninherited(9000, dec!(1000)),
+        // after the last object, doesn't count towards the most common bpm
+        uninherited(20000, dec!(2000)),
+    ]);
+    let bpm = timing_points.display_bpm(10000).unwrap();
+
+    assert_eq!(bpm.most_common, dec!(240));
+    // the bpm after the last object isn't the lowest either
+    assert_eq!(bpm.min, dec!(60));
+    assert_eq!(bpm.max, dec!(240));
+    assert_eq!(bpm.histogram[0].duration, 8000);
+    assert_eq!(bpm.histogram[3].duration, 0);
+    assert_eq!(bpm.to_display_string(), "60-240 (240)");
+}
+
+#[test]
+fn display_bpm_rounding() {
+    let timing_points = TimingPoints(vec![uninherited(0, dec!(352.9411764705882))]);
+    let bpm = timing_points.display_bpm(1000).unwrap();
+
+    assert_eq!(bpm.to_display_string(), "170");
+
+    // beat lengths that are the same after rounding are one bpm everywhere
+    let timing_points = TimingPoints(vec![
+        uninherited(0, dec!(333.3333)),
+        uninherited(1000, dec!(333.33334)),
+    ]);
+    let bpm = timing_points.display_bpm(2000).unwrap();
+    assert_eq!(bpm.histogram.len(), 1);
+    assert_eq!(bpm.min, bpm.most_common);
+    assert_eq!(bpm.max, bpm.most_common);
+
+    // beat lengths that round to 0 or are too small for the bpm to fit are skipped
+    let timing_points = TimingPoints(vec![
+        uninherited(0, dec!(0.0004)),
+        uninherited(500, dec!(0.0000000000000000000000000001)),
+        uninherited(1000, dec!(500)),
+    ]);
+    let bpm = timing_points.display_bpm(2000).unwrap();
+    assert_eq!(bpm.to_display_string(), "120");
+    assert!(
+        TimingPoint::checked_beat_duration_ms_to_bpm(dec!(0.0000000000000000000000000001))
+            .is_none()
+    );
+    assert!(TimingPoints(vec![inherited(0)]).display_bpm(1000).is_none());
+}
+
+#[test]
+fn timing_context() {
+    let timing_points = TimingPoints(vec![
+        uninherited(100, dec!(500)),
+        inherited(500),
+        uninherited(1100, dec!(250)),
+    ]);
+    let context = timing_points.timing_context().unwrap();
+
+    assert_eq!(context.to_beats(Millis(100)), Beats(dec!(0)));
+    assert_eq!(context.to_beats(Millis(-150)), Beats(dec!(-0.5)));
+    assert_eq!(context.to_beats(Millis(1100)), Beats(dec!(2)));
+    assert_eq!(context.to_beats(Millis(1350)), Beats(dec!(3)));
+
+    assert_eq!(context.to_millis(Beats(dec!(1.5))), Some(Millis(850)));
+    assert_eq!(context.to_millis(Beats(dec!(4))), Some(Millis(1600)));
+    assert_eq!(
+        context.add_beats(Millis(600), Beats(dec!(2))),
+        Some(Millis(1350))
+    );
+
+    assert_eq!(context.snap(Millis(1230), 2), Some(Millis(1225)));
+    assert_eq!(context.snap(Millis(360), 1), Some(Millis(600)));
+    assert_eq!(context.to_millis(Beats(dec!(10000000000))), None);
+    assert_eq!(context.add_beats(Millis(i32::MAX), Beats(dec!(1))), None);
+    assert_eq!(context.beat_length_at(Millis(0)), dec!(500));
+
+    assert_eq!(Millis(i32::MAX).checked_add(Millis(1)), None);
+    assert_eq!(Millis(i32::MIN).checked_sub(Millis(1)), None);
+    assert_eq!(Millis(100).checked_sub(Millis(150)), Some(Millis(-50)));
+
+    assert!(TimingPoints(vec![inherited(0)]).timing_context().is_none());
+}
+
+#[test]
+fn beat_length_text_preserved() {
+    let i = "0,300.000000000001,4,1,0,100,1,0
+1000,3e2,4,1,0,100,1,0
+2000,-100,4,1,0,100,0,0";
+    let timing_points = TimingPoints::from_str(i, 14).unwrap().unwrap();
+
+    assert_eq!(dec!(300), timing_points.0[1].beat_length);
+    assert_eq!(i, timing_points.to_string(14).unwrap());
+
+    // how the beat length was written doesn't change equality
+    let written_out = TimingPoints::from_str("1000,300,4,1,0,100,1,0", 14)
+        .unwrap()
+        .unwrap();
+    assert_eq!(timing_points.0[1], written_out.0[0]);
+}
+
+#[test]
+fn beat_length_text_dropped_when_changed() {
+    let mut timing_points = TimingPoints::from_str("1000,3e2,4,1,0,100,1,0", 14)
+        .unwrap()
+        .unwrap();
+    let untouched = timing_points.clone();
+
+    timing_points.0[0].beat_length = dec!(250);
+    assert_eq!(
+        "1000,250,4,1,0,100,1,0",
+        timing_points.to_string(14).unwrap()
+    );
+
+    // setting the value back uses the original text again
+    timing_points.0[0].beat_length = dec!(300);
+    assert!(timing_points.semantically_eq(&untouched));
+    assert_eq!(
+        "1000,3e2,4,1,0,100,1,0",
+        timing_points.to_string(14).unwrap()
+    );
+}
+
+#[test]
+fn short_timing_points() {
+    let i = "1000,500\n2000,250,3";
+    let timing_points = TimingPoints::from_str(i, 4).unwrap().unwrap();
+
+    // versions 3 and 4 have the times offset by 24ms
+    let first = TimingPoint::new_uninherited(
+        1024,
+        dec!(500),
+        4,
+        SampleSet::Normal,
+        SampleIndex::Index(1.try_into().unwrap()),
+        Volume::new(100, 4).unwrap(),
+        Effects::new(false, false),
+    );
+    assert!(
+        TimingPoints(vec![timing_points.0[0].clone()]).semantically_eq(&TimingPoints(vec![first]))
+    );
+    assert_eq!(timing_points.0[1].meter, 3);
+    assert_eq!(i, timing_points.to_string(4).unwrap());
+
+    // changing a missing field writes the fields up to it
+    let mut timing_points = timing_points;
+    timing_points.0[1].uninherited = false;
+    assert_eq!(
+        "1000,500\n2000,250,3,1,1,100,0",
+        timing_points.to_string(4).unwrap()
+    );
+    assert_eq!(
+        "1024,500,4,1,1,100\n2024,250,3,1,1,100,0",
+        timing_points.to_string(14).unwrap()
+    );
+
+    assert!(TimingPoint::from_str("1000,500", 14).is_err());
+    assert!(TimingPoint::from_str("1000", 4).is_err());
+}
+
+#[test]
+fn checked_bpm_and_sv() {
+    assert_eq!(uninherited(0, dec!(500)).checked_bpm(), Some(dec!(120)));
+    assert_eq!(uninherited(0, dec!(0)).checked_bpm(), None);
+    assert_eq!(uninherited(0, dec!(-500)).checked_bpm(), None);
+    assert_eq!(uninherited(0, dec!(500)).checked_sv(), None);
+
+    let mut timing_point = inherited(0);
+    assert_eq!(timing_point.checked_sv(), Some(dec!(1)));
+    assert_eq!(timing_point.checked_bpm(), None);
+    timing_point.beat_length = dec!(0);
+    assert_eq!(timing_point.checked_sv(), None);
+
+    // the BPM and multiplier of tiny beat lengths don't fit in a decimal
+    timing_point.beat_length = dec!(-0.0000000000000000000000000001);
+    assert_eq!(timing_point.checked_sv(), None);
+    assert_eq!(
+        uninherited(0, dec!(0.0000000000000000000000000001)).checked_bpm(),
+        None
+    );
+    assert_eq!(
+        TimingPoint::checked_bpm_to_beat_duration_ms(dec!(0.0000000000000000000000000001)),
+        None
+    );
+    assert_eq!(
+        TimingPoint::checked_bpm_to_beat_duration_ms(dec!(120)),
+        Some(dec!(500))
+    );
+}
+
+#[test]
+fn validated_constructors() {
+    let volume = || Volume::new(100, 14).unwrap();
+
+    assert_eq!(
+        TimingPoint::try_new_uninherited(
+            0,
+            dec!(0),
+            4,
+            SampleSet::Normal,
+            SampleIndex::OsuDefaultHitsounds,
+            volume(),
+            Effects::new(false, false),
+        ),
+        Err(TimingPointValueError::NonPositiveBeatDuration)
+    );
+    assert_eq!(
+        TimingPoint::try_new_inherited(
+            0,
+            dec!(-1),
+            4,
+            SampleSet::Normal,
+            SampleIndex::OsuDefaultHitsounds,
+            volume(),
+            Effects::new(false, false),
+        ),
+        Err(TimingPointValueError::NonPositiveSliderVelocity)
+    );
+    assert_eq!(
+        TimingPoint::try_new_uninherited(
+            0,
+            dec!(500),
+            4,
+            SampleSet::Normal,
+            SampleIndex::OsuDefaultHitsounds,
+            volume(),
+            Effects::new(false, false),
+        ),
+        Ok(uninherited(0, dec!(500)))
+    );
+}
+
+#[test]
+fn insert_keeps_hitsound_settings() {
+    let mut soft = uninherited(0, dec!(500));
+    soft.sample_set = SampleSet::Soft;
+    soft.sample_index = SampleIndex::Index(2.try_into().unwrap());
+    soft.volume = Volume::new(60, 14).unwrap();
+    soft.effects = Some(Effects::new(true, true));
+    let mut timing_points = TimingPoints(vec![soft, inherited(1000)]);
+
+    assert_eq!(timing_points.insert_inherited(500, dec!(2)), Ok(1));
+    let inserted = &timing_points.0[1];
+    assert!(!inserted.uninherited);
+    assert_eq!(inserted.calc_slider_velocity_multiplier(), Some(dec!(2)));
+    assert_eq!(inserted.sample_set, SampleSet::Soft);
+    assert_eq!(inserted.volume.volume(), 60);
+    assert_eq!(inserted.effects, Some(Effects::new(true, false)));
+
+    // uninherited timing points go before inherited ones at the same time
+    assert_eq!(timing_points.insert_uninherited(1000, dec!(240), 3), Ok(2));
+    let inserted = &timing_points.0[2];
+    assert_eq!(inserted.calc_bpm(), Some(dec!(240)));
+    assert_eq!(inserted.meter, 3);
+    assert_eq!(inserted.sample_set, SampleSet::Soft);
+    assert_eq!(timing_points.0[3].time, 1000);
+    assert!(!timing_points.0[3].uninherited);
+
+    assert_eq!(
+        timing_points.insert_uninherited(2000, dec!(0), 4),
+        Err(TimingPointValueError::NonPositiveBeatDuration)
+    );
+    assert_eq!(
+        timing_points.insert_inherited(2000, dec!(-1)),
+        Err(TimingPointValueError::NonPositiveSliderVelocity)
+    );
+    assert_eq!(
+        timing_points.insert_uninherited(2000, dec!(0.0000000000000000000000000001), 4),
+        Err(TimingPointValueError::BpmOutOfRange)
+    );
+    assert_eq!(
+        timing_points.insert_inherited(2000, dec!(0.0000000000000000000000000001)),
+        Err(TimingPointValueError::SliderVelocityOutOfRange)
+    );
+    assert_eq!(timing_points.0.len(), 4);
+
+    let mut empty = TimingPoints(Vec::new());
+    assert_eq!(empty.insert_uninherited(0, dec!(120), 4), Ok(0));
+    assert_eq!(empty.0[0].sample_set, SampleSet::Normal);
+    assert_eq!(empty.0[0].volume.volume(), 100);
+}
+
+#[test]
+fn sorted_timing_points() {
+    let mut timing_points = SortedTimingPoints::from(TimingPoints(vec![
+        uninherited(1000, dec!(250)),
+        inherited(500),
+        uninherited(0, dec!(500)),
+    ]));
+
+    assert_eq!(
+        timing_points.iter().map(|t| t.time).collect::<Vec<_>>(),
+        vec![0, 500, 1000]
+    );
+
+    assert_eq!(timing_points.insert(inherited(1000)), 3);
+    assert_eq!(timing_points.insert(uninherited(1000, dec!(200))), 3);
+    assert_eq!(timing_points.insert(uninherited(-100, dec!(1000))), 0);
+
+    assert_eq!(timing_points.bpm_at(-500), Some(dec!(60)));
+    assert_eq!(timing_points.bpm_at(700), Some(dec!(120)));
+    assert_eq!(timing_points.bpm_at(1000), Some(dec!(300)));
+    assert_eq!(timing_points.at(999).map(|t| t.time), Some(500));
+    assert_eq!(timing_points.in_range(0..1000).len(), 2);
+
+    let timing_points = TimingPoints::from(timing_points);
+    assert_eq!(timing_points.0.len(), 6);
+    assert!(!timing_points.0[5].uninherited);
+}
+
+#[test]
+fn estimate_bpm_from_taps() {
+    // the tap at 2500 is skipped
+    let taps = [3999, 1002, 1498, 2001, 3000, 3502, 3502];
+    let estimate = estimate_bpm(&taps).unwrap();
+
+    assert!((estimate.beat_length - 500.0).abs() < 1.0);
+    assert!((estimate.bpm() - 120.0).abs() < 0.5);
+    assert!((estimate.offset - 1000.0).abs() < 2.0);
+    assert!(estimate.error < 5.0);
+
+    let timing_points = TimingPoints::from_estimate(&estimate, 4).unwrap();
+    assert_eq!(timing_points.0.len(), 1);
+    assert_eq!(timing_points.0[0].time, 1001);
+    assert!(timing_points.0[0].uninherited);
+
+    assert_eq!(estimate_bpm(&[1000, 1000]), None);
+
+    // taps too far apart to subtract as `i32`
+    let estimate = estimate_bpm(&[i32::MIN, i32::MAX]).unwrap();
+    assert_eq!(estimate.beat_length, u32::MAX as f64);
+}
+
+#[test]
+fn timing_points_comments() {
+    let i = "0,500,4,2,0,60,1,0
+// kiai
+1000,-50,4,2,0,60,0,1";
+    let timing_points = TimingPoints::from_str(i, 14).unwrap().unwrap();
+
+    assert_eq!(
+        timing_points.0[1].comments.before,
+        vec![" kiai".to_string()]
+    );
+    assert_eq!(timing_points.to_string(14).unwrap(), i);
+
+    // indented comments are comments too
+    let timing_points = TimingPoints::from_str("\t// uninherited\n0,500,4,2,0,60,1,0", 14)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        timing_points.0[0].comments.before,
+        vec![" uninherited".to_string()]
+    );
+}
+
+#[test]
+fn active_at() {
+    let mut timing_points = TimingPoints(vec![
+        inherited(1500),
+        uninherited(1000, dec!(500)),
+        uninherited(2000, dec!(250)),
+        inherited(500),
+        inherited(2500),
+    ]);
+    let index = timing_points.index();
+
+    let active = index.active_at(0);
+    assert_eq!(active.uninherited.map(|t| t.time), Some(1000));
+    assert_eq!(active.inherited, None);
+
+    let active = index.active_at(700);
+    assert_eq!(active.uninherited.map(|t| t.time), Some(1000));
+    assert_eq!(active.inherited.map(|t| t.time), Some(500));
+
+    // uninherited timing points reset the slider velocity
+    assert_eq!(index.active_at(1200).inherited, None);
+    assert_eq!(index.active_at(1500).inherited.map(|t| t.time), Some(1500));
+    assert_eq!(index.active_at(2200).inherited, None);
+
+    let active = timing_points.active_at(3000);
+    assert_eq!(active.uninherited.map(|t| t.time), Some(2000));
+    assert_eq!(active.inherited.map(|t| t.time), Some(2500));
+
+    timing_points.sort();
+    assert_eq!(
+        timing_points.0.iter().map(|t| t.time).collect::<Vec<_>>(),
+        vec![500, 1000, 1500, 2000, 2500]
+    );
+}
+
+#[test]
+fn active_at_same_time() {
+    // the inherited timing point is written first, but uninherited ones go first at the same time
+    let timing_points = TimingPoints(vec![inherited(1000), uninherited(1000, dec!(500))]);
+    let active = timing_points.active_at(1000);
+    assert!(active.uninherited.is_some());
+    assert_eq!(active.inherited.map(|t| t.time), Some(1000));
+
+    let sorted = SortedTimingPoints::from(timing_points.clone());
+    assert_eq!(sorted.active_at(1000), active);
+    assert!(sorted.as_slice()[0].uninherited);
+
+    let mut inserted = SortedTimingPoints::new();
+    for timing_point in timing_points.0 {
+        inserted.insert(timing_point);
+    }
+    assert_eq!(inserted, sorted);
+}
+
+#[test]
+fn kiai_sections() {
+    let i = "1000,500,4,2,0,100,1,1
+1500,-100,4,2,0,100,0,0
+2000,-100,4,2,0,100,0,1
+2000,-100,4,2,0,100,0,0
+3000,-100,4,2,0,100,0,1
+500,-100,4,2,0,100,0,0
+4000,-100,4,2,0,100,0,0
+4000,-100,4,2,0,100,0,1
+5000,-100,4,2,0,100,0,1";
+    let timing_points = TimingPoints::from_str(i, 14).unwrap().unwrap();
+
+    assert_eq!(
+        timing_points.kiai_sections(),
+        vec![1000..1500, 3000..i32::MAX]
+    );
+    assert_eq!(TimingPoints::default().kiai_sections(), Vec::new());
+}
+
+#[test]
+fn try_insert() {
+    let mut timing_points = TimingPoints(vec![uninherited(1000, dec!(500))]);
+
+    assert_eq!(
+        timing_points.try_insert(inherited(995), &InsertOptions::new()),
+        Err(TimingPointInsertError::OutOfOrder {
+            time: 995,
+            previous: 1000,
+        })
+    );
+    assert_eq!(
+        timing_points.try_insert(inherited(995), &InsertOptions::new().time_tolerance(5)),
+        Ok(1)
+    );
+    assert_eq!(
+        timing_points.try_insert(inherited(992), &InsertOptions::new().time_tolerance(5)),
+        Err(TimingPointInsertError::OutOfOrder {
+            time: 992,
+            previous: 1000,
+        })
+    );
+    assert_eq!(
+        timing_points.try_insert(inherited(1500), &InsertOptions::new()),
+        Ok(2)
+    );
+}
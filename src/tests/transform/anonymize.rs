@@ -0,0 +1,64 @@
+use crate::{
+    osu_file::{OsuFile, SectionName},
+    transform::RemovedField,
+};
+
+#[test]
+fn anonymize_acid_rain() {
+    let i = include_str!("../osu_files/files/acid_rain.osu");
+    let mut osu_file = i.parse::<OsuFile>().unwrap();
+    let original = osu_file.clone();
+
+    let report = osu_file.anonymize();
+
+    let removed = |section, key| RemovedField { section, key };
+    assert_eq!(
+        vec![
+            removed(SectionName::Editor, "Bookmarks"),
+            removed(SectionName::Metadata, "Creator"),
+            removed(SectionName::Metadata, "Source"),
+            removed(SectionName::Metadata, "Tags"),
+            removed(SectionName::Metadata, "BeatmapID"),
+            removed(SectionName::Metadata, "BeatmapSetID"),
+        ],
+        report.removed
+    );
+
+    let metadata = osu_file.metadata.as_ref().unwrap();
+    assert!(metadata.creator.is_none());
+    assert_eq!(original.metadata.as_ref().unwrap().title, metadata.title);
+    assert_eq!(original.hitobjects, osu_file.hitobjects);
+    assert_eq!(original.timing_points, osu_file.timing_points);
+    assert_eq!(original.difficulty, osu_file.difficulty);
+
+    // nothing left to remove
+    assert!(osu_file.anonymize().is_empty());
+}
+
+#[test]
+fn anonymize_general_bookmarks() {
+    let mut osu_file = "osu file format v5
+
+[General]
+AudioFilename: audio.mp3
+EditorBookmarks: 100,200
+"
+    .parse::<OsuFile>()
+    .unwrap();
+
+    let report = osu_file.anonymize();
+
+    assert_eq!(
+        vec![RemovedField {
+            section: SectionName::General,
+            key: "EditorBookmarks",
+        }],
+        report.removed
+    );
+    assert!(osu_file
+        .general
+        .as_ref()
+        .unwrap()
+        .editor_bookmarks
+        .is_none());
+}
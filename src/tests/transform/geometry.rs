@@ -0,0 +1,87 @@
+use rust_decimal_macros::dec;
+
+use crate::{
+    osu_file::{hitobjects::HitObjectParams, HitObjects, Position, VersionedFromStr},
+    transform::TransformOptions,
+};
+
+fn hitobjects() -> HitObjects {
+    HitObjects::from_str(
+        "100,100,1000,1,0,0:0:0:0:
+500,300,2000,2,0,L|400:300,1,100",
+        14,
+    )
+    .unwrap()
+    .unwrap()
+}
+
+fn positions(hitobjects: &HitObjects) -> Vec<Position> {
+    let mut positions = Vec::new();
+
+    for obj in &hitobjects.0 {
+        positions.push(obj.position);
+
+        if let HitObjectParams::Slider(params) = &obj.obj_params {
+            positions.extend(params.curve_points.iter().map(|p| p.0));
+        }
+    }
+
+    positions
+}
+
+fn pos(x: i32, y: i32) -> Position {
+    Position::from(Position::new(x, y))
+}
+
+#[test]
+fn mirror() {
+    let mut hitobjects = hitobjects();
+
+    hitobjects.mirror_horizontal();
+    assert_eq!(
+        positions(&hitobjects),
+        vec![pos(412, 100), pos(12, 300), pos(112, 300)]
+    );
+
+    hitobjects.mirror_vertical();
+    assert_eq!(
+        positions(&hitobjects),
+        vec![pos(412, 284), pos(12, 84), pos(112, 84)]
+    );
+}
+
+#[test]
+fn rotate() {
+    let mut hitobjects = hitobjects();
+    hitobjects.rotate(90.0, &TransformOptions::new());
+
+    assert_eq!(
+        positions(&hitobjects),
+        vec![pos(348, 36), pos(148, 436), pos(148, 336)]
+    );
+
+    let mut hitobjects = self::hitobjects();
+    hitobjects.rotate(90.0, &TransformOptions::new().clamp(true));
+
+    // the slider is moved up with its head, keeping its shape
+    assert_eq!(
+        positions(&hitobjects),
+        vec![pos(348, 36), pos(148, 384), pos(148, 284)]
+    );
+}
+
+#[test]
+fn scale() {
+    let mut hitobjects = hitobjects();
+    hitobjects.scale(dec!(0.5), &TransformOptions::new());
+
+    assert_eq!(
+        positions(&hitobjects),
+        vec![pos(178, 146), pos(378, 246), pos(328, 246)]
+    );
+
+    match &hitobjects.0[1].obj_params {
+        HitObjectParams::Slider(params) => assert_eq!(params.length, dec!(50)),
+        _ => unreachable!(),
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{
+    osu_file::{OsuFile, SectionName},
+    transform::AudioLeadInChange,
+};
+
+#[test]
+fn ensure_audio_lead_in() {
+    let mut osu_file = "osu file format v14
+
+[General]
+AudioLeadIn: 500
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+256,192,800,1,0,0:0:0:0:"
+        .parse::<OsuFile>()
+        .unwrap();
+
+    assert_eq!(osu_file.first_object_gap(), Some(1300));
+    assert_eq!(osu_file.ensure_audio_lead_in(1000), None);
+
+    assert_eq!(
+        osu_file.ensure_audio_lead_in(2000),
+        Some(AudioLeadInChange {
+            previous_lead_in: 500,
+            lead_in: 1200,
+            previous_gap: 1300,
+            suggested_shift: 700,
+        })
+    );
+    assert!(osu_file.to_string().contains("AudioLeadIn: 1200"));
+    assert!(osu_file.dirty_sections().contains(&SectionName::General));
+    assert_eq!(osu_file.first_object_gap(), Some(2000));
+
+    let mut osu_file = "osu file format v14
+
+[HitObjects]
+256,192,0,1,0,0:0:0:0:"
+        .parse::<OsuFile>()
+        .unwrap();
+    assert_eq!(
+        osu_file.ensure_audio_lead_in(2000).map(|c| c.lead_in),
+        Some(2000)
+    );
+    assert_eq!(osu_file.first_object_gap(), Some(2000));
+
+    // a gap that's far too short doesn't overflow
+    let mut osu_file = "osu file format v14
+
+[General]
+AudioLeadIn: -2147483648
+
+[HitObjects]
+256,192,0,1,0,0:0:0:0:"
+        .parse::<OsuFile>()
+        .unwrap();
+    assert_eq!(
+        osu_file.ensure_audio_lead_in(i32::MAX).map(|c| c.lead_in),
+        Some(-1)
+    );
+}
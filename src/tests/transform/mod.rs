@@ -0,0 +1,7 @@
+mod anonymize;
+mod geometry;
+mod lead_in;
+mod replace_text;
+mod retime;
+mod slider_length;
+mod submission;
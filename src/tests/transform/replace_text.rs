@@ -0,0 +1,98 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    osu_file::{OsuFile, SectionName},
+    transform::{ReplaceScope, ReplaceTarget, TextMatcher, TextReplacement},
+};
+
+const OSU_FILE: &str = "osu file format v14
+
+[General]
+AudioFilename: Audio.mp3
+
+[Metadata]
+Title:Old Song
+Tags:old song-old
+
+[Events]
+0,0,\"audio_bg.jpg\",0,0
+Sprite,Foreground,Centre,\"sb/audio.png\",320,240
+";
+
+#[test]
+fn replace_text_scopes() {
+    let mut osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+    let matcher = TextMatcher::new("audio").case_sensitive(false);
+
+    let report = osu_file.replace_text(&matcher, "music", ReplaceScope::new().file_paths(true));
+
+    assert_eq!(
+        report.replacements,
+        vec![
+            TextReplacement {
+                target: ReplaceTarget::Field {
+                    section: SectionName::General,
+                    name: "AudioFilename",
+                },
+                before: "Audio.mp3".to_string(),
+                after: "music.mp3".to_string(),
+            },
+            TextReplacement {
+                target: ReplaceTarget::Event(0),
+                before: "audio_bg.jpg".to_string(),
+                after: "music_bg.jpg".to_string(),
+            },
+        ]
+    );
+
+    let report = osu_file.replace_text(
+        &matcher,
+        "music",
+        ReplaceScope::new().storyboard_paths(true),
+    );
+    assert_eq!(report.replacements.len(), 1);
+    assert!(osu_file.to_string().contains("\"sb/music.png\""));
+}
+
+#[test]
+fn replace_text_metadata() {
+    let mut osu_file = OSU_FILE.parse::<OsuFile>().unwrap();
+
+    let report = osu_file.replace_text(
+        &TextMatcher::new("old"),
+        "new",
+        ReplaceScope::new().metadata(true),
+    );
+
+    assert_eq!(
+        report.replacements,
+        vec![TextReplacement {
+            target: ReplaceTarget::Field {
+                section: SectionName::Metadata,
+                name: "Tags",
+            },
+            before: "old song-old".to_string(),
+            after: "new song-new".to_string(),
+        }]
+    );
+    // case sensitive by default
+    assert!(osu_file.to_string().contains("Title:Old Song"));
+
+    let whole = TextMatcher::new("song-new").whole_value(true);
+    let report = osu_file.replace_text(&whole, "song", ReplaceScope::all());
+    assert_eq!(report.replacements.len(), 1);
+    assert!(osu_file.to_string().contains("Tags:new song"));
+}
+
+#[test]
+fn text_matcher() {
+    let matcher = TextMatcher::new("BG").case_sensitive(false);
+
+    assert_eq!(
+        matcher.replace("bg/Bg.jpg", "x"),
+        Some("x/x.jpg".to_string())
+    );
+    assert_eq!(matcher.replace("cover.jpg", "x"), None);
+    assert_eq!(matcher.whole_value(true).replace("bg/Bg.jpg", "x"), None);
+    assert_eq!(TextMatcher::new("").replace("bg.jpg", "x"), None);
+}
@@ -0,0 +1,197 @@
+use std::path::PathBuf;
+
+use pretty_assertions::assert_eq;
+use rust_decimal_macros::dec;
+
+use crate::{
+    osu_file::{events::storyboard::sprites::Layer, OsuFile},
+    transform::{RetimeOptions, StoryboardExclusion},
+};
+
+const BEATMAP: &str = r#"osu file format v14
+
+[General]
+AudioFilename: audio.mp3
+AudioLeadIn: 1000
+PreviewTime: 3000
+
+[Editor]
+Bookmarks: 1000,2000
+
+[Events]
+0,0,"bg.jpg",0,0
+ F,0,1000,2000,0,1
+2,1000,2000
+Sprite,Foreground,Centre,"sb/a.png",320,240
+ F,0,1000,2000,0,1
+ L,1000,2
+  M,0,0,500,0,0,100,100
+Sprite,Overlay,Centre,"sb/credits.png",320,240
+ F,0,1000,2000,0,1
+Sprite,Background,Centre,"sb/intro.png",320,240
+ F,0,1000,2000,0,1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+1000,-50,4,2,0,100,0,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+256,192,2000,12,0,3000,0:0:0:0:"#;
+
+#[test]
+fn change_rate() {
+    let mut osu_file = BEATMAP.parse::<OsuFile>().unwrap();
+
+    osu_file
+        .change_rate(dec!(2), &RetimeOptions::new())
+        .unwrap();
+
+    let expected = r#"osu file format v14
+
+[General]
+AudioFilename: audio.mp3
+AudioLeadIn: 500
+PreviewTime: 1500
+
+[Editor]
+Bookmarks: 500,1000
+
+[Events]
+0,0,"bg.jpg",0,0
+ F,0,1000,2000,0,1
+2,500,1000
+Sprite,Foreground,Centre,"sb/a.png",320,240
+ F,0,1000,2000,0,1
+ L,1000,2
+  M,0,0,500,0,0,100,100
+Sprite,Overlay,Centre,"sb/credits.png",320,240
+ F,0,1000,2000,0,1
+Sprite,Background,Centre,"sb/intro.png",320,240
+ F,0,1000,2000,0,1
+
+[TimingPoints]
+0,250,4,2,0,100,1,0
+500,-50,4,2,0,100,0,0
+
+[HitObjects]
+256,192,500,1,0,0:0:0:0:
+256,192,1000,12,0,1500,0:0:0:0:"#;
+
+    assert_eq!(expected, osu_file.to_string());
+}
+
+#[test]
+fn change_rate_scale_storyboard() {
+    let mut osu_file = BEATMAP.parse::<OsuFile>().unwrap();
+    let options = RetimeOptions::new()
+        .scale_storyboard(true)
+        .exclude(StoryboardExclusion::Layer(Layer::Background))
+        .exclude(StoryboardExclusion::FilePath(PathBuf::from(
+            "sb/credits.png",
+        )));
+
+    osu_file.change_rate(dec!(2), &options).unwrap();
+
+    let events = osu_file.to_string();
+    let events = events
+        .split("[Events]\n")
+        .nth(1)
+        .and_then(|s| s.split("\n\n").next())
+        .unwrap();
+
+    let expected = r#"0,0,"bg.jpg",0,0
+ F,0,500,1000,0,1
+2,500,1000
+Sprite,Foreground,Centre,"sb/a.png",320,240
+ F,0,500,1000,0,1
+ L,500,2
+  M,0,0,250,0,0,100,100
+Sprite,Overlay,Centre,"sb/credits.png",320,240
+ F,0,1000,2000,0,1
+Sprite,Background,Centre,"sb/intro.png",320,240
+ F,0,1000,2000,0,1"#;
+
+    assert_eq!(expected, events);
+}
+
+#[test]
+fn change_rate_rounding() {
+    let mut osu_file = BEATMAP.parse::<OsuFile>().unwrap();
+
+    osu_file
+        .change_rate(dec!(1.5), &RetimeOptions::new())
+        .unwrap();
+
+    let hitobjects = osu_file.hitobjects.unwrap();
+    assert_eq!(667, hitobjects.0[0].time);
+    assert_eq!(1333, hitobjects.0[1].time);
+
+    assert!(BEATMAP
+        .parse::<OsuFile>()
+        .unwrap()
+        .change_rate(dec!(0), &RetimeOptions::new())
+        .is_err());
+}
+
+const TRIGGERS: &str = r#"osu file format v14
+
+[Events]
+Sprite,Foreground,Centre,"sb/a.png",320,240
+ L,1000,2
+  T,HitSoundClap,1,301,2
+   F,0,0,100,1,0
+ T,HitSoundClap,1000,2000
+  F,0,0,100,1,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:"#;
+
+fn events(osu_file: &OsuFile) -> String {
+    let s = osu_file.to_string();
+
+    s.split("[Events]\n")
+        .nth(1)
+        .and_then(|s| s.split("\n\n").next())
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn change_rate_triggers_in_loops() {
+    let mut osu_file = TRIGGERS.parse::<OsuFile>().unwrap();
+
+    osu_file
+        .change_rate(dec!(1.5), &RetimeOptions::new().scale_storyboard(true))
+        .unwrap();
+
+    // the window of the nested trigger is relative to the loop, so it's retimed from 1001 ~ 1301 and not 1 ~ 301
+    let expected = r#"Sprite,Foreground,Centre,"sb/a.png",320,240
+ L,667,2
+  T,HitSoundClap,0,200,2
+   F,0,0,67,1,0
+ T,HitSoundClap,667,1333
+  F,0,0,67,1,0"#;
+
+    assert_eq!(expected, events(&osu_file));
+}
+
+#[test]
+fn shift_times() {
+    let mut osu_file = TRIGGERS.parse::<OsuFile>().unwrap();
+
+    osu_file.shift_times(500, &RetimeOptions::new().scale_storyboard(true));
+
+    let expected = r#"Sprite,Foreground,Centre,"sb/a.png",320,240
+ L,1500,2
+  T,HitSoundClap,1,301,2
+   F,0,0,100,1,0
+ T,HitSoundClap,1500,2500
+  F,0,0,100,1,0"#;
+
+    assert_eq!(expected, events(&osu_file));
+    assert_eq!(1500, osu_file.hitobjects.as_ref().unwrap().0[0].time);
+
+    osu_file.shift_times(-2000, &RetimeOptions::new());
+    assert_eq!(0, osu_file.hitobjects.unwrap().0[0].time);
+}
@@ -0,0 +1,71 @@
+use pretty_assertions::assert_eq;
+use rust_decimal_macros::dec;
+
+use crate::osu_file::{
+    hitobjects::{CurveType, HitObjectParams, SlideParams},
+    OsuFile,
+};
+
+fn slider_lengths(osu_file: &OsuFile) -> Vec<rust_decimal::Decimal> {
+    osu_file
+        .hitobjects
+        .as_ref()
+        .unwrap()
+        .0
+        .iter()
+        .filter_map(|h| match &h.obj_params {
+            HitObjectParams::Slider(params) => Some(params.length),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn rescale_length_for_new_sv() {
+    let mut params = SlideParams {
+        curve_type: CurveType::Linear,
+        curve_points: Vec::new(),
+        slides: 1,
+        length: dec!(100),
+        edge_sounds: Vec::new(),
+        edge_sets: Vec::new(),
+    };
+
+    params.rescale_length_for_new_sv(dec!(1), dec!(2));
+    assert_eq!(params.length, dec!(200));
+
+    params.rescale_length_for_new_sv(dec!(0), dec!(2));
+    assert_eq!(params.length, dec!(200));
+}
+
+#[test]
+fn preserve_slider_durations() {
+    let before = "osu file format v14
+
+[Difficulty]
+SliderMultiplier:1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+1000,-100,4,2,0,100,0,0
+
+[HitObjects]
+0,0,0,2,0,L|100:0,1,100
+256,192,500,1,0,0:0:0:0:
+0,0,1000,2,0,L|100:0,1,100"
+        .parse::<OsuFile>()
+        .unwrap();
+
+    let mut osu_file = before.clone();
+    osu_file.timing_points.as_mut().unwrap().0[1].beat_length = dec!(-50);
+
+    assert_eq!(osu_file.preserve_slider_durations(&before), 1);
+    assert_eq!(slider_lengths(&osu_file), vec![dec!(100), dec!(200)]);
+    assert_eq!(osu_file.preserve_slider_durations(&osu_file.clone()), 0);
+    // tiny beat lengths clamp the slider velocity instead of overflowing
+    let mut osu_file = before.clone();
+    osu_file.timing_points.as_mut().unwrap().0[1].beat_length =
+        dec!(-0.0000000000000000000000000001);
+    assert_eq!(osu_file.preserve_slider_durations(&before), 1);
+    assert_eq!(slider_lengths(&osu_file), vec![dec!(100), dec!(1000)]);
+}
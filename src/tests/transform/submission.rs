@@ -0,0 +1,145 @@
+use pretty_assertions::assert_eq;
+use rust_decimal_macros::dec;
+
+use crate::{
+    lint::LintRule,
+    osu_file::{FieldValue, OsuFile, SectionName, FIELD_MIGRATIONS},
+    transform::{RemovedField, SubmissionError, SubmissionReport},
+};
+
+#[test]
+fn prepare_for_submission() {
+    let i = r#"osu file format v14
+
+[General]
+AudioFilename: audio.mp3
+AudioHash: abc
+StackLeniency: 0.70
+
+[Difficulty]
+HPDrainRate:5.50
+CircleSize:11
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.40
+SliderTickRate:1
+
+[Events]
+0,0,"bg.jpg",0,0
+0,0,"bg2.jpg",0,0
+
+[TimingPoints]
+1000,500,4,2,0,100,1,0
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+256,192,2000,1,0,0:0:0:0:
+100,100,1000,1,0,0:0:0:0:"#;
+    let mut osu_file = i.parse::<OsuFile>().unwrap();
+
+    let report = osu_file.prepare_for_submission().unwrap();
+
+    assert_eq!(
+        SubmissionReport {
+            removed: vec![RemovedField {
+                section: SectionName::General,
+                key: "AudioHash"
+            }],
+            moved: Vec::new(),
+            clamped: vec!["CircleSize"],
+            removed_backgrounds: 1,
+            sorted_hitobjects: true,
+            sorted_timing_points: true,
+        },
+        report
+    );
+
+    let expected = r#"osu file format v14
+
+[General]
+AudioFilename: audio.mp3
+StackLeniency: 0.7
+
+[Difficulty]
+HPDrainRate:5.5
+CircleSize:10
+OverallDifficulty:8
+ApproachRate:9
+SliderMultiplier:1.4
+SliderTickRate:1
+
+[Events]
+0,0,"bg.jpg",0,0
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+1000,500,4,2,0,100,1,0
+
+[HitObjects]
+100,100,1000,1,0,0:0:0:0:
+256,192,2000,1,0,0:0:0:0:"#;
+    assert_eq!(expected, osu_file.to_string());
+
+    // already normalized
+    assert!(osu_file.prepare_for_submission().unwrap().is_empty());
+}
+
+#[test]
+fn prepare_for_submission_lint_errors() {
+    let i = r#"osu file format v14
+
+[Events]
+0,0,"../bg.jpg",0,0"#;
+    let mut osu_file = i.parse::<OsuFile>().unwrap();
+    let original = osu_file.clone();
+
+    let err = osu_file.prepare_for_submission().unwrap_err();
+
+    assert!(matches!(
+        err,
+        SubmissionError::LintErrors(issues) if issues[0].rule == LintRule::ParentTraversal
+    ));
+    assert_eq!(original, osu_file);
+}
+
+#[test]
+fn prepare_for_submission_editor_fields() {
+    let i = "osu file format v14
+
+[General]
+AudioFilename: audio.mp3
+
+[Editor]
+DistanceSpacing: 1.5";
+    let mut osu_file = i.parse::<OsuFile>().unwrap();
+    // parsing already moves them, so they're only in `[General]` when set after
+    let general = osu_file.general_mut().as_mut().unwrap();
+    general
+        .set("EditorBookmarks", FieldValue::IntegerList(vec![100, 200]))
+        .unwrap();
+    general
+        .set("EditorDistanceSpacing", FieldValue::Decimal(dec!(1.2)))
+        .unwrap();
+
+    let report = osu_file.prepare_for_submission().unwrap();
+
+    assert_eq!(report.moved, vec![FIELD_MIGRATIONS[0]]);
+    assert_eq!(
+        report.removed,
+        vec![RemovedField {
+            section: SectionName::General,
+            key: "EditorDistanceSpacing"
+        }]
+    );
+
+    let editor = osu_file.editor.as_ref().unwrap();
+    assert_eq!(
+        editor.get("Bookmarks"),
+        Some(FieldValue::IntegerList(vec![100, 200]))
+    );
+    assert_eq!(
+        editor.get("DistanceSpacing"),
+        Some(FieldValue::Decimal(dec!(1.5)))
+    );
+    assert!(!osu_file.to_string().contains("EditorBookmarks"));
+}
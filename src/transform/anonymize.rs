@@ -0,0 +1,76 @@
+//! Module for removing the information that identifies who made a beatmap.
+
+use crate::osu_file::{OsuFile, SectionName};
+
+/// A field removed by [`OsuFile::anonymize`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct RemovedField {
+    /// Section the field was in.
+    pub section: SectionName,
+    /// Key of the field as written in the .osu file.
+    pub key: &'static str,
+}
+
+/// Fields removed by [`OsuFile::anonymize`], in the order they appear in the .osu file.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct AnonymizeReport {
+    pub removed: Vec<RemovedField>,
+}
+
+impl AnonymizeReport {
+    /// Returns `true` if nothing was removed.
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty()
+    }
+
+    fn take<T>(&mut self, field: &mut Option<T>, section: SectionName, key: &'static str) {
+        if field.take().is_some() {
+            self.removed.push(RemovedField { section, key });
+        }
+    }
+}
+
+impl OsuFile {
+    /// Removes the fields that identify who made the beatmap and where it's from.
+    /// - Removes `SkinPreference`, the bookmarks in `EditorBookmarks` of old files and in `Bookmarks`, `Creator`, `Source`, `Tags`, `BeatmapID` and `BeatmapSetID`.
+    /// - Gameplay data and the rest of the metadata such as the title and the difficulty name are kept.
+    /// - Returns which fields were removed, fields that were already missing aren't included.
+    pub fn anonymize(&mut self) -> AnonymizeReport {
+        let mut report = AnonymizeReport::default();
+
+        if let Some(general) = &mut self.general {
+            report.take(
+                &mut general.skin_preference,
+                SectionName::General,
+                "SkinPreference",
+            );
+            report.take(
+                &mut general.editor_bookmarks,
+                SectionName::General,
+                "EditorBookmarks",
+            );
+        }
+
+        if let Some(editor) = &mut self.editor {
+            report.take(&mut editor.bookmarks, SectionName::Editor, "Bookmarks");
+        }
+
+        if let Some(metadata) = &mut self.metadata {
+            report.take(&mut metadata.creator, SectionName::Metadata, "Creator");
+            report.take(&mut metadata.source, SectionName::Metadata, "Source");
+            report.take(&mut metadata.tags, SectionName::Metadata, "Tags");
+            report.take(&mut metadata.beatmap_id, SectionName::Metadata, "BeatmapID");
+            report.take(
+                &mut metadata.beatmap_set_id,
+                SectionName::Metadata,
+                "BeatmapSetID",
+            );
+        }
+
+        for removed in &report.removed {
+            self.mark_dirty(removed.section);
+        }
+
+        report
+    }
+}
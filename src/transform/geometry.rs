@@ -0,0 +1,143 @@
+//! Module for mirroring, rotating and scaling hitobjects on the playfield.
+//! - Slider anchors are moved with the objects, so sliders keep their shape.
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use rust_decimal_macros::dec;
+
+use crate::osu_file::{
+    hitobjects::{HitObject, HitObjectParams},
+    HitObjects, Position,
+};
+
+/// Width of the playfield in `osu!pixels`.
+pub const PLAYFIELD_WIDTH: Decimal = dec!(512);
+/// Height of the playfield in `osu!pixels`.
+pub const PLAYFIELD_HEIGHT: Decimal = dec!(384);
+
+const PLAYFIELD_CENTRE_X: Decimal = dec!(256);
+const PLAYFIELD_CENTRE_Y: Decimal = dec!(192);
+
+/// Options used by [`HitObjects::rotate`] and [`HitObjects::scale`].
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TransformOptions {
+    /// Moves objects that end up outside of the playfield back to its edge.
+    /// - Sliders are moved as a whole by the same distance as their head, so they keep their shape.
+    pub clamp: bool,
+}
+
+impl TransformOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets [`clamp`][Self::clamp].
+    pub fn clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+}
+
+fn round(value: Decimal) -> Decimal {
+    value.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+}
+
+/// Moves the object and its slider anchors with `f`.
+fn transform_object<F>(obj: &mut HitObject, f: &F)
+where
+    F: Fn(Position) -> Position,
+{
+    obj.position = f(obj.position);
+
+    if let HitObjectParams::Slider(params) = &mut obj.obj_params {
+        for point in &mut params.curve_points {
+            point.0 = f(point.0);
+        }
+    }
+}
+
+/// Moves the object back into the playfield, moving the slider anchors by the same distance.
+fn clamp_object(obj: &mut HitObject) {
+    let offset = Position::new(
+        obj.position.x.clamp(Decimal::ZERO, PLAYFIELD_WIDTH) - obj.position.x,
+        obj.position.y.clamp(Decimal::ZERO, PLAYFIELD_HEIGHT) - obj.position.y,
+    );
+
+    if offset.x.is_zero() && offset.y.is_zero() {
+        return;
+    }
+
+    transform_object(obj, &|p: Position| {
+        Position::new(p.x + offset.x, p.y + offset.y)
+    });
+}
+
+impl HitObjects {
+    fn transform<F>(&mut self, f: F, clamp: bool)
+    where
+        F: Fn(Position) -> Position,
+    {
+        for obj in &mut self.0 {
+            transform_object(obj, &f);
+
+            if clamp {
+                clamp_object(obj);
+            }
+        }
+    }
+
+    /// Mirrors the objects from left to right.
+    pub fn mirror_horizontal(&mut self) {
+        self.transform(|p| Position::new(PLAYFIELD_WIDTH - p.x, p.y), false);
+    }
+
+    /// Mirrors the objects from top to bottom, which is what hard rock does.
+    pub fn mirror_vertical(&mut self) {
+        self.transform(|p| Position::new(p.x, PLAYFIELD_HEIGHT - p.y), false);
+    }
+
+    /// Rotates the objects clockwise by `degrees` around the centre of the playfield.
+    /// - Positions are rounded to whole `osu!pixels`.
+    pub fn rotate(&mut self, degrees: f64, options: &TransformOptions) {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let (sin, cos) = match (Decimal::try_from(sin), Decimal::try_from(cos)) {
+            (Ok(sin), Ok(cos)) => (sin, cos),
+            _ => return,
+        };
+
+        // the y axis points down, so this goes clockwise on the screen
+        self.transform(
+            |p| {
+                let (x, y) = (p.x - PLAYFIELD_CENTRE_X, p.y - PLAYFIELD_CENTRE_Y);
+
+                Position::new(
+                    round(PLAYFIELD_CENTRE_X + x * cos - y * sin),
+                    round(PLAYFIELD_CENTRE_Y + x * sin + y * cos),
+                )
+            },
+            options.clamp,
+        );
+    }
+
+    /// Scales the objects by `factor` from the centre of the playfield.
+    /// - Positions are rounded to whole `osu!pixels`.
+    /// - Slider lengths are scaled with the path, which also scales their duration.
+    pub fn scale(&mut self, factor: Decimal, options: &TransformOptions) {
+        self.transform(
+            |p| {
+                Position::new(
+                    round(PLAYFIELD_CENTRE_X + (p.x - PLAYFIELD_CENTRE_X) * factor),
+                    round(PLAYFIELD_CENTRE_Y + (p.y - PLAYFIELD_CENTRE_Y) * factor),
+                )
+            },
+            options.clamp,
+        );
+
+        for obj in &mut self.0 {
+            if let HitObjectParams::Slider(params) = &mut obj.obj_params {
+                params.length *= factor.abs();
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Module for making sure there's enough time before the first hitobject for the player to get ready.
+
+use crate::osu_file::{Integer, OsuFile, SectionName};
+
+/// Change made by [`OsuFile::ensure_audio_lead_in`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct AudioLeadInChange {
+    /// `AudioLeadIn` before the change, where a missing field is `0`.
+    pub previous_lead_in: Integer,
+    /// `AudioLeadIn` after the change.
+    pub lead_in: Integer,
+    /// Time before the first hitobject before the change, counting `AudioLeadIn`.
+    pub previous_gap: Integer,
+    /// How much later every time in the beatmap would have to be moved to get the same gap without changing `AudioLeadIn`.
+    /// - Only possible if the audio has that much silence added to its start.
+    pub suggested_shift: Integer,
+}
+
+impl OsuFile {
+    /// Time before the first hitobject in milliseconds, counting `AudioLeadIn`.
+    /// - Returns `None` if there are no hitobjects.
+    pub fn first_object_gap(&self) -> Option<Integer> {
+        let first_time = self.hitobjects.as_ref()?.0.iter().map(|h| h.time).min()?;
+        let lead_in = self.audio_lead_in_or_default();
+
+        Some(
+            Integer::try_from(first_time)
+                .unwrap_or(Integer::MAX)
+                .saturating_add(lead_in),
+        )
+    }
+
+    fn audio_lead_in_or_default(&self) -> Integer {
+        self.general
+            .as_ref()
+            .and_then(|g| g.audio_lead_in.clone())
+            .map_or(0, Into::into)
+    }
+
+    /// Increases `AudioLeadIn` so there are at least `min_ms` milliseconds before the first hitobject, as the ranking criteria require.
+    /// - Returns `None` without changing anything if the gap is already long enough, or there are no hitobjects.
+    /// - Adds a `[General]` section if there isn't one.
+    pub fn ensure_audio_lead_in(&mut self, min_ms: Integer) -> Option<AudioLeadInChange> {
+        let previous_gap = self.first_object_gap()?;
+        if previous_gap >= min_ms {
+            return None;
+        }
+
+        let previous_lead_in = self.audio_lead_in_or_default();
+        let suggested_shift = min_ms.saturating_sub(previous_gap);
+        let lead_in = previous_lead_in.saturating_add(suggested_shift);
+
+        self.general
+            .get_or_insert_with(crate::osu_file::General::new)
+            .audio_lead_in = Some(lead_in.into());
+        self.mark_dirty(SectionName::General);
+
+        Some(AudioLeadInChange {
+            previous_lead_in,
+            lead_in,
+            previous_gap,
+            suggested_shift,
+        })
+    }
+}
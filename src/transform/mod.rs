@@ -0,0 +1,16 @@
+//! Module for transforms that edit an .osu file in place.
+
+pub mod anonymize;
+pub mod geometry;
+pub mod lead_in;
+pub mod replace_text;
+pub mod retime;
+pub mod slider_length;
+pub mod submission;
+
+pub use anonymize::*;
+pub use geometry::*;
+pub use lead_in::*;
+pub use replace_text::*;
+pub use retime::*;
+pub use submission::*;
@@ -0,0 +1,356 @@
+//! Module for replacing text in the metadata and the file paths, such as after the audio or background file is renamed.
+
+use std::path::PathBuf;
+
+use crate::osu_file::{
+    events::{storyboard::sprites::ObjectType, Event, Events},
+    FieldValue, FilePath, OsuFile, SectionName,
+};
+
+/// `Metadata` fields that are text.
+const METADATA_FIELDS: [&str; 8] = [
+    "Title",
+    "TitleUnicode",
+    "Artist",
+    "ArtistUnicode",
+    "Creator",
+    "Version",
+    "Source",
+    "Tags",
+];
+
+/// Text to find with [`OsuFile::replace_text`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TextMatcher {
+    /// The text to find, which never matches if it's empty.
+    pub pattern: String,
+    /// Matches the text with the same case only, which is the default.
+    pub case_sensitive: bool,
+    /// Only matches the whole value, so `bg.jpg` doesn't match `sb/bg.jpg`.
+    pub whole_value: bool,
+}
+
+impl TextMatcher {
+    /// Creates a case sensitive matcher for `pattern` anywhere in the value.
+    pub fn new<S: Into<String>>(pattern: S) -> Self {
+        Self {
+            pattern: pattern.into(),
+            case_sensitive: true,
+            whole_value: false,
+        }
+    }
+
+    /// Sets [`case_sensitive`][Self::case_sensitive].
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Sets [`whole_value`][Self::whole_value].
+    pub fn whole_value(mut self, whole_value: bool) -> Self {
+        self.whole_value = whole_value;
+        self
+    }
+
+    /// Length in bytes of the match at the start of `text`.
+    fn match_len(&self, text: &str) -> Option<usize> {
+        if self.case_sensitive {
+            return text
+                .starts_with(self.pattern.as_str())
+                .then_some(self.pattern.len());
+        }
+
+        let mut chars = text.char_indices();
+        for p in self.pattern.chars() {
+            let (_, c) = chars.next()?;
+            if !c.to_lowercase().eq(p.to_lowercase()) {
+                return None;
+            }
+        }
+
+        Some(chars.next().map_or(text.len(), |(i, _)| i))
+    }
+
+    /// Replaces each match in `text` with `replacement`.
+    /// - Returns `None` if the text isn't changed.
+    pub fn replace(&self, text: &str, replacement: &str) -> Option<String> {
+        if self.pattern.is_empty() {
+            return None;
+        }
+
+        let replaced = if self.whole_value {
+            if self.match_len(text) != Some(text.len()) {
+                return None;
+            }
+            replacement.to_string()
+        } else {
+            let mut replaced = String::with_capacity(text.len());
+            let mut rest = text;
+            while let Some(c) = rest.chars().next() {
+                match self.match_len(rest) {
+                    Some(len) => {
+                        replaced.push_str(replacement);
+                        rest = &rest[len..];
+                    }
+                    None => {
+                        replaced.push(c);
+                        rest = &rest[c.len_utf8()..];
+                    }
+                }
+            }
+            replaced
+        };
+
+        (replaced != text).then_some(replaced)
+    }
+}
+
+/// Where [`OsuFile::replace_text`] replaces text.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ReplaceScope {
+    /// The text fields of the `[Metadata]` section, such as `Title` and `Tags`.
+    /// - Each tag is matched on its own.
+    pub metadata: bool,
+    /// `AudioFilename`, and the files of the background and video events.
+    pub file_paths: bool,
+    /// The files of the storyboard sprites, animations and samples, in the .osu file and its .osb file.
+    pub storyboard_paths: bool,
+}
+
+impl ReplaceScope {
+    /// Creates a scope with nothing in it.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a scope with the metadata and all file paths.
+    pub fn all() -> Self {
+        Self {
+            metadata: true,
+            file_paths: true,
+            storyboard_paths: true,
+        }
+    }
+
+    /// Sets [`metadata`][Self::metadata].
+    pub fn metadata(mut self, metadata: bool) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Sets [`file_paths`][Self::file_paths].
+    pub fn file_paths(mut self, file_paths: bool) -> Self {
+        self.file_paths = file_paths;
+        self
+    }
+
+    /// Sets [`storyboard_paths`][Self::storyboard_paths].
+    pub fn storyboard_paths(mut self, storyboard_paths: bool) -> Self {
+        self.storyboard_paths = storyboard_paths;
+        self
+    }
+}
+
+/// What was changed by a replacement.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ReplaceTarget {
+    /// Field of a key-value section, by the name written in the file.
+    Field {
+        section: SectionName,
+        name: &'static str,
+    },
+    /// Event, by its index in the `[Events]` section.
+    Event(usize),
+    /// Event of the .osb file, by its index in its `[Events]` section.
+    OsbEvent(usize),
+}
+
+/// A value changed by [`OsuFile::replace_text`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct TextReplacement {
+    pub target: ReplaceTarget,
+    /// The value before the replacement, with the tags joined by spaces.
+    pub before: String,
+    /// The value after the replacement, with the tags joined by spaces.
+    pub after: String,
+}
+
+/// Values changed by [`OsuFile::replace_text`], in the order they appear in the file.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct ReplaceReport {
+    pub replacements: Vec<TextReplacement>,
+}
+
+impl ReplaceReport {
+    /// Returns `true` if nothing was changed.
+    pub fn is_empty(&self) -> bool {
+        self.replacements.is_empty()
+    }
+
+    fn push(&mut self, target: ReplaceTarget, before: String, after: String) {
+        self.replacements.push(TextReplacement {
+            target,
+            before,
+            after,
+        });
+    }
+}
+
+/// Replaces the text in the file paths of the events, where `file_paths` and `storyboard_paths` choose which events are included.
+fn replace_event_paths(
+    events: &mut Events,
+    matcher: &TextMatcher,
+    replacement: &str,
+    scope: ReplaceScope,
+    target: fn(usize) -> ReplaceTarget,
+    report: &mut ReplaceReport,
+) {
+    for (i, event) in events.0.iter_mut().enumerate() {
+        let (path, storyboard): (&mut FilePath, bool) = match event {
+            Event::Background(bg) => (&mut bg.file_name, false),
+            Event::Video(video) => (&mut video.file_name, false),
+            Event::SpriteLegacy(sprite) => (&mut sprite.file_name, true),
+            Event::AnimationLegacy(animation) => (&mut animation.file_name, true),
+            Event::SampleLegacy(sample) => (&mut sample.file_name, true),
+            Event::StoryboardObject(object) => match &mut object.object_type {
+                ObjectType::Sprite(sprite) => (&mut sprite.filepath, true),
+                ObjectType::Animation(animation) => (&mut animation.filepath, true),
+            },
+            Event::AudioSample(sample) => (&mut sample.filepath, true),
+            _ => continue,
+        };
+
+        let included = if storyboard {
+            scope.storyboard_paths
+        } else {
+            scope.file_paths
+        };
+        if !included {
+            continue;
+        }
+
+        let before = path.get().to_string_lossy().into_owned();
+        if let Some(after) = matcher.replace(&before, replacement) {
+            path.set(&after);
+            report.push(target(i), before, after);
+        }
+    }
+}
+
+impl OsuFile {
+    /// Replaces the text matched by `matcher` with `replacement` in the values chosen by `scope`.
+    /// - Paths are matched as they are written, so `/` and `\` are different.
+    /// - Returns each value that was changed.
+    pub fn replace_text(
+        &mut self,
+        matcher: &TextMatcher,
+        replacement: &str,
+        scope: ReplaceScope,
+    ) -> ReplaceReport {
+        let mut report = ReplaceReport::default();
+
+        if scope.file_paths {
+            if let Some(general) = &mut self.general {
+                if let Some(FieldValue::Path(path)) = general.get("AudioFilename") {
+                    let before = path.to_string_lossy().into_owned();
+                    if let Some(after) = matcher.replace(&before, replacement) {
+                        general
+                            .set("AudioFilename", FieldValue::Path(PathBuf::from(&after)))
+                            .unwrap();
+                        report.push(
+                            ReplaceTarget::Field {
+                                section: SectionName::General,
+                                name: "AudioFilename",
+                            },
+                            before,
+                            after,
+                        );
+                    }
+                }
+            }
+        }
+
+        if scope.metadata {
+            if let Some(metadata) = &mut self.metadata {
+                for name in METADATA_FIELDS {
+                    let (before, after, value) = match metadata.get(name) {
+                        Some(FieldValue::String(before)) => {
+                            match matcher.replace(&before, replacement) {
+                                Some(after) => (before, after.clone(), FieldValue::String(after)),
+                                None => continue,
+                            }
+                        }
+                        Some(FieldValue::StringList(tags)) => {
+                            let replaced = tags
+                                .iter()
+                                .map(|tag| {
+                                    matcher
+                                        .replace(tag, replacement)
+                                        .unwrap_or_else(|| tag.clone())
+                                })
+                                .collect::<Vec<_>>();
+                            if replaced == tags {
+                                continue;
+                            }
+                            (
+                                tags.join(" "),
+                                replaced.join(" "),
+                                FieldValue::StringList(replaced),
+                            )
+                        }
+                        _ => continue,
+                    };
+
+                    metadata.set(name, value).unwrap();
+                    report.push(
+                        ReplaceTarget::Field {
+                            section: SectionName::Metadata,
+                            name,
+                        },
+                        before,
+                        after,
+                    );
+                }
+            }
+        }
+
+        if scope.file_paths || scope.storyboard_paths {
+            if let Some(events) = &mut self.events {
+                replace_event_paths(
+                    events,
+                    matcher,
+                    replacement,
+                    scope,
+                    ReplaceTarget::Event,
+                    &mut report,
+                );
+            }
+        }
+
+        if scope.storyboard_paths {
+            if let Some(events) = self.osb.as_mut().and_then(|osb| osb.events.as_mut()) {
+                replace_event_paths(
+                    events,
+                    matcher,
+                    replacement,
+                    ReplaceScope::new().storyboard_paths(true),
+                    ReplaceTarget::OsbEvent,
+                    &mut report,
+                );
+            }
+        }
+
+        for replacement in &report.replacements {
+            match replacement.target {
+                ReplaceTarget::Field { section, .. } => self.mark_dirty(section),
+                ReplaceTarget::Event(_) => self.mark_dirty(SectionName::Events),
+                ReplaceTarget::OsbEvent(_) => (),
+            }
+        }
+
+        report
+    }
+}
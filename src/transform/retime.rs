@@ -0,0 +1,314 @@
+//! Module for changing the playback rate of a beatmap.
+
+use std::path::{Path, PathBuf};
+
+use rust_decimal::{prelude::ToPrimitive, Decimal, RoundingStrategy};
+use thiserror::Error;
+
+use crate::osu_file::{
+    events::{
+        storyboard::{
+            cmds::{Command, CommandProperties},
+            sprites::{Layer, ObjectType},
+        },
+        Event, Events,
+    },
+    hitobjects::HitObjectParams,
+    Integer, OsuFile, SectionName,
+};
+
+/// Storyboard elements that keep their absolute timing when retiming with [`RetimeOptions::scale_storyboard`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StoryboardExclusion {
+    /// Elements in the layer.
+    Layer(Layer),
+    /// Elements using the file, such as an image for the intro credits.
+    FilePath(PathBuf),
+}
+
+/// Options used by [`OsuFile::change_rate`] and [`OsuFile::shift_times`].
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RetimeOptions {
+    /// Retimes the storyboard the same way as the beatmap, keeping it aligned to the music.
+    /// - Times are rounded to the nearest millisecond.
+    /// - If `false`, the storyboard keeps its absolute timing.
+    pub scale_storyboard: bool,
+    /// Storyboard elements that keep their absolute timing even if [`scale_storyboard`][Self::scale_storyboard] is `true`.
+    pub exclusions: Vec<StoryboardExclusion>,
+}
+
+impl RetimeOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets [`scale_storyboard`][Self::scale_storyboard].
+    pub fn scale_storyboard(mut self, scale_storyboard: bool) -> Self {
+        self.scale_storyboard = scale_storyboard;
+        self
+    }
+
+    /// Adds an exclusion to [`exclusions`][Self::exclusions].
+    pub fn exclude(mut self, exclusion: StoryboardExclusion) -> Self {
+        self.exclusions.push(exclusion);
+        self
+    }
+
+    fn is_excluded(&self, layer: Option<Layer>, path: &Path) -> bool {
+        self.exclusions.iter().any(|exclusion| match exclusion {
+            StoryboardExclusion::Layer(excluded) => layer == Some(*excluded),
+            StoryboardExclusion::FilePath(excluded) => excluded == path,
+        })
+    }
+}
+
+/// Error used when the rate given to [`OsuFile::change_rate`] isn't positive.
+#[derive(Debug, Error)]
+#[error("The rate must be positive")]
+pub struct InvalidRate;
+
+struct Retimer {
+    rate: Decimal,
+    offset: Integer,
+}
+
+impl Retimer {
+    fn decimal(&self, value: Decimal) -> Decimal {
+        (value / self.rate).round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+            + Decimal::from(self.offset)
+    }
+
+    fn integer(&self, time: Integer) -> Integer {
+        self.decimal(Decimal::from(time)).to_i32().unwrap_or(time)
+    }
+
+    fn unsigned(&self, time: u32) -> u32 {
+        self.decimal(Decimal::from(time))
+            .max(Decimal::ZERO)
+            .to_u32()
+            .unwrap_or(time)
+    }
+
+    /// Retimes a duration, which is only scaled by the rate.
+    fn duration(&self, duration: Integer) -> Integer {
+        self.integer(duration) - self.integer(0)
+    }
+
+    /// Retimes a time that is relative to the absolute time `base`.
+    /// - The absolute time is retimed and made relative again, so it lands on the same millisecond as it would outside of the loop.
+    fn relative(&self, time: Integer, base: Integer) -> Integer {
+        self.integer(base.saturating_add(time)) - self.integer(base)
+    }
+
+    /// Retimes the commands, where `base` is the absolute time that the command times are relative to.
+    /// - Times in loops are relative to the start of the loop, which is used as the `base` of the commands in them.
+    /// - Times in triggers are relative to when the trigger activates, so they are retimed as durations.
+    /// - The start and end times of triggers are the window where they can activate, and group numbers are kept.
+    fn commands(&self, commands: &mut [Command], base: Option<Integer>) {
+        let time = |time: Integer| match base {
+            Some(base) => self.relative(time, base),
+            None => self.integer(time),
+        };
+
+        for command in commands {
+            let start_time = command.start_time;
+            command.start_time = start_time.map(time);
+
+            match &mut command.properties {
+                CommandProperties::Fade { end_time, .. }
+                | CommandProperties::Move { end_time, .. }
+                | CommandProperties::MoveX { end_time, .. }
+                | CommandProperties::MoveY { end_time, .. }
+                | CommandProperties::Scale { end_time, .. }
+                | CommandProperties::VectorScale { end_time, .. }
+                | CommandProperties::Rotate { end_time, .. }
+                | CommandProperties::Colour { end_time, .. }
+                | CommandProperties::Parameter { end_time, .. } => {
+                    *end_time = end_time.map(time);
+                }
+                CommandProperties::Loop { commands, .. } => {
+                    let start_time = start_time.unwrap_or_default();
+                    let loop_start = match base {
+                        Some(base) => base.saturating_add(start_time),
+                        None => start_time,
+                    };
+                    self.commands(commands, Some(loop_start));
+                }
+                CommandProperties::Trigger {
+                    end_time, commands, ..
+                } => {
+                    *end_time = end_time.map(time);
+                    self.commands(commands, Some(0));
+                }
+            }
+        }
+    }
+
+    fn events(&self, events: &mut Events, options: &RetimeOptions) {
+        let storyboard = |layer: Option<Layer>, path: &Path| {
+            options.scale_storyboard && !options.is_excluded(layer, path)
+        };
+
+        for event in &mut events.0 {
+            let layer = event.storyboard_layer();
+
+            match event {
+                Event::Background(bg) => {
+                    bg.start_time = self.integer(bg.start_time);
+                    if storyboard(layer, bg.file_name.get()) {
+                        self.commands(&mut bg.commands, None);
+                    }
+                }
+                Event::Video(video) => {
+                    video.start_time = self.integer(video.start_time);
+                    if storyboard(layer, video.file_name.get()) {
+                        self.commands(&mut video.commands, None);
+                    }
+                }
+                Event::Break(b) => {
+                    b.start_time = self.integer(b.start_time);
+                    b.end_time = self.integer(b.end_time);
+                }
+                Event::ColourTransformation(colour) if options.scale_storyboard => {
+                    colour.start_time = self.integer(colour.start_time);
+                }
+                Event::SpriteLegacy(sprite) if storyboard(layer, sprite.file_name.get()) => {
+                    self.commands(&mut sprite.commands, None);
+                }
+                Event::AnimationLegacy(animation)
+                    if storyboard(layer, animation.file_name.get()) =>
+                {
+                    self.commands(&mut animation.commands, None);
+                }
+                Event::SampleLegacy(sample) if storyboard(layer, sample.file_name.get()) => {
+                    sample.time = self.decimal(sample.time);
+                    self.commands(&mut sample.commands, None);
+                }
+                Event::StoryboardObject(object) => {
+                    let path = match &object.object_type {
+                        ObjectType::Sprite(sprite) => sprite.filepath.get(),
+                        ObjectType::Animation(animation) => animation.filepath.get(),
+                    };
+
+                    if !storyboard(layer, path) {
+                        continue;
+                    }
+
+                    if let ObjectType::Animation(animation) = &mut object.object_type {
+                        animation.frame_delay /= self.rate;
+                    }
+                    self.commands(&mut object.commands, None);
+                }
+                Event::AudioSample(sample) if storyboard(layer, sample.filepath.get()) => {
+                    sample.time = self.integer(sample.time);
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+impl OsuFile {
+    /// Changes the playback rate of the beatmap, like making a 1.5x rate version of it.
+    /// - A `rate` of `1.5` makes the beatmap play 1.5 times faster, dividing all of the times by `1.5`.
+    /// - Hitobjects, timing points, breaks, bookmarks, `PreviewTime` and `AudioLeadIn` are always retimed, and the BPM is scaled.
+    /// - The storyboard is only retimed with [`RetimeOptions::scale_storyboard`].
+    /// - The audio file isn't changed, so it has to be sped up separately.
+    pub fn change_rate(
+        &mut self,
+        rate: Decimal,
+        options: &RetimeOptions,
+    ) -> Result<(), InvalidRate> {
+        if rate <= Decimal::ZERO {
+            return Err(InvalidRate);
+        }
+
+        self.retime(&Retimer { rate, offset: 0 }, options);
+
+        Ok(())
+    }
+
+    /// Moves the beatmap by `offset` milliseconds, like after adding silence to the start of the audio.
+    /// - Hitobjects, timing points, breaks, bookmarks and `PreviewTime` are always moved, and hitobjects moved before `0` are kept at `0`.
+    /// - The storyboard is only moved with [`RetimeOptions::scale_storyboard`], where the times relative to loops and triggers are kept.
+    pub fn shift_times(&mut self, offset: Integer, options: &RetimeOptions) {
+        self.retime(
+            &Retimer {
+                rate: Decimal::ONE,
+                offset,
+            },
+            options,
+        );
+    }
+
+    fn retime(&mut self, retimer: &Retimer, options: &RetimeOptions) {
+        if let Some(general) = &mut self.general {
+            if let Some(preview_time) = general.preview_time.clone() {
+                let preview_time: Integer = preview_time.into();
+
+                // -1 is used for no preview time
+                if preview_time >= 0 {
+                    general.preview_time = Some(retimer.integer(preview_time).into());
+                }
+            }
+            if let Some(audio_lead_in) = general.audio_lead_in.clone() {
+                general.audio_lead_in = Some(retimer.duration(audio_lead_in.into()).into());
+            }
+        }
+
+        if let Some(editor) = &mut self.editor {
+            if let Some(bookmarks) = editor.bookmarks.clone() {
+                let bookmarks: Vec<Integer> = bookmarks.into();
+                editor.bookmarks = Some(
+                    bookmarks
+                        .into_iter()
+                        .map(|b| retimer.integer(b))
+                        .collect::<Vec<_>>()
+                        .into(),
+                );
+            }
+        }
+
+        if let Some(hitobjects) = &mut self.hitobjects {
+            for hitobject in &mut hitobjects.0 {
+                hitobject.time = retimer.unsigned(hitobject.time);
+
+                match &mut hitobject.obj_params {
+                    HitObjectParams::Spinner { end_time }
+                    | HitObjectParams::OsuManiaHold { end_time } => {
+                        *end_time = retimer.unsigned(*end_time);
+                    }
+                    HitObjectParams::HitCircle | HitObjectParams::Slider(_) => (),
+                }
+            }
+        }
+
+        if let Some(timing_points) = &mut self.timing_points {
+            for timing_point in &mut timing_points.0 {
+                timing_point.time = retimer.integer(timing_point.time);
+
+                if timing_point.uninherited && retimer.rate != Decimal::ONE {
+                    timing_point.beat_length /= retimer.rate;
+                }
+            }
+        }
+
+        if let Some(events) = &mut self.events {
+            retimer.events(events, options);
+        }
+        if let Some(events) = self.osb.as_mut().and_then(|osb| osb.events.as_mut()) {
+            retimer.events(events, options);
+        }
+
+        self.mark_existing_dirty(&[
+            SectionName::General,
+            SectionName::Editor,
+            SectionName::Events,
+            SectionName::TimingPoints,
+            SectionName::HitObjects,
+        ]);
+    }
+}
@@ -0,0 +1,77 @@
+//! Module for keeping the durations of sliders the same after editing the slider velocity.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::analysis::objects::field;
+use crate::osu_file::{
+    hitobjects::HitObjectParams, timingpoints::slider_velocity_at, OsuFile, SectionName,
+};
+
+impl OsuFile {
+    fn slider_velocities(&self) -> Vec<Option<(u32, Decimal)>> {
+        let timing_points = match &self.timing_points {
+            Some(timing_points) => &timing_points.0[..],
+            None => &[],
+        };
+        let slider_multiplier = match &self.difficulty {
+            Some(difficulty) => field(&difficulty.slider_multiplier, dec!(1.4)),
+            None => dec!(1.4),
+        };
+
+        self.hitobjects
+            .iter()
+            .flat_map(|h| &h.0)
+            .map(|obj| match obj.obj_params {
+                HitObjectParams::Slider(_) => Some((
+                    obj.time,
+                    slider_velocity_at(timing_points, slider_multiplier, obj.time),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Changes the lengths of the sliders so they last as long as they did in `before`, which is the file before the slider velocity was edited.
+    /// - Both the timing points and `SliderMultiplier` are taken into account.
+    /// - Sliders are matched with the hitobjects of `before` by their index, and are skipped if the time is different.
+    /// - Returns how many sliders were changed.
+    pub fn preserve_slider_durations(&mut self, before: &OsuFile) -> usize {
+        let old_velocities = before.slider_velocities();
+        let new_velocities = self.slider_velocities();
+
+        let hitobjects = match &mut self.hitobjects {
+            Some(hitobjects) => hitobjects,
+            None => return 0,
+        };
+
+        let mut changed = 0;
+        for ((obj, old), new) in hitobjects
+            .0
+            .iter_mut()
+            .zip(old_velocities)
+            .zip(new_velocities)
+        {
+            let (params, (old_time, old_sv), (_, new_sv)) = match (&mut obj.obj_params, old, new) {
+                (HitObjectParams::Slider(params), Some(old), Some(new)) => (params, old, new),
+                _ => continue,
+            };
+
+            if old_time != obj.time || old_sv == new_sv {
+                continue;
+            }
+
+            let length = params.length;
+            params.rescale_length_for_new_sv(old_sv, new_sv);
+            if params.length != length {
+                changed += 1;
+            }
+        }
+
+        if changed > 0 {
+            self.mark_dirty(SectionName::HitObjects);
+        }
+
+        changed
+    }
+}
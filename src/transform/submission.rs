@@ -0,0 +1,199 @@
+//! Module for preparing a beatmap to be uploaded.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use thiserror::Error;
+
+use super::RemovedField;
+use crate::{
+    lint::{LintIssue, Severity},
+    osu_file::{events::Event, FieldMigration, OsuFile, SectionName, FIELD_MIGRATIONS},
+};
+
+/// Changes made by [`OsuFile::prepare_for_submission`].
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct SubmissionReport {
+    /// Deprecated fields that were removed.
+    pub removed: Vec<RemovedField>,
+    /// Fields in the place older versions used that were moved to their place in the version, such as `EditorBookmarks` to `Bookmarks` in `[Editor]`.
+    pub moved: Vec<FieldMigration>,
+    /// Keys of the `[Difficulty]` fields that were out of range and got clamped.
+    pub clamped: Vec<&'static str>,
+    /// How many extra background events were removed.
+    pub removed_backgrounds: usize,
+    /// Whether the hitobjects weren't in order and got sorted.
+    pub sorted_hitobjects: bool,
+    /// Whether the timing points weren't in order and got sorted.
+    pub sorted_timing_points: bool,
+}
+
+impl SubmissionReport {
+    /// Returns `true` if nothing had to be changed other than the decimal formatting.
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.clamped.is_empty()
+            && self.removed_backgrounds == 0
+            && !self.sorted_hitobjects
+            && !self.sorted_timing_points
+    }
+
+    fn take<T>(&mut self, field: &mut Option<T>, section: SectionName, key: &'static str) {
+        if field.take().is_some() {
+            self.removed.push(RemovedField { section, key });
+        }
+    }
+
+    /// Clamps the field and removes trailing zeros from the decimal.
+    fn clamp<T>(&mut self, field: &mut Option<T>, key: &'static str, min: Decimal, max: Decimal)
+    where
+        T: Clone + Into<Decimal> + From<Decimal>,
+    {
+        if let Some(value) = field.clone().map(Into::into) {
+            let clamped = value.clamp(min, max);
+
+            if clamped != value {
+                self.clamped.push(key);
+            }
+
+            *field = Some(clamped.normalize().into());
+        }
+    }
+}
+
+/// Error used when [`OsuFile::prepare_for_submission`] refuses to prepare the beatmap.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum SubmissionError {
+    /// The beatmap has lint issues with the [`Error`][Severity::Error] severity.
+    #[error("The beatmap has {} lint errors", .0.len())]
+    LintErrors(Vec<LintIssue>),
+}
+
+impl OsuFile {
+    /// Normalizes the beatmap the way the ranking process expects.
+    /// - Refuses if [`lint_file_references`][Self::lint_file_references] finds errors, without changing the beatmap.
+    /// - Removes the deprecated `AudioHash`, `StoryFireInFront` and `AlwaysShowPlayfield` fields.
+    /// - Moves `EditorBookmarks` and `EditorDistanceSpacing` in `[General]` to `Bookmarks` and `DistanceSpacing` in `[Editor]` in versions that write them there, and removes them if `[Editor]` already has them, see [`FIELD_MIGRATIONS`].
+    /// - Sorts the hitobjects and timing points by time, keeping the order of the ones with the same time.
+    /// - Keeps only the first background event.
+    /// - Clamps `HPDrainRate`, `CircleSize`, `OverallDifficulty` and `ApproachRate` to `0 ~ 10`, `SliderMultiplier` to `0.4 ~ 3.6` and `SliderTickRate` to `0.5 ~ 8`.
+    /// - Writes the decimals in `[Difficulty]` and `StackLeniency` without trailing zeros.
+    pub fn prepare_for_submission(&mut self) -> Result<SubmissionReport, SubmissionError> {
+        let lint_errors = self
+            .lint_file_references(None)
+            .into_iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .collect::<Vec<_>>();
+
+        if !lint_errors.is_empty() {
+            return Err(SubmissionError::LintErrors(lint_errors));
+        }
+
+        let mut report = SubmissionReport::default();
+
+        if let Some(general) = &mut self.general {
+            let section = SectionName::General;
+
+            report.take(&mut general.audio_hash, section, "AudioHash");
+            report.take(
+                &mut general.story_fire_in_front,
+                section,
+                "StoryFireInFront",
+            );
+            report.take(
+                &mut general.always_show_playfield,
+                section,
+                "AlwaysShowPlayfield",
+            );
+
+            if let Some(stack_leniency) = general.stack_leniency.clone() {
+                let stack_leniency: Decimal = stack_leniency.into();
+                general.stack_leniency = Some(stack_leniency.normalize().into());
+            }
+        }
+
+        for migration in FIELD_MIGRATIONS {
+            if self.version < migration.version || !self.has_field(migration.old) {
+                continue;
+            }
+
+            if self.has_field(migration.new) {
+                report.removed.push(RemovedField {
+                    section: migration.old.section,
+                    key: migration.old.name,
+                });
+            } else {
+                report.moved.push(*migration);
+            }
+            self.move_field(migration.old, migration.new);
+        }
+
+        if let Some(difficulty) = &mut self.difficulty {
+            let (min, max) = (Decimal::ZERO, dec!(10));
+
+            report.clamp(&mut difficulty.hp_drain_rate, "HPDrainRate", min, max);
+            report.clamp(&mut difficulty.circle_size, "CircleSize", min, max);
+            report.clamp(
+                &mut difficulty.overall_difficulty,
+                "OverallDifficulty",
+                min,
+                max,
+            );
+            report.clamp(&mut difficulty.approach_rate, "ApproachRate", min, max);
+            report.clamp(
+                &mut difficulty.slider_multiplier,
+                "SliderMultiplier",
+                dec!(0.4),
+                dec!(3.6),
+            );
+            report.clamp(
+                &mut difficulty.slider_tickrate,
+                "SliderTickRate",
+                dec!(0.5),
+                dec!(8),
+            );
+        }
+
+        if let Some(events) = &mut self.events {
+            let mut found_background = false;
+            let count = events.0.len();
+
+            events.0.retain(|event| match event {
+                Event::Background(_) if found_background => false,
+                Event::Background(_) => {
+                    found_background = true;
+                    true
+                }
+                _ => true,
+            });
+
+            report.removed_backgrounds = count - events.0.len();
+        }
+
+        if let Some(hitobjects) = &mut self.hitobjects {
+            if !hitobjects.0.windows(2).all(|w| w[0].time <= w[1].time) {
+                hitobjects.0.sort_by_key(|h| h.time);
+                report.sorted_hitobjects = true;
+            }
+        }
+
+        if let Some(timing_points) = &mut self.timing_points {
+            if !timing_points.0.windows(2).all(|w| w[0].time <= w[1].time) {
+                timing_points.0.sort_by_key(|t| t.time);
+                report.sorted_timing_points = true;
+            }
+        }
+
+        self.mark_existing_dirty(&[
+            SectionName::General,
+            SectionName::Editor,
+            SectionName::Difficulty,
+            SectionName::Events,
+            SectionName::TimingPoints,
+            SectionName::HitObjects,
+        ]);
+
+        Ok(report)
+    }
+}